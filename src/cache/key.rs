@@ -6,9 +6,13 @@
 use crate::synthesis::envelope::Envelope;
 use crate::synthesis::filter::Filter;
 use crate::synthesis::filter_envelope::FilterEnvelope;
+use crate::synthesis::fm_operators::FMPatch;
 use crate::synthesis::fm_synthesis::FMParams;
+use crate::synthesis::modal::{Exciter, ModalResonator};
+use crate::synthesis::physical_model::PhysicalModel;
+use crate::synthesis::unison::Unison;
 use crate::synthesis::waveform::Waveform;
-use crate::track::NoteEvent; // Re-exported from track module
+use crate::track::{NoteEvent, VoiceState}; // Re-exported from track module
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
@@ -30,14 +34,15 @@ impl CacheKey {
     /// This creates a unique identifier based on:
     /// - Waveform type
     /// - Envelope (ADSR)
-    /// - FM synthesis parameters
+    /// - FM synthesis parameters and multi-operator FM patch
     /// - Filter settings
-    /// - Duration (affects envelope shape)
+    /// - Length in seconds (affects envelope shape)
     /// - Pitch bend
     ///
     /// Note: Frequency is NOT part of the cache key - we cache at a reference
     /// pitch and transpose during playback for better cache efficiency.
-    pub fn from_note_event(note: &NoteEvent, sample_rate: f32) -> Self {
+    /// `length` is how long the note is held, in seconds at its track's tempo.
+    pub fn from_note_event(note: &NoteEvent, length: f32, sample_rate: f32) -> Self {
         let mut hasher = DefaultHasher::new();
 
         // Waveform
@@ -49,11 +54,26 @@ impl CacheKey {
         // FM parameters
         hash_fm_params(&note.fm_params, &mut hasher);
 
+        // Multi-operator FM patch
+        if let Some(ref patch) = note.fm_patch {
+            hash_fm_patch(patch, &mut hasher);
+        }
+
+        // Physical model
+        if let Some(ref model) = note.physical_model {
+            hash_physical_model(model, &mut hasher);
+        }
+
+        // Modal resonator
+        if let Some(ref resonator) = note.resonator {
+            hash_resonator(resonator, &mut hasher);
+        }
+
         // Filter envelope
         hash_filter_envelope(&note.filter_envelope, &mut hasher);
 
-        // Duration (affects envelope shape)
-        hash_f32(note.duration, &mut hasher);
+        // Length (affects envelope shape)
+        hash_f32(length, &mut hasher);
 
         // Pitch bend
         hash_f32(note.pitch_bend_semitones, &mut hasher);
@@ -61,15 +81,22 @@ impl CacheKey {
         // Sample rate (affects filter frequencies)
         hash_f32(sample_rate, &mut hasher);
 
-        // Velocity (affects amplitude)
+        // Velocity (affects amplitude, and how hard a model or resonator is played)
         hash_f32(note.velocity, &mut hasher);
 
-        // Custom wavetable (if present, use pointer address as ID)
+        // Unison voices (detune and phase scatter change the waveform)
+        hash_unison(&note.unison, &mut hasher);
+
+        // Glide, legato offset and voice cut from the voice allocator
+        hash_voice(&note.voice, &mut hasher);
+
+        // Custom wavetable (if present, identified by its frames)
         if let Some(ref wavetable) = note.custom_wavetable {
-            // Use the wavetable reference address as a unique ID
-            // This assumes wavetables are shared/immutable
-            let ptr = wavetable as *const _ as usize;
-            ptr.hash(&mut hasher);
+            // Clones share their frames, which are never modified, so every note
+            // cloned from one table gets the same key and different tables never collide
+            (wavetable.frames_ptr() as usize).hash(&mut hasher);
+            wavetable.frame_count().hash(&mut hasher);
+            wavetable.position().to_bits().hash(&mut hasher);
         }
 
         CacheKey(hasher.finish())
@@ -80,11 +107,16 @@ impl CacheKey {
     /// Use this when you want to cache the filtered output.
     /// Note: Most users should cache the raw oscillator output and apply
     /// filters at runtime for more flexibility.
-    pub fn from_note_with_filter(note: &NoteEvent, filter: &Filter, sample_rate: f32) -> Self {
+    pub fn from_note_with_filter(
+        note: &NoteEvent,
+        length: f32,
+        filter: &Filter,
+        sample_rate: f32,
+    ) -> Self {
         let mut hasher = DefaultHasher::new();
 
         // Start with the base note hash
-        let base_key = Self::from_note_event(note, sample_rate);
+        let base_key = Self::from_note_event(note, length, sample_rate);
         base_key.0.hash(&mut hasher);
 
         // Add filter parameters
@@ -107,8 +139,56 @@ fn hash_f32(value: f32, hasher: &mut DefaultHasher) {
 }
 
 fn hash_waveform(waveform: &Waveform, hasher: &mut DefaultHasher) {
-    // Waveform is an enum, can use discriminant
     std::mem::discriminant(waveform).hash(hasher);
+    match *waveform {
+        Waveform::Pulse {
+            width,
+            pwm_rate,
+            pwm_depth,
+        } => {
+            hash_f32(width, hasher);
+            hash_f32(pwm_rate, hasher);
+            hash_f32(pwm_depth, hasher);
+        }
+        Waveform::Sync { ratio } => hash_f32(ratio, hasher),
+        Waveform::Ring { ratio, modulator } => {
+            hash_f32(ratio, hasher);
+            modulator.hash(hasher);
+        }
+        Waveform::CrossMod {
+            ratio,
+            amount,
+            modulator,
+        } => {
+            hash_f32(ratio, hasher);
+            hash_f32(amount, hasher);
+            modulator.hash(hasher);
+        }
+        Waveform::Supersaw { detune, mix } => {
+            hash_f32(detune, hasher);
+            hash_f32(mix, hasher);
+        }
+        Waveform::Noise { seed } => seed.hash(hasher),
+        Waveform::Sine | Waveform::Square | Waveform::Sawtooth | Waveform::Triangle => {}
+    }
+}
+
+fn hash_unison(unison: &Unison, hasher: &mut DefaultHasher) {
+    unison.voices.hash(hasher);
+    if unison.is_active() {
+        hash_f32(unison.detune_cents, hasher);
+        hash_f32(unison.spread, hasher);
+        hash_f32(unison.phase_random, hasher);
+    }
+}
+
+fn hash_voice(voice: &VoiceState, hasher: &mut DefaultHasher) {
+    hash_f32(voice.glide_from, hasher);
+    hash_f32(voice.glide_time, hasher);
+    hash_f32(voice.envelope_offset, hasher);
+    hash_f32(voice.phase, hasher);
+    voice.cut_at.map(f32::to_bits).hash(hasher);
+    hash_f32(voice.cut_fade, hasher);
 }
 
 fn hash_envelope(envelope: &Envelope, hasher: &mut DefaultHasher) {
@@ -129,6 +209,59 @@ fn hash_fm_params(fm: &FMParams, hasher: &mut DefaultHasher) {
     hash_f32(fm.index_env_amount, hasher);
 }
 
+fn hash_physical_model(model: &PhysicalModel, hasher: &mut DefaultHasher) {
+    model.kind.hash(hasher);
+    hash_f32(model.excitation_position, hasher);
+    hash_f32(model.damping, hasher);
+    hash_f32(model.stiffness, hasher);
+    hash_f32(model.pressure, hasher);
+    hash_f32(model.body.mix, hasher);
+    for mode in &model.body.modes {
+        hash_f32(mode.frequency, hasher);
+        hash_f32(mode.q, hasher);
+        hash_f32(mode.gain, hasher);
+    }
+}
+
+fn hash_resonator(resonator: &ModalResonator, hasher: &mut DefaultHasher) {
+    for mode in &resonator.modes {
+        hash_f32(mode.ratio, hasher);
+        hash_f32(mode.decay, hasher);
+        hash_f32(mode.gain, hasher);
+    }
+    match &resonator.exciter {
+        Exciter::Impulse => 0u8.hash(hasher),
+        Exciter::NoiseBurst { duration } => {
+            1u8.hash(hasher);
+            hash_f32(*duration, hasher);
+        }
+        Exciter::Mallet { hardness } => {
+            2u8.hash(hasher);
+            hash_f32(*hardness, hasher);
+        }
+        Exciter::Sample(sample) => {
+            // Samples are shared, not copied, so their data identifies them
+            3u8.hash(hasher);
+            std::sync::Arc::as_ptr(&sample.data).hash(hasher);
+            sample.data.len().hash(hasher);
+            sample.sample_rate.hash(hasher);
+        }
+    }
+}
+
+fn hash_fm_patch(patch: &FMPatch, hasher: &mut DefaultHasher) {
+    patch.algorithm.hash(hasher);
+    for op in &patch.operators {
+        hash_f32(op.ratio, hasher);
+        op.fixed_frequency.map(f32::to_bits).hash(hasher);
+        hash_f32(op.detune, hasher);
+        hash_f32(op.level, hasher);
+        hash_envelope(&op.envelope, hasher);
+        hash_f32(op.feedback, hasher);
+        hash_f32(op.velocity_sensitivity, hasher);
+    }
+}
+
 fn hash_filter_envelope(filter_env: &FilterEnvelope, hasher: &mut DefaultHasher) {
     hash_f32(filter_env.attack, hasher);
     hash_f32(filter_env.decay, hasher);
@@ -149,6 +282,7 @@ fn hash_filter(filter: &Filter, hasher: &mut DefaultHasher) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::composition::timing::Ticks;
     use crate::synthesis::envelope::EnvelopeCurve;
 
     #[test]
@@ -156,22 +290,29 @@ mod tests {
         let note1 = NoteEvent {
             frequencies: [440.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
             num_freqs: 1,
-            start_time: 0.0,
-            duration: 1.0,
+            start_time: Ticks::ZERO,
+            duration: Ticks::from_beats(2.0),
             waveform: Waveform::Sine,
             envelope: Envelope::with_curve(0.01, 0.1, 0.7, 0.2, EnvelopeCurve::Linear),
             filter_envelope: FilterEnvelope::default(),
             fm_params: FMParams::default(),
+            fm_patch: None,
+            physical_model: None,
+            resonator: None,
             pitch_bend_semitones: 0.0,
             custom_wavetable: None,
             velocity: 1.0,
             spatial_position: None,
+            unison: Unison::default(),
+            voice: VoiceState::default(),
+            renderings: Default::default(),
+            placed_seconds: None,
         };
 
         let note2 = note1.clone();
 
-        let key1 = CacheKey::from_note_event(&note1, 44100.0);
-        let key2 = CacheKey::from_note_event(&note2, 44100.0);
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
 
         assert_eq!(key1, key2);
     }
@@ -181,23 +322,30 @@ mod tests {
         let note1 = NoteEvent {
             frequencies: [440.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
             num_freqs: 1,
-            start_time: 0.0,
-            duration: 1.0,
+            start_time: Ticks::ZERO,
+            duration: Ticks::from_beats(2.0),
             waveform: Waveform::Sine,
             envelope: Envelope::with_curve(0.01, 0.1, 0.7, 0.2, EnvelopeCurve::Linear),
             filter_envelope: FilterEnvelope::default(),
             fm_params: FMParams::default(),
+            fm_patch: None,
+            physical_model: None,
+            resonator: None,
             pitch_bend_semitones: 0.0,
             custom_wavetable: None,
             velocity: 1.0,
             spatial_position: None,
+            unison: Unison::default(),
+            voice: VoiceState::default(),
+            renderings: Default::default(),
+            placed_seconds: None,
         };
 
         let mut note2 = note1.clone();
         note2.envelope = Envelope::with_curve(0.05, 0.1, 0.7, 0.2, EnvelopeCurve::Linear); // Different attack
 
-        let key1 = CacheKey::from_note_event(&note1, 44100.0);
-        let key2 = CacheKey::from_note_event(&note2, 44100.0);
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
 
         assert_ne!(key1, key2);
     }
@@ -207,26 +355,137 @@ mod tests {
         let note1 = NoteEvent {
             frequencies: [440.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
             num_freqs: 1,
-            start_time: 0.0,
-            duration: 1.0,
+            start_time: Ticks::ZERO,
+            duration: Ticks::from_beats(2.0),
             waveform: Waveform::Sine,
             envelope: Envelope::default(),
             filter_envelope: FilterEnvelope::default(),
             fm_params: FMParams::default(),
+            fm_patch: None,
+            physical_model: None,
+            resonator: None,
             pitch_bend_semitones: 0.0,
             custom_wavetable: None,
             velocity: 1.0,
             spatial_position: None,
+            unison: Unison::default(),
+            voice: VoiceState::default(),
+            renderings: Default::default(),
+            placed_seconds: None,
         };
 
         let mut note2 = note1.clone();
         note2.frequencies[0] = 880.0; // Different frequency (octave higher)
 
-        let key1 = CacheKey::from_note_event(&note1, 44100.0);
-        let key2 = CacheKey::from_note_event(&note2, 44100.0);
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
 
         // Keys should be THE SAME - we don't cache per-frequency
         // We cache the waveform shape and transpose during playback
         assert_eq!(key1, key2);
     }
+
+    #[test]
+    fn test_oscillator_parameters_in_key() {
+        let two_beats = Ticks::from_beats(2.0);
+        let note1 = NoteEvent::with_waveform(&[440.0], Ticks::ZERO, two_beats, Waveform::pulse(0.3));
+        let mut note2 = note1.clone();
+        note2.waveform = Waveform::pulse(0.4);
+
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
+
+        assert_ne!(key1, key2);
+    }
+
+    #[test]
+    fn test_unison_in_key() {
+        let two_beats = Ticks::from_beats(2.0);
+        let note1 = NoteEvent::with_waveform(&[440.0], Ticks::ZERO, two_beats, Waveform::Sawtooth);
+        let note2 = note1.clone().with_unison(Unison::new(5, 20.0));
+        let note3 = note1.clone().with_unison(Unison::new(5, 30.0));
+
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
+        let key3 = CacheKey::from_note_event(&note3, 1.0, 44100.0);
+
+        assert_ne!(key1, key2);
+        assert_ne!(key2, key3);
+    }
+
+    #[test]
+    fn test_fm_patch_in_key() {
+        let note1 = NoteEvent::new(&[440.0], Ticks::ZERO, Ticks::from_beats(2.0));
+        let note2 = note1.clone().with_fm_patch(FMPatch::electric_piano());
+        let mut patch = FMPatch::electric_piano();
+        patch.operators[5].feedback = 0.8;
+        let note3 = note1.clone().with_fm_patch(patch);
+
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
+        let key3 = CacheKey::from_note_event(&note3, 1.0, 44100.0);
+
+        assert_ne!(key1, key2);
+        assert_ne!(key2, key3);
+        assert_eq!(key2, CacheKey::from_note_event(&note2.clone(), 1.0, 44100.0));
+    }
+
+    #[test]
+    fn test_physical_model_in_key() {
+        let note1 = NoteEvent::new(&[440.0], Ticks::ZERO, Ticks::from_beats(2.0));
+        let note2 = note1.clone().with_physical_model(PhysicalModel::bowed_string());
+        let note3 = note1
+            .clone()
+            .with_physical_model(PhysicalModel::bowed_string().with_damping(0.9));
+        let mut note4 = note2.clone();
+        note4.velocity = 0.3;
+
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
+        let key3 = CacheKey::from_note_event(&note3, 1.0, 44100.0);
+        let key4 = CacheKey::from_note_event(&note4, 1.0, 44100.0);
+
+        assert_ne!(key1, key2);
+        assert_ne!(key2, key3);
+        assert_ne!(key2, key4);
+    }
+
+    #[test]
+    fn test_resonator_in_key() {
+        let note1 = NoteEvent::new(&[440.0], Ticks::ZERO, Ticks::from_beats(2.0));
+        let note2 = note1.clone().with_resonator(ModalResonator::bell());
+        let note3 = note1.clone().with_resonator(
+            ModalResonator::bell().with_exciter(Exciter::Mallet { hardness: 0.1 }),
+        );
+
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
+        let key3 = CacheKey::from_note_event(&note3, 1.0, 44100.0);
+
+        assert_ne!(key1, key2);
+        assert_ne!(key2, key3);
+        assert_eq!(key2, CacheKey::from_note_event(&note2.clone(), 1.0, 44100.0));
+    }
+
+    #[test]
+    fn test_custom_wavetable_in_key() {
+        use crate::synthesis::wavetable::Wavetable;
+
+        let table = Wavetable::from_fn(256, |phase| (phase * 2.0 * std::f32::consts::PI).sin());
+        let mut note1 = NoteEvent::new(&[440.0], Ticks::ZERO, Ticks::from_beats(2.0));
+        note1.custom_wavetable = Some(Box::new(table.clone()));
+        let mut note2 = note1.clone();
+        note2.custom_wavetable = Some(Box::new(table.clone().with_position(0.5)));
+        let mut note3 = note1.clone();
+        note3.custom_wavetable = Some(Box::new(Wavetable::pwm(0.3)));
+
+        let key1 = CacheKey::from_note_event(&note1, 1.0, 44100.0);
+        let key2 = CacheKey::from_note_event(&note2, 1.0, 44100.0);
+        let key3 = CacheKey::from_note_event(&note3, 1.0, 44100.0);
+
+        // Clones of one table share a key however their copies are stored
+        assert_eq!(key1, CacheKey::from_note_event(&note1.clone(), 1.0, 44100.0));
+        assert_ne!(key1, key2);
+        assert_ne!(key1, key3);
+    }
 }
@@ -139,6 +139,7 @@ mod tests {
     use super::*;
     use crate::composition::Composition;
     use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
     use crate::consts::notes::*;
     use crate::instruments::Instrument;
     use crate::track::AudioEvent;
@@ -154,7 +155,7 @@ mod tests {
 
         if let AudioEvent::Note(note) = &track.events[0] {
             assert_eq!(note.num_freqs, 3); // Major triad has 3 notes
-            assert_eq!(note.duration, 1.0);
+            assert_eq!(note.duration, at(1.0));
         } else {
             panic!("Expected NoteEvent");
         }
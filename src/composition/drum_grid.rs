@@ -1,10 +1,11 @@
+use crate::composition::timing::Ticks;
 use crate::instruments::drums::DrumType;
 use crate::track::Track;
 
 /// A step sequencer-style drum grid for easy drum pattern programming
 pub struct DrumGrid<'a> {
     track: &'a mut Track,
-    start_time: f32,
+    start_time: Ticks,
     steps: usize,
     step_duration: f32,
 }
@@ -14,10 +15,10 @@ impl<'a> DrumGrid<'a> {
     ///
     /// # Arguments
     /// * `track` - The track to add drum events to
-    /// * `start_time` - When the grid starts (in seconds)
+    /// * `start_time` - When the grid starts
     /// * `steps` - Number of steps in the grid (e.g., 16 for a bar of 16th notes)
     /// * `step_duration` - Duration of each step (e.g., 0.125 for 16th notes at 120bpm)
-    pub fn new(track: &'a mut Track, start_time: f32, steps: usize, step_duration: f32) -> Self {
+    pub fn new(track: &'a mut Track, start_time: Ticks, steps: usize, step_duration: f32) -> Self {
         Self {
             track,
             start_time,
@@ -41,13 +42,20 @@ impl<'a> DrumGrid<'a> {
     pub fn hit(self, drum_type: DrumType, steps: &[usize]) -> Self {
         for &step in steps {
             if step < self.steps {
-                let time = self.start_time + (step as f32 * self.step_duration);
-                self.track.add_drum(drum_type, time, None);
+                let time = self.step_time(step);
+                self.track.add_drum_ticks(drum_type, time, None);
             }
         }
         self
     }
 
+    /// Position of a step, following the track's tempo map
+    fn step_time(&self, step: usize) -> Ticks {
+        let map = self.track.tempo_map();
+        let start = map.seconds_at(self.start_time);
+        map.ticks_at(start + (step as f32 * self.step_duration) as f64)
+    }
+
     /// Add kick drum hits at specific steps
     pub fn kick(self, steps: &[usize]) -> Self {
         self.hit(DrumType::Kick, steps)
@@ -174,8 +182,7 @@ impl<'a> DrumGrid<'a> {
             return self;
         }
 
-        let grid_duration = self.duration();
-        let grid_end_time = self.start_time + grid_duration;
+        let grid_end_time = self.step_time(self.steps);
 
         // Collect all drum events in this grid's time range
         let pattern_events: Vec<_> = self
@@ -195,11 +202,10 @@ impl<'a> DrumGrid<'a> {
             .collect();
 
         // Repeat the pattern
-        for i in 0..times {
-            let offset = grid_duration * (i + 1) as f32;
+        for i in 1..=times {
+            let start = self.step_time(self.steps * i);
             for &(drum_type, relative_time) in &pattern_events {
-                self.track
-                    .add_drum(drum_type, self.start_time + relative_time + offset, None);
+                self.track.add_drum_ticks(drum_type, start + relative_time, None);
             }
         }
 
@@ -211,13 +217,14 @@ impl<'a> DrumGrid<'a> {
 mod tests {
     use super::*;
     use crate::track::AudioEvent;
+    use crate::composition::timing::ticks::at;
 
     #[test]
     fn test_drum_grid_creation() {
         let mut track = Track::new();
-        let grid = DrumGrid::new(&mut track, 0.0, 16, 0.125);
+        let grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125);
 
-        assert_eq!(grid.start_time, 0.0);
+        assert_eq!(grid.start_time, Ticks::ZERO);
         assert_eq!(grid.steps, 16);
         assert_eq!(grid.step_duration, 0.125);
     }
@@ -225,7 +232,7 @@ mod tests {
     #[test]
     fn test_drum_grid_duration() {
         let mut track = Track::new();
-        let grid = DrumGrid::new(&mut track, 0.0, 16, 0.125);
+        let grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125);
 
         assert_eq!(grid.duration(), 2.0); // 16 steps * 0.125 = 2.0 seconds
     }
@@ -233,36 +240,36 @@ mod tests {
     #[test]
     fn test_drum_grid_hit_basic() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 16, 0.125).hit(DrumType::Kick, &[0, 4, 8, 12]);
+        let _grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125).hit(DrumType::Kick, &[0, 4, 8, 12]);
 
         assert_eq!(track.events.len(), 4);
 
         // Verify first hit
         if let AudioEvent::Drum(drum) = &track.events[0] {
             assert!(matches!(drum.drum_type, DrumType::Kick));
-            assert_eq!(drum.start_time, 0.0);
+            assert_eq!(drum.start_time, at(0.0));
         }
 
         // Verify last hit
         if let AudioEvent::Drum(drum) = &track.events[3] {
             assert!(matches!(drum.drum_type, DrumType::Kick));
-            assert_eq!(drum.start_time, 1.5); // step 12 * 0.125
+            assert_eq!(drum.start_time, at(1.5)); // step 12 * 0.125
         }
     }
 
     #[test]
     fn test_drum_grid_hit_with_offset() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 2.0, 8, 0.25).hit(DrumType::Snare, &[2, 6]);
+        let _grid = DrumGrid::new(&mut track, at(2.0), 8, 0.25).hit(DrumType::Snare, &[2, 6]);
 
         assert_eq!(track.events.len(), 2);
 
         if let AudioEvent::Drum(drum) = &track.events[0] {
-            assert_eq!(drum.start_time, 2.5); // 2.0 start + (2 * 0.25)
+            assert_eq!(drum.start_time, at(2.5)); // 2.0 start + (2 * 0.25)
         }
 
         if let AudioEvent::Drum(drum) = &track.events[1] {
-            assert_eq!(drum.start_time, 3.5); // 2.0 start + (6 * 0.25)
+            assert_eq!(drum.start_time, at(3.5)); // 2.0 start + (6 * 0.25)
         }
     }
 
@@ -270,7 +277,7 @@ mod tests {
     fn test_drum_grid_out_of_bounds_steps() {
         let mut track = Track::new();
         let _grid =
-            DrumGrid::new(&mut track, 0.0, 16, 0.125).hit(DrumType::Kick, &[0, 8, 16, 20, 100]); // 16, 20, 100 are out of bounds
+            DrumGrid::new(&mut track, at(0.0), 16, 0.125).hit(DrumType::Kick, &[0, 8, 16, 20, 100]); // 16, 20, 100 are out of bounds
 
         // Should only add hits for valid steps (0, 8)
         assert_eq!(track.events.len(), 2);
@@ -279,7 +286,7 @@ mod tests {
     #[test]
     fn test_drum_grid_kick_convenience_method() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 16, 0.125).kick(&[0, 4, 8, 12]);
+        let _grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125).kick(&[0, 4, 8, 12]);
 
         assert_eq!(track.events.len(), 4);
 
@@ -293,7 +300,7 @@ mod tests {
     #[test]
     fn test_drum_grid_snare_convenience_method() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 16, 0.125).snare(&[4, 12]);
+        let _grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125).snare(&[4, 12]);
 
         assert_eq!(track.events.len(), 2);
 
@@ -307,7 +314,7 @@ mod tests {
     #[test]
     fn test_drum_grid_hihat_convenience_method() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 16, 0.125).hihat(&[0, 2, 4, 6, 8, 10, 12, 14]);
+        let _grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125).hihat(&[0, 2, 4, 6, 8, 10, 12, 14]);
 
         assert_eq!(track.events.len(), 8);
 
@@ -321,7 +328,7 @@ mod tests {
     #[test]
     fn test_drum_grid_chaining() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 16, 0.125)
+        let _grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125)
             .kick(&[0, 4, 8, 12])
             .snare(&[4, 12])
             .hihat(&[0, 2, 4, 6, 8, 10, 12, 14]);
@@ -333,7 +340,7 @@ mod tests {
     #[test]
     fn test_drum_grid_empty_pattern() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 16, 0.125).kick(&[]);
+        let _grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125).kick(&[]);
 
         assert_eq!(track.events.len(), 0);
     }
@@ -341,7 +348,7 @@ mod tests {
     #[test]
     fn test_drum_grid_repeat() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 4, 0.5) // 2 second pattern
+        let _grid = DrumGrid::new(&mut track, at(0.0), 4, 0.5) // 2 second pattern
             .kick(&[0, 2])
             .repeat(2); // Repeat 2 more times
 
@@ -350,20 +357,20 @@ mod tests {
 
         // Verify timing of repeats
         if let AudioEvent::Drum(drum) = &track.events[0] {
-            assert_eq!(drum.start_time, 0.0); // Original
+            assert_eq!(drum.start_time, at(0.0)); // Original
         }
         if let AudioEvent::Drum(drum) = &track.events[2] {
-            assert_eq!(drum.start_time, 2.0); // First repeat
+            assert_eq!(drum.start_time, at(2.0)); // First repeat
         }
         if let AudioEvent::Drum(drum) = &track.events[4] {
-            assert_eq!(drum.start_time, 4.0); // Second repeat
+            assert_eq!(drum.start_time, at(4.0)); // Second repeat
         }
     }
 
     #[test]
     fn test_drum_grid_repeat_zero_times() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 4, 0.5)
+        let _grid = DrumGrid::new(&mut track, at(0.0), 4, 0.5)
             .kick(&[0, 2])
             .repeat(0);
 
@@ -374,7 +381,7 @@ mod tests {
     #[test]
     fn test_drum_grid_repeat_with_chained_patterns() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 8, 0.25)
+        let _grid = DrumGrid::new(&mut track, at(0.0), 8, 0.25)
             .kick(&[0, 4])
             .snare(&[2, 6])
             .repeat(1);
@@ -387,7 +394,7 @@ mod tests {
     fn test_drum_grid_all_drum_types() {
         // Test that all convenience methods work
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 20, 0.1)
+        let _grid = DrumGrid::new(&mut track, at(0.0), 20, 0.1)
             .kick(&[0])
             .kick_808(&[1])
             .sub_kick(&[2])
@@ -415,7 +422,7 @@ mod tests {
     #[test]
     fn test_drum_grid_duplicate_steps() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 16, 0.125).kick(&[0, 4, 4, 8]); // Step 4 is duplicated
+        let _grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125).kick(&[0, 4, 4, 8]); // Step 4 is duplicated
 
         // Should add all 4 events (including duplicate)
         assert_eq!(track.events.len(), 4);
@@ -424,7 +431,7 @@ mod tests {
     #[test]
     fn test_drum_grid_overlapping_drums() {
         let mut track = Track::new();
-        let _grid = DrumGrid::new(&mut track, 0.0, 16, 0.125)
+        let _grid = DrumGrid::new(&mut track, at(0.0), 16, 0.125)
             .kick(&[0])
             .snare(&[0]); // Same step as kick
 
@@ -442,14 +449,14 @@ mod tests {
     fn test_drum_grid_fine_step_resolution() {
         let mut track = Track::new();
         // 32nd note grid
-        let _grid = DrumGrid::new(&mut track, 0.0, 32, 0.0625).hihat(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let _grid = DrumGrid::new(&mut track, at(0.0), 32, 0.0625).hihat(&[0, 1, 2, 3, 4, 5, 6, 7]);
 
         assert_eq!(track.events.len(), 8);
 
         // Verify tight spacing
         if let AudioEvent::Drum(drum1) = &track.events[0] {
             if let AudioEvent::Drum(drum2) = &track.events[1] {
-                assert_eq!(drum2.start_time - drum1.start_time, 0.0625);
+                assert_eq!(drum2.start_time - drum1.start_time, at(0.0625));
             }
         }
     }
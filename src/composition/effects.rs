@@ -1,10 +1,11 @@
 use super::TrackBuilder;
 use crate::synthesis::effects::{
-    AutoPan, BitCrusher, Chorus, Compressor, ConvolutionReverb, Delay, Distortion, EQ, Flanger,
-    Gate, Limiter, Phaser, Reverb, RingModulator, Saturation, Tremolo,
+    AutoPan, BitCrusher, Chorus, Compressor, ConvolutionReverb, Delay, Distortion, EQ, Effect,
+    Flanger, Gate, Limiter, Phaser, Reverb, RingModulator, Saturation, Tremolo,
 };
 use crate::synthesis::filter::Filter;
 use crate::synthesis::lfo::ModRoute;
+use crate::synthesis::mod_matrix::MatrixRoute;
 
 /// Builder for audio effects (accessed via `.effects()`)
 ///
@@ -137,11 +138,23 @@ impl<'a> EffectsBuilder<'a> {
         self
     }
 
+    /// Append any effect (built-in or user-defined) to the insert list
+    pub fn effect<E: Effect + 'static>(mut self, effect: E) -> Self {
+        self.inner = self.inner.effect(effect);
+        self
+    }
+
     /// Add LFO modulation
     pub fn modulate(mut self, mod_route: ModRoute) -> Self {
         self.inner = self.inner.modulate(mod_route);
         self
     }
+
+    /// Add a mod matrix route
+    pub fn matrix_route(mut self, route: MatrixRoute) -> Self {
+        self.inner = self.inner.matrix_route(route);
+        self
+    }
 }
 
 impl<'a> TrackBuilder<'a> {
@@ -537,11 +550,52 @@ impl<'a> TrackBuilder<'a> {
         self
     }
 
+    /// Append any effect to this track's insert list
+    ///
+    /// Inserts are processed in the order they are added, after the built-in effect
+    /// slots. Unlike the named effect methods, this accepts user-defined [`Effect`]s
+    /// and any number of instances of the same effect.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::composition::Composition;
+    /// # use tunes::composition::timing::Tempo;
+    /// # use tunes::synthesis::effects::Delay;
+    /// # use tunes::consts::notes::*;
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("guitar")
+    ///     .effect(Delay::slapback())
+    ///     .effect(Delay::dotted_eighth())
+    ///     .note(&[E3], 1.0);
+    /// ```
+    pub fn effect<E: Effect + 'static>(mut self, effect: E) -> Self {
+        self.get_track_mut().effects.add_effect(effect);
+        self
+    }
+
     /// Add an LFO modulation route to this track
     pub fn modulate(mut self, mod_route: ModRoute) -> Self {
         self.get_track_mut().modulation.push(mod_route);
         self
     }
+
+    /// Add a route to this track's mod matrix
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("pluck")
+    ///     .filter(Filter::low_pass(800.0, 0.3))
+    ///     // Velocity opens the filter up to two octaves, key tracking follows the pitch
+    ///     .matrix_route(MatrixRoute::new(ModSource::Velocity, ModDestination::FilterCutoff, 2.0))
+    ///     .matrix_route(MatrixRoute::new(ModSource::KeyTrack, ModDestination::FilterCutoff, 1.0))
+    ///     .note(&[C4], 0.5);
+    /// ```
+    pub fn matrix_route(mut self, route: MatrixRoute) -> Self {
+        self.get_track_mut().mod_matrix.add_route(route);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -167,7 +167,7 @@ impl<'a> TrackBuilder<'a> {
         self.get_track_mut().volume = target_volume;
 
         // Advance cursor to account for fade duration
-        self.cursor += duration;
+        self.advance(duration);
 
         self
     }
@@ -204,7 +204,7 @@ impl<'a> TrackBuilder<'a> {
         self.get_track_mut().pan = target_pan;
 
         // Advance cursor to account for pan duration
-        self.cursor += duration;
+        self.advance(duration);
 
         self
     }
@@ -239,7 +239,7 @@ impl<'a> TrackBuilder<'a> {
         self.get_track_mut().filter.cutoff = target_cutoff;
 
         // Advance cursor to account for sweep duration
-        self.cursor += duration;
+        self.advance(duration);
 
         self
     }
@@ -251,6 +251,7 @@ mod tests {
     use crate::synthesis::envelope::Envelope;
     use crate::consts::notes::*;
     use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
     use crate::synthesis::waveform::Waveform;
 
     #[test]
@@ -458,7 +459,7 @@ mod tests {
         let track = &mixer.tracks()[0];
         if let crate::track::AudioEvent::Note(note) = &track.events[0] {
             // Note should start at 3.0 (after the fade duration)
-            assert_eq!(note.start_time, 3.0);
+            assert_eq!(note.start_time, at(3.0));
         } else {
             panic!("Expected NoteEvent");
         }
@@ -634,7 +635,7 @@ mod tests {
         assert_eq!(track.volume, 0.0);
         if let crate::track::AudioEvent::Note(note) = &track.events[0] {
             // Note should start at 0.0 (no fade duration)
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         } else {
             panic!("Expected NoteEvent");
         }
@@ -10,7 +10,10 @@ mod generators;
 mod builders;
 
 // Re-export public items
-pub use random_walk::{random_walk_sequence, biased_random_walk_sequence};
+pub use random_walk::{
+    biased_random_walk_sequence, biased_random_walk_sequence_with_rng, random_walk_sequence,
+    random_walk_sequence_with_rng,
+};
 pub use builders::{TransformBuilder, GeneratorBuilder};
 
 // Note: Transform and generator method implementations are in their respective modules
@@ -20,6 +23,7 @@ mod tests {
     use super::*;
     use crate::composition::Composition;
     use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
     use crate::consts::notes::*;
     use crate::consts::scales::C4_MAJOR_SCALE;
     use crate::track::AudioEvent;
@@ -217,7 +221,9 @@ mod tests {
             if let AudioEvent::Note(note) = event {
                 // Check if timing is offset from exact multiples of 0.25
                 let expected_times = [0.0, 0.25, 0.5, 0.75];
-                let time_exact = expected_times.iter().any(|&t| (note.start_time - t).abs() < 0.001);
+                let time_exact = expected_times
+                    .iter()
+                    .any(|&t| (note.start_time - at(t)).0.abs() < at(0.001).0);
                 if !time_exact || (note.velocity - 0.7).abs() > 0.01 {
                     has_variance = true;
                     break;
@@ -243,19 +249,19 @@ mod tests {
         // After rotate(1): should be E4, G4, C5, C4
         if let AudioEvent::Note(note) = &events[0] {
             assert!((note.frequencies[0] - E4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.0); // Timing unchanged
+            assert_eq!(note.start_time, at(0.0)); // Timing unchanged
         }
         if let AudioEvent::Note(note) = &events[1] {
             assert!((note.frequencies[0] - G4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.25);
+            assert_eq!(note.start_time, at(0.25));
         }
         if let AudioEvent::Note(note) = &events[2] {
             assert!((note.frequencies[0] - C5).abs() < 0.1);
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
         }
         if let AudioEvent::Note(note) = &events[3] {
             assert!((note.frequencies[0] - C4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.75);
+            assert_eq!(note.start_time, at(0.75));
         }
     }
 
@@ -314,19 +320,19 @@ mod tests {
         // After retrograde: should be C5, G4, E4, C4 (reversed pitches)
         if let AudioEvent::Note(note) = &events[0] {
             assert!((note.frequencies[0] - C5).abs() < 0.1);
-            assert_eq!(note.start_time, 0.0); // Timing unchanged
+            assert_eq!(note.start_time, at(0.0)); // Timing unchanged
         }
         if let AudioEvent::Note(note) = &events[1] {
             assert!((note.frequencies[0] - G4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.25);
+            assert_eq!(note.start_time, at(0.25));
         }
         if let AudioEvent::Note(note) = &events[2] {
             assert!((note.frequencies[0] - E4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
         }
         if let AudioEvent::Note(note) = &events[3] {
             assert!((note.frequencies[0] - C4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.75);
+            assert_eq!(note.start_time, at(0.75));
         }
     }
 
@@ -354,10 +360,10 @@ mod tests {
 
         // Check that timing is preserved
         if let AudioEvent::Note(note) = &events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert_eq!(note.start_time, 0.25);
+            assert_eq!(note.start_time, at(0.25));
         }
 
         // At least one note should be different (with very high probability)
@@ -566,16 +572,16 @@ mod tests {
 
         // Check timing is stretched (doubled)
         if let AudioEvent::Note(note) = &events[0] {
-            assert!((note.start_time - 0.0).abs() < 0.01);
-            assert!((note.duration - 1.0).abs() < 0.01); // 0.5 * 2.0 = 1.0
+            assert!((note.start_time - at(0.0)).0.abs() < at(0.01).0);
+            assert!((note.duration - at(1.0)).0.abs() < at(0.01).0); // 0.5 * 2.0 = 1.0
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert!((note.start_time - 1.0).abs() < 0.01); // 0.5 * 2.0 = 1.0
-            assert!((note.duration - 1.0).abs() < 0.01);
+            assert!((note.start_time - at(1.0)).0.abs() < at(0.01).0); // 0.5 * 2.0 = 1.0
+            assert!((note.duration - at(1.0)).0.abs() < at(0.01).0);
         }
         if let AudioEvent::Note(note) = &events[2] {
-            assert!((note.start_time - 2.0).abs() < 0.01); // 1.0 * 2.0 = 2.0
-            assert!((note.duration - 1.0).abs() < 0.01);
+            assert!((note.start_time - at(2.0)).0.abs() < at(0.01).0); // 1.0 * 2.0 = 2.0
+            assert!((note.duration - at(1.0)).0.abs() < at(0.01).0);
         }
     }
 
@@ -593,12 +599,12 @@ mod tests {
 
         // Check timing is compressed (halved)
         if let AudioEvent::Note(note) = &events[0] {
-            assert!((note.start_time - 0.0).abs() < 0.01);
-            assert!((note.duration - 0.5).abs() < 0.01); // 1.0 * 0.5 = 0.5
+            assert!((note.start_time - at(0.0)).0.abs() < at(0.01).0);
+            assert!((note.duration - at(0.5)).0.abs() < at(0.01).0); // 1.0 * 0.5 = 0.5
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert!((note.start_time - 0.5).abs() < 0.01); // 1.0 * 0.5 = 0.5
-            assert!((note.duration - 0.5).abs() < 0.01);
+            assert!((note.start_time - at(0.5)).0.abs() < at(0.01).0); // 1.0 * 0.5 = 0.5
+            assert!((note.duration - at(0.5)).0.abs() < at(0.01).0);
         }
     }
 
@@ -615,12 +621,12 @@ mod tests {
 
         // Should remain unchanged
         if let AudioEvent::Note(note) = &events[0] {
-            assert!((note.start_time - 0.0).abs() < 0.01);
-            assert!((note.duration - 0.25).abs() < 0.01);
+            assert!((note.start_time - at(0.0)).0.abs() < at(0.01).0);
+            assert!((note.duration - at(0.25)).0.abs() < at(0.01).0);
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert!((note.start_time - 0.25).abs() < 0.01);
-            assert!((note.duration - 0.25).abs() < 0.01);
+            assert!((note.start_time - at(0.25)).0.abs() < at(0.01).0);
+            assert!((note.duration - at(0.25)).0.abs() < at(0.01).0);
         }
     }
 
@@ -649,12 +655,12 @@ mod tests {
         // Should be compressed by factor of 0.5/0.75 = 0.666...
         // First note at 0.0 with duration ~0.167 (0.25 * 0.666)
         if let AudioEvent::Note(note) = &events[0] {
-            assert_eq!(note.start_time, 0.0);
-            assert!((note.duration - 0.167).abs() < 0.01);
+            assert_eq!(note.start_time, at(0.0));
+            assert!((note.duration - at(0.167)).0.abs() < at(0.01).0);
         }
         // Second note at ~0.167
         if let AudioEvent::Note(note) = &events[1] {
-            assert!((note.start_time - 0.167).abs() < 0.01);
+            assert!((note.start_time - at(0.167)).0.abs() < at(0.01).0);
         }
     }
 
@@ -671,11 +677,11 @@ mod tests {
 
         // Should be expanded by factor of 2.0
         if let AudioEvent::Note(note) = &events[0] {
-            assert_eq!(note.start_time, 0.0);
-            assert!((note.duration - 1.0).abs() < 0.01);  // 0.5 * 2.0
+            assert_eq!(note.start_time, at(0.0));
+            assert!((note.duration - at(1.0)).0.abs() < at(0.01).0);  // 0.5 * 2.0
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert!((note.start_time - 1.0).abs() < 0.01);  // 0.5 * 2.0
+            assert!((note.start_time - at(1.0)).0.abs() < at(0.01).0);  // 0.5 * 2.0
         }
     }
 
@@ -707,13 +713,13 @@ mod tests {
 
         // Check quantized to nearest 0.25 grid
         if let AudioEvent::Note(note) = &events[0] {
-            assert!((note.start_time - 0.0).abs() < 0.01);  // 0.12 → 0.0
+            assert!((note.start_time - at(0.0)).0.abs() < at(0.01).0);  // 0.12 → 0.0
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert!((note.start_time - 0.5).abs() < 0.01);  // 0.48 → 0.5
+            assert!((note.start_time - at(0.5)).0.abs() < at(0.01).0);  // 0.48 → 0.5
         }
         if let AudioEvent::Note(note) = &events[2] {
-            assert!((note.start_time - 0.75).abs() < 0.01); // 0.77 → 0.75
+            assert!((note.start_time - at(0.75)).0.abs() < at(0.01).0); // 0.77 → 0.75
         }
     }
 
@@ -733,10 +739,10 @@ mod tests {
 
         // Check quantized to nearest 0.5 grid
         if let AudioEvent::Note(note) = &events[0] {
-            assert!((note.start_time - 0.0).abs() < 0.01);  // 0.13 → 0.0
+            assert!((note.start_time - at(0.0)).0.abs() < at(0.01).0);  // 0.13 → 0.0
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert!((note.start_time - 0.5).abs() < 0.01);  // 0.62 → 0.5
+            assert!((note.start_time - at(0.5)).0.abs() < at(0.01).0);  // 0.62 → 0.5
         }
     }
 
@@ -788,15 +794,15 @@ mod tests {
         // Check forward sequence
         if let AudioEvent::Note(note) = &events[0] {
             assert!((note.frequencies[0] - C4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &events[1] {
             assert!((note.frequencies[0] - E4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.25);
+            assert_eq!(note.start_time, at(0.25));
         }
         if let AudioEvent::Note(note) = &events[2] {
             assert!((note.frequencies[0] - G4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
         }
 
         // Check reversed sequence (should be G4, E4, C4)
@@ -838,7 +844,7 @@ mod tests {
         // Check first note and its stutters
         if let AudioEvent::Note(note) = &events[0] {
             assert!((note.frequencies[0] - C4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
     }
 
@@ -910,14 +916,14 @@ mod tests {
         // Check first grain
         if let AudioEvent::Note(note) = &events[0] {
             assert!((note.frequencies[0] - C4).abs() < 0.1);
-            assert_eq!(note.start_time, 0.0);
-            assert!((note.duration - 0.09).abs() < 0.01);  // 1.0/10 * 0.9 = 0.09
+            assert_eq!(note.start_time, at(0.0));
+            assert!((note.duration - at(0.09)).0.abs() < at(0.01).0);  // 1.0/10 * 0.9 = 0.09
         }
 
         // Check second grain
         if let AudioEvent::Note(note) = &events[1] {
             assert!((note.frequencies[0] - C4).abs() < 0.1);
-            assert!((note.start_time - 0.1).abs() < 0.01);  // 1.0/10 = 0.1
+            assert!((note.start_time - at(0.1)).0.abs() < at(0.01).0);  // 1.0/10 = 0.1
         }
     }
 
@@ -957,10 +963,10 @@ mod tests {
 
         // Check that timing is preserved
         if let AudioEvent::Note(note) = &events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert_eq!(note.start_time, 0.25);
+            assert_eq!(note.start_time, at(0.25));
         }
 
         // Check that all original frequencies are still present (just reordered)
@@ -1263,7 +1269,7 @@ mod tests {
 
         // Later notes should be shifted more in time
         if let (AudioEvent::Note(note1), AudioEvent::Note(note2)) = (&events[0], &events[1]) {
-            let expected_interval = 0.25;
+            let expected_interval = at(0.25);
             let actual_interval = note2.start_time - note1.start_time;
             // Second note should be pushed forward
             assert!(actual_interval > expected_interval);
@@ -1272,7 +1278,7 @@ mod tests {
         if let (AudioEvent::Note(note2), AudioEvent::Note(note3)) = (&events[1], &events[2]) {
             let interval = note3.start_time - note2.start_time;
             // Third note interval should be even larger due to accumulation
-            assert!(interval > 0.25);
+            assert!(interval > at(0.25));
         }
     }
 
@@ -1481,7 +1487,7 @@ mod tests {
             .pattern_start()
             .sieve_inclusive(100.0, 500.0);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -1492,7 +1498,7 @@ mod tests {
             .pattern_start()
             .sieve_exclusive(100.0, 500.0);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -1542,7 +1548,7 @@ mod tests {
         // Check it's a chord with 4 frequencies
         if let AudioEvent::Note(note) = &events[0] {
             assert_eq!(note.num_freqs, 4);
-            assert_eq!(note.duration, 2.0);
+            assert_eq!(note.duration, at(2.0));
         }
     }
 
@@ -1571,7 +1577,7 @@ mod tests {
             .group(3.0);
 
         // Cursor should be at pattern_start + duration
-        assert_eq!(builder.cursor, 3.0);
+        assert_eq!(builder.peek_cursor(), 3.0);
     }
 
     #[test]
@@ -1582,7 +1588,7 @@ mod tests {
             .pattern_start()
             .group(2.0);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -1638,8 +1644,8 @@ mod tests {
 
         // Check timing is preserved but shifted
         if let (AudioEvent::Note(note1), AudioEvent::Note(note3)) = (&events[0], &events[2]) {
-            assert_eq!(note1.start_time, 0.0);
-            assert_eq!(note3.start_time, 1.0); // After 2 notes of 0.5 duration each
+            assert_eq!(note1.start_time, at(0.0));
+            assert_eq!(note3.start_time, at(1.0)); // After 2 notes of 0.5 duration each
         }
     }
 
@@ -1668,7 +1674,7 @@ mod tests {
             .duplicate();
 
         // Cursor should be doubled (3 notes * 0.25 * 2 = 1.5)
-        assert_eq!(builder.cursor, 1.5);
+        assert_eq!(builder.peek_cursor(), 1.5);
     }
 
     #[test]
@@ -1679,7 +1685,7 @@ mod tests {
             .pattern_start()
             .duplicate();
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -1869,16 +1875,16 @@ mod tests {
 
         // Check timing
         if let AudioEvent::Note(note) = &events[0] {
-            assert!((note.start_time - 0.0).abs() < 0.01);
+            assert!((note.start_time - at(0.0)).0.abs() < at(0.01).0);
         }
         if let AudioEvent::Note(note) = &events[1] {
-            assert!((note.start_time - 0.5).abs() < 0.01);
+            assert!((note.start_time - at(0.5)).0.abs() < at(0.01).0);
         }
         if let AudioEvent::Note(note) = &events[2] {
-            assert!((note.start_time - 1.0).abs() < 0.01);
+            assert!((note.start_time - at(1.0)).0.abs() < at(0.01).0);
         }
         if let AudioEvent::Note(note) = &events[3] {
-            assert!((note.start_time - 1.5).abs() < 0.01);
+            assert!((note.start_time - at(1.5)).0.abs() < at(0.01).0);
         }
     }
 
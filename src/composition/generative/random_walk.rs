@@ -21,13 +21,35 @@ use crate::composition::TrackBuilder;
 /// // Use with: comp.track("melody").sequence_from(&walk, &C4_MAJOR_SCALE, 0.25);
 /// ```
 pub fn random_walk_sequence(start: u32, steps: usize, min: u32, max: u32) -> Vec<u32> {
+    random_walk_sequence_with_rng(start, steps, min, max, &mut rand::rng())
+}
+
+/// Generate a random walk sequence from a caller-supplied generator
+///
+/// Same as [`random_walk_sequence`], but reproducible when `rng` is seeded,
+/// e.g. with [`Composition::rng`](crate::composition::Composition::rng).
+///
+/// # Example
+/// ```
+/// # use tunes::prelude::*;
+/// # use tunes::composition::generative::random_walk_sequence_with_rng;
+/// let mut comp = Composition::new(Tempo::new(120.0));
+/// comp.set_seed(7);
+/// let walk = random_walk_sequence_with_rng(3, 32, 0, 7, comp.rng());
+/// ```
+pub fn random_walk_sequence_with_rng(
+    start: u32,
+    steps: usize,
+    min: u32,
+    max: u32,
+    rng: &mut impl Rng,
+) -> Vec<u32> {
     if steps == 0 || min >= max {
         return Vec::new();
     }
 
     let mut sequence = Vec::with_capacity(steps);
     let mut current = start.clamp(min, max - 1);
-    let mut rng = rand::rng();
 
     for _ in 0..steps {
         sequence.push(current);
@@ -36,7 +58,7 @@ pub fn random_walk_sequence(start: u32, steps: usize, min: u32, max: u32) -> Vec
         let step = if rng.random::<bool>() {
             rng.random_range(1..=2) // Move up
         } else {
-            -(rng.random_range(1..=2) as i32) // Move down
+            -rng.random_range(1..=2) // Move down
         };
 
         current = ((current as i32 + step).clamp(min as i32, (max - 1) as i32)) as u32;
@@ -66,6 +88,20 @@ pub fn biased_random_walk_sequence(
     min: u32,
     max: u32,
     up_bias: f32,
+) -> Vec<u32> {
+    biased_random_walk_sequence_with_rng(start, steps, min, max, up_bias, &mut rand::rng())
+}
+
+/// Generate a biased random walk sequence from a caller-supplied generator
+///
+/// Same as [`biased_random_walk_sequence`], but reproducible when `rng` is seeded.
+pub fn biased_random_walk_sequence_with_rng(
+    start: u32,
+    steps: usize,
+    min: u32,
+    max: u32,
+    up_bias: f32,
+    rng: &mut impl Rng,
 ) -> Vec<u32> {
     if steps == 0 || min >= max {
         return Vec::new();
@@ -73,7 +109,6 @@ pub fn biased_random_walk_sequence(
 
     let mut sequence = Vec::with_capacity(steps);
     let mut current = start.clamp(min, max - 1);
-    let mut rng = rand::rng();
 
     for _ in 0..steps {
         sequence.push(current);
@@ -83,7 +118,7 @@ pub fn biased_random_walk_sequence(
         let step = if go_up {
             rng.random_range(1..=2) // Move up
         } else {
-            -(rng.random_range(1..=2) as i32) // Move down
+            -rng.random_range(1..=2) // Move down
         };
 
         current = ((current as i32 + step).clamp(min as i32, (max - 1) as i32)) as u32;
@@ -134,24 +169,25 @@ impl<'a> TrackBuilder<'a> {
             .position(|&f| (f - start_freq).abs() < 0.1)
             .unwrap_or(0);
 
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
 
         for _ in 0..steps {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             let freq = scale[current_idx];
 
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
 
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
 
             // Random walk: move up or down by 1-2 scale degrees
             let step = if rng.random::<bool>() {
@@ -1,7 +1,9 @@
 //! Pattern transformation methods
 
 use crate::composition::TrackBuilder;
+use crate::composition::timing::Ticks;
 use crate::synthesis::envelope::Envelope;
+use crate::synthesis::unison::Unison;
 use crate::synthesis::waveform::Waveform;
 use crate::track::AudioEvent;
 
@@ -34,12 +36,16 @@ impl<'a> TrackBuilder<'a> {
     pub fn humanize(mut self, timing_variance: f32, velocity_variance: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
         let pattern_start = self.pattern_start;
         let cursor = self.cursor;
+        let variance = self.span(timing_variance).0;
+
+        use rand::Rng;
+        let mut rng = self.rng();
 
         // Apply humanization to notes in the pattern
         for event in &mut self.get_track_mut().events {
@@ -53,10 +59,8 @@ impl<'a> TrackBuilder<'a> {
                 match event {
                     AudioEvent::Note(note) => {
                         // Randomize timing
-                        use rand::Rng;
-                        let mut rng = rand::rng();
-                        let timing_offset = rng.random_range(-timing_variance..=timing_variance);
-                        note.start_time = (note.start_time + timing_offset).max(0.0);
+                        let timing_offset = Ticks(rng.random_range(-variance..=variance));
+                        note.start_time = (note.start_time + timing_offset).max(Ticks::ZERO);
 
                         // Randomize velocity
                         let velocity_offset =
@@ -65,10 +69,8 @@ impl<'a> TrackBuilder<'a> {
                     }
                     AudioEvent::Drum(drum) => {
                         // Randomize drum timing only
-                        use rand::Rng;
-                        let mut rng = rand::rng();
-                        let timing_offset = rng.random_range(-timing_variance..=timing_variance);
-                        drum.start_time = (drum.start_time + timing_offset).max(0.0);
+                        let timing_offset = Ticks(rng.random_range(-variance..=variance));
+                        drum.start_time = (drum.start_time + timing_offset).max(Ticks::ZERO);
                     }
                     _ => {}
                 }
@@ -108,7 +110,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn rotate(mut self, positions: i32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || positions == 0 {
+        if pattern_duration <= Ticks::ZERO || positions == 0 {
             return self;
         }
 
@@ -180,7 +182,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn mutate(mut self, max_semitones: i32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || max_semitones == 0 {
+        if pattern_duration <= Ticks::ZERO || max_semitones == 0 {
             return self;
         }
 
@@ -189,7 +191,7 @@ impl<'a> TrackBuilder<'a> {
         let cursor = self.cursor;
 
         use rand::Rng;
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
 
         // Mutate notes in the pattern
         for event in &mut self.get_track_mut().events {
@@ -256,7 +258,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn stack(mut self, semitones: i32, count: usize) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || count == 0 {
+        if pattern_duration <= Ticks::ZERO || count == 0 {
             return self;
         }
 
@@ -329,7 +331,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn stretch(mut self, factor: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || factor <= 0.0 || (factor - 1.0).abs() < 0.001 {
+        if pattern_duration <= Ticks::ZERO || factor <= 0.0 || (factor - 1.0).abs() < 0.001 {
             return self;
         }
 
@@ -343,15 +345,15 @@ impl<'a> TrackBuilder<'a> {
                     if note.start_time >= pattern_start && note.start_time < cursor {
                         // Stretch timing relative to pattern start
                         let offset = note.start_time - pattern_start;
-                        note.start_time = pattern_start + (offset * factor);
-                        note.duration *= factor;
+                        note.start_time = pattern_start + offset.scale(factor as f64);
+                        note.duration = note.duration.scale(factor as f64);
                     }
                 }
                 AudioEvent::Drum(drum) => {
                     if drum.start_time >= pattern_start && drum.start_time < cursor {
                         // Stretch timing relative to pattern start
                         let offset = drum.start_time - pattern_start;
-                        drum.start_time = pattern_start + (offset * factor);
+                        drum.start_time = pattern_start + offset.scale(factor as f64);
                     }
                 }
                 _ => {} // Ignore other event types
@@ -359,7 +361,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         // Update cursor to reflect stretched duration
-        self.cursor = pattern_start + (pattern_duration * factor);
+        self.cursor = pattern_start + pattern_duration.scale(factor as f64);
 
         self.get_track_mut().invalidate_time_cache();
         self.update_section_duration();
@@ -393,7 +395,7 @@ impl<'a> TrackBuilder<'a> {
     ///     .compress(1.0);  // Now exactly 1 beat
     /// ```
     pub fn compress(self, target_duration: f32) -> Self {
-        let current_duration = self.cursor - self.pattern_start;
+        let current_duration = self.peek_cursor() - self.to_seconds(self.pattern_start);
 
         if current_duration <= 0.0 || target_duration <= 0.0 {
             return self;
@@ -436,12 +438,17 @@ impl<'a> TrackBuilder<'a> {
     pub fn quantize(mut self, grid: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || grid <= 0.0 {
+        if pattern_duration <= Ticks::ZERO || grid <= 0.0 {
             return self;
         }
 
         let pattern_start = self.pattern_start;
         let cursor = self.cursor;
+        let grid = self.span_at(pattern_start, grid);
+        if grid <= Ticks::ZERO {
+            return self;
+        }
+        let snap = |offset: Ticks| grid * (offset.0 as f64 / grid.0 as f64).round() as i64;
 
         // Quantize all events in the pattern
         for event in &mut self.get_track_mut().events {
@@ -450,16 +457,14 @@ impl<'a> TrackBuilder<'a> {
                     if note.start_time >= pattern_start && note.start_time < cursor {
                         // Quantize to nearest grid position
                         let offset = note.start_time - pattern_start;
-                        let quantized_offset = (offset / grid).round() * grid;
-                        note.start_time = pattern_start + quantized_offset;
+                        note.start_time = pattern_start + snap(offset);
                     }
                 }
                 AudioEvent::Drum(drum) => {
                     if drum.start_time >= pattern_start && drum.start_time < cursor {
                         // Quantize to nearest grid position
                         let offset = drum.start_time - pattern_start;
-                        let quantized_offset = (offset / grid).round() * grid;
-                        drum.start_time = pattern_start + quantized_offset;
+                        drum.start_time = pattern_start + snap(offset);
                     }
                 }
                 _ => {} // Ignore other event types
@@ -497,7 +502,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn palindrome(mut self) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -534,7 +539,7 @@ impl<'a> TrackBuilder<'a> {
             let original_offset = match event {
                 AudioEvent::Note(note) => note.start_time - pattern_start,
                 AudioEvent::Drum(drum) => drum.start_time - pattern_start,
-                _ => 0.0,
+                _ => Ticks::ZERO,
             };
 
             let mirrored_offset = pattern_duration - original_offset;
@@ -555,7 +560,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         // Update cursor to reflect doubled length
-        self.cursor = cursor + pattern_duration;
+        self.cursor += self.cursor - self.pattern_start;
 
         self.get_track_mut().invalidate_time_cache();
         self.update_section_duration();
@@ -592,18 +597,21 @@ impl<'a> TrackBuilder<'a> {
     pub fn stutter(mut self, probability: f32, repeats: usize) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || probability <= 0.0 || repeats == 0 {
+        if pattern_duration <= Ticks::ZERO || probability <= 0.0 || repeats == 0 {
             return self;
         }
 
         let pattern_start = self.pattern_start;
         let cursor = self.cursor;
 
+        // Drums don't have duration, so they roll over a short fixed span
+        let drum_span = self.span(0.05);
+
         use rand::Rng;
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
 
         // Collect events that will stutter
-        let mut stutter_events: Vec<(usize, AudioEvent, f32)> = Vec::new();
+        let mut stutter_events: Vec<(usize, AudioEvent, Ticks)> = Vec::new();
 
         for (idx, event) in self.get_track_mut().events.iter().enumerate() {
             let should_stutter = rng.random_range(0.0..1.0) < probability;
@@ -620,8 +628,7 @@ impl<'a> TrackBuilder<'a> {
                 }
                 AudioEvent::Drum(drum) => {
                     if drum.start_time >= pattern_start && drum.start_time < cursor {
-                        // Drums don't have duration, use small interval
-                        stutter_events.push((idx, event.clone(), 0.05));
+                        stutter_events.push((idx, event.clone(), drum_span));
                     }
                 }
                 _ => {}
@@ -631,16 +638,16 @@ impl<'a> TrackBuilder<'a> {
         // Add stutter repeats
         for (_idx, event, base_duration) in stutter_events {
             // Calculate rapid repeat interval (divide duration by number of repeats)
-            let stutter_interval = base_duration / repeats as f32;
+            let stutter_interval = base_duration.scale(1.0 / repeats as f64);
 
             for i in 1..=repeats {
                 let mut stutter_copy = event.clone();
-                let offset = stutter_interval * i as f32;
+                let offset = stutter_interval * i as i64;
 
                 match &mut stutter_copy {
                     AudioEvent::Note(note) => {
                         note.start_time += offset;
-                        note.duration = stutter_interval * 0.8; // Slightly shorter for separation
+                        note.duration = stutter_interval.scale(0.8); // Slightly shorter for separation
                     }
                     AudioEvent::Drum(drum) => {
                         drum.start_time += offset;
@@ -688,15 +695,17 @@ impl<'a> TrackBuilder<'a> {
     pub fn stutter_every(mut self, nth: usize, repeats: usize) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || nth == 0 || repeats == 0 {
+        if pattern_duration <= Ticks::ZERO || nth == 0 || repeats == 0 {
             return self;
         }
 
         let pattern_start = self.pattern_start;
         let cursor = self.cursor;
 
+        let drum_span = self.span(0.05);
+
         // Collect events to stutter (every nth event)
-        let mut stutter_events: Vec<(usize, AudioEvent, f32)> = Vec::new();
+        let mut stutter_events: Vec<(usize, AudioEvent, Ticks)> = Vec::new();
         let mut note_count = 0;
 
         for (idx, event) in self.get_track_mut().events.iter().enumerate() {
@@ -713,7 +722,7 @@ impl<'a> TrackBuilder<'a> {
                     if drum.start_time >= pattern_start && drum.start_time < cursor {
                         note_count += 1;
                         if note_count % nth == 0 {
-                            stutter_events.push((idx, event.clone(), 0.05));
+                            stutter_events.push((idx, event.clone(), drum_span));
                         }
                     }
                 }
@@ -723,16 +732,16 @@ impl<'a> TrackBuilder<'a> {
 
         // Add stutter repeats
         for (_idx, event, base_duration) in stutter_events {
-            let stutter_interval = base_duration / repeats as f32;
+            let stutter_interval = base_duration.scale(1.0 / repeats as f64);
 
             for i in 1..=repeats {
                 let mut stutter_copy = event.clone();
-                let offset = stutter_interval * i as f32;
+                let offset = stutter_interval * i as i64;
 
                 match &mut stutter_copy {
                     AudioEvent::Note(note) => {
                         note.start_time += offset;
-                        note.duration = stutter_interval * 0.8;
+                        note.duration = stutter_interval.scale(0.8);
                     }
                     AudioEvent::Drum(drum) => {
                         drum.start_time += offset;
@@ -780,7 +789,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn granularize(mut self, divisions: usize) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || divisions == 0 {
+        if pattern_duration <= Ticks::ZERO || divisions == 0 {
             return self;
         }
 
@@ -809,12 +818,12 @@ impl<'a> TrackBuilder<'a> {
         // Create granularized versions
         for event in notes_to_granularize {
             if let AudioEvent::Note(note) = event {
-                let grain_duration = note.duration / divisions as f32;
+                let grain_duration = note.duration.scale(1.0 / divisions as f64);
 
                 for i in 0..divisions {
                     let mut grain = note.clone();
-                    grain.start_time = note.start_time + (grain_duration * i as f32);
-                    grain.duration = grain_duration * 0.9; // Slight gap between grains
+                    grain.start_time = note.start_time + grain_duration * i as i64;
+                    grain.duration = grain_duration.scale(0.9); // Slight gap between grains
 
                     self.get_track_mut().events.push(AudioEvent::Note(grain));
                 }
@@ -847,7 +856,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn magnetize(mut self, scale_notes: &[f32]) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || scale_notes.is_empty() {
+        if pattern_duration <= Ticks::ZERO || scale_notes.is_empty() {
             return self;
         }
 
@@ -904,7 +913,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn gravity(mut self, center_pitch: f32, strength: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || strength == 0.0 {
+        if pattern_duration <= Ticks::ZERO || strength == 0.0 {
             return self;
         }
 
@@ -956,7 +965,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn ripple(mut self, intensity: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || intensity == 0.0 {
+        if pattern_duration <= Ticks::ZERO || intensity == 0.0 {
             return self;
         }
 
@@ -964,7 +973,7 @@ impl<'a> TrackBuilder<'a> {
         let cursor = self.cursor;
 
         // Collect notes in time order
-        let mut note_data: Vec<(usize, f32, [f32; 8], usize)> = Vec::new();
+        let mut note_data: Vec<(usize, Ticks, [f32; 8], usize)> = Vec::new();
         for (idx, event) in self.get_track_mut().events.iter().enumerate() {
             if let AudioEvent::Note(note) = event {
                 if note.start_time >= pattern_start && note.start_time < cursor {
@@ -974,19 +983,21 @@ impl<'a> TrackBuilder<'a> {
         }
 
         // Sort by time
-        note_data.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        note_data.sort_by_key(|note| note.1);
 
         // Apply cascading ripple effects
         let mut accumulated_time_shift = 0.0;
         let mut accumulated_pitch_shift = 0.0;
         let decay = 0.7; // Each ripple decays to 70% of previous
 
-        for (i, (idx, _original_time, _original_freqs, num_freqs)) in note_data.iter().enumerate() {
+        for (i, (idx, original_time, _original_freqs, num_freqs)) in note_data.iter().enumerate() {
             if i > 0 {
+                let time_shift = self.span_at(*original_time, accumulated_time_shift);
+
                 // Apply accumulated effects from previous notes
                 if let AudioEvent::Note(note) = &mut self.get_track_mut().events[*idx] {
                     // Apply timing shift
-                    note.start_time += accumulated_time_shift;
+                    note.start_time += time_shift;
 
                     // Apply pitch shift
                     let pitch_shift_ratio = 2.0_f32.powf(accumulated_pitch_shift / 12.0);
@@ -1026,7 +1037,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn shuffle(mut self) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -1053,7 +1064,7 @@ impl<'a> TrackBuilder<'a> {
 
         // Shuffle using Fisher-Yates
         use rand::Rng;
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
         for i in (1..freqs.len()).rev() {
             let j = rng.random_range(0..=i);
             freqs.swap(i, j);
@@ -1099,7 +1110,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn thin(mut self, keep_probability: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -1115,7 +1126,7 @@ impl<'a> TrackBuilder<'a> {
 
         // Remove notes based on probability
         use rand::Rng;
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
 
         self.get_track_mut().events.retain(|event| {
             match event {
@@ -1156,7 +1167,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn retrograde(mut self) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -1223,7 +1234,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn shift(mut self, semitones: i32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || semitones == 0 {
+        if pattern_duration <= Ticks::ZERO || semitones == 0 {
             return self;
         }
 
@@ -1258,10 +1269,17 @@ impl<'a> TrackBuilder<'a> {
                                 envelope: note.envelope,
                                 filter_envelope: note.filter_envelope,
                                 fm_params: note.fm_params,
+                                fm_patch: note.fm_patch.clone(),
+                                physical_model: note.physical_model.clone(),
+                                resonator: note.resonator.clone(),
                                 pitch_bend_semitones: note.pitch_bend_semitones,
                                 custom_wavetable: note.custom_wavetable.clone(),
                                 velocity: note.velocity,
                                 spatial_position: note.spatial_position,
+                                unison: note.unison,
+                                voice: note.voice,
+                                renderings: Default::default(),
+                                placed_seconds: None,
                             }))
                         }
                         AudioEvent::Drum(_)
@@ -1324,7 +1342,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn invert(mut self, axis_freq: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -1364,10 +1382,17 @@ impl<'a> TrackBuilder<'a> {
                                 envelope: note.envelope,
                                 filter_envelope: note.filter_envelope,
                                 fm_params: note.fm_params,
+                                fm_patch: note.fm_patch.clone(),
+                                physical_model: note.physical_model.clone(),
+                                resonator: note.resonator.clone(),
                                 pitch_bend_semitones: note.pitch_bend_semitones,
                                 custom_wavetable: note.custom_wavetable.clone(),
                                 velocity: note.velocity,
                                 spatial_position: note.spatial_position,
+                                unison: note.unison,
+                                voice: note.voice,
+                                renderings: Default::default(),
+                                placed_seconds: None,
                             }))
                         }
                         AudioEvent::Drum(_)
@@ -1425,7 +1450,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn invert_constrained(mut self, axis_freq: f32, min_freq: f32, max_freq: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -1472,10 +1497,17 @@ impl<'a> TrackBuilder<'a> {
                                 envelope: note.envelope,
                                 filter_envelope: note.filter_envelope,
                                 fm_params: note.fm_params,
+                                fm_patch: note.fm_patch.clone(),
+                                physical_model: note.physical_model.clone(),
+                                resonator: note.resonator.clone(),
                                 pitch_bend_semitones: note.pitch_bend_semitones,
                                 custom_wavetable: note.custom_wavetable.clone(),
                                 velocity: note.velocity,
                                 spatial_position: note.spatial_position,
+                                unison: note.unison,
+                                voice: note.voice,
+                                renderings: Default::default(),
+                                placed_seconds: None,
                             }))
                         }
                         AudioEvent::Drum(_)
@@ -1531,7 +1563,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn sieve_inclusive(mut self, min_freq: f32, max_freq: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -1577,7 +1609,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn sieve_exclusive(mut self, min_freq: f32, max_freq: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -1623,12 +1655,13 @@ impl<'a> TrackBuilder<'a> {
     pub fn group(mut self, duration: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
         let pattern_start = self.pattern_start;
         let cursor = self.cursor;
+        let duration = self.span_at(pattern_start, duration);
 
         // Collect all note frequencies from the pattern
         let mut all_freqs = Vec::new();
@@ -1636,6 +1669,10 @@ impl<'a> TrackBuilder<'a> {
         let mut envelope = Envelope::default();
         let mut pitch_bend = 0.0;
         let mut velocity = 1.0;
+        let mut unison = Unison::default();
+        let mut fm_patch = None;
+        let mut physical_model = None;
+        let mut resonator = None;
 
         for event in &self.get_track_mut().events {
             if let AudioEvent::Note(note) = event {
@@ -1650,6 +1687,10 @@ impl<'a> TrackBuilder<'a> {
                         envelope = note.envelope;
                         pitch_bend = note.pitch_bend_semitones;
                         velocity = note.velocity;
+                        unison = note.unison;
+                        fm_patch = note.fm_patch.clone();
+                        physical_model = note.physical_model.clone();
+                        resonator = note.resonator.clone();
                     }
                 }
             }
@@ -1685,10 +1726,17 @@ impl<'a> TrackBuilder<'a> {
             envelope,
             filter_envelope: crate::synthesis::filter_envelope::FilterEnvelope::default(),
             fm_params: crate::synthesis::fm_synthesis::FMParams::default(),
+            fm_patch,
+            physical_model,
+            resonator,
             pitch_bend_semitones: pitch_bend,
             custom_wavetable: None,
             velocity,
             spatial_position: None,
+            unison,
+            voice: crate::track::VoiceState::default(),
+            renderings: Default::default(),
+            placed_seconds: None,
         });
 
         self.get_track_mut().events.push(chord_event);
@@ -1718,7 +1766,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn duplicate(mut self) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -1767,8 +1815,9 @@ impl<'a> TrackBuilder<'a> {
 
         // Update cursor and pattern_start
         // Set pattern_start to beginning of duplicated section so transforms only affect duplicated notes
-        self.pattern_start = cursor;
-        self.cursor = cursor + pattern_duration;
+        let pattern_length = self.cursor - self.pattern_start;
+        self.pattern_start = self.cursor;
+        self.cursor += pattern_length;
 
         self
     }
@@ -1801,7 +1850,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn range_dilation(mut self, factor: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || factor == 1.0 {
+        if pattern_duration <= Ticks::ZERO || factor == 1.0 {
             return self;
         }
 
@@ -1882,7 +1931,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn shape_contour(mut self, factor: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || factor == 1.0 {
+        if pattern_duration <= Ticks::ZERO || factor == 1.0 {
             return self;
         }
 
@@ -1890,7 +1939,7 @@ impl<'a> TrackBuilder<'a> {
         let cursor = self.cursor;
 
         // Collect note events in time order
-        let mut note_refs: Vec<(f32, usize)> = Vec::new();
+        let mut note_refs: Vec<(Ticks, usize)> = Vec::new();
         for (idx, event) in self.get_track_mut().events.iter().enumerate() {
             if let AudioEvent::Note(note) = event {
                 if note.start_time >= pattern_start && note.start_time < cursor {
@@ -1904,7 +1953,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         // Sort by time
-        note_refs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        note_refs.sort_by_key(|note| note.0);
 
         // Get first note's primary frequency as anchor
         let first_idx = note_refs[0].1;
@@ -1963,12 +2012,13 @@ impl<'a> TrackBuilder<'a> {
     pub fn echo(mut self, delay: f32, repeats: usize, decay: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || repeats == 0 || delay <= 0.0 {
+        if pattern_duration <= Ticks::ZERO || repeats == 0 || delay <= 0.0 {
             return self;
         }
 
         let pattern_start = self.pattern_start;
         let cursor = self.cursor;
+        let delay = self.span(delay);
 
         // Collect original events
         let original_events: Vec<AudioEvent> = self
@@ -1989,7 +2039,7 @@ impl<'a> TrackBuilder<'a> {
 
         // Create echoes
         for repeat in 1..=repeats {
-            let time_offset = delay * repeat as f32;
+            let time_offset = delay * repeat as i64;
             let volume_scale = decay.powi(repeat as i32);
 
             for event in &original_events {
@@ -2016,8 +2066,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         // Update cursor to end of last echo
-        let total_echo_duration = delay * repeats as f32;
-        self.cursor = cursor + total_echo_duration;
+        self.cursor += delay * repeats as i64;
 
         self.get_track_mut().invalidate_time_cache();
         self.update_section_duration();
@@ -2055,7 +2104,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn tempo_curve(mut self, end_factor: f32) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 || end_factor == 1.0 {
+        if pattern_duration <= Ticks::ZERO || end_factor == 1.0 {
             return self;
         }
 
@@ -2063,7 +2112,7 @@ impl<'a> TrackBuilder<'a> {
         let cursor = self.cursor;
 
         // Collect all events with their original relative times
-        let mut events_with_times: Vec<(usize, f32)> = Vec::new();
+        let mut events_with_times: Vec<(usize, Ticks)> = Vec::new();
         for (idx, event) in self.get_track_mut().events.iter().enumerate() {
             let start_time = match event {
                 AudioEvent::Note(n) => n.start_time,
@@ -2085,11 +2134,11 @@ impl<'a> TrackBuilder<'a> {
         // Each event's position gets scaled by lerp(1.0, end_factor, progress)
         for (idx, original_time) in events_with_times {
             let relative_time = original_time - pattern_start;
-            let progress = relative_time / pattern_duration; // 0.0 to 1.0
+            let progress = relative_time.0 as f32 / pattern_duration.0 as f32; // 0.0 to 1.0
 
             // Linear interpolation from 1.0 (start) to end_factor (end)
             let time_scale = 1.0 + (end_factor - 1.0) * progress;
-            let new_relative_time = relative_time * time_scale;
+            let new_relative_time = relative_time.scale(time_scale as f64);
             let new_time = pattern_start + new_relative_time;
 
             // Update event timing
@@ -2112,7 +2161,7 @@ impl<'a> TrackBuilder<'a> {
                 AudioEvent::Sample(s) if s.start_time >= pattern_start => Some(s.start_time),
                 _ => None,
             })
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
+            .max()
             .unwrap_or(cursor);
 
         self.cursor = new_end_time.max(pattern_start);
@@ -1,9 +1,11 @@
 use crate::synthesis::envelope::Envelope;
 use crate::instruments::Instrument;
 use crate::synthesis::sample::Sample;
-use crate::track::{Mixer, Track};
+use crate::track::{ExportOptions, LoopOptions, LoopSeam, Marker, Metadata, Mixer, Track};
 use crate::track::ids::{BusId, BusIdGenerator, TrackIdGenerator};
 use crate::synthesis::waveform::Waveform;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::collections::HashMap;
 
 // Import synthesis types - use prelude which re-exports them
@@ -16,6 +18,7 @@ use crate::synthesis::effects::{
 };
 use crate::synthesis::filter::Filter;
 use crate::synthesis::lfo::ModRoute;
+use crate::synthesis::mod_matrix::ModMatrix;
 
 // Module declarations
 pub mod drum_grid;
@@ -35,7 +38,7 @@ mod tuplets;
 // Re-export main types for public API
 pub use crate::instruments::drums::DrumType;
 pub use drum_grid::DrumGrid;
-pub use timing::Tempo;
+pub use timing::{Tempo, TempoCurve, TempoMap, Ticks};
 pub use sections::{Section, SectionBuilder};
 
 /// Template for reusing track settings across multiple tracks
@@ -61,7 +64,9 @@ pub struct TrackTemplate {
     pub tremolo: Option<Tremolo>,
     pub autopan: Option<AutoPan>,
     pub limiter: Option<Limiter>,
+    pub inserts: Vec<crate::synthesis::effects::EffectSlot>,
     pub modulation: Vec<ModRoute>,
+    pub mod_matrix: ModMatrix,
     pub midi_program: Option<u8>,
 
     // Builder-level settings (from TrackBuilder)
@@ -80,9 +85,14 @@ pub struct Composition {
     tracks: HashMap<String, Track>,
     pub(crate) sections: HashMap<String, sections::Section>,
     tempo: Tempo,
+    tempo_map: TempoMap,              // Beats-to-seconds conversion for every builder
     samples: HashMap<String, Sample>, // Cache of loaded samples
     markers: HashMap<String, f32>,    // Named time positions for easy navigation
+    arranged_sections: Vec<Marker>,   // Where arrange() placed each section
+    metadata: Metadata,               // Tags written into exported files
     templates: HashMap<String, TrackTemplate>, // Named track templates for reuse
+    seed: u64,                        // Seed behind every random choice made while building
+    rng: StdRng,                      // Generator all builder randomness draws from
 
     // ID generators and mappings for performance optimization
     bus_id_gen: BusIdGenerator,           // Generate unique bus IDs
@@ -103,13 +113,20 @@ impl Composition {
         bus_name_to_id.insert("default".to_string(), default_bus_id);
         bus_id_to_name.insert(default_bus_id, "default".to_string());
 
+        let seed = rand::random();
+
         Self {
             tracks: HashMap::new(),
             sections: HashMap::new(),
             tempo,
+            tempo_map: TempoMap::new(tempo.bpm),
             samples: HashMap::new(),
             markers: HashMap::new(),
+            arranged_sections: Vec::new(),
+            metadata: Metadata::default(),
             templates: HashMap::new(),
+            seed,
+            rng: StdRng::seed_from_u64(seed),
             bus_id_gen,
             track_id_gen: TrackIdGenerator::new(),
             bus_name_to_id,
@@ -156,8 +173,8 @@ impl Composition {
             context: BuilderContext::Direct,
             track_name: name.to_string(),
             bus_name: "default".to_string(),
-            cursor: 0.0,
-            pattern_start: 0.0,
+            cursor: Ticks::ZERO,
+            pattern_start: Ticks::ZERO,
             waveform: Waveform::Sine,      // Default to sine wave
             envelope: Envelope::default(), // Default envelope
             filter_envelope: FilterEnvelope::default(), // Default (no filter envelope)
@@ -225,7 +242,9 @@ impl Composition {
                     tremolo: None,
                     autopan: None,
                     limiter: None,
+                    inserts: Vec::new(),
                     modulation: Vec::new(),
+                    mod_matrix: ModMatrix::new(),
                     midi_program: None,
                     waveform: Waveform::Sine,
                     envelope: Envelope::default(),
@@ -244,8 +263,8 @@ impl Composition {
             context: BuilderContext::Direct,
             track_name: track_name.to_string(),
             bus_name: "default".to_string(),
-            cursor: 0.0,
-            pattern_start: 0.0,
+            cursor: Ticks::ZERO,
+            pattern_start: Ticks::ZERO,
             waveform: template.waveform,
             envelope: template.envelope,
             filter_envelope: template.filter_envelope,
@@ -281,7 +300,12 @@ impl Composition {
         track.effects.tremolo = template.tremolo;
         track.effects.autopan = template.autopan;
         track.effects.limiter = template.limiter;
+        for slot in template.inserts {
+            track.effects.add_slot(slot);
+        }
+        track.effects.compute_effect_order();
         track.modulation = template.modulation.clone();
+        track.mod_matrix = template.mod_matrix.clone();
         track.midi_program = template.midi_program;
 
         builder
@@ -295,8 +319,8 @@ impl Composition {
             context: BuilderContext::Direct,
             track_name: name.to_string(),
             bus_name: "default".to_string(),
-            cursor: 0.0,
-            pattern_start: 0.0,
+            cursor: Ticks::ZERO,
+            pattern_start: Ticks::ZERO,
             waveform: instrument.waveform,
             envelope: instrument.envelope,
             filter_envelope: FilterEnvelope::default(), // Default (no filter envelope)
@@ -320,6 +344,11 @@ impl Composition {
         track.effects.reverb = instrument.reverb.clone();
         track.effects.distortion = instrument.distortion.clone();
         track.modulation = instrument.modulation.clone();
+        track.unison = instrument.unison;
+        track.voicing = instrument.voicing;
+        track.fm_patch = instrument.fm_patch.clone();
+        track.physical_model = instrument.physical_model.clone();
+        track.resonator = instrument.resonator.clone();
 
         builder
     }
@@ -327,7 +356,13 @@ impl Composition {
     /// Convert this composition into a Mixer for playback
     pub fn into_mixer(self) -> Mixer {
         let mut mixer = Mixer::new(self.tempo);
-        for (name, mut track) in self.tracks {
+        mixer.set_tempo_map(self.tempo_map.clone());
+
+        // Add tracks in name order so bus layout and summing order are reproducible
+        let mut tracks: Vec<(String, Track)> = self.tracks.into_iter().collect();
+        tracks.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (name, mut track) in tracks {
             // Validate that all events in this track have the same spatial position (if any)
             Self::validate_track_spatial_positions(&name, &track);
 
@@ -344,6 +379,18 @@ impl Composition {
         // Phase 6: Resolve sidechain sources from string names to integer IDs
         mixer.resolve_sidechains();
 
+        // Carry markers and arranged sections into exported files
+        let mut metadata = self.metadata;
+        let mut markers: Vec<Marker> = self
+            .markers
+            .iter()
+            .map(|(name, &time)| Marker::new(name, time))
+            .collect();
+        markers.sort_by(|a, b| a.time.total_cmp(&b.time).then_with(|| a.name.cmp(&b.name)));
+        metadata.markers.extend(markers);
+        metadata.markers.extend(self.arranged_sections);
+        mixer.metadata = metadata;
+
         mixer
     }
 
@@ -465,7 +512,15 @@ impl Composition {
             .ok_or_else(|| crate::error::TunesError::SectionNotFound(section_name.to_string()))?;
 
         let mut mixer = Mixer::new(self.tempo);
-        for (track_name, track) in &section.tracks {
+        mixer.set_tempo_map(self.tempo_map.clone());
+        mixer.metadata = Metadata {
+            markers: Vec::new(),
+            ..self.metadata.clone()
+        };
+        let mut track_names: Vec<&String> = section.tracks.keys().collect();
+        track_names.sort();
+        for track_name in track_names {
+            let track = &section.tracks[track_name];
             let mut track_copy = track.clone();
             track_copy.name = Some(track_name.clone());
             mixer.add_track(track_copy);
@@ -539,11 +594,187 @@ impl Composition {
         mixer.export_wav(path, sample_rate)
     }
 
+    /// Export a specific section to a WAV file as a seamless loop
+    ///
+    /// Like [`export_section_wav`](Self::export_section_wav), but loops exactly the
+    /// section's length: reverb and delay tails past the end are folded back onto the
+    /// start, and a `smpl` chunk marks the loop points for samplers and game engines.
+    /// See [`Mixer::render_loop`] for details.
+    ///
+    /// Returns the seam measurement, so callers can check the join is click-free.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::prelude::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    ///
+    /// comp.section("battle")
+    ///     .instrument("strings", &Instrument::warm_pad())
+    ///     .reverb(Reverb::new(0.8, 0.4, 0.4))
+    ///     .notes(&[C3, G3, A3, F3], 1.0);
+    ///
+    /// let seam = comp.export_section_loop_wav("battle", "battle_loop.wav", 48000)?;
+    /// assert!(seam.is_click_free());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_section_loop_wav(
+        &self,
+        section_name: &str,
+        path: &str,
+        sample_rate: u32,
+    ) -> anyhow::Result<LoopSeam> {
+        let mut mixer = self
+            .section_to_mixer(section_name)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        let mut loop_options = LoopOptions::default();
+        if let Some(section) = self.sections.get(section_name) {
+            if section.duration > 0.0 {
+                loop_options = loop_options.with_length(section.duration);
+            }
+        }
+        mixer.export_loop_wav_with_options(
+            path,
+            sample_rate,
+            &ExportOptions::default(),
+            &loop_options,
+        )
+    }
+
+    /// Set the title, artist and other tags written into exported files
+    ///
+    /// Markers set with [`mark_at`](Self::mark_at) and sections placed with
+    /// [`arrange`](Self::arrange) are added to the metadata's markers when the
+    /// composition becomes a mixer.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # use tunes::track::Metadata;
+    /// let mut comp = Composition::new(Tempo::new(96.0));
+    /// comp.set_metadata(Metadata::default().with_title("Lullaby").with_artist("Nana"));
+    /// ```
+    pub fn set_metadata(&mut self, metadata: Metadata) {
+        self.metadata = metadata;
+    }
+
+    /// Get the tags written into exported files
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Seed every random choice made while building this composition
+    ///
+    /// Humanize, mutate, shuffle, thin, stutter, probability, random notes,
+    /// random walks, noise and granular grains all draw from one generator owned
+    /// by the composition. Building the same composition with the same seed
+    /// renders bit-identical audio, which makes golden-file tests possible and
+    /// lets you regenerate a take you liked.
+    ///
+    /// Call this before adding any tracks; choices already made are not redone.
+    /// Without it, each composition picks a fresh seed that [`seed`](Self::seed)
+    /// reports.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.set_seed(2024);
+    /// comp.track("hats")
+    ///     .pattern_start()
+    ///     .notes(&[C5; 16], 0.125)
+    ///     .humanize(0.01, 0.1)
+    ///     .thin(0.7);
+    /// ```
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Get the seed behind this composition's random choices
+    ///
+    /// Pass it to [`set_seed`](Self::set_seed) on a fresh composition to rebuild
+    /// the same take.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the composition's random generator
+    ///
+    /// Hand it to the `_with_rng` variants of free functions such as
+    /// [`random_walk_sequence_with_rng`](generative::random_walk_sequence_with_rng)
+    /// or `sequences::markov::generate_with_rng` so their output follows the
+    /// composition seed too.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Split off an independent generator for one builder call
+    pub(crate) fn fork_rng(&mut self) -> StdRng {
+        StdRng::seed_from_u64(self.rng.random())
+    }
+
     /// Get the tempo (returns a copy since Tempo is Copy)
+    ///
+    /// This is the tempo at the start of the song; see [`tempo_map`](Self::tempo_map)
+    /// for changes after that.
     pub fn tempo(&self) -> Tempo {
         self.tempo
     }
 
+    /// Get the tempo map that converts beats to seconds for every builder
+    pub fn tempo_map(&self) -> &TempoMap {
+        &self.tempo_map
+    }
+
+    /// Replace the tempo map and retime everything already written
+    ///
+    /// Every event, marker and section keeps its position in beats and moves
+    /// to wherever that beat now falls; note lengths stretch or shrink with the
+    /// tempo. Section contents are placed as though each section started at
+    /// the top of the song. Sample playback speed is left alone.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// let mut comp = Composition::new(Tempo::new(100.0));
+    /// comp.track("lead").quarters(&[C4, D4, E4, F4, G4, A4, B4, C5]);
+    ///
+    /// // Accelerate from 100 to 160 BPM across the second bar
+    /// let map = comp
+    ///     .tempo_map()
+    ///     .clone()
+    ///     .with_ramp(Ticks::from_beats(4.0), Ticks::from_beats(8.0), 160.0, TempoCurve::Linear);
+    /// comp.set_tempo_map(map);
+    /// ```
+    pub fn set_tempo_map(&mut self, tempo_map: TempoMap) {
+        let old = std::mem::replace(&mut self.tempo_map, tempo_map);
+        let new = &self.tempo_map;
+        let retime = |time: f32| new.seconds_at(old.ticks_at(time as f64)) as f32;
+
+        // Events are stored in ticks, so the tracks only need the new map
+        let section_tracks = self.sections.values_mut().flat_map(|s| s.tracks.values_mut());
+        for track in self.tracks.values_mut().chain(section_tracks) {
+            track.set_tempo_map(new.clone());
+        }
+
+        for section in self.sections.values_mut() {
+            section.duration = retime(section.duration);
+        }
+        for time in self.markers.values_mut() {
+            *time = retime(*time);
+        }
+        for marker in &mut self.arranged_sections {
+            if let Some(duration) = marker.duration.as_mut() {
+                *duration = retime(marker.time + *duration) - retime(marker.time);
+            }
+            marker.time = retime(marker.time);
+        }
+
+        self.tempo = Tempo::new(self.tempo_map.bpm_at(Ticks::ZERO));
+    }
+
     /// Start defining a reusable section
     ///
     /// Sections allow you to define reusable portions of music (verse, chorus, etc.)
@@ -610,11 +841,15 @@ impl Composition {
         for &section_name in section_names {
             if let Some(section) = self.sections.get(section_name) {
                 // Clone the section's tracks with time offset
-                let offset_tracks = section.clone_with_offset(current_time);
+                let offset = self.tempo_map.ticks_at(current_time as f64);
+                let offset_tracks = section.clone_with_offset(offset);
 
                 // Merge the offset tracks into the composition's tracks
                 for (track_name, offset_track) in offset_tracks {
-                    let comp_track = self.tracks.entry(track_name).or_default();
+                    let comp_track = self
+                        .tracks
+                        .entry(track_name)
+                        .or_insert_with(|| Self::new_track(&self.tempo_map));
 
                     // Append events from the section track
                     comp_track.events.extend(offset_track.events);
@@ -628,9 +863,16 @@ impl Composition {
                         comp_track.effects.reverb = offset_track.effects.reverb;
                         comp_track.effects.distortion = offset_track.effects.distortion;
                         comp_track.modulation = offset_track.modulation.clone();
+                        comp_track.mod_matrix = offset_track.mod_matrix.clone();
                     }
                 }
 
+                self.arranged_sections.push(Marker::region(
+                    section_name,
+                    current_time,
+                    section.duration,
+                ));
+
                 // Move forward in time by this section's duration
                 current_time += section.duration;
             }
@@ -649,7 +891,18 @@ impl Composition {
             .entry(section_name.to_string())
             .or_insert_with(|| sections::Section::new(section_name.to_string()));
 
-        section.tracks.entry(track_name.to_string()).or_default()
+        let tempo_map = &self.tempo_map;
+        section
+            .tracks
+            .entry(track_name.to_string())
+            .or_insert_with(|| Self::new_track(tempo_map))
+    }
+
+    /// Create an empty track that places its events with the given tempo map
+    fn new_track(tempo_map: &TempoMap) -> Track {
+        let mut track = Track::new();
+        track.set_tempo_map(tempo_map.clone());
+        track
     }
 
     // === MARKER MANAGEMENT ===
@@ -794,7 +1047,7 @@ mod marker_tests {
         let track = &mixer.tracks()[0];
 
         if let crate::track::AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 16.0);
+            assert_eq!(note.start_time, Ticks::from_seconds(16.0, 120.0));
         } else {
             panic!("Expected note event");
         }
@@ -814,7 +1067,7 @@ mod marker_tests {
         // All three tracks should have notes starting at 16.0
         for track in &mixer.tracks() {
             if let crate::track::AudioEvent::Note(note) = &track.events[0] {
-                assert_eq!(note.start_time, 16.0);
+                assert_eq!(note.start_time, Ticks::from_seconds(16.0, 120.0));
             }
         }
     }
@@ -857,7 +1110,7 @@ mod marker_tests {
         let mixer = comp.into_mixer();
 
         // Both should start at the same time
-        let times: Vec<f32> = mixer.tracks()
+        let times: Vec<Ticks> = mixer.tracks()
             .iter()
             .filter_map(|track| {
                 if let Some(crate::track::AudioEvent::Note(note)) = track.events.first() {
@@ -869,8 +1122,8 @@ mod marker_tests {
             .collect();
 
         assert_eq!(times.len(), 2);
-        assert_eq!(times[0], 16.0);
-        assert_eq!(times[1], 16.0);
+        assert_eq!(times[0], Ticks::from_seconds(16.0, 120.0));
+        assert_eq!(times[1], Ticks::from_seconds(16.0, 120.0));
     }
 }
 
@@ -892,8 +1145,8 @@ pub struct TrackBuilder<'a> {
     pub(crate) context: BuilderContext,
     pub(crate) track_name: String,
     pub(crate) bus_name: String,   // Which bus this track belongs to
-    pub(crate) cursor: f32,        // Current time position in the track
-    pub(crate) pattern_start: f32, // Start position of current pattern (for looping)
+    pub(crate) cursor: Ticks,        // Current time position in the track
+    pub(crate) pattern_start: Ticks, // Start position of current pattern (for looping)
     pub(crate) waveform: Waveform, // Current waveform for notes
     pub(crate) envelope: Envelope, // Current envelope for notes
     pub(crate) filter_envelope: FilterEnvelope, // Current filter envelope for notes
@@ -917,27 +1170,74 @@ impl<'a> TrackBuilder<'a> {
         // If the track already exists with an ID, we'll waste this ID value,
         // but that's acceptable for simplicity and avoiding borrow conflicts
         let new_id = self.composition.track_id_gen.next_id();
+        let seed = self.composition.seed;
 
         // Get or create the track
         let track = match &self.context {
-            BuilderContext::Direct => self
-                .composition
-                .tracks
-                .entry(self.track_name.clone())
-                .or_default(),
+            BuilderContext::Direct => {
+                let tempo_map = &self.composition.tempo_map;
+                self.composition
+                    .tracks
+                    .entry(self.track_name.clone())
+                    .or_insert_with(|| Composition::new_track(tempo_map))
+            }
             BuilderContext::Section(section_name) => self
                 .composition
                 .get_or_create_section_track(section_name, &self.track_name),
         };
 
-        // Assign the ID if this track doesn't have one yet (id == 0 means unassigned)
+        // Assign the ID if this track doesn't have one yet (id == 0 means unassigned),
+        // and seed the track's noise from the composition seed
         if track.id == 0 {
             track.id = new_id;
+            track.set_seed(seed.wrapping_add(new_id as u64));
         }
 
         track
     }
 
+    /// Convert a tick position on this builder's timeline to seconds
+    pub(crate) fn to_seconds(&self, ticks: Ticks) -> f32 {
+        self.composition.tempo_map.seconds_at(ticks) as f32
+    }
+
+    /// Convert seconds to the nearest tick on this builder's timeline
+    pub(crate) fn to_ticks(&self, seconds: f32) -> Ticks {
+        self.composition.tempo_map.ticks_at(seconds as f64)
+    }
+
+    /// Ticks spanned by `seconds` starting at the cursor, following the tempo map
+    pub(crate) fn span(&self, seconds: f32) -> Ticks {
+        self.span_at(self.cursor, seconds)
+    }
+
+    /// Ticks spanned by `seconds` starting at `start`, following the tempo map
+    pub(crate) fn span_at(&self, start: Ticks, seconds: f32) -> Ticks {
+        let map = &self.composition.tempo_map;
+        map.ticks_at(map.seconds_at(start) + seconds as f64) - start
+    }
+
+    /// Move the cursor forward by a duration in seconds
+    ///
+    /// The new position is rounded to whole ticks, so long runs of notes and
+    /// waits stay on the beat grid instead of accumulating `f32` rounding error.
+    pub(crate) fn advance(&mut self, seconds: f32) {
+        let map = &self.composition.tempo_map;
+        self.cursor = map.ticks_at(map.seconds_at(self.cursor) + seconds as f64);
+    }
+
+    /// Seconds spanned by `beats` starting at the cursor, following the tempo map
+    pub(crate) fn beat_seconds(&self, beats: f64) -> f32 {
+        let map = &self.composition.tempo_map;
+        let end = self.cursor + Ticks::from_beats(beats);
+        (map.seconds_at(end) - map.seconds_at(self.cursor)) as f32
+    }
+
+    /// Get a generator for this call's random choices, drawn from the composition seed
+    pub(crate) fn rng(&mut self) -> StdRng {
+        self.composition.fork_rng()
+    }
+
     /// Assign this track to a specific bus
     ///
     /// Buses allow you to group tracks together and apply shared effects.
@@ -988,9 +1288,10 @@ impl<'a> TrackBuilder<'a> {
 
     /// Update section duration if in section context
     pub(crate) fn update_section_duration(&mut self) {
+        let cursor = self.peek_cursor();
         if let BuilderContext::Section(section_name) = &self.context {
             if let Some(section) = self.composition.sections.get_mut(section_name) {
-                section.duration = section.duration.max(self.cursor);
+                section.duration = section.duration.max(cursor);
             }
         }
     }
@@ -1059,7 +1360,9 @@ impl<'a> TrackBuilder<'a> {
         let tremolo = track.effects.tremolo.clone();
         let autopan = track.effects.autopan.clone();
         let limiter = track.effects.limiter.clone();
+        let inserts = track.effects.inserts().to_vec();
         let modulation = track.modulation.clone();
+        let mod_matrix = track.mod_matrix.clone();
         let midi_program = track.midi_program;
 
         let template = TrackTemplate {
@@ -1083,7 +1386,9 @@ impl<'a> TrackBuilder<'a> {
             tremolo,
             autopan,
             limiter,
+            inserts,
             modulation,
+            mod_matrix,
             midi_program,
 
             // Builder-level settings
@@ -1127,11 +1432,13 @@ impl<'a> TrackBuilder<'a> {
 
         // Get the track reference directly from composition for lifetime 'a
         let track = match &self.context {
-            BuilderContext::Direct => self
-                .composition
-                .tracks
-                .entry(self.track_name.clone())
-                .or_default(),
+            BuilderContext::Direct => {
+                let tempo_map = &self.composition.tempo_map;
+                self.composition
+                    .tracks
+                    .entry(self.track_name.clone())
+                    .or_insert_with(|| Composition::new_track(tempo_map))
+            }
             BuilderContext::Section(section_name) => self
                 .composition
                 .get_or_create_section_track(section_name, &self.track_name),
@@ -1181,6 +1488,7 @@ impl<'a> TrackBuilder<'a> {
 mod tests {
     use super::*;
     use crate::consts::notes::{C4, E4, G4};
+    use crate::track::AudioEvent;
 
     #[test]
     fn test_save_and_use_template() {
@@ -1428,4 +1736,177 @@ mod tests {
         let mixer = comp.into_mixer();
         assert_eq!(mixer.tracks().len(), 2);
     }
+
+    #[test]
+    fn test_markers_and_sections_reach_mixer_metadata() {
+        let mut comp = Composition::new(Tempo::new(120.0));
+        comp.section("verse").track("lead").note(&[C4], 2.0);
+        comp.section("chorus").track("lead").note(&[E4], 1.0);
+        comp.arrange(&["verse", "chorus"]);
+        comp.mark_at("drop", 2.5);
+        comp.set_metadata(Metadata::default().with_title("Song"));
+
+        let mixer = comp.into_mixer();
+        let markers = &mixer.metadata.markers;
+        assert_eq!(mixer.metadata.title.as_deref(), Some("Song"));
+        assert_eq!(markers.len(), 3);
+        assert!(markers.contains(&Marker::new("drop", 2.5)));
+        assert!(markers.contains(&Marker::region("verse", 0.0, 2.0)));
+        assert!(markers.contains(&Marker::region("chorus", 2.0, 1.0)));
+    }
+
+    #[test]
+    fn test_section_loop_export_matches_section_length() {
+        let mut comp = Composition::new(Tempo::new(120.0));
+        comp.section("loop")
+            .track("lead")
+            .reverb(Reverb::new(0.6, 0.5, 0.4))
+            .notes(&[C4, E4], 0.25);
+
+        let path = "test_section_loop.wav";
+        let seam = comp.export_section_loop_wav("loop", path, 44100).unwrap();
+        let frames = hound::WavReader::open(path).unwrap().duration();
+        std::fs::remove_file(path).ok();
+
+        assert!(seam.is_click_free());
+        assert_eq!(frames, 22050);
+        assert!(comp.export_section_loop_wav("missing", path, 44100).is_err());
+    }
+
+    fn seeded_take(seed: u64) -> Vec<f32> {
+        use crate::synthesis::noise::NoiseType;
+
+        let mut comp = Composition::new(Tempo::new(120.0));
+        comp.set_seed(seed);
+        comp.track("lead")
+            .pattern_start()
+            .notes(&[C4, E4, G4, C4, E4, G4, C4, E4], 0.125)
+            .humanize(0.02, 0.2)
+            .mutate(2)
+            .shuffle()
+            .stutter(0.3, 2)
+            .thin(0.8);
+        comp.track("sparkle")
+            .pattern_start()
+            .random_notes(&[C4, E4, G4], 8, 0.125)
+            .probability(0.7);
+        comp.track("air").noise(NoiseType::Pink, 0.5, 0.2);
+        comp.track("snare")
+            .waveform(Waveform::noise())
+            .notes(&[C4, C4, C4, C4], 0.125);
+        comp.into_mixer().render_to_buffer(22050.0)
+    }
+
+    #[test]
+    fn test_same_seed_renders_bit_identically() {
+        let first = seeded_take(7);
+        let second = seeded_take(7);
+        assert!(!first.is_empty());
+        assert!(first.iter().zip(&second).all(|(a, b)| a.to_bits() == b.to_bits()));
+        assert_eq!(first.len(), second.len());
+        assert_ne!(first, seeded_take(8));
+    }
+
+    #[test]
+    fn test_seed_is_reported_and_drives_free_functions() {
+        use crate::composition::generative::random_walk_sequence_with_rng;
+
+        let mut comp = Composition::new(Tempo::new(120.0));
+        comp.set_seed(99);
+        assert_eq!(comp.seed(), 99);
+        let walk = random_walk_sequence_with_rng(3, 32, 0, 7, comp.rng());
+
+        let mut again = Composition::new(Tempo::new(120.0));
+        again.set_seed(comp.seed());
+        assert_eq!(walk, random_walk_sequence_with_rng(3, 32, 0, 7, again.rng()));
+    }
+
+    #[test]
+    fn test_generators_follow_the_composition_rng() {
+        use crate::synthesis::karplus_strong::KarplusStrong;
+        use crate::synthesis::noise::{NoiseType, WhiteNoise};
+
+        let take = |seed: u64| {
+            let mut comp = Composition::new(Tempo::new(120.0));
+            comp.set_seed(seed);
+            let mut samples = NoiseType::Brown.generate_with_rng(256, comp.rng());
+            samples.extend(WhiteNoise::with_rng(comp.rng()).generate(256));
+            samples.extend(KarplusStrong::with_rng(440.0, 44100.0, comp.rng()).generate(256));
+            samples
+        };
+
+        assert_eq!(take(3), take(3));
+        assert_ne!(take(3), take(4));
+    }
+
+    #[test]
+    fn test_noise_notes_follow_the_seed() {
+        let noise_hits = |seed: u64| -> Vec<Waveform> {
+            let mut comp = Composition::new(Tempo::new(120.0));
+            comp.set_seed(seed);
+            comp.track("snare")
+                .waveform(Waveform::noise())
+                .notes(&[C4, C4, C4], 0.25);
+            comp.tracks["snare"]
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    AudioEvent::Note(note) => Some(note.waveform),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // Every hit plays different noise, and the same seed brings the same hits back
+        let hits = noise_hits(5);
+        assert_ne!(hits[0], hits[1]);
+        assert_ne!(hits[1], hits[2]);
+        assert_eq!(hits, noise_hits(5));
+        assert_ne!(hits, noise_hits(6));
+    }
+
+    fn note_starts(comp: &Composition, track: &str) -> Vec<f32> {
+        comp.tracks[track]
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                AudioEvent::Note(note) => Some(comp.tempo_map.seconds_at(note.start_time) as f32),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quarters_accelerate_through_a_ramp() {
+        let mut comp = Composition::new(Tempo::new(60.0));
+        comp.set_tempo_map(TempoMap::new(60.0).with_ramp(
+            Ticks::ZERO,
+            Ticks::from_beats(4.0),
+            120.0,
+            TempoCurve::Linear,
+        ));
+        comp.track("lead").quarters(&[C4, E4, G4, E4, C4]);
+
+        let starts = note_starts(&comp, "lead");
+        let gaps: Vec<f32> = starts.windows(2).map(|w| w[1] - w[0]).collect();
+        assert!(gaps.windows(2).all(|w| w[1] < w[0]));
+        // 60 -> 120 BPM over four beats takes 4 ln 2 seconds rather than 3
+        assert!((starts[4] - 4.0 * std::f32::consts::LN_2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_set_tempo_map_keeps_events_on_their_beats() {
+        let mut comp = Composition::new(Tempo::new(120.0));
+        comp.track("lead").quarters(&[C4, E4, G4, C4]).wait(0.5).mark("end");
+        comp.set_tempo_map(TempoMap::new(120.0).with_tempo(Ticks::from_beats(2.0), 60.0));
+
+        assert_eq!(note_starts(&comp, "lead"), vec![0.0, 0.5, 1.0, 2.0]);
+        let last = comp.tracks["lead"].events.iter().rev().find_map(|event| match event {
+            AudioEvent::Note(note) => Some(note.duration),
+            _ => None,
+        });
+        assert_eq!(last, Some(Ticks::from_beats(1.0)));
+        assert_eq!(comp.markers["end"], 4.0);
+        assert_eq!(comp.tempo().bpm, 120.0);
+    }
 }
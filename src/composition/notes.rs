@@ -5,6 +5,7 @@ impl<'a> TrackBuilder<'a> {
     /// Add a note or chord at the current cursor position
     pub fn note(mut self, frequencies: &[f32], duration: f32) -> Self {
         let cursor = self.cursor;
+        let length = self.span(duration);
         let waveform = self.waveform;
         let envelope = self.envelope;
         let filter_envelope = self.filter_envelope;
@@ -14,10 +15,10 @@ impl<'a> TrackBuilder<'a> {
         let velocity = self.velocity;
         let spatial_position = self.spatial_position;
 
-        self.get_track_mut().add_note_with_complete_params(
+        self.get_track_mut().add_note_with_complete_params_ticks(
             frequencies,
             cursor,
-            duration,
+            length,
             waveform,
             envelope,
             filter_envelope,
@@ -28,7 +29,7 @@ impl<'a> TrackBuilder<'a> {
             spatial_position,
         );
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self.update_section_duration();
         self
     }
@@ -37,10 +38,10 @@ impl<'a> TrackBuilder<'a> {
     pub fn drum(mut self, drum_type: DrumType) -> Self {
         let cursor = self.cursor;
         let spatial_position = self.spatial_position;
-        self.get_track_mut().add_drum(drum_type, cursor, spatial_position);
+        self.get_track_mut().add_drum_ticks(drum_type, cursor, spatial_position);
         let base_duration = drum_type.duration();
         let swung_duration = self.apply_swing(base_duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self.update_section_duration();
         self
     }
@@ -91,7 +92,7 @@ impl<'a> TrackBuilder<'a> {
         self.get_track_mut().invalidate_time_cache();
 
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self.update_section_duration();
         self
     }
@@ -137,7 +138,7 @@ impl<'a> TrackBuilder<'a> {
         self.get_track_mut().invalidate_time_cache();
 
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self.update_section_duration();
         self
     }
@@ -221,7 +222,7 @@ impl<'a> TrackBuilder<'a> {
         self.get_track_mut().invalidate_time_cache();
 
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self.update_section_duration();
         self
     }
@@ -245,14 +246,15 @@ impl<'a> TrackBuilder<'a> {
         if segments == 1 {
             // Just play the start frequency
             let cursor = self.cursor;
-            self.get_track_mut().add_note_with_waveform_and_envelope(
+            let length = self.span(note_duration);
+            self.get_track_mut().add_note_with_waveform_and_envelope_ticks(
                 &[start_freq],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
             );
-            self.cursor += note_duration;
+            self.advance(note_duration);
             self.update_section_duration();
             return self;
         }
@@ -261,14 +263,15 @@ impl<'a> TrackBuilder<'a> {
             let t = i as f32 / (segments - 1) as f32;
             let freq = start_freq + (end_freq - start_freq) * t;
             let cursor = self.cursor;
-            self.get_track_mut().add_note_with_waveform_and_envelope(
+            let length = self.span(note_duration);
+            self.get_track_mut().add_note_with_waveform_and_envelope_ticks(
                 &[freq],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
             );
-            self.cursor += note_duration;
+            self.advance(note_duration);
         }
         self.update_section_duration();
         self
@@ -287,10 +290,11 @@ impl<'a> TrackBuilder<'a> {
 
         for &freq in frequencies {
             let cursor = self.cursor;
-            self.get_track_mut().add_note_with_complete_params(
+            let length = self.span(note_duration);
+            self.get_track_mut().add_note_with_complete_params_ticks(
                 &[freq],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
                 filter_envelope,
@@ -301,7 +305,7 @@ impl<'a> TrackBuilder<'a> {
                 spatial_position,
             );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self.update_section_duration();
         self
@@ -314,6 +318,7 @@ mod tests {
     use crate::composition::Composition;
     use crate::consts::notes::*;
     use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
     use crate::track::AudioEvent;
 
     #[test]
@@ -326,8 +331,8 @@ mod tests {
 
         if let AudioEvent::Note(note) = &track.events[0] {
             assert_eq!(note.frequencies[0], 440.0);
-            assert_eq!(note.start_time, 0.0);
-            assert_eq!(note.duration, 1.0);
+            assert_eq!(note.start_time, at(0.0));
+            assert_eq!(note.duration, at(1.0));
         } else {
             panic!("Expected NoteEvent");
         }
@@ -339,7 +344,7 @@ mod tests {
         let builder = comp.track("test").note(&[440.0], 1.0);
 
         // Cursor should have advanced by the note duration
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     #[test]
@@ -355,13 +360,13 @@ mod tests {
 
         // Verify timing
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.start_time, 1.0);
+            assert_eq!(note.start_time, at(1.0));
         }
     }
 
@@ -391,7 +396,7 @@ mod tests {
 
         if let AudioEvent::Drum(drum) = &track.events[0] {
             assert!(matches!(drum.drum_type, DrumType::Kick));
-            assert_eq!(drum.start_time, 0.0);
+            assert_eq!(drum.start_time, at(0.0));
         } else {
             panic!("Expected DrumEvent");
         }
@@ -403,7 +408,7 @@ mod tests {
         let builder = comp.track("drums").drum(DrumType::Kick);
 
         // Cursor should advance by kick duration (0.15s)
-        assert_eq!(builder.cursor, 0.15);
+        assert_eq!(builder.peek_cursor(), 0.15);
     }
 
     #[test]
@@ -442,7 +447,7 @@ mod tests {
         for (i, expected) in expected_freqs.iter().enumerate() {
             if let AudioEvent::Note(note) = &track.events[i] {
                 assert_eq!(note.frequencies[0], *expected);
-                assert_eq!(note.start_time, i as f32 * 0.1);
+                assert_eq!(note.start_time, at(i as f64 * 0.1));
             }
         }
     }
@@ -453,7 +458,7 @@ mod tests {
         let builder = comp.track("melody").interpolated(440.0, 880.0, 0, 0.1);
 
         // Check cursor first before moving comp
-        assert_eq!(builder.cursor, 0.0, "Cursor should not advance");
+        assert_eq!(builder.peek_cursor(), 0.0, "Cursor should not advance");
 
         let mixer = comp.into_mixer();
         // With zero segments, no track is created since interpolated returns early
@@ -474,7 +479,7 @@ mod tests {
 
         if let AudioEvent::Note(note) = &track.events[0] {
             assert_eq!(note.frequencies[0], 440.0); // Should use start freq
-            assert_eq!(note.duration, 0.5);
+            assert_eq!(note.duration, at(0.5));
         }
     }
 
@@ -490,8 +495,8 @@ mod tests {
         for (i, &expected_freq) in freqs.iter().enumerate() {
             if let AudioEvent::Note(note) = &track.events[i] {
                 assert_eq!(note.frequencies[0], expected_freq);
-                assert_eq!(note.start_time, i as f32 * 0.25);
-                assert_eq!(note.duration, 0.25);
+                assert_eq!(note.start_time, at(i as f64 * 0.25));
+                assert_eq!(note.duration, at(0.25));
             }
         }
     }
@@ -503,7 +508,7 @@ mod tests {
 
         // Check cursor first before moving comp
         assert_eq!(
-            builder.cursor, 0.0,
+            builder.peek_cursor(), 0.0,
             "Cursor should not advance for empty array"
         );
 
@@ -519,7 +524,7 @@ mod tests {
         let builder = comp.track("melody").notes(&freqs, 0.5);
 
         // Cursor should advance by num_notes * duration
-        assert_eq!(builder.cursor, 1.5); // 3 notes * 0.5s
+        assert_eq!(builder.peek_cursor(), 1.5); // 3 notes * 0.5s
     }
 
     #[test]
@@ -21,17 +21,18 @@ impl<'a> TrackBuilder<'a> {
         for i in 0..count {
             let freq = if i % 2 == 0 { note1 } else { note2 };
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self.update_section_duration();
         self
@@ -58,24 +59,26 @@ impl<'a> TrackBuilder<'a> {
     ///     .cascade(C4_MAJOR, 2.0, 0.1);  // Notes start 0.1s apart but sustain 2s each
     /// ```
     pub fn cascade(mut self, notes: &[f32], note_duration: f32, stagger: f32) -> Self {
-        let start_cursor = self.cursor;
+        let start_cursor = self.peek_cursor();
         let waveform = self.waveform;
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
 
         for (i, &freq) in notes.iter().enumerate() {
+            let start = self.to_ticks(start_cursor + (i as f32 * stagger));
+            let length = self.span_at(start, note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
-                    start_cursor + (i as f32 * stagger),
-                    note_duration,
+                    start,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
         }
         // Move cursor to the end of the cascade (last note start + its duration)
-        self.cursor = start_cursor + ((notes.len() - 1) as f32 * stagger) + note_duration;
+        self.cursor = self.to_ticks(start_cursor + ((notes.len() - 1) as f32 * stagger) + note_duration);
         self.update_section_duration();
         self
     }
@@ -101,17 +104,18 @@ impl<'a> TrackBuilder<'a> {
 
         for _ in 0..count {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[note],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self.update_section_duration();
         self
@@ -171,29 +175,31 @@ impl<'a> TrackBuilder<'a> {
 
         // Play grace note
         let cursor = self.cursor;
+        let length = self.span(grace_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[grace_note],
                 cursor,
-                grace_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += grace_duration;
+        self.advance(grace_duration);
 
         // Play main note
         let cursor = self.cursor;
+        let length = self.span(main_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[main_note],
                 cursor,
-                main_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += main_duration;
+        self.advance(main_duration);
 
         self.update_section_duration();
         self
@@ -230,40 +236,43 @@ impl<'a> TrackBuilder<'a> {
 
         // Main → Upper → Main
         let cursor = self.cursor;
+        let length = self.span(note_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[main_note],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += note_duration;
+        self.advance(note_duration);
 
         let cursor = self.cursor;
+        let length = self.span(note_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[upper_note],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += note_duration;
+        self.advance(note_duration);
 
         let cursor = self.cursor;
+        let length = self.span(note_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[main_note],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += note_duration;
+        self.advance(note_duration);
 
         self.update_section_duration();
         self
@@ -300,40 +309,43 @@ impl<'a> TrackBuilder<'a> {
 
         // Main → Lower → Main
         let cursor = self.cursor;
+        let length = self.span(note_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[main_note],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += note_duration;
+        self.advance(note_duration);
 
         let cursor = self.cursor;
+        let length = self.span(note_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[lower_note],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += note_duration;
+        self.advance(note_duration);
 
         let cursor = self.cursor;
+        let length = self.span(note_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[main_note],
                 cursor,
-                note_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += note_duration;
+        self.advance(note_duration);
 
         self.update_section_duration();
         self
@@ -374,16 +386,17 @@ impl<'a> TrackBuilder<'a> {
 
         for &note in &notes {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[note],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += note_duration;
+            self.advance(note_duration);
         }
 
         self.update_section_duration();
@@ -425,16 +438,17 @@ impl<'a> TrackBuilder<'a> {
 
         for &note in &notes {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[note],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += note_duration;
+            self.advance(note_duration);
         }
 
         self.update_section_duration();
@@ -447,6 +461,7 @@ mod tests {
     use crate::composition::Composition;
     use crate::consts::notes::*;
     use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
     use crate::track::AudioEvent;
 
     #[test]
@@ -478,7 +493,7 @@ mod tests {
         let builder = comp.track("trill").trill(C4, D4, 4, 0.25);
 
         // 4 notes * 0.25 = 1.0
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     #[test]
@@ -502,16 +517,16 @@ mod tests {
 
         // Notes should start at 0.0, 0.1, 0.2 but all sustain 2.0s
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
-            assert_eq!(note.duration, 2.0);
+            assert_eq!(note.start_time, at(0.0));
+            assert_eq!(note.duration, at(2.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.1);
-            assert_eq!(note.duration, 2.0);
+            assert_eq!(note.start_time, at(0.1));
+            assert_eq!(note.duration, at(2.0));
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.start_time, 0.2);
-            assert_eq!(note.duration, 2.0);
+            assert_eq!(note.start_time, at(0.2));
+            assert_eq!(note.duration, at(2.0));
         }
     }
 
@@ -521,7 +536,7 @@ mod tests {
         let builder = comp.track("cascade").cascade(&[C4, E4, G4], 2.0, 0.1);
 
         // Last note starts at 0.2 (2 staggers of 0.1) + duration 2.0 = 2.2
-        assert_eq!(builder.cursor, 2.2);
+        assert_eq!(builder.peek_cursor(), 2.2);
     }
 
     #[test]
@@ -546,7 +561,7 @@ mod tests {
         let builder = comp.track("tremolo").tremolo_note(C4, 10, 0.1);
 
         // 10 notes * 0.1 = 1.0 (with floating point tolerance)
-        assert!((builder.cursor - 1.0).abs() < 0.01);
+        assert!((builder.peek_cursor() - 1.0).abs() < 0.01);
     }
 
     #[test]
@@ -560,10 +575,10 @@ mod tests {
 
         // Should behave like cascade with small stagger
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.05);
+            assert_eq!(note.start_time, at(0.05));
         }
     }
 
@@ -578,15 +593,15 @@ mod tests {
         // Grace note first
         if let AudioEvent::Note(note) = &track.events[0] {
             assert_eq!(note.frequencies[0], B3);
-            assert_eq!(note.start_time, 0.0);
-            assert_eq!(note.duration, 0.05);
+            assert_eq!(note.start_time, at(0.0));
+            assert_eq!(note.duration, at(0.05));
         }
 
         // Main note second
         if let AudioEvent::Note(note) = &track.events[1] {
             assert_eq!(note.frequencies[0], C4);
-            assert_eq!(note.start_time, 0.05);
-            assert_eq!(note.duration, 0.5);
+            assert_eq!(note.start_time, at(0.05));
+            assert_eq!(note.duration, at(0.5));
         }
     }
 
@@ -596,7 +611,7 @@ mod tests {
         let builder = comp.track("grace").grace(C4, B3, 0.1, 0.9);
 
         // 0.1 + 0.9 = 1.0
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     #[test]
@@ -610,13 +625,13 @@ mod tests {
         // Pattern: Main → Upper → Main
         if let AudioEvent::Note(note) = &track.events[0] {
             assert_eq!(note.frequencies[0], C4); // Main
-            assert_eq!(note.duration, 0.1); // 0.3 / 3
+            assert_eq!(note.duration, at(0.1)); // 0.3 / 3
         }
         if let AudioEvent::Note(note) = &track.events[1] {
             // Upper (whole step up = 2 semitones)
             let expected_upper = C4 * 2.0f32.powf(2.0 / 12.0);
             assert!((note.frequencies[0] - expected_upper).abs() < 0.1);
-            assert_eq!(note.duration, 0.1);
+            assert_eq!(note.duration, at(0.1));
         }
         if let AudioEvent::Note(note) = &track.events[2] {
             assert_eq!(note.frequencies[0], C4); // Main
@@ -629,7 +644,7 @@ mod tests {
         let builder = comp.track("mord").mordent(C4, 0.0);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -668,7 +683,7 @@ mod tests {
 
         if let AudioEvent::Note(note) = &track.events[0] {
             assert!((note.frequencies[0] - upper).abs() < 0.1);
-            assert_eq!(note.duration, 0.1); // 0.4 / 4
+            assert_eq!(note.duration, at(0.1)); // 0.4 / 4
         }
         if let AudioEvent::Note(note) = &track.events[1] {
             assert_eq!(note.frequencies[0], C4);
@@ -726,7 +741,7 @@ mod tests {
         let builder = comp.track("neg").turn(C4, -0.1);
 
         // Should be no-op due to guard
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -735,6 +750,6 @@ mod tests {
         let builder = comp.track("mord").mordent(C4, 0.6);
 
         // 0.6 total duration
-        assert_eq!(builder.cursor, 0.6);
+        assert_eq!(builder.peek_cursor(), 0.6);
     }
 }
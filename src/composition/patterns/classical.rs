@@ -36,17 +36,18 @@ impl<'a> TrackBuilder<'a> {
 
         for &index in &pattern {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[chord[index]],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self.update_section_duration();
@@ -81,30 +82,32 @@ impl<'a> TrackBuilder<'a> {
 
         // Beat 1: Root note alone
         let cursor = self.cursor;
+        let length = self.span(beat_duration);
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[root],
                 cursor,
-                beat_duration,
+                length,
                 waveform,
                 envelope,
                 pitch_bend,
             );
-        self.cursor += beat_duration;
+        self.advance(beat_duration);
 
         // Beats 2 and 3: Chord
         for _ in 0..2 {
             let cursor = self.cursor;
+            let length = self.span(beat_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     chord,
                     cursor,
-                    beat_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += beat_duration;
+            self.advance(beat_duration);
         }
 
         self.update_section_duration();
@@ -147,29 +150,31 @@ impl<'a> TrackBuilder<'a> {
         for _ in 0..(measures * 2) {
             // Beats 1 & 3: Bass note
             let cursor = self.cursor;
+            let length = self.span(beat_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[root],
                     cursor,
-                    beat_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += beat_duration;
+            self.advance(beat_duration);
 
             // Beats 2 & 4: Chord
             let cursor = self.cursor;
+            let length = self.span(beat_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     chord,
                     cursor,
-                    beat_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += beat_duration;
+            self.advance(beat_duration);
         }
 
         self.update_section_duration();
@@ -242,17 +247,18 @@ impl<'a> TrackBuilder<'a> {
         for index in pattern {
             if index < chord.len() {
                 let cursor = self.cursor;
+                let length = self.span(note_duration);
                 self.get_track_mut()
-                    .add_note_with_waveform_envelope_and_bend(
+                    .add_note_with_waveform_envelope_and_bend_ticks(
                         &[chord[index]],
                         cursor,
-                        note_duration,
+                        length,
                         waveform,
                         envelope,
                         pitch_bend,
                     );
                 let swung_duration = self.apply_swing(note_duration);
-                self.cursor += swung_duration;
+                self.advance(swung_duration);
             }
         }
 
@@ -312,11 +318,12 @@ impl<'a> TrackBuilder<'a> {
 
         for _ in 0..repetitions {
             let cursor = self.cursor;
+            let length = self.span(note_speed);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
-                    notes, cursor, note_speed, waveform, envelope, pitch_bend,
+                .add_note_with_waveform_envelope_and_bend_ticks(
+                    notes, cursor, length, waveform, envelope, pitch_bend,
                 );
-            self.cursor += note_speed;
+            self.advance(note_speed);
         }
 
         self.update_section_duration();
@@ -345,17 +352,18 @@ impl<'a> TrackBuilder<'a> {
         for _ in 0..repeats {
             for &freq in pattern {
                 let cursor = self.cursor;
+                let length = self.span(note_duration);
                 self.get_track_mut()
-                    .add_note_with_waveform_envelope_and_bend(
+                    .add_note_with_waveform_envelope_and_bend_ticks(
                         &[freq],
                         cursor,
-                        note_duration,
+                        length,
                         waveform,
                         envelope,
                         pitch_bend,
                     );
                 let swung_duration = self.apply_swing(note_duration);
-                self.cursor += swung_duration;
+                self.advance(swung_duration);
             }
         }
 
@@ -396,14 +404,14 @@ impl<'a> TrackBuilder<'a> {
         chord_duration: f32,
     ) -> Self {
         let start_cursor = self.cursor;
-        let total_duration = chord_sequence.len() as f32 * chord_duration;
+        let total_duration = self.span(chord_sequence.len() as f32 * chord_duration);
         let waveform = self.waveform;
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
 
         // Add sustained pedal note for entire duration
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[pedal_note],
                 start_cursor,
                 total_duration,
@@ -415,16 +423,17 @@ impl<'a> TrackBuilder<'a> {
         // Add chord progression above the pedal
         for chord in chord_sequence {
             let cursor = self.cursor;
+            let length = self.span(chord_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     chord.as_slice(),
                     cursor,
-                    chord_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += chord_duration;
+            self.advance(chord_duration);
         }
 
         self.update_section_duration();
@@ -437,6 +446,7 @@ mod tests {
     use crate::composition::Composition;
     use crate::consts::notes::*;
     use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
     use crate::track::AudioEvent;
 
     #[test]
@@ -478,7 +488,7 @@ mod tests {
         let builder = comp.track("test").alberti_bass(&[C3, E3, G3], 0.125);
 
         // 4 notes * 0.125 = 0.5
-        assert!((builder.cursor - 0.5).abs() < 0.01);
+        assert!((builder.peek_cursor() - 0.5).abs() < 0.01);
     }
 
     #[test]
@@ -520,13 +530,13 @@ mod tests {
 
         // Verify timing: 0.0, 0.5, 1.0
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert!((note.start_time - 0.5).abs() < 0.01);
+            assert!((note.start_time - at(0.5)).0.abs() < at(0.01).0);
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert!((note.start_time - 1.0).abs() < 0.01);
+            assert!((note.start_time - at(1.0)).0.abs() < at(0.01).0);
         }
     }
 
@@ -565,7 +575,7 @@ mod tests {
         let builder = comp.track("test").stride_bass(C2, &[C3, E3], 0.25, 2);
 
         // Total time: 8 * 0.25 = 2.0
-        assert!((builder.cursor - 2.0).abs() < 0.01);
+        assert!((builder.peek_cursor() - 2.0).abs() < 0.01);
 
         let track = &comp.into_mixer().tracks()[0];
         // 2 measures * 2 pairs * 2 notes = 8 notes
@@ -657,7 +667,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").broken_chord(&[], 0, 0.125);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -691,7 +701,7 @@ mod tests {
             if let AudioEvent::Note(note) = event {
                 let non_zero = note.frequencies.iter().filter(|&&f| f != 0.0).count();
                 assert_eq!(non_zero, 3);
-                assert_eq!(note.duration, 0.05);
+                assert_eq!(note.duration, at(0.05));
             }
         }
     }
@@ -702,7 +712,7 @@ mod tests {
         let builder = comp.track("test").tremolo_strings(&[C4], 1.0, 0.0);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -711,7 +721,7 @@ mod tests {
         let builder = comp.track("test").tremolo_strings(&[C4], 0.5, 0.1);
 
         // 0.5 / 0.1 = 5 repetitions * 0.1 = 0.5
-        assert!((builder.cursor - 0.5).abs() < 0.01);
+        assert!((builder.peek_cursor() - 0.5).abs() < 0.01);
     }
 
     #[test]
@@ -740,7 +750,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").ostinato(&[C4, E4], 0.125, 0);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -749,7 +759,7 @@ mod tests {
         let builder = comp.track("test").ostinato(&[C4, E4, G4], 0.1, 4);
 
         // 3 notes * 4 repeats * 0.1 = 1.2
-        assert!((builder.cursor - 1.2).abs() < 0.01);
+        assert!((builder.peek_cursor() - 1.2).abs() < 0.01);
     }
 
     #[test]
@@ -767,14 +777,14 @@ mod tests {
             let non_zero = note.frequencies.iter().filter(|&&f| f != 0.0).count();
             assert_eq!(non_zero, 1);
             assert_eq!(note.frequencies[0], C2);
-            assert_eq!(note.duration, 3.0); // Total duration of all chords
+            assert_eq!(note.duration, at(3.0)); // Total duration of all chords
         }
 
         // Subsequent events should be the chords
         if let AudioEvent::Note(note) = &track.events[1] {
             let non_zero = note.frequencies.iter().filter(|&&f| f != 0.0).count();
             assert_eq!(non_zero, 3);
-            assert_eq!(note.duration, 1.0);
+            assert_eq!(note.duration, at(1.0));
         }
     }
 
@@ -788,10 +798,10 @@ mod tests {
 
         // Check chord timing
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert!((note.start_time - 0.5).abs() < 0.01);
+            assert!((note.start_time - at(0.5)).0.abs() < at(0.01).0);
         }
     }
 
@@ -802,7 +812,7 @@ mod tests {
         let builder = comp.track("test").pedal_point(C2, &chords, 0.75);
 
         // 3 chords * 0.75 = 2.25
-        assert!((builder.cursor - 2.25).abs() < 0.01);
+        assert!((builder.peek_cursor() - 2.25).abs() < 0.01);
     }
 
     #[test]
@@ -833,7 +843,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").stride_bass(C2, &[C3, E3], 0.5, 0);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -851,7 +861,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").ostinato(&[], 0.125, 5);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -861,7 +871,7 @@ mod tests {
         let builder = comp.track("test").pedal_point(C2, &chords, 1.0);
 
         // Cursor should not advance
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
 
         let track = &comp.into_mixer().tracks()[0];
         // Should only have the pedal note with 0 duration
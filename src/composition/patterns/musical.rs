@@ -11,11 +11,12 @@ impl<'a> TrackBuilder<'a> {
 
         for chord in chord_sequence {
             let cursor = self.cursor;
+            let length = self.span(chord_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     chord,
                     cursor,
-                    chord_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
@@ -23,7 +24,7 @@ impl<'a> TrackBuilder<'a> {
             // Store this chord for voice leading
             self.last_chord = Some(chord.to_vec());
             let swung_duration = self.apply_swing(chord_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self.update_section_duration();
         self
@@ -134,11 +135,12 @@ impl<'a> TrackBuilder<'a> {
 
         for chord in chord_vecs {
             let cursor = self.cursor;
+            let length = self.span(chord_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     chord.as_slice(),
                     cursor,
-                    chord_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
@@ -146,7 +148,7 @@ impl<'a> TrackBuilder<'a> {
             // Store this chord for voice leading
             self.last_chord = Some(chord.clone());
             let swung_duration = self.apply_swing(chord_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self.update_section_duration();
         self
@@ -187,17 +189,18 @@ impl<'a> TrackBuilder<'a> {
         // Play the scale in reverse order
         for &freq in scale.iter().rev() {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self.update_section_duration();
         self
@@ -230,33 +233,35 @@ impl<'a> TrackBuilder<'a> {
         // Play ascending
         for &freq in scale.iter() {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         // Play descending, skipping the first note (which was the peak)
         for &freq in scale.iter().rev().skip(1) {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self.update_section_duration();
@@ -290,33 +295,35 @@ impl<'a> TrackBuilder<'a> {
         // Play descending
         for &freq in scale.iter().rev() {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         // Play ascending, skipping the first note (which was the bottom)
         for &freq in scale.iter().skip(1) {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self
@@ -360,17 +367,18 @@ impl<'a> TrackBuilder<'a> {
         // Play the chord notes in reverse order
         for &freq in chord.iter().rev() {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -403,33 +411,35 @@ impl<'a> TrackBuilder<'a> {
         // Play ascending
         for &freq in chord.iter() {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         // Play descending, skipping the first note (which was the top)
         for &freq in chord.iter().rev().skip(1) {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self
@@ -463,33 +473,35 @@ impl<'a> TrackBuilder<'a> {
         // Play descending
         for &freq in chord.iter().rev() {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         // Play ascending, skipping the first note (which was the bottom)
         for &freq in chord.iter().skip(1) {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self
@@ -520,17 +532,18 @@ impl<'a> TrackBuilder<'a> {
         for &freq in notes {
             let doubled_freq = freq * multiplier;
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq, doubled_freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -558,17 +571,18 @@ impl<'a> TrackBuilder<'a> {
         for &freq in notes {
             let harmony_freq = freq * multiplier;
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq, harmony_freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -595,14 +609,14 @@ impl<'a> TrackBuilder<'a> {
     /// ```
     pub fn pedal(mut self, pedal_note: f32, melody_notes: &[f32], note_duration: f32) -> Self {
         let start_cursor = self.cursor;
-        let total_melody_duration = melody_notes.len() as f32 * note_duration;
+        let total_melody_duration = self.span(melody_notes.len() as f32 * note_duration);
         let waveform = self.waveform;
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
 
         // Add sustained pedal note
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
+            .add_note_with_waveform_envelope_and_bend_ticks(
                 &[pedal_note],
                 start_cursor,
                 total_melody_duration,
@@ -614,17 +628,18 @@ impl<'a> TrackBuilder<'a> {
         // Add melody notes on top
         for &freq in melody_notes {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self
@@ -666,17 +681,18 @@ impl<'a> TrackBuilder<'a> {
             let freq = notes[note_index];
 
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -731,18 +747,19 @@ impl<'a> TrackBuilder<'a> {
             let freq = center * 2.0_f32.powf(semitone_offset / 12.0);
 
             let cursor = self.cursor;
+            let length = self.span(step_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    step_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
 
             let swung_duration = self.apply_swing(step_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self.update_section_duration();
@@ -788,18 +805,19 @@ impl<'a> TrackBuilder<'a> {
             let freq = start + (stop - start) * t;
 
             let cursor = self.cursor;
+            let length = self.span(step_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    step_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
 
             let swung_duration = self.apply_swing(step_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         // Calculate initial bounce height
@@ -817,18 +835,19 @@ impl<'a> TrackBuilder<'a> {
                 let freq = stop + (peak - stop) * t;
 
                 let cursor = self.cursor;
+                let length = self.span(step_duration);
                 self.get_track_mut()
-                    .add_note_with_waveform_envelope_and_bend(
+                    .add_note_with_waveform_envelope_and_bend_ticks(
                         &[freq],
                         cursor,
-                        step_duration,
+                        length,
                         waveform,
                         envelope,
                         pitch_bend,
                     );
 
                 let swung_duration = self.apply_swing(step_duration);
-                self.cursor += swung_duration;
+                self.advance(swung_duration);
             }
 
             // Fall from peak back to floor
@@ -837,18 +856,19 @@ impl<'a> TrackBuilder<'a> {
                 let freq = peak + (stop - peak) * t;
 
                 let cursor = self.cursor;
+                let length = self.span(step_duration);
                 self.get_track_mut()
-                    .add_note_with_waveform_envelope_and_bend(
+                    .add_note_with_waveform_envelope_and_bend_ticks(
                         &[freq],
                         cursor,
-                        step_duration,
+                        length,
                         waveform,
                         envelope,
                         pitch_bend,
                     );
 
                 let swung_duration = self.apply_swing(step_duration);
-                self.cursor += swung_duration;
+                self.advance(swung_duration);
             }
         }
 
@@ -879,25 +899,26 @@ impl<'a> TrackBuilder<'a> {
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
 
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
 
         for _ in 0..count {
             // Generate random frequency in range
             let freq = rng.random_range(min..=max);
 
             let cursor = self.cursor;
+            let length = self.span(duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
 
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self.update_section_duration();
@@ -928,18 +949,19 @@ impl<'a> TrackBuilder<'a> {
 
         for _ in 0..count {
             let cursor = self.cursor;
+            let length = self.span(duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
 
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self.update_section_duration();
@@ -972,7 +994,7 @@ impl<'a> TrackBuilder<'a> {
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
 
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
 
         for _ in 0..count {
             // Pick a random note from the array
@@ -980,18 +1002,19 @@ impl<'a> TrackBuilder<'a> {
             let freq = notes[idx];
 
             let cursor = self.cursor;
+            let length = self.span(duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
 
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self.update_section_duration();
@@ -1021,25 +1044,26 @@ impl<'a> TrackBuilder<'a> {
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
 
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
 
         for _ in 0..count {
             // Generate completely random f32 frequency
             let freq = rng.random_range(min..=max);
 
             let cursor = self.cursor;
+            let length = self.span(duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
 
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self.update_section_duration();
@@ -1051,6 +1075,7 @@ impl<'a> TrackBuilder<'a> {
 mod tests {
     use crate::composition::Composition;
     use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
     use crate::consts::notes::*;
     use crate::prelude::Instrument;
     use crate::track::AudioEvent;
@@ -1067,14 +1092,14 @@ mod tests {
         // First chord
         if let AudioEvent::Note(note) = &track.events[0] {
             assert_eq!(note.num_freqs, 3);
-            assert_eq!(note.start_time, 0.0);
-            assert_eq!(note.duration, 1.0);
+            assert_eq!(note.start_time, at(0.0));
+            assert_eq!(note.duration, at(1.0));
         }
 
         // Second chord
         if let AudioEvent::Note(note) = &track.events[1] {
             assert_eq!(note.num_freqs, 3);
-            assert_eq!(note.start_time, 1.0);
+            assert_eq!(note.start_time, at(1.0));
         }
     }
 
@@ -1146,7 +1171,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("scale").scale_updown(&[], 0.1);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -1316,18 +1341,18 @@ mod tests {
         // First note should be the long pedal note
         if let AudioEvent::Note(pedal) = &track.events[0] {
             assert_eq!(pedal.frequencies[0], C4);
-            assert_eq!(pedal.duration, 1.5); // 3 notes * 0.5
-            assert_eq!(pedal.start_time, 0.0);
+            assert_eq!(pedal.duration, at(1.5)); // 3 notes * 0.5
+            assert_eq!(pedal.start_time, at(0.0));
         }
 
         // Melody notes should follow
         if let AudioEvent::Note(note) = &track.events[1] {
             assert_eq!(note.frequencies[0], E4);
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[2] {
             assert_eq!(note.frequencies[0], G4);
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
         }
     }
 
@@ -1337,7 +1362,7 @@ mod tests {
         let builder = comp.track("pedal").pedal(C4, &[E4, G4], 0.5);
 
         // 2 melody notes * 0.5 = 1.0
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     #[test]
@@ -1414,7 +1439,7 @@ mod tests {
         let empty: Vec<&[f32]> = vec![];
         let builder = comp.track("empty").chords(&empty, 1.0);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -1482,7 +1507,7 @@ mod tests {
         );
 
         // 4 chords * 1.5 duration = 6.0
-        assert_eq!(builder.cursor, 6.0);
+        assert_eq!(builder.peek_cursor(), 6.0);
     }
 
     #[test]
@@ -1527,7 +1552,7 @@ mod tests {
         let builder = comp.track("orbit").orbit(C4, 12.0, 8, 0.25, 1.0, false);
 
         // 8 steps * 0.25 duration = 2.0
-        assert_eq!(builder.cursor, 2.0);
+        assert_eq!(builder.peek_cursor(), 2.0);
     }
 
     #[test]
@@ -1676,7 +1701,7 @@ mod tests {
         let builder = comp.track("scatter").scatter(400.0, 800.0, 10, 0.25);
 
         // 10 notes * 0.25 duration = 2.5
-        assert_eq!(builder.cursor, 2.5);
+        assert_eq!(builder.peek_cursor(), 2.5);
     }
 
     #[test]
@@ -1719,7 +1744,7 @@ mod tests {
         let builder = comp.track("stream").stream(880.0, 8, 0.125);
 
         // 8 notes * 0.125 duration = 1.0
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     #[test]
@@ -1771,7 +1796,7 @@ mod tests {
         let builder = comp.track("empty").random_notes(&[], 10, 0.125);
 
         // Should return without advancing cursor if array is empty
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -1780,7 +1805,7 @@ mod tests {
         let builder = comp.track("random").random_notes(&[C4, E4, G4], 8, 0.25);
 
         // 8 notes * 0.25 duration = 2.0
-        assert_eq!(builder.cursor, 2.0);
+        assert_eq!(builder.peek_cursor(), 2.0);
     }
 
     #[test]
@@ -1836,7 +1861,7 @@ mod tests {
         let builder = comp.track("sprinkle").sprinkle(100.0, 1000.0, 16, 0.0625);
 
         // 16 notes * 0.0625 duration = 1.0
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     #[test]
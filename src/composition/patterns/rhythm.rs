@@ -1,4 +1,5 @@
 use crate::composition::TrackBuilder;
+use crate::composition::timing::Ticks;
 use crate::instruments::drums::DrumType;
 use crate::track::AudioEvent;
 
@@ -39,18 +40,18 @@ impl<'a> TrackBuilder<'a> {
     /// ```
     pub fn rhythm(mut self, pattern: &str, drum: DrumType, step_duration: f32) -> Self {
         let steps = pattern.len();
-        let start_time = self.cursor;
+        let start_time = self.peek_cursor();
 
         // Parse pattern: hit characters = hit, everything else = rest
         for (i, c) in pattern.chars().enumerate() {
             if matches!(c, 'x' | 'X' | '1' | '*') {
-                let time = start_time + (i as f32 * step_duration);
-                self.get_track_mut().add_drum(drum, time, None);
+                let time = self.to_ticks(start_time + (i as f32 * step_duration));
+                self.get_track_mut().add_drum_ticks(drum, time, None);
             }
         }
 
         // Advance cursor by total pattern duration
-        self.cursor += steps as f32 * step_duration;
+        self.advance(steps as f32 * step_duration);
         self.update_section_duration();
         self
     }
@@ -67,7 +68,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         let pattern_duration = self.cursor - self.pattern_start;
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -93,13 +94,13 @@ impl<'a> TrackBuilder<'a> {
             .collect();
 
         // Repeat the pattern
-        for i in 0..times {
-            let offset = pattern_duration * (i + 1) as f32;
+        for i in 1..=times {
+            let offset = pattern_duration * i as i64;
             for event in &pattern_events {
                 match event {
                     crate::track::AudioEvent::Note(note) => {
                         self.get_track_mut()
-                            .add_note_with_waveform_envelope_and_bend(
+                            .add_note_with_waveform_envelope_and_bend_ticks(
                                 &note.frequencies[..note.num_freqs],
                                 note.start_time + offset,
                                 note.duration,
@@ -110,7 +111,7 @@ impl<'a> TrackBuilder<'a> {
                     }
                     crate::track::AudioEvent::Drum(drum) => {
                         self.get_track_mut()
-                            .add_drum(drum.drum_type, drum.start_time + offset, None);
+                            .add_drum_ticks(drum.drum_type, drum.start_time + offset, None);
                     }
                     crate::track::AudioEvent::Sample(sample) => {
                         self.get_track_mut()
@@ -165,7 +166,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         // Move cursor to end of all repeats
-        self.cursor += pattern_duration * times as f32;
+        self.cursor += pattern_duration * times as i64;
         self.update_section_duration();
         self
     }
@@ -190,7 +191,7 @@ impl<'a> TrackBuilder<'a> {
     pub fn reverse(mut self) -> Self {
         let pattern_duration = self.cursor - self.pattern_start;
 
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -237,10 +238,7 @@ impl<'a> TrackBuilder<'a> {
                 crate::track::AudioEvent::TimeSignature(ts) => ts.start_time,
                 crate::track::AudioEvent::KeySignature(ks) => ks.start_time,
             };
-            // Handle NaN values - treat them as equal (shouldn't happen, but safe)
-            time_a
-                .partial_cmp(&time_b)
-                .unwrap_or(std::cmp::Ordering::Equal)
+            time_a.cmp(&time_b)
         });
 
         // Extract just the note/drum data (pitches, drum types)
@@ -259,7 +257,7 @@ impl<'a> TrackBuilder<'a> {
                 crate::track::AudioEvent::Drum(_drum) => (
                     [0.0; 8],
                     0,
-                    0.0,
+                    Ticks::ZERO,
                     crate::synthesis::waveform::Waveform::Sine,
                     crate::synthesis::envelope::Envelope::new(0.0, 0.0, 0.0, 0.0),
                     0.0,
@@ -268,7 +266,7 @@ impl<'a> TrackBuilder<'a> {
                 crate::track::AudioEvent::Sample(_sample) => (
                     [0.0; 8],
                     0,
-                    0.0,
+                    Ticks::ZERO,
                     crate::synthesis::waveform::Waveform::Sine,
                     crate::synthesis::envelope::Envelope::new(0.0, 0.0, 0.0, 0.0),
                     0.0,
@@ -277,7 +275,7 @@ impl<'a> TrackBuilder<'a> {
                 crate::track::AudioEvent::TempoChange(_tempo) => (
                     [0.0; 8],
                     0,
-                    0.0,
+                    Ticks::ZERO,
                     crate::synthesis::waveform::Waveform::Sine,
                     crate::synthesis::envelope::Envelope::new(0.0, 0.0, 0.0, 0.0),
                     0.0,
@@ -286,7 +284,7 @@ impl<'a> TrackBuilder<'a> {
                 crate::track::AudioEvent::TimeSignature(_time_sig) => (
                     [0.0; 8],
                     0,
-                    0.0,
+                    Ticks::ZERO,
                     crate::synthesis::waveform::Waveform::Sine,
                     crate::synthesis::envelope::Envelope::new(0.0, 0.0, 0.0, 0.0),
                     0.0,
@@ -295,7 +293,7 @@ impl<'a> TrackBuilder<'a> {
                 crate::track::AudioEvent::KeySignature(_key_sig) => (
                     [0.0; 8],
                     0,
-                    0.0,
+                    Ticks::ZERO,
                     crate::synthesis::waveform::Waveform::Sine,
                     crate::synthesis::envelope::Envelope::new(0.0, 0.0, 0.0, 0.0),
                     0.0,
@@ -321,7 +319,7 @@ impl<'a> TrackBuilder<'a> {
             .collect();
 
         // Get timing information
-        let timings: Vec<f32> = pattern_events
+        let timings: Vec<Ticks> = pattern_events
             .iter()
             .map(|event| match event {
                 crate::track::AudioEvent::Note(n) => n.start_time,
@@ -359,7 +357,7 @@ impl<'a> TrackBuilder<'a> {
                     let (freqs, num_freqs, duration, waveform, envelope, bend, _) =
                         note_data[reversed_idx];
                     self.get_track_mut()
-                        .add_note_with_waveform_envelope_and_bend(
+                        .add_note_with_waveform_envelope_and_bend_ticks(
                             &freqs[..num_freqs],
                             timing,
                             duration,
@@ -371,7 +369,7 @@ impl<'a> TrackBuilder<'a> {
                 crate::track::AudioEvent::Drum(_) => {
                     drum_idx -= 1;
                     if drum_idx < drum_data.len() {
-                        self.get_track_mut().add_drum(drum_data[drum_idx], timing, None);
+                        self.get_track_mut().add_drum_ticks(drum_data[drum_idx], timing, None);
                     }
                 }
                 crate::track::AudioEvent::Sample(_) => {
@@ -440,8 +438,9 @@ impl<'a> TrackBuilder<'a> {
             return self;
         }
 
-        let pattern_start = (self.cursor - duration).max(0.0);
         let cursor = self.cursor;
+        let pattern_start = self.to_ticks((self.peek_cursor() - duration).max(0.0));
+        let pattern_duration = cursor - pattern_start;
 
         // Collect events in the last N seconds
         let pattern_events: Vec<_> = self
@@ -463,13 +462,13 @@ impl<'a> TrackBuilder<'a> {
             .collect();
 
         // Repeat the pattern
-        for i in 0..times {
-            let offset = duration * (i + 1) as f32;
+        for i in 1..=times {
+            let offset = pattern_duration * i as i64;
             for event in &pattern_events {
                 match event {
                     crate::track::AudioEvent::Note(note) => {
                         self.get_track_mut()
-                            .add_note_with_waveform_envelope_and_bend(
+                            .add_note_with_waveform_envelope_and_bend_ticks(
                                 &note.frequencies[..note.num_freqs],
                                 note.start_time + offset,
                                 note.duration,
@@ -480,7 +479,7 @@ impl<'a> TrackBuilder<'a> {
                     }
                     crate::track::AudioEvent::Drum(drum) => {
                         self.get_track_mut()
-                            .add_drum(drum.drum_type, drum.start_time + offset, None);
+                            .add_drum_ticks(drum.drum_type, drum.start_time + offset, None);
                     }
                     crate::track::AudioEvent::Sample(sample) => {
                         self.get_track_mut()
@@ -535,7 +534,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         // Move cursor to end of all repeats
-        self.cursor += duration * times as f32;
+        self.cursor += pattern_duration * times as i64;
         self.update_section_duration();
         self
     }
@@ -568,7 +567,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         let pattern_duration = self.cursor - self.pattern_start;
-        if pattern_duration <= 0.0 {
+        if pattern_duration <= Ticks::ZERO {
             return self;
         }
 
@@ -592,7 +591,7 @@ impl<'a> TrackBuilder<'a> {
                 if event_time >= pattern_start && event_time < cursor {
                     // Time relative to pattern start, scaled by speed
                     let relative_time = event_time - pattern_start;
-                    let new_time = pattern_start + (relative_time / factor);
+                    let new_time = pattern_start + relative_time.scale(1.0 / factor as f64);
 
                     match event {
                         crate::track::AudioEvent::Note(note) => {
@@ -600,7 +599,7 @@ impl<'a> TrackBuilder<'a> {
                                 true,
                                 note.frequencies,
                                 note.num_freqs,
-                                note.duration / factor, // Scale duration
+                                note.duration.scale(1.0 / factor as f64), // Scale duration
                                 new_time,
                                 note.waveform,
                                 note.envelope,
@@ -613,7 +612,7 @@ impl<'a> TrackBuilder<'a> {
                             false,
                             [0.0; 8],
                             0,
-                            0.0,
+                            Ticks::ZERO,
                             new_time,
                             crate::synthesis::waveform::Waveform::Sine,
                             crate::synthesis::envelope::Envelope::new(0.0, 0.0, 0.0, 0.0),
@@ -625,7 +624,7 @@ impl<'a> TrackBuilder<'a> {
                             false,
                             [0.0; 8],
                             0,
-                            0.0,
+                            Ticks::ZERO,
                             new_time,
                             crate::synthesis::waveform::Waveform::Sine,
                             crate::synthesis::envelope::Envelope::new(0.0, 0.0, 0.0, 0.0),
@@ -700,7 +699,7 @@ impl<'a> TrackBuilder<'a> {
                 self.get_track_mut().invalidate_time_cache();
             } else if is_note {
                 self.get_track_mut()
-                    .add_note_with_waveform_envelope_and_bend(
+                    .add_note_with_waveform_envelope_and_bend_ticks(
                         &freqs[..num_freqs],
                         time,
                         duration,
@@ -709,12 +708,12 @@ impl<'a> TrackBuilder<'a> {
                         bend,
                     );
             } else {
-                self.get_track_mut().add_drum(drum_type, time, None);
+                self.get_track_mut().add_drum_ticks(drum_type, time, None);
             }
         }
 
         // Adjust cursor: new pattern duration is original / factor
-        self.cursor = self.pattern_start + (pattern_duration / factor);
+        self.cursor = self.pattern_start + pattern_duration.scale(1.0 / factor as f64);
         self.update_section_duration();
         self
     }
@@ -770,7 +769,7 @@ impl<'a> TrackBuilder<'a> {
 
         // Filter events probabilistically
         use rand::Rng;
-        let mut rng = rand::rng();
+        let mut rng = self.rng();
 
         let pattern_start = self.pattern_start;
         let cursor = self.cursor;
@@ -829,7 +828,7 @@ impl<'a> TrackBuilder<'a> {
         let cursor = self.cursor;
 
         // Collect all events in pattern range with their times
-        let mut events_in_range: Vec<f32> = {
+        let mut events_in_range: Vec<Ticks> = {
             let track = self.get_track_mut();
             track
                 .events
@@ -854,12 +853,12 @@ impl<'a> TrackBuilder<'a> {
         };
 
         // Sort by time
-        events_in_range.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        events_in_range.sort();
 
         // Add drum at every nth event (1-indexed: 4th, 8th, 12th...)
         for (i, &time) in events_in_range.iter().enumerate() {
             if (i + 1) % n == 0 {
-                self.get_track_mut().add_drum(drum, time, None);
+                self.get_track_mut().add_drum_ticks(drum, time, None);
             }
         }
 
@@ -872,7 +871,8 @@ mod tests {
     use crate::composition::Composition;
     use crate::instruments::drums::DrumType;
     use crate::consts::notes::*;
-    use crate::composition::timing::Tempo;
+    use crate::composition::timing::{Tempo, Ticks};
+    use crate::composition::timing::ticks::at;
     use crate::track::AudioEvent;
 
     #[test]
@@ -880,7 +880,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").wait(2.0).pattern_start();
 
-        assert_eq!(builder.pattern_start, 2.0);
+        assert_eq!(builder.to_seconds(builder.pattern_start), 2.0);
     }
 
     #[test]
@@ -911,21 +911,21 @@ mod tests {
 
         // Original pattern
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
             assert_eq!(note.frequencies[0], 440.0);
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.25);
+            assert_eq!(note.start_time, at(0.25));
             assert_eq!(note.frequencies[0], 550.0);
         }
 
         // Repeated pattern (starts at 0.5)
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
             assert_eq!(note.frequencies[0], 440.0);
         }
         if let AudioEvent::Note(note) = &track.events[3] {
-            assert_eq!(note.start_time, 0.75);
+            assert_eq!(note.start_time, at(0.75));
             assert_eq!(note.frequencies[0], 550.0);
         }
     }
@@ -942,7 +942,7 @@ mod tests {
 
         // Original pattern = 1.0s, repeated 2 times = 2.0s more
         // Total cursor should be at 3.0s
-        assert_eq!(builder.cursor, 3.0);
+        assert_eq!(builder.peek_cursor(), 3.0);
     }
 
     #[test]
@@ -1001,15 +1001,15 @@ mod tests {
 
         // Timing should stay the same, but notes reversed
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
             assert_eq!(note.frequencies[0], G4); // Was last, now first
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.25);
+            assert_eq!(note.start_time, at(0.25));
             assert_eq!(note.frequencies[0], E4); // Middle stays middle
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
             assert_eq!(note.frequencies[0], C4); // Was first, now last
         }
     }
@@ -1020,7 +1020,7 @@ mod tests {
         let builder = comp.track("melody").pattern_start().reverse();
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -1037,7 +1037,7 @@ mod tests {
         // Single note should be unchanged
         if let AudioEvent::Note(note) = &track.events[0] {
             assert_eq!(note.frequencies[0], 440.0);
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
     }
 
@@ -1052,7 +1052,7 @@ mod tests {
             .reverse();
 
         // Cursor should remain at end
-        assert_eq!(builder.cursor, 0.5);
+        assert_eq!(builder.peek_cursor(), 0.5);
     }
 
     #[test]
@@ -1083,7 +1083,7 @@ mod tests {
         let builder = comp.track("melody").note(&[440.0], 0.5).repeat_last(0.0, 2);
 
         // Check cursor first before moving comp
-        assert_eq!(builder.cursor, 0.5); // Cursor unchanged
+        assert_eq!(builder.peek_cursor(), 0.5); // Cursor unchanged
 
         let track = &comp.into_mixer().tracks()[0];
         assert_eq!(track.events.len(), 1); // No repeat should happen
@@ -1095,7 +1095,7 @@ mod tests {
         let builder = comp.track("melody").note(&[440.0], 0.5).repeat_last(0.5, 2);
 
         // Original 0.5s + 2 repeats * 0.5s = 1.5s total
-        assert_eq!(builder.cursor, 1.5);
+        assert_eq!(builder.peek_cursor(), 1.5);
     }
 
     #[test]
@@ -1165,7 +1165,7 @@ mod tests {
         // Total = 2.75
         // Then repeat last note once = +0.5
         // Final = 3.25
-        assert_eq!(builder.cursor, 3.25);
+        assert_eq!(builder.peek_cursor(), 3.25);
 
         let track = &comp.into_mixer().tracks()[0];
         assert_eq!(track.events.len(), 11); // 9 + 2 = 11
@@ -1186,7 +1186,7 @@ mod tests {
         if let AudioEvent::Note(note) = &track.events[0] {
             assert_eq!(note.num_freqs, 1);
             assert_eq!(note.frequencies[0], 660.0);
-            assert_eq!(note.duration, 0.25);
+            assert_eq!(note.duration, at(0.25));
         }
 
         // Second note should now be the chord (was first)
@@ -1194,7 +1194,7 @@ mod tests {
             assert_eq!(note.num_freqs, 2);
             assert_eq!(note.frequencies[0], 440.0);
             assert_eq!(note.frequencies[1], 550.0);
-            assert_eq!(note.duration, 0.5);
+            assert_eq!(note.duration, at(0.5));
         }
     }
 
@@ -1213,16 +1213,16 @@ mod tests {
 
         // Durations should be halved
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 0.25);
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.duration, at(0.25));
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.duration, 0.25);
-            assert_eq!(note.start_time, 0.25);
+            assert_eq!(note.duration, at(0.25));
+            assert_eq!(note.start_time, at(0.25));
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.duration, 0.25);
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.duration, at(0.25));
+            assert_eq!(note.start_time, at(0.5));
         }
     }
 
@@ -1239,12 +1239,12 @@ mod tests {
 
         // Durations should be doubled
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 1.0);
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.duration, at(1.0));
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.duration, 1.0);
-            assert_eq!(note.start_time, 1.0);
+            assert_eq!(note.duration, at(1.0));
+            assert_eq!(note.start_time, at(1.0));
         }
     }
 
@@ -1260,7 +1260,7 @@ mod tests {
             .speed(2.0);
 
         // Original pattern = 1.5s, at 2x speed = 0.75s
-        assert!((builder.cursor - 0.75).abs() < 0.001);
+        assert!((builder.peek_cursor() - 0.75).abs() < 0.001);
     }
 
     #[test]
@@ -1273,7 +1273,7 @@ mod tests {
             .speed(0.0);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.5);
+        assert_eq!(builder.peek_cursor(), 0.5);
     }
 
     #[test]
@@ -1286,7 +1286,7 @@ mod tests {
             .speed(-2.0);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.5);
+        assert_eq!(builder.peek_cursor(), 0.5);
     }
 
     #[test]
@@ -1306,7 +1306,7 @@ mod tests {
             assert_eq!(note.frequencies[1], E4);
             assert_eq!(note.frequencies[2], G4);
             // Duration should be halved
-            assert_eq!(note.duration, 0.25);
+            assert_eq!(note.duration, at(0.25));
         }
     }
 
@@ -1367,7 +1367,7 @@ mod tests {
             .probability(0.5);
 
         // Cursor should remain at 1.5 regardless of which notes were removed
-        assert_eq!(builder.cursor, 1.5);
+        assert_eq!(builder.peek_cursor(), 1.5);
     }
 
     #[test]
@@ -1406,7 +1406,7 @@ mod tests {
         // Should have all 4 notes with halved durations
         assert_eq!(track.events.len(), 4);
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 0.25);
+            assert_eq!(note.duration, at(0.25));
         }
     }
 
@@ -1425,10 +1425,10 @@ mod tests {
 
         // Check timing compression
         if let AudioEvent::Drum(drum) = &track.events[0] {
-            assert_eq!(drum.start_time, 0.0);
+            assert_eq!(drum.start_time, at(0.0));
         }
         if let AudioEvent::Drum(drum) = &track.events[1] {
-            assert!((drum.start_time - 0.25).abs() < 0.001); // Was 0.5, now 0.25
+            assert!((drum.start_time - at(0.25)).0.abs() < at(0.001).0); // Was 0.5, now 0.25
         }
     }
 
@@ -1466,7 +1466,7 @@ mod tests {
         // Durations should be scaled by speed
         if let AudioEvent::Note(note) = &track.events[0] {
             let expected_duration = 0.25 / 1.5;
-            assert!((note.duration - expected_duration).abs() < 0.001);
+            assert!((note.duration - at(expected_duration)).0.abs() < at(0.001).0);
         }
     }
 
@@ -1483,17 +1483,17 @@ mod tests {
 
         // Check timing of hits
         if let AudioEvent::Drum(drum) = &track.events[0] {
-            assert_eq!(drum.start_time, 0.0); // Position 0
+            assert_eq!(drum.start_time, at(0.0)); // Position 0
             assert!(matches!(drum.drum_type, DrumType::Kick));
         }
         if let AudioEvent::Drum(drum) = &track.events[1] {
-            assert_eq!(drum.start_time, 0.25); // Position 2 * 0.125
+            assert_eq!(drum.start_time, at(0.25)); // Position 2 * 0.125
         }
         if let AudioEvent::Drum(drum) = &track.events[2] {
-            assert_eq!(drum.start_time, 0.625); // Position 5 * 0.125
+            assert_eq!(drum.start_time, at(0.625)); // Position 5 * 0.125
         }
         if let AudioEvent::Drum(drum) = &track.events[3] {
-            assert_eq!(drum.start_time, 0.875); // Position 7 * 0.125
+            assert_eq!(drum.start_time, at(0.875)); // Position 7 * 0.125
         }
     }
 
@@ -1600,7 +1600,7 @@ mod tests {
 
         // Pattern is 9 chars long (including space), each step is 0.125s
         // Total duration = 9 * 0.125 = 1.125s
-        assert_eq!(builder.cursor, 1.125);
+        assert_eq!(builder.peek_cursor(), 1.125);
     }
 
     #[test]
@@ -1616,16 +1616,16 @@ mod tests {
 
         // Verify spacing
         if let AudioEvent::Drum(drum) = &track.events[0] {
-            assert_eq!(drum.start_time, 0.0);
+            assert_eq!(drum.start_time, at(0.0));
         }
         if let AudioEvent::Drum(drum) = &track.events[1] {
-            assert_eq!(drum.start_time, 0.25);
+            assert_eq!(drum.start_time, at(0.25));
         }
         if let AudioEvent::Drum(drum) = &track.events[2] {
-            assert_eq!(drum.start_time, 0.5);
+            assert_eq!(drum.start_time, at(0.5));
         }
         if let AudioEvent::Drum(drum) = &track.events[3] {
-            assert_eq!(drum.start_time, 0.75);
+            assert_eq!(drum.start_time, at(0.75));
         }
     }
 
@@ -1652,11 +1652,11 @@ mod tests {
 
         // First hit should start at 2.0
         if let AudioEvent::Drum(drum) = &track.events[0] {
-            assert_eq!(drum.start_time, 2.0);
+            assert_eq!(drum.start_time, at(2.0));
         }
         // Second hit at 2.5
         if let AudioEvent::Drum(drum) = &track.events[1] {
-            assert_eq!(drum.start_time, 2.5);
+            assert_eq!(drum.start_time, at(2.5));
         }
     }
 
@@ -1822,7 +1822,7 @@ mod tests {
         let track = &comp.into_mixer().tracks()[0];
 
         // Find the crash events and verify timing
-        let crashes: Vec<f32> = track
+        let crashes: Vec<Ticks> = track
             .events
             .iter()
             .filter_map(|e| match e {
@@ -1833,8 +1833,8 @@ mod tests {
 
         // Crashes should be at 2nd note (0.25) and 4th note (0.75)
         assert_eq!(crashes.len(), 2);
-        assert_eq!(crashes[0], 0.25);
-        assert_eq!(crashes[1], 0.75);
+        assert_eq!(crashes[0], at(0.25));
+        assert_eq!(crashes[1], at(0.75));
     }
 
     #[test]
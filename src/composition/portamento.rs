@@ -28,61 +28,65 @@ impl<'a> TrackBuilder<'a> {
         if scale_notes.is_empty() {
             // No scale notes in range, just play start and end
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[start_freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += note_duration;
+            self.advance(note_duration);
 
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[end_freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += note_duration;
+            self.advance(note_duration);
         } else {
             // Play all scale notes in order (ascending or descending)
             if start_freq < end_freq {
                 // Ascending
                 for &freq in &scale_notes {
                     let cursor = self.cursor;
+                    let length = self.span(note_duration);
                     self.get_track_mut()
-                        .add_note_with_waveform_envelope_and_bend(
+                        .add_note_with_waveform_envelope_and_bend_ticks(
                             &[freq],
                             cursor,
-                            note_duration,
+                            length,
                             waveform,
                             envelope,
                             pitch_bend,
                         );
                     let swung_duration = self.apply_swing(note_duration);
-                    self.cursor += swung_duration;
+                    self.advance(swung_duration);
                 }
             } else {
                 // Descending
                 for &freq in scale_notes.iter().rev() {
                     let cursor = self.cursor;
+                    let length = self.span(note_duration);
                     self.get_track_mut()
-                        .add_note_with_waveform_envelope_and_bend(
+                        .add_note_with_waveform_envelope_and_bend_ticks(
                             &[freq],
                             cursor,
-                            note_duration,
+                            length,
                             waveform,
                             envelope,
                             pitch_bend,
                         );
                     let swung_duration = self.apply_swing(note_duration);
-                    self.cursor += swung_duration;
+                    self.advance(swung_duration);
                 }
             }
         }
@@ -128,16 +132,17 @@ impl<'a> TrackBuilder<'a> {
             let t = i as f32 / (segments - 1) as f32;
             let freq = from + (to - from) * t;
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
-            self.cursor += note_duration;
+            self.advance(note_duration);
         }
         self.update_section_duration();
         self
@@ -278,7 +283,7 @@ mod tests {
         let builder = comp.track("slide").slide(C4, G4, 0.0);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -287,7 +292,7 @@ mod tests {
         let builder = comp.track("slide").slide(C4, G4, -0.1);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -296,7 +301,7 @@ mod tests {
         let builder = comp.track("slide").slide(C4, G4, 0.5);
 
         // Should advance by total duration
-        assert!((builder.cursor - 0.5).abs() < 0.01);
+        assert!((builder.peek_cursor() - 0.5).abs() < 0.01);
     }
 
     #[test]
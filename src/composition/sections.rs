@@ -8,7 +8,7 @@ use crate::synthesis::envelope::Envelope;
 use crate::synthesis::filter_envelope::FilterEnvelope;
 use crate::synthesis::fm_synthesis::FMParams;
 use crate::instruments::Instrument;
-use crate::composition::timing::Tempo;
+use crate::composition::timing::{Tempo, Ticks};
 use crate::track::{AudioEvent, Track};
 use crate::synthesis::waveform::Waveform;
 use std::collections::HashMap;
@@ -48,7 +48,7 @@ impl Section {
     ///
     /// This is used internally by the arrangement system to place sections
     /// at different points in time.
-    pub(crate) fn clone_with_offset(&self, time_offset: f32) -> HashMap<String, Track> {
+    pub(crate) fn clone_with_offset(&self, time_offset: Ticks) -> HashMap<String, Track> {
         self.tracks
             .iter()
             .map(|(name, track)| {
@@ -120,8 +120,8 @@ impl<'a> SectionBuilder<'a> {
             context: crate::composition::BuilderContext::Section(self.section_name),
             track_name: name.to_string(),
             bus_name: "default".to_string(),
-            cursor: 0.0,
-            pattern_start: 0.0,
+            cursor: Ticks::ZERO,
+            pattern_start: Ticks::ZERO,
             waveform: Waveform::Sine,
             envelope: Envelope::default(),
             filter_envelope: FilterEnvelope::default(),
@@ -160,8 +160,8 @@ impl<'a> SectionBuilder<'a> {
             context: crate::composition::BuilderContext::Section(self.section_name),
             track_name: name.to_string(),
             bus_name: "default".to_string(),
-            cursor: 0.0,
-            pattern_start: 0.0,
+            cursor: Ticks::ZERO,
+            pattern_start: Ticks::ZERO,
             waveform: instrument.waveform,
             envelope: instrument.envelope,
             filter_envelope: FilterEnvelope::default(),
@@ -193,6 +193,11 @@ impl Track {
         self.effects.reverb = instrument.reverb.clone();
         self.effects.distortion = instrument.distortion.clone();
         self.modulation = instrument.modulation.clone();
+        self.unison = instrument.unison;
+        self.voicing = instrument.voicing;
+        self.fm_patch = instrument.fm_patch.clone();
+        self.physical_model = instrument.physical_model.clone();
+        self.resonator = instrument.resonator.clone();
     }
 }
 
@@ -201,6 +206,7 @@ mod tests {
     use super::*;
     use crate::instruments::drums::DrumType;
     use crate::consts::notes::*;
+    use crate::composition::timing::ticks::at;
 
     #[test]
     fn test_create_empty_section() {
@@ -224,14 +230,14 @@ mod tests {
         track.add_note(&[E4], 1.0, 1.0);
         section.tracks.insert("melody".to_string(), track);
 
-        let offset_tracks = section.clone_with_offset(4.0);
+        let offset_tracks = section.clone_with_offset(at(4.0));
         let melody = &offset_tracks["melody"];
 
         if let AudioEvent::Note(note) = &melody.events[0] {
-            assert_eq!(note.start_time, 4.0);
+            assert_eq!(note.start_time, at(4.0));
         }
         if let AudioEvent::Note(note) = &melody.events[1] {
-            assert_eq!(note.start_time, 5.0);
+            assert_eq!(note.start_time, at(5.0));
         }
     }
 
@@ -313,13 +319,13 @@ mod tests {
 
         // Check timing
         if let AudioEvent::Note(note) = &melody.events[0] {
-            assert_eq!(note.start_time, 0.0); // Intro note
+            assert_eq!(note.start_time, at(0.0)); // Intro note
         }
         if let AudioEvent::Note(note) = &melody.events[1] {
-            assert_eq!(note.start_time, 1.0); // First verse note (after 1s intro)
+            assert_eq!(note.start_time, at(1.0)); // First verse note (after 1s intro)
         }
         if let AudioEvent::Note(note) = &melody.events[2] {
-            assert_eq!(note.start_time, 1.5); // Second verse note
+            assert_eq!(note.start_time, at(1.5)); // Second verse note
         }
     }
 
@@ -336,10 +342,10 @@ mod tests {
 
         // Check timing
         if let AudioEvent::Note(note) = &melody.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &melody.events[2] {
-            assert_eq!(note.start_time, 1.0); // Second chorus starts after first (1s)
+            assert_eq!(note.start_time, at(1.0)); // Second chorus starts after first (1s)
         }
     }
 
@@ -384,7 +390,7 @@ mod tests {
         // Check last note timing
         // intro: 2s, verse: 2s, chorus: 2s, verse starts at 6s
         if let AudioEvent::Note(note) = &melody.events[8] {
-            assert_eq!(note.start_time, 7.0); // 6s + 1s
+            assert_eq!(note.start_time, at(7.0)); // 6s + 1s
         }
     }
 
@@ -403,10 +409,10 @@ mod tests {
         let melody = section.tracks.get("melody").unwrap();
 
         if let AudioEvent::Note(note) = &melody.events[0] {
-            assert_eq!(note.start_time, 1.0);
+            assert_eq!(note.start_time, at(1.0));
         }
         if let AudioEvent::Note(note) = &melody.events[1] {
-            assert_eq!(note.start_time, 3.0);
+            assert_eq!(note.start_time, at(3.0));
         }
     }
 }
@@ -1,9 +1,14 @@
+use rand::Rng;
 use crate::composition::TrackBuilder;
-use crate::synthesis::granular::{GranularParams, create_granular_events};
+use crate::synthesis::granular::{GranularParams, create_granular_events_with_rng};
 use crate::synthesis::noise::NoiseType;
 use crate::prelude::{FMParams, FilterEnvelope};
+use crate::synthesis::fm_operators::FMPatch;
+use crate::synthesis::modal::ModalResonator;
+use crate::synthesis::physical_model::PhysicalModel;
+use crate::synthesis::unison::Unison;
 use crate::synthesis::sample::Sample;
-use crate::track::{AudioEvent, SampleEvent};
+use crate::track::{AudioEvent, SampleEvent, Voicing};
 
 /// Synthesis methods for TrackBuilder
 ///
@@ -56,6 +61,121 @@ impl<'a> TrackBuilder<'a> {
         self
     }
 
+    /// Set unison voices for subsequent notes
+    ///
+    /// Each note frequency is played by a stack of detuned oscillators, optionally
+    /// spread across the stereo field, for thick pads and leads.
+    ///
+    /// # Arguments
+    /// * `unison` - Voice count, detune, stereo spread and phase scatter
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("pad")
+    ///     .waveform(Waveform::Sawtooth)
+    ///     .unison(Unison::new(7, 20.0).with_spread(0.8).with_phase_random(1.0))
+    ///     .note(&[C4, E4, G4], 2.0);
+    /// ```
+    pub fn unison(mut self, unison: Unison) -> Self {
+        self.get_track_mut().unison = unison;
+        self
+    }
+
+    /// Play subsequent notes with a multi-operator FM patch
+    ///
+    /// Four or six sine operators connected by a DX7-style algorithm, each with
+    /// its own ratio, level, envelope and feedback. Replaces the waveform and
+    /// [`fm`](Self::fm) settings for the notes that follow.
+    ///
+    /// # Arguments
+    /// * `patch` - Operators and algorithm
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("keys")
+    ///     .fm_patch(FMPatch::electric_piano())
+    ///     .note(&[C4, E4, G4], 1.0);
+    /// ```
+    pub fn fm_patch(mut self, patch: FMPatch) -> Self {
+        self.get_track_mut().fm_patch = Some(patch);
+        self
+    }
+
+    /// Play subsequent notes with a waveguide physical model
+    ///
+    /// A simulated bowed or plucked string, blown pipe or struck bar, with its
+    /// excitation position, damping, stiffness and body resonance. Replaces every
+    /// other oscillator setting for the notes that follow; velocity sets how hard
+    /// each note is bowed, blown, plucked or struck.
+    ///
+    /// # Arguments
+    /// * `model` - The instrument to simulate
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("cello")
+    ///     .physical_model(PhysicalModel::bowed_string().with_body(BodyResonance::cello()))
+    ///     .note(&[C3], 2.0);
+    /// ```
+    pub fn physical_model(mut self, model: PhysicalModel) -> Self {
+        self.get_track_mut().physical_model = Some(model);
+        self
+    }
+
+    /// Play subsequent notes with a modal resonator bank
+    ///
+    /// A struck object described by its modes of vibration: bells, gongs, bars,
+    /// glasses or any recorded hit analysed with
+    /// [`ModalResonator::from_sample`]. Replaces every oscillator setting but a
+    /// physical model for the notes that follow. Each note rings for as long as
+    /// its modes do; velocity sets how hard it is struck.
+    ///
+    /// # Arguments
+    /// * `resonator` - The object to strike
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("bells")
+    ///     .resonator(ModalResonator::bell())
+    ///     .envelope(Envelope::new(0.001, 0.0, 1.0, 3.0))
+    ///     .notes(&[C5, G4, E4, C4], 1.0);
+    /// ```
+    pub fn resonator(mut self, resonator: ModalResonator) -> Self {
+        self.get_track_mut().resonator = Some(resonator);
+        self
+    }
+
+    /// Set how this track's notes are given to voices
+    ///
+    /// Limits polyphony with voice stealing, or turns the track into a mono synth
+    /// with note priority, legato and glide. Applies to the whole track when it is
+    /// turned into a mixer.
+    ///
+    /// # Arguments
+    /// * `voicing` - Poly or mono voice handling
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("acid")
+    ///     .waveform(Waveform::Sawtooth)
+    ///     .voicing(Voicing::mono().legato().with_glide(0.06))
+    ///     .notes(&[C3, C3, DS3, C4], 0.125);
+    /// ```
+    pub fn voicing(mut self, voicing: Voicing) -> Self {
+        self.get_track_mut().voicing = voicing;
+        self
+    }
+
     /// Create a custom FM sound with specific parameters
     ///
     /// # Arguments
@@ -215,7 +335,8 @@ impl<'a> TrackBuilder<'a> {
         let start_time = self.cursor;
 
         // Generate noise samples
-        let mut noise_samples = noise_type.generate(sample_count);
+        let seed = self.rng().random();
+        let mut noise_samples = noise_type.generate_with_seed(sample_count, seed);
 
         // Apply amplitude
         for sample in &mut noise_samples {
@@ -239,7 +360,7 @@ impl<'a> TrackBuilder<'a> {
         track.events.push(AudioEvent::Sample(sample_event));
 
         // Advance cursor (duration is in seconds)
-        self.cursor += duration;
+        self.advance(duration);
 
         self
     }
@@ -285,7 +406,15 @@ impl<'a> TrackBuilder<'a> {
         let start_time = self.cursor;
 
         // Generate all the grain events
-        let grain_events = create_granular_events(&source_sample, &params, duration, start_time);
+        let mut rng = self.rng();
+        let grain_events = create_granular_events_with_rng(
+            &source_sample,
+            &params,
+            duration,
+            start_time,
+            &self.composition.tempo_map,
+            &mut rng,
+        );
 
         // Add all events to the track
         let track = self.get_track_mut();
@@ -294,7 +423,7 @@ impl<'a> TrackBuilder<'a> {
         }
 
         // Advance cursor
-        self.cursor += duration;
+        self.advance(duration);
 
         self
     }
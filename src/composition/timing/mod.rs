@@ -5,6 +5,10 @@
 pub mod navigation;
 pub mod musical_time;
 pub mod tempo;
+pub mod tempo_map;
+pub mod ticks;
 
 // Re-export everything from submodules to maintain API compatibility
 pub use tempo::*;
+pub use tempo_map::*;
+pub use ticks::*;
@@ -1,3 +1,4 @@
+use crate::composition::timing::Ticks;
 use crate::composition::TrackBuilder;
 
 impl<'a> TrackBuilder<'a> {
@@ -19,42 +20,42 @@ impl<'a> TrackBuilder<'a> {
     ///     .trill(C4, D4, 16, qtr / 4.0);  // Sixteenth note trill
     /// ```
     pub fn tempo_quarter(&self) -> f32 {
-        self.tempo.quarter_note()
+        self.beat_seconds(1.0)
     }
 
     /// Get the duration of an eighth note at the current tempo
     pub fn tempo_eighth(&self) -> f32 {
-        self.tempo.eighth_note()
+        self.beat_seconds(0.5)
     }
 
     /// Get the duration of a sixteenth note at the current tempo
     pub fn tempo_sixteenth(&self) -> f32 {
-        self.tempo.eighth_note() / 2.0
+        self.beat_seconds(0.25)
     }
 
     /// Get the duration of a half note at the current tempo
     pub fn tempo_half(&self) -> f32 {
-        self.tempo.quarter_note() * 2.0
+        self.beat_seconds(2.0)
     }
 
     /// Get the duration of a whole note at the current tempo
     pub fn tempo_whole(&self) -> f32 {
-        self.tempo.whole_note()
+        self.beat_seconds(4.0)
     }
 
     /// Get the duration of a dotted quarter note at the current tempo
     pub fn tempo_dotted_quarter(&self) -> f32 {
-        self.tempo.quarter_note() * 1.5
+        self.beat_seconds(1.5)
     }
 
     /// Get the duration of a dotted eighth note at the current tempo
     pub fn tempo_dotted_eighth(&self) -> f32 {
-        self.tempo.eighth_note() * 1.5
+        self.beat_seconds(0.75)
     }
 
     /// Get the duration of a thirty-second note at the current tempo
     pub fn tempo_thirty_second(&self) -> f32 {
-        self.tempo.eighth_note() / 4.0
+        self.beat_seconds(0.125)
     }
 
     // ===== MUSICAL TIME POSITIONING =====
@@ -76,8 +77,7 @@ impl<'a> TrackBuilder<'a> {
     /// ```
     pub fn at_bar(mut self, bar: u32) -> Self {
         let beats_per_bar = 4.0;
-        let beat_duration = self.tempo.quarter_note();
-        self.cursor = (bar as f32 - 1.0) * beats_per_bar * beat_duration;
+        self.cursor = Ticks::from_beats((bar as f64 - 1.0) * beats_per_bar);
         self
     }
 
@@ -97,8 +97,7 @@ impl<'a> TrackBuilder<'a> {
     ///     .note(&[D4], 0.5);
     /// ```
     pub fn at_beat(mut self, beat: u32) -> Self {
-        let beat_duration = self.tempo.quarter_note();
-        self.cursor = (beat as f32 - 1.0) * beat_duration;
+        self.cursor = Ticks::from_beats(beat as f64 - 1.0);
         self
     }
 
@@ -119,8 +118,7 @@ impl<'a> TrackBuilder<'a> {
     ///     .note(&[E4], 0.5);
     /// ```
     pub fn beats(mut self, beats: f32) -> Self {
-        let beat_duration = self.tempo.quarter_note();
-        self.cursor += beats * beat_duration;
+        self.cursor += Ticks::from_beats(beats as f64);
         self
     }
 
@@ -142,8 +140,7 @@ impl<'a> TrackBuilder<'a> {
     /// ```
     pub fn bars(mut self, bars: f32) -> Self {
         let beats_per_bar = 4.0;
-        let beat_duration = self.tempo.quarter_note();
-        self.cursor += bars * beats_per_bar * beat_duration;
+        self.cursor += Ticks::from_beats(bars as f64 * beats_per_bar);
         self
     }
 
@@ -162,23 +159,24 @@ impl<'a> TrackBuilder<'a> {
     ///     .wholes(&[C4, E4, G4]);  // Each note lasts a whole note
     /// ```
     pub fn wholes(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.whole_note();
         for &freq in notes {
+            let duration = self.beat_seconds(4.0);
             let cursor = self.cursor;
+            let length = self.span(duration);
             let waveform = self.waveform;
             let envelope = self.envelope;
             let pitch_bend = self.pitch_bend;
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -196,23 +194,24 @@ impl<'a> TrackBuilder<'a> {
     ///     .halves(&[C2, G2]);  // Each note lasts a half note
     /// ```
     pub fn halves(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.quarter_note() * 2.0;
         for &freq in notes {
+            let duration = self.beat_seconds(2.0);
             let cursor = self.cursor;
+            let length = self.span(duration);
             let waveform = self.waveform;
             let envelope = self.envelope;
             let pitch_bend = self.pitch_bend;
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -230,23 +229,24 @@ impl<'a> TrackBuilder<'a> {
     ///     .quarters(&[C4, D4, E4, F4]);  // Four quarter notes
     /// ```
     pub fn quarters(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.quarter_note();
         for &freq in notes {
+            let duration = self.beat_seconds(1.0);
             let cursor = self.cursor;
+            let length = self.span(duration);
             let waveform = self.waveform;
             let envelope = self.envelope;
             let pitch_bend = self.pitch_bend;
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -264,23 +264,24 @@ impl<'a> TrackBuilder<'a> {
     ///     .eighths(&[C4, C4, C4, C4, C4, C4, C4, C4]);  // Eight eighth notes
     /// ```
     pub fn eighths(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.eighth_note();
         for &freq in notes {
+            let duration = self.beat_seconds(0.5);
             let cursor = self.cursor;
+            let length = self.span(duration);
             let waveform = self.waveform;
             let envelope = self.envelope;
             let pitch_bend = self.pitch_bend;
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -298,23 +299,24 @@ impl<'a> TrackBuilder<'a> {
     ///     .sixteenths(&[C4, D4, E4, F4, G4, A4, B4, C5]);  // Fast 16th note run
     /// ```
     pub fn sixteenths(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.eighth_note() / 2.0;
         for &freq in notes {
+            let duration = self.beat_seconds(0.25);
             let cursor = self.cursor;
+            let length = self.span(duration);
             let waveform = self.waveform;
             let envelope = self.envelope;
             let pitch_bend = self.pitch_bend;
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -332,23 +334,24 @@ impl<'a> TrackBuilder<'a> {
     ///     .dotted_quarters(&[C4, E4, G4]);  // Three dotted quarter notes
     /// ```
     pub fn dotted_quarters(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.quarter_note() * 1.5;
         for &freq in notes {
+            let duration = self.beat_seconds(1.5);
             let cursor = self.cursor;
+            let length = self.span(duration);
             let waveform = self.waveform;
             let envelope = self.envelope;
             let pitch_bend = self.pitch_bend;
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -366,23 +369,24 @@ impl<'a> TrackBuilder<'a> {
     ///     .dotted_eighths(&[C4, E4, G4, E4]);
     /// ```
     pub fn dotted_eighths(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.eighth_note() * 1.5;
         for &freq in notes {
+            let duration = self.beat_seconds(0.75);
             let cursor = self.cursor;
+            let length = self.span(duration);
             let waveform = self.waveform;
             let envelope = self.envelope;
             let pitch_bend = self.pitch_bend;
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
         self
     }
@@ -402,17 +406,18 @@ impl<'a> TrackBuilder<'a> {
     ///     .quarter(&[C4, E4, G4]);  // One quarter note chord
     /// ```
     pub fn quarter(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.quarter_note();
+        let duration = self.beat_seconds(1.0);
         let cursor = self.cursor;
+        let length = self.span(duration);
         let waveform = self.waveform;
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
-                notes, cursor, duration, waveform, envelope, pitch_bend,
+            .add_note_with_waveform_envelope_and_bend_ticks(
+                notes, cursor, length, waveform, envelope, pitch_bend,
             );
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self
     }
 
@@ -429,17 +434,18 @@ impl<'a> TrackBuilder<'a> {
     ///     .half(&[C2]);  // One half note
     /// ```
     pub fn half(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.quarter_note() * 2.0;
+        let duration = self.beat_seconds(2.0);
         let cursor = self.cursor;
+        let length = self.span(duration);
         let waveform = self.waveform;
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
-                notes, cursor, duration, waveform, envelope, pitch_bend,
+            .add_note_with_waveform_envelope_and_bend_ticks(
+                notes, cursor, length, waveform, envelope, pitch_bend,
             );
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self
     }
 
@@ -456,17 +462,18 @@ impl<'a> TrackBuilder<'a> {
     ///     .whole(&[C4, E4, G4, C5]);  // One whole note chord
     /// ```
     pub fn whole(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.whole_note();
+        let duration = self.beat_seconds(4.0);
         let cursor = self.cursor;
+        let length = self.span(duration);
         let waveform = self.waveform;
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
-                notes, cursor, duration, waveform, envelope, pitch_bend,
+            .add_note_with_waveform_envelope_and_bend_ticks(
+                notes, cursor, length, waveform, envelope, pitch_bend,
             );
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self
     }
 
@@ -483,17 +490,18 @@ impl<'a> TrackBuilder<'a> {
     ///     .eighth(&[C4, E4, G4]);  // One eighth note stab
     /// ```
     pub fn eighth(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.eighth_note();
+        let duration = self.beat_seconds(0.5);
         let cursor = self.cursor;
+        let length = self.span(duration);
         let waveform = self.waveform;
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
-                notes, cursor, duration, waveform, envelope, pitch_bend,
+            .add_note_with_waveform_envelope_and_bend_ticks(
+                notes, cursor, length, waveform, envelope, pitch_bend,
             );
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self
     }
 
@@ -510,23 +518,26 @@ impl<'a> TrackBuilder<'a> {
     ///     .sixteenth(&[C4]);  // One sixteenth note
     /// ```
     pub fn sixteenth(mut self, notes: &[f32]) -> Self {
-        let duration = self.tempo.eighth_note() / 2.0;
+        let duration = self.beat_seconds(0.25);
         let cursor = self.cursor;
+        let length = self.span(duration);
         let waveform = self.waveform;
         let envelope = self.envelope;
         let pitch_bend = self.pitch_bend;
         self.get_track_mut()
-            .add_note_with_waveform_envelope_and_bend(
-                notes, cursor, duration, waveform, envelope, pitch_bend,
+            .add_note_with_waveform_envelope_and_bend_ticks(
+                notes, cursor, length, waveform, envelope, pitch_bend,
             );
         let swung_duration = self.apply_swing(duration);
-        self.cursor += swung_duration;
+        self.advance(swung_duration);
         self
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::composition::timing::Ticks;
+    use crate::composition::timing::ticks::at;
     use crate::composition::Composition;
     use crate::consts::notes::*;
     use crate::composition::timing::Tempo;
@@ -625,7 +636,7 @@ mod tests {
         let builder = comp.track("test").at_bar(1);
 
         // Bar 1 starts at 0
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -634,7 +645,7 @@ mod tests {
         let builder = comp.track("test").at_bar(5);
 
         // Bar 5 = 4 bars * 4 beats * 0.5 seconds = 8.0
-        assert_eq!(builder.cursor, 8.0);
+        assert_eq!(builder.peek_cursor(), 8.0);
     }
 
     #[test]
@@ -643,7 +654,7 @@ mod tests {
         let builder = comp.track("test").at_beat(1);
 
         // Beat 1 starts at 0
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -652,7 +663,7 @@ mod tests {
         let builder = comp.track("test").at_beat(9);
 
         // Beat 9 = 8 beats * 0.5 = 4.0 seconds
-        assert_eq!(builder.cursor, 4.0);
+        assert_eq!(builder.peek_cursor(), 4.0);
     }
 
     // ===== WAIT METHODS =====
@@ -663,7 +674,7 @@ mod tests {
         let builder = comp.track("test").beats(2.0);
 
         // 2 beats at 120 BPM = 2 * 0.5 = 1.0
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     #[test]
@@ -672,7 +683,7 @@ mod tests {
         let builder = comp.track("test").beats(1.5);
 
         // 1.5 beats = 0.75 seconds at 120 BPM
-        assert_eq!(builder.cursor, 0.75);
+        assert_eq!(builder.peek_cursor(), 0.75);
     }
 
     #[test]
@@ -681,7 +692,7 @@ mod tests {
         let builder = comp.track("test").bars(2.0);
 
         // 2 bars = 2 * 4 beats * 0.5 = 4.0
-        assert_eq!(builder.cursor, 4.0);
+        assert_eq!(builder.peek_cursor(), 4.0);
     }
 
     #[test]
@@ -690,7 +701,7 @@ mod tests {
         let builder = comp.track("test").bars(0.5);
 
         // Half bar = 2 beats = 1.0 second at 120 BPM
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     // ===== MULTIPLE NOTES METHODS =====
@@ -706,7 +717,7 @@ mod tests {
         // Each whole note = 2.0 seconds at 120 BPM
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 2.0);
+                assert_eq!(note.duration, at(2.0));
             }
         }
     }
@@ -721,7 +732,7 @@ mod tests {
 
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 1.0);
+                assert_eq!(note.duration, at(1.0));
             }
         }
     }
@@ -736,7 +747,7 @@ mod tests {
 
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 0.5);
+                assert_eq!(note.duration, at(0.5));
             }
         }
     }
@@ -751,7 +762,7 @@ mod tests {
 
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 0.25);
+                assert_eq!(note.duration, at(0.25));
             }
         }
     }
@@ -767,7 +778,7 @@ mod tests {
 
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 0.125);
+                assert_eq!(note.duration, at(0.125));
             }
         }
     }
@@ -782,7 +793,7 @@ mod tests {
 
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 0.75);
+                assert_eq!(note.duration, at(0.75));
             }
         }
     }
@@ -797,7 +808,7 @@ mod tests {
 
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 0.375);
+                assert_eq!(note.duration, at(0.375));
             }
         }
     }
@@ -808,7 +819,7 @@ mod tests {
         let builder = comp.track("test").quarters(&[C4, D4, E4]);
 
         // 3 quarter notes = 3 * 0.5 = 1.5
-        assert_eq!(builder.cursor, 1.5);
+        assert_eq!(builder.peek_cursor(), 1.5);
     }
 
     // ===== SINGLE CHORD METHODS =====
@@ -822,7 +833,7 @@ mod tests {
         assert_eq!(track.events.len(), 1);
 
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 0.5);
+            assert_eq!(note.duration, at(0.5));
             assert_eq!(note.frequencies[0], C4);
         }
     }
@@ -836,7 +847,7 @@ mod tests {
         assert_eq!(track.events.len(), 1);
 
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 0.5);
+            assert_eq!(note.duration, at(0.5));
             assert!(note.frequencies.contains(&C4));
             assert!(note.frequencies.contains(&E4));
             assert!(note.frequencies.contains(&G4));
@@ -852,7 +863,7 @@ mod tests {
         assert_eq!(track.events.len(), 1);
 
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 1.0);
+            assert_eq!(note.duration, at(1.0));
         }
     }
 
@@ -865,7 +876,7 @@ mod tests {
         assert_eq!(track.events.len(), 1);
 
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 2.0);
+            assert_eq!(note.duration, at(2.0));
         }
     }
 
@@ -878,7 +889,7 @@ mod tests {
         assert_eq!(track.events.len(), 1);
 
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 0.25);
+            assert_eq!(note.duration, at(0.25));
         }
     }
 
@@ -891,7 +902,7 @@ mod tests {
         assert_eq!(track.events.len(), 1);
 
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.duration, 0.125);
+            assert_eq!(note.duration, at(0.125));
         }
     }
 
@@ -909,7 +920,7 @@ mod tests {
 
         // First note should start at bar 3
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 4.0);
+            assert_eq!(note.start_time, at(4.0));
         }
     }
 
@@ -924,7 +935,7 @@ mod tests {
             .beats(1.0) // +0.5 = 3.25
             .half(&[E4]); // +1.0 = 4.25
 
-        assert!((builder.cursor - 4.25).abs() < 0.01);
+        assert!((builder.peek_cursor() - 4.25).abs() < 0.01);
     }
 
     #[test]
@@ -932,7 +943,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").quarters(&[]);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -944,10 +955,10 @@ mod tests {
 
         // First note at 0.0, second at 2.0
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 2.0);
+            assert_eq!(note.start_time, at(2.0));
         }
     }
 
@@ -962,7 +973,7 @@ mod tests {
             .eighths(&[F4]) // +0.25 = 3.75
             .sixteenths(&[G4]); // +0.125 = 3.875
 
-        assert!((builder.cursor - 3.875).abs() < 0.01);
+        assert!((builder.peek_cursor() - 3.875).abs() < 0.01);
     }
 
     #[test]
@@ -975,7 +986,7 @@ mod tests {
         let track = &comp.into_mixer().tracks()[0];
 
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 2.0);
+            assert_eq!(note.start_time, at(2.0));
         }
     }
 
@@ -988,7 +999,7 @@ mod tests {
             .bars(1.0) // +2.0 = 2.5
             .quarter(&[E4]); // 2.5-3.0
 
-        assert_eq!(builder.cursor, 3.0);
+        assert_eq!(builder.peek_cursor(), 3.0);
     }
 
     #[test]
@@ -1003,16 +1014,16 @@ mod tests {
 
         // Verify the progression
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
-            assert_eq!(note.duration, 0.75);
+            assert_eq!(note.start_time, at(0.0));
+            assert_eq!(note.duration, at(0.75));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.75);
-            assert_eq!(note.duration, 0.75);
+            assert_eq!(note.start_time, at(0.75));
+            assert_eq!(note.duration, at(0.75));
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.start_time, 1.5);
-            assert_eq!(note.duration, 0.375);
+            assert_eq!(note.start_time, at(1.5));
+            assert_eq!(note.duration, at(0.375));
         }
     }
 
@@ -1024,6 +1035,33 @@ mod tests {
             .quarter(&[C4, E4, G4]) // Chord: 0.5
             .quarters(&[D4, E4]); // Notes: +1.0 = 1.5
 
-        assert_eq!(builder.cursor, 1.5);
+        assert_eq!(builder.peek_cursor(), 1.5);
+    }
+
+    #[test]
+    fn test_long_runs_stay_on_the_bar_grid() {
+        // Ten minutes of sixteenths at 137 BPM, then bar 401 via at_bar
+        let mut comp = Composition::new(Tempo::new(137.0));
+        let sixteenth = comp.tempo().sixteenth_note();
+        let builder = comp
+            .track("hats")
+            .notes(&[C5; 137 * 40], sixteenth)
+            .at_bar(401)
+            .note(&[C5], sixteenth);
+
+        let expected = Ticks::from_beats(1600.0).to_seconds(137.0) as f32;
+        assert_eq!(builder.peek_cursor(), expected + sixteenth);
+
+        let track = &comp.tracks["hats"];
+        let starts: Vec<Ticks> = track
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                AudioEvent::Note(note) => Some(note.start_time),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(starts[starts.len() - 2], Ticks::from_beats(1369.75));
+        assert_eq!(starts[starts.len() - 1], Ticks::from_beats(1600.0));
     }
 }
@@ -1,4 +1,4 @@
-use crate::composition::TrackBuilder;
+use crate::composition::{BuilderContext, TrackBuilder};
 
 impl<'a> TrackBuilder<'a> {
     /// Set swing/groove timing (0.5 = straight, 0.67 = triplet swing, 0.75 = heavy swing)
@@ -7,17 +7,17 @@ impl<'a> TrackBuilder<'a> {
         self
     }
     pub fn at(mut self, time: f32) -> Self {
-        self.cursor = time;
+        self.cursor = self.to_ticks(time);
         self
     }
 
     pub fn wait(mut self, duration: f32) -> Self {
-        self.cursor += duration;
+        self.advance(duration);
         self
     }
 
     pub fn seek(mut self, offset: f32) -> Self {
-        self.cursor += offset;
+        self.advance(offset);
         self
     }
 
@@ -43,9 +43,8 @@ impl<'a> TrackBuilder<'a> {
     ///     .notes(&[C2, G2], 1.0);
     /// ```
     pub fn mark(self, name: &str) -> Self {
-        self.composition
-            .markers
-            .insert(name.to_string(), self.cursor);
+        let cursor = self.peek_cursor();
+        self.composition.markers.insert(name.to_string(), cursor);
         self
     }
 
@@ -80,7 +79,7 @@ impl<'a> TrackBuilder<'a> {
                 return self;
             }
         };
-        self.cursor = marker_time;
+        self.cursor = self.to_ticks(marker_time);
         self
     }
 
@@ -123,11 +122,18 @@ impl<'a> TrackBuilder<'a> {
     /// println!("Currently at: {} seconds", current_time);
     /// ```
     pub fn peek_cursor(&self) -> f32 {
-        self.cursor
+        self.to_seconds(self.cursor)
     }
 
     /// Insert a tempo change at the current cursor position
     ///
+    /// Outside sections this also sets the composition's [`TempoMap`] from the
+    /// cursor onwards, so later musical-time helpers (`quarters`, `beats`,
+    /// `at_bar`, ...) on every track follow the new tempo. Durations given in
+    /// seconds are unaffected.
+    ///
+    /// [`TempoMap`]: crate::composition::timing::TempoMap
+    ///
     /// # Arguments
     /// * `bpm` - The new tempo in beats per minute
     ///
@@ -141,6 +147,9 @@ impl<'a> TrackBuilder<'a> {
     ///     .notes(&[C4, E4, G4], 0.5); // These notes at 90 BPM (affects MIDI export)
     /// ```
     pub fn tempo(mut self, bpm: f32) -> Self {
+        if let BuilderContext::Direct = self.context {
+            self.composition.tempo_map.set_tempo(self.cursor, bpm);
+        }
         let cursor = self.cursor;
         self.get_track_mut()
             .events
@@ -221,7 +230,8 @@ impl<'a> TrackBuilder<'a> {
 mod tests {
     use crate::composition::Composition;
     use crate::consts::notes::{C3, C4, E3, E4, G3, G4};
-    use crate::composition::timing::Tempo;
+    use crate::composition::timing::{Tempo, Ticks};
+    use crate::composition::timing::ticks::at;
 
     #[test]
     fn test_swing_sets_value() {
@@ -282,7 +292,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").at(5.0);
 
-        assert_eq!(builder.cursor, 5.0);
+        assert_eq!(builder.peek_cursor(), 5.0);
     }
 
     #[test]
@@ -291,7 +301,7 @@ mod tests {
         let builder = comp.track("test").wait(10.0).at(3.0);
 
         // Should be at 3.0, not 13.0
-        assert_eq!(builder.cursor, 3.0);
+        assert_eq!(builder.peek_cursor(), 3.0);
     }
 
     #[test]
@@ -299,7 +309,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").wait(5.0).at(0.0);
 
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -307,7 +317,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").wait(2.5);
 
-        assert_eq!(builder.cursor, 2.5);
+        assert_eq!(builder.peek_cursor(), 2.5);
     }
 
     #[test]
@@ -315,7 +325,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").wait(1.0).wait(2.0).wait(0.5);
 
-        assert_eq!(builder.cursor, 3.5);
+        assert_eq!(builder.peek_cursor(), 3.5);
     }
 
     #[test]
@@ -323,7 +333,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").wait(5.0).wait(0.0);
 
-        assert_eq!(builder.cursor, 5.0);
+        assert_eq!(builder.peek_cursor(), 5.0);
     }
 
     #[test]
@@ -331,7 +341,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").seek(3.0);
 
-        assert_eq!(builder.cursor, 3.0);
+        assert_eq!(builder.peek_cursor(), 3.0);
     }
 
     #[test]
@@ -339,7 +349,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").seek(1.5).seek(2.0);
 
-        assert_eq!(builder.cursor, 3.5);
+        assert_eq!(builder.peek_cursor(), 3.5);
     }
 
     #[test]
@@ -348,7 +358,7 @@ mod tests {
         let builder = comp.track("test").seek(5.0).seek(-2.0);
 
         // Should be at 3.0
-        assert_eq!(builder.cursor, 3.0);
+        assert_eq!(builder.peek_cursor(), 3.0);
     }
 
     #[test]
@@ -356,7 +366,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").at(0.0).wait(2.0).seek(1.0).swing(0.67);
 
-        assert_eq!(builder.cursor, 3.0);
+        assert_eq!(builder.peek_cursor(), 3.0);
         assert!((builder.swing - 0.67).abs() < 0.01);
     }
 
@@ -371,7 +381,7 @@ mod tests {
             .at(0.0) // Back to 0.0
             .wait(1.0); // Now at 1.0
 
-        assert_eq!(builder.cursor, 1.0);
+        assert_eq!(builder.peek_cursor(), 1.0);
     }
 
     #[test]
@@ -382,7 +392,7 @@ mod tests {
         let with_wait = comp1.track("wait").wait(2.5);
         let with_seek = comp2.track("seek").seek(2.5);
 
-        assert_eq!(with_wait.cursor, with_seek.cursor);
+        assert_eq!(with_wait.peek_cursor(), with_seek.peek_cursor());
     }
 
     #[test]
@@ -398,10 +408,10 @@ mod tests {
         assert_eq!(track.events.len(), 2);
 
         if let crate::track::AudioEvent::Note(note1) = &track.events[0] {
-            assert_eq!(note1.start_time, 0.0);
+            assert_eq!(note1.start_time, at(0.0));
         }
         if let crate::track::AudioEvent::Note(note2) = &track.events[1] {
-            assert_eq!(note2.start_time, 1.5); // 0.5 (after first note) + 1.0 (wait)
+            assert_eq!(note2.start_time, at(1.5)); // 0.5 (after first note) + 1.0 (wait)
         }
     }
 
@@ -415,7 +425,7 @@ mod tests {
 
         if let crate::track::AudioEvent::TempoChange(tempo) = &track.events[0] {
             assert_eq!(tempo.bpm, 90.0);
-            assert_eq!(tempo.start_time, 0.0);
+            assert_eq!(tempo.start_time, at(0.0));
         } else {
             panic!("Expected TempoChange event");
         }
@@ -429,7 +439,7 @@ mod tests {
         let track = &comp.into_mixer().tracks()[0];
         if let crate::track::AudioEvent::TempoChange(tempo) = &track.events[0] {
             assert_eq!(tempo.bpm, 140.0);
-            assert_eq!(tempo.start_time, 5.0);
+            assert_eq!(tempo.start_time, at(5.0));
         }
     }
 
@@ -448,17 +458,19 @@ mod tests {
 
         if let crate::track::AudioEvent::TempoChange(tempo1) = &track.events[0] {
             assert_eq!(tempo1.bpm, 100.0);
-            assert_eq!(tempo1.start_time, 0.0);
+            assert_eq!(tempo1.start_time, at(0.0));
         }
 
         if let crate::track::AudioEvent::TempoChange(tempo2) = &track.events[1] {
             assert_eq!(tempo2.bpm, 120.0);
-            assert_eq!(tempo2.start_time, 2.0);
+            // Two seconds at 100 BPM
+            assert_eq!(tempo2.start_time, Ticks::from_seconds(2.0, 100.0));
         }
 
         if let crate::track::AudioEvent::TempoChange(tempo3) = &track.events[2] {
             assert_eq!(tempo3.bpm, 140.0);
-            assert_eq!(tempo3.start_time, 4.0);
+            let then = Ticks::from_seconds(2.0, 100.0) + Ticks::from_seconds(2.0, 120.0);
+            assert_eq!(tempo3.start_time, then);
         }
     }
 
@@ -475,18 +487,18 @@ mod tests {
 
         // First note
         if let crate::track::AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
 
         // Tempo change
         if let crate::track::AudioEvent::TempoChange(tempo) = &track.events[1] {
             assert_eq!(tempo.bpm, 90.0);
-            assert_eq!(tempo.start_time, 0.5);
+            assert_eq!(tempo.start_time, at(0.5));
         }
 
         // Second note
         if let crate::track::AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
         }
     }
 
@@ -501,7 +513,7 @@ mod tests {
         if let crate::track::AudioEvent::TimeSignature(time_sig) = &track.events[0] {
             assert_eq!(time_sig.numerator, 3);
             assert_eq!(time_sig.denominator, 4);
-            assert_eq!(time_sig.start_time, 0.0);
+            assert_eq!(time_sig.start_time, at(0.0));
         } else {
             panic!("Expected TimeSignature event");
         }
@@ -516,7 +528,7 @@ mod tests {
         if let crate::track::AudioEvent::TimeSignature(time_sig) = &track.events[0] {
             assert_eq!(time_sig.numerator, 6);
             assert_eq!(time_sig.denominator, 8);
-            assert_eq!(time_sig.start_time, 8.0);
+            assert_eq!(time_sig.start_time, at(8.0));
         }
     }
 
@@ -536,19 +548,19 @@ mod tests {
         if let crate::track::AudioEvent::TimeSignature(time_sig1) = &track.events[0] {
             assert_eq!(time_sig1.numerator, 4);
             assert_eq!(time_sig1.denominator, 4);
-            assert_eq!(time_sig1.start_time, 0.0);
+            assert_eq!(time_sig1.start_time, at(0.0));
         }
 
         if let crate::track::AudioEvent::TimeSignature(time_sig2) = &track.events[1] {
             assert_eq!(time_sig2.numerator, 3);
             assert_eq!(time_sig2.denominator, 4);
-            assert_eq!(time_sig2.start_time, 4.0);
+            assert_eq!(time_sig2.start_time, at(4.0));
         }
 
         if let crate::track::AudioEvent::TimeSignature(time_sig3) = &track.events[2] {
             assert_eq!(time_sig3.numerator, 7);
             assert_eq!(time_sig3.denominator, 8);
-            assert_eq!(time_sig3.start_time, 7.0);
+            assert_eq!(time_sig3.start_time, at(7.0));
         }
     }
 
@@ -569,29 +581,29 @@ mod tests {
         if let crate::track::AudioEvent::TimeSignature(time_sig) = &track.events[0] {
             assert_eq!(time_sig.numerator, 4);
             assert_eq!(time_sig.denominator, 4);
-            assert_eq!(time_sig.start_time, 0.0);
+            assert_eq!(time_sig.start_time, at(0.0));
         }
 
         // First note
         if let crate::track::AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
 
         // Second note
         if let crate::track::AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
         }
 
         // Second time signature
         if let crate::track::AudioEvent::TimeSignature(time_sig) = &track.events[3] {
             assert_eq!(time_sig.numerator, 3);
             assert_eq!(time_sig.denominator, 4);
-            assert_eq!(time_sig.start_time, 1.0);
+            assert_eq!(time_sig.start_time, at(1.0));
         }
 
         // Third note
         if let crate::track::AudioEvent::Note(note) = &track.events[4] {
-            assert_eq!(note.start_time, 1.0);
+            assert_eq!(note.start_time, at(1.0));
         }
     }
 
@@ -661,7 +673,7 @@ mod tests {
 
         let builder = comp.track("track2").at_mark("drop");
 
-        assert_eq!(builder.cursor, 10.0);
+        assert_eq!(builder.peek_cursor(), 10.0);
     }
 
     #[test]
@@ -679,7 +691,7 @@ mod tests {
 
         // Note should still be at 1.0 (cursor unchanged by missing marker)
         if let crate::track::AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 1.0);
+            assert_eq!(note.start_time, at(1.0));
         }
     }
 
@@ -713,7 +725,7 @@ mod tests {
         // Track 2 uses those markers
         let bass_builder = comp.track("bass").at_mark("verse_start");
 
-        assert_eq!(bass_builder.cursor, 2.5); // 0.5 + 2.0
+        assert_eq!(bass_builder.peek_cursor(), 2.5); // 0.5 + 2.0
     }
 
     #[test]
@@ -778,12 +790,12 @@ mod tests {
         }
 
         // Sort to get consistent ordering
-        note_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        note_times.sort();
 
         // Verify we have 2 notes at the correct times
         assert_eq!(note_times.len(), 2);
-        assert_eq!(note_times[0], 8.0); // verse
-        assert_eq!(note_times[1], 24.0); // chorus
+        assert_eq!(note_times[0], at(8.0)); // verse
+        assert_eq!(note_times[1], at(24.0)); // chorus
     }
 
     #[test]
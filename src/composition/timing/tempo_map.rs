@@ -0,0 +1,404 @@
+//! Tempo maps with steps and ramps
+//!
+//! A [`TempoMap`] turns positions in beats into seconds and back. Notes are
+//! stored in ticks and each track carries the map that places them in seconds
+//! when rendering, so a passage can accelerate smoothly and changing the map
+//! moves everything already written with it (see
+//! [`Composition::set_tempo_map`](crate::composition::Composition::set_tempo_map)).
+//! MIDI export writes the map as tempo meta events and MIDI import reads them
+//! back into one.
+
+use super::ticks::Ticks;
+
+/// Shape of a tempo change between two points on a [`TempoMap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TempoCurve {
+    /// Jump to the new tempo and hold it
+    Step,
+    /// Change BPM by the same amount every beat
+    Linear,
+    /// Change BPM by the same ratio every beat, which sounds even across wide ramps
+    Exponential,
+}
+
+/// One stretch of a tempo map
+///
+/// The tempo starts at `start_bpm` at `start` and moves to `end_bpm` along
+/// `curve` by the start of the next segment. The last segment always holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TempoSegment {
+    pub start: Ticks,
+    pub start_bpm: f32,
+    pub end_bpm: f32,
+    pub curve: TempoCurve,
+}
+
+impl TempoSegment {
+    fn hold(start: Ticks, bpm: f32) -> Self {
+        Self {
+            start,
+            start_bpm: bpm,
+            end_bpm: bpm,
+            curve: TempoCurve::Step,
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.curve == TempoCurve::Step || self.start_bpm == self.end_bpm
+    }
+
+    /// Rate of change for a segment `length` beats long (BPM per beat or log-ratio per beat)
+    fn slope(&self, length: f64) -> f64 {
+        let (b0, b1) = (self.start_bpm as f64, self.end_bpm as f64);
+        match self.curve {
+            TempoCurve::Step => 0.0,
+            TempoCurve::Linear => (b1 - b0) / length,
+            TempoCurve::Exponential => (b1 / b0).ln() / length,
+        }
+    }
+
+    /// BPM `beats` into a segment `length` beats long
+    fn bpm_at(&self, beats: f64, length: f64) -> f64 {
+        let b0 = self.start_bpm as f64;
+        if self.is_constant() {
+            return b0;
+        }
+        match self.curve {
+            TempoCurve::Step => b0,
+            TempoCurve::Linear => b0 + self.slope(length) * beats,
+            TempoCurve::Exponential => b0 * (self.slope(length) * beats).exp(),
+        }
+    }
+
+    /// Seconds taken by the first `beats` of a segment `length` beats long
+    fn seconds(&self, beats: f64, length: f64) -> f64 {
+        let b0 = self.start_bpm as f64;
+        if self.is_constant() {
+            return 60.0 * beats / b0;
+        }
+        let k = self.slope(length);
+        match self.curve {
+            TempoCurve::Step => 60.0 * beats / b0,
+            TempoCurve::Linear => 60.0 / k * ((b0 + k * beats) / b0).ln(),
+            TempoCurve::Exponential => 60.0 / (b0 * k) * (1.0 - (-k * beats).exp()),
+        }
+    }
+
+    /// Beats covered by the first `seconds` of a segment `length` beats long
+    fn beats(&self, seconds: f64, length: f64) -> f64 {
+        let b0 = self.start_bpm as f64;
+        if self.is_constant() {
+            return seconds * b0 / 60.0;
+        }
+        let k = self.slope(length);
+        match self.curve {
+            TempoCurve::Step => seconds * b0 / 60.0,
+            TempoCurve::Linear => b0 / k * ((seconds * k / 60.0).exp() - 1.0),
+            TempoCurve::Exponential => -(1.0 - seconds * b0 * k / 60.0).ln() / k,
+        }
+    }
+}
+
+/// Tempo over the length of a song, as steps and ramps
+///
+/// Positions are [`Ticks`] from the start of the song. The map always has a
+/// tempo at the start; later changes are added with [`set_tempo`](Self::set_tempo)
+/// and [`ramp`](Self::ramp).
+///
+/// BPM values are clamped to 20–500, the same range as [`Tempo`](super::Tempo).
+///
+/// # Example
+/// ```
+/// use tunes::composition::timing::{TempoCurve, TempoMap, Ticks};
+///
+/// // 100 BPM, speeding up to 140 BPM over bars 5-8, then a sudden drop to 90
+/// let map = TempoMap::new(100.0)
+///     .with_ramp(Ticks::from_beats(16.0), Ticks::from_beats(32.0), 140.0, TempoCurve::Linear)
+///     .with_tempo(Ticks::from_beats(64.0), 90.0);
+///
+/// assert_eq!(map.bpm_at(Ticks::from_beats(8.0)), 100.0);
+/// assert_eq!(map.bpm_at(Ticks::from_beats(24.0)), 120.0);
+/// assert_eq!(map.bpm_at(Ticks::from_beats(40.0)), 140.0);
+/// assert_eq!(map.seconds_at(Ticks::from_beats(16.0)), 9.6);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TempoMap {
+    segments: Vec<TempoSegment>,
+}
+
+impl TempoMap {
+    /// Create a map that holds one tempo for the whole song
+    pub fn new(bpm: f32) -> Self {
+        Self {
+            segments: vec![TempoSegment::hold(Ticks::ZERO, clamp_bpm(bpm))],
+        }
+    }
+
+    /// Builder form of [`set_tempo`](Self::set_tempo)
+    pub fn with_tempo(mut self, at: Ticks, bpm: f32) -> Self {
+        self.set_tempo(at, bpm);
+        self
+    }
+
+    /// Builder form of [`ramp`](Self::ramp)
+    pub fn with_ramp(mut self, from: Ticks, to: Ticks, bpm: f32, curve: TempoCurve) -> Self {
+        self.ramp(from, to, bpm, curve);
+        self
+    }
+
+    /// Jump to `bpm` at `at` and hold it until the next change
+    ///
+    /// A step in the middle of a ramp cuts the rest of the ramp off.
+    pub fn set_tempo(&mut self, at: Ticks, bpm: f32) {
+        let at = at.max(Ticks::ZERO);
+        self.split(at);
+        let bpm = clamp_bpm(bpm);
+        let index = self.index_of(at);
+        let ramp = self.segments[index];
+        if !ramp.is_constant() {
+            // Drop the tempo the ramp was heading for along with the ramp
+            if let Some(next) = self.segments.get(index + 1) {
+                if next.is_constant() && next.start_bpm == ramp.end_bpm {
+                    self.segments.remove(index + 1);
+                }
+            }
+        }
+        self.segments[index] = TempoSegment::hold(at, bpm);
+    }
+
+    /// Move from the tempo at `from` to `bpm` by `to`, then hold `bpm`
+    ///
+    /// Changes between `from` and `to` are replaced; changes after `to` are kept.
+    /// A [`TempoCurve::Step`] ramp is the same as [`set_tempo`](Self::set_tempo) at `to`.
+    pub fn ramp(&mut self, from: Ticks, to: Ticks, bpm: f32, curve: TempoCurve) {
+        let from = from.max(Ticks::ZERO);
+        if to <= from || curve == TempoCurve::Step {
+            self.set_tempo(to.max(from), bpm);
+            return;
+        }
+
+        let bpm = clamp_bpm(bpm);
+        let start_bpm = self.bpm_at(from);
+        self.split(from);
+        self.split(to);
+        self.segments.retain(|s| s.start < from || s.start > to);
+
+        let after = self.segments.partition_point(|s| s.start < from);
+        self.segments.insert(
+            after,
+            TempoSegment {
+                start: from,
+                start_bpm,
+                end_bpm: bpm,
+                curve,
+            },
+        );
+        self.segments.insert(after + 1, TempoSegment::hold(to, bpm));
+    }
+
+    /// Get the segments of this map in time order
+    pub fn segments(&self) -> &[TempoSegment] {
+        &self.segments
+    }
+
+    /// Check whether the tempo never changes
+    pub fn is_constant(&self) -> bool {
+        self.segments.len() == 1
+    }
+
+    /// Get the BPM at a position
+    pub fn bpm_at(&self, at: Ticks) -> f32 {
+        let index = self.segment_at(at);
+        let segment = &self.segments[index];
+        let offset = (at - segment.start).beats().max(0.0);
+        segment.bpm_at(offset, self.length(index)) as f32
+    }
+
+    /// Convert a position to seconds from the start of the song
+    pub fn seconds_at(&self, at: Ticks) -> f64 {
+        let mut seconds = 0.0;
+        for (index, segment) in self.segments.iter().enumerate() {
+            let length = self.length(index);
+            let offset = (at - segment.start).beats();
+            if index + 1 == self.segments.len() || offset < length {
+                return seconds + segment.seconds(offset, length);
+            }
+            seconds += segment.seconds(length, length);
+        }
+        seconds
+    }
+
+    /// Convert seconds from the start of the song to the nearest tick
+    pub fn ticks_at(&self, seconds: f64) -> Ticks {
+        let mut elapsed = 0.0;
+        for (index, segment) in self.segments.iter().enumerate() {
+            let length = self.length(index);
+            let duration = segment.seconds(length, length);
+            if index + 1 == self.segments.len() || seconds - elapsed < duration {
+                let beats = segment.start.beats() + segment.beats(seconds - elapsed, length);
+                return Ticks::from_beats(beats);
+            }
+            elapsed += duration;
+        }
+        Ticks::ZERO
+    }
+
+    /// Convert beats from the start of the song to seconds
+    pub fn beats_to_seconds(&self, beats: f64) -> f64 {
+        self.seconds_at(Ticks::from_beats(beats))
+    }
+
+    /// Convert seconds from the start of the song to beats
+    pub fn seconds_to_beats(&self, seconds: f64) -> f64 {
+        self.ticks_at(seconds).beats()
+    }
+
+    /// Approximate the map with steps no further apart than `resolution`
+    ///
+    /// Each step uses the average tempo of the stretch it covers, so the steps
+    /// reach every boundary at exactly the same time as the ramp. Used where
+    /// only stepped tempo changes can be stored, such as MIDI files.
+    pub fn to_steps(&self, resolution: Ticks) -> Vec<(Ticks, f32)> {
+        let resolution = resolution.max(Ticks(1));
+        let mut steps = Vec::new();
+        for (index, segment) in self.segments.iter().enumerate() {
+            if segment.is_constant() || index + 1 == self.segments.len() {
+                steps.push((segment.start, segment.start_bpm));
+                continue;
+            }
+            let end = self.segments[index + 1].start;
+            let mut at = segment.start;
+            while at < end {
+                let next = (at + resolution).min(end);
+                let seconds = self.seconds_at(next) - self.seconds_at(at);
+                steps.push((at, (60.0 * (next - at).beats() / seconds) as f32));
+                at = next;
+            }
+        }
+        steps
+    }
+
+    /// Length in beats of the segment at `index` (infinite for the last one)
+    fn length(&self, index: usize) -> f64 {
+        match self.segments.get(index + 1) {
+            Some(next) => (next.start - self.segments[index].start).beats(),
+            None => f64::INFINITY,
+        }
+    }
+
+    /// Index of the segment that contains `at`
+    fn segment_at(&self, at: Ticks) -> usize {
+        self.segments
+            .partition_point(|s| s.start <= at)
+            .saturating_sub(1)
+    }
+
+    /// Index of the segment starting exactly at `at` (call [`split`](Self::split) first)
+    fn index_of(&self, at: Ticks) -> usize {
+        self.segments.partition_point(|s| s.start < at)
+    }
+
+    /// Make sure a segment starts at `at`, cutting the one that spans it in two
+    fn split(&mut self, at: Ticks) {
+        let index = self.segment_at(at);
+        if self.segments[index].start == at {
+            return;
+        }
+        let bpm = self.bpm_at(at);
+        let segment = self.segments[index];
+        self.segments[index].end_bpm = bpm;
+        self.segments.insert(
+            index + 1,
+            TempoSegment {
+                start: at,
+                start_bpm: bpm,
+                end_bpm: segment.end_bpm,
+                curve: segment.curve,
+            },
+        );
+    }
+}
+
+impl Default for TempoMap {
+    fn default() -> Self {
+        Self::new(120.0)
+    }
+}
+
+fn clamp_bpm(bpm: f32) -> f32 {
+    bpm.clamp(20.0, 500.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn beats(b: f64) -> Ticks {
+        Ticks::from_beats(b)
+    }
+
+    #[test]
+    fn test_constant_map_matches_ticks() {
+        let map = TempoMap::new(137.0);
+        let at = beats(1234.5);
+        assert_eq!(map.seconds_at(at), at.to_seconds(137.0));
+        assert_eq!(map.ticks_at(map.seconds_at(at)), at);
+        assert!(map.is_constant());
+    }
+
+    #[test]
+    fn test_steps_change_tempo_from_their_position() {
+        let map = TempoMap::new(120.0).with_tempo(beats(4.0), 60.0);
+        assert_eq!(map.seconds_at(beats(4.0)), 2.0);
+        assert_eq!(map.seconds_at(beats(6.0)), 4.0);
+        assert_eq!(map.ticks_at(4.0), beats(6.0));
+        assert_eq!(map.bpm_at(beats(3.9)), 120.0);
+        assert_eq!(map.bpm_at(beats(4.0)), 60.0);
+    }
+
+    #[test]
+    fn test_linear_ramp_integrates_tempo() {
+        let map = TempoMap::new(60.0).with_ramp(beats(0.0), beats(4.0), 120.0, TempoCurve::Linear);
+        // ∫ 60 / (60 + 15b) db over 0..4 = 4 ln 2
+        let expected = 4.0 * 2f64.ln();
+        assert!((map.seconds_at(beats(4.0)) - expected).abs() < 1e-9);
+        assert!((map.seconds_at(beats(6.0)) - expected - 1.0).abs() < 1e-9);
+        assert_eq!(map.bpm_at(beats(2.0)), 90.0);
+        assert_eq!(map.ticks_at(map.seconds_at(beats(3.0))), beats(3.0));
+    }
+
+    #[test]
+    fn test_exponential_ramp_doubles_evenly() {
+        let map =
+            TempoMap::new(60.0).with_ramp(beats(0.0), beats(2.0), 240.0, TempoCurve::Exponential);
+        assert!((map.bpm_at(beats(1.0)) - 120.0).abs() < 1e-3);
+        // ∫ 60 / (60·2^b) db over 0..2 = (1 - 1/4) / ln 2
+        let expected = 0.75 / 2f64.ln();
+        assert!((map.seconds_at(beats(2.0)) - expected).abs() < 1e-9);
+        assert_eq!(map.ticks_at(map.seconds_at(beats(1.5))), beats(1.5));
+    }
+
+    #[test]
+    fn test_step_inside_ramp_cuts_it_off() {
+        let map = TempoMap::new(100.0)
+            .with_ramp(beats(0.0), beats(8.0), 180.0, TempoCurve::Linear)
+            .with_tempo(beats(4.0), 70.0);
+        assert_eq!(map.bpm_at(beats(2.0)), 120.0);
+        assert_eq!(map.bpm_at(beats(6.0)), 70.0);
+        assert_eq!(map.bpm_at(beats(10.0)), 70.0);
+    }
+
+    #[test]
+    fn test_steps_reach_ramp_boundaries_on_time() {
+        let map = TempoMap::new(90.0).with_ramp(beats(4.0), beats(12.0), 150.0, TempoCurve::Linear);
+        let steps = map.to_steps(beats(0.25));
+        let mut stepped = TempoMap::new(steps[0].1);
+        for &(at, bpm) in &steps[1..] {
+            stepped.set_tempo(at, bpm);
+        }
+        for b in [4.0, 7.25, 12.0, 20.0] {
+            let diff = stepped.seconds_at(beats(b)) - map.seconds_at(beats(b));
+            assert!(diff.abs() < 1e-5, "beat {}: {}", b, diff);
+        }
+    }
+}
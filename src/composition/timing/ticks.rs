@@ -0,0 +1,157 @@
+//! Tick-based musical time
+//!
+//! Builder cursors and event positions are whole numbers of ticks rather than
+//! `f32` seconds. Adding thousands of notes and waits never drifts, bars and
+//! beats land exactly where they should, and MIDI ticks fall out by integer
+//! division. Events keep their ticks; the track's tempo map places them in
+//! seconds, and samples, only when the mixer renders.
+
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+/// Ticks per quarter-note beat
+///
+/// 705,600 = 2⁶·3²·5²·7², so halves through sixty-fourths, triplets, quintuplets,
+/// septuplets and the common MIDI resolutions (96, 192, 480, 960 PPQ) all divide
+/// it exactly. At 120 BPM one tick is under a microsecond.
+pub const TICKS_PER_BEAT: i64 = 705_600;
+
+/// A position or length in musical time, measured in ticks of a quarter note
+///
+/// # Example
+/// ```
+/// use tunes::composition::timing::Ticks;
+///
+/// let bar = Ticks::from_beats(4.0);
+/// let triplet = Ticks::from_beats(1.0 / 3.0);
+/// assert_eq!(triplet + triplet + triplet, Ticks::from_beats(1.0));
+/// assert_eq!(bar.to_seconds(120.0), 2.0);
+/// assert_eq!(bar.to_midi(480), 1920);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ticks(pub i64);
+
+impl Ticks {
+    /// The start of the timeline
+    pub const ZERO: Ticks = Ticks(0);
+
+    /// Convert a number of quarter-note beats to ticks, rounding to the nearest tick
+    pub fn from_beats(beats: f64) -> Self {
+        Ticks((beats * TICKS_PER_BEAT as f64).round() as i64)
+    }
+
+    /// Length in quarter-note beats
+    pub fn beats(self) -> f64 {
+        self.0 as f64 / TICKS_PER_BEAT as f64
+    }
+
+    /// Convert seconds at a constant tempo to ticks
+    pub fn from_seconds(seconds: f64, bpm: f32) -> Self {
+        Self::from_beats(seconds * bpm as f64 / 60.0)
+    }
+
+    /// Multiply by a factor, rounding to the nearest tick
+    pub fn scale(self, factor: f64) -> Self {
+        Ticks((self.0 as f64 * factor).round() as i64)
+    }
+
+    /// Convert to seconds at a constant tempo
+    pub fn to_seconds(self, bpm: f32) -> f64 {
+        self.beats() * 60.0 / bpm as f64
+    }
+
+    /// Convert to a sample index at a constant tempo, rounding to the nearest sample
+    pub fn to_samples(self, bpm: f32, sample_rate: u32) -> i64 {
+        (self.to_seconds(bpm) * sample_rate as f64).round() as i64
+    }
+
+    /// Convert to MIDI ticks at the given resolution, rounding to the nearest tick
+    pub fn to_midi(self, ppq: u16) -> u32 {
+        let scaled = self.0.max(0) as i128 * ppq as i128;
+        let beat = TICKS_PER_BEAT as i128;
+        ((scaled + beat / 2) / beat) as u32
+    }
+
+    /// Convert MIDI ticks at the given resolution to ticks
+    pub fn from_midi(ticks: u32, ppq: u16) -> Self {
+        let scaled = ticks as i128 * TICKS_PER_BEAT as i128;
+        let ppq = ppq.max(1) as i128;
+        Ticks(((scaled + ppq / 2) / ppq) as i64)
+    }
+}
+
+impl Add for Ticks {
+    type Output = Ticks;
+
+    fn add(self, other: Ticks) -> Ticks {
+        Ticks(self.0 + other.0)
+    }
+}
+
+impl Sub for Ticks {
+    type Output = Ticks;
+
+    fn sub(self, other: Ticks) -> Ticks {
+        Ticks(self.0 - other.0)
+    }
+}
+
+impl Mul<i64> for Ticks {
+    type Output = Ticks;
+
+    fn mul(self, times: i64) -> Ticks {
+        Ticks(self.0 * times)
+    }
+}
+
+impl AddAssign for Ticks {
+    fn add_assign(&mut self, other: Ticks) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Ticks {
+    fn sub_assign(&mut self, other: Ticks) {
+        self.0 -= other.0;
+    }
+}
+
+/// Ticks at `seconds` into a 120 BPM song, for tests
+#[cfg(test)]
+pub(crate) fn at(seconds: f64) -> Ticks {
+    Ticks::from_seconds(seconds, 120.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_divisions_are_exact() {
+        for division in [2, 3, 4, 5, 6, 7, 8, 9, 12, 16, 32, 64] {
+            assert_eq!(TICKS_PER_BEAT % division, 0, "1/{} beat", division);
+        }
+        for ppq in [96, 192, 480, 960] {
+            assert_eq!(TICKS_PER_BEAT % ppq, 0, "{} PPQ", ppq);
+        }
+    }
+
+    #[test]
+    fn test_long_runs_do_not_drift() {
+        // Ten minutes of sixteenth notes at 137 BPM
+        let sixteenth = Ticks::from_seconds(60.0 / 137.0 / 4.0, 137.0);
+        let mut cursor = Ticks::ZERO;
+        for _ in 0..(137 * 10 * 4) {
+            cursor += sixteenth;
+        }
+        assert_eq!(cursor, Ticks::from_beats(1370.0));
+        assert_eq!(cursor.to_samples(137.0, 44100), 44100 * 600);
+    }
+
+    #[test]
+    fn test_midi_round_trip() {
+        let position = Ticks::from_beats(7.25);
+        assert_eq!(position.to_midi(480), 3480);
+        assert_eq!(Ticks::from_midi(3480, 480), position);
+        assert_eq!(Ticks(-5).to_midi(480), 0);
+    }
+}
@@ -34,17 +34,18 @@ impl<'a> TrackBuilder<'a> {
 
         for &freq in notes {
             let cursor = self.cursor;
+            let length = self.span(note_duration);
             self.get_track_mut()
-                .add_note_with_waveform_envelope_and_bend(
+                .add_note_with_waveform_envelope_and_bend_ticks(
                     &[freq],
                     cursor,
-                    note_duration,
+                    length,
                     waveform,
                     envelope,
                     pitch_bend,
                 );
             let swung_duration = self.apply_swing(note_duration);
-            self.cursor += swung_duration;
+            self.advance(swung_duration);
         }
 
         self.update_section_duration();
@@ -150,6 +151,7 @@ mod tests {
     use crate::composition::Composition;
     use crate::consts::notes::*;
     use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
     use crate::track::AudioEvent;
 
     #[test]
@@ -163,7 +165,7 @@ mod tests {
         // Each note should have duration 1.5 / 3 = 0.5
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 0.5);
+                assert_eq!(note.duration, at(0.5));
             }
         }
     }
@@ -174,7 +176,7 @@ mod tests {
         let builder = comp.track("test").tuplet(&[C4, D4, E4, F4], 4, 2.0);
 
         // 4 notes * 0.5 each = 2.0
-        assert!((builder.cursor - 2.0).abs() < 0.01);
+        assert!((builder.peek_cursor() - 2.0).abs() < 0.01);
     }
 
     #[test]
@@ -183,7 +185,7 @@ mod tests {
         let builder = comp.track("test").tuplet(&[C4, D4], 0, 1.0);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -192,7 +194,7 @@ mod tests {
         let builder = comp.track("test").tuplet(&[C4, D4], 2, 0.0);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -201,7 +203,7 @@ mod tests {
         let builder = comp.track("test").tuplet(&[C4, D4], 2, -1.0);
 
         // Should be no-op
-        assert_eq!(builder.cursor, 0.0);
+        assert_eq!(builder.peek_cursor(), 0.0);
     }
 
     #[test]
@@ -215,7 +217,7 @@ mod tests {
         // Each should be 1.0 / 3 ≈ 0.333...
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert!((note.duration - 1.0 / 3.0).abs() < 0.01);
+                assert!((note.duration - at(1.0 / 3.0)).0.abs() < at(0.01).0);
             }
         }
     }
@@ -229,13 +231,13 @@ mod tests {
 
         // Notes should start at 0.0, 0.5, 1.0
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert!((note.start_time - 0.5).abs() < 0.01);
+            assert!((note.start_time - at(0.5)).0.abs() < at(0.01).0);
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert!((note.start_time - 1.0).abs() < 0.01);
+            assert!((note.start_time - at(1.0)).0.abs() < at(0.01).0);
         }
     }
 
@@ -250,7 +252,7 @@ mod tests {
         // Each should be 2.0 / 5 = 0.4
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert!((note.duration - 0.4).abs() < 0.01);
+                assert!((note.duration - at(0.4)).0.abs() < at(0.01).0);
             }
         }
     }
@@ -266,7 +268,7 @@ mod tests {
         // Each should be 3.0 / 6 = 0.5
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 0.5);
+                assert_eq!(note.duration, at(0.5));
             }
         }
     }
@@ -283,7 +285,7 @@ mod tests {
         // Each should be 1.4 / 7 = 0.2
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert!((note.duration - 0.2).abs() < 0.01);
+                assert!((note.duration - at(0.2)).0.abs() < at(0.01).0);
             }
         }
     }
@@ -300,7 +302,7 @@ mod tests {
         // All notes should have the calculated duration
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert_eq!(note.duration, 0.5);
+                assert_eq!(note.duration, at(0.5));
             }
         }
     }
@@ -316,7 +318,7 @@ mod tests {
 
         for event in &track.events {
             if let AudioEvent::Note(note) = event {
-                assert!((note.duration - 0.4).abs() < 0.01);
+                assert!((note.duration - at(0.4)).0.abs() < at(0.01).0);
             }
         }
     }
@@ -338,7 +340,7 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").tuplet(&[], 3, 1.0);
 
-        assert!((builder.cursor - 0.0).abs() < 0.01);
+        assert!((builder.peek_cursor() - 0.0).abs() < 0.01);
     }
 
     #[test]
@@ -350,16 +352,16 @@ mod tests {
 
         // Verify exact start times: 0, 0.25, 0.5, 0.75
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert!((note.start_time - 0.25).abs() < 0.001);
+            assert!((note.start_time - at(0.25)).0.abs() < at(0.001).0);
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert!((note.start_time - 0.5).abs() < 0.001);
+            assert!((note.start_time - at(0.5)).0.abs() < at(0.001).0);
         }
         if let AudioEvent::Note(note) = &track.events[3] {
-            assert!((note.start_time - 0.75).abs() < 0.001);
+            assert!((note.start_time - at(0.75)).0.abs() < at(0.001).0);
         }
     }
 
@@ -368,6 +370,6 @@ mod tests {
         let mut comp = Composition::new(Tempo::new(120.0));
         let builder = comp.track("test").triplet(&[C4, E4, G4], 1.5);
 
-        assert!((builder.cursor - 1.5).abs() < 0.01);
+        assert!((builder.peek_cursor() - 1.5).abs() < 0.01);
     }
 }
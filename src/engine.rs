@@ -3,9 +3,9 @@ use crate::error::{Result, TunesError};
 use crate::synthesis::spatial::{
     ListenerConfig, SpatialParams, SpatialPosition, calculate_spatial,
 };
-use crate::track::Mixer;
+use crate::track::{Mixer, MixerScene};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use crossbeam::channel::{Receiver, Sender, unbounded};
+use crossbeam::channel::{Receiver, Sender, bounded, unbounded};
 use ringbuf::{
     HeapRb,
     traits::{Consumer, Observer, Producer, Split},
@@ -28,11 +28,15 @@ use symphonia::core::sample::Sample as SymphoniaSample;
 /// Unique identifier for playing sounds
 pub type SoundId = u64;
 
+/// Scenes the audio thread can hand back before the control thread frees them
+const RETIRED_SCENE_CAPACITY: usize = 64;
+
 /// Commands sent from main thread to audio thread
 enum AudioCommand {
     Play {
         id: SoundId,
         mixer: Mixer,
+        scene: Box<MixerScene>, // The mixer's mix, captured before sending
         looping: bool,
     },
     Stop {
@@ -78,6 +82,13 @@ enum AudioCommand {
         target_rate: f32, // Target playback rate
         duration: f32,    // Duration in seconds
     },
+    MorphScene {
+        id: SoundId,
+        scene: Box<MixerScene>, // Target mixer scene
+        start: Box<MixerScene>, // Storage for the start of the morph (a copy of the target)
+        mix: Box<MixerScene>,   // Storage for the blended mix (a copy of the target)
+        duration: f32,          // Duration in seconds (0.0 = instant recall)
+    },
     SetSoundPosition {
         id: SoundId,
         position: SpatialPosition,
@@ -159,6 +170,21 @@ struct ActiveSound {
     rate_tween_duration: f32,
     rate_tween_start_value: f32,
     rate_tween_target_value: f32,
+    // Mixer scene morph state
+    scene_mix: Box<MixerScene>, // Mix last applied by the engine, where a new morph starts
+    scene_morph_start_time: Option<f32>,
+    scene_morph_duration: f32,
+    scene_morph_start_scene: Option<Box<MixerScene>>,
+    scene_morph_target_scene: Option<Box<MixerScene>>,
+}
+
+/// Hand a scene the audio thread is done with to the control thread to free
+///
+/// Dropped here instead only if the control thread has let the channel fill up.
+fn retire_scene(retired_scenes: &Sender<Box<MixerScene>>, scene: Option<Box<MixerScene>>) {
+    if let Some(scene) = scene {
+        let _ = retired_scenes.try_send(scene);
+    }
 }
 
 /// State for a streaming audio source
@@ -414,6 +440,7 @@ fn decoder_thread_func(
 /// Central audio engine that manages playback with concurrent mixing
 pub struct AudioEngine {
     command_tx: Sender<AudioCommand>,
+    retired_scenes: Receiver<Box<MixerScene>>, // Scenes replaced on the audio thread, freed here
     next_id: Arc<AtomicU64>,
     callback_state: Arc<Mutex<AudioCallbackState>>,
     #[allow(dead_code)] // Reserved for future spatial audio runtime control
@@ -505,6 +532,7 @@ impl AudioEngine {
 
         // Create command channel for communication with audio thread
         let (command_tx, command_rx): (Sender<AudioCommand>, Receiver<AudioCommand>) = unbounded();
+        let (retired_scenes_tx, retired_scenes) = bounded(RETIRED_SCENE_CAPACITY);
 
         // Shared state for audio callback (includes pre-allocated buffers)
         let callback_state: Arc<Mutex<AudioCallbackState>> =
@@ -553,6 +581,7 @@ impl AudioEngine {
                             &mut listener,
                             &mut spatial,
                             sample_rate,
+                            &retired_scenes_tx,
                         );
                     }
 
@@ -566,6 +595,7 @@ impl AudioEngine {
                         &spatial,
                         sample_rate,
                         channels,
+                        &retired_scenes_tx,
                     );
 
                     // Mix streaming sounds into the output buffer
@@ -587,6 +617,7 @@ impl AudioEngine {
 
         Ok(Self {
             command_tx,
+            retired_scenes,
             next_id: Arc::new(AtomicU64::new(1)),
             callback_state,
             listener_config,
@@ -656,9 +687,15 @@ impl AudioEngine {
         listener: &mut ListenerConfig,
         spatial: &mut SpatialParams,
         sample_rate: f32,
+        retired_scenes: &Sender<Box<MixerScene>>,
     ) {
         match cmd {
-            AudioCommand::Play { id, mixer, looping } => {
+            AudioCommand::Play {
+                id,
+                mixer,
+                scene,
+                looping,
+            } => {
                 active_sounds.insert(
                     id,
                     ActiveSound {
@@ -683,6 +720,11 @@ impl AudioEngine {
                         rate_tween_duration: 0.0,
                         rate_tween_start_value: 1.0,
                         rate_tween_target_value: 1.0,
+                        scene_mix: scene,
+                        scene_morph_start_time: None,
+                        scene_morph_duration: 0.0,
+                        scene_morph_start_scene: None,
+                        scene_morph_target_scene: None,
                     },
                 );
             }
@@ -789,6 +831,39 @@ impl AudioEngine {
                     sound.pan_tween_target_value = target_pan.clamp(-1.0, 1.0);
                 }
             }
+            AudioCommand::MorphScene {
+                id,
+                scene,
+                mut start,
+                mix,
+                duration,
+            } => {
+                // Every scene replaced here goes back to the control thread to be freed
+                let retire = |scene| retire_scene(retired_scenes, scene);
+                let Some(sound) = active_sounds.get_mut(&id) else {
+                    for unused in [scene, start, mix] {
+                        retire(Some(unused));
+                    }
+                    return;
+                };
+                if duration <= 0.0 {
+                    sound.mixer.recall_scene(&scene);
+                    retire(Some(std::mem::replace(&mut sound.scene_mix, scene)));
+                    retire(Some(start));
+                    retire(Some(mix));
+                    sound.scene_morph_start_time = None;
+                    retire(sound.scene_morph_start_scene.take());
+                    retire(sound.scene_morph_target_scene.take());
+                } else {
+                    // Start from the current mix, even partway through another morph
+                    sound.scene_mix.interpolate_into(&scene, 0.0, &mut start);
+                    retire(Some(std::mem::replace(&mut sound.scene_mix, mix)));
+                    sound.scene_morph_start_time = Some(sound.elapsed_time);
+                    sound.scene_morph_duration = duration;
+                    retire(sound.scene_morph_start_scene.replace(start));
+                    retire(sound.scene_morph_target_scene.replace(scene));
+                }
+            }
             AudioCommand::TweenPlaybackRate {
                 id,
                 target_rate,
@@ -884,6 +959,7 @@ impl AudioEngine {
         spatial_params: &SpatialParams,
         sample_rate: f32,
         channels: usize,
+        retired_scenes: &Sender<Box<MixerScene>>,
     ) {
         // Clear output buffer
         output.fill(0.0);
@@ -927,6 +1003,25 @@ impl AudioEngine {
                 (Some(listener), Some(spatial_params)) // Use composition-time position
             };
 
+            // Apply scene morph if active (before the mixer renders this block)
+            if let Some(morph_start) = sound.scene_morph_start_time {
+                let morph_elapsed = sound.elapsed_time - morph_start;
+                if let (Some(from), Some(to)) = (
+                    sound.scene_morph_start_scene.as_ref(),
+                    sound.scene_morph_target_scene.as_ref(),
+                ) {
+                    let t = (morph_elapsed / sound.scene_morph_duration).clamp(0.0, 1.0);
+                    from.interpolate_into(to, t, &mut sound.scene_mix);
+                    sound.mixer.recall_scene(&sound.scene_mix);
+                }
+                if morph_elapsed >= sound.scene_morph_duration {
+                    // Morph complete
+                    sound.scene_morph_start_time = None;
+                    retire_scene(retired_scenes, sound.scene_morph_start_scene.take());
+                    retire_scene(retired_scenes, sound.scene_morph_target_scene.take());
+                }
+            }
+
             // Process entire block at once
             temp_buffer.fill(0.0);
             sound.mixer.process_block(
@@ -1155,21 +1250,22 @@ impl AudioEngine {
     pub fn play_mixer_realtime(&self, mixer: &Mixer) -> Result<SoundId> {
         let id = self.next_id.fetch_add(1, Ordering::Relaxed);
 
-        // Clone mixer and automatically enable GPU if engine was created with GPU support
-        #[cfg(feature = "gpu")]
+        // Clone mixer, size its effects for the output device and
+        // automatically enable GPU if engine was created with GPU support
         let mut mixer_clone = mixer.clone();
-        #[cfg(not(feature = "gpu"))]
-        let mixer_clone = mixer.clone();
+        mixer_clone.prepare(self.sample_rate, self.buffer_size as usize);
 
         #[cfg(feature = "gpu")]
         if self.enable_gpu_for_samples {
             mixer_clone.enable_gpu();
         }
 
+        let scene = Box::new(mixer_clone.capture_scene("playing"));
         self.command_tx
             .send(AudioCommand::Play {
                 id,
                 mixer: mixer_clone,
+                scene,
                 looping: false,
             })
             .map_err(|_| TunesError::AudioEngineError("Audio engine stopped".to_string()))?;
@@ -1271,21 +1367,22 @@ impl AudioEngine {
     pub fn play_looping(&self, mixer: &Mixer) -> Result<SoundId> {
         let id = self.next_id.fetch_add(1, Ordering::Relaxed);
 
-        // Clone mixer and automatically enable GPU if engine was created with GPU support
-        #[cfg(feature = "gpu")]
+        // Clone mixer, size its effects for the output device and
+        // automatically enable GPU if engine was created with GPU support
         let mut mixer_clone = mixer.clone();
-        #[cfg(not(feature = "gpu"))]
-        let mixer_clone = mixer.clone();
+        mixer_clone.prepare(self.sample_rate, self.buffer_size as usize);
 
         #[cfg(feature = "gpu")]
         if self.enable_gpu_for_samples {
             mixer_clone.enable_gpu();
         }
 
+        let scene = Box::new(mixer_clone.capture_scene("playing"));
         self.command_tx
             .send(AudioCommand::Play {
                 id,
                 mixer: mixer_clone,
+                scene,
                 looping: true,
             })
             .map_err(|_| TunesError::AudioEngineError("Audio engine stopped".to_string()))?;
@@ -1718,6 +1815,86 @@ impl AudioEngine {
         Ok(())
     }
 
+    /// Instantly apply a mixer scene to a playing sound
+    ///
+    /// Sets the volume, pan, mute/solo and effect parameters of the sound's tracks,
+    /// buses and master chain to the values stored in the scene. Capture scenes with
+    /// [`Mixer::capture_scene`] before starting playback.
+    ///
+    /// # Arguments
+    /// * `id` - The sound to change
+    /// * `scene` - The scene to recall
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::prelude::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// # let engine = AudioEngine::new()?;
+    /// let mut mixer = comp.into_mixer();
+    /// let normal = mixer.capture_scene("normal");
+    /// mixer.get_bus_mut("default").unwrap().volume = 0.3;
+    /// let quiet = mixer.capture_scene("quiet");
+    ///
+    /// let id = engine.play_mixer_realtime(&mixer)?;
+    /// engine.recall_scene(id, &normal)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn recall_scene(&self, id: SoundId, scene: &MixerScene) -> Result<()> {
+        self.morph_scene(id, scene, 0.0)
+    }
+
+    /// Smoothly morph a playing sound into a mixer scene
+    ///
+    /// Interpolates every volume, pan and effect parameter from its current value to
+    /// the scene's value over the specified duration. Mute and solo switch when the
+    /// morph completes. Starting a new morph replaces one that is still running and
+    /// continues from wherever it had reached.
+    ///
+    /// The current value is the mix the engine last set: the mixer as it was passed to
+    /// playback, or the last scene recalled or morphed into. Changes the mixer makes to
+    /// itself while playing, such as its own scheduled scenes, are not tracked.
+    ///
+    /// # Arguments
+    /// * `id` - The sound to morph
+    /// * `scene` - Target scene
+    /// * `duration` - Morph duration in seconds (0.0 = instant)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::prelude::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// # let engine = AudioEngine::new()?;
+    /// let mut mixer = comp.into_mixer();
+    /// mixer.master_reverb(Reverb::new(0.9, 0.3, 0.0));
+    /// let explore = mixer.capture_scene("explore");
+    /// mixer.master.reverb.as_mut().unwrap().mix = 0.6;
+    /// let underwater = mixer.capture_scene("underwater");
+    ///
+    /// let id = engine.play_mixer_realtime(&mixer)?;
+    /// engine.recall_scene(id, &explore)?;
+    /// engine.morph_scene(id, &underwater, 3.0)?; // Dive in over 3 seconds
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn morph_scene(&self, id: SoundId, scene: &MixerScene, duration: f32) -> Result<()> {
+        // Free the scenes earlier morphs left behind
+        while self.retired_scenes.try_recv().is_ok() {}
+
+        self.command_tx
+            .send(AudioCommand::MorphScene {
+                id,
+                scene: Box::new(scene.clone()),
+                start: Box::new(scene.clone()),
+                mix: Box::new(scene.clone()),
+                duration,
+            })
+            .map_err(|_| TunesError::AudioEngineError("Audio engine stopped".to_string()))?;
+        Ok(())
+    }
+
     /// Smoothly tween the playback rate (pitch and speed) of a playing sound
     ///
     /// Gradually changes the playback rate from its current value to the target rate
@@ -57,6 +57,9 @@ pub enum TunesError {
     /// IO error
     IoError(String),
 
+    /// Export was cancelled by its progress callback
+    ExportCancelled,
+
     /// Generic error for cases not covered above
     Other(String),
 }
@@ -125,6 +128,9 @@ impl fmt::Display for TunesError {
             TunesError::IoError(msg) => {
                 write!(f, "IO error: {}", msg)
             }
+            TunesError::ExportCancelled => {
+                write!(f, "Export cancelled")
+            }
             TunesError::Other(msg) => {
                 write!(f, "{}", msg)
             }
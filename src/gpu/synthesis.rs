@@ -42,6 +42,10 @@ struct GpuNoteParams {
 
     velocity: f32,
     _padding: u32,
+
+    osc_a: f32,
+    osc_b: f32,
+    osc_c: f32,
 }
 
 // Ensure proper alignment for GPU
@@ -112,13 +116,19 @@ impl GpuSynthesizer {
 
     /// Synthesize a note on the GPU
     ///
+    /// `length` is how long the note is held, in seconds at its track's tempo.
     /// Returns the rendered audio samples (mono, f32)
-    pub fn synthesize_note(&self, note: &NoteEvent, sample_rate: f32) -> Result<Vec<f32>> {
+    pub fn synthesize_note(
+        &self,
+        note: &NoteEvent,
+        length: f32,
+        sample_rate: f32,
+    ) -> Result<Vec<f32>> {
         // Convert NoteEvent to GPU parameters
-        let gpu_params = self.note_to_gpu_params(note, sample_rate);
+        let gpu_params = self.note_to_gpu_params(note, length, sample_rate);
 
         // Calculate output size
-        let total_duration = note.envelope.total_duration(note.duration);
+        let total_duration = note.total_duration(length);
         let total_samples = (total_duration * sample_rate) as usize;
 
         // Create GPU buffers
@@ -219,19 +229,33 @@ impl GpuSynthesizer {
     }
 
     /// Convert NoteEvent to GPU-friendly parameters
-    fn note_to_gpu_params(&self, note: &NoteEvent, sample_rate: f32) -> GpuNoteParams {
-        let waveform_id = match note.waveform {
-            Waveform::Sine => 0,
-            Waveform::Sawtooth => 1,
-            Waveform::Square => 2,
-            Waveform::Triangle => 3,
+    fn note_to_gpu_params(&self, note: &NoteEvent, length: f32, sample_rate: f32) -> GpuNoteParams {
+        let (waveform_id, osc_a, osc_b, osc_c) = match note.waveform {
+            Waveform::Sine => (0, 0.0, 0.0, 0.0),
+            Waveform::Sawtooth => (1, 0.0, 0.0, 0.0),
+            Waveform::Square => (2, 0.0, 0.0, 0.0),
+            Waveform::Triangle => (3, 0.0, 0.0, 0.0),
+            Waveform::Pulse {
+                width,
+                pwm_rate,
+                pwm_depth,
+            } => (4, width, pwm_rate, pwm_depth),
+            Waveform::Sync { ratio } => (5, ratio, 0.0, 0.0),
+            Waveform::Ring { ratio, modulator } => (6, ratio, 0.0, modulator as u32 as f32),
+            Waveform::CrossMod {
+                ratio,
+                amount,
+                modulator,
+            } => (7, ratio, amount, modulator as u32 as f32),
+            Waveform::Noise { seed } => (8, f32::from_bits(seed), 0.0, 0.0),
+            Waveform::Supersaw { detune, mix } => (9, detune, mix, 0.0),
         };
 
         let fm_enabled = if note.fm_params.mod_index > 0.0 { 1 } else { 0 };
 
         GpuNoteParams {
             frequency: note.frequencies[0], // Use first frequency
-            duration: note.duration,
+            duration: length,
             sample_rate,
             waveform: waveform_id,
 
@@ -246,6 +270,10 @@ impl GpuSynthesizer {
 
             velocity: note.velocity,
             _padding: 0,
+
+            osc_a,
+            osc_b,
+            osc_c,
         }
     }
 }
@@ -253,6 +281,7 @@ impl GpuSynthesizer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::composition::timing::Ticks;
     use crate::synthesis::envelope::Envelope;
     use crate::synthesis::fm_synthesis::FMParams;
 
@@ -273,21 +302,28 @@ mod tests {
         let note = NoteEvent {
             frequencies: [440.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
             num_freqs: 1,
-            start_time: 0.0,
-            duration: 0.5,
+            start_time: Ticks::ZERO,
+            duration: Ticks::from_beats(1.0),
             waveform: Waveform::Sine,
             envelope: Envelope::default(),
             filter_envelope: Default::default(),
             fm_params: FMParams::default(),
+            fm_patch: None,
+            physical_model: None,
+            resonator: None,
             pitch_bend_semitones: 0.0,
             custom_wavetable: None,
             velocity: 1.0,
             spatial_position: None,
+            unison: Default::default(),
+            voice: Default::default(),
+            renderings: Default::default(),
+            placed_seconds: None,
         };
 
         // Synthesize on GPU
         let samples = synthesizer
-            .synthesize_note(&note, 44100.0)
+            .synthesize_note(&note, 0.5, 44100.0)
             .expect("GPU synthesis failed");
 
         // Verify output
@@ -8,7 +8,8 @@ struct NoteParams {
     frequency: f32,           // Hz
     duration: f32,            // seconds
     sample_rate: f32,         // samples per second (usually 44100)
-    waveform: u32,            // 0=Sine, 1=Saw, 2=Square, 3=Triangle
+    waveform: u32,            // 0=Sine, 1=Saw, 2=Square, 3=Triangle, 4=Pulse, 5=Sync,
+                              // 6=Ring, 7=CrossMod, 8=Noise, 9=Supersaw
 
     // Envelope (ADSR)
     attack: f32,              // seconds
@@ -23,6 +24,11 @@ struct NoteParams {
 
     velocity: f32,            // 0.0 to 1.0
     _padding: u32,            // Alignment padding
+
+    // Virtual-analog oscillator parameters (meaning depends on waveform)
+    osc_a: f32,               // Pulse width, sync/ring/cross ratio, supersaw detune, noise seed bits
+    osc_b: f32,               // PWM rate, cross-mod amount, supersaw mix
+    osc_c: f32,               // PWM depth, ring/cross modulator shape
 }
 
 // Input: Note parameters
@@ -67,6 +73,96 @@ fn generate_waveform(waveform: u32, phase: f32) -> f32 {
     }
 }
 
+// PolyBLEP residual for a step at phase 0 (dt = phase increment per sample)
+fn poly_blep(phase: f32, dt: f32) -> f32 {
+    if phase < dt {
+        let t = phase / dt;
+        return t + t - t * t - 1.0;
+    } else if phase > 1.0 - dt {
+        let t = (phase - 1.0) / dt;
+        return t * t + t + t + 1.0;
+    }
+    return 0.0;
+}
+
+fn saw_blep(phase: f32, dt: f32) -> f32 {
+    return 2.0 * phase - 1.0 - poly_blep(phase, dt);
+}
+
+fn hash_noise(seed: u32) -> f32 {
+    var x = (seed * 0x9E3779B9u) ^ 0x85EBCA6Bu;
+    x = x ^ (x >> 16u);
+    x = x * 0x7FEB352Du;
+    x = x ^ (x >> 15u);
+    x = x * 0x846CA68Bu;
+    x = x ^ (x >> 16u);
+    return f32(x) / 4294967295.0 * 2.0 - 1.0;
+}
+
+// Second oscillator of ring and cross modulation (0=Sine, 1=Triangle, 2=Square, 3=Saw)
+fn modulator_wave(phase: f32) -> f32 {
+    switch u32(params.osc_c) {
+        case 1u: { return triangle_wave(phase); }
+        case 2u: { return square_wave(phase); }
+        case 3u: { return saw_wave(phase); }
+        default: { return sine_wave(phase); }
+    }
+}
+
+// Virtual-analog oscillators (mirror Waveform::oscillate on the CPU)
+fn generate_analog(waveform: u32, time: f32, freq: f32, sample_idx: u32) -> f32 {
+    let phase = fract(time * freq);
+    let dt = freq / params.sample_rate;
+    switch waveform {
+        case 4u: {
+            let lfo = sin(time * params.osc_b * TWO_PI);
+            let width = clamp(params.osc_a + params.osc_c * lfo, 0.02, 0.98);
+            let naive = select(-1.0, 1.0, phase < width);
+            return naive + poly_blep(phase, dt) - poly_blep(fract(phase - width + 1.0), dt);
+        }
+        case 5u: {
+            let ratio = max(params.osc_a, 1.0);
+            let cycles = phase * ratio;
+            let slave = fract(cycles);
+            let slave_dt = dt * ratio;
+            var value = 2.0 * slave - 1.0;
+            let wrapped = slave < slave_dt && cycles >= 1.0;
+            let wrapping = slave > 1.0 - slave_dt && floor(cycles) + 1.0 < ratio;
+            if wrapped || wrapping {
+                value = value - poly_blep(slave, slave_dt);
+            }
+            let reached = 1.0 - (ceil(ratio) - ratio);
+            return value - reached * poly_blep(phase, dt);
+        }
+        case 6u: {
+            return saw_blep(phase, dt) * modulator_wave(time * freq * params.osc_a);
+        }
+        case 7u: {
+            let modulator = modulator_wave(time * freq * params.osc_a);
+            return saw_wave(phase + params.osc_b * modulator);
+        }
+        case 8u: {
+            return hash_noise(sample_idx + bitcast<u32>(params.osc_a));
+        }
+        case 9u: {
+            var detune = array<f32, 7>(-0.11002313, -0.06288439, -0.01952356, 0.0, 0.01991221, 0.06216538, 0.10745242);
+            var start = array<f32, 7>(0.0, 0.63, 0.19, 0.87, 0.41, 0.72, 0.28);
+            let mix = clamp(params.osc_b, 0.0, 1.0);
+            let centre_gain = 1.0 - 0.55 * mix;
+            let side_gain = -0.74 * mix * mix + 1.28 * mix + 0.04;
+            var sum = 0.0;
+            for (var voice = 0u; voice < 7u; voice = voice + 1u) {
+                let ratio = 1.0 + clamp(params.osc_a, 0.0, 1.0) * detune[voice];
+                let voice_phase = fract(time * freq * ratio + start[voice]);
+                let gain = select(side_gain, centre_gain, voice == 3u);
+                sum = sum + gain * saw_blep(voice_phase, dt * ratio);
+            }
+            return sum / (centre_gain + 6.0 * side_gain);
+        }
+        default: { return sine_wave(phase); }
+    }
+}
+
 // ADSR envelope
 fn envelope_amplitude(time: f32, note_duration: f32, attack: f32, decay: f32, sustain: f32, release: f32) -> f32 {
     let total_duration = attack + decay + note_duration + release;
@@ -125,6 +221,8 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             params.fm_mod_ratio,
             params.fm_mod_index
         );
+    } else if params.waveform >= 4u {
+        oscillator_output = generate_analog(params.waveform, time, params.frequency, sample_idx);
     } else {
         // Basic waveform
         let phase = time * params.frequency;
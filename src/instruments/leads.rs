@@ -5,22 +5,20 @@ use crate::synthesis::effects::{Delay, Distortion, Reverb};
 use crate::synthesis::envelope::Envelope;
 use crate::synthesis::filter::Filter;
 use crate::synthesis::lfo::{LFO, ModRoute, ModTarget};
+use crate::synthesis::unison::Unison;
 use crate::synthesis::waveform::Waveform;
+use crate::track::Voicing;
 
 impl Instrument {
     /// Pluck lead - fast attack and decay
     pub fn pluck() -> Self {
         Self {
-            name: "Pluck".to_string(),
             waveform: Waveform::Triangle,
             envelope: Envelope::pluck(),
             filter: Filter::low_pass(3000.0, 0.3),
-            modulation: Vec::new(),
             delay: Some(Delay::new(0.25, 0.3, 0.3)),
             reverb: Some(Reverb::new(0.3, 0.4, 0.2)),
-            distortion: None,
-            volume: 1.0,
-            pan: 0.0,
+            ..Self::new("Pluck")
         }
     }
 
@@ -28,32 +26,26 @@ impl Instrument {
     pub fn saw_lead() -> Self {
         let vibrato = LFO::new(Waveform::Sine, 5.0, 0.3);
         Self {
-            name: "Saw Lead".to_string(),
             waveform: Waveform::Sawtooth,
             envelope: Envelope::new(0.01, 0.2, 0.7, 0.3),
             filter: Filter::low_pass(4000.0, 0.4),
             modulation: vec![ModRoute::new(vibrato, ModTarget::FilterCutoff, 0.2)],
             delay: Some(Delay::new(0.375, 0.3, 0.25)),
             reverb: Some(Reverb::new(0.4, 0.5, 0.2)),
-            distortion: None,
-            volume: 1.0,
-            pan: 0.0,
+            unison: Unison::new(3, 10.0).with_spread(0.3).with_phase_random(1.0),
+            ..Self::new("Saw Lead")
         }
     }
 
     /// Square lead - hollow, retro game sound
     pub fn square_lead() -> Self {
         Self {
-            name: "Square Lead".to_string(),
             waveform: Waveform::Square,
             envelope: Envelope::new(0.005, 0.1, 0.6, 0.2),
             filter: Filter::low_pass(2000.0, 0.3),
-            modulation: Vec::new(),
             delay: Some(Delay::new(0.5, 0.4, 0.3)),
             reverb: Some(Reverb::new(0.5, 0.5, 0.3)),
-            distortion: None,
-            volume: 1.0,
-            pan: 0.0,
+            ..Self::new("Square Lead")
         }
     }
 
@@ -61,7 +53,6 @@ impl Instrument {
     pub fn bright_lead() -> Self {
         let vibrato = LFO::new(Waveform::Sine, 6.0, 0.4);
         Self {
-            name: "Bright Lead".to_string(),
             waveform: Waveform::Sawtooth,
             envelope: Envelope::new(0.005, 0.15, 0.8, 0.25),
             filter: Filter::low_pass(6000.0, 0.6), // Very bright
@@ -69,8 +60,8 @@ impl Instrument {
             delay: Some(Delay::new(0.25, 0.2, 0.15)),
             reverb: Some(Reverb::new(0.35, 0.4, 0.25)),
             distortion: Some(Distortion::new(1.5, 0.3)), // Slight grit
-            volume: 1.0,
-            pan: 0.0,
+            unison: Unison::new(3, 12.0).with_spread(0.4).with_phase_random(1.0),
+            ..Self::new("Bright Lead")
         }
     }
 
@@ -78,32 +69,25 @@ impl Instrument {
     pub fn synth_lead() -> Self {
         let vibrato = LFO::new(Waveform::Sine, 4.5, 0.35);
         Self {
-            name: "Synth Lead".to_string(),
             waveform: Waveform::Triangle,
             envelope: Envelope::new(0.01, 0.2, 0.7, 0.3),
             filter: Filter::low_pass(3500.0, 0.4),
             modulation: vec![ModRoute::new(vibrato, ModTarget::FilterCutoff, 0.2)],
             delay: Some(Delay::new(0.375, 0.25, 0.2)),
             reverb: Some(Reverb::new(0.45, 0.5, 0.3)),
-            distortion: None,
-            volume: 1.0,
-            pan: 0.0,
+            ..Self::new("Synth Lead")
         }
     }
 
     /// Arp lead - bright, fast attack for arpeggios
     pub fn arp_lead() -> Self {
         Self {
-            name: "Arp Lead".to_string(),
             waveform: Waveform::Square,
             envelope: Envelope::new(0.001, 0.05, 0.5, 0.1), // Very fast attack/decay
             filter: Filter::low_pass(4500.0, 0.5),
-            modulation: Vec::new(),
             delay: Some(Delay::new(0.125, 0.25, 0.2)), // Eighth note delay
             reverb: Some(Reverb::new(0.3, 0.4, 0.2)),
-            distortion: None,
-            volume: 1.0,
-            pan: 0.0,
+            ..Self::new("Arp Lead")
         }
     }
 
@@ -111,7 +95,6 @@ impl Instrument {
     pub fn laser_lead() -> Self {
         let sweep_lfo = LFO::new(Waveform::Triangle, 8.0, 1.0); // Fast sweep
         Self {
-            name: "Laser Lead".to_string(),
             waveform: Waveform::Square,
             envelope: Envelope::new(0.001, 0.08, 0.6, 0.15), // Sharp attack
             filter: Filter::low_pass(8000.0, 0.8),            // Very bright, resonant
@@ -119,8 +102,7 @@ impl Instrument {
             delay: Some(Delay::new(0.25, 0.2, 0.15)),
             reverb: Some(Reverb::new(0.25, 0.35, 0.2)),
             distortion: Some(Distortion::new(1.8, 0.35)), // Digital edge
-            volume: 1.0,
-            pan: 0.0,
+            ..Self::new("Laser Lead")
         }
     }
 
@@ -128,7 +110,6 @@ impl Instrument {
     pub fn detuned_lead() -> Self {
         let vibrato = LFO::new(Waveform::Sine, 4.8, 0.25);
         Self {
-            name: "Detuned Lead".to_string(),
             waveform: Waveform::Sawtooth,
             envelope: Envelope::new(0.01, 0.18, 0.75, 0.3),
             filter: Filter::low_pass(4500.0, 0.45),
@@ -136,8 +117,8 @@ impl Instrument {
             delay: Some(Delay::new(0.3, 0.25, 0.2)),
             reverb: Some(Reverb::new(0.4, 0.45, 0.28)),
             distortion: Some(Distortion::new(1.2, 0.15)), // Slight thickness
-            volume: 1.0,
-            pan: 0.0,
+            unison: Unison::new(7, 25.0).with_spread(0.7).with_phase_random(1.0),
+            ..Self::new("Detuned Lead")
         }
     }
 
@@ -145,7 +126,6 @@ impl Instrument {
     pub fn scream_lead() -> Self {
         let vibrato = LFO::new(Waveform::Sine, 6.5, 0.45);
         Self {
-            name: "Scream Lead".to_string(),
             waveform: Waveform::Sawtooth,
             envelope: Envelope::new(0.005, 0.12, 0.85, 0.25),
             filter: Filter::low_pass(7000.0, 0.7), // Bright and resonant
@@ -153,8 +133,24 @@ impl Instrument {
             delay: Some(Delay::new(0.375, 0.28, 0.22)),
             reverb: Some(Reverb::new(0.35, 0.42, 0.25)),
             distortion: Some(Distortion::new(3.5, 0.6)), // Heavy distortion
-            volume: 1.0,
-            pan: 0.0,
+            unison: Unison::new(3, 15.0).with_spread(0.3).with_phase_random(1.0),
+            ..Self::new("Scream Lead")
+        }
+    }
+
+    /// Sync lead - hard-synced saw with a tearing, vocal edge
+    pub fn sync_lead() -> Self {
+        let vibrato = LFO::new(Waveform::Sine, 5.5, 0.3);
+        Self {
+            waveform: Waveform::sync(2.6),
+            envelope: Envelope::new(0.005, 0.15, 0.8, 0.3),
+            filter: Filter::low_pass(5500.0, 0.5),
+            modulation: vec![ModRoute::new(vibrato, ModTarget::FilterCutoff, 0.2)],
+            delay: Some(Delay::new(0.375, 0.25, 0.2)),
+            reverb: Some(Reverb::new(0.3, 0.4, 0.22)),
+            distortion: Some(Distortion::new(1.4, 0.2)),
+            voicing: Voicing::mono().legato().with_glide(0.05),
+            ..Self::new("Sync Lead")
         }
     }
 }
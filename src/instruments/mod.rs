@@ -18,8 +18,12 @@ pub mod experimental;
 use crate::synthesis::effects::{Delay, Distortion, Reverb};
 use crate::synthesis::envelope::Envelope;
 use crate::synthesis::filter::Filter;
+use crate::synthesis::fm_operators::FMPatch;
 use crate::synthesis::lfo::ModRoute;
-use crate::track::Track;
+use crate::synthesis::modal::ModalResonator;
+use crate::synthesis::physical_model::PhysicalModel;
+use crate::synthesis::unison::Unison;
+use crate::track::{Track, Voicing};
 use crate::synthesis::waveform::Waveform;
 
 /// Instrument preset that combines all synthesis parameters
@@ -35,6 +39,11 @@ pub struct Instrument {
     pub distortion: Option<Distortion>,
     pub volume: f32,
     pub pan: f32,
+    pub unison: Unison,
+    pub voicing: Voicing,
+    pub fm_patch: Option<FMPatch>,
+    pub physical_model: Option<PhysicalModel>,
+    pub resonator: Option<ModalResonator>,
 }
 
 impl Instrument {
@@ -51,6 +60,11 @@ impl Instrument {
             distortion: None,
             volume: 1.0,
             pan: 0.0,
+            unison: Unison::default(),
+            voicing: Voicing::default(),
+            fm_patch: None,
+            physical_model: None,
+            resonator: None,
         }
     }
 
@@ -63,6 +77,11 @@ impl Instrument {
         track.effects.reverb = self.reverb.clone();
         track.effects.distortion = self.distortion.clone();
         track.modulation = self.modulation.clone();
+        track.unison = self.unison;
+        track.voicing = self.voicing;
+        track.fm_patch = self.fm_patch.clone();
+        track.physical_model = self.physical_model.clone();
+        track.resonator = self.resonator.clone();
         track
     }
 }
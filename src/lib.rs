@@ -41,9 +41,14 @@ pub mod track;
 /// Prelude module for convenient imports
 pub mod prelude {
     // Core composition
-    pub use crate::composition::{Composition, DrumGrid, DrumType, Tempo};
+    pub use crate::composition::{
+        Composition, DrumGrid, DrumType, Tempo, TempoCurve, TempoMap, Ticks,
+    };
     pub use crate::engine::{AudioEngine, SoundId};
-    pub use crate::track::Mixer;
+    pub use crate::track::{
+        ExportOptions, ExportProgress, LoopOptions, Marker, Metadata, Mixer, MixerScene,
+        NotePriority, Normalization, OggOptions, StemOptions, VoiceStealing, Voicing,
+    };
 
     // Error handling
     pub use crate::error::{Result, TunesError};
@@ -66,8 +71,10 @@ pub mod prelude {
 
     // Advanced synthesis
     pub use crate::synthesis::{
-        AdditiveSynth, Envelope, FMParams, FilterEnvelope, GranularParams, KarplusStrong,
-        NoiseType, Partial, Sample, SampleSlice, Waveform, Wavetable,
+        AdditiveSynth, BodyResonance, Envelope, Exciter, FMAlgorithm, FMOperator, FMParams,
+        FMPatch, FilterEnvelope, GranularParams, KarplusStrong, Material, ModalResonator,
+        ModelKind, Modulator, NoiseType, Partial, PhysicalModel, Sample, SampleSlice,
+        SinusoidalAnalysis, Unison, Waveform, Wavetable,
     };
 
     // Noise generators
@@ -83,8 +90,9 @@ pub mod prelude {
         AttenuationModel, ListenerConfig, SpatialParams, SpatialPosition, SpatialResult, Vec3,
     };
 
-    // LFO
+    // LFO and modulation matrix
     pub use crate::synthesis::{LFO, ModRoute, ModTarget};
+    pub use crate::synthesis::{MatrixRoute, ModCurve, ModDestination, ModMatrix, ModSource};
 
     // Sequences
     pub use crate::sequences::{
@@ -1,9 +1,10 @@
 /// MIDI export functionality
 /// Converts compositions to Standard MIDI Files (SMF).
 /// Supports notes, drums, tempo, but not samples or effects (MIDI limitations).
+use crate::composition::timing::{TempoMap, Ticks};
 use crate::instruments::drums::DrumType;
 use crate::error::{Result, TunesError};
-use crate::track::{AudioEvent, Mixer};
+use crate::track::{AudioEvent, Marker, Mixer, NoteEvent};
 use midly::{
     Header, MetaMessage, MidiMessage, PitchBend, Smf, Timing, TrackEvent, TrackEventKind,
     num::{u4, u7, u14, u15, u24, u28},
@@ -57,27 +58,38 @@ pub fn midi_note_to_frequency(note: u8) -> f32 {
 ///
 /// # Arguments
 /// * `time` - Time in seconds
-/// * `tempo` - Tempo in BPM
+/// * `tempo_map` - Tempo map the times were written against
 /// * `ppq` - Pulses per quarter note (ticks per beat)
-fn seconds_to_ticks(time: f32, tempo: f32, ppq: u16) -> u32 {
-    // Beats = time * (bpm / 60)
-    // Ticks = beats * ppq
-    let beats = time * (tempo / 60.0);
-    let ticks = beats * ppq as f32;
-    ticks.round() as u32
+fn seconds_to_ticks(time: f32, tempo_map: &TempoMap, ppq: u16) -> u32 {
+    // Builders place events on a tick grid, so going through `Ticks` in f64
+    // lands them back on exact MIDI ticks however late in the song they are
+    tempo_map.ticks_at(time as f64).to_midi(ppq)
 }
 
 /// Convert MIDI ticks to time in seconds
 ///
 /// # Arguments
 /// * `ticks` - MIDI ticks
-/// * `tempo` - Tempo in BPM
+/// * `tempo_map` - Tempo map built from the file's tempo events
 /// * `ppq` - Pulses per quarter note (ticks per beat)
-fn ticks_to_seconds(ticks: u32, tempo: f32, ppq: u16) -> f32 {
-    // Beats = ticks / ppq
-    // Time = beats / (bpm / 60)
-    let beats = ticks as f32 / ppq as f32;
-    beats / (tempo / 60.0)
+fn ticks_to_seconds(ticks: u32, tempo_map: &TempoMap, ppq: u16) -> f32 {
+    tempo_map.seconds_at(Ticks::from_midi(ticks, ppq)) as f32
+}
+
+/// Position where a note is released, earlier than written if its voice is cut
+///
+/// # Arguments
+/// * `note` - The note to release
+/// * `tempo_map` - Tempo map the note was voiced against
+fn held_until(note: &NoteEvent, tempo_map: &TempoMap) -> Ticks {
+    let end = note.start_time + note.duration;
+    match note.voice.cut_at {
+        Some(cut_at) => {
+            let start = tempo_map.seconds_at(note.start_time);
+            end.min(tempo_map.ticks_at(start + cut_at as f64))
+        }
+        None => end,
+    }
 }
 
 /// Convert DrumType to General MIDI percussion note number
@@ -389,7 +401,9 @@ fn mod_value_to_cc(value: f32, bipolar: bool) -> u8 {
 impl Mixer {
     /// Export the mixer to a MIDI file
     ///
-    /// Uses the tempo from the composition automatically.
+    /// Uses the tempo from the composition automatically. Key signatures and
+    /// the markers in `Mixer::metadata` (including arranged sections) are
+    /// written as meta events on the tempo track, and the title as its name.
     ///
     /// # Arguments
     /// * `path` - Output file path (e.g., "song.mid")
@@ -417,36 +431,38 @@ impl Mixer {
     pub fn export_midi(&self, path: &str) -> Result<()> {
         let mut tracks = Vec::new();
 
-        // Keep initial BPM for time-to-tick conversions
-        let bpm = self.tempo.bpm;
-
         // Track 0: Tempo track (meta information)
         let mut tempo_track = Vec::new();
 
-        // Collect all tempo changes from all tracks
-        let mut tempo_changes = Vec::new();
-
-        // Add initial tempo
-        tempo_changes.push((0.0, bpm));
-
         // Collect tempo changes from all tracks
+        let mut tempo_events = Vec::new();
         for track in self.all_tracks() {
             for event in &track.events {
                 if let AudioEvent::TempoChange(tempo_event) = event {
-                    tempo_changes.push((tempo_event.start_time, tempo_event.bpm));
+                    tempo_events.push((tempo_event.start_time, tempo_event.bpm));
                 }
             }
         }
+        tempo_events.sort_by_key(|event| event.0);
+
+        // Fold changes the tempo map doesn't already know about into it. Events
+        // are written at their own ticks; markers and modulation, which are in
+        // seconds, are converted through the map
+        let mut tempo_map = self.tempo_map.clone();
+        for (at, tempo_bpm) in tempo_events {
+            if (tempo_map.bpm_at(at) - tempo_bpm).abs() > 0.001 {
+                tempo_map.set_tempo(at, tempo_bpm);
+            }
+        }
 
-        // Sort by time and remove duplicates at same time (keep last)
-        tempo_changes.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-        tempo_changes.dedup_by(|a, b| (a.0 - b.0).abs() < 0.001);
+        // MIDI tempo is stepwise, so ramps are written as sixteenth-note steps
+        let tempo_changes = tempo_map.to_steps(Ticks::from_beats(0.25));
 
         // Collect all time signature changes from all tracks
-        let mut time_sig_changes: Vec<(f32, u8, u8)> = Vec::new();
+        let mut time_sig_changes: Vec<(Ticks, u8, u8)> = Vec::new();
 
         // Add default time signature (4/4) at the start
-        time_sig_changes.push((0.0, 4, 4));
+        time_sig_changes.push((Ticks::ZERO, 4, 4));
 
         // Collect time signature changes from all tracks
         for track in self.all_tracks() {
@@ -462,15 +478,28 @@ impl Mixer {
         }
 
         // Sort by time and remove duplicates at same time (keep last)
-        time_sig_changes.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-        time_sig_changes.dedup_by(|a, b| (a.0 - b.0).abs() < 0.001);
+        time_sig_changes.sort_by_key(|change| change.0);
+        time_sig_changes.dedup_by_key(|change| change.0);
 
-        // Combine tempo and time signature changes into a single sorted list
-        // We'll use an enum to distinguish between the two types
+        // Combine tempo, time signature, key signature and marker changes into a single sorted list
+        // We'll use an enum to distinguish between the types
         #[derive(Debug, Clone, Copy)]
-        enum MetaChange {
-            Tempo(f32, f32),            // (time, bpm)
-            TimeSignature(f32, u8, u8), // (time, numerator, denominator)
+        enum MetaChange<'a> {
+            Tempo(Ticks, f32),            // (time, bpm)
+            TimeSignature(Ticks, u8, u8), // (time, numerator, denominator)
+            KeySignature(Ticks, i8, bool), // (time, sharps/flats, minor)
+            Marker(Ticks, &'a str),       // (time, name)
+        }
+
+        impl MetaChange<'_> {
+            fn time(&self) -> Ticks {
+                match self {
+                    MetaChange::Tempo(t, _) => *t,
+                    MetaChange::TimeSignature(t, _, _) => *t,
+                    MetaChange::KeySignature(t, _, _) => *t,
+                    MetaChange::Marker(t, _) => *t,
+                }
+            }
         }
 
         let mut meta_changes: Vec<MetaChange> = Vec::new();
@@ -485,27 +514,43 @@ impl Mixer {
             meta_changes.push(MetaChange::TimeSignature(time, numerator, denominator));
         }
 
-        // Sort by time
-        meta_changes.sort_by(|a, b| {
-            let time_a = match a {
-                MetaChange::Tempo(t, _) => *t,
-                MetaChange::TimeSignature(t, _, _) => *t,
-            };
-            let time_b = match b {
-                MetaChange::Tempo(t, _) => *t,
-                MetaChange::TimeSignature(t, _, _) => *t,
-            };
-            time_a
-                .partial_cmp(&time_b)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        // Add key signature changes from all tracks
+        for track in self.all_tracks() {
+            for event in &track.events {
+                if let AudioEvent::KeySignature(key_event) = event {
+                    let key = &key_event.key_signature;
+                    meta_changes.push(MetaChange::KeySignature(
+                        key_event.start_time,
+                        key.to_midi_sharps_flats(),
+                        key.is_minor(),
+                    ));
+                }
+            }
+        }
+
+        // Add markers and section regions (regions are marked at their start)
+        for marker in &self.metadata.markers {
+            let time = tempo_map.ticks_at(marker.time.max(0.0) as f64);
+            meta_changes.push(MetaChange::Marker(time, &marker.name));
+        }
+
+        // Sort by time (stable, so same-time markers keep their order)
+        meta_changes.sort_by_key(MetaChange::time);
+
+        // Song title is the sequence name of the tempo track
+        if let Some(title) = &self.metadata.title {
+            tempo_track.push(TrackEvent {
+                delta: u28::new(0),
+                kind: TrackEventKind::Meta(MetaMessage::TrackName(title.as_bytes())),
+            });
+        }
 
         // Convert to MIDI events with delta times
         let mut last_tick = 0u32;
         for meta_change in meta_changes {
             match meta_change {
                 MetaChange::Tempo(time, tempo_bpm) => {
-                    let tick = seconds_to_ticks(time, bpm, PPQ);
+                    let tick = time.to_midi(PPQ);
                     let delta = tick.saturating_sub(last_tick);
                     last_tick = tick;
 
@@ -518,7 +563,7 @@ impl Mixer {
                     });
                 }
                 MetaChange::TimeSignature(time, numerator, denominator) => {
-                    let tick = seconds_to_ticks(time, bpm, PPQ);
+                    let tick = time.to_midi(PPQ);
                     let delta = tick.saturating_sub(last_tick);
                     last_tick = tick;
 
@@ -543,6 +588,26 @@ impl Mixer {
                         )),
                     });
                 }
+                MetaChange::KeySignature(time, sharps_flats, minor) => {
+                    let tick = time.to_midi(PPQ);
+                    let delta = tick.saturating_sub(last_tick);
+                    last_tick = tick;
+
+                    tempo_track.push(TrackEvent {
+                        delta: u28::new(delta),
+                        kind: TrackEventKind::Meta(MetaMessage::KeySignature(sharps_flats, minor)),
+                    });
+                }
+                MetaChange::Marker(time, name) => {
+                    let tick = time.to_midi(PPQ);
+                    let delta = tick.saturating_sub(last_tick);
+                    last_tick = tick;
+
+                    tempo_track.push(TrackEvent {
+                        delta: u28::new(delta),
+                        kind: TrackEventKind::Meta(MetaMessage::Marker(name.as_bytes())),
+                    });
+                }
             }
         }
 
@@ -630,8 +695,8 @@ impl Mixer {
             for event in &track.events {
                 match event {
                     AudioEvent::Note(note) => {
-                        let start_tick = seconds_to_ticks(note.start_time, bpm, PPQ);
-                        let end_tick = seconds_to_ticks(note.start_time + note.duration, bpm, PPQ);
+                        let start_tick = note.start_time.to_midi(PPQ);
+                        let end_tick = held_until(note, &self.tempo_map).to_midi(PPQ);
                         // Combine per-note velocity with track volume for final MIDI velocity
                         let combined_velocity = (note.velocity * track.volume).clamp(0.0, 1.0);
                         let velocity = volume_to_velocity(combined_velocity);
@@ -669,7 +734,7 @@ impl Mixer {
                         }
                     }
                     AudioEvent::Drum(drum) => {
-                        let tick = seconds_to_ticks(drum.start_time, bpm, PPQ);
+                        let tick = drum.start_time.to_midi(PPQ);
                         let midi_note = drum_type_to_midi_note(drum.drum_type);
                         let velocity = DEFAULT_VELOCITY;
 
@@ -697,7 +762,7 @@ impl Mixer {
                         // (Added to tempo track with time signature meta messages)
                     }
                     AudioEvent::KeySignature(_) => {
-                        // Key signatures will be handled separately
+                        // Key signatures are handled separately
                         // (Added to tempo track with key signature meta messages)
                     }
                 }
@@ -711,7 +776,7 @@ impl Mixer {
 
                 if track_duration > 0.0 {
                     // Sample interval: every 1/32 note or 50ms, whichever is more frequent
-                    let beats_per_second = bpm / 60.0;
+                    let beats_per_second = self.tempo.bpm / 60.0;
                     let seconds_per_32nd = 1.0 / (beats_per_second * 8.0);
                     let sample_interval = seconds_per_32nd.min(0.05); // Min of 1/32 note or 50ms
 
@@ -732,7 +797,7 @@ impl Mixer {
                         let mut lfo_copy = mod_route.lfo;
                         for i in 0..num_samples {
                             let time = i as f32 * sample_interval;
-                            let tick = seconds_to_ticks(time, bpm, PPQ);
+                            let tick = seconds_to_ticks(time, &tempo_map, PPQ);
 
                             // Tick the LFO and get value
                             lfo_copy.tick();
@@ -835,14 +900,14 @@ impl Mixer {
     /// # Supported Features
     /// - Note events (converted to NoteEvent with frequency from MIDI note number)
     /// - Drum events on channel 10 (converted to DrumEvent)
-    /// - Tempo changes (meta events)
+    /// - Tempo changes (meta events, collected into `Mixer::tempo_map` so every
+    ///   event lands at the right time)
     /// - Time signatures (meta events)
+    /// - Markers (meta events, stored in `Mixer::metadata`)
     /// - Multiple tracks
     /// - Track names
     ///
     /// # Limitations
-    /// - Tempo changes occurring mid-track use the initial tempo for time calculations
-    ///   (tempo change events are still preserved and exported correctly)
     /// - Pitch bend events are converted to static pitch offsets (not continuous)
     /// - Control change (CC) events are ignored
     /// - Program changes are stored but don't affect playback
@@ -888,13 +953,12 @@ impl Mixer {
             }
         };
 
-        // Default tempo (120 BPM) - will be updated if tempo meta event is found
-        let mut current_tempo = 120.0;
-        let mut tempo_changes: Vec<(f32, f32)> = Vec::new(); // (time, bpm)
-        let mut time_sig_changes: Vec<(f32, u8, u8)> = Vec::new(); // (time, numerator, denominator)
+        let mut tempo_ticks: Vec<(u32, f32)> = Vec::new(); // (tick, bpm)
+        let mut time_sig_ticks: Vec<(u32, u8, u8)> = Vec::new(); // (tick, numerator, denominator)
+        let mut marker_ticks: Vec<(u32, String)> = Vec::new(); // (tick, name)
 
-        // First pass: Extract tempo and time signature from all tracks
-        for (track_idx, track) in smf.tracks.iter().enumerate() {
+        // First pass: Extract tempo, time signature and markers from all tracks
+        for track in smf.tracks.iter() {
             let mut absolute_tick = 0u32;
 
             for event in track {
@@ -905,18 +969,15 @@ impl Mixer {
                         MetaMessage::Tempo(tempo) => {
                             let us_per_quarter = tempo.as_int();
                             let bpm = 60_000_000.0 / us_per_quarter as f32;
-                            let time = ticks_to_seconds(absolute_tick, current_tempo, ppq);
-                            tempo_changes.push((time, bpm));
-
-                            // Update current tempo for future time calculations
-                            if track_idx == 0 {
-                                current_tempo = bpm;
-                            }
+                            tempo_ticks.push((absolute_tick, bpm));
                         }
                         MetaMessage::TimeSignature(num, denom, _, _) => {
                             let denominator = 2u8.pow(*denom as u32);
-                            let time = ticks_to_seconds(absolute_tick, current_tempo, ppq);
-                            time_sig_changes.push((time, *num, denominator));
+                            time_sig_ticks.push((absolute_tick, *num, denominator));
+                        }
+                        MetaMessage::Marker(name) => {
+                            let name = String::from_utf8_lossy(name).to_string();
+                            marker_ticks.push((absolute_tick, name));
                         }
                         _ => {}
                     }
@@ -924,15 +985,34 @@ impl Mixer {
             }
         }
 
-        // Reset tempo to initial value for second pass
-        current_tempo = if let Some((_, bpm)) = tempo_changes.first() {
-            *bpm
-        } else {
-            120.0
+        // Build the tempo map (120 BPM until the first tempo event) so every
+        // tick below converts to the time it actually plays at
+        tempo_ticks.sort_by_key(|(tick, _)| *tick);
+        let initial_bpm = match tempo_ticks.first() {
+            Some((0, bpm)) => *bpm,
+            _ => 120.0,
         };
+        let mut tempo_map = TempoMap::new(initial_bpm);
+        for (tick, bpm) in &tempo_ticks {
+            tempo_map.set_tempo(Ticks::from_midi(*tick, ppq), *bpm);
+        }
+
+        let tempo_changes: Vec<(Ticks, f32)> = tempo_ticks
+            .iter()
+            .map(|(tick, bpm)| (Ticks::from_midi(*tick, ppq), *bpm))
+            .collect();
+        let time_sig_changes: Vec<(Ticks, u8, u8)> = time_sig_ticks
+            .iter()
+            .map(|(tick, num, denom)| (Ticks::from_midi(*tick, ppq), *num, *denom))
+            .collect();
 
         // Create mixer with the initial tempo
-        let mut mixer = Mixer::new(crate::composition::timing::Tempo::new(current_tempo));
+        let mut mixer = Mixer::new(crate::composition::timing::Tempo::new(initial_bpm));
+        mixer.set_tempo_map(tempo_map.clone());
+        mixer.metadata.markers = marker_ticks
+            .iter()
+            .map(|(tick, name)| Marker::new(name, ticks_to_seconds(*tick, &tempo_map, ppq)))
+            .collect();
         let mut audio_tracks: Vec<Track> = Vec::new();
 
         // Second pass: Convert MIDI tracks to audio tracks
@@ -945,12 +1025,12 @@ impl Mixer {
 
             // Track active notes for Note On/Off pairing
             // Key: (channel, note), Value: (start_time, velocity)
-            let mut active_notes: std::collections::HashMap<(u8, u8), (f32, u8)> =
+            let mut active_notes: std::collections::HashMap<(u8, u8), (Ticks, u8)> =
                 std::collections::HashMap::new();
 
             for event in midi_track {
                 absolute_tick += event.delta.as_int();
-                let time = ticks_to_seconds(absolute_tick, current_tempo, ppq);
+                let time = Ticks::from_midi(absolute_tick, ppq);
 
                 match &event.kind {
                     TrackEventKind::Meta(MetaMessage::TrackName(name)) => {
@@ -982,7 +1062,7 @@ impl Mixer {
                                         if ch_num == 9 {
                                             // Drum track
                                             if let Some(drum_type) = midi_note_to_drum_type(note) {
-                                                track.add_drum(drum_type, start_time, None);
+                                                track.add_drum_ticks(drum_type, start_time, None);
                                             }
                                         } else {
                                             // Melodic track
@@ -1024,7 +1104,7 @@ impl Mixer {
                                     if ch_num == 9 {
                                         // Drum track
                                         if let Some(drum_type) = midi_note_to_drum_type(note) {
-                                            track.add_drum(drum_type, start_time, None);
+                                            track.add_drum_ticks(drum_type, start_time, None);
                                         }
                                     } else {
                                         // Melodic track
@@ -1065,12 +1145,14 @@ impl Mixer {
             // Handle any "hanging" notes that never received a Note Off
             // Give them a default duration of 0.1 seconds
             for ((ch_num, note), (start_time, start_vel)) in active_notes.drain() {
-                let duration = 0.1; // Default duration for hanging notes
+                // Default duration for hanging notes
+                let end = tempo_map.ticks_at(tempo_map.seconds_at(start_time) + 0.1);
+                let duration = end - start_time;
 
                 if ch_num == 9 {
                     // Drum track
                     if let Some(drum_type) = midi_note_to_drum_type(note) {
-                        track.add_drum(drum_type, start_time, None);
+                        track.add_drum_ticks(drum_type, start_time, None);
                     }
                 } else {
                     // Melodic track
@@ -1150,6 +1232,7 @@ impl Mixer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::composition::timing::ticks::at;
 
     #[test]
     fn test_frequency_to_midi_note() {
@@ -1168,13 +1251,13 @@ mod tests {
         // At 120 BPM, 1 beat = 0.5 seconds
         // At 480 PPQ, 1 beat = 480 ticks
         // So 0.5 seconds = 480 ticks
-        assert_eq!(seconds_to_ticks(0.5, 120.0, 480), 480);
+        assert_eq!(seconds_to_ticks(0.5, &TempoMap::new(120.0), 480), 480);
 
         // 1 second = 2 beats = 960 ticks
-        assert_eq!(seconds_to_ticks(1.0, 120.0, 480), 960);
+        assert_eq!(seconds_to_ticks(1.0, &TempoMap::new(120.0), 480), 960);
 
         // At 60 BPM, 1 beat = 1 second = 480 ticks
-        assert_eq!(seconds_to_ticks(1.0, 60.0, 480), 480);
+        assert_eq!(seconds_to_ticks(1.0, &TempoMap::new(60.0), 480), 480);
     }
 
     #[test]
@@ -1297,28 +1380,35 @@ mod tests {
         // At 120 BPM, 1 beat = 0.5 seconds
         // At 480 PPQ, 1 beat = 480 ticks
         // So 480 ticks = 0.5 seconds
-        assert_eq!(ticks_to_seconds(480, 120.0, 480), 0.5);
+        assert_eq!(ticks_to_seconds(480, &TempoMap::new(120.0), 480), 0.5);
 
         // 960 ticks = 1 second
-        assert_eq!(ticks_to_seconds(960, 120.0, 480), 1.0);
+        assert_eq!(ticks_to_seconds(960, &TempoMap::new(120.0), 480), 1.0);
 
         // At 60 BPM, 1 beat = 1 second = 480 ticks
-        assert_eq!(ticks_to_seconds(480, 60.0, 480), 1.0);
+        assert_eq!(ticks_to_seconds(480, &TempoMap::new(60.0), 480), 1.0);
     }
 
     #[test]
     fn test_ticks_seconds_roundtrip() {
         // Test that converting back and forth works
-        let tempo = 120.0;
+        let tempo = TempoMap::new(120.0);
         let ppq = 480;
 
         for seconds in [0.25, 0.5, 1.0, 2.0, 4.0] {
-            let ticks = seconds_to_ticks(seconds, tempo, ppq);
-            let converted_back = ticks_to_seconds(ticks, tempo, ppq);
+            let ticks = seconds_to_ticks(seconds, &tempo, ppq);
+            let converted_back = ticks_to_seconds(ticks, &tempo, ppq);
             assert!((converted_back - seconds).abs() < 0.001);
         }
     }
 
+    #[test]
+    fn test_seconds_to_ticks_stays_exact_late_in_a_song() {
+        // Bar 401 at 137 BPM is over eleven minutes in
+        let time = Ticks::from_beats(1600.0).to_seconds(137.0) as f32;
+        assert_eq!(seconds_to_ticks(time, &TempoMap::new(137.0), 480), 1600 * 480);
+    }
+
     #[test]
     fn test_midi_note_to_drum_type() {
         // Test standard drum mappings
@@ -1353,4 +1443,81 @@ mod tests {
             assert!(converted_back.is_some());
         }
     }
+
+    #[test]
+    fn test_markers_and_key_survive_midi_round_trip() {
+        use crate::composition::timing::Tempo;
+        use crate::theory::key_signature::KeySignature;
+        use crate::track::{Metadata, Track};
+
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        let mut track = Track::new();
+        track.add_note(&[440.0], 0.0, 4.0);
+        track.events.push(AudioEvent::KeySignature(
+            crate::track::KeySignatureEvent {
+                start_time: at(0.0),
+                key_signature: KeySignature::A_MINOR,
+            },
+        ));
+        mixer.add_track(track);
+        mixer.metadata = Metadata::default()
+            .with_title("Round Trip")
+            .with_marker(Marker::new("intro", 0.0))
+            .with_marker(Marker::new("chorus", 2.0));
+
+        let path = "test_markers_round_trip.mid";
+        mixer.export_midi(path).unwrap();
+        let bytes = std::fs::read(path).unwrap();
+        let imported = Mixer::import_midi(path).unwrap();
+        std::fs::remove_file(path).ok();
+
+        let smf = Smf::parse(&bytes).unwrap();
+        assert!(smf.tracks[0].iter().any(|event| matches!(
+            event.kind,
+            TrackEventKind::Meta(MetaMessage::KeySignature(0, true))
+        )));
+        assert!(smf.tracks[0].iter().any(|event| matches!(
+            event.kind,
+            TrackEventKind::Meta(MetaMessage::TrackName(b"Round Trip"))
+        )));
+        assert_eq!(imported.metadata.markers, mixer.metadata.markers);
+    }
+
+    #[test]
+    fn test_tempo_changes_survive_midi_round_trip() {
+        use crate::composition::timing::Tempo;
+        use crate::composition::Composition;
+        use crate::consts::notes::*;
+
+        let mut comp = Composition::new(Tempo::new(120.0));
+        comp.track("lead")
+            .quarters(&[C4, D4, E4, F4])
+            .tempo(90.0)
+            .quarters(&[G4, A4, B4, C5]);
+        let mixer = comp.into_mixer();
+
+        let path = "test_tempo_round_trip.mid";
+        mixer.export_midi(path).unwrap();
+        let imported = Mixer::import_midi(path).unwrap();
+        std::fs::remove_file(path).ok();
+
+        let starts = |mixer: &Mixer| -> Vec<Ticks> {
+            let mut starts: Vec<Ticks> = mixer.all_tracks()[0]
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    AudioEvent::Note(note) => Some(note.start_time),
+                    _ => None,
+                })
+                .collect();
+            starts.sort();
+            starts
+        };
+        let expected = starts(&mixer);
+        let gap = mixer.tempo_map.seconds_at(expected[5]) - mixer.tempo_map.seconds_at(expected[4]);
+        assert!((gap - 60.0 / 90.0).abs() < 1e-9);
+        // Quarter notes land on whole MIDI ticks, so they come back exactly
+        assert_eq!(starts(&imported), expected);
+        assert!((imported.tempo_map.bpm_at(Ticks::from_beats(6.0)) - 90.0).abs() < 0.01);
+    }
 }
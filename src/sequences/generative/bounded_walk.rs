@@ -79,8 +79,21 @@
 /// - **Scale-based melodies**: Step between scale degrees randomly
 /// - **Dynamic contrast**: Volume variation within acceptable range
 pub fn generate(start: f32, step: f32, min: f32, max: f32, steps: usize) -> Vec<f32> {
-    use rand::Rng;
-    let mut rng = rand::rng();
+    generate_with_rng(start, step, min, max, steps, &mut rand::rng())
+}
+
+/// Generate a bounded random walk from a caller-supplied generator
+///
+/// Same as [`generate`], but reproducible when `rng` is seeded, e.g. with
+/// [`Composition::rng`](crate::composition::Composition::rng).
+pub fn generate_with_rng(
+    start: f32,
+    step: f32,
+    min: f32,
+    max: f32,
+    steps: usize,
+    rng: &mut impl rand::Rng,
+) -> Vec<f32> {
     let start_clamped = start.clamp(min, max);
     let mut seq = vec![start_clamped];
 
@@ -79,8 +79,19 @@ pub fn generate(
     start_state: u32,
     length: usize,
 ) -> Vec<u32> {
-    use rand::Rng;
-    let mut rng = rand::rng();
+    generate_with_rng(transitions, start_state, length, &mut rand::rng())
+}
+
+/// Generate a Markov chain sequence from a caller-supplied generator
+///
+/// Same as [`generate`], but reproducible when `rng` is seeded, e.g. with
+/// [`Composition::rng`](crate::composition::Composition::rng).
+pub fn generate_with_rng(
+    transitions: &std::collections::HashMap<u32, Vec<(u32, f32)>>,
+    start_state: u32,
+    length: usize,
+    rng: &mut impl rand::Rng,
+) -> Vec<u32> {
     let mut sequence = vec![start_state];
     let mut current_state = start_state;
 
@@ -70,8 +70,28 @@
 /// This is an unbounded walk - values can grow arbitrarily large or small.
 /// Use `bounded_walk()` if you need to constrain the range.
 pub fn generate(start: f32, step_size: f32, steps: usize) -> Vec<f32> {
-    use rand::Rng;
-    let mut rng = rand::rng();
+    generate_with_rng(start, step_size, steps, &mut rand::rng())
+}
+
+/// Generate a random walk from a caller-supplied generator
+///
+/// Same as [`generate`], but reproducible when `rng` is seeded, e.g. with
+/// [`Composition::rng`](crate::composition::Composition::rng).
+///
+/// # Example
+/// ```
+/// use rand::SeedableRng;
+/// use tunes::sequences;
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+/// let walk = sequences::random_walk::generate_with_rng(110.0, 8.0, 16, &mut rng);
+/// ```
+pub fn generate_with_rng(
+    start: f32,
+    step_size: f32,
+    steps: usize,
+    rng: &mut impl rand::Rng,
+) -> Vec<f32> {
     let mut seq = vec![start];
 
     for _ in 1..steps {
@@ -18,6 +18,10 @@
 //! - **Bells/gongs**: Inharmonic partials create metallic timbres
 //! - **Evolving pads**: Animate partial amplitudes over time
 //! - **Spectral composition**: Precise control over frequency content
+//! - **Resynthesis**: Partials tracked in a recording by
+//!   [`SinusoidalAnalysis`](crate::synthesis::sinusoidal::SinusoidalAnalysis) follow
+//!   their own [`PartialEnvelope`]s, and can be re-pitched, stretched in time and
+//!   rebalanced independently
 //!
 //! # Example
 //!
@@ -36,7 +40,9 @@
 //! let samples = synth.generate(44100);
 //! ```
 
+use crate::synthesis::sinusoidal::SinusoidalAnalysis;
 use std::f32::consts::PI;
+use std::sync::Arc;
 
 /// A single partial (sine wave component) in additive synthesis
 ///
@@ -44,7 +50,7 @@ use std::f32::consts::PI;
 /// - **Frequency ratio**: Multiplier relative to fundamental frequency
 /// - **Amplitude**: Volume of this partial (0.0 - 1.0)
 /// - **Phase offset**: Starting phase (0.0 - 1.0, where 1.0 = full cycle)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Partial {
     /// Frequency multiplier relative to fundamental
     /// - 1.0 = fundamental frequency
@@ -142,6 +148,80 @@ impl Partial {
     }
 }
 
+/// How a partial's level and pitch change over time
+///
+/// Breakpoints are `frame_time` seconds apart and interpolated linearly. The
+/// amplitude multiplies the partial's `amplitude`, and the frequency its
+/// `frequency_ratio` (1.0 = the partial's own frequency). Past the last
+/// breakpoint the partial is silent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialEnvelope {
+    /// Seconds between breakpoints
+    pub frame_time: f32,
+    /// Level at each breakpoint
+    pub amplitude: Vec<f32>,
+    /// Frequency multiplier at each breakpoint
+    pub frequency: Vec<f32>,
+}
+
+impl PartialEnvelope {
+    /// Create an envelope from breakpoints `frame_time` seconds apart
+    ///
+    /// # Arguments
+    /// * `frame_time` - Seconds between breakpoints
+    /// * `amplitude` - Level at each breakpoint
+    /// * `frequency` - Frequency multiplier at each breakpoint, as long as `amplitude`
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::synthesis::additive::PartialEnvelope;
+    ///
+    /// // Swell in over half a second while bending up a semitone
+    /// let swell = PartialEnvelope::new(0.25, vec![0.0, 0.5, 1.0], vec![1.0, 1.03, 1.0595]);
+    /// assert_eq!(swell.duration(), 0.5);
+    /// ```
+    pub fn new(frame_time: f32, amplitude: Vec<f32>, frequency: Vec<f32>) -> Self {
+        Self {
+            frame_time: frame_time.max(1e-6),
+            amplitude,
+            frequency,
+        }
+    }
+
+    /// Time of the last breakpoint, in seconds
+    pub fn duration(&self) -> f32 {
+        self.amplitude.len().saturating_sub(1) as f32 * self.frame_time
+    }
+
+    /// Level at `time` seconds
+    pub fn amplitude_at(&self, time: f32) -> f32 {
+        if time > self.duration() {
+            return 0.0;
+        }
+        interpolate(&self.amplitude, time / self.frame_time)
+    }
+
+    /// Frequency multiplier at `time` seconds
+    pub fn frequency_at(&self, time: f32) -> f32 {
+        if self.frequency.is_empty() {
+            return 1.0;
+        }
+        interpolate(&self.frequency, time / self.frame_time)
+    }
+}
+
+/// Linear interpolation between breakpoints, holding the ends
+#[inline]
+fn interpolate(points: &[f32], position: f32) -> f32 {
+    let position = position.max(0.0);
+    let index = position as usize;
+    match (points.get(index), points.get(index + 1)) {
+        (Some(&a), Some(&b)) => a + (b - a) * (position - index as f32),
+        (Some(&a), None) => a,
+        _ => points.last().copied().unwrap_or(0.0),
+    }
+}
+
 /// Additive synthesizer - generates sound by summing sine wave partials
 ///
 /// Build complex timbres from simple components. Perfect for:
@@ -149,6 +229,8 @@ impl Partial {
 /// - Bell/metallic sounds (inharmonic partials)
 /// - Evolving pads (time-varying amplitudes)
 /// - Precise spectral control
+/// - Resynthesis of analysed recordings, with pitch, time and partials
+///   changed independently
 ///
 /// # Example
 /// ```
@@ -173,6 +255,14 @@ pub struct AdditiveSynth {
     partials: Vec<Partial>,
     /// Phase accumulators for each partial (in cycles, 0.0 - 1.0)
     phases: Vec<f32>,
+    /// Envelope of each partial, if it changes over time
+    envelopes: Vec<Option<Arc<PartialEnvelope>>>,
+    /// Seconds into the envelopes
+    time: f32,
+    /// How much slower than recorded the envelopes play (2.0 = twice as long)
+    time_stretch: f32,
+    /// Whether the sum is divided by the number of partials
+    normalize: bool,
 }
 
 impl AdditiveSynth {
@@ -194,9 +284,47 @@ impl AdditiveSynth {
             sample_rate,
             partials: Vec::new(),
             phases: Vec::new(),
+            envelopes: Vec::new(),
+            time: 0.0,
+            time_stretch: 1.0,
+            normalize: true,
         }
     }
 
+    /// Resynthesize the partials tracked in a recording
+    ///
+    /// The synth starts at the recording's own fundamental, with every partial
+    /// following its analysed envelope at its analysed level; the sum is not
+    /// normalized, so the result is as loud as the recording. Change the pitch
+    /// with [`Self::set_frequency`], the speed with [`Self::with_time_stretch`]
+    /// and the balance of partials with [`Self::map_partials`], each without
+    /// affecting the others.
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::synthesis::additive::AdditiveSynth;
+    /// use tunes::synthesis::sinusoidal::SinusoidalAnalysis;
+    /// use tunes::synthesis::Sample;
+    ///
+    /// let tone: Vec<f32> = (0..22050)
+    ///     .map(|i| (i as f32 * 220.0 * std::f32::consts::TAU / 44100.0).sin() * 0.5)
+    ///     .collect();
+    /// let analysis = SinusoidalAnalysis::from_sample(&Sample::from_mono(tone, 44100), 16)?;
+    ///
+    /// // A fifth up, at half speed
+    /// let mut synth = AdditiveSynth::from_analysis(&analysis, 44100.0).with_time_stretch(2.0);
+    /// synth.set_frequency(analysis.fundamental * 1.5);
+    /// let samples = synth.generate((synth.duration().unwrap() * 44100.0) as usize);
+    /// # Ok::<(), tunes::error::TunesError>(())
+    /// ```
+    pub fn from_analysis(analysis: &SinusoidalAnalysis, sample_rate: f32) -> Self {
+        let mut synth = Self::new(analysis.fundamental, sample_rate).with_normalization(false);
+        for tracked in &analysis.partials {
+            synth = synth.add_partial_with_envelope(tracked.partial, tracked.envelope.clone());
+        }
+        synth
+    }
+
     /// Add a partial to the synthesis (builder pattern)
     ///
     /// # Example
@@ -210,6 +338,37 @@ impl AdditiveSynth {
     pub fn add_partial(mut self, partial: Partial) -> Self {
         self.phases.push(partial.phase_offset);
         self.partials.push(partial);
+        self.envelopes.push(None);
+        self
+    }
+
+    /// Add a partial whose level and pitch follow an envelope (builder pattern)
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::synthesis::additive::{AdditiveSynth, Partial, PartialEnvelope};
+    ///
+    /// // An octave that fades in as the fundamental fades out
+    /// let synth = AdditiveSynth::new(220.0, 44100.0)
+    ///     .add_partial_with_envelope(
+    ///         Partial::harmonic(1, 1.0),
+    ///         PartialEnvelope::new(1.0, vec![1.0, 0.0], vec![1.0, 1.0]),
+    ///     )
+    ///     .add_partial_with_envelope(
+    ///         Partial::harmonic(2, 1.0),
+    ///         PartialEnvelope::new(1.0, vec![0.0, 1.0], vec![1.0, 1.0]),
+    ///     );
+    /// assert_eq!(synth.duration(), Some(1.0));
+    /// ```
+    pub fn add_partial_with_envelope(
+        mut self,
+        partial: Partial,
+        envelope: PartialEnvelope,
+    ) -> Self {
+        self = self.add_partial(partial);
+        if let Some(last) = self.envelopes.last_mut() {
+            *last = Some(Arc::new(envelope));
+        }
         self
     }
 
@@ -235,6 +394,71 @@ impl AdditiveSynth {
         self
     }
 
+    /// Play partial envelopes `stretch` times slower, without changing pitch
+    ///
+    /// # Arguments
+    /// * `stretch` - 2.0 plays twice as long, 0.5 twice as fast
+    pub fn with_time_stretch(mut self, stretch: f32) -> Self {
+        self.set_time_stretch(stretch);
+        self
+    }
+
+    /// Change how much slower than recorded partial envelopes play
+    pub fn set_time_stretch(&mut self, stretch: f32) {
+        self.time_stretch = stretch.max(1e-3);
+    }
+
+    /// Divide the sum by the number of partials (on by default)
+    ///
+    /// Keeps hand-built timbres within range however many partials they have.
+    /// Resynthesized recordings turn it off, since their partials already carry
+    /// their measured levels.
+    pub fn with_normalization(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Change every partial's ratio, level or phase (builder pattern)
+    ///
+    /// Envelopes still apply on top, so a recording's partials can be
+    /// rebalanced, retuned or removed while keeping their movement.
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::synthesis::additive::{AdditiveSynth, Partial};
+    ///
+    /// // Keep only the odd harmonics, for a hollow, clarinet-like tone
+    /// let synth = AdditiveSynth::new(220.0, 44100.0)
+    ///     .with_partials((1..=8).map(|n| Partial::harmonic(n, 1.0 / n as f32)).collect())
+    ///     .map_partials(|partial| {
+    ///         if partial.frequency_ratio.round() as u32 % 2 == 0 {
+    ///             partial.amplitude = 0.0;
+    ///         }
+    ///     });
+    /// ```
+    pub fn map_partials(mut self, mut f: impl FnMut(&mut Partial)) -> Self {
+        for partial in &mut self.partials {
+            f(partial);
+        }
+        self
+    }
+
+    /// The partials being summed
+    pub fn partials(&self) -> &[Partial] {
+        &self.partials
+    }
+
+    /// How long the partial envelopes last at the current time stretch, in
+    /// seconds, or `None` if no partial has an envelope and the sound never ends
+    pub fn duration(&self) -> Option<f32> {
+        self.envelopes
+            .iter()
+            .flatten()
+            .map(|envelope| envelope.duration())
+            .reduce(f32::max)
+            .map(|duration| duration * self.time_stretch)
+    }
+
     /// Set the fundamental frequency
     ///
     /// # Example
@@ -248,28 +472,43 @@ impl AdditiveSynth {
         self.fundamental_freq = freq;
     }
 
-    /// Reset all phase accumulators to their initial offsets
+    /// Reset all phase accumulators to their initial offsets, and envelopes to their start
     pub fn reset(&mut self) {
         for (i, partial) in self.partials.iter().enumerate() {
             self.phases[i] = partial.phase_offset;
         }
+        self.time = 0.0;
     }
 
     /// Generate a single sample
     ///
     /// Sums all partials at their current phase positions and advances phases.
+    /// Partials with envelopes take their level and pitch from them; any pushed
+    /// above the Nyquist frequency are left out.
     #[inline]
     pub fn sample(&mut self) -> f32 {
         let mut output = 0.0;
 
         // Sum all partials
         for (i, partial) in self.partials.iter().enumerate() {
-            // Calculate this partial's frequency
-            let freq = self.fundamental_freq * partial.frequency_ratio;
+            // Calculate this partial's frequency and level
+            let (freq, amplitude) = match &self.envelopes[i] {
+                Some(envelope) => (
+                    self.fundamental_freq
+                        * partial.frequency_ratio
+                        * envelope.frequency_at(self.time),
+                    partial.amplitude * envelope.amplitude_at(self.time),
+                ),
+                None => (
+                    self.fundamental_freq * partial.frequency_ratio,
+                    partial.amplitude,
+                ),
+            };
 
             // Generate sine wave at current phase
-            let sample = (self.phases[i] * 2.0 * PI).sin() * partial.amplitude;
-            output += sample;
+            if freq < self.sample_rate * 0.5 {
+                output += (self.phases[i] * 2.0 * PI).sin() * amplitude;
+            }
 
             // Advance phase (frequency determines how fast phase increases)
             let phase_increment = freq / self.sample_rate;
@@ -281,11 +520,15 @@ impl AdditiveSynth {
             }
         }
 
+        self.time += 1.0 / (self.sample_rate * self.time_stretch);
+
         // Normalize by number of partials to prevent clipping
-        if !self.partials.is_empty() {
+        if self.partials.is_empty() {
+            0.0
+        } else if self.normalize {
             output / self.partials.len() as f32
         } else {
-            0.0
+            output
         }
     }
 
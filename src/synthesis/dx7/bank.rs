@@ -0,0 +1,779 @@
+//! The voices bundled with the crate
+//!
+//! Written for this crate rather than copied from a factory bank. Each voice
+//! starts from the INIT VOICE; only the settings that differ are listed.
+
+use super::{Dx7Operator, Dx7Voice};
+
+/// An operator at an output level and coarse ratio with its envelope
+fn op(output_level: u8, coarse: u8, rates: [u8; 4], levels: [u8; 4]) -> Dx7Operator {
+    Dx7Operator {
+        output_level,
+        coarse,
+        rates,
+        levels,
+        ..Dx7Operator::default()
+    }
+}
+
+/// A voice from its name, algorithm, feedback and operators 1-6
+fn voice(name: &str, algorithm: u8, feedback: u8, operators: [Dx7Operator; 6]) -> Dx7Voice {
+    Dx7Voice {
+        name: format!("{name:<10}"),
+        operators,
+        algorithm,
+        feedback,
+        ..Dx7Voice::default()
+    }
+}
+
+/// The 32 voices, in bank order
+pub(super) fn voices() -> Vec<Dx7Voice> {
+    vec![
+        voice(
+            "TINE EP",
+            5,
+            6,
+            [
+                op(99, 1, [96, 25, 25, 67], [99, 75, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 7,
+                    ..op(58, 14, [95, 50, 35, 78], [99, 75, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 8,
+                    ..op(99, 1, [95, 20, 20, 50], [99, 95, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(89, 1, [95, 29, 20, 50], [99, 95, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 6,
+                    ..op(99, 1, [95, 20, 20, 50], [99, 95, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(79, 1, [95, 29, 20, 50], [99, 95, 0, 0])
+                },
+            ],
+        ),
+        voice(
+            "SOFT EP",
+            5,
+            3,
+            [
+                op(99, 1, [90, 30, 20, 60], [99, 85, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(70, 1, [90, 40, 25, 60], [99, 70, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 9,
+                    ..op(95, 1, [90, 25, 20, 55], [99, 90, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 7,
+                    ..op(60, 7, [95, 60, 40, 70], [99, 50, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 5,
+                    ..op(90, 1, [90, 25, 20, 55], [99, 90, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 4,
+                    ..op(65, 1, [90, 40, 25, 60], [99, 70, 0, 0])
+                },
+            ],
+        ),
+        voice(
+            "DX BELL",
+            5,
+            0,
+            [
+                op(99, 1, [99, 20, 15, 25], [99, 80, 0, 0]),
+                Dx7Operator {
+                    fine: 50,
+                    velocity_sensitivity: 5,
+                    ..op(80, 3, [99, 30, 20, 30], [99, 70, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 76,
+                    ..op(95, 2, [99, 22, 18, 25], [99, 80, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 40,
+                    velocity_sensitivity: 6,
+                    ..op(75, 5, [99, 35, 25, 30], [99, 60, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 40,
+                    ..op(90, 5, [99, 25, 20, 25], [99, 70, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 93,
+                    velocity_sensitivity: 6,
+                    ..op(70, 8, [99, 45, 30, 35], [99, 50, 0, 0])
+                },
+            ],
+        ),
+        voice(
+            "TUB BELLS",
+            5,
+            7,
+            [
+                op(99, 1, [95, 33, 71, 25], [99, 0, 32, 0]),
+                Dx7Operator {
+                    fine: 50,
+                    velocity_sensitivity: 2,
+                    ..op(76, 3, [98, 12, 71, 28], [99, 0, 32, 0])
+                },
+                Dx7Operator {
+                    detune: 10,
+                    ..op(95, 1, [95, 33, 71, 25], [99, 0, 32, 0])
+                },
+                Dx7Operator {
+                    fine: 50,
+                    velocity_sensitivity: 2,
+                    ..op(78, 3, [98, 12, 71, 28], [99, 0, 32, 0])
+                },
+                Dx7Operator {
+                    detune: 4,
+                    ..op(93, 1, [95, 33, 71, 25], [99, 0, 32, 0])
+                },
+                Dx7Operator {
+                    fine: 52,
+                    velocity_sensitivity: 2,
+                    ..op(74, 2, [98, 12, 71, 28], [99, 0, 32, 0])
+                },
+            ],
+        ),
+        voice(
+            "SOLID BASS",
+            1,
+            5,
+            [
+                op(99, 0, [99, 40, 30, 70], [99, 85, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 4,
+                    ..op(85, 0, [99, 55, 35, 70], [99, 60, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 9,
+                    ..op(90, 0, [99, 40, 30, 70], [99, 85, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 3,
+                    ..op(75, 1, [99, 60, 40, 70], [99, 55, 0, 0])
+                },
+                op(70, 1, [99, 70, 40, 70], [99, 40, 0, 0]),
+                op(65, 1, [99, 75, 40, 70], [99, 30, 0, 0]),
+            ],
+        ),
+        voice(
+            "SLAP BASS",
+            16,
+            6,
+            [
+                op(99, 0, [99, 45, 30, 75], [99, 80, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(80, 1, [99, 65, 40, 75], [99, 50, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 7,
+                    ..op(75, 3, [99, 75, 45, 75], [99, 30, 0, 0])
+                },
+                op(70, 1, [99, 70, 40, 75], [99, 40, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(78, 0, [99, 60, 40, 75], [99, 50, 0, 0])
+                },
+                op(70, 1, [99, 60, 40, 75], [99, 40, 0, 0]),
+            ],
+        ),
+        voice(
+            "SYN BASS",
+            3,
+            7,
+            [
+                op(99, 0, [99, 50, 40, 80], [99, 90, 70, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 4,
+                    ..op(82, 0, [99, 45, 40, 80], [99, 70, 50, 0])
+                },
+                op(77, 1, [99, 50, 40, 80], [99, 70, 40, 0]),
+                Dx7Operator {
+                    detune: 9,
+                    ..op(95, 0, [99, 50, 40, 80], [99, 90, 70, 0])
+                },
+                op(80, 0, [99, 45, 40, 80], [99, 70, 50, 0]),
+                op(72, 1, [99, 50, 40, 80], [99, 60, 40, 0]),
+            ],
+        ),
+        Dx7Voice {
+            lfo_speed: 37,
+            lfo_pitch_depth: 5,
+            ..voice(
+                "BRASS 1",
+                22,
+                7,
+                [
+                    op(99, 1, [72, 76, 99, 71], [99, 88, 96, 0]),
+                    Dx7Operator {
+                        velocity_sensitivity: 2,
+                        ..op(86, 1, [62, 51, 29, 71], [82, 95, 96, 0])
+                    },
+                    Dx7Operator {
+                        detune: 8,
+                        ..op(98, 1, [72, 76, 99, 71], [99, 88, 96, 0])
+                    },
+                    Dx7Operator {
+                        detune: 6,
+                        ..op(97, 1, [72, 76, 99, 71], [99, 88, 96, 0])
+                    },
+                    Dx7Operator {
+                        detune: 9,
+                        ..op(96, 1, [72, 76, 99, 71], [99, 88, 96, 0])
+                    },
+                    Dx7Operator {
+                        velocity_sensitivity: 2,
+                        ..op(84, 1, [53, 61, 99, 71], [99, 92, 90, 0])
+                    },
+                ],
+            )
+        },
+        voice(
+            "HORNS",
+            18,
+            4,
+            [
+                op(99, 1, [60, 40, 99, 65], [99, 90, 95, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 3,
+                    ..op(80, 1, [55, 45, 99, 65], [95, 85, 90, 0])
+                },
+                op(72, 3, [60, 50, 99, 65], [90, 80, 85, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 2,
+                    ..op(78, 1, [55, 45, 99, 65], [95, 85, 90, 0])
+                },
+                op(70, 2, [50, 45, 99, 65], [90, 85, 85, 0]),
+                op(65, 1, [50, 45, 99, 65], [90, 85, 85, 0]),
+            ],
+        ),
+        voice(
+            "PIPE ORGAN",
+            32,
+            0,
+            [
+                op(95, 1, [75, 99, 99, 60], [99, 99, 99, 0]),
+                op(88, 2, [75, 99, 99, 60], [99, 99, 99, 0]),
+                op(84, 4, [75, 99, 99, 60], [99, 99, 99, 0]),
+                op(80, 0, [75, 99, 99, 60], [99, 99, 99, 0]),
+                op(78, 3, [75, 99, 99, 60], [99, 99, 99, 0]),
+                op(74, 8, [75, 99, 99, 60], [99, 99, 99, 0]),
+            ],
+        ),
+        voice(
+            "DRAWBARS",
+            32,
+            5,
+            [
+                op(97, 0, [99, 99, 99, 85], [99, 99, 99, 0]),
+                op(94, 1, [99, 99, 99, 85], [99, 99, 99, 0]),
+                Dx7Operator {
+                    fine: 50,
+                    ..op(90, 1, [99, 99, 99, 85], [99, 99, 99, 0])
+                },
+                op(88, 2, [99, 99, 99, 85], [99, 99, 99, 0]),
+                op(84, 4, [99, 99, 99, 85], [99, 99, 99, 0]),
+                op(78, 6, [99, 99, 99, 85], [99, 99, 99, 0]),
+            ],
+        ),
+        voice(
+            "PERC ORGAN",
+            32,
+            0,
+            [
+                op(97, 1, [99, 99, 99, 85], [99, 99, 99, 0]),
+                op(94, 2, [99, 99, 99, 85], [99, 99, 99, 0]),
+                op(88, 0, [99, 99, 99, 85], [99, 99, 99, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 4,
+                    ..op(85, 3, [99, 60, 30, 85], [99, 0, 0, 0])
+                },
+                op(80, 4, [99, 99, 99, 85], [99, 99, 99, 0]),
+                op(70, 8, [99, 99, 99, 85], [99, 99, 99, 0]),
+            ],
+        ),
+        voice(
+            "MARIMBA",
+            7,
+            0,
+            [
+                op(99, 1, [99, 40, 30, 50], [99, 0, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(70, 4, [99, 70, 40, 60], [99, 0, 0, 0])
+                },
+                op(90, 4, [99, 55, 40, 55], [99, 0, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 4,
+                    ..op(60, 1, [99, 70, 40, 60], [99, 0, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(55, 10, [99, 80, 40, 60], [99, 0, 0, 0])
+                },
+                Dx7Operator::default(),
+            ],
+        ),
+        Dx7Voice {
+            lfo_speed: 60,
+            lfo_amp_depth: 30,
+            lfo_wave: 4,
+            ..voice(
+                "VIBES",
+                5,
+                0,
+                [
+                    op(99, 1, [99, 28, 20, 40], [99, 40, 0, 0]),
+                    Dx7Operator {
+                        velocity_sensitivity: 5,
+                        ..op(60, 4, [99, 45, 30, 45], [99, 20, 0, 0])
+                    },
+                    Dx7Operator {
+                        detune: 9,
+                        ..op(90, 1, [99, 28, 20, 40], [99, 40, 0, 0])
+                    },
+                    Dx7Operator {
+                        velocity_sensitivity: 5,
+                        ..op(55, 4, [99, 45, 30, 45], [99, 20, 0, 0])
+                    },
+                    op(70, 4, [99, 40, 30, 45], [99, 10, 0, 0]),
+                    op(50, 1, [99, 50, 30, 45], [99, 10, 0, 0]),
+                ],
+            )
+        },
+        voice(
+            "GLOCKEN",
+            5,
+            0,
+            [
+                op(99, 2, [99, 30, 20, 35], [99, 30, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(75, 7, [99, 50, 30, 40], [99, 10, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 40,
+                    ..op(90, 5, [99, 35, 25, 35], [99, 20, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(65, 9, [99, 55, 35, 40], [99, 10, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 93,
+                    ..op(80, 8, [99, 40, 30, 35], [99, 10, 0, 0])
+                },
+                op(60, 1, [99, 60, 35, 40], [99, 10, 0, 0]),
+            ],
+        ),
+        voice(
+            "KALIMBA",
+            5,
+            2,
+            [
+                op(99, 1, [99, 45, 35, 55], [99, 0, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(72, 6, [99, 70, 45, 60], [99, 0, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 8,
+                    ..op(85, 1, [99, 45, 35, 55], [99, 0, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 51,
+                    velocity_sensitivity: 6,
+                    ..op(65, 3, [99, 75, 45, 60], [99, 0, 0, 0])
+                },
+                Dx7Operator::default(),
+                Dx7Operator::default(),
+            ],
+        ),
+        Dx7Voice {
+            lfo_speed: 30,
+            lfo_delay: 33,
+            lfo_pitch_depth: 6,
+            lfo_wave: 4,
+            ..voice(
+                "STRINGS",
+                2,
+                4,
+                [
+                    op(99, 1, [45, 30, 99, 55], [99, 90, 95, 0]),
+                    op(75, 1, [40, 30, 99, 55], [95, 90, 90, 0]),
+                    Dx7Operator {
+                        detune: 10,
+                        ..op(97, 1, [45, 30, 99, 55], [99, 90, 95, 0])
+                    },
+                    Dx7Operator {
+                        detune: 4,
+                        ..op(70, 1, [40, 30, 99, 55], [95, 90, 90, 0])
+                    },
+                    op(68, 3, [40, 30, 99, 55], [90, 85, 85, 0]),
+                    op(60, 1, [40, 30, 99, 55], [90, 85, 85, 0]),
+                ],
+            )
+        },
+        Dx7Voice {
+            lfo_speed: 25,
+            lfo_delay: 50,
+            lfo_pitch_depth: 4,
+            lfo_wave: 4,
+            ..voice(
+                "WARM PAD",
+                31,
+                3,
+                [
+                    op(97, 1, [30, 25, 99, 40], [99, 95, 95, 0]),
+                    Dx7Operator {
+                        detune: 11,
+                        ..op(95, 1, [30, 25, 99, 40], [99, 95, 95, 0])
+                    },
+                    Dx7Operator {
+                        detune: 3,
+                        ..op(95, 1, [30, 25, 99, 40], [99, 95, 95, 0])
+                    },
+                    op(92, 2, [28, 25, 99, 40], [99, 95, 95, 0]),
+                    op(96, 0, [30, 25, 99, 40], [99, 95, 95, 0]),
+                    op(70, 2, [25, 25, 99, 40], [90, 90, 90, 0]),
+                ],
+            )
+        },
+        voice(
+            "GLASS PAD",
+            5,
+            0,
+            [
+                op(99, 1, [35, 25, 99, 40], [99, 90, 90, 0]),
+                op(60, 5, [35, 25, 99, 40], [90, 80, 80, 0]),
+                Dx7Operator {
+                    detune: 9,
+                    ..op(95, 2, [35, 25, 99, 40], [99, 90, 90, 0])
+                },
+                op(55, 7, [35, 25, 99, 40], [90, 80, 80, 0]),
+                Dx7Operator {
+                    detune: 5,
+                    ..op(92, 4, [35, 25, 99, 40], [99, 90, 90, 0])
+                },
+                op(50, 9, [35, 25, 99, 40], [90, 80, 80, 0]),
+            ],
+        ),
+        Dx7Voice {
+            lfo_speed: 32,
+            lfo_delay: 40,
+            lfo_pitch_depth: 5,
+            lfo_wave: 4,
+            ..voice(
+                "CHOIR",
+                25,
+                2,
+                [
+                    op(97, 1, [40, 30, 99, 45], [99, 95, 95, 0]),
+                    op(95, 2, [40, 30, 99, 45], [99, 95, 95, 0]),
+                    Dx7Operator {
+                        detune: 10,
+                        ..op(93, 1, [40, 30, 99, 45], [99, 95, 95, 0])
+                    },
+                    op(92, 3, [40, 30, 99, 45], [99, 90, 90, 0]),
+                    Dx7Operator {
+                        detune: 4,
+                        ..op(90, 1, [40, 30, 99, 45], [99, 95, 95, 0])
+                    },
+                    op(62, 1, [40, 30, 99, 45], [90, 90, 90, 0]),
+                ],
+            )
+        },
+        Dx7Voice {
+            lfo_speed: 34,
+            lfo_delay: 40,
+            lfo_pitch_depth: 4,
+            lfo_wave: 4,
+            ..voice(
+                "FLUTE",
+                19,
+                5,
+                [
+                    op(99, 1, [60, 40, 99, 60], [99, 95, 95, 0]),
+                    Dx7Operator {
+                        velocity_sensitivity: 3,
+                        ..op(50, 1, [60, 40, 99, 60], [90, 80, 80, 0])
+                    },
+                    op(40, 3, [60, 40, 99, 60], [90, 80, 80, 0]),
+                    op(85, 2, [60, 40, 99, 60], [99, 90, 90, 0]),
+                    Dx7Operator {
+                        detune: 9,
+                        ..op(70, 1, [60, 40, 99, 60], [99, 90, 90, 0])
+                    },
+                    op(45, 1, [70, 60, 99, 60], [99, 50, 40, 0]),
+                ],
+            )
+        },
+        voice(
+            "CLARINET",
+            1,
+            6,
+            [
+                op(99, 1, [60, 40, 99, 60], [99, 95, 95, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 3,
+                    ..op(76, 2, [60, 40, 99, 60], [95, 90, 90, 0])
+                },
+                op(80, 3, [60, 40, 99, 60], [95, 90, 90, 0]),
+                op(60, 1, [60, 40, 99, 60], [95, 90, 90, 0]),
+                op(50, 2, [60, 40, 99, 60], [90, 85, 85, 0]),
+                op(55, 1, [60, 40, 99, 60], [90, 85, 85, 0]),
+            ],
+        ),
+        voice(
+            "HARPSI",
+            5,
+            0,
+            [
+                op(99, 1, [99, 45, 30, 70], [99, 60, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 3,
+                    ..op(78, 5, [99, 55, 35, 70], [99, 40, 0, 0])
+                },
+                op(95, 2, [99, 45, 30, 70], [99, 60, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 3,
+                    ..op(74, 7, [99, 55, 35, 70], [99, 40, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 9,
+                    ..op(90, 1, [99, 45, 30, 70], [99, 60, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 3,
+                    ..op(76, 9, [99, 60, 35, 70], [99, 30, 0, 0])
+                },
+            ],
+        ),
+        voice(
+            "CLAV",
+            3,
+            6,
+            [
+                op(99, 1, [99, 55, 40, 80], [99, 70, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(85, 3, [99, 60, 40, 80], [99, 60, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(78, 5, [99, 70, 45, 80], [99, 40, 0, 0])
+                },
+                op(92, 2, [99, 55, 40, 80], [99, 70, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(80, 1, [99, 60, 40, 80], [99, 60, 0, 0])
+                },
+                op(70, 3, [99, 70, 45, 80], [99, 40, 0, 0]),
+            ],
+        ),
+        voice(
+            "PLUCK GTR",
+            2,
+            5,
+            [
+                op(99, 1, [99, 35, 28, 60], [99, 70, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(70, 3, [99, 60, 40, 65], [99, 30, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 8,
+                    ..op(95, 1, [99, 38, 28, 60], [99, 70, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 4,
+                    ..op(75, 1, [99, 55, 40, 65], [99, 40, 0, 0])
+                },
+                op(65, 2, [99, 60, 40, 65], [99, 30, 0, 0]),
+                op(60, 5, [99, 70, 45, 65], [99, 20, 0, 0]),
+            ],
+        ),
+        voice(
+            "HARP",
+            5,
+            0,
+            [
+                op(99, 1, [99, 30, 25, 45], [99, 50, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(62, 2, [99, 50, 35, 50], [99, 20, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 9,
+                    ..op(93, 1, [99, 32, 25, 45], [99, 50, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(55, 4, [99, 55, 35, 50], [99, 15, 0, 0])
+                },
+                op(88, 2, [99, 35, 25, 45], [99, 40, 0, 0]),
+                op(50, 1, [99, 55, 35, 50], [99, 10, 0, 0]),
+            ],
+        ),
+        Dx7Voice {
+            lfo_speed: 38,
+            lfo_delay: 50,
+            lfo_pitch_depth: 6,
+            lfo_wave: 4,
+            ..voice(
+                "SYNC LEAD",
+                1,
+                7,
+                [
+                    op(99, 1, [99, 60, 99, 70], [99, 95, 95, 0]),
+                    Dx7Operator {
+                        velocity_sensitivity: 3,
+                        ..op(88, 3, [75, 40, 99, 70], [99, 70, 75, 0])
+                    },
+                    Dx7Operator {
+                        detune: 10,
+                        ..op(92, 1, [99, 60, 99, 70], [99, 95, 95, 0])
+                    },
+                    op(85, 2, [75, 40, 99, 70], [99, 80, 80, 0]),
+                    op(80, 1, [75, 40, 99, 70], [99, 80, 80, 0]),
+                    op(78, 1, [75, 40, 99, 70], [99, 80, 80, 0]),
+                ],
+            )
+        },
+        voice(
+            "SQUARE LD",
+            3,
+            7,
+            [
+                op(99, 1, [99, 60, 99, 70], [99, 95, 95, 0]),
+                op(80, 1, [99, 60, 99, 70], [99, 90, 90, 0]),
+                op(75, 2, [99, 60, 99, 70], [99, 90, 90, 0]),
+                Dx7Operator {
+                    detune: 9,
+                    ..op(95, 1, [99, 60, 99, 70], [99, 95, 95, 0])
+                },
+                op(80, 1, [99, 60, 99, 70], [99, 90, 90, 0]),
+                op(75, 2, [99, 60, 99, 70], [99, 90, 90, 0]),
+            ],
+        ),
+        voice(
+            "STEEL DRUM",
+            5,
+            0,
+            [
+                op(99, 1, [99, 40, 30, 50], [99, 30, 0, 0]),
+                Dx7Operator {
+                    fine: 1,
+                    velocity_sensitivity: 5,
+                    ..op(75, 2, [99, 50, 40, 55], [99, 20, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 2,
+                    ..op(90, 3, [99, 45, 35, 50], [99, 20, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(65, 1, [99, 60, 40, 55], [99, 10, 0, 0])
+                },
+                Dx7Operator {
+                    fine: 3,
+                    ..op(85, 4, [99, 50, 40, 50], [99, 10, 0, 0])
+                },
+                op(60, 0, [99, 65, 40, 55], [99, 10, 0, 0]),
+            ],
+        ),
+        voice(
+            "LOG DRUM",
+            5,
+            0,
+            [
+                op(99, 0, [99, 55, 40, 60], [99, 0, 0, 0]),
+                Dx7Operator {
+                    fine: 50,
+                    velocity_sensitivity: 5,
+                    ..op(70, 1, [99, 70, 45, 65], [99, 0, 0, 0])
+                },
+                op(90, 1, [99, 55, 40, 60], [99, 0, 0, 0]),
+                Dx7Operator {
+                    fine: 76,
+                    velocity_sensitivity: 5,
+                    ..op(65, 2, [99, 70, 45, 65], [99, 0, 0, 0])
+                },
+                Dx7Operator::default(),
+                Dx7Operator::default(),
+            ],
+        ),
+        Dx7Voice {
+            lfo_speed: 20,
+            lfo_pitch_depth: 40,
+            lfo_wave: 5,
+            pitch_mod_sensitivity: 5,
+            ..voice(
+                "SPACE FX",
+                1,
+                7,
+                [
+                    op(99, 1, [25, 20, 99, 30], [99, 90, 90, 0]),
+                    Dx7Operator {
+                        fine: 71,
+                        ..op(90, 0, [20, 15, 99, 30], [99, 70, 80, 0])
+                    },
+                    Dx7Operator {
+                        detune: 14,
+                        ..op(90, 1, [25, 20, 99, 30], [99, 90, 90, 0])
+                    },
+                    Dx7Operator {
+                        fine: 33,
+                        ..op(85, 0, [20, 15, 99, 30], [99, 70, 80, 0])
+                    },
+                    op(80, 1, [20, 15, 99, 30], [99, 80, 80, 0]),
+                    Dx7Operator {
+                        fine: 50,
+                        fixed_frequency: true,
+                        ..op(85, 1, [20, 15, 99, 30], [99, 80, 80, 0])
+                    },
+                ],
+            )
+        },
+        voice(
+            "CELESTA",
+            5,
+            0,
+            [
+                op(99, 2, [99, 35, 25, 45], [99, 40, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(65, 8, [99, 55, 35, 50], [99, 15, 0, 0])
+                },
+                op(92, 4, [99, 38, 28, 45], [99, 30, 0, 0]),
+                Dx7Operator {
+                    velocity_sensitivity: 5,
+                    ..op(55, 1, [99, 55, 35, 50], [99, 15, 0, 0])
+                },
+                Dx7Operator {
+                    detune: 9,
+                    ..op(85, 2, [99, 40, 30, 45], [99, 30, 0, 0])
+                },
+                Dx7Operator {
+                    velocity_sensitivity: 6,
+                    ..op(50, 14, [99, 70, 40, 50], [99, 5, 0, 0])
+                },
+            ],
+        ),
+    ]
+}
@@ -0,0 +1,828 @@
+//! DX7 SysEx patch import and export
+//!
+//! Reads the voice dumps a Yamaha DX7 sends and receives: 32-voice banks
+//! (4104-byte `.syx` files) and single-voice dumps (163 bytes). A file may hold
+//! several dumps one after another. Each [`Dx7Voice`] keeps every parameter of
+//! the original and converts to an [`FMPatch`] or an [`Instrument`] preset.
+//!
+//! Writing a voice back out gives the same bytes for any dump this module wrote
+//! and for well-formed ones from elsewhere. Unused bits set in a packed byte are
+//! dropped and non-printable name characters become spaces, so such a dump reads
+//! back as the same voices but not the same bytes.
+//!
+//! The conversion keeps what the [`FMPatch`] engine can play: the algorithm,
+//! feedback, operator frequencies, output levels, velocity sensitivity and the
+//! four-stage envelopes (as ADSR). Keyboard level/rate scaling, the LFO and the
+//! pitch envelope are kept in the voice but not played.
+//!
+//! A bank of 32 voices ships with the crate; see [`bundled_bank`].
+//!
+//! # Example
+//! ```
+//! use tunes::synthesis::dx7;
+//!
+//! let voices = dx7::bundled_bank();
+//! assert_eq!(voices.len(), 32);
+//!
+//! // Write the bank back out as a SysEx dump
+//! let bytes = dx7::bank_to_sysex(&voices, 0).unwrap();
+//! assert_eq!(dx7::parse_sysex(&bytes).unwrap(), voices);
+//! ```
+
+use crate::error::{Result, TunesError};
+use crate::instruments::Instrument;
+use crate::synthesis::envelope::Envelope;
+use crate::synthesis::fm_operators::{FMAlgorithm, FMOperator, FMPatch};
+use std::path::Path;
+
+mod bank;
+
+const SYSEX_START: u8 = 0xF0;
+const SYSEX_END: u8 = 0xF7;
+const YAMAHA_ID: u8 = 0x43;
+
+/// Format byte and data size of a single-voice dump
+const VOICE_FORMAT: u8 = 0;
+const VOICE_SIZE: usize = 155;
+
+/// Format byte and data size of a 32-voice bank
+const BANK_FORMAT: u8 = 9;
+const BANK_SIZE: usize = 4096;
+const BANK_VOICES: usize = 32;
+const PACKED_VOICE_SIZE: usize = 128;
+
+/// Bytes per operator in the unpacked and packed layouts
+const OPERATOR_SIZE: usize = 21;
+const PACKED_OPERATOR_SIZE: usize = 17;
+
+const NAME_LENGTH: usize = 10;
+
+/// Seconds a DX7 envelope stage at rate 0 takes to sweep the full level range
+const SLOWEST_STAGE: f32 = 39.0;
+
+/// Rate steps that halve an envelope stage's time
+const RATE_STEPS_PER_HALVING: f32 = 7.2;
+
+/// One operator of a [`Dx7Voice`], in the DX7's own units (mostly 0-99)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dx7Operator {
+    /// Envelope rates 1-4 (0-99)
+    pub rates: [u8; 4],
+    /// Envelope levels 1-4 (0-99)
+    pub levels: [u8; 4],
+    /// Keyboard level scaling break point (0-99, 39 = C3)
+    pub breakpoint: u8,
+    /// Keyboard level scaling depth below and above the break point (0-99)
+    pub left_depth: u8,
+    pub right_depth: u8,
+    /// Keyboard level scaling curves below and above the break point (0-3)
+    pub left_curve: u8,
+    pub right_curve: u8,
+    /// Keyboard rate scaling (0-7)
+    pub rate_scaling: u8,
+    /// Amplitude modulation sensitivity (0-3)
+    pub amp_mod_sensitivity: u8,
+    /// Key velocity sensitivity (0-7)
+    pub velocity_sensitivity: u8,
+    /// Output level (0-99)
+    pub output_level: u8,
+    /// Fixed frequency instead of a ratio
+    pub fixed_frequency: bool,
+    /// Frequency coarse (0-31) and fine (0-99)
+    pub coarse: u8,
+    pub fine: u8,
+    /// Detune (0-14, 7 = none)
+    pub detune: u8,
+}
+
+impl Default for Dx7Operator {
+    /// The operator of the DX7's INIT VOICE, silent
+    fn default() -> Self {
+        Self {
+            rates: [99, 99, 99, 99],
+            levels: [99, 99, 99, 0],
+            breakpoint: 39,
+            left_depth: 0,
+            right_depth: 0,
+            left_curve: 0,
+            right_curve: 0,
+            rate_scaling: 0,
+            amp_mod_sensitivity: 0,
+            velocity_sensitivity: 0,
+            output_level: 0,
+            fixed_frequency: false,
+            coarse: 1,
+            fine: 0,
+            detune: 7,
+        }
+    }
+}
+
+impl Dx7Operator {
+    /// Read an operator from the unpacked layout
+    fn from_unpacked(data: &[u8]) -> Self {
+        Self {
+            rates: [data[0], data[1], data[2], data[3]],
+            levels: [data[4], data[5], data[6], data[7]],
+            breakpoint: data[8],
+            left_depth: data[9],
+            right_depth: data[10],
+            left_curve: data[11] & 0x03,
+            right_curve: data[12] & 0x03,
+            rate_scaling: data[13] & 0x07,
+            amp_mod_sensitivity: data[14] & 0x03,
+            velocity_sensitivity: data[15] & 0x07,
+            output_level: data[16],
+            fixed_frequency: data[17] & 0x01 != 0,
+            coarse: data[18] & 0x1F,
+            fine: data[19],
+            detune: data[20] & 0x0F,
+        }
+    }
+
+    fn write_unpacked(&self, data: &mut [u8]) {
+        data[..4].copy_from_slice(&self.rates);
+        data[4..8].copy_from_slice(&self.levels);
+        data[8] = self.breakpoint;
+        data[9] = self.left_depth;
+        data[10] = self.right_depth;
+        data[11] = self.left_curve;
+        data[12] = self.right_curve;
+        data[13] = self.rate_scaling;
+        data[14] = self.amp_mod_sensitivity;
+        data[15] = self.velocity_sensitivity;
+        data[16] = self.output_level;
+        data[17] = self.fixed_frequency as u8;
+        data[18] = self.coarse;
+        data[19] = self.fine;
+        data[20] = self.detune;
+    }
+
+    /// Read an operator from the packed bank layout
+    fn from_packed(data: &[u8]) -> Self {
+        Self {
+            rates: [data[0], data[1], data[2], data[3]],
+            levels: [data[4], data[5], data[6], data[7]],
+            breakpoint: data[8],
+            left_depth: data[9],
+            right_depth: data[10],
+            left_curve: data[11] & 0x03,
+            right_curve: (data[11] >> 2) & 0x03,
+            rate_scaling: data[12] & 0x07,
+            detune: (data[12] >> 3) & 0x0F,
+            amp_mod_sensitivity: data[13] & 0x03,
+            velocity_sensitivity: (data[13] >> 2) & 0x07,
+            output_level: data[14],
+            fixed_frequency: data[15] & 0x01 != 0,
+            coarse: (data[15] >> 1) & 0x1F,
+            fine: data[16],
+        }
+    }
+
+    fn write_packed(&self, data: &mut [u8]) {
+        data[..4].copy_from_slice(&self.rates);
+        data[4..8].copy_from_slice(&self.levels);
+        data[8] = self.breakpoint;
+        data[9] = self.left_depth;
+        data[10] = self.right_depth;
+        data[11] = (self.left_curve & 0x03) | (self.right_curve & 0x03) << 2;
+        data[12] = (self.rate_scaling & 0x07) | (self.detune & 0x0F) << 3;
+        data[13] = (self.amp_mod_sensitivity & 0x03) | (self.velocity_sensitivity & 0x07) << 2;
+        data[14] = self.output_level;
+        data[15] = self.fixed_frequency as u8 | (self.coarse & 0x1F) << 1;
+        data[16] = self.fine;
+    }
+
+    /// Convert to an [`FMOperator`], shifting ratio frequencies by `transpose` semitones
+    pub fn to_fm_operator(self, transpose: i32) -> FMOperator {
+        let mut operator = if self.fixed_frequency {
+            // 1, 10, 100 or 1000 Hz, times up to 10^0.99
+            let exponent = (self.coarse & 0x03) as f32 + self.fine.min(99) as f32 / 100.0;
+            FMOperator::fixed(10f32.powf(exponent))
+        } else {
+            let coarse = if self.coarse == 0 {
+                0.5
+            } else {
+                self.coarse as f32
+            };
+            let ratio = coarse * (1.0 + self.fine.min(99) as f32 / 100.0);
+            FMOperator::new(ratio * 2f32.powf(transpose as f32 / 12.0))
+        };
+
+        // Detune is roughly a cent per step either side of 7
+        operator.detune = self.detune.min(14) as f32 - 7.0;
+
+        let [l1, l2, l3, _] = self.levels.map(|level| level.min(99));
+        let [r1, r2, r3, r4] = self.rates.map(|rate| rate.min(99));
+        let peak = level_to_amplitude(l1.max(l2));
+        let attack = stage_time(r1, 0, l1);
+        let decay = stage_time(r2, l1, l2) + stage_time(r3, l2, l3);
+        let sustain = if peak > 0.0 {
+            level_to_amplitude(l3) / peak
+        } else {
+            0.0
+        };
+        let release = stage_time(r4, l3, 0);
+
+        operator
+            .with_level(level_to_amplitude(self.output_level) * peak)
+            .with_envelope(Envelope::new(attack, decay, sustain, release))
+            .with_velocity_sensitivity(self.velocity_sensitivity.min(7) as f32 / 7.0)
+    }
+}
+
+/// A DX7 voice with every parameter of the original dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dx7Voice {
+    /// Voice name, up to 10 characters (kept with its padding)
+    pub name: String,
+    /// Operators 1-6; `operators[0]` is operator 1
+    pub operators: [Dx7Operator; 6],
+    /// Pitch envelope rates and levels (0-99, 50 = no shift)
+    pub pitch_rates: [u8; 4],
+    pub pitch_levels: [u8; 4],
+    /// Algorithm (1-32)
+    pub algorithm: u8,
+    /// Feedback (0-7)
+    pub feedback: u8,
+    pub oscillator_key_sync: bool,
+    /// LFO speed, delay, pitch and amplitude modulation depth (0-99)
+    pub lfo_speed: u8,
+    pub lfo_delay: u8,
+    pub lfo_pitch_depth: u8,
+    pub lfo_amp_depth: u8,
+    pub lfo_key_sync: bool,
+    /// LFO wave (0-5: triangle, saw down, saw up, square, sine, sample and hold)
+    pub lfo_wave: u8,
+    /// Pitch modulation sensitivity (0-7)
+    pub pitch_mod_sensitivity: u8,
+    /// Transpose in semitones (0-48, 24 = C3, no shift)
+    pub transpose: u8,
+}
+
+impl Default for Dx7Voice {
+    /// The DX7's INIT VOICE: operator 1 alone, as a plain sine
+    fn default() -> Self {
+        let mut operators = [Dx7Operator::default(); 6];
+        operators[0].output_level = 99;
+        Self {
+            name: "INIT VOICE".to_string(),
+            operators,
+            pitch_rates: [99, 99, 99, 99],
+            pitch_levels: [50, 50, 50, 50],
+            algorithm: 1,
+            feedback: 0,
+            oscillator_key_sync: true,
+            lfo_speed: 35,
+            lfo_delay: 0,
+            lfo_pitch_depth: 0,
+            lfo_amp_depth: 0,
+            lfo_key_sync: true,
+            lfo_wave: 0,
+            pitch_mod_sensitivity: 3,
+            transpose: 24,
+        }
+    }
+}
+
+impl Dx7Voice {
+    /// Read a voice from the 155-byte layout of a single-voice dump
+    pub fn from_unpacked(data: &[u8]) -> Result<Self> {
+        check_size(data, VOICE_SIZE, "voice")?;
+        let ops = &data[..6 * OPERATOR_SIZE];
+        Ok(Self {
+            name: read_name(&data[145..155]),
+            // Operator 6 comes first
+            operators: std::array::from_fn(|i| {
+                Dx7Operator::from_unpacked(&ops[(5 - i) * OPERATOR_SIZE..][..OPERATOR_SIZE])
+            }),
+            pitch_rates: [data[126], data[127], data[128], data[129]],
+            pitch_levels: [data[130], data[131], data[132], data[133]],
+            algorithm: (data[134] & 0x1F) + 1,
+            feedback: data[135] & 0x07,
+            oscillator_key_sync: data[136] & 0x01 != 0,
+            lfo_speed: data[137],
+            lfo_delay: data[138],
+            lfo_pitch_depth: data[139],
+            lfo_amp_depth: data[140],
+            lfo_key_sync: data[141] & 0x01 != 0,
+            lfo_wave: data[142] & 0x07,
+            pitch_mod_sensitivity: data[143] & 0x07,
+            transpose: data[144],
+        })
+    }
+
+    /// Write the voice in the 155-byte layout of a single-voice dump
+    pub fn to_unpacked(&self) -> [u8; VOICE_SIZE] {
+        let mut data = [0u8; VOICE_SIZE];
+        for (i, op) in self.operators.iter().enumerate() {
+            op.write_unpacked(&mut data[(5 - i) * OPERATOR_SIZE..][..OPERATOR_SIZE]);
+        }
+        data[126..130].copy_from_slice(&self.pitch_rates);
+        data[130..134].copy_from_slice(&self.pitch_levels);
+        data[134] = self.algorithm.clamp(1, 32) - 1;
+        data[135] = self.feedback;
+        data[136] = self.oscillator_key_sync as u8;
+        data[137] = self.lfo_speed;
+        data[138] = self.lfo_delay;
+        data[139] = self.lfo_pitch_depth;
+        data[140] = self.lfo_amp_depth;
+        data[141] = self.lfo_key_sync as u8;
+        data[142] = self.lfo_wave;
+        data[143] = self.pitch_mod_sensitivity;
+        data[144] = self.transpose;
+        write_name(&self.name, &mut data[145..155]);
+        data
+    }
+
+    /// Read a voice from the 128-byte packed layout used in banks
+    pub fn from_packed(data: &[u8]) -> Result<Self> {
+        check_size(data, PACKED_VOICE_SIZE, "packed voice")?;
+        let ops = &data[..6 * PACKED_OPERATOR_SIZE];
+        Ok(Self {
+            name: read_name(&data[118..128]),
+            operators: std::array::from_fn(|i| {
+                Dx7Operator::from_packed(
+                    &ops[(5 - i) * PACKED_OPERATOR_SIZE..][..PACKED_OPERATOR_SIZE],
+                )
+            }),
+            pitch_rates: [data[102], data[103], data[104], data[105]],
+            pitch_levels: [data[106], data[107], data[108], data[109]],
+            algorithm: (data[110] & 0x1F) + 1,
+            feedback: data[111] & 0x07,
+            oscillator_key_sync: data[111] & 0x08 != 0,
+            lfo_speed: data[112],
+            lfo_delay: data[113],
+            lfo_pitch_depth: data[114],
+            lfo_amp_depth: data[115],
+            lfo_key_sync: data[116] & 0x01 != 0,
+            lfo_wave: (data[116] >> 1) & 0x07,
+            pitch_mod_sensitivity: (data[116] >> 4) & 0x07,
+            transpose: data[117],
+        })
+    }
+
+    /// Write the voice in the 128-byte packed layout used in banks
+    pub fn to_packed(&self) -> [u8; PACKED_VOICE_SIZE] {
+        let mut data = [0u8; PACKED_VOICE_SIZE];
+        for (i, op) in self.operators.iter().enumerate() {
+            op.write_packed(&mut data[(5 - i) * PACKED_OPERATOR_SIZE..][..PACKED_OPERATOR_SIZE]);
+        }
+        data[102..106].copy_from_slice(&self.pitch_rates);
+        data[106..110].copy_from_slice(&self.pitch_levels);
+        data[110] = self.algorithm.clamp(1, 32) - 1;
+        data[111] = (self.feedback & 0x07) | (self.oscillator_key_sync as u8) << 3;
+        data[112] = self.lfo_speed;
+        data[113] = self.lfo_delay;
+        data[114] = self.lfo_pitch_depth;
+        data[115] = self.lfo_amp_depth;
+        data[116] = self.lfo_key_sync as u8
+            | (self.lfo_wave & 0x07) << 1
+            | (self.pitch_mod_sensitivity & 0x07) << 4;
+        data[117] = self.transpose;
+        write_name(&self.name, &mut data[118..128]);
+        data
+    }
+
+    /// Single-voice SysEx dump on a MIDI channel (0-15)
+    pub fn to_sysex(&self, channel: u8) -> Vec<u8> {
+        dump(VOICE_FORMAT, channel, &self.to_unpacked())
+    }
+
+    /// Convert to an [`FMPatch`] (see the [module docs](self) for what carries over)
+    pub fn to_fm_patch(&self) -> FMPatch {
+        let algorithm = FMAlgorithm::dx7(self.algorithm.clamp(1, 32));
+        let transpose = self.transpose.min(48) as i32 - 24;
+        let mut patch = FMPatch::new(algorithm);
+        for (op, dx7_op) in patch.operators.iter_mut().zip(&self.operators) {
+            *op = dx7_op.to_fm_operator(transpose);
+        }
+        if let Some(number) = algorithm.feedback_operator() {
+            patch.operators[number - 1].feedback = self.feedback.min(7) as f32 / 7.0;
+        }
+        patch
+    }
+
+    /// Convert to an [`Instrument`] playing the voice's [`FMPatch`]
+    pub fn to_instrument(&self) -> Instrument {
+        let patch = self.to_fm_patch();
+        let mut instrument = Instrument::new(self.name.trim());
+        // The operator envelopes shape the sound; the note just has to outlast them
+        instrument.envelope = Envelope::new(0.001, 0.1, 1.0, patch.release().max(0.05));
+        instrument.fm_patch = Some(patch);
+        instrument
+    }
+}
+
+/// Read every voice in a buffer of DX7 SysEx dumps
+///
+/// Accepts 32-voice bank dumps and single-voice dumps, in any number and order.
+///
+/// # Errors
+/// Returns [`TunesError::MidiError`] if a message isn't a DX7 voice or bank dump,
+/// is cut short, or fails its checksum.
+pub fn parse_sysex(bytes: &[u8]) -> Result<Vec<Dx7Voice>> {
+    let mut voices = Vec::new();
+    let mut rest = bytes;
+    while let Some(start) = rest.iter().position(|&b| b == SYSEX_START) {
+        let message = &rest[start..];
+        let end = message
+            .iter()
+            .position(|&b| b == SYSEX_END)
+            .ok_or_else(|| sysex_error("SysEx message has no end byte"))?;
+        read_dump(&message[..=end], &mut voices)?;
+        rest = &message[end + 1..];
+    }
+
+    if voices.is_empty() {
+        return Err(sysex_error("no DX7 voice data found"));
+    }
+    Ok(voices)
+}
+
+/// Read every voice in a `.syx` file (see [`parse_sysex`])
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<Dx7Voice>> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(|e| {
+        TunesError::IoError(format!(
+            "Failed to read SysEx file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    parse_sysex(&bytes)
+}
+
+/// 32-voice bank SysEx dump on a MIDI channel (0-15)
+///
+/// Banks with fewer than 32 voices are filled up with INIT VOICEs.
+///
+/// # Errors
+/// Returns [`TunesError::MidiError`] if there are more than 32 voices
+pub fn bank_to_sysex(voices: &[Dx7Voice], channel: u8) -> Result<Vec<u8>> {
+    if voices.len() > BANK_VOICES {
+        return Err(sysex_error(&format!(
+            "a DX7 bank holds {} voices, got {}",
+            BANK_VOICES,
+            voices.len()
+        )));
+    }
+
+    let init = Dx7Voice::default();
+    let mut data = Vec::with_capacity(BANK_SIZE);
+    for i in 0..BANK_VOICES {
+        data.extend_from_slice(&voices.get(i).unwrap_or(&init).to_packed());
+    }
+    Ok(dump(BANK_FORMAT, channel, &data))
+}
+
+/// The 32 voices bundled with the crate
+///
+/// Electric pianos, bells, basses, brass, organs, mallets, pads and effects,
+/// written for this crate and free to use like the rest of it.
+pub fn bundled_bank() -> Vec<Dx7Voice> {
+    bank::voices()
+}
+
+impl Instrument {
+    /// Load every voice in a DX7 `.syx` file as an instrument preset
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::instruments::Instrument;
+    /// let presets = Instrument::from_dx7_file("banks/rom1a.syx")?;
+    /// let epiano = presets.iter().find(|i| i.name == "E.PIANO 1");
+    /// # Ok::<(), tunes::error::TunesError>(())
+    /// ```
+    pub fn from_dx7_file<P: AsRef<Path>>(path: P) -> Result<Vec<Instrument>> {
+        Ok(load(path)?.iter().map(Dx7Voice::to_instrument).collect())
+    }
+
+    /// The voices of the bundled DX7 bank as instrument presets
+    pub fn dx7_bank() -> Vec<Instrument> {
+        bundled_bank().iter().map(Dx7Voice::to_instrument).collect()
+    }
+}
+
+/// Read the voices out of one complete SysEx message
+fn read_dump(message: &[u8], voices: &mut Vec<Dx7Voice>) -> Result<()> {
+    // F0 43 0n ff msb lsb <data> checksum F7
+    if message.len() < 8 || message[1] != YAMAHA_ID || message[2] & 0xF0 != 0 {
+        return Err(sysex_error("not a Yamaha DX7 voice dump"));
+    }
+    let format = message[3];
+    let size = ((message[4] as usize) << 7) | message[5] as usize;
+    let data = &message[6..message.len() - 2];
+    if data.len() != size {
+        return Err(sysex_error(&format!(
+            "dump says {} data bytes but holds {}",
+            size,
+            data.len()
+        )));
+    }
+    if checksum(data) != message[message.len() - 2] {
+        return Err(sysex_error("checksum mismatch"));
+    }
+
+    match (format, size) {
+        (VOICE_FORMAT, VOICE_SIZE) => voices.push(Dx7Voice::from_unpacked(data)?),
+        (BANK_FORMAT, BANK_SIZE) => {
+            for packed in data.chunks_exact(PACKED_VOICE_SIZE) {
+                voices.push(Dx7Voice::from_packed(packed)?);
+            }
+        }
+        _ => {
+            return Err(sysex_error(&format!(
+                "unsupported dump format {} with {} bytes",
+                format, size
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Wrap data in a DX7 SysEx message
+fn dump(format: u8, channel: u8, data: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(data.len() + 8);
+    message.extend_from_slice(&[
+        SYSEX_START,
+        YAMAHA_ID,
+        channel & 0x0F,
+        format,
+        (data.len() >> 7) as u8,
+        (data.len() & 0x7F) as u8,
+    ]);
+    message.extend_from_slice(data);
+    message.push(checksum(data));
+    message.push(SYSEX_END);
+    message
+}
+
+/// Two's complement of the 7-bit sum of the data
+fn checksum(data: &[u8]) -> u8 {
+    let sum = data.iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+    sum.wrapping_neg() & 0x7F
+}
+
+fn check_size(data: &[u8], size: usize, what: &str) -> Result<()> {
+    if data.len() == size {
+        Ok(())
+    } else {
+        Err(sysex_error(&format!(
+            "a DX7 {} is {} bytes, got {}",
+            what,
+            size,
+            data.len()
+        )))
+    }
+}
+
+fn read_name(data: &[u8]) -> String {
+    data.iter()
+        .map(|&b| {
+            if (32..127).contains(&b) {
+                b as char
+            } else {
+                ' '
+            }
+        })
+        .collect()
+}
+
+fn write_name(name: &str, data: &mut [u8]) {
+    let mut chars = name
+        .chars()
+        .filter(|c| c.is_ascii() && !c.is_ascii_control());
+    for byte in data.iter_mut().take(NAME_LENGTH) {
+        *byte = chars.next().map_or(b' ', |c| c as u8);
+    }
+}
+
+fn sysex_error(message: &str) -> TunesError {
+    TunesError::MidiError(format!("DX7 SysEx: {}", message))
+}
+
+/// Amplitude of a DX7 output or envelope level (0-99), about 0.75 dB per step
+fn level_to_amplitude(level: u8) -> f32 {
+    if level == 0 {
+        0.0
+    } else {
+        2f32.powf((level.min(99) as f32 - 99.0) / 8.0)
+    }
+}
+
+/// Seconds an envelope stage at `rate` takes to move between two levels
+fn stage_time(rate: u8, from: u8, to: u8) -> f32 {
+    let full = SLOWEST_STAGE * 2f32.powf(-(rate.min(99) as f32) / RATE_STEPS_PER_HALVING);
+    (full * from.abs_diff(to) as f32 / 99.0).max(0.001)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_bank_round_trips() {
+        let voices = bundled_bank();
+        assert_eq!(voices.len(), 32);
+        let bytes = bank_to_sysex(&voices, 0).unwrap();
+        assert_eq!(bytes.len(), 4104);
+        assert_eq!(parse_sysex(&bytes).unwrap(), voices);
+        assert_eq!(bank_to_sysex(&parse_sysex(&bytes).unwrap(), 0).unwrap(), bytes);
+
+        // Every voice also survives a single-voice dump
+        for voice in &voices {
+            assert_eq!(
+                parse_sysex(&voice.to_sysex(3)).unwrap(),
+                vec![voice.clone()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_stray_bits_are_dropped() {
+        let mut bytes = Dx7Voice::default().to_sysex(0);
+        // Unused top bits of operator 6's curves and name bytes outside ASCII
+        bytes[6 + 11] |= 0x70;
+        bytes[6 + 145] = 0x07;
+        let data_end = bytes.len() - 2;
+        bytes[data_end] = checksum(&bytes[6..data_end]);
+
+        let voice = &parse_sysex(&bytes).unwrap()[0];
+        assert_eq!(voice.operators[5].left_curve, 0);
+        assert!(voice.name.starts_with(' '));
+        assert_ne!(voice.to_sysex(0), bytes);
+    }
+
+    #[test]
+    fn test_packed_and_unpacked_layouts_agree() {
+        for voice in bundled_bank() {
+            assert_eq!(Dx7Voice::from_packed(&voice.to_packed()).unwrap(), voice);
+            assert_eq!(
+                Dx7Voice::from_unpacked(&voice.to_unpacked()).unwrap(),
+                voice
+            );
+        }
+    }
+
+    #[test]
+    fn test_bad_dumps_are_rejected() {
+        let mut bytes = Dx7Voice::default().to_sysex(0);
+        let checksum_index = bytes.len() - 2;
+        bytes[checksum_index] ^= 0x01;
+        assert!(parse_sysex(&bytes).is_err());
+
+        assert!(parse_sysex(&bytes[..100]).is_err());
+        assert!(parse_sysex(&[0xF0, 0x41, 0x10, 0x42, 0xF7]).is_err());
+        assert!(bank_to_sysex(&vec![Dx7Voice::default(); 33], 0).is_err());
+    }
+
+    /// Operator parameters as the DX7 sends them, in the order of the SysEx spec
+    const INIT_OPERATOR: [u8; OPERATOR_SIZE] = [
+        99, 99, 99, 99, // EG rates 1-4
+        99, 99, 99, 0, // EG levels 1-4
+        39, 0, 0, // Break point (C3), left and right depth
+        0, 0, // Left and right curves (-LIN)
+        0, 0, 0, // Rate scaling, amp mod and velocity sensitivity
+        0, // Output level
+        0, 1, 0, 7, // Ratio mode, coarse 1, fine 0, no detune
+    ];
+
+    #[test]
+    fn test_init_voice_dump_decodes_to_spec_values() {
+        let mut bytes = vec![0xF0, 0x43, 0x00, 0x00, 0x01, 0x1B];
+        // Operators 6 down to 1; only operator 1 is audible
+        for _ in 0..5 {
+            bytes.extend_from_slice(&INIT_OPERATOR);
+        }
+        let mut operator_1 = INIT_OPERATOR;
+        operator_1[16] = 99;
+        bytes.extend_from_slice(&operator_1);
+        bytes.extend_from_slice(&[
+            99, 99, 99, 99, 50, 50, 50, 50, // Pitch EG rates and levels
+            0, 0, 1, // Algorithm 1, feedback 0, oscillator key sync on
+            35, 0, 0, 0, 1, 0, // LFO speed, delay, PMD, AMD, sync on, triangle
+            3, 24, // Pitch mod sensitivity, transpose C3
+        ]);
+        bytes.extend_from_slice(b"INIT VOICE");
+        bytes.extend_from_slice(&[0x67, 0xF7]);
+
+        let voices = parse_sysex(&bytes).unwrap();
+        assert_eq!(voices.len(), 1);
+        let voice = &voices[0];
+        assert_eq!(voice.name, "INIT VOICE");
+        assert_eq!(voice.algorithm, 1);
+        assert_eq!(voice.feedback, 0);
+        assert!(voice.oscillator_key_sync && voice.lfo_key_sync);
+        assert_eq!(voice.lfo_speed, 35);
+        assert_eq!(voice.pitch_levels, [50; 4]);
+        assert_eq!(voice.pitch_mod_sensitivity, 3);
+        assert_eq!(voice.transpose, 24);
+
+        let op1 = &voice.operators[0];
+        assert_eq!(op1.output_level, 99);
+        assert_eq!(op1.rates, [99; 4]);
+        assert_eq!(op1.levels, [99, 99, 99, 0]);
+        assert_eq!(op1.breakpoint, 39);
+        assert_eq!((op1.coarse, op1.fine, op1.detune), (1, 0, 7));
+        assert!(!op1.fixed_frequency);
+        assert!(voice.operators[1..].iter().all(|op| op.output_level == 0));
+
+        assert_eq!(*voice, Dx7Voice::default());
+        assert_eq!(voice.to_sysex(0), bytes);
+    }
+
+    #[test]
+    fn test_packed_bit_fields_decode_to_spec_values() {
+        // Every field distinct, so swapped or shifted bit fields show up
+        let curves = 0b0110; // Right curve -EXP (1), left curve +EXP (2)
+        let scaling = 0b1001101; // Detune 9, rate scaling 5
+        let sensitivity = 0b11011; // Velocity sensitivity 6, amp mod sensitivity 3
+        let frequency = 0b100011; // Coarse 17, fixed frequency
+        let mut operator = vec![10, 20, 30, 40, 50, 60, 70, 80, 45, 11, 22];
+        operator.extend_from_slice(&[curves, scaling, sensitivity, 88, frequency, 42]);
+        let mut packed = Vec::with_capacity(PACKED_VOICE_SIZE);
+        for _ in 0..6 {
+            packed.extend_from_slice(&operator);
+        }
+        let algorithm = 31; // Algorithm 32
+        let feedback = 0b1101; // Oscillator key sync, feedback 5
+        let lfo = 0b1101001; // Pitch mod sensitivity 6, LFO wave 4 (sine), LFO sync
+        packed.extend_from_slice(&[
+            1, 2, 3, 4, 51, 52, 53, 54, algorithm, feedback, 12, 13, 14, 15, lfo, 36,
+        ]);
+        packed.extend_from_slice(b"BITFIELDS ");
+
+        let voice = Dx7Voice::from_packed(&packed).unwrap();
+        let op = &voice.operators[5];
+        assert_eq!(op.rates, [10, 20, 30, 40]);
+        assert_eq!(op.levels, [50, 60, 70, 80]);
+        assert_eq!((op.breakpoint, op.left_depth, op.right_depth), (45, 11, 22));
+        assert_eq!((op.left_curve, op.right_curve), (2, 1));
+        assert_eq!((op.rate_scaling, op.detune), (5, 9));
+        assert_eq!((op.amp_mod_sensitivity, op.velocity_sensitivity), (3, 6));
+        assert_eq!(op.output_level, 88);
+        assert!(op.fixed_frequency);
+        assert_eq!((op.coarse, op.fine), (17, 42));
+
+        assert_eq!(voice.pitch_rates, [1, 2, 3, 4]);
+        assert_eq!(voice.pitch_levels, [51, 52, 53, 54]);
+        assert_eq!((voice.algorithm, voice.feedback), (32, 5));
+        assert!(voice.oscillator_key_sync && voice.lfo_key_sync);
+        assert_eq!((voice.lfo_speed, voice.lfo_delay), (12, 13));
+        assert_eq!((voice.lfo_pitch_depth, voice.lfo_amp_depth), (14, 15));
+        assert_eq!((voice.lfo_wave, voice.pitch_mod_sensitivity), (4, 6));
+        assert_eq!(voice.transpose, 36);
+        assert_eq!(voice.name, "BITFIELDS ");
+        assert_eq!(voice.to_packed()[..], packed[..]);
+    }
+
+    #[test]
+    fn test_init_voice_plays_a_sine() {
+        let patch = Dx7Voice::default().to_fm_patch();
+        assert_eq!(patch.algorithm, FMAlgorithm::dx7(1));
+        assert_eq!(patch.operators[0].level, 1.0);
+        assert!(patch.operators[1..].iter().all(|op| op.level == 0.0));
+
+        // Algorithm 1 mixes operators 1 and 3, and operator 3 is silent
+        let t = 0.1 + 0.25 / 440.0;
+        assert!((patch.sample(440.0, t, 1.0, 1.0, 0.0) - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_operator_conversion() {
+        let op = Dx7Operator {
+            coarse: 0,
+            fine: 50,
+            output_level: 91,
+            velocity_sensitivity: 7,
+            rates: [99, 50, 50, 50],
+            levels: [99, 99, 0, 0],
+            ..Dx7Operator::default()
+        };
+        let fm = op.to_fm_operator(12);
+        assert!((fm.ratio - 1.5).abs() < 1e-5);
+        assert!((fm.level - 0.5).abs() < 1e-5);
+        assert_eq!(fm.velocity_sensitivity, 1.0);
+        assert_eq!(fm.envelope.sustain, 0.0);
+        assert!(fm.envelope.decay > 0.1);
+
+        let fixed = Dx7Operator {
+            fixed_frequency: true,
+            coarse: 2,
+            fine: 0,
+            ..Dx7Operator::default()
+        };
+        assert_eq!(fixed.to_fm_operator(0).fixed_frequency, Some(100.0));
+    }
+
+    #[test]
+    fn test_bank_voices_become_instruments() {
+        let instruments = Instrument::dx7_bank();
+        assert_eq!(instruments.len(), 32);
+        for instrument in &instruments {
+            assert!(!instrument.name.is_empty());
+            assert!(instrument.fm_patch.is_some());
+        }
+    }
+}
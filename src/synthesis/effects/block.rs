@@ -0,0 +1,244 @@
+//! Shared helpers for block-based effect processing
+//!
+//! Built-in effects process a block in segments of at most [`AUTOMATION_INTERVAL`]
+//! frames. Segment boundaries are aligned to the global sample counter, so automation
+//! is evaluated at exactly the same points as in the per-sample `process` methods, and
+//! parameters stay constant inside a segment. This lets each effect hoist its
+//! coefficient math out of the inner loop and run stateless stages (waveshaping, LFOs,
+//! dry/wet mixing) through SIMD kernels.
+//!
+//! Segments are measured in frames. A stereo block is processed as an interleaved
+//! stream with `channels = 2`, which matches how the per-sample stereo path feeds the
+//! left and right samples of each frame through the same effect instance.
+
+use crate::synthesis::simd::{SimdLanes, SimdWidth, SIMD};
+use wide::{f32x4, f32x8};
+
+/// Number of frames between automation lookups (a power of two)
+pub(crate) const AUTOMATION_INTERVAL: usize = 64;
+
+/// Largest number of interleaved channels a segment can hold
+pub(crate) const MAX_CHANNELS: usize = 2;
+
+/// Size of stack scratch buffers that hold one segment
+pub(crate) const MAX_SEGMENT_SAMPLES: usize = AUTOMATION_INTERVAL * MAX_CHANNELS;
+
+/// A run of frames processed with constant parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Segment {
+    /// First frame of the segment (relative to the block)
+    pub start: usize,
+    /// One past the last frame of the segment
+    pub end: usize,
+    /// Whether the segment starts on an automation boundary
+    pub update_automation: bool,
+}
+
+impl Segment {
+    /// Sample range of this segment in a buffer with `channels` interleaved channels
+    #[inline]
+    pub fn samples(&self, channels: usize) -> std::ops::Range<usize> {
+        self.start * channels..self.end * channels
+    }
+}
+
+/// Split a block of `num_frames` frames starting at `sample_count` into segments
+///
+/// Every segment except possibly the first and last spans exactly
+/// [`AUTOMATION_INTERVAL`] frames.
+pub(crate) fn segments(num_frames: usize, sample_count: u64) -> impl Iterator<Item = Segment> {
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        if start >= num_frames {
+            return None;
+        }
+        let offset = ((sample_count + start as u64) & (AUTOMATION_INTERVAL as u64 - 1)) as usize;
+        let end = (start + AUTOMATION_INTERVAL - offset).min(num_frames);
+        let segment = Segment {
+            start,
+            end,
+            update_automation: offset == 0,
+        };
+        start = end;
+        Some(segment)
+    })
+}
+
+/// Split an interleaved stereo frame into its left and right samples
+#[inline(always)]
+pub(crate) fn split_frame(frame: &mut [f32]) -> (&mut f32, &mut f32) {
+    let (left, right) = frame.split_at_mut(1);
+    (&mut left[0], &mut right[0])
+}
+
+/// A stateless per-sample operation that can run at any SIMD lane width
+pub(crate) trait Kernel {
+    /// Process a vector of samples
+    ///
+    /// `index` holds each lane's position relative to the start of the buffer,
+    /// for kernels that depend on time (LFOs, ramps).
+    fn apply<V: SimdLanes>(&self, input: V, index: V) -> V;
+}
+
+/// Run a kernel over a buffer in-place using the widest available SIMD lanes
+#[inline]
+pub(crate) fn apply_kernel<K: Kernel>(buffer: &mut [f32], kernel: &K) {
+    match SIMD.simd_width() {
+        SimdWidth::X8 => apply_kernel_lanes::<f32x8, K>(buffer, kernel),
+        SimdWidth::X4 => apply_kernel_lanes::<f32x4, K>(buffer, kernel),
+        SimdWidth::Scalar => apply_kernel_lanes::<f32, K>(buffer, kernel),
+    }
+}
+
+#[inline(always)]
+fn apply_kernel_lanes<V: SimdLanes, K: Kernel>(buffer: &mut [f32], kernel: &K) {
+    let mut chunks = buffer.chunks_exact_mut(V::LANES);
+    let mut index = 0;
+    for chunk in &mut chunks {
+        let output = kernel.apply(V::from_array(chunk), V::ramp(index as f32));
+        output.write_to_slice(chunk);
+        index += V::LANES;
+    }
+
+    // Handle remainder with scalar
+    for sample in chunks.into_remainder() {
+        *sample = kernel.apply(*sample, index as f32);
+        index += 1;
+    }
+}
+
+/// Blend a wet signal into a buffer holding the dry signal
+///
+/// Computes `dry * (1 - mix) + wet * mix` for every sample.
+#[inline]
+pub(crate) fn mix_dry_wet(buffer: &mut [f32], wet: &[f32], mix: f32) {
+    match SIMD.simd_width() {
+        SimdWidth::X8 => mix_dry_wet_lanes::<f32x8>(buffer, wet, mix),
+        SimdWidth::X4 => mix_dry_wet_lanes::<f32x4>(buffer, wet, mix),
+        SimdWidth::Scalar => mix_dry_wet_lanes::<f32>(buffer, wet, mix),
+    }
+}
+
+#[inline(always)]
+fn mix_dry_wet_lanes<V: SimdLanes>(buffer: &mut [f32], wet: &[f32], mix: f32) {
+    debug_assert_eq!(buffer.len(), wet.len());
+    let dry_gain = V::splat(1.0 - mix);
+    let wet_gain = V::splat(mix);
+
+    let mut dry_chunks = buffer.chunks_exact_mut(V::LANES);
+    let mut wet_chunks = wet.chunks_exact(V::LANES);
+    for (dry, wet) in (&mut dry_chunks).zip(&mut wet_chunks) {
+        let output = V::from_array(dry).mul_add(dry_gain, V::from_array(wet).mul(wet_gain));
+        output.write_to_slice(dry);
+    }
+
+    // Handle remainder with scalar
+    for (dry, &wet) in dry_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(wet_chunks.remainder())
+    {
+        *dry = dry.mul_add(1.0 - mix, wet * mix);
+    }
+}
+
+/// Sine LFO evaluated in parallel across lanes
+///
+/// Lane `i` sees the phase after `i` samples, so an LFO can be rendered for a whole
+/// segment without a loop-carried phase accumulator.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SineLfo {
+    /// Phase of the first sample (0.0 to 1.0)
+    pub phase: f32,
+    /// Phase advance per sample (cycles)
+    pub increment: f32,
+}
+
+impl SineLfo {
+    /// LFO value at each lane's sample index
+    #[inline(always)]
+    pub fn at<V: SimdLanes>(&self, index: V) -> V {
+        let phase = index.mul_add(V::splat(self.increment), V::splat(self.phase));
+        let wrapped = phase.sub(phase.floor());
+        wrapped.mul(V::splat(std::f32::consts::TAU)).sin()
+    }
+
+    /// Phase after `samples` samples, wrapped to 0.0..1.0
+    #[inline]
+    pub fn phase_after(&self, samples: usize) -> f32 {
+        let phase = (samples as f32).mul_add(self.increment, self.phase);
+        phase - phase.floor()
+    }
+
+    /// Fill `output` with consecutive LFO values
+    #[inline]
+    pub fn fill(&self, output: &mut [f32]) {
+        apply_kernel(output, self);
+    }
+}
+
+impl Kernel for SineLfo {
+    #[inline(always)]
+    fn apply<V: SimdLanes>(&self, _input: V, index: V) -> V {
+        self.at(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_aligned_start() {
+        let segments: Vec<Segment> = segments(150, 0).collect();
+        assert_eq!(segments.len(), 3);
+        assert_eq!((segments[0].start, segments[0].end), (0, 64));
+        assert_eq!((segments[2].start, segments[2].end), (128, 150));
+        assert!(segments.iter().all(|s| s.update_automation));
+    }
+
+    #[test]
+    fn test_segments_unaligned_start() {
+        let segments: Vec<Segment> = segments(100, 40).collect();
+        // First segment runs up to the next multiple of 64 without an automation update
+        assert_eq!((segments[0].start, segments[0].end), (0, 24));
+        assert!(!segments[0].update_automation);
+        assert_eq!((segments[1].start, segments[1].end), (24, 88));
+        assert!(segments[1].update_automation);
+        assert_eq!((segments[2].start, segments[2].end), (88, 100));
+    }
+
+    #[test]
+    fn test_segments_empty_block() {
+        assert_eq!(segments(0, 17).count(), 0);
+    }
+
+    #[test]
+    fn test_mix_dry_wet() {
+        let mut buffer = vec![1.0; 11];
+        let wet = vec![3.0; 11];
+        mix_dry_wet(&mut buffer, &wet, 0.25);
+        assert!(buffer.iter().all(|&s| (s - 1.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_sine_lfo_matches_accumulated_phase() {
+        let lfo = SineLfo {
+            phase: 0.9,
+            increment: 0.013,
+        };
+        let mut output = vec![0.0; 37];
+        lfo.fill(&mut output);
+
+        let mut phase = lfo.phase;
+        for &value in &output {
+            let expected = (phase * std::f32::consts::TAU).sin();
+            assert!((value - expected).abs() < 1e-4);
+            phase += lfo.increment;
+            if phase >= 1.0 {
+                phase -= 1.0;
+            }
+        }
+        assert!((lfo.phase_after(37) - phase).abs() < 1e-4);
+    }
+}
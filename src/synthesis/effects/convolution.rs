@@ -26,7 +26,10 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use super::effect::Effect;
 use crate::error::{Result, TunesError};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use crate::synthesis::sample::Sample;
 use crate::track::PRIORITY_SPATIAL;
 use rustfft::num_complex::Complex;
@@ -38,6 +41,13 @@ use std::sync::Arc;
 ///
 /// Applies the acoustic characteristics of a space to audio through convolution.
 /// Uses overlap-add FFT algorithm for efficient real-time processing.
+///
+/// # Latency
+///
+/// Input is convolved one block at a time, so the wet signal comes out `block_size`
+/// samples late. The dry signal is delayed by the same amount inside the effect, keeping
+/// the two aligned at any mix; [`latency`](Effect::latency) reports the delay so the
+/// mixer can compensate the other tracks and buses.
 #[derive(Clone)]
 pub struct ConvolutionReverb {
     /// Pre-computed impulse response in frequency domain
@@ -49,17 +59,14 @@ pub struct ConvolutionReverb {
     /// Processing block size (samples per FFT block)
     block_size: usize,
 
-    /// Hop size for overlap-add (typically block_size / 2)
-    hop_size: usize,
+    /// Impulse response length in samples
+    ir_length: usize,
 
-    /// Input accumulation buffer
-    input_buffer: Vec<f32>,
+    /// Per-channel convolution state (left/mono, right)
+    channels: [ConvolutionChannel; 2],
 
-    /// Output buffer (stores processed samples)
-    output_buffer: VecDeque<f32>,
-
-    /// Overlap buffer for overlap-add algorithm
-    overlap_buffer: Vec<f32>,
+    /// Scratch buffer for the FFT of one block
+    spectrum: Vec<Complex<f32>>,
 
     /// Forward FFT planner
     fft: Arc<dyn Fft<f32>>,
@@ -72,9 +79,40 @@ pub struct ConvolutionReverb {
 
     /// Processing priority (lower = earlier in signal chain)
     pub priority: u8,
+}
+
+/// Overlap-add state for one channel
+#[derive(Debug, Clone)]
+struct ConvolutionChannel {
+    /// Input samples collected for the next block
+    input: Vec<f32>,
+
+    /// Convolved samples waiting to be output
+    output: VecDeque<f32>,
+
+    /// Convolution tail carried into the following blocks
+    overlap: Vec<f32>,
 
-    /// Sample counter for processing state
-    sample_count: u64,
+    /// Dry signal delayed by one block to line up with the wet signal
+    dry: VecDeque<f32>,
+}
+
+impl ConvolutionChannel {
+    fn new(block_size: usize, fft_size: usize) -> Self {
+        Self {
+            input: Vec::with_capacity(block_size),
+            output: VecDeque::with_capacity(block_size),
+            overlap: vec![0.0; fft_size],
+            dry: std::iter::repeat_n(0.0, block_size).collect(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.input.clear();
+        self.output.clear();
+        self.overlap.fill(0.0);
+        self.dry.iter_mut().for_each(|sample| *sample = 0.0);
+    }
 }
 
 impl ConvolutionReverb {
@@ -136,15 +174,16 @@ impl ConvolutionReverb {
             ir_fft,
             fft_size,
             block_size,
-            hop_size: block_size / 2,
-            input_buffer: Vec::with_capacity(block_size),
-            output_buffer: VecDeque::with_capacity(fft_size),
-            overlap_buffer: vec![0.0; fft_size],
+            ir_length: ir.len(),
+            channels: [
+                ConvolutionChannel::new(block_size, fft_size),
+                ConvolutionChannel::new(block_size, fft_size),
+            ],
+            spectrum: vec![Complex::new(0.0, 0.0); fft_size],
             fft,
             ifft,
             mix: mix.clamp(0.0, 1.0),
             priority: PRIORITY_SPATIAL, // Convolution reverb typically comes last
-            sample_count: 0,
         })
     }
 
@@ -183,7 +222,8 @@ impl ConvolutionReverb {
 
     /// Process a single audio sample through convolution
     ///
-    /// Uses overlap-add FFT convolution for efficient processing.
+    /// Uses overlap-add FFT convolution for efficient processing. The output is
+    /// delayed by `block_size` samples (see [Latency](#latency)).
     ///
     /// # Arguments
     /// * `input` - Input sample
@@ -191,64 +231,73 @@ impl ConvolutionReverb {
     /// # Returns
     /// Processed output sample (wet/dry mixed)
     pub fn process(&mut self, input: f32) -> f32 {
-        // Accumulate input samples
-        self.input_buffer.push(input);
+        self.process_channel(0, input)
+    }
 
-        // Process block when we have enough samples
-        if self.input_buffer.len() >= self.block_size {
-            self.process_block();
-        }
+    /// Process a stereo frame, convolving each channel with the impulse response
+    pub fn process_stereo(&mut self, left: f32, right: f32) -> (f32, f32) {
+        (self.process_channel(0, left), self.process_channel(1, right))
+    }
 
-        // Get output sample
-        let output = self.output_buffer.pop_front().unwrap_or(0.0);
+    /// Process one sample on the given channel
+    fn process_channel(&mut self, channel: usize, input: f32) -> f32 {
+        let state = &mut self.channels[channel];
 
-        self.sample_count += 1;
+        // Output the previous block before collecting this sample, so every sample
+        // comes out exactly block_size samples after it went in
+        let wet = state.output.pop_front().unwrap_or(0.0);
+        state.dry.push_back(input);
+        let dry = state.dry.pop_front().unwrap_or(0.0);
+
+        state.input.push(input);
+        if state.input.len() >= self.block_size {
+            self.convolve_block(channel);
+        }
 
         // Apply wet/dry mix
-        input * (1.0 - self.mix) + output * self.mix
+        dry * (1.0 - self.mix) + wet * self.mix
     }
 
-    /// Process accumulated input block with FFT convolution
-    fn process_block(&mut self) {
-        // Prepare input block (zero-padded to FFT size)
-        let mut input_complex = vec![Complex::new(0.0, 0.0); self.fft_size];
+    /// Convolve a channel's collected input block with the impulse response
+    fn convolve_block(&mut self, channel: usize) {
+        let state = &mut self.channels[channel];
 
-        for (i, &sample) in self.input_buffer.iter().enumerate().take(self.block_size) {
-            input_complex[i] = Complex::new(sample, 0.0);
+        // Prepare input block (zero-padded to FFT size)
+        self.spectrum.fill(Complex::new(0.0, 0.0));
+        for (bin, &sample) in self.spectrum.iter_mut().zip(&state.input) {
+            *bin = Complex::new(sample, 0.0);
         }
 
         // FFT the input block
-        self.fft.process(&mut input_complex);
+        self.fft.process(&mut self.spectrum);
 
         // Multiply in frequency domain (complex multiplication = convolution in time domain)
-        for i in 0..self.fft_size {
-            input_complex[i] *= self.ir_fft[i];
+        for (bin, ir_bin) in self.spectrum.iter_mut().zip(&self.ir_fft) {
+            *bin *= ir_bin;
         }
 
         // IFFT back to time domain
-        self.ifft.process(&mut input_complex);
+        self.ifft.process(&mut self.spectrum);
 
         // Normalize (rustfft doesn't auto-normalize IFFT)
         let scale = 1.0 / (self.fft_size as f32);
 
-        // Overlap-add with previous block
+        // Overlap-add: the first block of the result (plus the tail of earlier blocks)
+        // is ready to output, the rest carries over into the next blocks
+        for i in 0..self.block_size {
+            state
+                .output
+                .push_back(self.spectrum[i].re * scale + state.overlap[i]);
+        }
         for i in 0..self.fft_size {
-            let sample = input_complex[i].re * scale;
-
-            // Add to overlap buffer and output
-            let output_sample = sample + self.overlap_buffer[i];
-            self.output_buffer.push_back(output_sample);
-
-            // Update overlap buffer for next block
-            self.overlap_buffer[i] = if i < self.fft_size - self.block_size {
-                input_complex[i + self.block_size].re * scale
+            state.overlap[i] = if i + self.block_size < self.fft_size {
+                state.overlap[i + self.block_size] + self.spectrum[i + self.block_size].re * scale
             } else {
                 0.0
             };
         }
 
-        // Keep hop_size samples in input buffer for overlap
-        self.input_buffer.drain(0..self.hop_size);
+        state.input.clear();
     }
 
     /// Process a block of samples at once (more efficient than sample-by-sample)
@@ -265,10 +314,9 @@ impl ConvolutionReverb {
     ///
     /// Clears all internal buffers. Useful when stopping/starting playback.
     pub fn reset(&mut self) {
-        self.input_buffer.clear();
-        self.output_buffer.clear();
-        self.overlap_buffer.fill(0.0);
-        self.sample_count = 0;
+        for channel in &mut self.channels {
+            channel.reset();
+        }
     }
 
     /// Get the wet/dry mix amount
@@ -292,6 +340,48 @@ impl std::fmt::Debug for ConvolutionReverb {
     }
 }
 
+impl Effect for ConvolutionReverb {
+    fn process_block(&mut self, buffer: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {
+        self.process_block_direct(buffer);
+    }
+
+    fn process_stereo_block(
+        &mut self,
+        left: &mut [f32],
+        right: &mut [f32],
+        _sample_rate: f32,
+        _time: f32,
+        _sample_count: u64,
+    ) {
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            (*l, *r) = self.process_stereo(*l, *r);
+        }
+    }
+
+    fn reset(&mut self) {
+        ConvolutionReverb::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("mix", self.mix)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        if name == "mix" {
+            self.set_mix(value);
+        }
+    }
+
+    fn tail_length(&self) -> f32 {
+        // Impulse responses are generated and loaded at 44.1 kHz
+        self.ir_length as f32 / 44100.0
+    }
+
+    fn latency(&self) -> usize {
+        self.block_size
+    }
+}
+
 /// Parameters for synthetic impulse response generation
 ///
 /// These parameters define the characteristics of a simulated acoustic space.
@@ -407,7 +497,22 @@ impl ConvolutionReverb {
 /// - Early reflections based on room geometry
 /// - Diffuse reverb tail with exponential decay
 /// - Frequency-dependent damping
+///
+/// The random reflections and tail come from a fixed seed, so the same
+/// parameters always produce the same response.
 pub fn generate_ir(params: &IRParams) -> Vec<f32> {
+    generate_ir_with_seed(params, IR_SEED)
+}
+
+/// Seed used by [`generate_ir`]
+const IR_SEED: u64 = 0x1A5E_ED00;
+
+/// Generate a synthetic impulse response from parameters and a seed
+///
+/// Different seeds give different but equally plausible rooms with the same
+/// size, decay and damping.
+pub fn generate_ir_with_seed(params: &IRParams, seed: u64) -> Vec<f32> {
+    let mut rng = StdRng::seed_from_u64(seed);
     let duration = params.rt60 * 1.5; // Generate 1.5x RT60 for full decay
     let num_samples = (duration * params.sample_rate) as usize;
 
@@ -417,10 +522,10 @@ pub fn generate_ir(params: &IRParams) -> Vec<f32> {
     ir[0] = 1.0;
 
     // 2. Add early reflections (geometric room model)
-    add_early_reflections(&mut ir, params);
+    add_early_reflections(&mut ir, params, &mut rng);
 
     // 3. Add diffuse reverb tail (exponential decay)
-    add_diffuse_tail(&mut ir, params);
+    add_diffuse_tail(&mut ir, params, &mut rng);
 
     // 4. Normalize to prevent clipping
     let max = ir.iter().map(|x| x.abs()).fold(0.0f32, f32::max);
@@ -434,7 +539,7 @@ pub fn generate_ir(params: &IRParams) -> Vec<f32> {
 }
 
 /// Add early reflections based on room geometry
-fn add_early_reflections(ir: &mut [f32], params: &IRParams) {
+fn add_early_reflections(ir: &mut [f32], params: &IRParams, rng: &mut StdRng) {
     let (length, width, height) = params.room_dimensions;
     let speed_of_sound = 343.0; // m/s
 
@@ -457,14 +562,12 @@ fn add_early_reflections(ir: &mut [f32], params: &IRParams) {
 
     // Add additional random early reflections based on density
     if params.early_density > 0.5 {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
         let num_extra = (params.early_density * 20.0) as usize;
 
         for _ in 0..num_extra {
-            let delay = rng.gen_range(0.01..0.1); // 10-100ms
+            let delay = rng.random_range(0.01..0.1); // 10-100ms
             let delay_samples = (delay * params.sample_rate) as usize;
-            let amplitude = rng.gen_range(0.1..0.5);
+            let amplitude = rng.random_range(0.1..0.5);
 
             if delay_samples < ir.len() {
                 ir[delay_samples] += amplitude;
@@ -474,9 +577,7 @@ fn add_early_reflections(ir: &mut [f32], params: &IRParams) {
 }
 
 /// Add diffuse reverb tail with exponential decay
-fn add_diffuse_tail(ir: &mut [f32], params: &IRParams) {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
+fn add_diffuse_tail(ir: &mut [f32], params: &IRParams, rng: &mut StdRng) {
 
     // Start diffuse tail after early reflections (~50ms)
     let start_sample = (0.05 * params.sample_rate) as usize;
@@ -492,7 +593,7 @@ fn add_diffuse_tail(ir: &mut [f32], params: &IRParams) {
 
     for i in start_sample..ir.len() {
         // Generate random noise
-        let noise = rng.gen_range(-1.0..1.0);
+        let noise = rng.random_range(-1.0..1.0);
 
         // Apply exponential decay
         let decay = decay_coefficient.powf((i - start_sample) as f32);
@@ -716,8 +817,48 @@ mod tests {
         // Reset
         reverb.reset();
 
-        // Buffers should be empty
-        assert_eq!(reverb.sample_count, 0);
+        // Buffers should be empty: the reverb behaves like a fresh instance
+        let mut fresh = ConvolutionReverb::from_samples(&ir, 0.5, None).unwrap();
+        for i in 0..5000 {
+            let input = if i == 0 { 1.0 } else { 0.0 };
+            assert_eq!(reverb.process(input), fresh.process(input));
+        }
+    }
+
+    #[test]
+    fn test_output_is_delayed_by_reported_latency() {
+        let ir = vec![1.0, 0.5, 0.25];
+        let mut reverb = ConvolutionReverb::from_samples(&ir, 0.5, Some(64)).unwrap();
+        assert_eq!(reverb.latency(), 64);
+
+        // An impulse comes out block_size samples late, dry and wet aligned,
+        // and keeps convolving correctly across block boundaries
+        let output: Vec<f32> = (0..300)
+            .map(|i| reverb.process(if i == 0 || i == 130 { 1.0 } else { 0.0 }))
+            .collect();
+        let mut expected = vec![0.0; 300];
+        for start in [64, 194] {
+            expected[start] = 0.5 + 0.5;
+            expected[start + 1] = 0.5 * 0.5;
+            expected[start + 2] = 0.5 * 0.25;
+        }
+        for (a, b) in output.iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_stereo_channels_are_independent() {
+        let ir = vec![1.0, 0.5];
+        let mut reverb = ConvolutionReverb::from_samples(&ir, 1.0, Some(32)).unwrap();
+        let mut left = vec![0.0; 100];
+        let mut right = vec![0.0; 100];
+        left[0] = 1.0;
+        reverb.process_stereo_block(&mut left, &mut right, 44100.0, 0.0, 0);
+
+        assert!((left[32] - 1.0).abs() < 1e-5);
+        assert!((left[33] - 0.5).abs() < 1e-5);
+        assert!(right.iter().all(|s| s.abs() < 1e-6));
     }
 
     #[test]
@@ -729,6 +870,13 @@ mod tests {
         assert!(ir[0].abs() > 0.0); // Should have initial impulse
     }
 
+    #[test]
+    fn test_generate_ir_is_reproducible() {
+        let params = IRParams::small_room();
+        assert_eq!(generate_ir(&params), generate_ir(&params));
+        assert_ne!(generate_ir_with_seed(&params, 1), generate_ir_with_seed(&params, 2));
+    }
+
     #[test]
     fn test_from_params() {
         let reverb = ConvolutionReverb::from_params(IRParams::cathedral(), 0.5);
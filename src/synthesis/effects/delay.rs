@@ -1,9 +1,14 @@
+use super::block;
+use super::effect::{process_stereo_as_stream, Effect};
 use crate::synthesis::automation::Automation;
 use crate::track::PRIORITY_TIME_BASED;
 
 /// Standard audio sample rate
 const DEFAULT_SAMPLE_RATE: f32 = 44100.0;
 
+/// Longest delay time in seconds that automation and parameter changes can reach
+const MAX_DELAY_TIME: f32 = 10.0;
+
 /// Delay effect with feedback
 #[derive(Debug, Clone)]
 pub struct Delay {
@@ -11,8 +16,9 @@ pub struct Delay {
     pub feedback: f32,   // Feedback amount (0.0 to 0.99)
     pub mix: f32,        // Wet/dry mix (0.0 = dry, 1.0 = wet)
     pub priority: u8,    // Processing priority (lower = earlier in signal chain)
-    buffer: Vec<f32>,
+    buffer: Vec<f32>, // Delay line, long enough for the longest delay time once prepared
     write_pos: usize,
+    sample_rate: f32, // Sample rate the delay line is sized for
 
     // Automation (optional)
     delay_time_automation: Option<Automation>,
@@ -36,6 +42,7 @@ impl Delay {
             priority: PRIORITY_TIME_BASED, // Time-based effects typically come late in chain
             buffer: vec![0.0; buffer_size.max(1)],
             write_pos: 0,
+            sample_rate,
             delay_time_automation: None,
             feedback_automation: None,
             mix_automation: None,
@@ -61,11 +68,28 @@ impl Delay {
     }
 
     /// Add automation for the delay time parameter
+    ///
+    /// The delay line grows to hold the automation's longest delay time.
     pub fn with_delay_time_automation(mut self, automation: Automation) -> Self {
         self.delay_time_automation = Some(automation);
+        self.reserve(self.longest_delay_time());
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.feedback_automation {
+            self.feedback = auto.value_at(time).clamp(0.0, 0.99);
+        }
+        if let Some(auto) = &self.delay_time_automation {
+            self.delay_time = auto.value_at(time).clamp(0.001, MAX_DELAY_TIME);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -77,15 +101,7 @@ impl Delay {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         // Use bitwise AND instead of modulo for power-of-2
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.feedback_automation {
-                self.feedback = auto.value_at(time).clamp(0.0, 0.99);
-            }
-            if let Some(auto) = &self.delay_time_automation {
-                self.delay_time = auto.value_at(time).clamp(0.001, 10.0);
-            }
+            self.update_automation(time);
         }
 
         // Early exit for bypassed effect
@@ -93,14 +109,13 @@ impl Delay {
             return input;
         }
 
-        // Read from delay buffer
-        let delayed = self.buffer[self.write_pos];
+        // Read from delay buffer, delay_time behind the write position
+        let delay_samples = self.delay_samples();
+        let buffer_len = self.buffer.len();
+        let delayed = self.buffer[(self.write_pos + buffer_len - delay_samples) % buffer_len];
 
         // Write input + feedback to buffer using FMA
         self.buffer[self.write_pos] = delayed.mul_add(self.feedback, input);
-
-        // Advance write position using bitwise AND (assumes power-of-2 buffer size)
-        let buffer_len = self.buffer.len();
         self.write_pos = (self.write_pos + 1) % buffer_len;
 
         // Mix dry and wet signals using FMA
@@ -109,6 +124,9 @@ impl Delay {
 
     /// Process a block of samples
     ///
+    /// Automation is evaluated once per 64-sample segment, at the same points as
+    /// [`process`](Self::process), so the output matches per-sample processing.
+    ///
     /// # Arguments
     /// * `buffer` - Buffer of samples to process in-place
     /// * `time` - Starting time in seconds (for automation)
@@ -116,20 +134,115 @@ impl Delay {
     /// * `sample_rate` - Sample rate in Hz (for time advancement)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], time: f32, sample_count: u64, sample_rate: f32) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, current_time, current_sample_count);
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            if self.mix < 0.0001 {
+                continue;
+            }
+
+            let feedback = self.feedback;
+            let mix = self.mix;
+            let dry = 1.0 - mix;
+            let delay_samples = self.delay_samples();
+            let buffer_len = self.buffer.len();
+            let mut write_pos = self.write_pos;
+            let mut read_pos = (write_pos + buffer_len - delay_samples) % buffer_len;
+
+            for sample in &mut buffer[segment.samples(channels)] {
+                let input = *sample;
+                let delayed = self.buffer[read_pos];
+                self.buffer[write_pos] = delayed.mul_add(feedback, input);
+                write_pos += 1;
+                if write_pos == buffer_len {
+                    write_pos = 0;
+                }
+                read_pos += 1;
+                if read_pos == buffer_len {
+                    read_pos = 0;
+                }
+                *sample = input.mul_add(dry, delayed * mix);
+            }
+
+            self.write_pos = write_pos;
         }
     }
 
+    /// Current delay in samples, limited to the length of the delay line
+    ///
+    /// Changing the delay time only moves the read position, so processing never
+    /// allocates. The line is sized ahead of time for the delay time and the range
+    /// of its automation.
+    fn delay_samples(&self) -> usize {
+        ((self.delay_time * self.sample_rate) as usize).clamp(1, self.buffer.len())
+    }
+
+    /// Longest delay time the delay reaches: its own, or its automation's highest value
+    fn longest_delay_time(&self) -> f32 {
+        let automated = self
+            .delay_time_automation
+            .as_ref()
+            .map_or(0.0, |automation| automation.value_range().1);
+        self.delay_time.max(automated).clamp(0.001, MAX_DELAY_TIME)
+    }
+
+    /// Grow the delay line to hold `delay_time` seconds, keeping the echoes in it
+    ///
+    /// The line at least doubles, so a delay time raised step by step reallocates
+    /// only a few times.
+    fn reserve(&mut self, delay_time: f32) {
+        let needed = ((delay_time * self.sample_rate) as usize).max(1);
+        if self.buffer.len() >= needed {
+            return;
+        }
+        let longest = (MAX_DELAY_TIME * self.sample_rate) as usize;
+        let size = (self.buffer.len() * 2).clamp(needed, longest.max(needed));
+
+        // Unroll the ring with the newest sample last, behind the added silence
+        let mut grown = vec![0.0; size - self.buffer.len()];
+        grown.extend_from_slice(&self.buffer[self.write_pos..]);
+        grown.extend_from_slice(&self.buffer[..self.write_pos]);
+        self.buffer = grown;
+        self.write_pos = 0;
+    }
+
     /// Reset the delay buffer
     pub fn reset(&mut self) {
         self.buffer.fill(0.0);
         self.write_pos = 0;
     }
 
+    /// Size the delay line for the longest delay time at a sample rate
+    ///
+    /// The line holds the delay time and the whole range of its automation, keeping
+    /// the delay time in seconds constant. The buffer is cleared if the sample rate
+    /// changes; at the current sample rate the echoes already in the line are kept,
+    /// and once the line is long enough this does nothing.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate != self.sample_rate {
+            self.sample_rate = sample_rate;
+            let size = (self.longest_delay_time() * sample_rate) as usize;
+            self.buffer = vec![0.0; size.max(1)];
+            self.write_pos = 0;
+        } else {
+            self.reserve(self.longest_delay_time());
+        }
+    }
+
     // ========== PRESETS ==========
 
     /// Eighth note delay (125ms at 120 BPM) with subtle feedback
@@ -172,3 +285,87 @@ impl Delay {
         Self::new(1.0, 0.6, 0.5)
     }
 }
+
+impl Effect for Delay {
+    fn prepare(&mut self, sample_rate: f32, _max_block_size: usize) {
+        self.set_sample_rate(sample_rate);
+    }
+
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Delay::process_block(self, buffer, time, sample_count, sample_rate);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        Delay::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("delay_time", self.delay_time), ("feedback", self.feedback), ("mix", self.mix)]
+    }
+
+    /// Setting a delay time longer than the line grows it
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "delay_time" => {
+                self.delay_time = value.clamp(0.001, MAX_DELAY_TIME);
+                self.reserve(self.delay_time);
+            }
+            "feedback" => self.feedback = value.clamp(0.0, 0.99),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+
+    fn tail_length(&self) -> f32 {
+        if self.feedback <= 0.0 {
+            return self.delay_time;
+        }
+        // Number of repeats until the echo drops below -60 dB
+        let repeats = (0.001f32.ln() / self.feedback.ln()).max(1.0);
+        self.delay_time * (repeats + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepared_delay_never_reallocates_while_processing() {
+        let automation = Automation::linear(&[(0.0, 0.01), (0.05, 0.5)]);
+        let mut delay = Delay::new(0.01, 0.0, 1.0).with_delay_time_automation(automation);
+        Effect::prepare(&mut delay, 44100.0, 512);
+        let line = delay.buffer.as_ptr();
+        assert_eq!(delay.buffer.len(), 22050);
+
+        // Automation sweeping to its longest delay only moves the read position
+        let mut buffer = vec![0.0; 4096];
+        delay.process_block(&mut buffer, 0.0, 0, 44100.0);
+        assert_eq!(delay.delay_time, 0.5);
+        assert_eq!(delay.buffer.as_ptr(), line);
+        assert_eq!(delay.buffer.len(), 22050);
+    }
+
+    #[test]
+    fn test_delay_line_fits_the_delay_time() {
+        let mut delay = Delay::new(0.25, 0.0, 1.0);
+        Effect::prepare(&mut delay, 48000.0, 512);
+        assert_eq!(delay.buffer.len(), 12000);
+
+        // A longer delay set from the control side grows the line, echoes and all
+        let mut buffer = vec![0.0; 100];
+        buffer[0] = 1.0;
+        delay.process_block(&mut buffer, 0.0, 0, 48000.0);
+        Effect::set_parameter(&mut delay, "delay_time", 1.0);
+        assert_eq!(delay.buffer.len(), 48000);
+
+        let mut buffer = vec![0.0; 48000];
+        delay.process_block(&mut buffer, 0.0, 100, 48000.0);
+        assert_eq!(buffer[47900], 1.0);
+        assert_eq!(buffer.iter().filter(|&&s| s != 0.0).count(), 1);
+    }
+}
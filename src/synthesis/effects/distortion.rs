@@ -1,4 +1,7 @@
+use super::block;
+use super::effect::{process_stereo_as_stream, Effect};
 use crate::synthesis::automation::Automation;
+use crate::synthesis::simd::SimdLanes;
 use crate::track::PRIORITY_NORMAL;
 
 /// Distortion/overdrive effect
@@ -43,6 +46,17 @@ impl Distortion {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.drive_automation {
+            self.drive = auto.value_at(time).max(1.0);
+        }
+    }
+
     /// Process a single sample using soft clipping
     ///
     /// # Arguments
@@ -54,12 +68,7 @@ impl Distortion {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         // Use bitwise AND instead of modulo for power-of-2
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.drive_automation {
-                self.drive = auto.value_at(time).max(1.0);
-            }
+            self.update_automation(time);
         }
 
         if self.mix < 0.0001 {
@@ -86,81 +95,34 @@ impl Distortion {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     /// * `sample_rate` - Sample rate in Hz (for time advancement)
     #[inline]
-    pub fn process_block(&mut self, buffer: &mut [f32], time: f32, sample_count: u64, _sample_rate: f32) {
-        use crate::synthesis::simd::{SimdWidth, SIMD};
-
-        // Update automation params if needed (check first sample)
-        if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.drive_automation {
-                self.drive = auto.value_at(time).max(1.0);
+    pub fn process_block(&mut self, buffer: &mut [f32], time: f32, sample_count: u64, sample_rate: f32) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        let time_delta = 1.0 / sample_rate;
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
             }
-        }
-
-        // Early exit if no effect
-        if self.mix < 0.0001 {
-            return;
-        }
-
-        // Dispatch to SIMD implementation
-        match SIMD.simd_width() {
-            SimdWidth::X8 => self.process_block_simd::<8>(buffer),
-            SimdWidth::X4 => self.process_block_simd::<4>(buffer),
-            SimdWidth::Scalar => self.process_block_scalar(buffer),
-        }
-    }
-
-    /// SIMD implementation - processes N samples at once
-    #[inline(always)]
-    fn process_block_simd<const N: usize>(&self, buffer: &mut [f32]) {
-        let drive = self.drive;
-        let mix = self.mix;
-        let one_minus_mix = 1.0 - mix;
-        let compensation = 1.0 / drive.sqrt();
-
-        let num_chunks = buffer.len() / N;
-        let remainder_start = num_chunks * N;
-
-        // Process N samples at a time
-        for chunk_idx in 0..num_chunks {
-            let chunk_start = chunk_idx * N;
-            let chunk = &mut buffer[chunk_start..chunk_start + N];
-
-            for sample in chunk.iter_mut() {
-                let input = *sample;
-                let amplified = input * drive;
-                let distorted = amplified.tanh();
-                let normalized = distorted * compensation;
-                *sample = input.mul_add(one_minus_mix, normalized * mix);
+            if self.mix < 0.0001 {
+                continue;
             }
-        }
-
-        // Handle remainder with scalar
-        for i in remainder_start..buffer.len() {
-            let input = buffer[i];
-            let amplified = input * drive;
-            let distorted = amplified.tanh();
-            let normalized = distorted * compensation;
-            buffer[i] = input.mul_add(one_minus_mix, normalized * mix);
-        }
-    }
 
-    /// Scalar fallback
-    #[inline(always)]
-    fn process_block_scalar(&self, buffer: &mut [f32]) {
-        let drive = self.drive;
-        let mix = self.mix;
-        let one_minus_mix = 1.0 - mix;
-        let compensation = 1.0 / drive.sqrt();
-
-        for sample in buffer.iter_mut() {
-            let input = *sample;
-            let amplified = input * drive;
-            let distorted = amplified.tanh();
-            let normalized = distorted * compensation;
-            *sample = input.mul_add(one_minus_mix, normalized * mix);
+            let kernel = DistortionKernel {
+                drive: self.drive,
+                compensation: 1.0 / self.drive.sqrt(),
+                mix: self.mix,
+            };
+            block::apply_kernel(&mut buffer[segment.samples(channels)], &kernel);
         }
     }
 
@@ -197,6 +159,44 @@ impl Distortion {
     }
 }
 
+impl Effect for Distortion {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Distortion::process_block(self, buffer, time, sample_count, sample_rate);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("drive", self.drive), ("mix", self.mix)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "drive" => self.drive = value.max(1.0),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
+
+/// Vectorized tanh soft clipper with the parameters of one segment
+struct DistortionKernel {
+    drive: f32,
+    compensation: f32,
+    mix: f32,
+}
+
+impl block::Kernel for DistortionKernel {
+    #[inline(always)]
+    fn apply<V: SimdLanes>(&self, input: V, _index: V) -> V {
+        let distorted = input.mul(V::splat(self.drive)).tanh();
+        let normalized = distorted.mul(V::splat(self.compensation));
+        input.mul_add(V::splat(1.0 - self.mix), normalized.mul(V::splat(self.mix)))
+    }
+}
+
 /// Bit crusher - lo-fi digital degradation effect
 #[derive(Debug, Clone)]
 pub struct BitCrusher {
@@ -258,6 +258,20 @@ impl BitCrusher {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.bit_depth_automation {
+            self.bit_depth = auto.value_at(time).clamp(1.0, 16.0);
+        }
+        if let Some(auto) = &self.sample_rate_reduction_automation {
+            self.sample_rate_reduction = auto.value_at(time).max(1.0);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -269,15 +283,7 @@ impl BitCrusher {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         // Use bitwise AND instead of modulo for power-of-2
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.bit_depth_automation {
-                self.bit_depth = auto.value_at(time).clamp(1.0, 16.0);
-            }
-            if let Some(auto) = &self.sample_rate_reduction_automation {
-                self.sample_rate_reduction = auto.value_at(time).max(1.0);
-            }
+            self.update_automation(time);
         }
 
         // Sample rate reduction (sample & hold)
@@ -306,11 +312,44 @@ impl BitCrusher {
     /// * `sample_rate` - Sample rate in Hz (for time advancement)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], time: f32, sample_count: u64, sample_rate: f32) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, current_time, current_sample_count);
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+
+            let levels = self.bit_depth.exp2();
+            let reduction = self.sample_rate_reduction;
+            let mix = self.mix;
+            let dry = 1.0 - mix;
+            let mut hold_sample = self.hold_sample;
+            let mut counter = self.sample_counter;
+
+            for sample in &mut buffer[segment.samples(channels)] {
+                let input = *sample;
+                counter += 1.0;
+                if counter >= reduction {
+                    hold_sample = input.clamp(-2.0, 2.0);
+                    counter = 0.0;
+                }
+                let quantized = (hold_sample * levels).round() / levels;
+                *sample = input.mul_add(dry, quantized * mix).clamp(-2.0, 2.0);
+            }
+
+            self.hold_sample = hold_sample;
+            self.sample_counter = counter;
         }
     }
 
@@ -348,6 +387,37 @@ impl BitCrusher {
     }
 }
 
+impl Effect for BitCrusher {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        BitCrusher::process_block(self, buffer, time, sample_count, sample_rate);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        BitCrusher::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("bit_depth", self.bit_depth),
+            ("sample_rate_reduction", self.sample_rate_reduction),
+            ("mix", self.mix),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "bit_depth" => self.bit_depth = value.clamp(1.0, 16.0),
+            "sample_rate_reduction" => self.sample_rate_reduction = value.max(1.0),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
+
 /// Saturation effect - analog-style harmonic distortion
 #[derive(Debug, Clone)]
 pub struct Saturation {
@@ -405,6 +475,20 @@ impl Saturation {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.drive_automation {
+            self.drive = auto.value_at(time).clamp(1.0, 20.0);
+        }
+        if let Some(auto) = &self.character_automation {
+            self.character = auto.value_at(time).clamp(0.0, 1.0);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -416,15 +500,7 @@ impl Saturation {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         // Use bitwise AND instead of modulo for power-of-2
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.drive_automation {
-                self.drive = auto.value_at(time).clamp(1.0, 20.0);
-            }
-            if let Some(auto) = &self.character_automation {
-                self.character = auto.value_at(time).clamp(0.0, 1.0);
-            }
+            self.update_automation(time);
         }
 
         if self.mix < 0.0001 {
@@ -456,113 +532,35 @@ impl Saturation {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     /// * `sample_rate` - Sample rate in Hz (for time advancement)
     #[inline]
-    pub fn process_block(&mut self, buffer: &mut [f32], time: f32, sample_count: u64, _sample_rate: f32) {
-        use crate::synthesis::simd::{SimdWidth, SIMD};
-
-        // Update automation params if needed
-        if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.drive_automation {
-                self.drive = auto.value_at(time).clamp(1.0, 20.0);
+    pub fn process_block(&mut self, buffer: &mut [f32], time: f32, sample_count: u64, sample_rate: f32) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        let time_delta = 1.0 / sample_rate;
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
             }
-            if let Some(auto) = &self.character_automation {
-                self.character = auto.value_at(time).clamp(0.0, 1.0);
+            if self.mix < 0.0001 {
+                continue;
             }
-        }
-
-        // Early exit if no effect
-        if self.mix < 0.0001 {
-            return;
-        }
-
-        // Dispatch to SIMD implementation
-        match SIMD.simd_width() {
-            SimdWidth::X8 => self.process_block_simd::<8>(buffer),
-            SimdWidth::X4 => self.process_block_simd::<4>(buffer),
-            SimdWidth::Scalar => self.process_block_scalar(buffer),
-        }
-    }
 
-    /// SIMD implementation - processes N samples at once
-    #[inline(always)]
-    fn process_block_simd<const N: usize>(&self, buffer: &mut [f32]) {
-        let drive = self.drive;
-        let character = self.character;
-        let mix = self.mix;
-        let one_minus_mix = 1.0 - mix;
-        let one_minus_character = 1.0 - character;
-        let compensation = 1.0 / drive.sqrt();
-
-        let num_chunks = buffer.len() / N;
-        let remainder_start = num_chunks * N;
-
-        // Process N samples at a time
-        for chunk_idx in 0..num_chunks {
-            let chunk_start = chunk_idx * N;
-            let chunk = &mut buffer[chunk_start..chunk_start + N];
-
-            for sample in chunk.iter_mut() {
-                let input = *sample;
-                let amplified = input * drive;
-
-                // Blend between soft (tanh) and hard (cubic) saturation
-                let soft = amplified.tanh();
-                let hard = if amplified.abs() <= 1.0 {
-                    amplified.mul_add(1.5, -0.5 * amplified * amplified.abs())
-                } else {
-                    amplified.signum()
-                };
-
-                let saturated = soft.mul_add(one_minus_character, hard * character);
-                let normalized = saturated * compensation;
-                *sample = input.mul_add(one_minus_mix, normalized * mix);
-            }
-        }
-
-        // Handle remainder with scalar
-        for i in remainder_start..buffer.len() {
-            let input = buffer[i];
-            let amplified = input * drive;
-
-            let soft = amplified.tanh();
-            let hard = if amplified.abs() <= 1.0 {
-                amplified.mul_add(1.5, -0.5 * amplified * amplified.abs())
-            } else {
-                amplified.signum()
-            };
-
-            let saturated = soft.mul_add(one_minus_character, hard * character);
-            let normalized = saturated * compensation;
-            buffer[i] = input.mul_add(one_minus_mix, normalized * mix);
-        }
-    }
-
-    /// Scalar fallback
-    #[inline(always)]
-    fn process_block_scalar(&self, buffer: &mut [f32]) {
-        let drive = self.drive;
-        let character = self.character;
-        let mix = self.mix;
-        let one_minus_mix = 1.0 - mix;
-        let one_minus_character = 1.0 - character;
-        let compensation = 1.0 / drive.sqrt();
-
-        for sample in buffer.iter_mut() {
-            let input = *sample;
-            let amplified = input * drive;
-
-            let soft = amplified.tanh();
-            let hard = if amplified.abs() <= 1.0 {
-                amplified.mul_add(1.5, -0.5 * amplified * amplified.abs())
-            } else {
-                amplified.signum()
+            let kernel = SaturationKernel {
+                drive: self.drive,
+                character: self.character,
+                compensation: 1.0 / self.drive.sqrt(),
+                mix: self.mix,
             };
-
-            let saturated = soft.mul_add(one_minus_character, hard * character);
-            let normalized = saturated * compensation;
-            *sample = input.mul_add(one_minus_mix, normalized * mix);
+            block::apply_kernel(&mut buffer[segment.samples(channels)], &kernel);
         }
     }
 
@@ -598,3 +596,54 @@ impl Saturation {
         Self::new(5.0, 0.9, 1.0)
     }
 }
+
+impl Effect for Saturation {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Saturation::process_block(self, buffer, time, sample_count, sample_rate);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("drive", self.drive), ("character", self.character), ("mix", self.mix)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "drive" => self.drive = value.clamp(1.0, 20.0),
+            "character" => self.character = value.clamp(0.0, 1.0),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
+
+/// Vectorized soft/hard saturation with the parameters of one segment
+struct SaturationKernel {
+    drive: f32,
+    character: f32,
+    compensation: f32,
+    mix: f32,
+}
+
+impl block::Kernel for SaturationKernel {
+    #[inline(always)]
+    fn apply<V: SimdLanes>(&self, input: V, _index: V) -> V {
+        let amplified = input.mul(V::splat(self.drive));
+        let soft = amplified.tanh();
+
+        // The cubic curve reaches exactly +/-1 at |x| = 1, so clamping first
+        // reproduces the hard clip above that without a branch
+        let clipped = amplified.max(V::splat(-1.0)).min(V::splat(1.0));
+        let hard = clipped.mul_add(V::splat(1.5), V::splat(-0.5).mul(clipped).mul(clipped.abs()));
+
+        let saturated = soft.mul_add(
+            V::splat(1.0 - self.character),
+            hard.mul(V::splat(self.character)),
+        );
+        let normalized = saturated.mul(V::splat(self.compensation));
+        input.mul_add(V::splat(1.0 - self.mix), normalized.mul(V::splat(self.mix)))
+    }
+}
@@ -1,4 +1,8 @@
+use super::block;
+use super::effect::{process_stereo_as_stream, Effect};
 use crate::synthesis::automation::Automation;
+use crate::synthesis::loudness::TruePeakInterpolator;
+use std::collections::VecDeque;
 use crate::track::{
     PRIORITY_EARLY, PRIORITY_LAST, TrackId, BusId,
 };
@@ -430,6 +434,38 @@ impl Compressor {
         self
     }
 
+    /// Gain reduction for a detector envelope (1.0 below the threshold)
+    #[inline]
+    fn gain_for_envelope(envelope: f32, threshold: f32, ratio: f32) -> f32 {
+        if envelope > threshold {
+            let over_threshold = envelope / threshold.max(0.001); // Prevent division by zero
+            let compressed = over_threshold.powf(1.0 / ratio);
+            (compressed * threshold / envelope).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.threshold_automation {
+            self.threshold = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.ratio_automation {
+            self.ratio = auto.value_at(time).max(1.0);
+        }
+        if let Some(auto) = &self.attack_automation {
+            self.attack = auto.value_at(time).max(0.001);
+        }
+        if let Some(auto) = &self.release_automation {
+            self.release = auto.value_at(time).max(0.001);
+        }
+        if let Some(auto) = &self.makeup_gain_automation {
+            self.makeup_gain = auto.value_at(time).max(0.1);
+        }
+    }
+
     /// Process a single sample at given sample rate
     ///
     /// # Arguments
@@ -453,21 +489,7 @@ impl Compressor {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         // Use bitwise AND instead of modulo for power-of-2
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.threshold_automation {
-                self.threshold = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.ratio_automation {
-                self.ratio = auto.value_at(time).max(1.0);
-            }
-            if let Some(auto) = &self.attack_automation {
-                self.attack = auto.value_at(time).max(0.001);
-            }
-            if let Some(auto) = &self.release_automation {
-                self.release = auto.value_at(time).max(0.001);
-            }
-            if let Some(auto) = &self.makeup_gain_automation {
-                self.makeup_gain = auto.value_at(time).max(0.1);
-            }
+            self.update_automation(time);
         }
 
         // Use sidechain envelope if provided, otherwise use input level
@@ -489,13 +511,7 @@ impl Compressor {
         self.envelope = self.envelope.clamp(0.0, 2.0);
 
         // Calculate gain reduction
-        let gain = if self.envelope > self.threshold {
-            let over_threshold = self.envelope / self.threshold.max(0.001); // Prevent division by zero
-            let compressed = over_threshold.powf(1.0 / self.ratio);
-            (compressed * self.threshold / self.envelope).clamp(0.0, 1.0)
-        } else {
-            1.0
-        };
+        let gain = Self::gain_for_envelope(self.envelope, self.threshold, self.ratio);
 
         // Apply compression and makeup gain using FMA, clamp output to prevent clipping
         let output = input * gain * self.makeup_gain;
@@ -529,21 +545,7 @@ impl Compressor {
     ) -> (f32, f32) {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.threshold_automation {
-                self.threshold = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.ratio_automation {
-                self.ratio = auto.value_at(time).max(1.0);
-            }
-            if let Some(auto) = &self.attack_automation {
-                self.attack = auto.value_at(time).max(0.001);
-            }
-            if let Some(auto) = &self.release_automation {
-                self.release = auto.value_at(time).max(0.001);
-            }
-            if let Some(auto) = &self.makeup_gain_automation {
-                self.makeup_gain = auto.value_at(time).max(0.1);
-            }
+            self.update_automation(time);
         }
 
         // Use sidechain envelope if provided, otherwise use max of both channels for detection
@@ -565,13 +567,7 @@ impl Compressor {
         self.envelope = self.envelope.clamp(0.0, 2.0);
 
         // Calculate gain reduction (same for both channels)
-        let gain = if self.envelope > self.threshold {
-            let over_threshold = self.envelope / self.threshold.max(0.001);
-            let compressed = over_threshold.powf(1.0 / self.ratio);
-            (compressed * self.threshold / self.envelope).clamp(0.0, 1.0)
-        } else {
-            1.0
-        };
+        let gain = Self::gain_for_envelope(self.envelope, self.threshold, self.ratio);
 
         // Apply same gain to both channels with makeup gain
         let left_out = (left * gain * self.makeup_gain).clamp(-2.0, 2.0);
@@ -582,6 +578,9 @@ impl Compressor {
 
     /// Process a block of samples
     ///
+    /// Attack and release coefficients are computed once per 64-sample segment
+    /// instead of once per sample.
+    ///
     /// # Arguments
     /// * `buffer` - Buffer of samples to process in-place
     /// * `sample_rate` - Sample rate in Hz
@@ -591,11 +590,111 @@ impl Compressor {
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64, sidechain_envelope: Option<f32>) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, sample_rate, current_time, current_sample_count, sidechain_envelope);
+
+        // Multiband compression filters every sample through each band's compressor
+        if self.bands.is_some() {
+            for (i, sample) in buffer.iter_mut().enumerate() {
+                let current_time = time + (i as f32 * time_delta);
+                let current_sample_count = sample_count + i as u64;
+                *sample = self.process(*sample, sample_rate, current_time, current_sample_count, sidechain_envelope);
+            }
+            return;
+        }
+
+        for segment in block::segments(buffer.len(), sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+
+            let (attack_coeff, release_coeff) = self.envelope_coefficients(sample_rate);
+            let threshold = self.threshold;
+            let ratio = self.ratio;
+            let makeup_gain = self.makeup_gain;
+            let mut envelope = self.envelope;
+
+            for sample in &mut buffer[segment.start..segment.end] {
+                let input = *sample;
+                let input_level = sidechain_envelope.unwrap_or_else(|| input.abs());
+                let coeff = if input_level > envelope {
+                    attack_coeff
+                } else {
+                    release_coeff
+                };
+                envelope = envelope
+                    .mul_add(coeff, input_level * (1.0 - coeff))
+                    .clamp(0.0, 2.0);
+
+                let gain = Self::gain_for_envelope(envelope, threshold, ratio);
+                *sample = (input * gain * makeup_gain).clamp(-2.0, 2.0);
+            }
+
+            self.envelope = envelope;
+        }
+    }
+
+    /// Process a block of interleaved stereo frames with linked compression
+    ///
+    /// Block equivalent of [`process_stereo_linked`](Self::process_stereo_linked).
+    pub(crate) fn process_stereo_linked_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+        sidechain_envelope: Option<f32>,
+    ) {
+        let time_delta = 1.0 / sample_rate;
+        for segment in block::segments(buffer.len() / 2, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            let frames = buffer[segment.samples(2)]
+                .chunks_exact_mut(2)
+                .map(block::split_frame);
+            self.process_linked_frames(frames, sample_rate, sidechain_envelope);
+        }
+    }
+
+    /// Run the stereo-linked detector over frames that share one set of parameters
+    #[inline]
+    fn process_linked_frames<'a>(
+        &mut self,
+        frames: impl Iterator<Item = (&'a mut f32, &'a mut f32)>,
+        sample_rate: f32,
+        sidechain_envelope: Option<f32>,
+    ) {
+        let (attack_coeff, release_coeff) = self.envelope_coefficients(sample_rate);
+        let threshold = self.threshold;
+        let ratio = self.ratio;
+        let makeup_gain = self.makeup_gain;
+        let mut envelope = self.envelope;
+
+        for (left, right) in frames {
+            let input_level = sidechain_envelope.unwrap_or_else(|| left.abs().max(right.abs()));
+            let coeff = if input_level > envelope {
+                attack_coeff
+            } else {
+                release_coeff
+            };
+            envelope = envelope
+                .mul_add(coeff, input_level * (1.0 - coeff))
+                .clamp(0.0, 2.0);
+
+            let gain = Self::gain_for_envelope(envelope, threshold, ratio);
+            *left = (*left * gain * makeup_gain).clamp(-2.0, 2.0);
+            *right = (*right * gain * makeup_gain).clamp(-2.0, 2.0);
         }
+
+        self.envelope = envelope;
+    }
+
+    /// Attack and release smoothing coefficients for the current settings
+    #[inline]
+    fn envelope_coefficients(&self, sample_rate: f32) -> (f32, f32) {
+        (
+            (-1.0 / (self.attack * sample_rate)).exp(),
+            (-1.0 / (self.release * sample_rate)).exp(),
+        )
     }
 
     /// Reset the compressor state
@@ -641,6 +740,58 @@ impl Compressor {
     }
 }
 
+impl Effect for Compressor {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Compressor::process_block(self, buffer, sample_rate, time, sample_count, None);
+    }
+
+    fn process_stereo_block(
+        &mut self,
+        left: &mut [f32],
+        right: &mut [f32],
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        // Stereo-linked so both channels receive the same gain reduction
+        let time_delta = 1.0 / sample_rate;
+        for segment in block::segments(left.len().min(right.len()), sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            let frames = left[segment.start..segment.end]
+                .iter_mut()
+                .zip(right[segment.start..segment.end].iter_mut());
+            self.process_linked_frames(frames, sample_rate, None);
+        }
+    }
+
+    fn reset(&mut self) {
+        Compressor::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("threshold", self.threshold),
+            ("ratio", self.ratio),
+            ("attack", self.attack),
+            ("release", self.release),
+            ("makeup_gain", self.makeup_gain),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "threshold" => self.threshold = value.clamp(0.0, 1.0),
+            "ratio" => self.ratio = value.max(1.0),
+            "attack" => self.attack = value.max(0.001),
+            "release" => self.release = value.max(0.001),
+            "makeup_gain" => self.makeup_gain = value.max(0.1),
+            _ => {}
+        }
+    }
+}
+
 /// Gate - noise gate / expander
 ///
 /// Reduces the level of signals below a threshold, useful for removing
@@ -712,6 +863,17 @@ impl Gate {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.threshold_automation {
+            self.threshold = auto.value_at(time);
+        }
+        if let Some(auto) = &self.ratio_automation {
+            self.ratio = auto.value_at(time).max(1.0);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -723,12 +885,7 @@ impl Gate {
     pub fn process(&mut self, input: f32, sample_rate: f32, time: f32, sample_count: u64) -> f32 {
         // Quantized automation lookups (every 64 samples)
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.threshold_automation {
-                self.threshold = auto.value_at(time);
-            }
-            if let Some(auto) = &self.ratio_automation {
-                self.ratio = auto.value_at(time).max(1.0);
-            }
+            self.update_automation(time);
         }
 
         // Convert input to dB
@@ -772,11 +929,56 @@ impl Gate {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, sample_rate, current_time, current_sample_count);
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+
+            let attack_coeff = (-1.0 / (self.attack * sample_rate)).exp();
+            let release_coeff = (-1.0 / (self.release * sample_rate)).exp();
+            let threshold = self.threshold;
+            let ratio = self.ratio;
+            let mut envelope = self.envelope;
+
+            for sample in &mut buffer[segment.samples(channels)] {
+                let input = *sample;
+                let input_db = if input.abs() > 0.0001 {
+                    20.0 * input.abs().log10()
+                } else {
+                    -100.0
+                };
+
+                let target_envelope = if input_db > threshold {
+                    1.0
+                } else {
+                    let db_below = threshold - input_db;
+                    let expansion = db_below * (ratio - 1.0) / ratio;
+                    10.0_f32.powf(-expansion / 20.0)
+                };
+
+                let coeff = if target_envelope > envelope {
+                    attack_coeff
+                } else {
+                    release_coeff
+                };
+                envelope = target_envelope + coeff * (envelope - target_envelope);
+                *sample = input * envelope;
+            }
+
+            self.envelope = envelope;
         }
     }
 
@@ -813,6 +1015,39 @@ impl Gate {
     }
 }
 
+impl Effect for Gate {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Gate::process_block(self, buffer, sample_rate, time, sample_count);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        Gate::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("threshold", self.threshold),
+            ("ratio", self.ratio),
+            ("attack", self.attack),
+            ("release", self.release),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "threshold" => self.threshold = value,
+            "ratio" => self.ratio = value.max(1.0),
+            "attack" => self.attack = value.max(0.0001),
+            "release" => self.release = value.max(0.001),
+            _ => {}
+        }
+    }
+}
+
 /// Limiter - brick-wall peak limiter
 ///
 /// Prevents signal from exceeding a threshold, acting as a safety net
@@ -827,6 +1062,9 @@ pub struct Limiter {
 
     // Automation (optional)
     threshold_automation: Option<Automation>,
+
+    // True-peak lookahead, for frames fed through `process_lookahead`
+    lookahead: Option<Box<TruePeakLookahead>>,
 }
 
 impl Limiter {
@@ -844,6 +1082,7 @@ impl Limiter {
             gain_reduction: 1.0,
             _sample_rate: sample_rate,
             threshold_automation: None,
+            lookahead: None,
         }
     }
 
@@ -864,6 +1103,14 @@ impl Limiter {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.threshold_automation {
+            self.threshold = auto.value_at(time);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -875,9 +1122,7 @@ impl Limiter {
     pub fn process(&mut self, input: f32, sample_rate: f32, time: f32, sample_count: u64) -> f32 {
         // Quantized automation lookups (every 64 samples)
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.threshold_automation {
-                self.threshold = auto.value_at(time);
-            }
+            self.update_automation(time);
         }
 
         // Convert threshold from dB to linear
@@ -932,9 +1177,7 @@ impl Limiter {
     ) -> (f32, f32) {
         // Quantized automation lookups (every 64 samples)
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.threshold_automation {
-                self.threshold = auto.value_at(time);
-            }
+            self.update_automation(time);
         }
 
         // Convert threshold from dB to linear
@@ -965,6 +1208,9 @@ impl Limiter {
 
     /// Process a block of samples
     ///
+    /// The threshold and release coefficient are computed once per 64-sample
+    /// segment instead of once per sample.
+    ///
     /// # Arguments
     /// * `buffer` - Buffer of samples to process in-place
     /// * `sample_rate` - Sample rate in Hz
@@ -973,10 +1219,135 @@ impl Limiter {
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, sample_rate, current_time, current_sample_count);
+        for segment in block::segments(buffer.len(), sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+
+            let threshold_linear = 10.0_f32.powf(self.threshold / 20.0);
+            let release_coeff = (-1.0 / (self.release * sample_rate)).exp();
+            let mut gain_reduction = self.gain_reduction;
+
+            for sample in &mut buffer[segment.start..segment.end] {
+                let input_abs = sample.abs();
+                let target_gain = if input_abs > threshold_linear {
+                    threshold_linear / input_abs
+                } else {
+                    1.0
+                };
+                gain_reduction = if target_gain < gain_reduction {
+                    target_gain
+                } else {
+                    target_gain + release_coeff * (gain_reduction - target_gain)
+                };
+                *sample *= gain_reduction;
+            }
+
+            self.gain_reduction = gain_reduction;
+        }
+    }
+
+    /// Process a block of interleaved stereo frames with linked limiting
+    ///
+    /// Block equivalent of [`process_stereo_linked`](Self::process_stereo_linked).
+    pub(crate) fn process_stereo_linked_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        let time_delta = 1.0 / sample_rate;
+        for segment in block::segments(buffer.len() / 2, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            let frames = buffer[segment.samples(2)]
+                .chunks_exact_mut(2)
+                .map(block::split_frame);
+            self.process_linked_frames(frames, sample_rate);
+        }
+    }
+
+    /// Run the stereo-linked peak detector over frames that share one set of parameters
+    #[inline]
+    fn process_linked_frames<'a>(
+        &mut self,
+        frames: impl Iterator<Item = (&'a mut f32, &'a mut f32)>,
+        sample_rate: f32,
+    ) {
+        let threshold_linear = 10.0_f32.powf(self.threshold / 20.0);
+        let release_coeff = (-1.0 / (self.release * sample_rate)).exp();
+        let mut gain_reduction = self.gain_reduction;
+
+        for (left, right) in frames {
+            let peak = left.abs().max(right.abs());
+            let target_gain = if peak > threshold_linear {
+                threshold_linear / peak
+            } else {
+                1.0
+            };
+            gain_reduction = if target_gain < gain_reduction {
+                target_gain
+            } else {
+                target_gain + release_coeff * (gain_reduction - target_gain)
+            };
+            *left *= gain_reduction;
+            *right *= gain_reduction;
+        }
+
+        self.gain_reduction = gain_reduction;
+    }
+
+    /// Look ahead by `seconds` and hold true peaks, rather than sample peaks, below the
+    /// threshold in [`process_lookahead`](Self::process_lookahead)
+    ///
+    /// Only offline rendering uses this; in an effect chain the limiter keeps reacting
+    /// to sample peaks without delaying the signal.
+    pub(crate) fn with_true_peak_lookahead(mut self, seconds: f32, sample_rate: f32) -> Self {
+        let frames = ((seconds * sample_rate) as usize).max(1);
+        self.lookahead = Some(Box::new(TruePeakLookahead::new(frames, sample_rate)));
+        self
+    }
+
+    /// Frames between a frame going into [`process_lookahead`](Self::process_lookahead)
+    /// and coming out
+    pub(crate) fn lookahead_latency(&self) -> usize {
+        self.lookahead.as_ref().map_or(0, |lookahead| lookahead.latency())
+    }
+
+    /// Limit a stereo frame ahead of its true peaks, appending the frame that leaves
+    /// the lookahead to `out`
+    ///
+    /// Each frame's true peak is reconstructed with a [`TruePeakInterpolator`]. The
+    /// gain holds the lowest reduction needed over the samples a peak is
+    /// reconstructed from, follows it with the limiter's instant attack and release,
+    /// and is averaged over the lookahead so it ramps down before the peak arrives
+    /// without undershooting it. Without [`with_true_peak_lookahead`](Self::with_true_peak_lookahead)
+    /// frames pass straight through the sample-peak limiter.
+    pub(crate) fn process_lookahead(&mut self, frame: [f32; 2], sample_rate: f32, out: &mut Vec<f32>) {
+        let Some(lookahead) = self.lookahead.as_mut() else {
+            let (left, right) = self.process_stereo_linked(frame[0], frame[1], sample_rate, 0.0, 1);
+            out.extend_from_slice(&[left, right]);
+            return;
+        };
+
+        let threshold_linear = 10.0_f32.powf(self.threshold / 20.0);
+        let minimum = lookahead.hold(frame, threshold_linear);
+        let release_coeff = (-1.0 / (self.release * sample_rate)).exp();
+        self.gain_reduction = if minimum < self.gain_reduction {
+            minimum
+        } else {
+            minimum + release_coeff * (self.gain_reduction - minimum)
+        };
+        lookahead.delay(frame, self.gain_reduction, out);
+    }
+
+    /// Push silence through [`process_lookahead`](Self::process_lookahead) until every
+    /// frame has come out
+    pub(crate) fn flush_lookahead(&mut self, sample_rate: f32, out: &mut Vec<f32>) {
+        for _ in 0..self.lookahead_latency() {
+            self.process_lookahead([0.0; 2], sample_rate, out);
         }
     }
 
@@ -994,6 +1365,9 @@ impl Limiter {
     /// Reset the limiter state
     pub fn reset(&mut self) {
         self.gain_reduction = 1.0;
+        if let Some(lookahead) = &mut self.lookahead {
+            **lookahead = TruePeakLookahead::new(lookahead.frames, lookahead.sample_rate);
+        }
     }
 
     // ========== PRESETS ==========
@@ -1023,3 +1397,126 @@ impl Limiter {
         Self::with_sample_rate(0.0, 0.01, DEFAULT_SAMPLE_RATE)
     }
 }
+
+/// Lookahead state of a true-peak [`Limiter`]
+#[derive(Debug, Clone)]
+struct TruePeakLookahead {
+    frames: usize,
+    sample_rate: f32,
+    detectors: [TruePeakInterpolator; 2],
+    // Frames pushed so far
+    pushed: u64,
+    // Candidates for the lowest gain required over the hold window, oldest first
+    required: VecDeque<(u64, f32)>,
+    // Gain over the lookahead, and its sum, for the smoothed gain
+    smoothing: VecDeque<f32>,
+    smoothing_sum: f64,
+    // Frames waiting for their gain
+    delayed: VecDeque<[f32; 2]>,
+}
+
+impl TruePeakLookahead {
+    fn new(frames: usize, sample_rate: f32) -> Self {
+        Self {
+            frames,
+            sample_rate,
+            detectors: [
+                TruePeakInterpolator::new(sample_rate as u32),
+                TruePeakInterpolator::new(sample_rate as u32),
+            ],
+            pushed: 0,
+            required: VecDeque::new(),
+            smoothing: std::iter::repeat_n(1.0, frames).collect(),
+            smoothing_sum: frames as f64,
+            delayed: VecDeque::new(),
+        }
+    }
+
+    fn latency(&self) -> usize {
+        self.frames + 2 * TruePeakInterpolator::DELAY - 1
+    }
+
+    /// Detect the frame's true peak and return the lowest gain required over the
+    /// window that covers every sample a peak is reconstructed from
+    fn hold(&mut self, frame: [f32; 2], threshold: f32) -> f32 {
+        let peak = self.detectors[0]
+            .push(frame[0])
+            .max(self.detectors[1].push(frame[1]));
+        let required = if peak > threshold {
+            threshold / peak
+        } else {
+            1.0
+        };
+
+        let window = (self.frames + 2 * TruePeakInterpolator::DELAY) as u64;
+        while self.required.back().is_some_and(|&(_, gain)| gain >= required) {
+            self.required.pop_back();
+        }
+        self.required.push_back((self.pushed, required));
+        while self.required.front().is_some_and(|&(frame, _)| frame + window <= self.pushed) {
+            self.required.pop_front();
+        }
+        self.pushed += 1;
+        self.required.front().map_or(1.0, |&(_, gain)| gain)
+    }
+
+    /// Average `gain` over the lookahead and apply it to the frame leaving the delay
+    fn delay(&mut self, frame: [f32; 2], gain: f32, out: &mut Vec<f32>) {
+        self.smoothing.push_back(gain);
+        self.smoothing_sum += gain as f64;
+        if let Some(oldest) = self.smoothing.pop_front() {
+            self.smoothing_sum -= oldest as f64;
+        }
+
+        self.delayed.push_back(frame);
+        if self.delayed.len() > self.latency() {
+            let gain = (self.smoothing_sum / self.frames as f64) as f32;
+            if let Some([left, right]) = self.delayed.pop_front() {
+                out.extend_from_slice(&[left * gain, right * gain]);
+            }
+        }
+    }
+}
+
+impl Effect for Limiter {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Limiter::process_block(self, buffer, sample_rate, time, sample_count);
+    }
+
+    fn process_stereo_block(
+        &mut self,
+        left: &mut [f32],
+        right: &mut [f32],
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        // Stereo-linked so both channels receive the same gain reduction
+        let time_delta = 1.0 / sample_rate;
+        for segment in block::segments(left.len().min(right.len()), sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            let frames = left[segment.start..segment.end]
+                .iter_mut()
+                .zip(right[segment.start..segment.end].iter_mut());
+            self.process_linked_frames(frames, sample_rate);
+        }
+    }
+
+    fn reset(&mut self) {
+        Limiter::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("threshold", self.threshold), ("release", self.release)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "threshold" => self.threshold = value,
+            "release" => self.release = value.max(0.001),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! The `Effect` trait and insert slots for user-defined processors
+//!
+//! Every built-in effect implements [`Effect`], and so can any type you write.
+//! Effects implementing the trait can be placed in an [`EffectChain`](super::EffectChain)'s
+//! insert list, which holds any number of processors (including several instances of
+//! the same built-in effect) in an explicit, user-controlled order.
+//!
+//! # Writing a custom effect
+//!
+//! ```
+//! use tunes::synthesis::effects::{Effect, EffectChain, Reverb};
+//!
+//! /// A simple gain stage
+//! #[derive(Debug, Clone)]
+//! struct Gain {
+//!     amount: f32,
+//! }
+//!
+//! impl Effect for Gain {
+//!     fn process_block(&mut self, buffer: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {
+//!         for sample in buffer.iter_mut() {
+//!             *sample *= self.amount;
+//!         }
+//!     }
+//!
+//!     fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {
+//!         // No state, so both channels can share the same code
+//!         for sample in left.iter_mut().chain(right.iter_mut()) {
+//!             *sample *= self.amount;
+//!         }
+//!     }
+//! }
+//!
+//! let mut chain = EffectChain::new()
+//!     .with_effect(Gain { amount: 0.5 })
+//!     .with_effect(Reverb::hall());
+//!
+//! // Insert, reorder and bypass at runtime
+//! chain.insert_effect(0, Gain { amount: 2.0 });
+//! chain.move_effect(2, 0);
+//! chain.set_bypass(1, true);
+//! assert_eq!(chain.insert_count(), 3);
+//! ```
+
+use crate::track::PRIORITY_LAST;
+
+/// An audio processor that can be placed in an effect chain
+///
+/// Only [`process_block`](Effect::process_block) and
+/// [`process_stereo_block`](Effect::process_stereo_block) are required. The remaining
+/// methods have sensible defaults and can be overridden to give the mixer more
+/// information about the effect (its tail, its latency).
+///
+/// Effects must be `Clone` (so mixers can be cloned) and `Send` (so tracks can be
+/// rendered in parallel).
+pub trait Effect: EffectClone + std::fmt::Debug + Send {
+    /// Human-readable name of this effect
+    ///
+    /// Defaults to the type name without its module path.
+    fn name(&self) -> &str {
+        let full = std::any::type_name::<Self>();
+        full.rsplit("::").next().unwrap_or(full)
+    }
+
+    /// Prepare the effect for rendering
+    ///
+    /// Called before rendering starts with the sample rate and the largest block size
+    /// that will be passed to `process_block`. Allocate buffers here rather than in
+    /// the processing methods.
+    fn prepare(&mut self, _sample_rate: f32, _max_block_size: usize) {}
+
+    /// Process a block of mono samples in-place
+    ///
+    /// # Arguments
+    /// * `buffer` - Buffer of samples to process in-place
+    /// * `sample_rate` - Sample rate in Hz
+    /// * `time` - Time of the first sample in seconds (for automation)
+    /// * `sample_count` - Global sample counter of the first sample
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64);
+
+    /// Process a block of stereo samples in-place
+    ///
+    /// There is no default: running both channels through one instance would mix
+    /// their state, so every effect has to say how it handles stereo. Effects with
+    /// per-channel state (delays, filters) should keep that state for each channel,
+    /// effects with linked detection (compressors) read both channels together, and
+    /// effects without state can apply the same code to both. Built-in effects
+    /// process a stereo insert exactly as they process a stereo bus in their own
+    /// chain slot.
+    ///
+    /// # Arguments
+    /// * `left` - Left channel samples
+    /// * `right` - Right channel samples (same length as `left`)
+    /// * `sample_rate` - Sample rate in Hz
+    /// * `time` - Time of the first frame in seconds (for automation)
+    /// * `sample_count` - Global sample counter of the first frame
+    fn process_stereo_block(
+        &mut self,
+        left: &mut [f32],
+        right: &mut [f32],
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    );
+
+    /// Clear all internal state (delay lines, envelopes, LFO phases)
+    fn reset(&mut self) {}
+
+    /// How long the effect keeps producing output after its input goes silent, in seconds
+    ///
+    /// The mixer keeps processing a track while any of its effects has a tail, so
+    /// delay repeats and reverb decays are not cut off when the last note ends.
+    fn tail_length(&self) -> f32 {
+        0.0
+    }
+
+    /// Processing latency in samples
+    ///
+    /// Lookahead effects (limiters, linear-phase filters, block convolution) delay
+    /// their output; reporting it here lets the mixer compensate.
+    fn latency(&self) -> usize {
+        0
+    }
+
+    /// Current values of the effect's continuous parameters, as `(name, value)` pairs
+    ///
+    /// Mixer scenes use this to snapshot effect settings and morph between them.
+    /// Names match the effect's field names. The default reports no parameters.
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        Vec::new()
+    }
+
+    /// Set a parameter reported by [`parameters`](Effect::parameters)
+    ///
+    /// Values are clamped to the parameter's valid range; unknown names are ignored.
+    fn set_parameter(&mut self, _name: &str, _value: f32) {}
+}
+
+/// Run a stereo block through `process_block` frame by frame, left sample then right
+///
+/// This is how the built-in effects process stereo in their chain slots: both
+/// channels form a single interleaved stream through one instance.
+pub(crate) fn process_stereo_as_stream<E: Effect + ?Sized>(
+    effect: &mut E,
+    left: &mut [f32],
+    right: &mut [f32],
+    sample_rate: f32,
+    time: f32,
+    sample_count: u64,
+) {
+    let time_delta = 1.0 / sample_rate;
+    for (i, (l, r)) in left.iter_mut().zip(right.iter_mut()).enumerate() {
+        let current_time = time + (i as f32 * time_delta);
+        let current_sample_count = sample_count + i as u64;
+        effect.process_block(std::slice::from_mut(l), sample_rate, current_time, current_sample_count);
+        effect.process_block(std::slice::from_mut(r), sample_rate, current_time, current_sample_count);
+    }
+}
+
+/// Helper trait that lets boxed effects be cloned
+///
+/// Implemented automatically for every `Effect + Clone` type; you never need to
+/// implement it yourself.
+pub trait EffectClone {
+    /// Clone this effect into a new box
+    fn clone_box(&self) -> Box<dyn Effect>;
+}
+
+impl<T> EffectClone for T
+where
+    T: Effect + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn Effect> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Effect> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// An entry in an effect chain's insert list
+///
+/// Holds the effect together with its bypass state and processing priority.
+/// Bypassed effects are skipped during processing but keep their settings and
+/// position in the chain.
+#[derive(Debug, Clone)]
+pub struct EffectSlot {
+    /// The effect processor
+    pub effect: Box<dyn Effect>,
+
+    /// Whether the effect is currently bypassed
+    pub bypassed: bool,
+
+    /// Processing priority (lower = earlier in signal chain), shared with the built-in slots
+    ///
+    /// Inserts run after built-in effects of the same priority. After changing this in
+    /// place, call [`EffectChain::compute_effect_order`](super::EffectChain::compute_effect_order).
+    pub priority: u8,
+}
+
+impl EffectSlot {
+    /// Create a new active slot for an effect
+    ///
+    /// The slot gets [`PRIORITY_LAST`], so it runs after every built-in effect.
+    pub fn new<E: Effect + 'static>(effect: E) -> Self {
+        Self {
+            effect: Box::new(effect),
+            bypassed: false,
+            priority: PRIORITY_LAST,
+        }
+    }
+
+    /// Set the processing priority (lower = earlier in signal chain)
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Name of the effect in this slot
+    pub fn name(&self) -> &str {
+        self.effect.name()
+    }
+}
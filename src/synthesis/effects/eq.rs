@@ -1,3 +1,5 @@
+use super::block;
+use super::effect::{process_stereo_as_stream, Effect};
 use crate::synthesis::automation::Automation;
 use crate::track::PRIORITY_EARLY;
 
@@ -77,6 +79,20 @@ impl EQ {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.low_gain_automation {
+            self.low_gain = auto.value_at(time).clamp(0.0, 4.0);
+        }
+        if let Some(auto) = &self.mid_gain_automation {
+            self.mid_gain = auto.value_at(time).clamp(0.0, 4.0);
+        }
+        if let Some(auto) = &self.high_gain_automation {
+            self.high_gain = auto.value_at(time).clamp(0.0, 4.0);
+        }
+    }
+
     /// Process a single sample at given sample rate
     ///
     /// # Arguments
@@ -89,15 +105,7 @@ impl EQ {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         // Use bitwise AND instead of modulo for power-of-2
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.low_gain_automation {
-                self.low_gain = auto.value_at(time).clamp(0.0, 4.0);
-            }
-            if let Some(auto) = &self.mid_gain_automation {
-                self.mid_gain = auto.value_at(time).clamp(0.0, 4.0);
-            }
-            if let Some(auto) = &self.high_gain_automation {
-                self.high_gain = auto.value_at(time).clamp(0.0, 4.0);
-            }
+            self.update_automation(time);
         }
 
         // Early exit if all gains are unity (no EQ needed)
@@ -137,11 +145,55 @@ impl EQ {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, sample_rate, current_time, current_sample_count);
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            if (self.low_gain - 1.0).abs() < 0.01
+                && (self.mid_gain - 1.0).abs() < 0.01
+                && (self.high_gain - 1.0).abs() < 0.01
+            {
+                continue;
+            }
+
+            let low_coeff = (2.0 * std::f32::consts::PI * self.low_freq / sample_rate).min(0.9);
+            let high_coeff = (2.0 * std::f32::consts::PI * self.high_freq / sample_rate).min(0.9);
+            let low_gain = self.low_gain;
+            let mid_gain = self.mid_gain;
+            let high_gain = self.high_gain;
+            let mut low_state = self.low_state[0];
+            let mut high_state = self.high_state[0];
+
+            for sample in &mut buffer[segment.samples(channels)] {
+                let input = *sample;
+
+                let diff_low = input - low_state;
+                low_state = low_state.mul_add(1.0, low_coeff * diff_low);
+                let low = low_state * low_gain;
+
+                let diff_high = input - high_state;
+                high_state = high_state.mul_add(1.0, high_coeff * diff_high);
+                let high = diff_high * high_gain;
+
+                let mid = (input - low_state - diff_high) * mid_gain;
+                *sample = low + mid + high;
+            }
+
+            self.low_state[0] = low_state;
+            self.high_state[0] = high_state;
         }
     }
 
@@ -190,6 +242,41 @@ impl EQ {
     }
 }
 
+impl Effect for EQ {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        EQ::process_block(self, buffer, sample_rate, time, sample_count);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        EQ::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("low_gain", self.low_gain),
+            ("mid_gain", self.mid_gain),
+            ("high_gain", self.high_gain),
+            ("low_freq", self.low_freq),
+            ("high_freq", self.high_freq),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "low_gain" => self.low_gain = value.clamp(0.0, 4.0),
+            "mid_gain" => self.mid_gain = value.clamp(0.0, 4.0),
+            "high_gain" => self.high_gain = value.clamp(0.0, 4.0),
+            "low_freq" => self.low_freq = value.clamp(20.0, 20000.0),
+            "high_freq" => self.high_freq = value.clamp(20.0, 20000.0),
+            _ => {}
+        }
+    }
+}
+
 /// A single parametric EQ band using a biquad peaking filter
 ///
 /// Allows boosting or cutting a specific frequency range with adjustable bandwidth.
@@ -299,6 +386,32 @@ impl EQBand {
         output
     }
 
+    /// Process a buffer through this EQ band in-place
+    #[inline]
+    fn process_buffer(&mut self, buffer: &mut [f32]) {
+        if !self.enabled {
+            return;
+        }
+
+        let (b0, b1, b2, a1, a2) = (self.b0, self.b1, self.b2, self.a1, self.a2);
+        let (mut x1, mut x2, mut y1, mut y2) = (self.x1, self.x2, self.y1, self.y2);
+
+        for sample in buffer.iter_mut() {
+            let input = *sample;
+            let output = b0 * input + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+            x2 = x1;
+            x1 = input;
+            y2 = y1;
+            y1 = output;
+            *sample = output;
+        }
+
+        self.x1 = x1;
+        self.x2 = x2;
+        self.y1 = y1;
+        self.y2 = y2;
+    }
+
     /// Reset filter state
     pub fn reset(&mut self) {
         self.x1 = 0.0;
@@ -451,22 +564,36 @@ impl ParametricEQ {
 
     /// Process a block of samples through all EQ bands
     ///
+    /// Each band filters the whole block before the next band runs, keeping the
+    /// filter state in registers.
+    ///
     /// # Arguments
     /// * `buffer` - Buffer of samples to process in-place
-    /// * `time` - Starting time in seconds (for compatibility, currently unused)
-    /// * `sample_index` - Starting sample index
-    /// * `sample_rate` - Sample rate in Hz (for time advancement)
+    /// * `_time` - Starting time in seconds (for compatibility, currently unused)
+    /// * `_sample_index` - Starting sample index (for compatibility, currently unused)
+    /// * `_sample_rate` - Sample rate in Hz (for compatibility, currently unused)
     #[inline]
-    pub fn process_block(&mut self, buffer: &mut [f32], time: f32, sample_index: usize, sample_rate: f32) {
-        let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_index = sample_index + i;
-            *sample = self.process(*sample, current_time, current_sample_index);
+    pub fn process_block(&mut self, buffer: &mut [f32], _time: f32, _sample_index: usize, _sample_rate: f32) {
+        for band in &mut self.bands {
+            band.process_buffer(buffer);
         }
     }
 }
 
+impl Effect for ParametricEQ {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        ParametricEQ::process_block(self, buffer, time, sample_count as usize, sample_rate);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        ParametricEQ::reset(self);
+    }
+}
+
 impl Default for ParametricEQ {
     fn default() -> Self {
         Self::new()
@@ -12,6 +12,8 @@ pub mod modulation;
 pub mod spatial;
 pub mod eq;
 pub mod convolution;
+pub mod effect;
+mod block;
 
 // Re-export all effect types
 pub use delay::Delay;
@@ -22,6 +24,7 @@ pub use modulation::{Chorus, Phaser, Flanger, RingModulator, Tremolo};
 pub use spatial::AutoPan;
 pub use eq::{EQ, EQBand, ParametricEQ, EQPreset};
 pub use convolution::{Convolution, ConvolutionReverb, IRParams};
+pub use effect::{Effect, EffectClone, EffectSlot};
 
 /// Effect chain for processing audio through multiple effects in priority order
 ///
@@ -29,6 +32,12 @@ pub use convolution::{Convolution, ConvolutionReverb, IRParams};
 /// through them in a defined order based on priority. Lower priority values are processed
 /// earlier in the chain.
 ///
+/// Besides the fixed built-in slots, the chain holds an ordered list of inserts: any
+/// number of boxed [`Effect`]s (built-in or user-defined). Inserts share the priority
+/// scale with the built-in slots, so one can run before the EQ or between distortion and
+/// reverb; by default they come last, in list order. Inserts can be added, removed,
+/// reordered and bypassed at runtime.
+///
 /// # Example
 /// ```no_run
 /// use tunes::prelude::*;
@@ -59,12 +68,28 @@ pub struct EffectChain {
     pub limiter: Option<Limiter>,
     pub parametric_eq: Option<ParametricEQ>,
 
-    // Pre-computed effect processing order (cached for performance)
+    // Pre-computed processing order of built-in slots and inserts (cached for performance)
     // Effect IDs: 0=EQ, 1=Compressor, 2=Gate, 3=Saturation, 4=BitCrusher, 5=Distortion,
     //             6=Chorus, 7=Phaser, 8=Flanger, 9=RingMod, 10=Tremolo,
     //             11=Delay, 12=Reverb, 13=Limiter, 14=ParametricEQ, 15=ConvolutionReverb
     // (AutoPan excluded - handled separately in stereo stage)
-    pub(crate) effect_order: Vec<u8>,
+    pub(crate) effect_order: Vec<ChainStage>,
+
+    // Ordered insert list (merged into effect_order by priority)
+    inserts: Vec<EffectSlot>,
+
+    // Scratch buffers for de-interleaving stereo blocks (reused between calls)
+    scratch_left: Vec<f32>,
+    scratch_right: Vec<f32>,
+}
+
+/// One step of an effect chain's processing order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChainStage {
+    /// A built-in slot, by effect ID
+    Builtin(u8),
+    /// An insert, by index in the insert list
+    Insert(usize),
 }
 
 impl EffectChain {
@@ -89,6 +114,9 @@ impl EffectChain {
             limiter: None,
             parametric_eq: None,
             effect_order: Vec::new(),
+            inserts: Vec::new(),
+            scratch_left: Vec::new(),
+            scratch_right: Vec::new(),
         }
     }
 
@@ -96,64 +124,70 @@ impl EffectChain {
     ///
     /// Called automatically when effects are added/modified.
     /// This pre-computation avoids allocating and sorting on every audio sample.
+    /// Built-in slots come before inserts of the same priority, and inserts of the
+    /// same priority keep their list order.
     pub fn compute_effect_order(&mut self) {
-        // Build list of (priority, effect_id) for active effects
-        let mut effects = Vec::with_capacity(15);
+        // Build list of (priority, stage) for active effects
+        let mut effects = Vec::with_capacity(15 + self.inserts.len());
 
         if let Some(ref eq) = self.eq {
-            effects.push((eq.priority, 0));
+            effects.push((eq.priority, ChainStage::Builtin(0)));
         }
         if let Some(ref compressor) = self.compressor {
-            effects.push((compressor.priority, 1));
+            effects.push((compressor.priority, ChainStage::Builtin(1)));
         }
         if let Some(ref gate) = self.gate {
-            effects.push((gate.priority, 2));
+            effects.push((gate.priority, ChainStage::Builtin(2)));
         }
         if let Some(ref saturation) = self.saturation {
-            effects.push((saturation.priority, 3));
+            effects.push((saturation.priority, ChainStage::Builtin(3)));
         }
         if let Some(ref bitcrusher) = self.bitcrusher {
-            effects.push((bitcrusher.priority, 4));
+            effects.push((bitcrusher.priority, ChainStage::Builtin(4)));
         }
         if let Some(ref distortion) = self.distortion {
-            effects.push((distortion.priority, 5));
+            effects.push((distortion.priority, ChainStage::Builtin(5)));
         }
         if let Some(ref chorus) = self.chorus {
-            effects.push((chorus.priority, 6));
+            effects.push((chorus.priority, ChainStage::Builtin(6)));
         }
         if let Some(ref phaser) = self.phaser {
-            effects.push((phaser.priority, 7));
+            effects.push((phaser.priority, ChainStage::Builtin(7)));
         }
         if let Some(ref flanger) = self.flanger {
-            effects.push((flanger.priority, 8));
+            effects.push((flanger.priority, ChainStage::Builtin(8)));
         }
         if let Some(ref ring_mod) = self.ring_mod {
-            effects.push((ring_mod.priority, 9));
+            effects.push((ring_mod.priority, ChainStage::Builtin(9)));
         }
         if let Some(ref tremolo) = self.tremolo {
-            effects.push((tremolo.priority, 10));
+            effects.push((tremolo.priority, ChainStage::Builtin(10)));
         }
         if let Some(ref delay) = self.delay {
-            effects.push((delay.priority, 11));
+            effects.push((delay.priority, ChainStage::Builtin(11)));
         }
         if let Some(ref reverb) = self.reverb {
-            effects.push((reverb.priority, 12));
+            effects.push((reverb.priority, ChainStage::Builtin(12)));
         }
         if let Some(ref limiter) = self.limiter {
-            effects.push((limiter.priority, 13));
+            effects.push((limiter.priority, ChainStage::Builtin(13)));
         }
         if let Some(ref parametric_eq) = self.parametric_eq {
-            effects.push((parametric_eq.priority, 14));
+            effects.push((parametric_eq.priority, ChainStage::Builtin(14)));
         }
         if let Some(ref convolution_reverb) = self.convolution_reverb {
-            effects.push((convolution_reverb.priority, 15));
+            effects.push((convolution_reverb.priority, ChainStage::Builtin(15)));
         }
 
-        // Sort by priority (lower = earlier in chain)
+        for (index, slot) in self.inserts.iter().enumerate() {
+            effects.push((slot.priority, ChainStage::Insert(index)));
+        }
+
+        // Sort by priority (lower = earlier in chain); the sort is stable
         effects.sort_by_key(|&(priority, _)| priority);
 
-        // Extract just the effect IDs
-        self.effect_order = effects.into_iter().map(|(_, id)| id).collect();
+        // Extract just the stages
+        self.effect_order = effects.into_iter().map(|(_, stage)| stage).collect();
     }
 
     /// Process a mono audio sample through the effect chain
@@ -180,7 +214,22 @@ impl EffectChain {
         let mut signal = input;
 
         // Process effects in pre-computed priority order
-        for &effect_id in &self.effect_order {
+        for &stage in &self.effect_order {
+            let effect_id = match stage {
+                ChainStage::Builtin(effect_id) => effect_id,
+                ChainStage::Insert(index) => {
+                    let slot = &mut self.inserts[index];
+                    if !slot.bypassed {
+                        slot.effect.process_block(
+                            std::slice::from_mut(&mut signal),
+                            sample_rate,
+                            time,
+                            sample_count,
+                        );
+                    }
+                    continue;
+                }
+            };
             signal = match effect_id {
                 0 => {
                     // EQ
@@ -334,7 +383,17 @@ impl EffectChain {
     ) {
         // Process effects in pre-computed priority order
         // Each effect processes the entire buffer before moving to the next effect
-        for &effect_id in &self.effect_order {
+        for &stage in &self.effect_order {
+            let effect_id = match stage {
+                ChainStage::Builtin(effect_id) => effect_id,
+                ChainStage::Insert(index) => {
+                    let slot = &mut self.inserts[index];
+                    if !slot.bypassed {
+                        slot.effect.process_block(buffer, sample_rate, time, sample_count);
+                    }
+                    continue;
+                }
+            };
             match effect_id {
                 0 => {
                     // EQ
@@ -468,7 +527,23 @@ impl EffectChain {
 
         // Process effects in pre-computed priority order
         // Compressor and limiter use stereo-linked processing to prevent image shift
-        for &effect_id in &self.effect_order {
+        for &stage in &self.effect_order {
+            let effect_id = match stage {
+                ChainStage::Builtin(effect_id) => effect_id,
+                ChainStage::Insert(index) => {
+                    let slot = &mut self.inserts[index];
+                    if !slot.bypassed {
+                        slot.effect.process_stereo_block(
+                            std::slice::from_mut(&mut left_signal),
+                            std::slice::from_mut(&mut right_signal),
+                            sample_rate,
+                            time,
+                            sample_count,
+                        );
+                    }
+                    continue;
+                }
+            };
             match effect_id {
                 0 => {
                     // EQ (process each channel)
@@ -590,6 +665,13 @@ impl EffectChain {
                         right_signal = parametric_eq.process(right_signal, time, sample_count as usize);
                     }
                 }
+                15 => {
+                    // ConvolutionReverb (independent state per channel)
+                    if let Some(ref mut convolution_reverb) = self.convolution_reverb {
+                        (left_signal, right_signal) =
+                            convolution_reverb.process_stereo(left_signal, right_signal);
+                    }
+                }
                 _ => {}
             }
         }
@@ -614,27 +696,490 @@ impl EffectChain {
         sample_count: u64,
         sidechain_envelope: Option<f32>,
     ) {
-        let time_delta = 1.0 / sample_rate;
-
-        // Process each stereo frame (2 samples: left + right)
-        for (i, frame) in buffer.chunks_mut(2).enumerate() {
-            if frame.len() == 2 {
-                let current_time = time + (i as f32 * time_delta);
-                let current_sample_count = sample_count + i as u64;
-
-                let (left, right) = self.process_stereo(
-                    frame[0],
-                    frame[1],
-                    sample_rate,
-                    current_time,
-                    current_sample_count,
-                    sidechain_envelope,
-                );
-
-                frame[0] = left;
-                frame[1] = right;
+        // Only process complete frames
+        let num_samples = buffer.len() & !1;
+        let buffer = &mut buffer[..num_samples];
+
+        // Process effects in pre-computed priority order, each over the whole block.
+        // Per-channel effects see the interleaved stream exactly as process_stereo
+        // feeds it to them; compressor and limiter stay stereo-linked.
+        for &stage in &self.effect_order {
+            let effect_id = match stage {
+                ChainStage::Builtin(effect_id) => effect_id,
+                ChainStage::Insert(index) => {
+                    let slot = &mut self.inserts[index];
+                    if !slot.bypassed {
+                        Self::process_insert_interleaved(
+                            slot,
+                            buffer,
+                            &mut self.scratch_left,
+                            &mut self.scratch_right,
+                            sample_rate,
+                            time,
+                            sample_count,
+                        );
+                    }
+                    continue;
+                }
+            };
+            match effect_id {
+                0 => {
+                    if let Some(ref mut eq) = self.eq {
+                        eq.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                1 => {
+                    if let Some(ref mut compressor) = self.compressor {
+                        compressor.process_stereo_linked_interleaved(
+                            buffer,
+                            sample_rate,
+                            time,
+                            sample_count,
+                            sidechain_envelope,
+                        );
+                    }
+                }
+                2 => {
+                    if let Some(ref mut gate) = self.gate {
+                        gate.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                3 => {
+                    if let Some(ref mut saturation) = self.saturation {
+                        saturation.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                4 => {
+                    if let Some(ref mut bitcrusher) = self.bitcrusher {
+                        bitcrusher.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                5 => {
+                    if let Some(ref mut distortion) = self.distortion {
+                        distortion.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                6 => {
+                    if let Some(ref mut chorus) = self.chorus {
+                        chorus.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                7 => {
+                    if let Some(ref mut phaser) = self.phaser {
+                        phaser.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                8 => {
+                    if let Some(ref mut flanger) = self.flanger {
+                        flanger.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                9 => {
+                    if let Some(ref mut ring_mod) = self.ring_mod {
+                        ring_mod.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                10 => {
+                    if let Some(ref mut tremolo) = self.tremolo {
+                        tremolo.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                11 => {
+                    if let Some(ref mut delay) = self.delay {
+                        delay.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                12 => {
+                    if let Some(ref mut reverb) = self.reverb {
+                        reverb.process_interleaved(buffer, 2, sample_rate, time, sample_count);
+                    }
+                }
+                13 => {
+                    if let Some(ref mut limiter) = self.limiter {
+                        limiter.process_stereo_linked_interleaved(buffer, sample_rate, time, sample_count);
+                    }
+                }
+                14 => {
+                    if let Some(ref mut parametric_eq) = self.parametric_eq {
+                        parametric_eq.process_block(buffer, time, sample_count as usize, sample_rate);
+                    }
+                }
+                15 => {
+                    if let Some(ref mut convolution_reverb) = self.convolution_reverb {
+                        for frame in buffer.chunks_exact_mut(2) {
+                            (frame[0], frame[1]) = convolution_reverb.process_stereo(frame[0], frame[1]);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+    }
+
+    /// Run one insert over an interleaved stereo block
+    ///
+    /// Inserts process whole channel blocks, so the block is de-interleaved into the
+    /// chain's scratch buffers and written back afterwards.
+    fn process_insert_interleaved(
+        slot: &mut EffectSlot,
+        buffer: &mut [f32],
+        scratch_left: &mut Vec<f32>,
+        scratch_right: &mut Vec<f32>,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        scratch_left.clear();
+        scratch_right.clear();
+        for frame in buffer.chunks_exact(2) {
+            scratch_left.push(frame[0]);
+            scratch_right.push(frame[1]);
+        }
+
+        slot.effect.process_stereo_block(
+            scratch_left,
+            scratch_right,
+            sample_rate,
+            time,
+            sample_count,
+        );
+
+        for (i, frame) in buffer.chunks_exact_mut(2).enumerate() {
+            frame[0] = scratch_left[i];
+            frame[1] = scratch_right[i];
+        }
+    }
+
+    // ===== Inserts =====
+
+    /// Append an effect to the insert list (builder pattern)
+    ///
+    /// Inserts accept any [`Effect`], including additional instances of built-in
+    /// effects. They get [`PRIORITY_LAST`](crate::track::PRIORITY_LAST) and so run in
+    /// list order after the built-in slots; use [`with_slot`](Self::with_slot) to place
+    /// one among the built-ins.
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::prelude::*;
+    ///
+    /// // Two independent delays in one chain
+    /// let chain = EffectChain::new()
+    ///     .with_effect(Delay::slapback())
+    ///     .with_effect(Delay::quarter_note());
+    /// assert_eq!(chain.insert_count(), 2);
+    /// ```
+    pub fn with_effect<E: Effect + 'static>(mut self, effect: E) -> Self {
+        self.add_effect(effect);
+        self
+    }
+
+    /// Append an effect to the end of the insert list
+    ///
+    /// Returns the index of the new insert.
+    pub fn add_effect<E: Effect + 'static>(&mut self, effect: E) -> usize {
+        self.add_slot(EffectSlot::new(effect))
+    }
+
+    /// Append a slot to the insert list (builder pattern)
+    ///
+    /// The slot's priority places it among the built-in effects.
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::prelude::*;
+    /// use tunes::track::PRIORITY_NORMAL;
+    ///
+    /// // A second delay that runs before the reverb
+    /// let chain = EffectChain::new()
+    ///     .with_reverb(Reverb::hall())
+    ///     .with_slot(EffectSlot::new(Delay::slapback()).with_priority(PRIORITY_NORMAL));
+    /// assert_eq!(chain.insert_count(), 1);
+    /// ```
+    pub fn with_slot(mut self, slot: EffectSlot) -> Self {
+        self.add_slot(slot);
+        self
+    }
+
+    /// Append an existing slot (effect, bypass state and priority) to the insert list
+    pub fn add_slot(&mut self, slot: EffectSlot) -> usize {
+        self.inserts.push(slot);
+        self.compute_effect_order();
+        self.inserts.len() - 1
+    }
+
+    /// Insert an effect at a position in the insert list
+    ///
+    /// Indices past the end append the effect.
+    pub fn insert_effect<E: Effect + 'static>(&mut self, index: usize, effect: E) {
+        let index = index.min(self.inserts.len());
+        self.inserts.insert(index, EffectSlot::new(effect));
+        self.compute_effect_order();
+    }
+
+    /// Remove an effect from the insert list
+    ///
+    /// Returns the removed effect, or `None` if the index is out of range.
+    pub fn remove_effect(&mut self, index: usize) -> Option<Box<dyn Effect>> {
+        if index < self.inserts.len() {
+            let slot = self.inserts.remove(index);
+            self.compute_effect_order();
+            Some(slot.effect)
+        } else {
+            None
+        }
+    }
+
+    /// Move an insert from one position to another
+    ///
+    /// List order decides between inserts of the same priority.
+    /// Returns `false` if either index is out of range.
+    pub fn move_effect(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.inserts.len() || to >= self.inserts.len() {
+            return false;
+        }
+        let slot = self.inserts.remove(from);
+        self.inserts.insert(to, slot);
+        self.compute_effect_order();
+        true
+    }
+
+    /// Set an insert's processing priority, placing it among the built-in effects
+    ///
+    /// Returns `false` if the index is out of range.
+    pub fn set_priority(&mut self, index: usize, priority: u8) -> bool {
+        match self.inserts.get_mut(index) {
+            Some(slot) => {
+                slot.priority = priority;
+                self.compute_effect_order();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Bypass or re-enable an insert
+    ///
+    /// Returns `false` if the index is out of range.
+    pub fn set_bypass(&mut self, index: usize, bypassed: bool) -> bool {
+        match self.inserts.get_mut(index) {
+            Some(slot) => {
+                slot.bypassed = bypassed;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check whether an insert is bypassed (`false` if the index is out of range)
+    pub fn is_bypassed(&self, index: usize) -> bool {
+        self.inserts.get(index).is_some_and(|slot| slot.bypassed)
+    }
+
+    /// Number of effects in the insert list
+    pub fn insert_count(&self) -> usize {
+        self.inserts.len()
+    }
+
+    /// All inserts in processing order
+    pub fn inserts(&self) -> &[EffectSlot] {
+        &self.inserts
+    }
+
+    /// Mutable access to the inserts, e.g. to change an effect's parameters
+    pub fn inserts_mut(&mut self) -> &mut [EffectSlot] {
+        &mut self.inserts
+    }
+
+    /// Get an insert's effect by index
+    pub fn effect(&self, index: usize) -> Option<&dyn Effect> {
+        self.inserts.get(index).map(|slot| slot.effect.as_ref())
+    }
+
+    /// Get mutable access to an insert's effect by index
+    pub fn effect_mut(&mut self, index: usize) -> Option<&mut (dyn Effect + 'static)> {
+        self.inserts.get_mut(index).map(|slot| slot.effect.as_mut())
+    }
+
+    /// Find an effect by [`name`](Effect::name): the built-in slots first, then the inserts in order
+    pub fn effect_named_mut(&mut self, name: &str) -> Option<&mut dyn Effect> {
+        let location = self.locate(name)?;
+        self.effect_at_mut(location)
+    }
+
+    /// Where the effect [`effect_named_mut`](Self::effect_named_mut) would find sits
+    pub(crate) fn locate(&mut self, name: &str) -> Option<EffectLocation> {
+        let builtin = self
+            .builtin_effects_mut()
+            .iter()
+            .position(|effect| effect.as_ref().is_some_and(|effect| effect.name() == name));
+        match builtin {
+            Some(slot) => Some(EffectLocation::Builtin(slot)),
+            None => self
+                .inserts
+                .iter()
+                .position(|slot| slot.name() == name)
+                .map(EffectLocation::Insert),
+        }
+    }
+
+    /// Get the effect at a location found with [`locate`](Self::locate)
+    pub(crate) fn effect_at_mut(&mut self, location: EffectLocation) -> Option<&mut dyn Effect> {
+        match location {
+            EffectLocation::Builtin(slot) => {
+                self.builtin_effects_mut().into_iter().nth(slot).flatten()
+            }
+            EffectLocation::Insert(index) => self
+                .inserts
+                .get_mut(index)
+                .map(|slot| slot.effect.as_mut() as &mut dyn Effect),
+        }
+    }
+
+    /// Iterate over every active effect in the chain, built-in slots and inserts
+    fn active_effects(&self) -> impl Iterator<Item = &dyn Effect> {
+        let builtins = self.builtin_effects();
+        builtins.into_iter().flatten().chain(
+            self.inserts
+                .iter()
+                .filter(|slot| !slot.bypassed)
+                .map(|slot| slot.effect.as_ref()),
+        )
+    }
+
+    /// Longest tail of any active effect in the chain, in seconds
+    pub fn tail_length(&self) -> f32 {
+        self.active_effects()
+            .map(|effect| effect.tail_length())
+            .fold(0.0, f32::max)
+    }
+
+    /// Whether any effect in the chain keeps sounding after its input stops
+    ///
+    /// Used by the mixer to keep processing tracks past their last event. Effects
+    /// with latency count too, since their delayed output is still in flight.
+    #[inline]
+    pub(crate) fn has_tail(&self) -> bool {
+        self.active_effects()
+            .any(|effect| effect.tail_length() > 0.0 || effect.latency() > 0)
+    }
+
+    /// Total processing latency of the chain in samples
+    pub fn latency(&self) -> usize {
+        self.active_effects().map(|effect| effect.latency()).sum()
+    }
+
+    /// Prepare every effect in the chain for rendering
+    ///
+    /// Resizes sample-rate-dependent buffers (delay lines, reverb combs) and
+    /// pre-allocates scratch space for blocks of up to `max_block_size` frames.
+    /// The mixer calls this before rendering.
+    pub fn prepare(&mut self, sample_rate: f32, max_block_size: usize) {
+        for effect in self.builtin_effects_mut().into_iter().flatten() {
+            effect.prepare(sample_rate, max_block_size);
+        }
+        for slot in &mut self.inserts {
+            slot.effect.prepare(sample_rate, max_block_size);
+        }
+        if !self.inserts.is_empty() {
+            self.scratch_left.reserve(max_block_size);
+            self.scratch_right.reserve(max_block_size);
+        }
+    }
+
+    /// Clear the internal state of every effect in the chain
+    pub fn reset(&mut self) {
+        for effect in self.builtin_effects_mut().into_iter().flatten() {
+            effect.reset();
+        }
+        for slot in &mut self.inserts {
+            slot.effect.reset();
+        }
+    }
+
+    /// Snapshot the parameters of every built-in slot and insert
+    pub(crate) fn capture_parameters(&self) -> ChainParameters {
+        let autopan = self.autopan.as_ref().map(|e| e as &dyn Effect);
+        let builtins = self
+            .builtin_effects()
+            .into_iter()
+            .chain(std::iter::once(autopan))
+            .enumerate()
+            .filter_map(|(slot, effect)| effect.map(|effect| (slot, effect.parameters())))
+            .collect();
+        let inserts = self
+            .inserts
+            .iter()
+            .map(|slot| (slot.bypassed, slot.effect.parameters()))
+            .collect();
+        ChainParameters { builtins, inserts }
+    }
+
+    /// Apply a parameter snapshot taken with [`capture_parameters`](Self::capture_parameters)
+    ///
+    /// Slots that are empty in this chain, and inserts beyond the end of the list,
+    /// are skipped.
+    pub(crate) fn apply_parameters(&mut self, parameters: &ChainParameters) {
+        let mut builtins = self.builtin_effects_mut();
+        for (slot, values) in &parameters.builtins {
+            if let Some(Some(effect)) = builtins.get_mut(*slot) {
+                for &(name, value) in values {
+                    effect.set_parameter(name, value);
+                }
             }
         }
+        for (slot, (bypassed, values)) in self.inserts.iter_mut().zip(&parameters.inserts) {
+            slot.bypassed = *bypassed;
+            for &(name, value) in values {
+                slot.effect.set_parameter(name, value);
+            }
+        }
+    }
+
+    /// Mutable access to every built-in slot (in effect ID order, AutoPan last)
+    fn builtin_effects_mut(&mut self) -> [Option<&mut dyn Effect>; 17] {
+        [
+            self.eq.as_mut().map(|e| e as &mut dyn Effect),
+            self.compressor.as_mut().map(|e| e as &mut dyn Effect),
+            self.gate.as_mut().map(|e| e as &mut dyn Effect),
+            self.saturation.as_mut().map(|e| e as &mut dyn Effect),
+            self.bitcrusher.as_mut().map(|e| e as &mut dyn Effect),
+            self.distortion.as_mut().map(|e| e as &mut dyn Effect),
+            self.chorus.as_mut().map(|e| e as &mut dyn Effect),
+            self.phaser.as_mut().map(|e| e as &mut dyn Effect),
+            self.flanger.as_mut().map(|e| e as &mut dyn Effect),
+            self.ring_mod.as_mut().map(|e| e as &mut dyn Effect),
+            self.tremolo.as_mut().map(|e| e as &mut dyn Effect),
+            self.delay.as_mut().map(|e| e as &mut dyn Effect),
+            self.reverb.as_mut().map(|e| e as &mut dyn Effect),
+            self.limiter.as_mut().map(|e| e as &mut dyn Effect),
+            self.parametric_eq.as_mut().map(|e| e as &mut dyn Effect),
+            self.convolution_reverb.as_mut().map(|e| e as &mut dyn Effect),
+            self.autopan.as_mut().map(|e| e as &mut dyn Effect),
+        ]
+    }
+
+    /// Shared access to every built-in slot (in effect ID order, AutoPan excluded)
+    fn builtin_effects(&self) -> [Option<&dyn Effect>; 16] {
+        [
+            self.eq.as_ref().map(|e| e as &dyn Effect),
+            self.compressor.as_ref().map(|e| e as &dyn Effect),
+            self.gate.as_ref().map(|e| e as &dyn Effect),
+            self.saturation.as_ref().map(|e| e as &dyn Effect),
+            self.bitcrusher.as_ref().map(|e| e as &dyn Effect),
+            self.distortion.as_ref().map(|e| e as &dyn Effect),
+            self.chorus.as_ref().map(|e| e as &dyn Effect),
+            self.phaser.as_ref().map(|e| e as &dyn Effect),
+            self.flanger.as_ref().map(|e| e as &dyn Effect),
+            self.ring_mod.as_ref().map(|e| e as &dyn Effect),
+            self.tremolo.as_ref().map(|e| e as &dyn Effect),
+            self.delay.as_ref().map(|e| e as &dyn Effect),
+            self.reverb.as_ref().map(|e| e as &dyn Effect),
+            self.limiter.as_ref().map(|e| e as &dyn Effect),
+            self.parametric_eq.as_ref().map(|e| e as &dyn Effect),
+            self.convolution_reverb.as_ref().map(|e| e as &dyn Effect),
+        ]
     }
 
     /// Add EQ effect (builder pattern)
@@ -750,6 +1295,93 @@ impl EffectChain {
     }
 }
 
+/// Where an effect sits in an [`EffectChain`]
+///
+/// Built-in slots are keyed by effect ID (AutoPan is slot 16); inserts by list position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum EffectLocation {
+    Builtin(usize),
+    Insert(usize),
+}
+
+/// Snapshot of the effect parameters in an [`EffectChain`]
+///
+/// Built-in slots are keyed by effect ID (AutoPan is slot 16); inserts by list position.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ChainParameters {
+    builtins: Vec<(usize, Vec<(&'static str, f32)>)>,
+    inserts: Vec<(bool, Vec<(&'static str, f32)>)>,
+}
+
+impl ChainParameters {
+    /// Replace one parameter of the effect at `location`, if the snapshot has it
+    pub(crate) fn set(&mut self, location: EffectLocation, parameter: &str, value: f32) {
+        let values = match location {
+            EffectLocation::Builtin(slot) => self
+                .builtins
+                .iter_mut()
+                .find(|(candidate, _)| *candidate == slot)
+                .map(|(_, values)| values),
+            EffectLocation::Insert(index) => {
+                self.inserts.get_mut(index).map(|(_, values)| values)
+            }
+        };
+        if let Some(entry) = values
+            .into_iter()
+            .flatten()
+            .find(|(name, _)| *name == parameter)
+        {
+            entry.1 = value;
+        }
+    }
+
+    /// Blend towards `target` by `amount` (0.0 = self, 1.0 = target), writing into `out`
+    ///
+    /// Parameters are interpolated linearly by name. Parameters that only exist in
+    /// `target` take the target value, and insert bypass switches once `amount`
+    /// reaches 1.0. `out` must have the layout of `target` (a clone of it, or an
+    /// earlier result against it), so the blend never allocates.
+    pub(crate) fn interpolate_into(
+        &self,
+        target: &ChainParameters,
+        amount: f32,
+        out: &mut ChainParameters,
+    ) {
+        for ((slot, values), (_, out_values)) in target.builtins.iter().zip(&mut out.builtins) {
+            let from = self
+                .builtins
+                .iter()
+                .find(|(other, _)| other == slot)
+                .map(|(_, values)| values.as_slice());
+            interpolate_values(from, values, amount, out_values);
+        }
+        for (index, ((bypassed, values), (out_bypassed, out_values))) in
+            target.inserts.iter().zip(&mut out.inserts).enumerate()
+        {
+            let from = self.inserts.get(index);
+            *out_bypassed = match from {
+                Some((from_bypassed, _)) if amount < 1.0 => *from_bypassed,
+                _ => *bypassed,
+            };
+            interpolate_values(from.map(|(_, v)| v.as_slice()), values, amount, out_values);
+        }
+    }
+}
+
+fn interpolate_values(
+    from: Option<&[(&'static str, f32)]>,
+    to: &[(&'static str, f32)],
+    amount: f32,
+    out: &mut [(&'static str, f32)],
+) {
+    for (&(name, target), (_, value)) in to.iter().zip(out) {
+        let start = from
+            .and_then(|values| values.iter().find(|(other, _)| *other == name))
+            .map_or(target, |&(_, value)| value);
+        *value = start + (target - start) * amount;
+    }
+}
+
 impl Default for EffectChain {
     fn default() -> Self {
         Self::new()
@@ -850,4 +1482,380 @@ mod tests {
         // but reset() should clear internal filter state
         eq.reset();
     }
+
+    /// Test effect that multiplies every sample by a constant
+    #[derive(Debug, Clone)]
+    struct Gain(f32);
+
+    impl Effect for Gain {
+        fn process_block(&mut self, buffer: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {
+            for sample in buffer.iter_mut() {
+                *sample *= self.0;
+            }
+        }
+
+        fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {
+            for sample in left.iter_mut().chain(right.iter_mut()) {
+                *sample *= self.0;
+            }
+        }
+    }
+
+    /// Test effect that adds a constant to every sample (order-sensitive with Gain)
+    #[derive(Debug, Clone)]
+    struct Offset(f32);
+
+    impl Effect for Offset {
+        fn process_block(&mut self, buffer: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {
+            for sample in buffer.iter_mut() {
+                *sample += self.0;
+            }
+        }
+
+        fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {
+            for sample in left.iter_mut().chain(right.iter_mut()) {
+                *sample += self.0;
+            }
+        }
+
+        fn tail_length(&self) -> f32 {
+            0.5
+        }
+
+        fn latency(&self) -> usize {
+            64
+        }
+    }
+
+    #[test]
+    fn test_inserts_process_in_list_order() {
+        let mut chain = EffectChain::new().with_effect(Gain(2.0)).with_effect(Offset(1.0));
+        assert_eq!(chain.process_mono(1.0, 44100.0, 0.0, 0), 3.0);
+
+        // Swap the order: (1 + 1) * 2
+        assert!(chain.move_effect(1, 0));
+        assert_eq!(chain.process_mono(1.0, 44100.0, 0.0, 0), 4.0);
+    }
+
+    #[test]
+    fn test_insert_bypass() {
+        let mut chain = EffectChain::new().with_effect(Gain(2.0)).with_effect(Offset(1.0));
+        assert!(chain.set_bypass(0, true));
+        assert!(chain.is_bypassed(0));
+        assert_eq!(chain.process_mono(1.0, 44100.0, 0.0, 0), 2.0);
+
+        chain.set_bypass(0, false);
+        assert_eq!(chain.process_mono(1.0, 44100.0, 0.0, 0), 3.0);
+
+        // Out-of-range indices are rejected
+        assert!(!chain.set_bypass(5, true));
+        assert!(!chain.is_bypassed(5));
+    }
+
+    #[test]
+    fn test_insert_and_remove_effects() {
+        let mut chain = EffectChain::new().with_effect(Gain(2.0));
+        chain.insert_effect(0, Offset(1.0));
+        assert_eq!(chain.insert_count(), 2);
+        assert_eq!(chain.inserts()[0].name(), "Offset");
+        assert_eq!(chain.inserts()[1].name(), "Gain");
+
+        let removed = chain.remove_effect(0).unwrap();
+        assert_eq!(removed.name(), "Offset");
+        assert_eq!(chain.insert_count(), 1);
+        assert!(chain.remove_effect(3).is_none());
+        assert!(!chain.move_effect(0, 3));
+    }
+
+    #[test]
+    fn test_multiple_delays_in_one_chain() {
+        let mut chain = EffectChain::new()
+            .with_effect(Delay::new(0.01, 0.0, 0.5))
+            .with_effect(Delay::new(0.02, 0.0, 0.5));
+        assert_eq!(chain.insert_count(), 2);
+
+        let mut buffer = vec![0.0; 2048];
+        buffer[0] = 1.0;
+        chain.process_mono_block(&mut buffer, 44100.0, 0.0, 0);
+
+        // Echoes from each delay and from the delays in series
+        assert!(buffer[441].abs() > 0.0);
+        assert!(buffer[882].abs() > 0.0);
+        assert!(buffer[1323].abs() > 0.0);
+    }
+
+    #[test]
+    fn test_inserts_block_matches_per_sample() {
+        let mut block_chain = EffectChain::new().with_effect(Delay::new(0.001, 0.5, 0.5));
+        let mut sample_chain = block_chain.clone();
+
+        let input: Vec<f32> = (0..256).map(|i| (i as f32 * 0.1).sin()).collect();
+        let mut block = input.clone();
+        block_chain.process_mono_block(&mut block, 44100.0, 0.0, 0);
+
+        for (i, &x) in input.iter().enumerate() {
+            let out = sample_chain.process_mono(x, 44100.0, i as f32 / 44100.0, i as u64);
+            assert!((out - block[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_inserts_stereo_block() {
+        let mut chain = EffectChain::new().with_effect(Gain(0.5));
+        let mut buffer = vec![1.0, -1.0, 0.5, -0.5];
+        chain.process_stereo_block(&mut buffer, 44100.0, 0.0, 0, None);
+        assert_eq!(buffer, vec![0.5, -0.5, 0.25, -0.25]);
+    }
+
+    #[test]
+    fn test_insert_priority_places_it_among_builtins() {
+        use crate::track::{PRIORITY_FIRST, PRIORITY_LAST};
+
+        let mut distortion = Distortion::new(4.0, 1.0);
+        let offset_first = distortion.clone().process(1.5, 0.0, 0);
+        let distorted = distortion.process(0.5, 0.0, 0);
+        let offset_last = distorted + 1.0;
+        assert!((offset_first - offset_last).abs() > 0.1);
+
+        let mut chain = EffectChain::new()
+            .with_distortion(Distortion::new(4.0, 1.0))
+            .with_effect(Offset(1.0));
+        assert_eq!(chain.process_mono(0.5, 44100.0, 0.0, 0), offset_last);
+
+        assert!(chain.set_priority(0, PRIORITY_FIRST));
+        assert_eq!(chain.process_mono(0.5, 44100.0, 0.0, 0), offset_first);
+        let (left, right) = chain.process_stereo(0.5, 0.5, 44100.0, 0.0, 0, None);
+        assert_eq!((left, right), (offset_first, offset_first));
+
+        // The block paths use an approximate tanh
+        let mut block = [0.5; 4];
+        chain.process_mono_block(&mut block, 44100.0, 0.0, 0);
+        assert!(block.iter().all(|&x| (x - offset_first).abs() < 1e-3));
+        let mut stereo = [0.5; 4];
+        chain.process_stereo_block(&mut stereo, 44100.0, 0.0, 0, None);
+        assert!(stereo.iter().all(|&x| (x - offset_first).abs() < 1e-3));
+
+        // A slot added with a priority lands in the same place
+        let mut chain = EffectChain::new()
+            .with_distortion(Distortion::new(4.0, 1.0))
+            .with_slot(EffectSlot::new(Offset(1.0)).with_priority(PRIORITY_FIRST));
+        assert_eq!(chain.process_mono(0.5, 44100.0, 0.0, 0), offset_first);
+
+        // Removing an insert keeps the order consistent
+        chain.add_effect(Gain(2.0));
+        chain.remove_effect(0);
+        assert!(!chain.set_priority(1, PRIORITY_LAST));
+        assert_eq!(chain.process_mono(0.5, 44100.0, 0.0, 0), distorted * 2.0);
+    }
+
+    /// Chain of built-in effects with automation changing mid-block
+    fn automated_builtin_chain() -> EffectChain {
+        use crate::synthesis::automation::Automation;
+
+        EffectChain::new()
+            .with_eq(
+                EQ::new(1.5, 1.0, 0.8, 250.0, 4000.0)
+                    .with_low_gain_automation(Automation::linear(&[(0.0, 0.5), (0.01, 2.0)])),
+            )
+            .with_compressor(Compressor::new(0.3, 4.0, 0.001, 0.05, 1.2))
+            .with_saturation(Saturation::new(2.0, 0.5, 0.7))
+            .with_distortion(
+                Distortion::new(2.0, 0.5)
+                    .with_drive_automation(Automation::linear(&[(0.0, 1.0), (0.01, 8.0)])),
+            )
+            .with_phaser(Phaser::new(2.0, 0.7, 0.4, 4, 0.5))
+            .with_tremolo(Tremolo::new(5.0, 0.5))
+            .with_delay(
+                Delay::new(0.002, 0.4, 0.3)
+                    .with_mix_automation(Automation::linear(&[(0.0, 0.0), (0.01, 0.6)])),
+            )
+            .with_reverb(Reverb::new(0.5, 0.5, 0.3))
+            .with_limiter(Limiter::new(0.8, 0.05))
+    }
+
+    #[test]
+    fn test_builtin_block_matches_per_sample() {
+        let sample_rate = 44100.0;
+        let start = 40; // Not aligned to the automation interval
+        let mut block_chain = automated_builtin_chain();
+        let mut sample_chain = automated_builtin_chain();
+
+        let input: Vec<f32> = (0..1000).map(|i| (i as f32 * 0.05).sin() * 0.8).collect();
+        let mut block = input.clone();
+
+        // Uneven block sizes so segments straddle block boundaries
+        let mut offset = 0;
+        for len in [100, 37, 300, 563] {
+            let sample_count = start + offset as u64;
+            block_chain.process_mono_block(
+                &mut block[offset..offset + len],
+                sample_rate,
+                sample_count as f32 / sample_rate,
+                sample_count,
+            );
+            offset += len;
+        }
+
+        for (i, &x) in input.iter().enumerate() {
+            let sample_count = start + i as u64;
+            let out = sample_chain.process_mono(x, sample_rate, sample_count as f32 / sample_rate, sample_count);
+            assert!((out - block[i]).abs() < 1e-3, "sample {}: {} vs {}", i, out, block[i]);
+        }
+    }
+
+    #[test]
+    fn test_builtin_stereo_block_matches_per_sample() {
+        let sample_rate = 44100.0;
+        let mut block_chain = automated_builtin_chain();
+        let mut sample_chain = automated_builtin_chain();
+
+        let input: Vec<f32> = (0..1200)
+            .map(|i| ((i / 2) as f32 * if i % 2 == 0 { 0.05 } else { 0.07 }).sin() * 0.8)
+            .collect();
+        let mut block = input.clone();
+        block_chain.process_stereo_block(&mut block, sample_rate, 0.0, 0, None);
+
+        for (i, frame) in input.chunks(2).enumerate() {
+            let (left, right) = sample_chain.process_stereo(
+                frame[0],
+                frame[1],
+                sample_rate,
+                i as f32 / sample_rate,
+                i as u64,
+                None,
+            );
+            assert!((left - block[i * 2]).abs() < 1e-3);
+            assert!((right - block[i * 2 + 1]).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_prepare_resizes_delay_for_sample_rate() {
+        let mut delay = Delay::new(0.01, 0.0, 1.0);
+        Effect::prepare(&mut delay, 96000.0, 512);
+
+        let mut buffer = vec![0.0; 1024];
+        buffer[0] = 1.0;
+        delay.process_block(&mut buffer, 0.0, 0, 96000.0);
+
+        // 10 ms at 96 kHz, not 441 samples
+        assert_eq!(buffer[960], 1.0);
+        assert_eq!(buffer[441], 0.0);
+    }
+
+    #[test]
+    fn test_delay_time_parameter_moves_the_echo() {
+        let mut delay = Delay::new(0.01, 0.0, 1.0);
+        Effect::prepare(&mut delay, 44100.0, 2048);
+        let mut buffer = vec![0.0; 2048];
+        buffer[0] = 1.0;
+        Effect::set_parameter(&mut delay, "delay_time", 0.02);
+        delay.process_block(&mut buffer, 0.0, 0, 44100.0);
+        assert_eq!(buffer[882], 1.0);
+        assert_eq!(buffer[441], 0.0);
+
+        // Shortening it again reads the same line nearer the write position
+        let mut buffer = vec![0.0; 2048];
+        buffer[0] = 1.0;
+        Effect::set_parameter(&mut delay, "delay_time", 0.005);
+        delay.process_block(&mut buffer, 0.0, 2048, 44100.0);
+        assert_eq!(buffer[220], 1.0);
+    }
+
+    #[test]
+    fn test_lengthened_delay_keeps_its_echoes() {
+        let mut delay = Delay::new(0.01, 0.0, 1.0);
+        Effect::prepare(&mut delay, 44100.0, 1024);
+        let mut buffer = vec![0.0; 100];
+        buffer[0] = 1.0;
+        delay.process_block(&mut buffer, 0.0, 0, 44100.0);
+
+        // The impulse 100 samples in the past now comes out 882 samples after it went in
+        Effect::set_parameter(&mut delay, "delay_time", 0.02);
+        let mut buffer = vec![0.0; 1024];
+        delay.process_block(&mut buffer, 0.0, 100, 44100.0);
+        assert_eq!(buffer[782], 1.0);
+        assert_eq!(buffer.iter().filter(|s| **s != 0.0).count(), 1);
+    }
+
+    #[test]
+    fn test_prepare_keeps_state_at_same_sample_rate() {
+        let mut reverb = Reverb::new(0.5, 0.5, 0.5);
+        let mut input = vec![0.0; 256];
+        input[0] = 1.0;
+        reverb.process_block(&mut input, 0.0, 0, 44100.0);
+
+        // Preparing at the construction rate leaves the tail intact
+        let mut prepared = reverb.clone();
+        prepared.set_sample_rate(44100.0);
+        let mut expected = vec![0.0; 4096];
+        let mut actual = vec![0.0; 4096];
+        reverb.process_block(&mut expected, 0.0, 256, 44100.0);
+        prepared.process_block(&mut actual, 0.0, 256, 44100.0);
+        assert_eq!(expected, actual);
+        assert!(actual.iter().any(|&s| s != 0.0));
+    }
+
+    #[test]
+    fn test_chain_prepare_reaches_inserts() {
+        let mut chain = EffectChain::new()
+            .with_delay(Delay::new(0.01, 0.0, 1.0))
+            .with_effect(Delay::new(0.01, 0.0, 1.0));
+        chain.prepare(48000.0, 256);
+
+        let mut buffer = vec![0.0; 1024];
+        buffer[0] = 1.0;
+        chain.process_mono_block(&mut buffer, 48000.0, 0.0, 0);
+
+        // Both delays are 480 samples long, so the impulse arrives after 960
+        assert_eq!(buffer[960], 1.0);
+    }
+
+    #[test]
+    fn test_chain_tail_and_latency() {
+        let mut chain = EffectChain::new();
+        assert_eq!(chain.tail_length(), 0.0);
+        assert_eq!(chain.latency(), 0);
+        assert!(!chain.has_tail());
+
+        chain.add_effect(Offset(0.0));
+        assert_eq!(chain.tail_length(), 0.5);
+        assert_eq!(chain.latency(), 64);
+        assert!(chain.has_tail());
+
+        // Bypassed inserts don't contribute
+        chain.set_bypass(0, true);
+        assert_eq!(chain.latency(), 0);
+        assert!(!chain.has_tail());
+
+        // Built-in delay has a tail
+        let chain = EffectChain::new().with_delay(Delay::new(0.25, 0.5, 0.3));
+        assert!(chain.tail_length() > 0.25);
+        assert!(chain.has_tail());
+
+        // Built-ins without a tail or latency don't keep a track running
+        let chain = EffectChain::new().with_eq(EQ::new(1.5, 1.0, 0.5, 300.0, 3000.0));
+        assert!(!chain.has_tail());
+    }
+
+    #[test]
+    fn test_chain_clone_is_independent() {
+        let chain = EffectChain::new().with_effect(Gain(2.0));
+        let mut cloned = chain.clone();
+        cloned.set_bypass(0, true);
+        assert!(!chain.is_bypassed(0));
+        assert!(cloned.is_bypassed(0));
+    }
+
+    #[test]
+    fn test_chain_reset_clears_inserts() {
+        let mut chain = EffectChain::new().with_effect(Delay::new(0.001, 0.5, 1.0));
+        let mut buffer = vec![1.0; 64];
+        chain.process_mono_block(&mut buffer, 44100.0, 0.0, 0);
+
+        chain.reset();
+        let mut silence = vec![0.0; 64];
+        chain.process_mono_block(&mut silence, 44100.0, 0.0, 0);
+        assert!(silence.iter().all(|&s| s == 0.0));
+    }
 }
@@ -1,4 +1,7 @@
+use super::block;
+use super::effect::{process_stereo_as_stream, Effect};
 use crate::synthesis::automation::Automation;
+use crate::synthesis::simd::SimdLanes;
 use crate::track::PRIORITY_MODULATION;
 
 /// Standard audio sample rate
@@ -32,6 +35,7 @@ pub struct Chorus {
     buffer: Vec<f32>,
     write_pos: usize,
     lfo_phase: f32,
+    sample_rate: f32, // Sample rate the delay line is sized for
 
     // Automation (optional)
     rate_automation: Option<Automation>,
@@ -64,6 +68,7 @@ impl Chorus {
             buffer: vec![0.0; buffer_size],
             write_pos: 0,
             lfo_phase: 0.0,
+            sample_rate,
             rate_automation: None,
             depth_automation: None,
             mix_automation: None,
@@ -94,6 +99,20 @@ impl Chorus {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.rate_automation {
+            self.rate = auto.value_at(time).clamp(0.1, 10.0);
+        }
+        if let Some(auto) = &self.depth_automation {
+            self.depth = auto.value_at(time).clamp(0.5, 50.0);
+        }
+    }
+
     /// Process a single sample at given sample rate
     ///
     /// # Arguments
@@ -106,15 +125,7 @@ impl Chorus {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         // Use bitwise AND instead of modulo for power-of-2
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.rate_automation {
-                self.rate = auto.value_at(time).clamp(0.1, 10.0);
-            }
-            if let Some(auto) = &self.depth_automation {
-                self.depth = auto.value_at(time).clamp(0.5, 50.0);
-            }
+            self.update_automation(time);
         }
 
         if self.mix < 0.0001 {
@@ -148,6 +159,8 @@ impl Chorus {
 
     /// Process a block of samples
     ///
+    /// The LFO for each 64-sample segment is rendered with SIMD before the delay line runs.
+    ///
     /// # Arguments
     /// * `buffer` - Buffer of samples to process in-place
     /// * `sample_rate` - Sample rate in Hz
@@ -155,11 +168,60 @@ impl Chorus {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, sample_rate, current_time, current_sample_count);
+        let mut lfo_buffer = [0.0f32; block::MAX_SEGMENT_SAMPLES];
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            if self.mix < 0.0001 {
+                continue;
+            }
+
+            let samples = &mut buffer[segment.samples(channels)];
+            let lfo_source = block::SineLfo {
+                phase: self.lfo_phase,
+                increment: self.rate / sample_rate,
+            };
+            let lfo = &mut lfo_buffer[..samples.len()];
+            lfo_source.fill(lfo);
+
+            let depth = self.depth;
+            let mix = self.mix;
+            let dry = 1.0 - mix;
+            let buffer_len = self.buffer.len();
+            let mut write_pos = self.write_pos;
+
+            for (sample, &lfo) in samples.iter_mut().zip(lfo.iter()) {
+                let input = *sample;
+                self.buffer[write_pos] = input;
+
+                let delay_ms = depth * (0.5 + 0.5 * lfo);
+                let delay_samples = ((delay_ms * sample_rate / 1000.0) as usize).min(buffer_len - 1);
+                let read_pos = (write_pos + buffer_len - delay_samples) % buffer_len;
+                let delayed = self.buffer[read_pos];
+
+                write_pos += 1;
+                if write_pos == buffer_len {
+                    write_pos = 0;
+                }
+                *sample = input.mul_add(dry, delayed * mix);
+            }
+
+            self.write_pos = write_pos;
+            self.lfo_phase = lfo_source.phase_after(samples.len());
         }
     }
 
@@ -170,6 +232,20 @@ impl Chorus {
         self.lfo_phase = 0.0;
     }
 
+    /// Resize the delay line for a new sample rate
+    ///
+    /// The buffer is cleared if the rate changes; calling this with the current
+    /// sample rate does nothing.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate == self.sample_rate {
+            return;
+        }
+        let max_delay_samples = ((self.depth * 2.0) * sample_rate / 1000.0) as usize;
+        self.buffer = vec![0.0; max_delay_samples.max(1)];
+        self.write_pos = 0;
+        self.sample_rate = sample_rate;
+    }
+
     // ========== PRESETS ==========
 
     /// Subtle chorus - gentle thickening
@@ -198,6 +274,37 @@ impl Chorus {
     }
 }
 
+impl Effect for Chorus {
+    fn prepare(&mut self, sample_rate: f32, _max_block_size: usize) {
+        self.set_sample_rate(sample_rate);
+    }
+
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Chorus::process_block(self, buffer, sample_rate, time, sample_count);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        Chorus::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("rate", self.rate), ("depth", self.depth), ("mix", self.mix)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "rate" => self.rate = value.clamp(0.1, 10.0),
+            "depth" => self.depth = value.clamp(0.5, 50.0),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
+
 /// Phaser - creates sweeping notches in the frequency spectrum
 #[derive(Debug, Clone)]
 pub struct Phaser {
@@ -289,6 +396,23 @@ impl Phaser {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.rate_automation {
+            self.rate = auto.value_at(time).clamp(0.1, 10.0);
+        }
+        if let Some(auto) = &self.depth_automation {
+            self.depth = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.feedback_automation {
+            self.feedback = auto.value_at(time).clamp(0.0, 0.95);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -300,18 +424,7 @@ impl Phaser {
     pub fn process(&mut self, input: f32, sample_rate: f32, time: f32, sample_count: u64) -> f32 {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.rate_automation {
-                self.rate = auto.value_at(time).clamp(0.1, 10.0);
-            }
-            if let Some(auto) = &self.depth_automation {
-                self.depth = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.feedback_automation {
-                self.feedback = auto.value_at(time).clamp(0.0, 0.95);
-            }
+            self.update_automation(time);
         }
 
         if self.mix < 0.0001 || self.depth < 0.0001 {
@@ -348,6 +461,9 @@ impl Phaser {
 
     /// Process a block of samples
     ///
+    /// The all-pass coefficients for each 64-sample segment are computed with SIMD,
+    /// then each all-pass stage runs over the whole segment in turn.
+    ///
     /// # Arguments
     /// * `buffer` - Buffer of samples to process in-place
     /// * `sample_rate` - Sample rate in Hz
@@ -355,11 +471,63 @@ impl Phaser {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, sample_rate, current_time, current_sample_count);
+        let mut lfo_buffer = [0.0f32; block::MAX_SEGMENT_SAMPLES];
+        let mut wet_buffer = [0.0f32; block::MAX_SEGMENT_SAMPLES];
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            if self.mix < 0.0001 || self.depth < 0.0001 {
+                continue;
+            }
+
+            let samples = &mut buffer[segment.samples(channels)];
+            let sweep = PhaserSweep {
+                lfo: block::SineLfo {
+                    phase: self.lfo_phase,
+                    increment: self.rate / sample_rate,
+                },
+                depth: self.depth,
+            };
+            let coefficients = &mut lfo_buffer[..samples.len()];
+            block::apply_kernel(coefficients, &sweep);
+
+            // Run each all-pass stage over the whole segment
+            let wet = &mut wet_buffer[..samples.len()];
+            wet.copy_from_slice(samples);
+            for filter in &mut self.allpass_states {
+                let mut z1 = filter.z1;
+                for (sample, &coefficient) in wet.iter_mut().zip(coefficients.iter()) {
+                    let output = -*sample + z1;
+                    z1 = *sample + coefficient * output;
+                    *sample = output;
+                }
+                filter.z1 = z1;
+            }
+
+            let feedback = self.feedback;
+            let mix = self.mix;
+            let dry = 1.0 - mix;
+            for (sample, &stages_out) in samples.iter_mut().zip(wet.iter()) {
+                let input = *sample;
+                let output = input + stages_out * feedback;
+                *sample = input.mul_add(dry, output * mix);
+            }
+
+            self.lfo_phase = sweep.lfo.phase_after(samples.len());
         }
     }
 
@@ -397,6 +565,39 @@ impl Phaser {
     }
 }
 
+impl Effect for Phaser {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Phaser::process_block(self, buffer, sample_rate, time, sample_count);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        Phaser::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("rate", self.rate),
+            ("depth", self.depth),
+            ("feedback", self.feedback),
+            ("mix", self.mix),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "rate" => self.rate = value.clamp(0.1, 10.0),
+            "depth" => self.depth = value.clamp(0.0, 1.0),
+            "feedback" => self.feedback = value.clamp(0.0, 0.95),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
+
 /// Flanger - creates jet-plane/swoosh effects with very short delays
 #[derive(Debug, Clone)]
 pub struct Flanger {
@@ -408,6 +609,7 @@ pub struct Flanger {
     buffer: Vec<f32>,
     write_pos: usize,
     lfo_phase: f32,
+    sample_rate: f32, // Sample rate the delay line is sized for
 
     // Automation (optional)
     rate_automation: Option<Automation>,
@@ -453,6 +655,7 @@ impl Flanger {
             buffer: vec![0.0; buffer_size],
             write_pos: 0,
             lfo_phase: 0.0,
+            sample_rate,
             rate_automation: None,
             depth_automation: None,
             feedback_automation: None,
@@ -490,6 +693,23 @@ impl Flanger {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.rate_automation {
+            self.rate = auto.value_at(time).clamp(0.1, 10.0);
+        }
+        if let Some(auto) = &self.depth_automation {
+            self.depth = auto.value_at(time).clamp(0.5, 50.0);
+        }
+        if let Some(auto) = &self.feedback_automation {
+            self.feedback = auto.value_at(time).clamp(0.0, 0.95);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -501,18 +721,7 @@ impl Flanger {
     pub fn process(&mut self, input: f32, sample_rate: f32, time: f32, sample_count: u64) -> f32 {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.rate_automation {
-                self.rate = auto.value_at(time).clamp(0.1, 10.0);
-            }
-            if let Some(auto) = &self.depth_automation {
-                self.depth = auto.value_at(time).clamp(0.5, 50.0);
-            }
-            if let Some(auto) = &self.feedback_automation {
-                self.feedback = auto.value_at(time).clamp(0.0, 0.95);
-            }
+            self.update_automation(time);
         }
 
         // Safety check: if buffer is empty, just pass through
@@ -552,6 +761,8 @@ impl Flanger {
 
     /// Process a block of samples
     ///
+    /// The LFO for each 64-sample segment is rendered with SIMD before the delay line runs.
+    ///
     /// # Arguments
     /// * `buffer` - Buffer of samples to process in-place
     /// * `sample_rate` - Sample rate in Hz
@@ -559,11 +770,65 @@ impl Flanger {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, sample_rate, current_time, current_sample_count);
+        let mut lfo_buffer = [0.0f32; block::MAX_SEGMENT_SAMPLES];
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            if self.buffer.is_empty() || self.mix < 0.0001 {
+                continue;
+            }
+
+            let samples = &mut buffer[segment.samples(channels)];
+            let lfo_source = block::SineLfo {
+                phase: self.lfo_phase,
+                increment: self.rate / sample_rate,
+            };
+            let lfo = &mut lfo_buffer[..samples.len()];
+            lfo_source.fill(lfo);
+
+            let depth = self.depth;
+            let feedback = self.feedback;
+            let mix = self.mix;
+            let dry = 1.0 - mix;
+            let buffer_len = self.buffer.len();
+            let mut write_pos = self.write_pos;
+
+            for (sample, &lfo) in samples.iter_mut().zip(lfo.iter()) {
+                let input = *sample;
+
+                let delay_ms = depth * (0.5 + 0.5 * lfo);
+                let delay_samples = ((delay_ms * sample_rate / 1000.0) as usize).min(buffer_len - 1);
+                let read_pos = if write_pos >= delay_samples {
+                    write_pos - delay_samples
+                } else {
+                    buffer_len - (delay_samples - write_pos)
+                };
+                let delayed = self.buffer[read_pos];
+                self.buffer[write_pos] = delayed.mul_add(feedback, input);
+
+                write_pos += 1;
+                if write_pos == buffer_len {
+                    write_pos = 0;
+                }
+                *sample = input.mul_add(dry, delayed * mix);
+            }
+
+            self.write_pos = write_pos;
+            self.lfo_phase = lfo_source.phase_after(samples.len());
         }
     }
 
@@ -574,6 +839,20 @@ impl Flanger {
         self.lfo_phase = 0.0;
     }
 
+    /// Resize the delay line for a new sample rate
+    ///
+    /// The buffer is cleared if the rate changes; calling this with the current
+    /// sample rate does nothing.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate == self.sample_rate {
+            return;
+        }
+        let max_delay_samples = ((self.depth * 2.0) * sample_rate / 1000.0) as usize;
+        self.buffer = vec![0.0; max_delay_samples.max(1)];
+        self.write_pos = 0;
+        self.sample_rate = sample_rate;
+    }
+
     // ========== PRESETS ==========
 
     /// Subtle flanger - gentle swoosh (0.5 Hz)
@@ -602,6 +881,43 @@ impl Flanger {
     }
 }
 
+impl Effect for Flanger {
+    fn prepare(&mut self, sample_rate: f32, _max_block_size: usize) {
+        self.set_sample_rate(sample_rate);
+    }
+
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Flanger::process_block(self, buffer, sample_rate, time, sample_count);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        Flanger::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![
+            ("rate", self.rate),
+            ("depth", self.depth),
+            ("feedback", self.feedback),
+            ("mix", self.mix),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "rate" => self.rate = value.clamp(0.1, 10.0),
+            "depth" => self.depth = value.clamp(0.5, 50.0),
+            "feedback" => self.feedback = value.clamp(0.0, 0.95),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
+
 /// Ring Modulator - creates metallic/robotic inharmonic tones
 #[derive(Debug, Clone)]
 pub struct RingModulator {
@@ -657,6 +973,17 @@ impl RingModulator {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.carrier_freq_automation {
+            self.carrier_freq = auto.value_at(time).clamp(20.0, 10000.0);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -668,12 +995,7 @@ impl RingModulator {
     pub fn process(&mut self, input: f32, sample_rate: f32, time: f32, sample_count: u64) -> f32 {
         // Quantized automation lookups (every 64 samples = 1.45ms @ 44.1kHz)
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.carrier_freq_automation {
-                self.carrier_freq = auto.value_at(time).clamp(20.0, 10000.0);
-            }
+            self.update_automation(time);
         }
 
         if self.mix < 0.0001 {
@@ -705,100 +1027,37 @@ impl RingModulator {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
-        use crate::synthesis::simd::{SimdWidth, SIMD};
-
-        // Update automation params if needed
-        if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.carrier_freq_automation {
-                self.carrier_freq = auto.value_at(time).clamp(20.0, 10000.0);
-            }
-        }
-
-        // Early exit if no effect
-        if self.mix < 0.0001 {
-            return;
-        }
-
-        // Dispatch to SIMD implementation
-        match SIMD.simd_width() {
-            SimdWidth::X8 => self.process_block_simd::<8>(buffer, sample_rate),
-            SimdWidth::X4 => self.process_block_simd::<4>(buffer, sample_rate),
-            SimdWidth::Scalar => self.process_block_scalar(buffer, sample_rate),
-        }
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
     }
 
-    /// SIMD implementation - processes N samples at once
-    #[inline(always)]
-    fn process_block_simd<const N: usize>(&mut self, buffer: &mut [f32], sample_rate: f32) {
-        let phase_increment = self.carrier_freq / sample_rate;
-        let mix = self.mix;
-        let one_minus_mix = 1.0 - mix;
-
-        let num_chunks = buffer.len() / N;
-        let remainder_start = num_chunks * N;
-
-        // Process N samples at a time
-        for chunk_idx in 0..num_chunks {
-            let chunk_start = chunk_idx * N;
-            let chunk = &mut buffer[chunk_start..chunk_start + N];
-
-            // Generate N carrier phases
-            let mut phases = [0.0f32; 8]; // Max size for N=8
-            for i in 0..N {
-                phases[i] = self.phase + (i as f32) * phase_increment;
-                if phases[i] >= 1.0 {
-                    phases[i] -= 1.0;
-                }
-            }
-
-            // Process N samples
-            for i in 0..N {
-                let carrier = crate::synthesis::wavetable::WAVETABLE.sample(phases[i]);
-                let input = chunk[i];
-                let modulated = input * carrier;
-                chunk[i] = input.mul_add(one_minus_mix, modulated * mix);
-            }
-
-            self.phase += (N as f32) * phase_increment;
-            if self.phase >= 1.0 {
-                self.phase -= 1.0;
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        let time_delta = 1.0 / sample_rate;
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
             }
-        }
-
-        // Handle remainder with scalar
-        for i in remainder_start..buffer.len() {
-            let carrier = crate::synthesis::wavetable::WAVETABLE.sample(self.phase);
-            let input = buffer[i];
-            let modulated = input * carrier;
-            buffer[i] = input.mul_add(one_minus_mix, modulated * mix);
-
-            self.phase += phase_increment;
-            if self.phase >= 1.0 {
-                self.phase -= 1.0;
+            if self.mix < 0.0001 {
+                continue;
             }
-        }
-    }
 
-    /// Scalar fallback
-    #[inline(always)]
-    fn process_block_scalar(&mut self, buffer: &mut [f32], sample_rate: f32) {
-        let phase_increment = self.carrier_freq / sample_rate;
-        let mix = self.mix;
-        let one_minus_mix = 1.0 - mix;
-
-        for sample in buffer.iter_mut() {
-            let carrier = crate::synthesis::wavetable::WAVETABLE.sample(self.phase);
-            let input = *sample;
-            let modulated = input * carrier;
-            *sample = input.mul_add(one_minus_mix, modulated * mix);
-
-            self.phase += phase_increment;
-            if self.phase >= 1.0 {
-                self.phase -= 1.0;
-            }
+            let samples = &mut buffer[segment.samples(channels)];
+            let kernel = RingModKernel {
+                carrier: block::SineLfo {
+                    phase: self.phase,
+                    increment: self.carrier_freq / sample_rate,
+                },
+                mix: self.mix,
+            };
+            block::apply_kernel(samples, &kernel);
+            self.phase = kernel.carrier.phase_after(samples.len());
         }
     }
 
@@ -840,6 +1099,32 @@ impl RingModulator {
     }
 }
 
+impl Effect for RingModulator {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        RingModulator::process_block(self, buffer, sample_rate, time, sample_count);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        RingModulator::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("carrier_freq", self.carrier_freq), ("mix", self.mix)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "carrier_freq" => self.carrier_freq = value.clamp(20.0, 10000.0),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
+
 /// Tremolo - rhythmic amplitude modulation
 ///
 /// Creates periodic volume changes, adding rhythmic movement to the signal.
@@ -900,6 +1185,17 @@ impl Tremolo {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.rate_automation {
+            self.rate = auto.value_at(time).max(0.01);
+        }
+        if let Some(auto) = &self.depth_automation {
+            self.depth = auto.value_at(time).clamp(0.0, 1.0);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -911,12 +1207,7 @@ impl Tremolo {
     pub fn process(&mut self, input: f32, sample_rate: f32, time: f32, sample_count: u64) -> f32 {
         // Quantized automation lookups (every 64 samples)
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.rate_automation {
-                self.rate = auto.value_at(time).max(0.01);
-            }
-            if let Some(auto) = &self.depth_automation {
-                self.depth = auto.value_at(time).clamp(0.0, 1.0);
-            }
+            self.update_automation(time);
         }
 
         // Early out if no modulation
@@ -949,99 +1240,37 @@ impl Tremolo {
     /// * `sample_count` - Starting sample counter (for quantized automation lookups)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
-        use crate::synthesis::simd::{SimdWidth, SIMD};
-
-        // Update automation params if needed
-        if sample_count & 63 == 0 {
-            if let Some(auto) = &self.rate_automation {
-                self.rate = auto.value_at(time).clamp(0.1, 100.0);
-            }
-            if let Some(auto) = &self.depth_automation {
-                self.depth = auto.value_at(time).clamp(0.0, 1.0);
-            }
-        }
-
-        // Early exit if no effect
-        if self.depth < 0.0001 {
-            return;
-        }
-
-        // Dispatch to SIMD implementation
-        match SIMD.simd_width() {
-            SimdWidth::X8 => self.process_block_simd::<8>(buffer, sample_rate),
-            SimdWidth::X4 => self.process_block_simd::<4>(buffer, sample_rate),
-            SimdWidth::Scalar => self.process_block_scalar(buffer, sample_rate),
-        }
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
     }
 
-    /// SIMD implementation - processes N samples at once
-    #[inline(always)]
-    fn process_block_simd<const N: usize>(&mut self, buffer: &mut [f32], sample_rate: f32) {
-        use std::f32::consts::PI;
-        let phase_increment = self.rate / sample_rate;
-        let depth = self.depth;
-        let two_pi = 2.0 * PI;
-
-        let num_chunks = buffer.len() / N;
-        let remainder_start = num_chunks * N;
-
-        // Process N samples at a time
-        for chunk_idx in 0..num_chunks {
-            let chunk_start = chunk_idx * N;
-            let chunk = &mut buffer[chunk_start..chunk_start + N];
-
-            // Generate N phases
-            let mut phases = [0.0f32; 8]; // Max size for N=8
-            for i in 0..N {
-                phases[i] = self.phase + (i as f32) * phase_increment;
-                if phases[i] >= 1.0 {
-                    phases[i] -= 1.0;
-                }
-            }
-
-            // Process N samples
-            for i in 0..N {
-                let lfo = (phases[i] * two_pi).sin();
-                let modulation = 1.0 - (depth * (1.0 - lfo) * 0.5);
-                chunk[i] *= modulation;
-            }
-
-            self.phase += (N as f32) * phase_increment;
-            if self.phase >= 1.0 {
-                self.phase -= 1.0;
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        let time_delta = 1.0 / sample_rate;
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
             }
-        }
-
-        // Handle remainder with scalar
-        for i in remainder_start..buffer.len() {
-            let lfo = (self.phase * two_pi).sin();
-            let modulation = 1.0 - (depth * (1.0 - lfo) * 0.5);
-            buffer[i] *= modulation;
-
-            self.phase += phase_increment;
-            if self.phase >= 1.0 {
-                self.phase -= 1.0;
+            if self.depth < 0.0001 {
+                continue;
             }
-        }
-    }
 
-    /// Scalar fallback
-    #[inline(always)]
-    fn process_block_scalar(&mut self, buffer: &mut [f32], sample_rate: f32) {
-        use std::f32::consts::PI;
-        let phase_increment = self.rate / sample_rate;
-        let depth = self.depth;
-        let two_pi = 2.0 * PI;
-
-        for sample in buffer.iter_mut() {
-            let lfo = (self.phase * two_pi).sin();
-            let modulation = 1.0 - (depth * (1.0 - lfo) * 0.5);
-            *sample *= modulation;
-
-            self.phase += phase_increment;
-            if self.phase >= 1.0 {
-                self.phase -= 1.0;
-            }
+            let samples = &mut buffer[segment.samples(channels)];
+            let kernel = TremoloKernel {
+                lfo: block::SineLfo {
+                    phase: self.phase,
+                    increment: self.rate / sample_rate,
+                },
+                depth: self.depth,
+            };
+            block::apply_kernel(samples, &kernel);
+            self.phase = kernel.lfo.phase_after(samples.len());
         }
     }
 
@@ -1077,3 +1306,79 @@ impl Tremolo {
         Self::new(12.0, 0.9)
     }
 }
+
+impl Effect for Tremolo {
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Tremolo::process_block(self, buffer, sample_rate, time, sample_count);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        Tremolo::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("rate", self.rate), ("depth", self.depth)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "rate" => self.rate = value.max(0.01),
+            "depth" => self.depth = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
+
+/// Maps the phaser LFO to all-pass coefficients for one segment
+struct PhaserSweep {
+    lfo: block::SineLfo,
+    depth: f32,
+}
+
+impl block::Kernel for PhaserSweep {
+    #[inline(always)]
+    fn apply<V: SimdLanes>(&self, _input: V, index: V) -> V {
+        // Same delay mapping as Phaser::process (0.5 to 5.0)
+        let lfo = self.lfo.at(index);
+        let delay = V::splat(0.5 * self.depth)
+            .mul(lfo)
+            .add(V::splat(0.5))
+            .mul_add(V::splat(4.5), V::splat(0.5));
+        let one = V::splat(1.0);
+        one.sub(delay).div(one.add(delay))
+    }
+}
+
+/// Vectorized sine-carrier ring modulation for one segment
+struct RingModKernel {
+    carrier: block::SineLfo,
+    mix: f32,
+}
+
+impl block::Kernel for RingModKernel {
+    #[inline(always)]
+    fn apply<V: SimdLanes>(&self, input: V, index: V) -> V {
+        let modulated = input.mul(self.carrier.at(index));
+        input.mul_add(V::splat(1.0 - self.mix), modulated.mul(V::splat(self.mix)))
+    }
+}
+
+/// Vectorized tremolo gain for one segment
+struct TremoloKernel {
+    lfo: block::SineLfo,
+    depth: f32,
+}
+
+impl block::Kernel for TremoloKernel {
+    #[inline(always)]
+    fn apply<V: SimdLanes>(&self, input: V, index: V) -> V {
+        let one = V::splat(1.0);
+        let lfo = self.lfo.at(index);
+        let modulation = one.sub(V::splat(self.depth * 0.5).mul(one.sub(lfo)));
+        input.mul(modulation)
+    }
+}
@@ -1,3 +1,5 @@
+use super::block;
+use super::effect::{process_stereo_as_stream, Effect};
 use crate::synthesis::automation::Automation;
 use crate::track::PRIORITY_SPATIAL;
 
@@ -14,6 +16,7 @@ pub struct Reverb {
     comb_buffers: Vec<Vec<f32>>,
     comb_positions: Vec<usize>,
     filter_state: Vec<f32>,
+    sample_rate: f32, // Sample rate the comb filters are sized for
 
     // Automation (optional)
     mix_automation: Option<Automation>,
@@ -29,17 +32,7 @@ impl Reverb {
 
     /// Create a new reverb effect with custom sample rate
     pub fn with_sample_rate(room_size: f32, damping: f32, mix: f32, sample_rate: f32) -> Self {
-        // Prime numbers for comb filter delays (in samples) - scaled by room size
-        let base_delays = [1557, 1617, 1491, 1422, 1277, 1356, 1188, 1116];
-        let scale = 1.0 + room_size * 2.0;
-
-        let comb_buffers: Vec<Vec<f32>> = base_delays
-            .iter()
-            .map(|&delay| {
-                let size = ((delay as f32 * scale * sample_rate) / 44100.0) as usize;
-                vec![0.0; size.max(1)]
-            })
-            .collect();
+        let comb_buffers = Self::comb_buffers(room_size, sample_rate);
 
         Self {
             room_size: room_size.clamp(0.0, 1.0),
@@ -49,12 +42,28 @@ impl Reverb {
             comb_positions: vec![0; comb_buffers.len()],
             filter_state: vec![0.0; comb_buffers.len()],
             comb_buffers,
+            sample_rate,
             mix_automation: None,
             room_size_automation: None,
             damping_automation: None,
         }
     }
 
+    /// Allocate comb filter delay lines for a room size and sample rate
+    fn comb_buffers(room_size: f32, sample_rate: f32) -> Vec<Vec<f32>> {
+        // Prime numbers for comb filter delays (in samples at 44.1 kHz) - scaled by room size
+        let base_delays = [1557, 1617, 1491, 1422, 1277, 1356, 1188, 1116];
+        let scale = 1.0 + room_size * 2.0;
+
+        base_delays
+            .iter()
+            .map(|&delay| {
+                let size = ((delay as f32 * scale * sample_rate) / 44100.0) as usize;
+                vec![0.0; size.max(1)]
+            })
+            .collect()
+    }
+
     /// Set the processing priority (lower = earlier in signal chain)
     pub fn with_priority(mut self, priority: u8) -> Self {
         self.priority = priority;
@@ -90,6 +99,20 @@ impl Reverb {
         self
     }
 
+    /// Re-evaluate automated parameters at the given time
+    #[inline]
+    fn update_automation(&mut self, time: f32) {
+        if let Some(auto) = &self.mix_automation {
+            self.mix = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.room_size_automation {
+            self.room_size = auto.value_at(time).clamp(0.0, 1.0);
+        }
+        if let Some(auto) = &self.damping_automation {
+            self.damping = auto.value_at(time).clamp(0.0, 1.0);
+        }
+    }
+
     /// Process a single sample
     ///
     /// # Arguments
@@ -102,15 +125,7 @@ impl Reverb {
         // This reduces automation overhead by 64x with no perceptible quality loss
         // Use bitwise AND instead of modulo for power-of-2
         if sample_count & 63 == 0 {
-            if let Some(auto) = &self.mix_automation {
-                self.mix = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.room_size_automation {
-                self.room_size = auto.value_at(time).clamp(0.0, 1.0);
-            }
-            if let Some(auto) = &self.damping_automation {
-                self.damping = auto.value_at(time).clamp(0.0, 1.0);
-            }
+            self.update_automation(time);
         }
 
         if self.mix < 0.0001 {
@@ -150,6 +165,9 @@ impl Reverb {
 
     /// Process a block of samples
     ///
+    /// Each comb filter runs over a whole 64-sample segment before the next one, and
+    /// the dry/wet mix is vectorized. Automation is evaluated once per segment.
+    ///
     /// # Arguments
     /// * `buffer` - Buffer of samples to process in-place
     /// * `time` - Starting time in seconds (for automation)
@@ -157,11 +175,69 @@ impl Reverb {
     /// * `sample_rate` - Sample rate in Hz (for time advancement)
     #[inline]
     pub fn process_block(&mut self, buffer: &mut [f32], time: f32, sample_count: u64, sample_rate: f32) {
+        self.process_interleaved(buffer, 1, sample_rate, time, sample_count);
+    }
+
+    /// Process a block of interleaved frames as a single sample stream
+    pub(crate) fn process_interleaved(
+        &mut self,
+        buffer: &mut [f32],
+        channels: usize,
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
         let time_delta = 1.0 / sample_rate;
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let current_time = time + (i as f32 * time_delta);
-            let current_sample_count = sample_count + i as u64;
-            *sample = self.process(*sample, current_time, current_sample_count);
+        let mut wet = [0.0f32; block::MAX_SEGMENT_SAMPLES];
+
+        for segment in block::segments(buffer.len() / channels, sample_count) {
+            if segment.update_automation {
+                self.update_automation(time + segment.start as f32 * time_delta);
+            }
+            if self.mix < 0.0001 {
+                continue;
+            }
+
+            let samples = &mut buffer[segment.samples(channels)];
+            let wet = &mut wet[..samples.len()];
+            wet.fill(0.0);
+
+            let feedback = self.room_size.mul_add(0.48, 0.5);
+            let damping = self.damping;
+            let inv_damping = 1.0 - damping;
+
+            // Run each comb over the whole segment, keeping its state in registers
+            for ((comb, position), filter_state) in self
+                .comb_buffers
+                .iter_mut()
+                .zip(self.comb_positions.iter_mut())
+                .zip(self.filter_state.iter_mut())
+            {
+                let comb_len = comb.len();
+                let mut pos = *position;
+                let mut state = *filter_state;
+
+                for (&input, output) in samples.iter().zip(wet.iter_mut()) {
+                    let delayed = comb[pos];
+                    state = delayed.mul_add(inv_damping, state * damping);
+                    comb[pos] = state.mul_add(feedback, input);
+                    pos += 1;
+                    if pos == comb_len {
+                        pos = 0;
+                    }
+                    *output += delayed;
+                }
+
+                *position = pos;
+                *filter_state = state;
+            }
+
+            // Average the combs, then mix
+            let num_combs = self.comb_buffers.len() as f32;
+            for output in wet.iter_mut() {
+                *output /= num_combs;
+            }
+            block::mix_dry_wet(samples, wet, self.mix);
         }
     }
 
@@ -174,6 +250,20 @@ impl Reverb {
         self.filter_state.fill(0.0);
     }
 
+    /// Resize the comb filters for a new sample rate
+    ///
+    /// Keeps the reverb's delay times in seconds constant. All state is cleared if
+    /// the rate changes; calling this with the current sample rate does nothing.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate == self.sample_rate {
+            return;
+        }
+        self.comb_buffers = Self::comb_buffers(self.room_size, sample_rate);
+        self.comb_positions.fill(0);
+        self.filter_state.fill(0.0);
+        self.sample_rate = sample_rate;
+    }
+
     // ========== PRESETS ==========
 
     /// Small room reverb - intimate, subtle space
@@ -216,3 +306,42 @@ impl Reverb {
         Self::new(0.4, 0.8, 0.3)
     }
 }
+
+impl Effect for Reverb {
+    fn prepare(&mut self, sample_rate: f32, _max_block_size: usize) {
+        self.set_sample_rate(sample_rate);
+    }
+
+    fn process_block(&mut self, buffer: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        Reverb::process_block(self, buffer, time, sample_count, sample_rate);
+    }
+
+    fn process_stereo_block(&mut self, left: &mut [f32], right: &mut [f32], sample_rate: f32, time: f32, sample_count: u64) {
+        process_stereo_as_stream(self, left, right, sample_rate, time, sample_count);
+    }
+
+    fn reset(&mut self) {
+        Reverb::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("room_size", self.room_size), ("damping", self.damping), ("mix", self.mix)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "room_size" => self.room_size = value.clamp(0.0, 1.0),
+            "damping" => self.damping = value.clamp(0.0, 1.0),
+            "mix" => self.mix = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+
+    fn tail_length(&self) -> f32 {
+        // Longest comb delay and its feedback determine the -60 dB decay time
+        let feedback = self.room_size.mul_add(0.48, 0.5);
+        let longest_comb = 1617.0 * (1.0 + self.room_size * 2.0) / DEFAULT_SAMPLE_RATE;
+        let repeats = 0.001f32.ln() / feedback.ln();
+        longest_comb * repeats
+    }
+}
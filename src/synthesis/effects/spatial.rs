@@ -1,3 +1,4 @@
+use super::effect::Effect;
 use crate::synthesis::automation::Automation;
 use crate::track::PRIORITY_MODULATION;
 
@@ -134,3 +135,45 @@ impl AutoPan {
         Self::new(1.0, 1.0)
     }
 }
+
+impl Effect for AutoPan {
+    /// Panning needs two channels, so mono processing leaves the signal untouched
+    fn process_block(&mut self, _buffer: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {}
+
+    fn process_stereo_block(
+        &mut self,
+        left: &mut [f32],
+        right: &mut [f32],
+        sample_rate: f32,
+        time: f32,
+        sample_count: u64,
+    ) {
+        let time_delta = 1.0 / sample_rate;
+        for (i, (l, r)) in left.iter_mut().zip(right.iter_mut()).enumerate() {
+            let pan = self.get_pan_offset(
+                sample_rate,
+                time + (i as f32 * time_delta),
+                sample_count + i as u64,
+            );
+            // Balance-style panning (no attenuation at center)
+            *l *= if pan <= 0.0 { 1.0 } else { 1.0 - pan };
+            *r *= if pan >= 0.0 { 1.0 } else { 1.0 + pan };
+        }
+    }
+
+    fn reset(&mut self) {
+        AutoPan::reset(self);
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f32)> {
+        vec![("rate", self.rate), ("depth", self.depth)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "rate" => self.rate = value.max(0.01),
+            "depth" => self.depth = value.clamp(0.0, 1.0),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,609 @@
+//! Multi-operator FM synthesis with DX7-style algorithms
+//!
+//! An [`FMPatch`] is four or six sine operators wired together by an
+//! [`FMAlgorithm`]. Modulators bend the phase of the operators below them, and
+//! the carriers are mixed to the output. Every [`FMOperator`] has its own
+//! frequency ratio (or fixed frequency), output level, envelope and feedback.
+//!
+//! All 32 DX7 algorithms and the 8 algorithms of the 4-operator Yamaha synths
+//! (DX21, DX27, TX81Z) are built in, numbered as on the front panels. Operator 1
+//! is always a carrier; higher-numbered operators sit further up the stack.
+//!
+//! # Example
+//! ```
+//! use tunes::synthesis::envelope::Envelope;
+//! use tunes::synthesis::fm_operators::{FMAlgorithm, FMOperator, FMPatch};
+//!
+//! // Algorithm 5: three modulator → carrier pairs
+//! let patch = FMPatch::new(FMAlgorithm::dx7(5))
+//!     .with_operator(2, FMOperator::new(14.0).with_level(0.2)
+//!         .with_envelope(Envelope::new(0.001, 0.3, 0.0, 0.2)))
+//!     .with_operator(6, FMOperator::new(1.0).with_level(0.3).with_feedback(0.5));
+//! let sample = patch.sample(440.0, 0.01, 1.0, 0.8, 0.0);
+//! assert!(sample.abs() <= 1.0);
+//! ```
+
+use crate::synthesis::envelope::Envelope;
+use crate::synthesis::wavetable::WAVETABLE;
+
+/// Phase deviation of a modulator at full level, in cycles
+///
+/// About 12.6 radians, close to the deepest modulation a DX7 operator gives.
+pub const MODULATION_DEPTH: f32 = 2.0;
+
+/// Phase deviation of an operator's feedback at full amount, in cycles
+const FEEDBACK_DEPTH: f32 = 0.25;
+
+/// Passes used to settle feedback loops
+///
+/// Operators are evaluated without state, so a feedback loop is solved by
+/// running the stack a few times and feeding back the average of the last two
+/// passes, which is what keeps DX7 feedback from oscillating.
+const FEEDBACK_PASSES: usize = 3;
+
+/// Carriers and (modulator, target) connections of one algorithm, numbered from 1
+type AlgorithmLayout = (&'static [u8], &'static [(u8, u8)]);
+
+/// DX7 algorithms 1-32 as (carriers, modulator → target connections)
+///
+/// A connection whose modulator isn't above its target is a feedback path.
+#[rustfmt::skip]
+const DX7_ALGORITHMS: [AlgorithmLayout; 32] = [
+    (&[1, 3], &[(2, 1), (4, 3), (5, 4), (6, 5), (6, 6)]),
+    (&[1, 3], &[(2, 1), (4, 3), (5, 4), (6, 5), (2, 2)]),
+    (&[1, 4], &[(2, 1), (3, 2), (5, 4), (6, 5), (6, 6)]),
+    (&[1, 4], &[(2, 1), (3, 2), (5, 4), (6, 5), (4, 6)]),
+    (&[1, 3, 5], &[(2, 1), (4, 3), (6, 5), (6, 6)]),
+    (&[1, 3, 5], &[(2, 1), (4, 3), (6, 5), (5, 6)]),
+    (&[1, 3], &[(2, 1), (4, 3), (5, 3), (6, 5), (6, 6)]),
+    (&[1, 3], &[(2, 1), (4, 3), (5, 3), (6, 5), (4, 4)]),
+    (&[1, 3], &[(2, 1), (4, 3), (5, 3), (6, 5), (2, 2)]),
+    (&[1, 4], &[(2, 1), (3, 2), (5, 4), (6, 4), (3, 3)]),
+    (&[1, 4], &[(2, 1), (3, 2), (5, 4), (6, 4), (6, 6)]),
+    (&[1, 3], &[(2, 1), (4, 3), (5, 3), (6, 3), (2, 2)]),
+    (&[1, 3], &[(2, 1), (4, 3), (5, 3), (6, 3), (6, 6)]),
+    (&[1, 3], &[(2, 1), (4, 3), (5, 4), (6, 4), (6, 6)]),
+    (&[1, 3], &[(2, 1), (4, 3), (5, 4), (6, 4), (2, 2)]),
+    (&[1], &[(2, 1), (3, 1), (5, 1), (4, 3), (6, 5), (6, 6)]),
+    (&[1], &[(2, 1), (3, 1), (5, 1), (4, 3), (6, 5), (2, 2)]),
+    (&[1], &[(2, 1), (3, 1), (4, 1), (5, 4), (6, 5), (3, 3)]),
+    (&[1, 4, 5], &[(2, 1), (3, 2), (6, 4), (6, 5), (6, 6)]),
+    (&[1, 2, 4], &[(3, 1), (3, 2), (5, 4), (6, 4), (3, 3)]),
+    (&[1, 2, 4, 5], &[(3, 1), (3, 2), (6, 4), (6, 5), (3, 3)]),
+    (&[1, 3, 4, 5], &[(2, 1), (6, 3), (6, 4), (6, 5), (6, 6)]),
+    (&[1, 2, 4, 5], &[(3, 2), (6, 4), (6, 5), (6, 6)]),
+    (&[1, 2, 3, 4, 5], &[(6, 3), (6, 4), (6, 5), (6, 6)]),
+    (&[1, 2, 3, 4, 5], &[(6, 4), (6, 5), (6, 6)]),
+    (&[1, 2, 4], &[(3, 2), (5, 4), (6, 4), (6, 6)]),
+    (&[1, 2, 4], &[(3, 2), (5, 4), (6, 4), (3, 3)]),
+    (&[1, 3, 6], &[(2, 1), (4, 3), (5, 4), (5, 5)]),
+    (&[1, 2, 3, 5], &[(4, 3), (6, 5), (6, 6)]),
+    (&[1, 2, 3, 6], &[(4, 3), (5, 4), (5, 5)]),
+    (&[1, 2, 3, 4, 5], &[(6, 5), (6, 6)]),
+    (&[1, 2, 3, 4, 5, 6], &[(6, 6)]),
+];
+
+/// 4-operator algorithms 1-8, as on the DX21/DX27/TX81Z
+#[rustfmt::skip]
+const FOUR_OP_ALGORITHMS: [AlgorithmLayout; 8] = [
+    (&[1], &[(2, 1), (3, 2), (4, 3), (4, 4)]),
+    (&[1], &[(2, 1), (3, 2), (4, 2), (4, 4)]),
+    (&[1], &[(2, 1), (3, 2), (4, 1), (4, 4)]),
+    (&[1], &[(2, 1), (3, 1), (4, 3), (4, 4)]),
+    (&[1, 3], &[(2, 1), (4, 3), (4, 4)]),
+    (&[1, 2, 3], &[(4, 1), (4, 2), (4, 3), (4, 4)]),
+    (&[1, 2, 3], &[(4, 3), (4, 4)]),
+    (&[1, 2, 3, 4], &[(4, 4)]),
+];
+
+/// How the operators of an [`FMPatch`] are connected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FMAlgorithm {
+    operators: u8,
+    // Per operator, a bit for each operator that modulates it
+    modulators: [u8; 6],
+    carriers: u8,
+}
+
+impl FMAlgorithm {
+    /// One of the 32 DX7 algorithms (6 operators)
+    ///
+    /// # Panics
+    /// Panics if `number` isn't between 1 and 32
+    pub fn dx7(number: u8) -> Self {
+        assert!(
+            (1..=32).contains(&number),
+            "DX7 algorithms are numbered 1 to 32, got {number}"
+        );
+        let (carriers, connections) = DX7_ALGORITHMS[number as usize - 1];
+        Self::custom(6, carriers, connections)
+    }
+
+    /// One of the 8 algorithms of the 4-operator Yamaha synths
+    ///
+    /// # Panics
+    /// Panics if `number` isn't between 1 and 8
+    pub fn four_op(number: u8) -> Self {
+        assert!(
+            (1..=8).contains(&number),
+            "4-operator algorithms are numbered 1 to 8, got {number}"
+        );
+        let (carriers, connections) = FOUR_OP_ALGORITHMS[number as usize - 1];
+        Self::custom(4, carriers, connections)
+    }
+
+    /// Build an algorithm from its carriers and `(modulator, target)` connections
+    ///
+    /// Operators are numbered from 1. A connection from an operator to itself or
+    /// to one above it is a feedback path, scaled by the target's
+    /// [`feedback`](FMOperator::feedback).
+    ///
+    /// # Panics
+    /// Panics if `operators` isn't between 1 and 6 or an operator number is out of range
+    pub fn custom(operators: u8, carriers: &[u8], connections: &[(u8, u8)]) -> Self {
+        assert!(
+            (1..=6).contains(&operators),
+            "an FM algorithm has 1 to 6 operators, got {operators}"
+        );
+        let valid = |op: u8| (1..=operators).contains(&op);
+
+        let mut algorithm = Self {
+            operators,
+            modulators: [0; 6],
+            carriers: 0,
+        };
+        for &carrier in carriers {
+            assert!(valid(carrier), "no operator {carrier} in this algorithm");
+            algorithm.carriers |= 1 << (carrier - 1);
+        }
+        for &(modulator, target) in connections {
+            assert!(
+                valid(modulator) && valid(target),
+                "no connection {modulator} → {target} in this algorithm"
+            );
+            algorithm.modulators[target as usize - 1] |= 1 << (modulator - 1);
+        }
+        algorithm
+    }
+
+    /// Number of operators the algorithm connects
+    pub fn operator_count(&self) -> usize {
+        self.operators as usize
+    }
+
+    /// Check whether an operator (numbered from 1) is mixed to the output
+    pub fn is_carrier(&self, operator: usize) -> bool {
+        (1..=6).contains(&operator) && self.carriers & (1 << (operator - 1)) != 0
+    }
+
+    /// The operator (numbered from 1) that a feedback path leads into, if any
+    pub fn feedback_operator(&self) -> Option<usize> {
+        (0..self.operator_count())
+            .find(|&target| self.modulators[target] & ((2 << target) - 1) != 0)
+            .map(|target| target + 1)
+    }
+}
+
+/// One sine oscillator of an [`FMPatch`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FMOperator {
+    /// Frequency as a multiple of the note's frequency
+    pub ratio: f32,
+    /// Fixed frequency in Hz, used instead of the ratio when set
+    pub fixed_frequency: Option<f32>,
+    /// Detune in cents
+    pub detune: f32,
+    /// Output level (0.0 to 1.0)
+    ///
+    /// A carrier's level is its volume in the mix; a modulator at full level
+    /// bends its target's phase by [`MODULATION_DEPTH`] cycles.
+    pub level: f32,
+    /// Shape of the level over the note
+    pub envelope: Envelope,
+    /// Self-modulation through the algorithm's feedback path (0.0 to 1.0)
+    pub feedback: f32,
+    /// How much note velocity scales the level (0.0 = not at all, 1.0 = fully)
+    pub velocity_sensitivity: f32,
+}
+
+impl FMOperator {
+    /// Create an operator at a frequency ratio, full level, holding for the note
+    pub fn new(ratio: f32) -> Self {
+        Self {
+            ratio: ratio.max(0.0),
+            fixed_frequency: None,
+            detune: 0.0,
+            level: 1.0,
+            envelope: Envelope::new(0.001, 0.01, 1.0, 0.01),
+            feedback: 0.0,
+            velocity_sensitivity: 0.0,
+        }
+    }
+
+    /// Create an operator at a fixed frequency in Hz, whatever note is played
+    pub fn fixed(frequency: f32) -> Self {
+        Self {
+            fixed_frequency: Some(frequency.max(0.0)),
+            ..Self::new(1.0)
+        }
+    }
+
+    /// Set the output level (0.0 to 1.0)
+    pub fn with_level(mut self, level: f32) -> Self {
+        self.level = level.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the level envelope
+    pub fn with_envelope(mut self, envelope: Envelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    /// Set the detune in cents
+    pub fn with_detune(mut self, cents: f32) -> Self {
+        self.detune = cents;
+        self
+    }
+
+    /// Set the feedback amount (0.0 to 1.0)
+    ///
+    /// Only heard on the operator the algorithm's feedback path leads into.
+    pub fn with_feedback(mut self, feedback: f32) -> Self {
+        self.feedback = feedback.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set how much velocity scales the level (0.0 to 1.0)
+    pub fn with_velocity_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.velocity_sensitivity = sensitivity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Frequency in Hz for a note at `frequency`
+    #[inline]
+    pub fn frequency_for(&self, frequency: f32) -> f32 {
+        let base = self.fixed_frequency.unwrap_or(frequency * self.ratio);
+        if self.detune == 0.0 {
+            base
+        } else {
+            base * 2.0f32.powf(self.detune / 1200.0)
+        }
+    }
+
+    /// Level at `time_in_note`, after the envelope and velocity
+    #[inline]
+    fn gain(&self, time_in_note: f32, note_duration: f32, velocity: f32) -> f32 {
+        let velocity_scale = 1.0 - self.velocity_sensitivity * (1.0 - velocity.clamp(0.0, 1.0));
+        self.level * velocity_scale * self.envelope.amplitude_at(time_in_note, note_duration)
+    }
+}
+
+impl Default for FMOperator {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+/// Four or six FM operators and the algorithm connecting them
+///
+/// Set on a track with [`TrackBuilder::fm_patch`](crate::composition::TrackBuilder::fm_patch),
+/// or on a single note with [`NoteEvent::with_fm_patch`](crate::track::NoteEvent::with_fm_patch).
+/// A patch replaces the note's waveform and two-operator [`FMParams`](crate::synthesis::FMParams).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FMPatch {
+    pub algorithm: FMAlgorithm,
+    /// One operator per algorithm operator; `operators[0]` is operator 1
+    pub operators: Vec<FMOperator>,
+}
+
+impl FMPatch {
+    /// Create a patch with every operator at ratio 1.0 and full level
+    pub fn new(algorithm: FMAlgorithm) -> Self {
+        Self {
+            algorithm,
+            operators: vec![FMOperator::default(); algorithm.operator_count()],
+        }
+    }
+
+    /// Replace an operator, numbered from 1 as in the algorithm
+    ///
+    /// # Panics
+    /// Panics if the algorithm has no such operator
+    pub fn with_operator(mut self, number: usize, operator: FMOperator) -> Self {
+        assert!(
+            (1..=self.operators.len()).contains(&number),
+            "no operator {number} in a {}-operator patch",
+            self.operators.len()
+        );
+        self.operators[number - 1] = operator;
+        self
+    }
+
+    /// Longest time any operator keeps sounding after the note ends, in seconds
+    pub fn release(&self) -> f32 {
+        self.operators
+            .iter()
+            .map(|op| op.envelope.release)
+            .fold(0.0, f32::max)
+    }
+
+    /// Generate one sample of the patch
+    ///
+    /// # Arguments
+    /// * `frequency` - Note frequency (Hz)
+    /// * `time_in_note` - Time within the note (seconds)
+    /// * `note_duration` - Total note duration (seconds)
+    /// * `velocity` - Note velocity (0.0 to 1.0), scaled by each operator's sensitivity
+    /// * `index_offset` - Added to the modulation depth of every modulator, in cycles
+    ///
+    /// # Returns
+    /// Sample value between -1.0 and 1.0
+    #[inline]
+    pub fn sample(
+        &self,
+        frequency: f32,
+        time_in_note: f32,
+        note_duration: f32,
+        velocity: f32,
+        index_offset: f32,
+    ) -> f32 {
+        let count = self.operators.len().min(self.algorithm.operator_count());
+        let mut phases = [0.0f32; 6];
+        let mut gains = [0.0f32; 6];
+        for (i, op) in self.operators[..count].iter().enumerate() {
+            phases[i] = op.frequency_for(frequency) * time_in_note;
+            gains[i] = op.gain(time_in_note, note_duration, velocity);
+        }
+
+        let depth = (MODULATION_DEPTH + index_offset).max(0.0);
+        let feedback = self.algorithm.feedback_operator().is_some()
+            && self.operators.iter().any(|op| op.feedback > 0.0);
+        let passes = if feedback { FEEDBACK_PASSES } else { 1 };
+
+        // Outputs of the current pass, and the fed-back values for the next
+        let mut outputs = [0.0f32; 6];
+        let mut previous = [0.0f32; 6];
+        let mut fed_back = [0.0f32; 6];
+        for pass in 0..passes {
+            // Modulators sit above their targets, so work down from the top
+            for i in (0..count).rev() {
+                let mut offset = 0.0;
+                let mut modulators = self.algorithm.modulators[i];
+                while modulators != 0 {
+                    let j = modulators.trailing_zeros() as usize;
+                    modulators &= modulators - 1;
+                    if j > i {
+                        offset += outputs[j] * depth;
+                    } else {
+                        offset += fed_back[j] * self.operators[i].feedback * FEEDBACK_DEPTH;
+                    }
+                }
+                outputs[i] = if gains[i] == 0.0 {
+                    0.0
+                } else {
+                    WAVETABLE.sample(phases[i] + offset) * gains[i]
+                };
+            }
+
+            for i in 0..count {
+                fed_back[i] = if pass == 0 {
+                    outputs[i]
+                } else {
+                    (outputs[i] + previous[i]) * 0.5
+                };
+            }
+            previous = outputs;
+        }
+
+        let mut mix = 0.0;
+        let mut carriers = 0;
+        for (i, output) in outputs[..count].iter().enumerate() {
+            if self.algorithm.is_carrier(i + 1) {
+                mix += output;
+                carriers += 1;
+            }
+        }
+        if carriers > 0 {
+            mix / carriers as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// DX7-style electric piano: a bright tine over two warm tone bars
+    pub fn electric_piano() -> Self {
+        Self::new(FMAlgorithm::dx7(5))
+            .with_operator(
+                1,
+                FMOperator::new(1.0).with_envelope(Envelope::new(0.001, 2.5, 0.3, 0.6)),
+            )
+            .with_operator(
+                2,
+                FMOperator::new(14.0)
+                    .with_level(0.12)
+                    .with_envelope(Envelope::new(0.001, 0.25, 0.0, 0.2))
+                    .with_velocity_sensitivity(0.9),
+            )
+            .with_operator(
+                3,
+                FMOperator::new(1.0)
+                    .with_detune(3.0)
+                    .with_envelope(Envelope::new(0.001, 3.0, 0.35, 0.6)),
+            )
+            .with_operator(
+                4,
+                FMOperator::new(1.0)
+                    .with_level(0.3)
+                    .with_envelope(Envelope::new(0.001, 1.2, 0.1, 0.5))
+                    .with_velocity_sensitivity(0.7),
+            )
+            .with_operator(
+                5,
+                FMOperator::new(1.0)
+                    .with_detune(-3.0)
+                    .with_level(0.6)
+                    .with_envelope(Envelope::new(0.001, 3.5, 0.3, 0.6)),
+            )
+            .with_operator(
+                6,
+                FMOperator::new(1.0)
+                    .with_level(0.2)
+                    .with_feedback(0.4)
+                    .with_envelope(Envelope::new(0.001, 0.8, 0.05, 0.4))
+                    .with_velocity_sensitivity(0.6),
+            )
+    }
+
+    /// Struck bell: inharmonic modulator pairs with a long ring
+    pub fn bell() -> Self {
+        Self::new(FMAlgorithm::dx7(5))
+            .with_operator(
+                1,
+                FMOperator::new(1.0).with_envelope(Envelope::new(0.001, 4.0, 0.0, 3.0)),
+            )
+            .with_operator(
+                2,
+                FMOperator::new(3.5)
+                    .with_level(0.4)
+                    .with_envelope(Envelope::new(0.001, 2.5, 0.0, 2.0))
+                    .with_velocity_sensitivity(0.7),
+            )
+            .with_operator(
+                3,
+                FMOperator::new(2.76)
+                    .with_level(0.5)
+                    .with_envelope(Envelope::new(0.001, 2.5, 0.0, 2.0)),
+            )
+            .with_operator(
+                4,
+                FMOperator::new(5.4)
+                    .with_level(0.3)
+                    .with_envelope(Envelope::new(0.001, 1.5, 0.0, 1.2))
+                    .with_velocity_sensitivity(0.8),
+            )
+            .with_operator(
+                5,
+                FMOperator::new(5.4)
+                    .with_level(0.25)
+                    .with_envelope(Envelope::new(0.001, 1.2, 0.0, 1.0)),
+            )
+            .with_operator(
+                6,
+                FMOperator::new(8.93)
+                    .with_level(0.2)
+                    .with_envelope(Envelope::new(0.001, 0.6, 0.0, 0.5))
+                    .with_velocity_sensitivity(0.8),
+            )
+    }
+
+    /// Punchy 4-operator bass with a feedback-growled top operator
+    pub fn bass() -> Self {
+        Self::new(FMAlgorithm::four_op(1))
+            .with_operator(
+                1,
+                FMOperator::new(0.5).with_envelope(Envelope::new(0.001, 0.6, 0.6, 0.15)),
+            )
+            .with_operator(
+                2,
+                FMOperator::new(0.5)
+                    .with_level(0.35)
+                    .with_envelope(Envelope::new(0.001, 0.3, 0.3, 0.15))
+                    .with_velocity_sensitivity(0.6),
+            )
+            .with_operator(
+                3,
+                FMOperator::new(1.0)
+                    .with_level(0.2)
+                    .with_envelope(Envelope::new(0.001, 0.2, 0.1, 0.1)),
+            )
+            .with_operator(
+                4,
+                FMOperator::new(1.0)
+                    .with_level(0.15)
+                    .with_feedback(0.6)
+                    .with_envelope(Envelope::new(0.001, 0.15, 0.0, 0.1)),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_algorithm_tables() {
+        for number in 1..=32 {
+            let algorithm = FMAlgorithm::dx7(number);
+            assert_eq!(algorithm.operator_count(), 6);
+            assert!(algorithm.is_carrier(1), "algorithm {number}");
+            assert!(
+                algorithm.feedback_operator().is_some(),
+                "algorithm {number}"
+            );
+        }
+        for number in 1..=8 {
+            let algorithm = FMAlgorithm::four_op(number);
+            assert_eq!(algorithm.operator_count(), 4);
+            assert_eq!(algorithm.feedback_operator(), Some(4));
+        }
+
+        assert_eq!(FMAlgorithm::dx7(1).feedback_operator(), Some(6));
+        assert_eq!(FMAlgorithm::dx7(2).feedback_operator(), Some(2));
+        // Algorithm 4 feeds operator 4 back to the top of its stack
+        assert_eq!(FMAlgorithm::dx7(4).feedback_operator(), Some(6));
+        assert!((1..=6).all(|op| FMAlgorithm::dx7(32).is_carrier(op)));
+    }
+
+    #[test]
+    fn test_carriers_alone_are_sines() {
+        let patch = FMPatch::new(FMAlgorithm::four_op(8));
+        // Past the 1 ms attack every operator is at full level
+        for i in 20..100 {
+            let t = i as f32 / 4410.0;
+            let expected = (t * 440.0 * std::f32::consts::TAU).sin();
+            assert!((patch.sample(440.0, t, 1.0, 1.0, 0.0) - expected).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_modulation_and_feedback_stay_in_range() {
+        for patch in [FMPatch::electric_piano(), FMPatch::bell(), FMPatch::bass()] {
+            for i in 0..2000 {
+                let t = i as f32 / 2000.0;
+                let sample = patch.sample(220.0, t, 0.5, 1.0, 3.0);
+                assert!(sample.is_finite() && sample.abs() <= 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_modulator_changes_the_carrier() {
+        let plain = FMPatch::new(FMAlgorithm::four_op(1))
+            .with_operator(2, FMOperator::new(1.0).with_level(0.0))
+            .with_operator(3, FMOperator::new(1.0).with_level(0.0))
+            .with_operator(4, FMOperator::new(1.0).with_level(0.0));
+        let modulated = plain
+            .clone()
+            .with_operator(2, FMOperator::new(2.0).with_level(0.5));
+
+        let difference: f32 = (0..100)
+            .map(|i| {
+                let t = i as f32 / 44100.0;
+                (plain.sample(440.0, t, 1.0, 1.0, 0.0) - modulated.sample(440.0, t, 1.0, 1.0, 0.0))
+                    .abs()
+            })
+            .sum();
+        assert!(difference > 1.0);
+    }
+
+    #[test]
+    fn test_fixed_frequency_and_velocity() {
+        let op = FMOperator::fixed(100.0).with_detune(1200.0);
+        assert!((op.frequency_for(440.0) - 200.0).abs() < 0.01);
+
+        let op = FMOperator::new(1.0).with_velocity_sensitivity(1.0);
+        assert!((op.gain(0.5, 1.0, 0.5) - 0.5).abs() < 1e-6);
+        assert!((op.gain(0.5, 1.0, 1.0) - 1.0).abs() < 1e-6);
+    }
+}
@@ -20,6 +20,7 @@
 //!     .granular("drums.wav", GranularParams::time_stretch(), 8.0);
 //! ```
 
+use crate::composition::timing::{TempoMap, Ticks};
 use crate::synthesis::sample::Sample;
 use crate::track::SampleEvent;
 use rand::Rng;
@@ -208,7 +209,8 @@ fn apply_hann_window(sample: Sample) -> Sample {
 /// * `source_sample` - The sample to granulate
 /// * `params` - Granular synthesis parameters
 /// * `output_duration` - How long the granular effect should last (seconds)
-/// * `start_time` - When to start playing in the composition (seconds)
+/// * `start_time` - When to start playing in the composition
+/// * `tempo_map` - Tempo map that places the grains, which are spaced in seconds
 ///
 /// # Returns
 /// Vector of SampleEvents representing all the grains
@@ -216,9 +218,31 @@ pub fn create_granular_events(
     source_sample: &Sample,
     params: &GranularParams,
     output_duration: f32,
-    start_time: f32,
+    start_time: Ticks,
+    tempo_map: &TempoMap,
+) -> Vec<SampleEvent> {
+    create_granular_events_with_rng(
+        source_sample,
+        params,
+        output_duration,
+        start_time,
+        tempo_map,
+        &mut rand::rng(),
+    )
+}
+
+/// Generate granular synthesis events from a caller-supplied generator
+///
+/// Same as [`create_granular_events`], but grain positions and pitch
+/// variations are reproducible when `rng` is seeded.
+pub fn create_granular_events_with_rng(
+    source_sample: &Sample,
+    params: &GranularParams,
+    output_duration: f32,
+    start_time: Ticks,
+    tempo_map: &TempoMap,
+    rng: &mut impl Rng,
 ) -> Vec<SampleEvent> {
-    let mut rng = rand::rng();
     let mut events = Vec::new();
 
     // Convert grain size to seconds
@@ -238,9 +262,10 @@ pub fn create_granular_events(
     let num_grains = ((output_duration / grain_spacing).ceil() as usize).min(10000);
 
     // Create each grain
-    let mut current_time = start_time;
+    let start = tempo_map.seconds_at(start_time) as f32;
+    let mut current_time = start;
     for _ in 0..num_grains {
-        if current_time >= start_time + output_duration {
+        if current_time >= start + output_duration {
             break;
         }
 
@@ -285,7 +310,7 @@ pub fn create_granular_events(
         // Create the sample event
         events.push(SampleEvent {
             sample: grain_with_envelope,
-            start_time: current_time,
+            start_time: tempo_map.ticks_at(current_time as f64),
             playback_rate: pitch_variation,
             volume: 1.0,
             spatial_position: None,
@@ -369,28 +394,32 @@ mod tests {
 
     #[test]
     fn test_create_granular_events_basic() {
+        let map = TempoMap::default();
         let sample = create_test_sample(1.0);
         let params = GranularParams::default();
-        let events = create_granular_events(&sample, &params, 2.0, 0.0);
+        let events = create_granular_events(&sample, &params, 2.0, Ticks::ZERO, &map);
 
         assert!(!events.is_empty(), "Should generate grains");
 
         // All events should be within the output duration
         for event in &events {
-            assert!(event.start_time >= 0.0 && event.start_time <= 2.0);
+            assert!(event.start_time >= Ticks::ZERO && event.start_time <= map.ticks_at(2.0));
         }
     }
 
     #[test]
     fn test_granular_density_affects_grain_count() {
+        let map = TempoMap::default();
         let sample = create_test_sample(1.0);
         let output_duration = 1.0;
 
         let low_density = GranularParams::new(50.0, 0.2, 0.5, 0.1, 0.0);
         let high_density = GranularParams::new(50.0, 0.8, 0.5, 0.1, 0.0);
 
-        let low_events = create_granular_events(&sample, &low_density, output_duration, 0.0);
-        let high_events = create_granular_events(&sample, &high_density, output_duration, 0.0);
+        let low_events =
+            create_granular_events(&sample, &low_density, output_duration, Ticks::ZERO, &map);
+        let high_events =
+            create_granular_events(&sample, &high_density, output_duration, Ticks::ZERO, &map);
 
         assert!(
             high_events.len() > low_events.len(),
@@ -402,9 +431,10 @@ mod tests {
 
     #[test]
     fn test_granular_grain_size_affects_duration() {
+        let map = TempoMap::default();
         let sample = create_test_sample(1.0);
         let params = GranularParams::new(50.0, 0.5, 0.5, 0.0, 0.0);
-        let events = create_granular_events(&sample, &params, 1.0, 0.0);
+        let events = create_granular_events(&sample, &params, 1.0, Ticks::ZERO, &map);
 
         // Check that grains have approximately correct duration
         if !events.is_empty() {
@@ -421,15 +451,17 @@ mod tests {
 
     #[test]
     fn test_granular_position_spread() {
+        let map = TempoMap::default();
         let sample = create_test_sample(2.0);
 
         // No spread - all grains from same position
         let no_spread = GranularParams::new(50.0, 0.5, 0.5, 0.0, 0.0);
-        let events_no_spread = create_granular_events(&sample, &no_spread, 0.5, 0.0);
+        let events_no_spread = create_granular_events(&sample, &no_spread, 0.5, Ticks::ZERO, &map);
 
         // High spread - grains from different positions
         let high_spread = GranularParams::new(50.0, 0.5, 0.5, 0.5, 0.0);
-        let events_high_spread = create_granular_events(&sample, &high_spread, 0.5, 0.0);
+        let events_high_spread =
+            create_granular_events(&sample, &high_spread, 0.5, Ticks::ZERO, &map);
 
         assert!(!events_no_spread.is_empty());
         assert!(!events_high_spread.is_empty());
@@ -440,9 +472,10 @@ mod tests {
 
     #[test]
     fn test_granular_pitch_variation() {
+        let map = TempoMap::default();
         let sample = create_test_sample(1.0);
         let params = GranularParams::new(50.0, 0.5, 0.5, 0.1, 0.3);
-        let events = create_granular_events(&sample, &params, 1.0, 0.0);
+        let events = create_granular_events(&sample, &params, 1.0, Ticks::ZERO, &map);
 
         // Should have some pitch variation in playback rates
         let playback_rates: Vec<f32> = events.iter().map(|e| e.playback_rate).collect();
@@ -464,16 +497,17 @@ mod tests {
 
     #[test]
     fn test_granular_respects_start_time() {
+        let map = TempoMap::default();
         let sample = create_test_sample(1.0);
         let params = GranularParams::default();
-        let start_time = 5.0;
-        let events = create_granular_events(&sample, &params, 1.0, start_time);
+        let start_time = map.ticks_at(5.0);
+        let events = create_granular_events(&sample, &params, 1.0, start_time, &map);
 
         // All events should start at or after start_time
         for event in &events {
             assert!(
                 event.start_time >= start_time,
-                "Event at {} should be >= {}",
+                "Event at {:?} should be >= {:?}",
                 event.start_time,
                 start_time
             );
@@ -482,18 +516,20 @@ mod tests {
 
     #[test]
     fn test_granular_zero_duration() {
+        let map = TempoMap::default();
         let sample = create_test_sample(1.0);
         let params = GranularParams::default();
-        let events = create_granular_events(&sample, &params, 0.0, 0.0);
+        let events = create_granular_events(&sample, &params, 0.0, Ticks::ZERO, &map);
 
         assert_eq!(events.len(), 0, "Zero duration should produce no grains");
     }
 
     #[test]
     fn test_granular_very_short_sample() {
+        let map = TempoMap::default();
         let sample = create_test_sample(0.01); // 10ms sample
         let params = GranularParams::new(50.0, 0.5, 0.5, 0.1, 0.0); // 50ms grains
-        let events = create_granular_events(&sample, &params, 1.0, 0.0);
+        let events = create_granular_events(&sample, &params, 1.0, Ticks::ZERO, &map);
 
         // Should still generate events, even if grains are longer than source
         // (they'll just use the full sample)
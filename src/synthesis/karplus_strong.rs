@@ -81,25 +81,23 @@ impl KarplusStrong {
     /// let string = KarplusStrong::new(440.0, 44100.0);
     /// ```
     pub fn new(frequency: f32, sample_rate: f32) -> Self {
-        // Calculate delay line length from frequency
-        let buffer_length = (sample_rate / frequency).round() as usize;
-        let buffer_length = buffer_length.max(1); // Prevent zero-length buffer
-
-        let mut rng = rand::rngs::StdRng::from_rng(&mut rand::rng());
-
-        // Initialize buffer with white noise
-        let buffer: Vec<f32> = (0..buffer_length)
-            .map(|_| rng.random_range(-1.0..1.0))
-            .collect();
+        Self::with_rng(frequency, sample_rate, &mut rand::rng())
+    }
 
-        Self {
-            buffer,
-            position: 0,
-            decay: 0.996,      // Good default for realistic decay
-            brightness: 0.5,   // Medium brightness
-            prev_sample: 0.0,
-            rng,
-        }
+    /// Create with a seed drawn from `rng`
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::prelude::*;
+    /// use tunes::synthesis::karplus_strong::KarplusStrong;
+    ///
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.set_seed(7);
+    /// // The pluck follows the composition seed, like the rest of the take
+    /// let string = KarplusStrong::with_rng(440.0, 44100.0, comp.rng());
+    /// ```
+    pub fn with_rng<R: Rng + ?Sized>(frequency: f32, sample_rate: f32, rng: &mut R) -> Self {
+        Self::with_seed(frequency, sample_rate, rng.random())
     }
 
     /// Create with a specific seed for deterministic output
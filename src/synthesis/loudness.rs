@@ -0,0 +1,512 @@
+//! Loudness metering (ITU-R BS.1770-4 / EBU R128)
+//!
+//! [`LoudnessMeter`] measures programme loudness the way streaming services and
+//! broadcasters do: the signal is K-weighted (a high shelf modelling the head plus a
+//! high pass), squared and averaged over sliding windows.
+//!
+//! - **Momentary** loudness uses a 400 ms window, **short-term** a 3 s window.
+//! - **Integrated** loudness averages the whole programme, ignoring silence below
+//!   -70 LUFS and anything 10 LU quieter than the ungated average.
+//! - **Loudness range** (EBU Tech 3342) is the spread between the 10th and 95th
+//!   percentile of short-term loudness.
+//! - **True peak** oversamples the signal to catch peaks between samples that a DAC
+//!   would reconstruct.
+//!
+//! # Example
+//! ```
+//! # use tunes::synthesis::loudness::LoudnessMeter;
+//! let tone: Vec<f32> = (0..44100 * 2)
+//!     .map(|i| (i as f32 * 1000.0 * std::f32::consts::TAU / 44100.0).sin() * 0.5)
+//!     .collect();
+//!
+//! let mut meter = LoudnessMeter::new(44100, 1);
+//! meter.process(&tone);
+//! let loudness = meter.loudness();
+//! assert!((loudness.integrated + 9.0).abs() < 0.2);
+//! ```
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+/// Gating blocks advance in steps of 100 ms
+const STEPS_PER_SECOND: usize = 10;
+/// Steps in a momentary (400 ms) window
+const MOMENTARY_STEPS: usize = 4;
+/// Steps in a short-term (3 s) window
+const SHORT_TERM_STEPS: usize = 30;
+/// Blocks quieter than this are silence and never counted
+const ABSOLUTE_GATE: f64 = -70.0;
+/// Integrated loudness ignores blocks this far below the ungated average
+const INTEGRATED_RELATIVE_GATE: f64 = -10.0;
+/// Loudness range ignores blocks this far below the ungated average
+const RANGE_RELATIVE_GATE: f64 = -20.0;
+/// Taps per phase of the true-peak interpolator
+const TRUE_PEAK_TAPS: usize = 12;
+
+/// Result of a loudness measurement
+///
+/// Loudness values are in LUFS, ranges in LU and peaks in dB relative to full scale.
+/// Measurements of silence (or of programmes shorter than one window) are
+/// `f32::NEG_INFINITY`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Loudness {
+    /// Gated loudness of the whole programme (LUFS)
+    pub integrated: f32,
+    /// Loudest 400 ms window (LUFS)
+    pub momentary_max: f32,
+    /// Loudest 3 s window (LUFS)
+    pub short_term_max: f32,
+    /// Loudness range (LU)
+    pub loudness_range: f32,
+    /// Highest reconstructed peak between or on samples (dBTP)
+    pub true_peak: f32,
+    /// Highest sample value (dBFS)
+    pub sample_peak: f32,
+}
+
+impl Loudness {
+    /// Gain in dB that brings the integrated loudness to `target_lufs`
+    ///
+    /// Returns 0 dB for silence, which cannot be normalized.
+    pub fn gain_to(&self, target_lufs: f32) -> f32 {
+        if self.integrated.is_finite() {
+            target_lufs - self.integrated
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Streaming loudness meter for interleaved audio
+///
+/// Feed audio with [`process`](Self::process) in blocks of any size, then read the
+/// running [`momentary`](Self::momentary) and [`short_term`](Self::short_term) values
+/// or the full [`loudness`](Self::loudness) report. Every channel has unit weight, so
+/// a mono signal reads 3 LU quieter than the same signal on both stereo channels.
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    channels: usize,
+    step_frames: usize,
+    filters: Vec<KWeighting>,
+    peaks: Vec<TruePeak>,
+    // Weighted sum of squares in the current 100 ms step, and frames counted so far
+    step_energy: f64,
+    step_filled: usize,
+    // Mean square of the most recent steps (up to one short-term window)
+    recent: VecDeque<f64>,
+    // Mean square of every momentary and short-term block, for gating
+    momentary_blocks: Vec<f64>,
+    short_term_blocks: Vec<f64>,
+    sample_peak: f32,
+}
+
+impl LoudnessMeter {
+    /// Create a meter for `channels` interleaved channels at `sample_rate` Hz
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        let channels = channels.max(1);
+        let sample_rate = sample_rate.max(1);
+        Self {
+            channels,
+            step_frames: (sample_rate as usize / STEPS_PER_SECOND).max(1),
+            filters: vec![KWeighting::new(sample_rate as f64); channels],
+            peaks: vec![TruePeak::new(sample_rate); channels],
+            step_energy: 0.0,
+            step_filled: 0,
+            recent: VecDeque::with_capacity(SHORT_TERM_STEPS),
+            momentary_blocks: Vec::new(),
+            short_term_blocks: Vec::new(),
+            sample_peak: 0.0,
+        }
+    }
+
+    /// Measure a block of interleaved samples
+    pub fn process(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(self.channels) {
+            for (channel, &sample) in frame.iter().enumerate() {
+                let weighted = self.filters[channel].process(sample as f64);
+                self.step_energy += weighted * weighted;
+                self.peaks[channel].process(sample);
+                self.sample_peak = self.sample_peak.max(sample.abs());
+            }
+
+            self.step_filled += 1;
+            if self.step_filled == self.step_frames {
+                self.finish_step();
+            }
+        }
+    }
+
+    /// Close the current 100 ms step and record any windows that end with it
+    fn finish_step(&mut self) {
+        if self.recent.len() == SHORT_TERM_STEPS {
+            self.recent.pop_front();
+        }
+        self.recent
+            .push_back(self.step_energy / self.step_frames as f64);
+        self.step_energy = 0.0;
+        self.step_filled = 0;
+
+        if let Some(power) = self.window_power(MOMENTARY_STEPS) {
+            self.momentary_blocks.push(power);
+        }
+        if let Some(power) = self.window_power(SHORT_TERM_STEPS) {
+            self.short_term_blocks.push(power);
+        }
+    }
+
+    /// Mean square over the last `steps` steps, once that many have been measured
+    fn window_power(&self, steps: usize) -> Option<f64> {
+        (self.recent.len() >= steps)
+            .then(|| self.recent.iter().rev().take(steps).sum::<f64>() / steps as f64)
+    }
+
+    /// Loudness of the most recent 400 ms (LUFS)
+    pub fn momentary(&self) -> f32 {
+        self.window_power(MOMENTARY_STEPS)
+            .map_or(f32::NEG_INFINITY, |power| power_to_lufs(power) as f32)
+    }
+
+    /// Loudness of the most recent 3 s (LUFS)
+    pub fn short_term(&self) -> f32 {
+        self.window_power(SHORT_TERM_STEPS)
+            .map_or(f32::NEG_INFINITY, |power| power_to_lufs(power) as f32)
+    }
+
+    /// Gated loudness of everything measured so far (LUFS)
+    pub fn integrated(&self) -> f32 {
+        let gated = relative_gate(&self.momentary_blocks, INTEGRATED_RELATIVE_GATE);
+        if gated.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+        power_to_lufs(gated.iter().sum::<f64>() / gated.len() as f64) as f32
+    }
+
+    /// Loudness range of everything measured so far (LU)
+    pub fn loudness_range(&self) -> f32 {
+        let mut levels: Vec<f64> = relative_gate(&self.short_term_blocks, RANGE_RELATIVE_GATE)
+            .into_iter()
+            .map(power_to_lufs)
+            .collect();
+        if levels.is_empty() {
+            return 0.0;
+        }
+        levels.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |p: f64| levels[((levels.len() - 1) as f64 * p).round() as usize];
+        (percentile(0.95) - percentile(0.10)) as f32
+    }
+
+    /// Highest reconstructed peak so far (dBTP)
+    pub fn true_peak(&self) -> f32 {
+        let peak = self
+            .peaks
+            .iter()
+            .fold(0.0f32, |peak, meter| peak.max(meter.peak));
+        amplitude_to_db(peak.max(self.sample_peak))
+    }
+
+    /// Full report of everything measured so far
+    pub fn loudness(&self) -> Loudness {
+        let loudest = |blocks: &[f64]| {
+            blocks
+                .iter()
+                .copied()
+                .reduce(f64::max)
+                .map_or(f32::NEG_INFINITY, |power| power_to_lufs(power) as f32)
+        };
+        Loudness {
+            integrated: self.integrated(),
+            momentary_max: loudest(&self.momentary_blocks),
+            short_term_max: loudest(&self.short_term_blocks),
+            loudness_range: self.loudness_range(),
+            true_peak: self.true_peak(),
+            sample_peak: amplitude_to_db(self.sample_peak),
+        }
+    }
+}
+
+/// Measure the loudness of a complete interleaved buffer
+///
+/// # Example
+/// ```
+/// # use tunes::prelude::*;
+/// # use tunes::synthesis::loudness;
+/// let mut comp = Composition::new(Tempo::new(120.0));
+/// comp.track("lead").notes(&[C4, E4, G4, C5], 0.5);
+/// let buffer = comp.into_mixer().render_to_buffer(44100.0);
+///
+/// let measured = loudness::measure(&buffer, 44100, 2);
+/// println!("{:.1} LUFS, {:.1} dBTP", measured.integrated, measured.true_peak);
+/// ```
+pub fn measure(samples: &[f32], sample_rate: u32, channels: usize) -> Loudness {
+    let mut meter = LoudnessMeter::new(sample_rate, channels);
+    meter.process(samples);
+    meter.loudness()
+}
+
+/// Blocks above the absolute gate and within `relative` LU of their average
+fn relative_gate(blocks: &[f64], relative: f64) -> Vec<f64> {
+    let audible: Vec<f64> = blocks
+        .iter()
+        .copied()
+        .filter(|&power| power_to_lufs(power) > ABSOLUTE_GATE)
+        .collect();
+    if audible.is_empty() {
+        return audible;
+    }
+    let average = power_to_lufs(audible.iter().sum::<f64>() / audible.len() as f64);
+    audible
+        .into_iter()
+        .filter(|&power| power_to_lufs(power) > average + relative)
+        .collect()
+}
+
+/// Loudness of a K-weighted mean square, summed over channels
+fn power_to_lufs(power: f64) -> f64 {
+    if power > 0.0 {
+        -0.691 + 10.0 * power.log10()
+    } else {
+        f64::NEG_INFINITY
+    }
+}
+
+fn amplitude_to_db(amplitude: f32) -> f32 {
+    if amplitude > 0.0 {
+        20.0 * amplitude.log10()
+    } else {
+        f32::NEG_INFINITY
+    }
+}
+
+/// BS.1770 K-weighting: a high shelf followed by a high pass
+///
+/// Coefficients are derived from the analog prototypes so any sample rate matches the
+/// 48 kHz reference filter.
+#[derive(Debug, Clone)]
+struct KWeighting {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeighting {
+    fn new(sample_rate: f64) -> Self {
+        // High shelf: +4 dB above roughly 1.7 kHz
+        let k = (PI * 1_681.974_450_955_533 / sample_rate).tan();
+        let q = 0.707_175_236_955_419_6;
+        let vh = 10f64.powf(3.999_843_853_973_347 / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            state: [0.0; 2],
+        };
+
+        // High pass at roughly 38 Hz
+        let k = (PI * 38.135_470_876_024_44 / sample_rate).tan();
+        let q = 0.500_327_037_323_877_3;
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = Biquad {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            state: [0.0; 2],
+        };
+
+        Self { shelf, high_pass }
+    }
+
+    #[inline]
+    fn process(&mut self, sample: f64) -> f64 {
+        self.high_pass.process(self.shelf.process(sample))
+    }
+}
+
+/// Transposed direct form II biquad
+#[derive(Debug, Clone)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    state: [f64; 2],
+}
+
+impl Biquad {
+    #[inline]
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.state[0];
+        self.state[0] = self.b[1] * input - self.a[0] * output + self.state[1];
+        self.state[1] = self.b[2] * input - self.a[1] * output;
+        output
+    }
+}
+
+/// True-peak detector for one channel
+///
+/// Tracks the largest magnitude on or between samples, as reconstructed by a
+/// [`TruePeakInterpolator`].
+#[derive(Debug, Clone)]
+struct TruePeak {
+    interpolator: TruePeakInterpolator,
+    peak: f32,
+}
+
+impl TruePeak {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            interpolator: TruePeakInterpolator::new(sample_rate),
+            peak: 0.0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, sample: f32) {
+        self.peak = self.peak.max(self.interpolator.push(sample));
+    }
+}
+
+/// Reconstructs the signal between samples of one channel
+///
+/// Upsamples by 4 (2 above 96 kHz) with a windowed-sinc interpolator. The points it
+/// reconstructs lie about [`DELAY`](Self::DELAY) samples behind the newest input.
+#[derive(Debug, Clone)]
+pub(crate) struct TruePeakInterpolator {
+    // Interpolation filter, one row of taps per intermediate phase
+    phases: Vec<[f32; TRUE_PEAK_TAPS]>,
+    history: [f32; TRUE_PEAK_TAPS],
+}
+
+impl TruePeakInterpolator {
+    /// Samples between the newest input and the interval that [`push`](Self::push) reads
+    pub(crate) const DELAY: usize = TRUE_PEAK_TAPS / 2;
+
+    pub(crate) fn new(sample_rate: u32) -> Self {
+        let factor = match sample_rate {
+            0..96000 => 4,
+            96000..192000 => 2,
+            _ => 1,
+        };
+        let half = (TRUE_PEAK_TAPS / 2) as f64;
+        // Phase 0 falls on the original samples, which are tracked directly
+        let phases = (1..factor)
+            .map(|phase| {
+                let offset = phase as f64 / factor as f64;
+                let mut taps = [0.0; TRUE_PEAK_TAPS];
+                for (tap, value) in taps.iter_mut().enumerate() {
+                    // Distance from the interpolated point to this input sample
+                    let x = tap as f64 - half + 1.0 - offset;
+                    let window = 0.5 + 0.5 * (PI * x / (half + 1.0)).cos();
+                    let sinc = if x == 0.0 {
+                        1.0
+                    } else {
+                        (PI * x).sin() / (PI * x)
+                    };
+                    *value = (sinc * window) as f32;
+                }
+                taps
+            })
+            .collect();
+        Self {
+            phases,
+            history: [0.0; TRUE_PEAK_TAPS],
+        }
+    }
+
+    /// Add a sample and return the largest magnitude from the sample `DELAY` steps
+    /// ago up to and including the one after it, on or between the two
+    #[inline]
+    pub(crate) fn push(&mut self, sample: f32) -> f32 {
+        self.history.copy_within(1.., 0);
+        self.history[TRUE_PEAK_TAPS - 1] = sample;
+        let ends = self.history[Self::DELAY - 1]
+            .abs()
+            .max(self.history[Self::DELAY].abs());
+        self.phases
+            .iter()
+            .map(|taps| taps.iter().zip(&self.history).map(|(t, h)| t * h).sum::<f32>())
+            .fold(ends, |peak, value| peak.max(value.abs()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f32, amplitude: f32, seconds: f32, sample_rate: u32) -> Vec<f32> {
+        let frames = (seconds * sample_rate as f32) as usize;
+        (0..frames)
+            .map(|i| {
+                let phase = i as f32 * frequency * std::f32::consts::TAU / sample_rate as f32;
+                phase.sin() * amplitude
+            })
+            .collect()
+    }
+
+    fn stereo(mono: &[f32]) -> Vec<f32> {
+        mono.iter().flat_map(|&s| [s, s]).collect()
+    }
+
+    #[test]
+    fn test_full_scale_sine_reads_reference_level() {
+        // EBU Tech 3341: a 0 dBFS 1 kHz sine on one channel reads -3.01 LUFS
+        for sample_rate in [44100, 48000, 96000] {
+            let loudness = measure(&sine(1000.0, 1.0, 5.0, sample_rate), sample_rate, 1);
+            assert!((loudness.integrated + 3.01).abs() < 0.1, "{:?}", loudness);
+        }
+    }
+
+    #[test]
+    fn test_ebu_stereo_reference_reads_minus_23() {
+        // EBU Tech 3341 case 1: -23 dBFS 1 kHz sine on both channels reads -23 LUFS
+        let amplitude = 10f32.powf(-23.0 / 20.0);
+        let loudness = measure(&stereo(&sine(1000.0, amplitude, 5.0, 48000)), 48000, 2);
+        assert!((loudness.integrated + 23.0).abs() < 0.1);
+        assert!((loudness.momentary_max + 23.0).abs() < 0.1);
+        assert!((loudness.short_term_max + 23.0).abs() < 0.1);
+        assert!(loudness.loudness_range < 0.1);
+    }
+
+    #[test]
+    fn test_gating_ignores_silence() {
+        let mut signal = sine(1000.0, 0.5, 3.0, 48000);
+        signal.extend(std::iter::repeat_n(0.0, 48000 * 10));
+        let gated = measure(&signal, 48000, 1);
+        let tone_only = measure(&sine(1000.0, 0.5, 3.0, 48000), 48000, 1);
+        // Only the windows straddling the end of the tone pull the average down
+        assert!((gated.integrated - tone_only.integrated).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_loudness_range_of_two_levels() {
+        // After EBU Tech 3342 case 1: a step from -20 dBFS to -30 dBFS has an LRA of 10 LU
+        let mut signal = sine(1000.0, 10f32.powf(-20.0 / 20.0), 10.0, 48000);
+        signal.extend(sine(1000.0, 10f32.powf(-30.0 / 20.0), 10.0, 48000));
+        let loudness = measure(&signal, 48000, 1);
+        assert!(
+            (loudness.loudness_range - 10.0).abs() < 0.5,
+            "{}",
+            loudness.loudness_range
+        );
+    }
+
+    #[test]
+    fn test_true_peak_catches_intersample_peaks() {
+        // A quarter-rate sine sampled at 45° never hits its peak on a sample
+        let signal: Vec<f32> = (0..48000)
+            .map(|i| {
+                (std::f32::consts::FRAC_PI_2 * (i % 4) as f32 + std::f32::consts::FRAC_PI_4).sin()
+            })
+            .collect();
+        let loudness = measure(&signal, 48000, 1);
+        assert!((loudness.sample_peak + 3.01).abs() < 0.05);
+        assert!(loudness.true_peak > -0.5, "{}", loudness.true_peak);
+    }
+
+    #[test]
+    fn test_silence_is_negative_infinity() {
+        let loudness = measure(&[0.0; 48000], 48000, 2);
+        assert_eq!(loudness.integrated, f32::NEG_INFINITY);
+        assert_eq!(loudness.gain_to(-14.0), 0.0);
+    }
+}
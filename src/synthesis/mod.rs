@@ -10,17 +10,27 @@ pub mod sample;
 pub mod slice;
 pub mod effects;
 pub mod fm_synthesis;
+pub mod fm_operators;
+pub mod dx7;
 pub mod granular;
 pub mod wavetable;
 pub mod filter_envelope;
 pub mod karplus_strong;
+pub mod physical_model;
+pub mod modal;
+pub(crate) mod render_cache;
 pub mod additive;
+pub mod sinusoidal;
 pub mod spatial;
 pub mod simd;
+pub mod loudness;
+pub mod unison;
+pub mod mod_matrix;
 
 // Re-export main types for convenience
-pub use waveform::Waveform;
+pub use waveform::{Modulator, Waveform};
 pub use lfo::{LFO, ModRoute, ModTarget};
+pub use mod_matrix::{MatrixRoute, ModCurve, ModDestination, ModMatrix, ModSource};
 pub use filter::{Filter, FilterType};
 pub use noise::{
     NoiseType, NoiseGenerator, WhiteNoise, BrownNoise, PinkNoise, BlueNoise, GreenNoise,
@@ -32,14 +42,21 @@ pub use slice::SampleSlice;
 pub use effects::*;
 pub use envelope::Envelope;
 pub use fm_synthesis::FMParams;
+pub use fm_operators::{FMAlgorithm, FMOperator, FMPatch};
+pub use dx7::{Dx7Operator, Dx7Voice};
 pub use granular::GranularParams;
 pub use wavetable::Wavetable;
 pub use filter_envelope::FilterEnvelope;
 pub use karplus_strong::KarplusStrong;
-pub use additive::{AdditiveSynth, Partial};
+pub use physical_model::{BodyMode, BodyResonance, ModelKind, PhysicalModel};
+pub use modal::{Exciter, Material, ModalAnalysis, ModalResonator, Mode, Shape};
+pub use additive::{AdditiveSynth, Partial, PartialEnvelope};
+pub use sinusoidal::{SinusoidalAnalysis, TrackedPartial};
 pub use spatial::{
     Vec3, SpatialPosition, ListenerConfig, SpatialParams, SpatialResult, AttenuationModel,
     calculate_spatial, calculate_attenuation, calculate_azimuth, azimuth_to_pan,
     calculate_doppler,
 };
 pub use simd::{SimdDispatcher, SimdLanes, SimdWidth, SIMD};
+pub use loudness::{Loudness, LoudnessMeter};
+pub use unison::Unison;
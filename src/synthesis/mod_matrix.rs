@@ -0,0 +1,589 @@
+//! Modulation matrix: any source to any destination
+//!
+//! A [`ModMatrix`] is a list of [`MatrixRoute`]s, each connecting one [`ModSource`]
+//! to one [`ModDestination`] through a [`ModCurve`] and an amount. Sources are
+//! either free-running (LFOs, automation lanes, envelope followers) or belong to
+//! each note (envelopes, velocity, key tracking, a random value). Destinations are
+//! either per note (pitch, FM index, pulse width, unison detune, wavetable
+//! position) or per track
+//! (filter, volume, pan, effect parameters).
+//!
+//! When a per-note source drives a track destination, the most recently started
+//! note that is still sounding supplies the value, the way a mono synth follows
+//! its last key.
+//!
+//! # Example
+//! ```
+//! use tunes::synthesis::envelope::Envelope;
+//! use tunes::synthesis::lfo::LFO;
+//! use tunes::synthesis::mod_matrix::{MatrixRoute, ModCurve, ModDestination, ModMatrix, ModSource};
+//!
+//! let matrix = ModMatrix::new()
+//!     // Harder hits open the filter by up to two octaves
+//!     .with_route(MatrixRoute::new(ModSource::Velocity, ModDestination::FilterCutoff, 2.0)
+//!         .with_curve(ModCurve::Exponential))
+//!     // A slow LFO breathes the delay mix
+//!     .with_route(MatrixRoute::new(
+//!         ModSource::Lfo(LFO::slow_sine(1.0)),
+//!         ModDestination::effect("Delay", "mix"),
+//!         0.2,
+//!     ))
+//!     // A decaying envelope per note sweeps the FM index
+//!     .with_route(MatrixRoute::new(
+//!         ModSource::Envelope(Envelope::new(0.0, 0.3, 0.0, 0.1)),
+//!         ModDestination::FmIndex,
+//!         4.0,
+//!     ));
+//! assert_eq!(matrix.len(), 3);
+//! ```
+
+use crate::synthesis::automation::Automation;
+use crate::synthesis::envelope::Envelope;
+use crate::synthesis::lfo::{ModRoute, ModTarget, LFO};
+
+/// Frequency key tracking is measured from (middle C, in Hz)
+const KEY_TRACK_CENTER: f32 = 261.625_58;
+
+/// Where a modulation value comes from
+#[derive(Debug, Clone)]
+pub enum ModSource {
+    /// Free-running LFO, bipolar (-depth to depth)
+    Lfo(LFO),
+    /// An extra ADSR envelope, restarted by every note (0.0 to 1.0)
+    Envelope(Envelope),
+    /// Note velocity (0.0 to 1.0)
+    Velocity,
+    /// Note pitch in octaves from middle C (one octave up = 1.0)
+    KeyTrack,
+    /// A fixed random value per note (-1.0 to 1.0), repeatable between renders
+    Random,
+    /// An automation lane, read at song time (its values are used as-is)
+    Automation(Automation),
+    /// Level of the track's own synthesized signal (0.0 and up)
+    ///
+    /// `attack` and `release` are the follower's rise and fall times in seconds.
+    EnvelopeFollower { attack: f32, release: f32 },
+}
+
+impl ModSource {
+    /// Check whether the source takes its value from a note
+    pub fn is_per_note(&self) -> bool {
+        matches!(
+            self,
+            ModSource::Envelope(_) | ModSource::Velocity | ModSource::KeyTrack | ModSource::Random
+        )
+    }
+}
+
+/// What a modulation value changes, and the unit its amount is in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModDestination {
+    /// Track filter cutoff, in octaves
+    FilterCutoff,
+    /// Track filter resonance, added (0.0 to 0.99)
+    FilterResonance,
+    /// Track volume, as a fraction of itself (-1.0 silences, 1.0 doubles)
+    Volume,
+    /// Track pan, added (-1.0 left to 1.0 right)
+    Pan,
+    /// Note pitch, in semitones
+    Pitch,
+    /// Note FM modulation index, added (notes without FM are left alone)
+    FmIndex,
+    /// Pulse width of notes using a pulse waveform, added (0.01 to 0.99)
+    PulseWidth,
+    /// Unison detune, in cents added to the outermost voices
+    UnisonDetune,
+    /// Morph position of notes using a custom wavetable, added (0.0 to 1.0)
+    WavetablePosition,
+    /// A parameter of the track's effect with this [`name`](crate::synthesis::effects::Effect::name),
+    /// in the parameter's own unit
+    ///
+    /// Built-in slots are searched first, then the inserts in order. Parameters are
+    /// updated once per rendered block.
+    Effect {
+        effect: &'static str,
+        parameter: &'static str,
+    },
+}
+
+impl ModDestination {
+    /// Target a parameter of one of the track's effects, e.g. `effect("Reverb", "room_size")`
+    pub const fn effect(effect: &'static str, parameter: &'static str) -> Self {
+        ModDestination::Effect { effect, parameter }
+    }
+
+    /// Check whether the destination is a property of each note
+    pub fn is_per_note(&self) -> bool {
+        matches!(
+            self,
+            ModDestination::Pitch
+                | ModDestination::FmIndex
+                | ModDestination::PulseWidth
+                | ModDestination::UnisonDetune
+                | ModDestination::WavetablePosition
+        )
+    }
+}
+
+/// Shape applied to a source before it is scaled by the route's amount
+///
+/// Curves bend the magnitude and keep the sign, so bipolar sources stay symmetric.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ModCurve {
+    /// Unchanged
+    #[default]
+    Linear,
+    /// Slow start, fast finish (squared)
+    Exponential,
+    /// Fast start, slow finish (square root)
+    Logarithmic,
+    /// Eased at both ends (smoothstep, magnitude clamped to 1.0)
+    SCurve,
+    /// Quantized to this many steps per unit
+    Stepped(u8),
+}
+
+impl ModCurve {
+    /// Shape a source value
+    #[inline]
+    pub fn apply(&self, value: f32) -> f32 {
+        let magnitude = value.abs();
+        let shaped = match *self {
+            ModCurve::Linear => return value,
+            ModCurve::Exponential => magnitude * magnitude,
+            ModCurve::Logarithmic => magnitude.sqrt(),
+            ModCurve::SCurve => {
+                let x = magnitude.min(1.0);
+                x * x * (3.0 - 2.0 * x)
+            }
+            ModCurve::Stepped(steps) => {
+                let steps = steps.max(1) as f32;
+                (magnitude * steps).floor() / steps
+            }
+        };
+        shaped.copysign(value)
+    }
+}
+
+/// One connection in a [`ModMatrix`]
+#[derive(Debug, Clone)]
+pub struct MatrixRoute {
+    pub source: ModSource,
+    pub destination: ModDestination,
+    /// Scale of the shaped source, in the destination's unit
+    pub amount: f32,
+    pub curve: ModCurve,
+
+    // Latest value of a free-running source, and the envelope follower's level
+    current: f32,
+}
+
+impl MatrixRoute {
+    /// Connect `source` to `destination`, scaled by `amount` with a linear curve
+    pub fn new(source: ModSource, destination: ModDestination, amount: f32) -> Self {
+        Self {
+            source,
+            destination,
+            amount,
+            curve: ModCurve::Linear,
+            current: 0.0,
+        }
+    }
+
+    /// Set the curve applied to the source (builder pattern)
+    pub fn with_curve(mut self, curve: ModCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Advance a free-running source by one sample
+    #[inline]
+    fn tick(&mut self, time: f32, input: f32, sample_rate: f32) {
+        match &mut self.source {
+            ModSource::Lfo(lfo) => {
+                lfo.tick();
+                self.current = lfo.bipolar_value();
+            }
+            ModSource::Automation(automation) => self.current = automation.value_at(time),
+            ModSource::EnvelopeFollower { attack, release } => {
+                let level = input.abs();
+                let seconds = if level > self.current {
+                    *attack
+                } else {
+                    *release
+                };
+                let coefficient = (-1.0 / (seconds.max(1e-4) * sample_rate)).exp();
+                self.current = level + (self.current - level) * coefficient;
+            }
+            _ => {}
+        }
+    }
+
+    /// Shaped and scaled value of the route, reading per-note sources from `note`
+    ///
+    /// Per-note sources are zero when no note is given.
+    #[inline]
+    pub(crate) fn value(&self, note: Option<&NoteContext>) -> f32 {
+        let raw = match &self.source {
+            ModSource::Lfo(_) | ModSource::Automation(_) | ModSource::EnvelopeFollower { .. } => {
+                self.current
+            }
+            ModSource::Envelope(envelope) => note.map_or(0.0, |note| {
+                envelope.amplitude_at(note.time_in_note, note.duration)
+            }),
+            ModSource::Velocity => note.map_or(0.0, |note| note.velocity),
+            ModSource::KeyTrack => note.map_or(0.0, |note| {
+                (note.frequency.max(1e-3) / KEY_TRACK_CENTER).log2()
+            }),
+            ModSource::Random => note.map_or(0.0, |note| note.random()),
+        };
+        self.curve.apply(raw) * self.amount
+    }
+}
+
+impl From<ModRoute> for MatrixRoute {
+    /// Carry an LFO route over, keeping the range its target had
+    fn from(route: ModRoute) -> Self {
+        let (destination, scale) = match route.target {
+            ModTarget::FilterCutoff => (ModDestination::FilterCutoff, 2.0),
+            ModTarget::FilterResonance => (ModDestination::FilterResonance, 0.5),
+            ModTarget::Volume => (ModDestination::Volume, 0.5),
+            ModTarget::Pitch => (ModDestination::Pitch, 1.0),
+            ModTarget::Pan => (ModDestination::Pan, 1.0),
+        };
+        Self::new(ModSource::Lfo(route.lfo), destination, route.amount * scale)
+    }
+}
+
+/// A track's modulation routes
+///
+/// See the [module documentation](self) for how sources and destinations combine.
+#[derive(Debug, Clone, Default)]
+pub struct ModMatrix {
+    pub routes: Vec<MatrixRoute>,
+}
+
+impl ModMatrix {
+    /// Create an empty matrix
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a route (builder pattern)
+    pub fn with_route(mut self, route: MatrixRoute) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Add a route
+    pub fn add_route(&mut self, route: MatrixRoute) {
+        self.routes.push(route);
+    }
+
+    /// Number of routes
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Check whether the matrix has no routes
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Check whether any route changes how individual notes are synthesized
+    pub fn modulates_notes(&self) -> bool {
+        self.routes
+            .iter()
+            .any(|route| route.destination.is_per_note())
+    }
+
+    /// Check whether any route reads from or changes individual notes
+    ///
+    /// Such notes have to be synthesized as they play, so they skip the sample cache.
+    pub fn reads_notes(&self) -> bool {
+        self.routes
+            .iter()
+            .any(|route| route.source.is_per_note() || route.destination.is_per_note())
+    }
+
+    /// Set the sample rate of every LFO source
+    pub fn prepare(&mut self, sample_rate: f32) {
+        for route in &mut self.routes {
+            if let ModSource::Lfo(lfo) = &mut route.source {
+                lfo.set_sample_rate(sample_rate);
+            }
+        }
+    }
+
+    /// Restart every LFO and clear envelope followers
+    pub fn reset(&mut self) {
+        for route in &mut self.routes {
+            if let ModSource::Lfo(lfo) = &mut route.source {
+                lfo.reset();
+            }
+            route.current = 0.0;
+        }
+    }
+
+    /// Advance the free-running sources by one sample
+    ///
+    /// `time` is the song time and `input` the signal the envelope followers track.
+    #[inline]
+    pub(crate) fn tick(&mut self, time: f32, input: f32, sample_rate: f32) {
+        for route in &mut self.routes {
+            route.tick(time, input, sample_rate);
+        }
+    }
+
+    /// Sum of the per-note destinations for one note
+    #[inline]
+    pub(crate) fn note_modulation(&self, note: &NoteContext) -> NoteModulation {
+        let mut modulation = NoteModulation::default();
+        for route in &self.routes {
+            let target = match route.destination {
+                ModDestination::Pitch => &mut modulation.pitch,
+                ModDestination::FmIndex => &mut modulation.fm_index,
+                ModDestination::PulseWidth => &mut modulation.pulse_width,
+                ModDestination::UnisonDetune => &mut modulation.unison_detune,
+                ModDestination::WavetablePosition => &mut modulation.wavetable_position,
+                _ => continue,
+            };
+            *target += route.value(Some(note));
+        }
+        modulation
+    }
+
+    /// Sum of the track destinations, reading per-note sources from `note`
+    #[inline]
+    pub(crate) fn track_modulation(&self, note: Option<&NoteContext>) -> TrackModulation {
+        let mut modulation = TrackModulation::default();
+        for route in &self.routes {
+            let target = match route.destination {
+                ModDestination::FilterCutoff => &mut modulation.cutoff,
+                ModDestination::FilterResonance => &mut modulation.resonance,
+                ModDestination::Volume => &mut modulation.volume,
+                ModDestination::Pan => &mut modulation.pan,
+                _ => continue,
+            };
+            *target += route.value(note);
+        }
+        modulation
+    }
+
+    /// Offsets of every modulated effect parameter as `(effect, parameter, offset)`,
+    /// with routes to the same parameter summed
+    ///
+    /// `offsets` is cleared and refilled, in the order the parameters first appear
+    /// in the routes, so it can be kept and reused from sample to sample.
+    pub(crate) fn effect_modulation(
+        &self,
+        note: Option<&NoteContext>,
+        offsets: &mut Vec<(&'static str, &'static str, f32)>,
+    ) {
+        offsets.clear();
+        for route in &self.routes {
+            if let ModDestination::Effect { effect, parameter } = route.destination {
+                let value = route.value(note);
+                match offsets
+                    .iter_mut()
+                    .find(|(e, p, _)| *e == effect && *p == parameter)
+                {
+                    Some((_, _, offset)) => *offset += value,
+                    None => offsets.push((effect, parameter, value)),
+                }
+            }
+        }
+    }
+}
+
+/// What per-note sources read from a note
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NoteContext {
+    pub velocity: f32,
+    /// Pitch of the note's first frequency in Hz
+    pub frequency: f32,
+    /// Identifies the note for its random value
+    pub seed: u32,
+    pub time_in_note: f32,
+    /// How long the note is held before release
+    pub duration: f32,
+}
+
+impl NoteContext {
+    /// The note's random value (-1.0 to 1.0)
+    #[inline]
+    fn random(&self) -> f32 {
+        // Integer hash (lowbias32) so every note gets its own value
+        let mut x = self.seed;
+        x ^= x >> 16;
+        x = x.wrapping_mul(0x7feb_352d);
+        x ^= x >> 15;
+        x = x.wrapping_mul(0x846c_a68b);
+        x ^= x >> 16;
+        (x >> 8) as f32 / (1u32 << 23) as f32 - 1.0
+    }
+}
+
+/// Per-note destination offsets for one sample
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct NoteModulation {
+    /// Semitones
+    pub pitch: f32,
+    pub fm_index: f32,
+    pub pulse_width: f32,
+    /// Cents
+    pub unison_detune: f32,
+    pub wavetable_position: f32,
+}
+
+/// Track destination offsets for one sample
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct TrackModulation {
+    /// Octaves
+    pub cutoff: f32,
+    pub resonance: f32,
+    /// Fraction of the track volume
+    pub volume: f32,
+    pub pan: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthesis::waveform::Waveform;
+
+    fn note(velocity: f32, frequency: f32, seed: u32) -> NoteContext {
+        NoteContext {
+            velocity,
+            frequency,
+            seed,
+            time_in_note: 0.0,
+            duration: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_curves_keep_sign_and_endpoints() {
+        for curve in [
+            ModCurve::Linear,
+            ModCurve::Exponential,
+            ModCurve::Logarithmic,
+            ModCurve::SCurve,
+            ModCurve::Stepped(4),
+        ] {
+            assert_eq!(curve.apply(0.0), 0.0);
+            assert!((curve.apply(1.0) - 1.0).abs() < 1e-6, "{:?}", curve);
+            assert!((curve.apply(-1.0) + 1.0).abs() < 1e-6, "{:?}", curve);
+        }
+        assert_eq!(ModCurve::Exponential.apply(0.5), 0.25);
+        assert!((ModCurve::Logarithmic.apply(0.25) - 0.5).abs() < 1e-6);
+        assert_eq!(ModCurve::Stepped(4).apply(0.6), 0.5);
+    }
+
+    #[test]
+    fn test_note_sources() {
+        let route = |source| MatrixRoute::new(source, ModDestination::Pitch, 2.0);
+
+        assert_eq!(
+            route(ModSource::Velocity).value(Some(&note(0.5, 440.0, 0))),
+            1.0
+        );
+        let octave_up =
+            route(ModSource::KeyTrack).value(Some(&note(1.0, KEY_TRACK_CENTER * 2.0, 0)));
+        assert!((octave_up - 2.0).abs() < 1e-4);
+
+        // Random values are repeatable per note and differ between notes
+        let random = route(ModSource::Random);
+        let a = random.value(Some(&note(1.0, 440.0, 1)));
+        assert_eq!(a, random.value(Some(&note(1.0, 440.0, 1))));
+        assert_ne!(a, random.value(Some(&note(1.0, 440.0, 2))));
+        assert!(a.abs() <= 2.0);
+
+        // Without a note, per-note sources are silent
+        assert_eq!(route(ModSource::Velocity).value(None), 0.0);
+    }
+
+    #[test]
+    fn test_destinations_are_split_between_notes_and_track() {
+        let mut matrix = ModMatrix::new()
+            .with_route(MatrixRoute::new(
+                ModSource::Velocity,
+                ModDestination::FmIndex,
+                3.0,
+            ))
+            .with_route(MatrixRoute::new(
+                ModSource::Velocity,
+                ModDestination::FilterCutoff,
+                1.0,
+            ))
+            .with_route(MatrixRoute::new(
+                ModSource::Velocity,
+                ModDestination::WavetablePosition,
+                0.5,
+            ))
+            .with_route(MatrixRoute::new(
+                ModSource::Automation(Automation::linear(&[(0.0, 0.0), (1.0, 1.0)])),
+                ModDestination::effect("Delay", "mix"),
+                0.5,
+            ))
+            .with_route(MatrixRoute::new(
+                ModSource::Automation(Automation::constant(1.0)),
+                ModDestination::effect("Delay", "mix"),
+                0.1,
+            ));
+        assert!(matrix.modulates_notes());
+
+        matrix.tick(0.5, 0.0, 44100.0);
+        let context = note(1.0, 440.0, 0);
+        assert_eq!(matrix.note_modulation(&context).fm_index, 3.0);
+        assert_eq!(matrix.note_modulation(&context).pitch, 0.0);
+        assert_eq!(matrix.note_modulation(&context).wavetable_position, 0.5);
+        assert_eq!(matrix.track_modulation(Some(&context)).cutoff, 1.0);
+
+        let mut effects = Vec::new();
+        matrix.effect_modulation(None, &mut effects);
+        assert_eq!(effects.len(), 1);
+        assert!((effects[0].2 - 0.35).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_envelope_follower_tracks_input() {
+        let mut matrix = ModMatrix::new().with_route(MatrixRoute::new(
+            ModSource::EnvelopeFollower {
+                attack: 0.001,
+                release: 0.05,
+            },
+            ModDestination::Volume,
+            1.0,
+        ));
+        for i in 0..441 {
+            matrix.tick(i as f32 / 44100.0, 0.8, 44100.0);
+        }
+        let loud = matrix.track_modulation(None).volume;
+        assert!((loud - 0.8).abs() < 0.01, "{}", loud);
+
+        for i in 0..441 {
+            matrix.tick(i as f32 / 44100.0, 0.0, 44100.0);
+        }
+        let falling = matrix.track_modulation(None).volume;
+        assert!(falling > 0.0 && falling < loud);
+    }
+
+    #[test]
+    fn test_lfo_routes_carry_over() {
+        let lfo = LFO::with_sample_rate(Waveform::Sine, 1.0, 1.0, 100.0);
+        let route = MatrixRoute::from(ModRoute::new(lfo, ModTarget::FilterCutoff, 0.5));
+        assert_eq!(route.destination, ModDestination::FilterCutoff);
+        assert_eq!(route.amount, 1.0);
+
+        let mut matrix = ModMatrix::new().with_route(route);
+        let peak = (0..100)
+            .map(|i| {
+                matrix.tick(i as f32 / 100.0, 0.0, 100.0);
+                matrix.track_modulation(None).cutoff
+            })
+            .fold(0.0f32, f32::max);
+        assert!((peak - 1.0).abs() < 0.01);
+    }
+}
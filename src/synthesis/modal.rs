@@ -0,0 +1,903 @@
+//! Modal synthesis
+//!
+//! A [`ModalResonator`] models a struck, shaken or rubbed object by its modes of
+//! vibration: a bank of tuned resonators, each ringing at its own frequency and
+//! dying away at its own rate, driven by an [`Exciter`]. Bells, gongs, bars,
+//! glasses and drum heads differ mostly in where their modes lie and how quickly
+//! each one decays.
+//!
+//! Modes can be:
+//! - **Set directly** with [`ModalResonator::with_mode`]
+//! - **Generated** from a [`Material`] and a [`Shape`] with
+//!   [`ModalResonator::from_material`], or one of the presets such as
+//!   [`ModalResonator::bell`]
+//! - **Analysed** from a recording with [`ModalResonator::from_sample`], which
+//!   finds the ringing partials of a hit and measures how fast each decays
+//!
+//! Mode frequencies are ratios of the note's frequency, so a resonator plays at
+//! any pitch. Set one on a track with
+//! [`TrackBuilder::resonator`](crate::composition::TrackBuilder::resonator) or on
+//! a note with [`NoteEvent::with_resonator`](crate::track::NoteEvent::with_resonator).
+//! Like [`PhysicalModel`](crate::synthesis::PhysicalModel)s, each note is rendered
+//! once and played back from the rendering. An object rings for as long as its
+//! modes do, however long the note is held; the note's envelope cuts it short.
+//!
+//! # Example
+//! ```
+//! use tunes::synthesis::modal::{Exciter, ModalResonator};
+//!
+//! let chime = ModalResonator::new()
+//!     .with_mode(1.0, 3.0, 1.0)
+//!     .with_mode(2.76, 1.5, 0.5)
+//!     .with_mode(5.40, 0.8, 0.25)
+//!     .with_exciter(Exciter::Mallet { hardness: 0.8 });
+//!
+//! let samples = chime.render(880.0, 0.8, 44100.0);
+//! assert!(samples.iter().all(|s| s.abs() <= 1.0));
+//! ```
+
+use super::render_cache::{RenderCache, Rendered};
+use super::sample::Sample;
+use crate::error::{Result, TunesError};
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+/// Longest a resonator rings, in seconds
+const MAX_RING_TIME: f32 = 12.0;
+
+/// How far past a mode's T60 rendering continues, so the tail fades out fully
+const RING_OVERHANG: f32 = 1.4;
+
+/// Level below which the end of a rendering is trimmed
+const SILENCE: f32 = 1e-4;
+
+/// Peak level of a note played at full velocity
+const OUTPUT_LEVEL: f32 = 0.8;
+
+/// Mallet contact time for the softest and the hardest mallet, in seconds
+const SOFT_CONTACT: f32 = 0.005;
+const HARD_CONTACT: f32 = 0.0002;
+
+/// Most samples of a recording searched for modes
+const ANALYSIS_LENGTH: usize = 65536;
+
+/// Spectral peaks this far below the strongest are not taken as modes, in dB
+const PEAK_FLOOR_DB: f32 = 50.0;
+
+/// Frame size and hop used to follow each mode's decay
+const DECAY_FRAME: usize = 4096;
+const DECAY_HOP: usize = 1024;
+
+/// Longest stretch of a recording followed while measuring decays, in seconds
+const MAX_ANALYSIS_TIME: f32 = 10.0;
+
+/// How far a mode is followed below its starting level, in dB
+const DECAY_RANGE_DB: f32 = 60.0;
+
+/// Shortest and longest decay a mode can be given, in seconds
+const MIN_DECAY: f32 = 0.01;
+const MAX_DECAY: f32 = 20.0;
+
+/// One mode of vibration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mode {
+    /// Frequency as a ratio of the note's frequency
+    pub ratio: f32,
+    /// Time to die away by 60 dB, in seconds
+    pub decay: f32,
+    /// Relative level (0.0 - 1.0)
+    pub gain: f32,
+}
+
+impl Mode {
+    /// Create a mode at `ratio` times the note's frequency
+    ///
+    /// # Arguments
+    /// * `ratio` - Frequency ratio to the note
+    /// * `decay` - Time to die away by 60 dB, in seconds
+    /// * `gain` - Relative level (0.0 - 1.0)
+    pub fn new(ratio: f32, decay: f32, gain: f32) -> Self {
+        Self {
+            ratio: ratio.max(0.0),
+            decay: decay.max(MIN_DECAY),
+            gain: gain.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// What sets the modes ringing
+#[derive(Debug, Clone)]
+pub enum Exciter {
+    /// A single click, which rings every mode at its full gain
+    Impulse,
+    /// A burst of white noise fading out over `duration` seconds, for scrapes,
+    /// shakes and brushes
+    NoiseBurst { duration: f32 },
+    /// A mallet or stick; harder mallets (0.0 - 1.0) touch for less time and
+    /// ring the higher modes more. Playing louder hardens the strike a little.
+    Mallet { hardness: f32 },
+    /// Any recording, played into the resonators
+    Sample(Sample),
+}
+
+/// Exciters are equal when their settings are; samples must share their data
+impl PartialEq for Exciter {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Exciter::Impulse, Exciter::Impulse) => true,
+            (Exciter::NoiseBurst { duration: a }, Exciter::NoiseBurst { duration: b }) => a == b,
+            (Exciter::Mallet { hardness: a }, Exciter::Mallet { hardness: b }) => a == b,
+            (Exciter::Sample(a), Exciter::Sample(b)) => {
+                Arc::ptr_eq(&a.data, &b.data)
+                    && a.channels == b.channels
+                    && a.sample_rate == b.sample_rate
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Exciter {
+    /// The excitation signal for one note
+    fn signal(&self, velocity: f32, sample_rate: f32) -> Vec<f32> {
+        match self {
+            Exciter::Impulse => vec![1.0],
+            Exciter::NoiseBurst { duration } => {
+                let length = ((duration.max(0.0) * sample_rate) as usize).max(1);
+                let mut state = 0x9E37_79B9u32;
+                (0..length)
+                    .map(|i| {
+                        state ^= state << 13;
+                        state ^= state >> 17;
+                        state ^= state << 5;
+                        let noise = state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+                        noise * (1.0 - i as f32 / length as f32)
+                    })
+                    .collect()
+            }
+            Exciter::Mallet { hardness } => {
+                let hardness = (hardness.clamp(0.0, 1.0) * (0.7 + 0.3 * velocity)).min(1.0);
+                let contact = SOFT_CONTACT + (HARD_CONTACT - SOFT_CONTACT) * hardness;
+                let length = ((contact * sample_rate) as usize).max(1);
+                let pulse: Vec<f32> = (0..length)
+                    .map(|i| (PI * (i as f32 + 0.5) / length as f32).sin())
+                    .collect();
+                let area: f32 = pulse.iter().sum();
+                pulse.into_iter().map(|s| s / area).collect()
+            }
+            Exciter::Sample(sample) => {
+                let mono = sample.to_mono();
+                let step = sample.sample_rate as f32 / sample_rate;
+                if mono.is_empty() || (step - 1.0).abs() < 1e-6 {
+                    return mono;
+                }
+                let length = (mono.len() as f32 / step) as usize;
+                (0..length)
+                    .map(|i| {
+                        let position = i as f32 * step;
+                        let index = position as usize;
+                        let fraction = position - index as f32;
+                        let a = mono[index];
+                        let b = mono.get(index + 1).copied().unwrap_or(0.0);
+                        a + (b - a) * fraction
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// What an object is made of: how long it rings, how much faster its higher
+/// modes die, and how hard it is struck by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Material {
+    Wood,
+    Metal,
+    Glass,
+    Stone,
+}
+
+impl Material {
+    /// (decay of the lowest mode in seconds, damping exponent, mallet hardness)
+    fn properties(self) -> (f32, f32, f32) {
+        match self {
+            Material::Wood => (0.6, 1.2, 0.4),
+            Material::Metal => (5.0, 0.4, 0.7),
+            Material::Glass => (3.0, 0.6, 0.9),
+            Material::Stone => (1.0, 0.9, 0.8),
+        }
+    }
+}
+
+/// The form of an object, which sets where its modes lie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// A uniform bar free at both ends, as in glockenspiels and tubular bells
+    Bar,
+    /// A bar undercut so its modes fall at 1:4:10, as in marimbas
+    TunedBar,
+    /// A flat circular plate, as in gongs and cymbals
+    Plate,
+    /// A circular membrane, as in drum heads
+    Membrane,
+    /// A church bell, with the hum an octave below the strike
+    Bell,
+    /// A bowl or wine glass
+    Bowl,
+}
+
+impl Shape {
+    /// (frequency ratio, gain) of each mode
+    fn modes(self) -> &'static [(f32, f32)] {
+        match self {
+            Shape::Bar => &[
+                (1.0, 1.0),
+                (2.756, 0.6),
+                (5.404, 0.4),
+                (8.933, 0.3),
+                (13.345, 0.2),
+                (18.638, 0.15),
+            ],
+            Shape::TunedBar => &[(1.0, 1.0), (3.99, 0.35), (9.95, 0.15), (17.6, 0.08)],
+            Shape::Plate => &[
+                (1.0, 1.0),
+                (1.73, 0.8),
+                (2.33, 0.7),
+                (3.91, 0.5),
+                (4.11, 0.5),
+                (6.30, 0.35),
+                (6.71, 0.3),
+                (8.95, 0.2),
+            ],
+            Shape::Membrane => &[
+                (1.0, 1.0),
+                (1.593, 0.8),
+                (2.136, 0.6),
+                (2.296, 0.5),
+                (2.653, 0.45),
+                (2.917, 0.35),
+                (3.156, 0.3),
+                (3.501, 0.25),
+            ],
+            Shape::Bell => &[
+                (0.5, 0.6),
+                (1.0, 0.8),
+                (1.183, 0.7),
+                (1.506, 0.5),
+                (2.0, 0.9),
+                (2.514, 0.4),
+                (2.662, 0.35),
+                (3.011, 0.3),
+                (4.166, 0.2),
+            ],
+            Shape::Bowl => &[
+                (1.0, 1.0),
+                (2.32, 0.5),
+                (4.25, 0.3),
+                (6.63, 0.2),
+                (9.38, 0.1),
+            ],
+        }
+    }
+}
+
+/// Modes found in a recording by [`ModalResonator::analyze`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModalAnalysis {
+    /// Frequency of the lowest mode in Hz, which the mode ratios are relative to
+    pub fundamental: f32,
+    /// Modes from lowest to highest, gains relative to the strongest
+    pub modes: Vec<Mode>,
+}
+
+impl ModalAnalysis {
+    /// A resonator ringing the analysed modes, struck with an impulse
+    pub fn into_resonator(self) -> ModalResonator {
+        ModalResonator::new()
+            .with_modes(self.modes)
+            .with_exciter(Exciter::Impulse)
+    }
+}
+
+/// A bank of tuned, decaying resonators driven by an exciter
+pub struct ModalResonator {
+    pub modes: Vec<Mode>,
+    pub exciter: Exciter,
+    renders: RenderCache<ModalResonator>,
+}
+
+impl ModalResonator {
+    /// An empty resonator struck by a medium mallet; add modes with [`Self::with_mode`]
+    pub fn new() -> Self {
+        Self {
+            modes: Vec::new(),
+            exciter: Exciter::Mallet { hardness: 0.5 },
+            renders: RenderCache::default(),
+        }
+    }
+
+    /// Add a mode at `ratio` times the note's frequency
+    ///
+    /// # Arguments
+    /// * `ratio` - Frequency ratio to the note
+    /// * `decay` - Time to die away by 60 dB, in seconds
+    /// * `gain` - Relative level (0.0 - 1.0)
+    pub fn with_mode(mut self, ratio: f32, decay: f32, gain: f32) -> Self {
+        self.modes.push(Mode::new(ratio, decay, gain));
+        self
+    }
+
+    /// Replace every mode
+    pub fn with_modes(mut self, modes: Vec<Mode>) -> Self {
+        self.modes = modes;
+        self
+    }
+
+    pub fn with_exciter(mut self, exciter: Exciter) -> Self {
+        self.exciter = exciter;
+        self
+    }
+
+    /// Multiply every mode's decay, to let an object ring longer or damp it
+    pub fn with_decay_scale(mut self, scale: f32) -> Self {
+        let scale = scale.max(0.0);
+        for mode in &mut self.modes {
+            mode.decay = (mode.decay * scale).max(MIN_DECAY);
+        }
+        self
+    }
+
+    /// Modes of `shape` ringing as `material` does, struck by a mallet
+    ///
+    /// Higher modes die away faster in damped materials such as wood, and ring
+    /// nearly as long as the lowest in metal and glass.
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::synthesis::modal::{Material, ModalResonator, Shape};
+    ///
+    /// let cowbell = ModalResonator::from_material(Material::Metal, Shape::Plate)
+    ///     .with_decay_scale(0.1);
+    /// assert_eq!(cowbell.modes.len(), 8);
+    /// ```
+    pub fn from_material(material: Material, shape: Shape) -> Self {
+        let (decay, damping, hardness) = material.properties();
+        let modes = shape
+            .modes()
+            .iter()
+            .map(|&(ratio, gain)| Mode::new(ratio, decay * ratio.max(1.0).powf(-damping), gain))
+            .collect();
+        Self::new()
+            .with_modes(modes)
+            .with_exciter(Exciter::Mallet { hardness })
+    }
+
+    /// A church bell
+    pub fn bell() -> Self {
+        Self::from_material(Material::Metal, Shape::Bell).with_decay_scale(1.2)
+    }
+
+    /// A large gong struck with a soft beater
+    pub fn gong() -> Self {
+        Self::from_material(Material::Metal, Shape::Plate)
+            .with_decay_scale(1.5)
+            .with_exciter(Exciter::Mallet { hardness: 0.25 })
+    }
+
+    /// A marimba bar
+    pub fn marimba() -> Self {
+        Self::from_material(Material::Wood, Shape::TunedBar).with_decay_scale(1.5)
+    }
+
+    /// A wine glass tapped with a fingernail
+    pub fn glass() -> Self {
+        Self::from_material(Material::Glass, Shape::Bowl)
+    }
+
+    /// A wood block struck with a stick
+    pub fn wood_block() -> Self {
+        Self::from_material(Material::Wood, Shape::Bar)
+            .with_decay_scale(0.25)
+            .with_exciter(Exciter::Mallet { hardness: 0.9 })
+    }
+
+    /// A resonator with the modes of a recorded hit
+    ///
+    /// Shorthand for [`Self::analyze`] followed by
+    /// [`ModalAnalysis::into_resonator`].
+    pub fn from_sample(sample: &Sample, max_modes: usize) -> Result<Self> {
+        Ok(Self::analyze(sample, max_modes)?.into_resonator())
+    }
+
+    /// Find the modes of a recorded hit
+    ///
+    /// The strongest spectral peaks after the loudest moment of the recording
+    /// are taken as modes, then each is followed over time to measure its decay
+    /// and its level at the strike. Works best on a single hit of a ringing
+    /// object, recorded dry. Stereo recordings are mixed down to mono.
+    ///
+    /// # Arguments
+    /// * `sample` - The recorded hit
+    /// * `max_modes` - Most modes to keep, the strongest first
+    ///
+    /// # Example
+    /// ```no_run
+    /// use tunes::synthesis::modal::ModalResonator;
+    /// use tunes::synthesis::Sample;
+    ///
+    /// let hit = Sample::from_file("samples/bell.wav")?;
+    /// let analysis = ModalResonator::analyze(&hit, 24)?;
+    /// println!("bell rings at {:.1} Hz", analysis.fundamental);
+    /// let bell = analysis.into_resonator();
+    /// # Ok::<(), tunes::error::TunesError>(())
+    /// ```
+    pub fn analyze(sample: &Sample, max_modes: usize) -> Result<ModalAnalysis> {
+        let mono = sample.to_mono();
+        let sample_rate = sample.sample_rate as f32;
+        let onset = mono
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .map_or(0, |(i, _)| i);
+        let signal = &mono[onset.min(mono.len())..];
+        if signal.len() < DECAY_FRAME {
+            return Err(TunesError::InvalidAudioFormat(format!(
+                "{} samples after the strike are too few to analyse, {} are needed",
+                signal.len(),
+                DECAY_FRAME
+            )));
+        }
+
+        let frequencies = find_peaks(signal, sample_rate, max_modes);
+        if frequencies.is_empty() {
+            return Err(TunesError::InvalidAudioFormat(
+                "no ringing partials found in sample".to_string(),
+            ));
+        }
+        let fundamental = frequencies.iter().copied().fold(f32::MAX, f32::min);
+
+        let signal = &signal[..signal.len().min((MAX_ANALYSIS_TIME * sample_rate) as usize)];
+        let measured: Vec<(f32, f32, f32)> = frequencies
+            .iter()
+            .map(|&frequency| {
+                let (decay, level) = measure_decay(signal, frequency, sample_rate);
+                (frequency, decay, level)
+            })
+            .collect();
+        let loudest = measured
+            .iter()
+            .map(|&(_, _, level)| level)
+            .fold(0.0f32, f32::max)
+            .max(f32::MIN_POSITIVE);
+
+        let mut modes: Vec<Mode> = measured
+            .into_iter()
+            .map(|(frequency, decay, level)| {
+                Mode::new(frequency / fundamental, decay, level / loudest)
+            })
+            .collect();
+        modes.sort_by(|a, b| a.ratio.total_cmp(&b.ratio));
+        Ok(ModalAnalysis { fundamental, modes })
+    }
+
+    /// Render one note from the strike until it dies away
+    ///
+    /// Modes above the Nyquist limit are left out. The loudest moment is scaled to
+    /// `velocity`, so harder playing is louder and, with a mallet, brighter.
+    ///
+    /// # Arguments
+    /// * `frequency` - Pitch in Hz, the frequency of a mode with ratio 1.0
+    /// * `velocity` - How hard the note is played (0.0 - 1.0)
+    /// * `sample_rate` - Sample rate in Hz
+    pub fn render(&self, frequency: f32, velocity: f32, sample_rate: f32) -> Vec<f32> {
+        let velocity = velocity.clamp(0.0, 1.0);
+        let excitation = self.exciter.signal(velocity, sample_rate);
+        let r60 = |decay: f32| (-(1000.0f32.ln()) / (decay * sample_rate)).exp();
+        let mut resonators: Vec<Resonator> = self
+            .modes
+            .iter()
+            .filter(|mode| {
+                let f = mode.ratio * frequency;
+                f > 0.0 && f < sample_rate * 0.45 && mode.gain > 0.0
+            })
+            .map(|mode| {
+                Resonator::new(
+                    mode.ratio * frequency,
+                    r60(mode.decay),
+                    mode.gain,
+                    sample_rate,
+                )
+            })
+            .collect();
+        let ring = self
+            .modes
+            .iter()
+            .map(|mode| mode.decay * RING_OVERHANG)
+            .fold(0.0f32, f32::max)
+            .min(MAX_RING_TIME);
+        if resonators.is_empty() || excitation.is_empty() {
+            return Vec::new();
+        }
+
+        let length = excitation.len() + (ring * sample_rate) as usize;
+        let mut samples: Vec<f32> = (0..length)
+            .map(|i| {
+                let input = excitation.get(i).copied().unwrap_or(0.0);
+                resonators.iter_mut().map(|r| r.tick(input)).sum()
+            })
+            .collect();
+
+        let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        if peak > 0.0 {
+            let scale = OUTPUT_LEVEL * velocity / peak;
+            for sample in &mut samples {
+                *sample *= scale;
+            }
+        }
+        let end = samples
+            .iter()
+            .rposition(|s| s.abs() >= SILENCE)
+            .map_or(0, |i| i + 1);
+        samples.truncate(end);
+        samples
+    }
+
+    /// Sample a note at `time` seconds in
+    ///
+    /// The note is rendered on first use and kept, so later calls for the same
+    /// note only read the rendering. Returns 0.0 once the note has died away.
+    ///
+    /// # Arguments
+    /// * `frequency` - Pitch in Hz
+    /// * `time` - Seconds since the note started
+    /// * `velocity` - How hard the note is played (0.0 - 1.0)
+    /// * `sample_rate` - Sample rate in Hz
+    pub fn sample(&self, frequency: f32, time: f32, velocity: f32, sample_rate: f32) -> f32 {
+        self.renders
+            .sample(self, frequency, time, 0.0, velocity, sample_rate)
+    }
+
+    /// Render a note ahead of playback, keeping it cached while the result is held
+    pub(crate) fn prerender(&self, frequency: f32, velocity: f32, sample_rate: f32) -> Arc<[f32]> {
+        self.renders.render(self, frequency, 0.0, velocity, sample_rate)
+    }
+}
+
+impl Default for ModalResonator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rendered for ModalResonator {
+    fn settings(&self) -> Self {
+        Self {
+            modes: self.modes.clone(),
+            exciter: self.exciter.clone(),
+            renders: RenderCache::default(),
+        }
+    }
+
+    fn render_note(
+        &self,
+        frequency: f32,
+        _duration: f32,
+        velocity: f32,
+        sample_rate: f32,
+    ) -> Vec<f32> {
+        self.render(frequency, velocity, sample_rate)
+    }
+}
+
+/// Clones share their renderings, so every note of a track's resonator is cached once
+impl Clone for ModalResonator {
+    fn clone(&self) -> Self {
+        Self {
+            renders: self.renders.clone(),
+            ..self.settings()
+        }
+    }
+}
+
+impl std::fmt::Debug for ModalResonator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModalResonator")
+            .field("modes", &self.modes)
+            .field("exciter", &self.exciter)
+            .finish()
+    }
+}
+
+/// Resonators are equal when their settings are, whatever they have cached
+impl PartialEq for ModalResonator {
+    fn eq(&self, other: &Self) -> bool {
+        self.modes == other.modes && self.exciter == other.exciter
+    }
+}
+
+/// Two-pole resonator whose impulse response is a decaying sine of peak `gain`
+struct Resonator {
+    a1: f32,
+    a2: f32,
+    b0: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl Resonator {
+    fn new(frequency: f32, radius: f32, gain: f32, sample_rate: f32) -> Self {
+        let omega = 2.0 * PI * frequency / sample_rate;
+        Self {
+            a1: 2.0 * radius * omega.cos(),
+            a2: -radius * radius,
+            b0: gain * omega.sin(),
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, input: f32) -> f32 {
+        let output = self.b0 * input + self.a1 * self.y1 + self.a2 * self.y2;
+        self.y2 = self.y1;
+        self.y1 = output;
+        output
+    }
+}
+
+/// Frequencies of the strongest spectral peaks, at most `max_peaks` of them
+fn find_peaks(signal: &[f32], sample_rate: f32, max_peaks: usize) -> Vec<f32> {
+    let length = signal.len().min(ANALYSIS_LENGTH);
+    let size = length.next_power_of_two();
+    let mut spectrum: Vec<Complex<f32>> = (0..size)
+        .map(|i| {
+            let value = if i < length {
+                signal[i] * hann(i, length)
+            } else {
+                0.0
+            };
+            Complex::new(value, 0.0)
+        })
+        .collect();
+    FftPlanner::new()
+        .plan_fft_forward(size)
+        .process(&mut spectrum);
+
+    let magnitudes: Vec<f32> = spectrum[..size / 2].iter().map(|c| c.norm()).collect();
+    let strongest = magnitudes.iter().copied().fold(0.0f32, f32::max);
+    if strongest <= 0.0 {
+        return Vec::new();
+    }
+    let floor = strongest * 10.0f32.powf(-PEAK_FLOOR_DB / 20.0);
+    let bin_width = sample_rate / size as f32;
+
+    let mut peaks: Vec<(f32, f32)> = (2..magnitudes.len().saturating_sub(2))
+        .filter(|&i| {
+            let m = magnitudes[i];
+            m > floor
+                && m > magnitudes[i - 1]
+                && m > magnitudes[i + 1]
+                && m >= magnitudes[i - 2]
+                && m >= magnitudes[i + 2]
+        })
+        .map(|i| {
+            // Parabolic interpolation on log magnitude finds the peak between bins
+            let (a, b, c) = (
+                magnitudes[i - 1].max(f32::MIN_POSITIVE).ln(),
+                magnitudes[i].ln(),
+                magnitudes[i + 1].max(f32::MIN_POSITIVE).ln(),
+            );
+            let denominator = a - 2.0 * b + c;
+            let offset = if denominator.abs() > f32::EPSILON {
+                0.5 * (a - c) / denominator
+            } else {
+                0.0
+            };
+            ((i as f32 + offset) * bin_width, magnitudes[i])
+        })
+        .filter(|&(frequency, _)| frequency >= 20.0 && frequency < sample_rate * 0.45)
+        .collect();
+    peaks.sort_by(|a, b| b.1.total_cmp(&a.1));
+    peaks.truncate(max_peaks);
+    peaks.into_iter().map(|(frequency, _)| frequency).collect()
+}
+
+/// Decay time and level at the strike of the partial at `frequency`
+///
+/// The partial's level is measured frame by frame until it has fallen
+/// `DECAY_RANGE_DB`, and a straight line fitted to its level in dB gives both
+/// the decay rate and, extrapolated back, the level at the start.
+fn measure_decay(signal: &[f32], frequency: f32, sample_rate: f32) -> (f32, f32) {
+    let omega = 2.0 * PI * frequency / sample_rate;
+    let window: Vec<f32> = (0..DECAY_FRAME).map(|i| hann(i, DECAY_FRAME)).collect();
+    let window_sum: f32 = window.iter().sum();
+    let (cos, sin): (Vec<f32>, Vec<f32>) = (0..DECAY_FRAME)
+        .map(|i| {
+            let phase = omega * i as f32;
+            (phase.cos() * window[i], phase.sin() * window[i])
+        })
+        .unzip();
+
+    let mut points: Vec<(f32, f32)> = Vec::new();
+    let mut start = 0;
+    while start + DECAY_FRAME <= signal.len() {
+        let frame = &signal[start..start + DECAY_FRAME];
+        let re: f32 = frame.iter().zip(&cos).map(|(x, c)| x * c).sum();
+        let im: f32 = frame.iter().zip(&sin).map(|(x, s)| x * s).sum();
+        let amplitude = 2.0 * (re * re + im * im).sqrt() / window_sum;
+        let level = 20.0 * amplitude.max(1e-12).log10();
+        if points
+            .first()
+            .is_some_and(|&(_, first)| level < first - DECAY_RANGE_DB)
+        {
+            break;
+        }
+        let time = (start + DECAY_FRAME / 2) as f32 / sample_rate;
+        points.push((time, level));
+        start += DECAY_HOP;
+    }
+
+    if points.len() < 2 {
+        let level = points.first().map_or(0.0, |&(_, level)| level);
+        return (MIN_DECAY, 10.0f32.powf(level / 20.0));
+    }
+    let n = points.len() as f32;
+    let mean_time = points.iter().map(|p| p.0).sum::<f32>() / n;
+    let mean_level = points.iter().map(|p| p.1).sum::<f32>() / n;
+    let covariance: f32 = points
+        .iter()
+        .map(|p| (p.0 - mean_time) * (p.1 - mean_level))
+        .sum();
+    let variance: f32 = points.iter().map(|p| (p.0 - mean_time).powi(2)).sum();
+    let slope = covariance / variance;
+    let intercept = mean_level - slope * mean_time;
+
+    let decay = if slope < 0.0 {
+        (-DECAY_RANGE_DB / slope).clamp(MIN_DECAY, MAX_DECAY)
+    } else {
+        MAX_DECAY
+    };
+    (decay, 10.0f32.powf(intercept / 20.0))
+}
+
+#[inline]
+fn hann(i: usize, length: usize) -> f32 {
+    0.5 - 0.5 * (2.0 * PI * i as f32 / length as f32).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44100.0;
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len().max(1) as f32).sqrt()
+    }
+
+    #[test]
+    fn test_mode_rings_at_pitch_and_decays() {
+        let resonator = ModalResonator::new()
+            .with_mode(1.0, 1.0, 1.0)
+            .with_exciter(Exciter::Impulse);
+        let samples = resonator.render(440.0, 1.0, SR);
+
+        let second = (SR * 0.5) as usize..(SR * 0.6) as usize;
+        let crossings = samples[second.clone()]
+            .windows(2)
+            .filter(|w| w[0] <= 0.0 && w[1] > 0.0)
+            .count();
+        assert!(
+            (43..=45).contains(&crossings),
+            "{} cycles in 0.1s",
+            crossings
+        );
+
+        // One T60 on, the level has fallen by 60 dB
+        let early = rms(&samples[..(SR * 0.1) as usize]);
+        let late = rms(&samples[SR as usize..(SR * 1.1) as usize]);
+        let drop = 20.0 * (early / late).log10();
+        assert!((drop - 60.0).abs() < 3.0, "fell {} dB", drop);
+    }
+
+    #[test]
+    fn test_presets_ring_within_range() {
+        for resonator in [
+            ModalResonator::bell(),
+            ModalResonator::gong(),
+            ModalResonator::marimba(),
+            ModalResonator::glass(),
+            ModalResonator::wood_block(),
+        ] {
+            let samples = resonator.render(220.0, 0.9, SR);
+            let peak = samples.iter().fold(0.0f32, |p, s| p.max(s.abs()));
+            assert!((peak - OUTPUT_LEVEL * 0.9).abs() < 1e-3, "{:?}", resonator);
+            assert!(samples.len() < (MAX_RING_TIME * SR) as usize + 1000);
+        }
+        // Wood dies away long before metal
+        assert!(
+            ModalResonator::marimba().render(220.0, 1.0, SR).len()
+                < ModalResonator::bell().render(220.0, 1.0, SR).len() / 3
+        );
+    }
+
+    #[test]
+    fn test_harder_mallet_is_brighter() {
+        let brightness = |hardness: f32| {
+            let samples = ModalResonator::from_material(Material::Metal, Shape::Bar)
+                .with_exciter(Exciter::Mallet { hardness })
+                .render(440.0, 1.0, SR);
+            let window = &samples[..(SR * 0.2) as usize];
+            window
+                .windows(2)
+                .filter(|w| (w[0] <= 0.0) != (w[1] <= 0.0))
+                .count()
+        };
+        assert!(brightness(1.0) > brightness(0.0));
+    }
+
+    #[test]
+    fn test_sample_exciter_rings_the_modes() {
+        let noise: Vec<f32> = Exciter::NoiseBurst { duration: 0.05 }.signal(1.0, SR);
+        let resonator =
+            ModalResonator::glass().with_exciter(Exciter::Sample(Sample::from_mono(noise, 22050)));
+        let samples = resonator.render(660.0, 0.5, SR);
+        // The 0.05 s burst at half the rate lasts 0.1 s; the glass rings on well past it
+        assert!(samples.len() > SR as usize);
+        assert!(rms(&samples[(SR * 0.5) as usize..(SR * 0.6) as usize]) > 1e-3);
+    }
+
+    #[test]
+    fn test_analysis_recovers_modes() {
+        let original = ModalResonator::new()
+            .with_mode(1.0, 1.5, 1.0)
+            .with_mode(2.756, 0.8, 0.5)
+            .with_mode(5.404, 0.4, 0.25)
+            .with_exciter(Exciter::Impulse);
+        let hit = Sample::from_mono(original.render(220.0, 1.0, SR), SR as u32);
+
+        let analysis = ModalResonator::analyze(&hit, 3).unwrap();
+        assert!((analysis.fundamental - 220.0).abs() < 0.5);
+        assert_eq!(analysis.modes.len(), 3);
+        for (found, expected) in analysis.modes.iter().zip(&original.modes) {
+            assert!(
+                (found.ratio / expected.ratio - 1.0).abs() < 0.005,
+                "{:?}",
+                found
+            );
+            assert!(
+                (found.decay / expected.decay - 1.0).abs() < 0.15,
+                "{:?}",
+                found
+            );
+            assert!(
+                (found.gain / expected.gain - 1.0).abs() < 0.2,
+                "{:?}",
+                found
+            );
+        }
+
+        let resonator = analysis.into_resonator();
+        assert_eq!(resonator.exciter, Exciter::Impulse);
+        assert!(!resonator.render(220.0, 1.0, SR).is_empty());
+    }
+
+    #[test]
+    fn test_analysis_rejects_short_or_silent_samples() {
+        assert!(ModalResonator::analyze(&Sample::from_mono(vec![0.5; 100], 44100), 8).is_err());
+        assert!(ModalResonator::analyze(&Sample::from_mono(vec![0.0; 8192], 44100), 8).is_err());
+    }
+
+    #[test]
+    fn test_sample_reads_the_rendering() {
+        let resonator = ModalResonator::wood_block();
+        let samples = resonator.render(330.0, 0.7, SR);
+        let clone = resonator.clone();
+        assert_eq!(resonator.sample(330.0, 100.0 / SR, 0.7, SR), samples[100]);
+        assert_eq!(clone.sample(330.0, 100.0 / SR, 0.7, SR), samples[100]);
+        assert_eq!(resonator.sample(330.0, -1.0, 0.7, SR), 0.0);
+        assert_eq!(resonator.sample(330.0, 60.0, 0.7, SR), 0.0);
+    }
+}
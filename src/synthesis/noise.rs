@@ -47,29 +47,48 @@ impl NoiseType {
     /// # Returns
     /// Vector of samples in the range [-1.0, 1.0]
     pub fn generate(self, length: usize) -> Vec<f32> {
+        self.generate_with_rng(length, &mut rand::rng())
+    }
+
+    /// Generate noise samples of this type, seeded from `rng`
+    ///
+    /// Pass [`Composition::rng`](crate::composition::Composition::rng) so the noise
+    /// follows the composition seed.
+    pub fn generate_with_rng<R: Rng + ?Sized>(self, length: usize, rng: &mut R) -> Vec<f32> {
+        self.generate_with_seed(length, rng.random())
+    }
+
+    /// Generate noise samples of this type from a specific seed
+    ///
+    /// The same seed always produces the same samples.
+    ///
+    /// # Arguments
+    /// * `length` - Number of samples to generate
+    /// * `seed` - Seed for the underlying generator
+    pub fn generate_with_seed(self, length: usize, seed: u64) -> Vec<f32> {
         match self {
             NoiseType::White => {
-                let mut gen = WhiteNoise::new();
+                let mut gen = WhiteNoise::with_seed(seed);
                 gen.generate(length)
             }
             NoiseType::Brown => {
-                let mut gen = BrownNoise::new();
+                let mut gen = BrownNoise::with_seed(seed);
                 gen.generate(length)
             }
             NoiseType::Pink => {
-                let mut gen = PinkNoise::new();
+                let mut gen = PinkNoise::with_seed(seed);
                 gen.generate(length)
             }
             NoiseType::Blue => {
-                let mut gen = BlueNoise::new();
+                let mut gen = BlueNoise::with_seed(seed);
                 gen.generate(length)
             }
             NoiseType::Green => {
-                let mut gen = GreenNoise::new();
+                let mut gen = GreenNoise::with_seed(seed);
                 gen.generate(length)
             }
             NoiseType::Perlin => {
-                let mut gen = PerlinNoise::new();
+                let mut gen = PerlinNoise::with_seed(seed as u32);
                 gen.generate(length)
             }
         }
@@ -126,9 +145,24 @@ pub struct WhiteNoise {
 impl WhiteNoise {
     /// Create a new white noise generator with random seed
     pub fn new() -> Self {
-        Self {
-            rng: rand::rngs::StdRng::from_rng(&mut rand::rng()),
-        }
+        Self::with_rng(&mut rand::rng())
+    }
+
+    /// Create a new white noise generator seeded from `rng`
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::prelude::*;
+    /// use tunes::synthesis::noise::WhiteNoise;
+    ///
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.set_seed(7);
+    /// // Follows the composition seed, like the rest of the take
+    /// let mut noise = WhiteNoise::with_rng(comp.rng());
+    /// let samples = noise.generate(512);
+    /// ```
+    pub fn with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_seed(rng.random())
     }
 
     /// Create a new white noise generator with a specific seed
@@ -212,11 +246,12 @@ impl BrownNoise {
     /// Uses a default step size of 0.05 which provides good balance
     /// between smoothness and variation.
     pub fn new() -> Self {
-        Self {
-            current: 0.0,
-            step_size: 0.05,
-            rng: rand::rngs::StdRng::from_rng(&mut rand::rng()),
-        }
+        Self::with_rng(&mut rand::rng())
+    }
+
+    /// Create a new brown noise generator seeded from `rng`
+    pub fn with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_seed(rng.random())
     }
 
     /// Create a new brown noise generator with a specific seed
@@ -261,9 +296,8 @@ impl BrownNoise {
     /// ```
     pub fn with_step_size(step_size: f32) -> Self {
         Self {
-            current: 0.0,
             step_size,
-            rng: rand::rngs::StdRng::from_rng(&mut rand::rng()),
+            ..Self::new()
         }
     }
 
@@ -355,12 +389,12 @@ pub struct PinkNoise {
 impl PinkNoise {
     /// Create a new pink noise generator
     pub fn new() -> Self {
-        Self {
-            white: WhiteNoise::new(),
-            rows: [0.0; 7],
-            running_sum: 0.0,
-            updates: 0,
-        }
+        Self::with_rng(&mut rand::rng())
+    }
+
+    /// Create a new pink noise generator seeded from `rng`
+    pub fn with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_seed(rng.random())
     }
 
     /// Create with a specific seed for deterministic output
@@ -435,10 +469,12 @@ pub struct BlueNoise {
 impl BlueNoise {
     /// Create a new blue noise generator
     pub fn new() -> Self {
-        Self {
-            white: WhiteNoise::new(),
-            prev: 0.0,
-        }
+        Self::with_rng(&mut rand::rng())
+    }
+
+    /// Create a new blue noise generator seeded from `rng`
+    pub fn with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_seed(rng.random())
     }
 
     /// Create with a specific seed
@@ -502,10 +538,12 @@ pub struct GreenNoise {
 impl GreenNoise {
     /// Create a new green noise generator
     pub fn new() -> Self {
-        Self {
-            pink: PinkNoise::new(),
-            prev: 0.0,
-        }
+        Self::with_rng(&mut rand::rng())
+    }
+
+    /// Create a new green noise generator seeded from `rng`
+    pub fn with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_seed(rng.random())
     }
 
     /// Create with a specific seed
@@ -593,12 +631,12 @@ pub struct PerlinNoise {
 impl PerlinNoise {
     /// Create a new Perlin noise generator with random seed
     pub fn new() -> Self {
-        let mut rng = rand::rngs::StdRng::from_rng(&mut rand::rng());
-        Self {
-            x: 0.0,
-            frequency: 0.02, // Default frequency for smooth variation
-            seed: rng.random_range(0..u32::MAX),
-        }
+        Self::with_rng(&mut rand::rng())
+    }
+
+    /// Create a new Perlin noise generator seeded from `rng`
+    pub fn with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::with_seed(rng.random())
     }
 
     /// Create with a specific seed for deterministic output
@@ -628,11 +666,9 @@ impl PerlinNoise {
     /// let mut texture = PerlinNoise::with_frequency(0.05);
     /// ```
     pub fn with_frequency(frequency: f32) -> Self {
-        let mut rng = rand::rngs::StdRng::from_rng(&mut rand::rng());
         Self {
-            x: 0.0,
             frequency,
-            seed: rng.random_range(0..u32::MAX),
+            ..Self::new()
         }
     }
 
@@ -1055,6 +1091,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_noise_type_seeded_deterministic() {
+        for noise_type in [
+            NoiseType::White,
+            NoiseType::Brown,
+            NoiseType::Pink,
+            NoiseType::Blue,
+            NoiseType::Green,
+            NoiseType::Perlin,
+        ] {
+            assert_eq!(
+                noise_type.generate_with_seed(256, 7),
+                noise_type.generate_with_seed(256, 7),
+                "{:?} should be deterministic for a fixed seed",
+                noise_type
+            );
+        }
+    }
+
     #[test]
     fn test_perlin_noise_frequency() {
         // Higher frequency should create faster variation
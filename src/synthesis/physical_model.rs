@@ -0,0 +1,1086 @@
+//! Digital waveguide physical models
+//!
+//! A [`PhysicalModel`] simulates the vibrating part of an instrument and the way
+//! it is played, rather than shaping an oscillator. Travelling waves run through
+//! delay lines whose length sets the pitch, losing energy and high frequencies at
+//! every reflection, and an exciter keeps feeding them:
+//!
+//! - **Bowed string**: a stick-slip bow friction curve drives two delay lines,
+//!   one either side of the bow
+//! - **Plucked string**: a single string loop excited by a shaped pluck, a step
+//!   beyond [`KarplusStrong`](crate::synthesis::KarplusStrong) with pluck position,
+//!   stiffness and body
+//! - **Blown pipe**: an air jet across a bore, as in flutes, piccolos and flue
+//!   organ pipes
+//! - **Struck bar**: a banded waveguide, one tuned loop per bar mode, for
+//!   marimbas, xylophones and vibraphones
+//!
+//! Every model exposes the same controls: where it is excited, how much it is
+//! damped, how stiff it is (dispersion for strings and pipes, inharmonic modes
+//! for bars), how hard it is played, and the [`BodyResonance`] it sounds through.
+//!
+//! Models are played as notes: set one on a track with
+//! [`TrackBuilder::physical_model`](crate::composition::TrackBuilder::physical_model)
+//! or on a note with [`NoteEvent::with_physical_model`](crate::track::NoteEvent::with_physical_model).
+//! Each note is simulated once and played back from the rendering, so glides,
+//! pitch bends and unison bend the rendered note rather than re-running the model.
+//!
+//! # Example
+//! ```
+//! use tunes::synthesis::physical_model::{BodyResonance, PhysicalModel};
+//!
+//! let violin = PhysicalModel::bowed_string()
+//!     .with_excitation_position(0.12)
+//!     .with_pressure(0.6)
+//!     .with_body(BodyResonance::violin());
+//!
+//! // One second of A4, held for 0.8 seconds at velocity 0.8
+//! let samples = violin.render(440.0, 0.8, 0.8, 44100.0);
+//! assert!(samples.iter().all(|s| s.abs() <= 1.0));
+//! ```
+
+use super::render_cache::{RenderCache, Rendered};
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+/// Longest a model rings on after its note ends, in seconds
+const MAX_RING_TIME: f32 = 6.0;
+
+/// Level below which a ringing model counts as silent
+const SILENCE: f32 = 1e-4;
+
+/// Seconds of silence after the note ends before a rendering stops
+const SILENT_TAIL: f32 = 0.05;
+
+/// Seconds a lightly damped plucked string takes to die away by 60 dB
+const PLUCK_DECAY: f32 = 12.0;
+
+/// Mode frequencies of a free uniform bar, relative to its fundamental
+const BAR_MODES: [f32; 4] = [1.0, 2.756, 5.404, 8.933];
+
+/// Impulse a bar receives from a full-velocity strike, per sample of its period
+const STRIKE_FORCE: f32 = 1.25;
+
+/// Sharpness of the band-pass filter that keeps each bar loop to its own mode
+const BAR_MODE_Q: f32 = 20.0;
+
+/// The vibrating object and how it is played
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModelKind {
+    /// String driven by a bow's stick-slip friction
+    BowedString,
+    /// String released from a pluck
+    PluckedString,
+    /// Bore driven by an air jet, like a flute or flue pipe
+    BlownPipe,
+    /// Bar or plate hit by a mallet
+    StruckBar,
+}
+
+/// One resonance of an instrument body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodyMode {
+    /// Centre frequency in Hz
+    pub frequency: f32,
+    /// Sharpness of the resonance (higher = narrower)
+    pub q: f32,
+    /// Gain of the resonance
+    pub gain: f32,
+}
+
+/// Resonances of the body a model sounds through
+///
+/// A string alone is thin; most of an acoustic instrument's character comes from
+/// the wooden body or resonator tube it drives. Each [`BodyMode`] is a resonant
+/// band-pass filter, mixed in alongside the dry model.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BodyResonance {
+    pub modes: Vec<BodyMode>,
+    /// Balance of body resonances to dry model (0.0 = dry, 1.0 = body only)
+    pub mix: f32,
+}
+
+impl BodyResonance {
+    /// No body, the model is heard dry
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// An empty body with the given mix, to add modes to
+    pub fn new(mix: f32) -> Self {
+        Self {
+            modes: Vec::new(),
+            mix: mix.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Add a resonance
+    ///
+    /// # Arguments
+    /// * `frequency` - Centre frequency in Hz
+    /// * `q` - Sharpness of the resonance
+    /// * `gain` - Gain of the resonance
+    pub fn with_mode(mut self, frequency: f32, q: f32, gain: f32) -> Self {
+        self.modes.push(BodyMode {
+            frequency,
+            q: q.max(0.1),
+            gain,
+        });
+        self
+    }
+
+    /// Check whether the body changes the sound
+    pub fn is_active(&self) -> bool {
+        self.mix > 0.0 && !self.modes.is_empty()
+    }
+
+    /// Violin body: air resonance near 275 Hz, main wood modes and the bridge hill
+    pub fn violin() -> Self {
+        Self::new(0.6)
+            .with_mode(275.0, 8.0, 1.0)
+            .with_mode(460.0, 10.0, 1.2)
+            .with_mode(550.0, 10.0, 1.0)
+            .with_mode(2500.0, 2.0, 0.8)
+    }
+
+    /// Viola body, a little lower and darker than the violin
+    pub fn viola() -> Self {
+        Self::new(0.6)
+            .with_mode(230.0, 8.0, 1.0)
+            .with_mode(380.0, 10.0, 1.2)
+            .with_mode(480.0, 10.0, 1.0)
+            .with_mode(2200.0, 2.0, 0.6)
+    }
+
+    /// Cello body
+    pub fn cello() -> Self {
+        Self::new(0.6)
+            .with_mode(100.0, 8.0, 1.0)
+            .with_mode(170.0, 10.0, 1.2)
+            .with_mode(220.0, 10.0, 1.0)
+            .with_mode(1000.0, 2.0, 0.6)
+    }
+
+    /// Double bass body
+    pub fn double_bass() -> Self {
+        Self::new(0.55)
+            .with_mode(60.0, 6.0, 1.0)
+            .with_mode(100.0, 8.0, 1.2)
+            .with_mode(135.0, 8.0, 1.0)
+            .with_mode(800.0, 2.0, 0.5)
+    }
+
+    /// Acoustic guitar or harp soundboard
+    pub fn guitar() -> Self {
+        Self::new(0.5)
+            .with_mode(100.0, 6.0, 1.0)
+            .with_mode(200.0, 8.0, 1.0)
+            .with_mode(400.0, 6.0, 0.7)
+            .with_mode(3000.0, 1.5, 0.4)
+    }
+
+    /// Resonator tubes under the bars of a marimba or vibraphone
+    pub fn resonator_tube() -> Self {
+        Self::new(0.35)
+            .with_mode(200.0, 2.0, 1.0)
+            .with_mode(600.0, 2.0, 0.5)
+    }
+}
+
+/// A waveguide model of an acoustic instrument, played as notes
+///
+/// See the [module documentation](self) for the four kinds of model. All
+/// controls run from 0.0 to 1.0.
+pub struct PhysicalModel {
+    pub kind: ModelKind,
+    /// Where the exciter meets the instrument: bow or pluck distance from the
+    /// bridge, jet length relative to the bore, or strike point along the bar
+    pub excitation_position: f32,
+    /// Energy and brightness lost on every reflection (higher = shorter, darker)
+    pub damping: f32,
+    /// Dispersion of strings and pipes, or how far a bar's modes are stretched
+    /// from harmonic towards those of a free bar
+    pub stiffness: f32,
+    /// Bow pressure, breath pressure, or pluck and mallet hardness
+    pub pressure: f32,
+    pub body: BodyResonance,
+    renders: RenderCache<PhysicalModel>,
+}
+
+impl PhysicalModel {
+    /// Create a model with the usual settings for its kind
+    pub fn new(kind: ModelKind) -> Self {
+        let (excitation_position, damping, stiffness) = match kind {
+            ModelKind::BowedString => (0.127, 0.5, 0.1),
+            ModelKind::PluckedString => (0.2, 0.3, 0.05),
+            ModelKind::BlownPipe => (0.5, 0.5, 0.0),
+            ModelKind::StruckBar => (0.3, 0.5, 1.0),
+        };
+        Self {
+            kind,
+            excitation_position,
+            damping,
+            stiffness,
+            pressure: 0.5,
+            body: BodyResonance::none(),
+            renders: RenderCache::default(),
+        }
+    }
+
+    /// A bowed string
+    pub fn bowed_string() -> Self {
+        Self::new(ModelKind::BowedString)
+    }
+
+    /// A plucked string
+    pub fn plucked_string() -> Self {
+        Self::new(ModelKind::PluckedString)
+    }
+
+    /// A jet-driven pipe such as a flute
+    pub fn blown_pipe() -> Self {
+        Self::new(ModelKind::BlownPipe)
+    }
+
+    /// A bar hit by a mallet
+    pub fn struck_bar() -> Self {
+        Self::new(ModelKind::StruckBar)
+    }
+
+    /// Set where the instrument is excited (0.0 - 1.0)
+    pub fn with_excitation_position(mut self, position: f32) -> Self {
+        self.excitation_position = position.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the losses per reflection (0.0 = rings longest, 1.0 = dead)
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the stiffness (0.0 = ideal, harmonic; 1.0 = stiff, inharmonic)
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the bow or breath pressure, or pluck and mallet hardness (0.0 - 1.0)
+    pub fn with_pressure(mut self, pressure: f32) -> Self {
+        self.pressure = pressure.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the body the model sounds through
+    pub fn with_body(mut self, body: BodyResonance) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Simulate a note from start to silence
+    ///
+    /// Bows and breath keep driving the model for `duration` seconds; after that,
+    /// or straight away for plucks and strikes, it rings on until it dies away.
+    ///
+    /// # Arguments
+    /// * `frequency` - Pitch in Hz
+    /// * `duration` - How long the note is held, in seconds
+    /// * `velocity` - How hard the note is played (0.0 - 1.0)
+    /// * `sample_rate` - Sample rate in Hz
+    pub fn render(
+        &self,
+        frequency: f32,
+        duration: f32,
+        velocity: f32,
+        sample_rate: f32,
+    ) -> Vec<f32> {
+        let frequency = frequency.clamp(1.0, sample_rate * 0.45);
+        let velocity = velocity.clamp(0.0, 1.0);
+        let duration = duration.max(0.0);
+        let mut samples = match self.kind {
+            ModelKind::BowedString => {
+                let mut string = BowedString::new(self, frequency, velocity, sample_rate);
+                run(duration, sample_rate, |time| string.tick(time < duration))
+            }
+            ModelKind::PluckedString => {
+                let mut string = PluckedString::new(self, frequency, velocity, sample_rate);
+                run(duration, sample_rate, |_| string.tick())
+            }
+            ModelKind::BlownPipe => {
+                let mut pipe = BlownPipe::new(self, frequency, velocity, sample_rate);
+                run(duration, sample_rate, |time| pipe.tick(time < duration))
+            }
+            ModelKind::StruckBar => {
+                let mut bar = StruckBar::new(self, frequency, velocity, sample_rate);
+                run(duration, sample_rate, |_| bar.tick())
+            }
+        };
+
+        if self.body.is_active() {
+            let mut modes: Vec<(Biquad, f32)> = self
+                .body
+                .modes
+                .iter()
+                .filter(|mode| mode.frequency < sample_rate * 0.45)
+                .map(|mode| {
+                    (
+                        Biquad::band_pass(mode.frequency, mode.q, sample_rate),
+                        mode.gain,
+                    )
+                })
+                .collect();
+            let mix = self.body.mix;
+            for sample in &mut samples {
+                let resonance: f32 = modes
+                    .iter_mut()
+                    .map(|(filter, gain)| filter.tick(*sample) * *gain)
+                    .sum();
+                *sample = *sample * (1.0 - mix) + resonance * mix;
+            }
+        }
+
+        for sample in &mut samples {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+        samples
+    }
+
+    /// Sample a note at `time` seconds in
+    ///
+    /// The note is rendered on first use at `frequency` and kept, so later calls
+    /// for the same note only read the rendering. Returns 0.0 once the note has
+    /// died away.
+    ///
+    /// # Arguments
+    /// * `frequency` - Pitch in Hz
+    /// * `time` - Seconds since the note started
+    /// * `duration` - How long the note is held, in seconds
+    /// * `velocity` - How hard the note is played (0.0 - 1.0)
+    /// * `sample_rate` - Sample rate in Hz
+    pub fn sample(
+        &self,
+        frequency: f32,
+        time: f32,
+        duration: f32,
+        velocity: f32,
+        sample_rate: f32,
+    ) -> f32 {
+        self.renders
+            .sample(self, frequency, time, duration, velocity, sample_rate)
+    }
+
+    /// Render a note ahead of playback, keeping it cached while the result is held
+    pub(crate) fn prerender(
+        &self,
+        frequency: f32,
+        duration: f32,
+        velocity: f32,
+        sample_rate: f32,
+    ) -> Arc<[f32]> {
+        self.renders
+            .render(self, frequency, duration, velocity, sample_rate)
+    }
+}
+
+impl Rendered for PhysicalModel {
+    fn settings(&self) -> Self {
+        Self {
+            kind: self.kind,
+            excitation_position: self.excitation_position,
+            damping: self.damping,
+            stiffness: self.stiffness,
+            pressure: self.pressure,
+            body: self.body.clone(),
+            renders: RenderCache::default(),
+        }
+    }
+
+    fn render_note(
+        &self,
+        frequency: f32,
+        duration: f32,
+        velocity: f32,
+        sample_rate: f32,
+    ) -> Vec<f32> {
+        self.render(frequency, duration, velocity, sample_rate)
+    }
+}
+
+/// Clones share their renderings, so every note of a track's model is cached once
+impl Clone for PhysicalModel {
+    fn clone(&self) -> Self {
+        Self {
+            renders: self.renders.clone(),
+            ..self.settings()
+        }
+    }
+}
+
+impl std::fmt::Debug for PhysicalModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PhysicalModel")
+            .field("kind", &self.kind)
+            .field("excitation_position", &self.excitation_position)
+            .field("damping", &self.damping)
+            .field("stiffness", &self.stiffness)
+            .field("pressure", &self.pressure)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+/// Models are equal when their settings are, whatever they have cached
+impl PartialEq for PhysicalModel {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.excitation_position == other.excitation_position
+            && self.damping == other.damping
+            && self.stiffness == other.stiffness
+            && self.pressure == other.pressure
+            && self.body == other.body
+    }
+}
+
+/// Run a model until it falls silent after its note ends
+fn run(duration: f32, sample_rate: f32, mut tick: impl FnMut(f32) -> f32) -> Vec<f32> {
+    let max_samples = ((duration + MAX_RING_TIME) * sample_rate) as usize;
+    let held_samples = (duration * sample_rate) as usize;
+    let silent_tail = (SILENT_TAIL * sample_rate) as usize;
+    let mut samples = Vec::with_capacity(held_samples + silent_tail);
+    let mut quiet = 0;
+
+    for i in 0..max_samples {
+        let sample = tick(i as f32 / sample_rate);
+        samples.push(sample);
+        if i >= held_samples {
+            quiet = if sample.abs() < SILENCE { quiet + 1 } else { 0 };
+            if quiet > silent_tail {
+                samples.truncate(samples.len() - quiet);
+                break;
+            }
+        }
+    }
+    samples
+}
+
+/// Exciter level ramping up over `attack` seconds while held and off over `release`
+#[inline]
+fn drive(held: bool, level: &mut f32, attack: f32, release: f32, sample_rate: f32) -> f32 {
+    if held {
+        *level = (*level + 1.0 / (attack * sample_rate)).min(1.0);
+    } else {
+        *level = (*level - 1.0 / (release * sample_rate)).max(0.0);
+    }
+    *level
+}
+
+/// Delay line with linearly interpolated fractional delay
+struct DelayLine {
+    buffer: Vec<f32>,
+    write: usize,
+    delay: f32,
+    last: f32,
+}
+
+impl DelayLine {
+    fn new(delay: f32) -> Self {
+        let delay = delay.max(0.0);
+        Self {
+            buffer: vec![0.0; delay as usize + 2],
+            write: 0,
+            delay,
+            last: 0.0,
+        }
+    }
+
+    /// Write a sample and return the one written `delay` samples before it
+    #[inline]
+    fn tick(&mut self, input: f32) -> f32 {
+        let len = self.buffer.len();
+        self.buffer[self.write] = input;
+        let read = self.write as f32 - self.delay + len as f32;
+        let index = read as usize;
+        let fraction = read - index as f32;
+        let a = self.buffer[index % len];
+        let b = self.buffer[(index + 1) % len];
+        self.write = (self.write + 1) % len;
+        self.last = a + (b - a) * fraction;
+        self.last
+    }
+}
+
+/// One-pole low-pass with gain, the loss at each reflection
+struct OnePole {
+    pole: f32,
+    gain: f32,
+    state: f32,
+}
+
+impl OnePole {
+    fn new(pole: f32, gain: f32) -> Self {
+        Self {
+            pole,
+            gain,
+            state: 0.0,
+        }
+    }
+
+    /// Delay the filter adds at `omega` radians per sample, in samples
+    fn delay(&self, omega: f32) -> f32 {
+        (self.pole * omega.sin()).atan2(1.0 - self.pole * omega.cos()) / omega
+    }
+
+    /// Gain of the filter at `omega` radians per sample, leaving out `gain`
+    fn response(&self, omega: f32) -> f32 {
+        (1.0 - self.pole) / (1.0 - 2.0 * self.pole * omega.cos() + self.pole * self.pole).sqrt()
+    }
+
+    #[inline]
+    fn tick(&mut self, input: f32) -> f32 {
+        self.state = self.gain * (1.0 - self.pole) * input + self.pole * self.state;
+        self.state
+    }
+}
+
+/// Chain of first-order allpasses delaying low frequencies more than high ones
+///
+/// A stiff string or a real bore passes its upper partials round slightly faster
+/// than its fundamental, which stretches them sharp.
+struct Dispersion {
+    coefficient: f32,
+    stages: Vec<(f32, f32)>,
+}
+
+impl Dispersion {
+    fn new(stiffness: f32, period: f32) -> Self {
+        let coefficient = -0.6 * stiffness;
+        let stage_delay = (1.0 - coefficient) / (1.0 + coefficient);
+        // Keep the allpasses to a quarter of the loop so high notes stay in tune
+        let count = if stiffness > 0.0 {
+            ((period * 0.25 / stage_delay) as usize).min(4)
+        } else {
+            0
+        };
+        Self {
+            coefficient,
+            stages: vec![(0.0, 0.0); count],
+        }
+    }
+
+    /// Delay the chain adds at `omega` radians per sample, in samples
+    fn delay(&self, omega: f32) -> f32 {
+        let a = self.coefficient;
+        let phase =
+            (-omega.sin()).atan2(a + omega.cos()) - (-a * omega.sin()).atan2(1.0 + a * omega.cos());
+        -phase / omega * self.stages.len() as f32
+    }
+
+    #[inline]
+    fn tick(&mut self, mut input: f32) -> f32 {
+        let a = self.coefficient;
+        for (x1, y1) in &mut self.stages {
+            let output = a * input + *x1 - a * *y1;
+            *x1 = input;
+            *y1 = output;
+            input = output;
+        }
+        input
+    }
+}
+
+/// Band-pass biquad with 0 dB gain at its centre
+struct Biquad {
+    b0: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x: [f32; 2],
+    y: [f32; 2],
+}
+
+impl Biquad {
+    fn band_pass(frequency: f32, q: f32, sample_rate: f32) -> Self {
+        let w = 2.0 * PI * frequency / sample_rate;
+        let alpha = w.sin() / (2.0 * q);
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: alpha / a0,
+            b2: -alpha / a0,
+            a1: -2.0 * w.cos() / a0,
+            a2: (1.0 - alpha) / a0,
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, input: f32) -> f32 {
+        let output =
+            self.b0 * input + self.b2 * self.x[1] - self.a1 * self.y[0] - self.a2 * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+/// Removes the constant offset from a signal
+#[derive(Default)]
+struct DcBlocker {
+    input: f32,
+    output: f32,
+}
+
+impl DcBlocker {
+    #[inline]
+    fn tick(&mut self, input: f32) -> f32 {
+        self.output = input - self.input + 0.995 * self.output;
+        self.input = input;
+        self.output
+    }
+}
+
+/// Deterministic white noise, so a note renders the same every time
+struct Noise(u32);
+
+impl Noise {
+    fn new(frequency: f32) -> Self {
+        Self(frequency.to_bits() | 1)
+    }
+
+    #[inline]
+    fn tick(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0 as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+}
+
+/// Bow on a string: two delay lines meeting at the bow, after STK's bowed string
+struct BowedString {
+    neck: DelayLine,
+    bridge: DelayLine,
+    filter: OnePole,
+    dispersion: Dispersion,
+    slope: f32,
+    bow_velocity: f32,
+    bow_level: f32,
+    sample_rate: f32,
+}
+
+impl BowedString {
+    fn new(model: &PhysicalModel, frequency: f32, velocity: f32, sample_rate: f32) -> Self {
+        let period = sample_rate / frequency;
+        let omega = 2.0 * PI / period;
+        let filter = OnePole::new(0.3 + 0.5 * model.damping, 0.995 - 0.06 * model.damping);
+        let dispersion = Dispersion::new(model.stiffness, period);
+        let length = (period - 2.0 - filter.delay(omega) - dispersion.delay(omega)).max(2.0);
+        let beta = model.excitation_position.clamp(0.02, 0.5);
+        Self {
+            neck: DelayLine::new(length * (1.0 - beta)),
+            bridge: DelayLine::new(length * beta),
+            filter,
+            dispersion,
+            slope: 5.0 - 4.0 * model.pressure,
+            bow_velocity: 0.03 + 0.2 * velocity,
+            bow_level: 0.0,
+            sample_rate,
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, held: bool) -> f32 {
+        let bow =
+            self.bow_velocity * drive(held, &mut self.bow_level, 0.05, 0.01, self.sample_rate);
+        let bridge_reflection = -self.dispersion.tick(self.filter.tick(self.bridge.last));
+        let nut_reflection = -self.neck.last;
+        let string_velocity = bridge_reflection + nut_reflection;
+        let difference = bow - string_velocity;
+        // Friction only acts while the bow is on the string
+        let new_velocity = if bow > 0.0 {
+            difference
+                * ((difference * self.slope).abs() + 0.75)
+                    .powi(-4)
+                    .clamp(0.01, 0.98)
+        } else {
+            0.0
+        };
+        self.neck.tick(bridge_reflection + new_velocity);
+        self.bridge.tick(nut_reflection + new_velocity)
+    }
+}
+
+/// Plucked string: one loop, excited by a noise burst comb-filtered at the pluck point
+struct PluckedString {
+    string: DelayLine,
+    filter: OnePole,
+    dispersion: Dispersion,
+    excitation: Vec<f32>,
+    position: usize,
+}
+
+impl PluckedString {
+    fn new(model: &PhysicalModel, frequency: f32, velocity: f32, sample_rate: f32) -> Self {
+        let period = sample_rate / frequency;
+        let omega = 2.0 * PI / period;
+        // The fundamental dies away over the same time at every pitch; the loss
+        // filter opens up on high notes so it doesn't eat their fundamental
+        let t60 = PLUCK_DECAY * 0.02f32.powf(model.damping);
+        let mut filter = OnePole::new(
+            (0.05 + 0.6 * model.damping) * (440.0 / frequency).min(1.0),
+            1.0,
+        );
+        filter.gain = (10f32.powf(-3.0 / (frequency * t60)) / filter.response(omega)).min(0.9995);
+        let dispersion = Dispersion::new(model.stiffness, period);
+        let length = (period - 1.0 - filter.delay(omega) - dispersion.delay(omega)).max(1.0);
+
+        // Softer plucks are darker; plucking at a fraction of the string
+        // cancels the harmonics with a node there
+        let mut noise = Noise::new(frequency);
+        let mut tone = OnePole::new(0.9 * (1.0 - model.pressure), 1.0);
+        let burst: Vec<f32> = (0..period as usize)
+            .map(|_| tone.tick(noise.tick()))
+            .collect();
+        let offset = ((model.excitation_position.clamp(0.02, 0.98) * period) as usize).max(1);
+        let excitation = (0..burst.len())
+            .map(|i| {
+                let delayed = if i >= offset { burst[i - offset] } else { 0.0 };
+                (burst[i] - delayed) * velocity * 0.5
+            })
+            .collect();
+
+        Self {
+            string: DelayLine::new(length),
+            filter,
+            dispersion,
+            excitation,
+            position: 0,
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self) -> f32 {
+        let excitation = self.excitation.get(self.position).copied().unwrap_or(0.0);
+        self.position += 1;
+        let output = excitation + self.dispersion.tick(self.filter.tick(self.string.last));
+        self.string.tick(output);
+        output
+    }
+}
+
+/// Air jet across a bore, after STK's flute
+struct BlownPipe {
+    jet: DelayLine,
+    bore: DelayLine,
+    filter: OnePole,
+    dispersion: Dispersion,
+    reflection_dc: DcBlocker,
+    output_dc: DcBlocker,
+    noise: Noise,
+    max_pressure: f32,
+    breath_level: f32,
+    sample_rate: f32,
+}
+
+impl BlownPipe {
+    fn new(model: &PhysicalModel, frequency: f32, velocity: f32, sample_rate: f32) -> Self {
+        let period = sample_rate / frequency;
+        let omega = 2.0 * PI / period;
+        let filter = OnePole::new(0.5 + 0.3 * model.damping, 0.99 - 0.04 * model.damping);
+        let dispersion = Dispersion::new(model.stiffness, period);
+        let length = (period - filter.delay(omega) - dispersion.delay(omega)).max(2.0);
+        // Half the bore speaks in tune; shorter jets pull sharp and overblow,
+        // longer ones flatten, as a flute player rolling the embouchure does
+        let jet_ratio = 0.4 + 0.2 * model.excitation_position;
+        Self {
+            jet: DelayLine::new(length * jet_ratio),
+            bore: DelayLine::new(length),
+            filter,
+            dispersion,
+            reflection_dc: DcBlocker::default(),
+            output_dc: DcBlocker::default(),
+            noise: Noise::new(frequency),
+            max_pressure: (0.9 + 0.2 * velocity) * (0.85 + 0.3 * model.pressure),
+            breath_level: 0.0,
+            sample_rate,
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, held: bool) -> f32 {
+        let level = drive(held, &mut self.breath_level, 0.03, 0.02, self.sample_rate);
+        let breath = self.max_pressure * level * (1.0 + 0.15 * self.noise.tick());
+
+        let reflected = self.dispersion.tick(self.filter.tick(self.bore.last));
+        let reflected = self.reflection_dc.tick(reflected);
+
+        let jet = self.jet.tick(breath - 0.5 * reflected);
+        let jet = (jet * (jet * jet - 1.0)).clamp(-1.0, 1.0);
+        // The jet pushes the bore off centre; only its vibration is heard
+        self.output_dc
+            .tick(self.bore.tick(jet + 0.5 * reflected) * 0.3)
+    }
+}
+
+/// One tuned loop of a banded waveguide
+struct BarMode {
+    delay: DelayLine,
+    filter: Biquad,
+    gain: f32,
+    weight: f32,
+}
+
+/// Mallet on a bar: a banded waveguide with one loop per mode
+struct StruckBar {
+    modes: Vec<BarMode>,
+    strike: Vec<f32>,
+    position: usize,
+}
+
+impl StruckBar {
+    fn new(model: &PhysicalModel, frequency: f32, velocity: f32, sample_rate: f32) -> Self {
+        let decay = 8.0 * 0.02f32.powf(model.damping);
+        // Harder mallets make shorter strikes, which reach higher modes. The
+        // mallet leaves within half a period so the fundamental always sounds.
+        let contact = (0.004 - 0.0037 * model.pressure).min(0.5 / frequency);
+        let modes: Vec<BarMode> = BAR_MODES
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &bar_ratio)| {
+                let harmonic = (i + 1) as f32;
+                let ratio = harmonic + (bar_ratio - harmonic) * model.stiffness;
+                let mode_frequency = frequency * ratio;
+                if mode_frequency >= sample_rate * 0.45 {
+                    return None;
+                }
+                // Higher modes die away faster. A trip round the loop takes a
+                // period plus the band-pass filter's group delay at its centre.
+                let t60 = decay / ratio.powf(0.7);
+                let round_trip = (1.0 + BAR_MODE_Q / PI) / mode_frequency;
+                let shape = (PI * harmonic * model.excitation_position).sin().abs();
+                Some(BarMode {
+                    delay: DelayLine::new(sample_rate / mode_frequency - 1.0),
+                    filter: Biquad::band_pass(mode_frequency, BAR_MODE_Q, sample_rate),
+                    gain: 10f32.powf(-3.0 * round_trip / t60),
+                    weight: shape * mallet_spectrum(mode_frequency * contact),
+                })
+            })
+            .collect();
+
+        // Each mode takes in the impulse over a band proportional to its
+        // frequency, which the period makes up for; the strike is then shared
+        // out so a hard hit on every mode is no louder than one on a single mode
+        let period = sample_rate / frequency;
+        let width = ((contact * sample_rate) as usize).max(2);
+        let total: f32 = modes.iter().map(|mode| mode.weight).sum();
+        let force = velocity * STRIKE_FORCE * period / (width as f32 * total.max(0.5));
+        let strike = (0..width)
+            .map(|i| (1.0 - (2.0 * PI * i as f32 / width as f32).cos()) * force)
+            .collect();
+
+        Self {
+            modes,
+            strike,
+            position: 0,
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self) -> f32 {
+        let strike = self.strike.get(self.position).copied().unwrap_or(0.0);
+        self.position += 1;
+        self.modes
+            .iter_mut()
+            .map(|mode| {
+                let output = mode
+                    .filter
+                    .tick(strike * mode.weight + mode.gain * mode.delay.last);
+                mode.delay.tick(output);
+                output
+            })
+            .sum()
+    }
+}
+
+/// Relative level of a raised-cosine strike at `cycles` periods per contact time
+fn mallet_spectrum(cycles: f32) -> f32 {
+    if (cycles - 1.0).abs() < 1e-3 {
+        return 0.5;
+    }
+    let sinc = if cycles == 0.0 {
+        1.0
+    } else {
+        (PI * cycles).sin() / (PI * cycles)
+    };
+    (sinc / (1.0 - cycles * cycles)).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    /// Strongest period between 40 Hz and 2 kHz by autocorrelation, as a frequency
+    fn pitch(samples: &[f32]) -> f32 {
+        let window = &samples[..4096];
+        let lags = (SAMPLE_RATE / 2000.0) as usize..(SAMPLE_RATE / 40.0) as usize;
+        let correlation = |lag: usize| -> f32 {
+            window[..window.len() - lag]
+                .iter()
+                .zip(&window[lag..])
+                .map(|(a, b)| a * b)
+                .sum()
+        };
+        let best = lags
+            .max_by(|&a, &b| correlation(a).total_cmp(&correlation(b)))
+            .unwrap();
+        SAMPLE_RATE / best as f32
+    }
+
+    fn steady(samples: &[f32]) -> &[f32] {
+        &samples[(0.3 * SAMPLE_RATE) as usize..]
+    }
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()))
+    }
+
+    #[test]
+    fn test_models_play_in_tune() {
+        for model in [
+            PhysicalModel::bowed_string(),
+            PhysicalModel::plucked_string(),
+            PhysicalModel::blown_pipe(),
+            PhysicalModel::struck_bar().with_stiffness(0.0),
+        ] {
+            for frequency in [220.0, 440.0] {
+                let samples = model.render(frequency, 1.0, 0.8, SAMPLE_RATE);
+                let measured = pitch(steady(&samples));
+                let cents = 1200.0 * (measured / frequency).log2();
+                assert!(
+                    cents.abs() < 25.0,
+                    "{:?} at {frequency} Hz played {measured} Hz",
+                    model.kind
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_models_stay_in_range_and_fall_silent() {
+        for model in [
+            PhysicalModel::bowed_string().with_body(BodyResonance::violin()),
+            PhysicalModel::plucked_string().with_body(BodyResonance::guitar()),
+            PhysicalModel::blown_pipe().with_pressure(1.0),
+            PhysicalModel::struck_bar().with_pressure(1.0),
+        ] {
+            let samples = model.render(330.0, 0.5, 1.0, SAMPLE_RATE);
+            let level = peak(&samples);
+            assert!(
+                level > 0.05 && level <= 1.0,
+                "{:?} peaked at {level}",
+                model.kind
+            );
+            assert!(samples.len() < ((0.5 + MAX_RING_TIME) * SAMPLE_RATE) as usize);
+            assert!(peak(&samples[samples.len() - 100..]) < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_bow_and_breath_sustain_until_released() {
+        for model in [PhysicalModel::bowed_string(), PhysicalModel::blown_pipe()] {
+            let samples = model.render(440.0, 1.0, 0.8, SAMPLE_RATE);
+            let early = peak(&samples[(0.3 * SAMPLE_RATE) as usize..(0.4 * SAMPLE_RATE) as usize]);
+            let late = peak(&samples[(0.85 * SAMPLE_RATE) as usize..(0.95 * SAMPLE_RATE) as usize]);
+            assert!(
+                late > early * 0.7,
+                "{:?} faded while held: {early} then {late}",
+                model.kind
+            );
+            let released = peak(&samples[(1.15 * SAMPLE_RATE) as usize..]);
+            assert!(released < late * 0.5);
+        }
+    }
+
+    #[test]
+    fn test_damping_shortens_plucks_and_strikes() {
+        for kind in [ModelKind::PluckedString, ModelKind::StruckBar] {
+            let ringing =
+                PhysicalModel::new(kind)
+                    .with_damping(0.1)
+                    .render(440.0, 0.1, 0.8, SAMPLE_RATE);
+            let damped =
+                PhysicalModel::new(kind)
+                    .with_damping(0.9)
+                    .render(440.0, 0.1, 0.8, SAMPLE_RATE);
+            assert!(damped.len() < ringing.len(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_controls_change_the_sound() {
+        let base = PhysicalModel::bowed_string();
+        let reference = base.render(262.0, 0.5, 0.8, SAMPLE_RATE);
+        for changed in [
+            base.clone().with_excitation_position(0.3),
+            base.clone().with_stiffness(0.8),
+            base.clone().with_pressure(0.9),
+            base.clone().with_body(BodyResonance::cello()),
+        ] {
+            let samples = changed.render(262.0, 0.5, 0.8, SAMPLE_RATE);
+            let difference: f32 = reference
+                .iter()
+                .zip(&samples)
+                .map(|(a, b)| (a - b).abs())
+                .sum();
+            assert!(difference > 1.0, "{changed:?} sounded the same");
+        }
+    }
+
+    #[test]
+    fn test_sample_reads_the_cached_rendering() {
+        let model = PhysicalModel::plucked_string();
+        let samples = model.render(440.0, 0.5, 0.8, SAMPLE_RATE);
+        for i in [0, 100, 5000] {
+            let time = i as f32 / SAMPLE_RATE;
+            assert_eq!(model.sample(440.0, time, 0.5, 0.8, SAMPLE_RATE), samples[i]);
+        }
+        assert_eq!(model.sample(440.0, 100.0, 0.5, 0.8, SAMPLE_RATE), 0.0);
+
+        // A clone shares the cache but not changed settings
+        let mut darker = model.clone();
+        darker.damping = 0.9;
+        let dark = darker.render(440.0, 0.5, 0.8, SAMPLE_RATE);
+        assert_eq!(
+            darker.sample(440.0, 0.05, 0.5, 0.8, SAMPLE_RATE),
+            dark[(0.05 * SAMPLE_RATE) as usize]
+        );
+        assert_ne!(darker, model);
+        assert_eq!(model.renders.len(), 2);
+    }
+
+    #[test]
+    fn test_held_renderings_stay_cached() {
+        let model = PhysicalModel::plucked_string();
+        let frequency = |i: usize| 110.0 + i as f32;
+        let held: Vec<_> = (0..40)
+            .map(|i| model.prerender(frequency(i), 0.01, 0.8, 8000.0))
+            .collect();
+
+        // Renderings nobody holds come and go around the held ones
+        for i in 40..100 {
+            model.sample(frequency(i), 0.0, 0.01, 0.8, 8000.0);
+        }
+        assert_eq!(model.renders.len(), 40 + 32);
+        for (i, samples) in held.iter().enumerate() {
+            let again = model.prerender(frequency(i), 0.01, 0.8, 8000.0);
+            assert!(Arc::ptr_eq(samples, &again));
+        }
+
+        // Once let go, they make way like the rest
+        drop(held);
+        model.sample(frequency(100), 0.0, 0.01, 0.8, 8000.0);
+        assert_eq!(model.renders.len(), 32);
+    }
+}
@@ -0,0 +1,199 @@
+//! Rendered notes of stateful instruments, kept for playback
+//!
+//! Oscillators are sampled at any time independently, but waveguides and
+//! resonators carry state from one sample to the next. Such instruments render
+//! each note once, from start to finish, and the oscillator reads the rendering
+//! back. The cache lives behind an `Arc` so clones of an instrument share it.
+//!
+//! Tracks render their notes ahead of time when they are prepared and give each
+//! note its renderings, which playback reads directly with [`read`]. Holding them
+//! also keeps them in the cache for as long as the notes can play.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// How many renderings nobody holds a cache keeps before reusing the least recent
+const RENDER_CACHE_SIZE: usize = 32;
+
+/// Read a rendering at `time` seconds in, interpolating between samples
+///
+/// Returns 0.0 before the note starts and once it has died away.
+#[inline]
+pub(crate) fn read(samples: &[f32], time: f32, sample_rate: f32) -> f32 {
+    if time < 0.0 {
+        return 0.0;
+    }
+    let position = time * sample_rate;
+    let index = position as usize;
+    let fraction = position - index as f32;
+    match (samples.get(index), samples.get(index + 1)) {
+        (Some(&a), Some(&b)) => a + (b - a) * fraction,
+        (Some(&a), None) => a * (1.0 - fraction),
+        _ => 0.0,
+    }
+}
+
+/// An instrument that renders whole notes
+pub(crate) trait Rendered: PartialEq {
+    /// Copy of the instrument's settings with a cache of its own
+    fn settings(&self) -> Self;
+
+    /// Render one note from start until it dies away
+    fn render_note(
+        &self,
+        frequency: f32,
+        duration: f32,
+        velocity: f32,
+        sample_rate: f32,
+    ) -> Vec<f32>;
+}
+
+/// Frequency, duration, velocity and sample rate of a note, as bits
+type NoteKey = [u32; 4];
+
+/// One note as rendered, with the settings it was rendered with
+struct Rendering<M> {
+    settings: M,
+    samples: Arc<[f32]>,
+    last_used: u64,
+}
+
+impl<M> Rendering<M> {
+    /// Whether anyone besides the cache holds this rendering
+    fn is_held(&self) -> bool {
+        Arc::strong_count(&self.samples) > 1
+    }
+}
+
+/// Renderings of each note, and the clock that orders their use
+struct Renderings<M> {
+    notes: HashMap<NoteKey, Vec<Rendering<M>>>,
+    clock: u64,
+}
+
+/// Rendered notes, shared between clones of an instrument
+///
+/// Entries remember the settings they were rendered with, so a clone whose
+/// public fields were changed afterwards never plays another instrument's notes.
+/// Renderings held through [`render`](Self::render) stay until they are let go;
+/// of the rest, the cache keeps the most recently read.
+pub(crate) struct RenderCache<M>(Arc<Mutex<Renderings<M>>>);
+
+impl<M> Default for RenderCache<M> {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(Renderings {
+            notes: HashMap::new(),
+            clock: 0,
+        })))
+    }
+}
+
+impl<M> Clone for RenderCache<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<M: Rendered> RenderCache<M> {
+    /// How many notes are cached
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.0.lock().unwrap().notes.values().map(Vec::len).sum()
+    }
+
+    /// Sample a note at `time` seconds in, rendering it on first use
+    ///
+    /// Returns 0.0 before the note starts and once it has died away.
+    pub(crate) fn sample(
+        &self,
+        model: &M,
+        frequency: f32,
+        time: f32,
+        duration: f32,
+        velocity: f32,
+        sample_rate: f32,
+    ) -> f32 {
+        if time < 0.0 {
+            return 0.0;
+        }
+        let samples = self.render(model, frequency, duration, velocity, sample_rate);
+        read(&samples, time, sample_rate)
+    }
+
+    /// Get a note's rendering, rendering it if it isn't cached
+    ///
+    /// The cache keeps the rendering for as long as the returned `Arc` is held.
+    /// Notes are rendered with the cache unlocked, so other notes can be read
+    /// meanwhile.
+    pub(crate) fn render(
+        &self,
+        model: &M,
+        frequency: f32,
+        duration: f32,
+        velocity: f32,
+        sample_rate: f32,
+    ) -> Arc<[f32]> {
+        let note = [
+            frequency.to_bits(),
+            duration.to_bits(),
+            velocity.to_bits(),
+            sample_rate.to_bits(),
+        ];
+        if let Some(samples) = self.lookup(model, &note) {
+            return samples;
+        }
+
+        let samples: Arc<[f32]> = model
+            .render_note(frequency, duration, velocity, sample_rate)
+            .into();
+
+        // Another clone may have rendered the same note in the meantime
+        if let Some(samples) = self.lookup(model, &note) {
+            return samples;
+        }
+        let mut cache = self.0.lock().unwrap();
+        let Renderings { notes, clock } = &mut *cache;
+        Self::evict(notes);
+        notes.entry(note).or_default().push(Rendering {
+            settings: model.settings(),
+            samples: samples.clone(),
+            last_used: *clock,
+        });
+        samples
+    }
+
+    /// Find a cached rendering of a note, marking it as just used
+    fn lookup(&self, model: &M, note: &NoteKey) -> Option<Arc<[f32]>> {
+        let mut cache = self.0.lock().unwrap();
+        let Renderings { notes, clock } = &mut *cache;
+        *clock += 1;
+        let rendering = notes
+            .get_mut(note)?
+            .iter_mut()
+            .find(|rendering| rendering.settings == *model)?;
+        rendering.last_used = *clock;
+        Some(rendering.samples.clone())
+    }
+
+    /// Make room for one more rendering nobody holds
+    ///
+    /// The renderings read least recently make way, so a long note isn't rendered
+    /// again because shorter ones came and went around it.
+    fn evict(notes: &mut HashMap<NoteKey, Vec<Rendering<M>>>) {
+        let mut unheld: Vec<u64> = notes
+            .values()
+            .flatten()
+            .filter(|rendering| !rendering.is_held())
+            .map(|rendering| rendering.last_used)
+            .collect();
+        if unheld.len() < RENDER_CACHE_SIZE {
+            return;
+        }
+        let excess = unheld.len() + 1 - RENDER_CACHE_SIZE;
+        let (_, &mut cutoff, _) = unheld.select_nth_unstable(excess - 1);
+        for renders in notes.values_mut() {
+            renders.retain(|rendering| rendering.is_held() || rendering.last_used > cutoff);
+        }
+        notes.retain(|_, renders| !renders.is_empty());
+    }
+}
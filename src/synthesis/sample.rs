@@ -2,6 +2,7 @@
 /// This module provides functionality to load audio samples from various formats
 /// (WAV, MP3, OGG, FLAC, AAC) and play them back with pitch shifting, looping, and effects.
 use crate::error::{Result, TunesError};
+use super::loudness::{self, Loudness};
 use rayon::prelude::*;
 use std::fs::File;
 use std::path::Path;
@@ -628,6 +629,33 @@ impl Sample {
         }
     }
 
+    /// Measure the sample's loudness (EBU R128)
+    ///
+    /// Mono samples count one channel, so they read 3 LU quieter than the same audio
+    /// on both channels of a stereo sample.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::synthesis::sample::Sample;
+    /// let dialog = Sample::from_file("voice/line_01.wav")?;
+    /// let loudness = dialog.loudness();
+    /// println!("{:.1} LUFS, {:.1} dBTP", loudness.integrated, loudness.true_peak);
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn loudness(&self) -> Loudness {
+        loudness::measure(&self.data, self.sample_rate, self.channels as usize)
+    }
+
+    /// Scale the sample to an integrated loudness in LUFS
+    ///
+    /// Applies a single gain, so loud transients may exceed ±1.0 when the target is
+    /// far above the sample's current loudness; check [`loudness`](Self::loudness)
+    /// for the true peak. Silent samples are returned unchanged.
+    pub fn normalize_loudness(&self, target_lufs: f32) -> Self {
+        let gain_db = self.loudness().gain_to(target_lufs);
+        self.with_gain(10f32.powf(gain_db / 20.0))
+    }
+
     /// Apply gain (volume) to the sample
     ///
     /// # Arguments
@@ -764,6 +792,15 @@ impl Sample {
         self.num_frames
     }
 
+    /// The sample's frames mixed down to mono
+    pub(crate) fn to_mono(&self) -> Vec<f32> {
+        let channels = self.channels.max(1) as usize;
+        self.data
+            .chunks_exact(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect()
+    }
+
     /// Get the sample rate in Hz
     pub fn sample_rate(&self) -> u32 {
         self.sample_rate
@@ -1202,6 +1239,21 @@ mod tests {
         assert!((left - 1.0).abs() < 0.01); // Peak should be at 1.0
     }
 
+    #[test]
+    fn test_normalize_loudness() {
+        let tone: Vec<f32> = (0..44100 * 2)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 44100.0).sin() * 0.1)
+            .collect();
+        let sample = Sample::from_mono(tone, 44100);
+
+        let normalized = sample.normalize_loudness(-16.0);
+        assert!((normalized.loudness().integrated + 16.0).abs() < 0.05);
+
+        // Silence has no loudness to normalize
+        let silent = Sample::from_mono(vec![0.0; 44100], 44100).normalize_loudness(-16.0);
+        assert!(silent.data.iter().all(|&s| s == 0.0));
+    }
+
     #[test]
     fn test_reverse() {
         let data = vec![1.0, 2.0, 3.0, 4.0];
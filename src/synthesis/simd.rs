@@ -46,6 +46,8 @@ pub trait SimdLanes: Copy + Clone + Sized {
     // Construction
     fn splat(val: f32) -> Self;
     fn from_array(arr: &[f32]) -> Self;
+    /// Lanes holding `start`, `start + 1`, `start + 2`, ...
+    fn ramp(start: f32) -> Self;
 
     // Arithmetic
     fn add(self, other: Self) -> Self;
@@ -57,6 +59,20 @@ pub trait SimdLanes: Copy + Clone + Sized {
     fn abs(self) -> Self;
     fn min(self, other: Self) -> Self;
     fn max(self, other: Self) -> Self;
+    fn mul_add(self, mul: Self, add: Self) -> Self;
+    fn floor(self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    fn exp(self) -> Self;
+    fn sin(self) -> Self;
+
+    /// Hyperbolic tangent, built from `exp` so it vectorizes
+    #[inline(always)]
+    fn tanh(self) -> Self {
+        // tanh(x) = sign(x) * (1 - e^(-2|x|)) / (1 + e^(-2|x|))
+        let e = Self::splat(-2.0).mul(self.abs()).exp();
+        let one = Self::splat(1.0);
+        one.sub(e).div(one.add(e)).copysign(self)
+    }
 
     // Conversion
     fn write_to_slice(self, slice: &mut [f32]);
@@ -82,6 +98,15 @@ macro_rules! impl_simd_lanes {
                 <$type>::from(fixed)
             }
 
+            #[inline(always)]
+            fn ramp(start: f32) -> Self {
+                let mut fixed = [0.0f32; $lanes];
+                for (i, lane) in fixed.iter_mut().enumerate() {
+                    *lane = start + i as f32;
+                }
+                <$type>::from(fixed)
+            }
+
             #[inline(always)]
             fn add(self, other: Self) -> Self {
                 self + other
@@ -117,6 +142,31 @@ macro_rules! impl_simd_lanes {
                 self.max(other)
             }
 
+            #[inline(always)]
+            fn mul_add(self, mul: Self, add: Self) -> Self {
+                self.mul_add(mul, add)
+            }
+
+            #[inline(always)]
+            fn floor(self) -> Self {
+                self.floor()
+            }
+
+            #[inline(always)]
+            fn copysign(self, sign: Self) -> Self {
+                self.copysign(sign)
+            }
+
+            #[inline(always)]
+            fn exp(self) -> Self {
+                self.exp()
+            }
+
+            #[inline(always)]
+            fn sin(self) -> Self {
+                self.sin()
+            }
+
             #[inline(always)]
             fn write_to_slice(self, slice: &mut [f32]) {
                 let arr = self.to_array();
@@ -144,6 +194,11 @@ impl SimdLanes for f32 {
         arr[0]
     }
 
+    #[inline(always)]
+    fn ramp(start: f32) -> Self {
+        start
+    }
+
     #[inline(always)]
     fn add(self, other: Self) -> Self {
         self + other
@@ -179,6 +234,36 @@ impl SimdLanes for f32 {
         self.max(other)
     }
 
+    #[inline(always)]
+    fn mul_add(self, mul: Self, add: Self) -> Self {
+        self.mul_add(mul, add)
+    }
+
+    #[inline(always)]
+    fn floor(self) -> Self {
+        self.floor()
+    }
+
+    #[inline(always)]
+    fn copysign(self, sign: Self) -> Self {
+        self.copysign(sign)
+    }
+
+    #[inline(always)]
+    fn exp(self) -> Self {
+        self.exp()
+    }
+
+    #[inline(always)]
+    fn sin(self) -> Self {
+        self.sin()
+    }
+
+    #[inline(always)]
+    fn tanh(self) -> Self {
+        self.tanh()
+    }
+
     #[inline(always)]
     fn write_to_slice(self, slice: &mut [f32]) {
         slice[0] = self;
@@ -393,6 +478,21 @@ mod tests {
         assert_eq!(buffer, vec![2.0, 3.0, 4.0]);
     }
 
+    #[test]
+    fn test_ramp() {
+        assert_eq!(f32x4::ramp(2.0).to_array(), [2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(f32::ramp(7.0), 7.0);
+    }
+
+    #[test]
+    fn test_vector_tanh_matches_scalar() {
+        for i in -400..=400 {
+            let x = i as f32 * 0.05;
+            let vector = SimdLanes::tanh(f32x8::splat(x)).to_array()[0];
+            assert!((vector - x.tanh()).abs() < 1e-6, "tanh({}) = {} vs {}", x, vector, x.tanh());
+        }
+    }
+
     #[test]
     fn test_write_to_slice() {
         let vec = f32x4::splat(42.0);
@@ -0,0 +1,528 @@
+//! Sinusoidal analysis of recordings into additive partials
+//!
+//! A [`SinusoidalAnalysis`] breaks a recording down into the sine waves it is
+//! made of. The recording is cut into short overlapping frames, the spectral
+//! peaks of each frame are found, and peaks are joined from frame to frame into
+//! partials that can glide, swell and fade: each becomes a [`Partial`] with a
+//! [`PartialEnvelope`] following its level and pitch.
+//!
+//! Played back through [`AdditiveSynth::from_analysis`], the partials rebuild the
+//! recording's pitched content (breath, bow and hammer noise are left out), and
+//! pitch, timing and the balance of partials can each be changed on their own:
+//! transpose without speeding up, slow down without dropping in pitch, or
+//! remove and retune individual partials.
+//!
+//! Analysis works best on a single monophonic note with a fundamental above
+//! about 60 Hz; stereo recordings are mixed down to mono.
+//!
+//! # Example
+//! ```no_run
+//! use tunes::synthesis::additive::AdditiveSynth;
+//! use tunes::synthesis::sinusoidal::SinusoidalAnalysis;
+//! use tunes::synthesis::Sample;
+//!
+//! let cello = Sample::from_file("samples/cello_c3.wav")?;
+//! let analysis = SinusoidalAnalysis::from_sample(&cello, 40)?;
+//!
+//! // Up a fourth, twice as long, with the even partials taken out
+//! let mut synth = AdditiveSynth::from_analysis(&analysis, 44100.0)
+//!     .with_time_stretch(2.0)
+//!     .map_partials(|partial| {
+//!         if partial.frequency_ratio.round() as u32 % 2 == 0 {
+//!             partial.amplitude = 0.0;
+//!         }
+//!     });
+//! synth.set_frequency(analysis.fundamental * 4.0 / 3.0);
+//! let samples = synth.generate((synth.duration().unwrap() * 44100.0) as usize);
+//! let hollow_cello = Sample::from_mono(samples, 44100);
+//! # Ok::<(), tunes::error::TunesError>(())
+//! ```
+//!
+//! [`AdditiveSynth::from_analysis`]: crate::synthesis::additive::AdditiveSynth::from_analysis
+
+use super::additive::{Partial, PartialEnvelope};
+use super::sample::Sample;
+use crate::error::{Result, TunesError};
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use std::f32::consts::PI;
+
+/// Samples per analysis frame, four periods of 43 Hz at 44.1 kHz
+const FRAME_SIZE: usize = 4096;
+
+/// Samples between frames, which sets the time resolution of the envelopes
+const HOP_SIZE: usize = 256;
+
+/// Frames are zero-padded this many times over to place peaks more finely
+const ZERO_PADDING: usize = 2;
+
+/// Peaks this far below the loudest in the recording are ignored, in dB
+const PEAK_FLOOR_DB: f32 = 70.0;
+
+/// Peaks this far below the loudest in their own frame are ignored, in dB
+const FRAME_FLOOR_DB: f32 = 60.0;
+
+/// Largest change in frequency between frames for a peak to continue a
+/// partial, as a fraction of its frequency
+const MAX_DEVIATION: f32 = 0.03;
+
+/// Frames a partial may go missing before it is ended
+const MAX_GAP: usize = 3;
+
+/// Partials lasting less than this are taken as noise, in seconds
+const MIN_PARTIAL_TIME: f32 = 0.03;
+
+/// Partials with this fraction of the strongest one's energy can be the fundamental
+const FUNDAMENTAL_ENERGY: f32 = 0.1;
+
+/// A partial followed through a recording, ready for [`AdditiveSynth`]
+///
+/// [`AdditiveSynth`]: crate::synthesis::additive::AdditiveSynth
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedPartial {
+    /// Average frequency as a ratio of the fundamental, at full amplitude
+    pub partial: Partial,
+    /// Level and pitch at every frame of the recording
+    pub envelope: PartialEnvelope,
+}
+
+/// The partials found in a recording by STFT peak tracking
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinusoidalAnalysis {
+    /// Estimated fundamental in Hz: the lowest of the strong partials
+    pub fundamental: f32,
+    /// Length of the recording in seconds
+    pub duration: f32,
+    /// Partials from lowest to highest
+    pub partials: Vec<TrackedPartial>,
+}
+
+/// A partial as it is being followed from frame to frame
+struct Track {
+    start: usize,
+    frequencies: Vec<f32>,
+    amplitudes: Vec<f32>,
+    missed: usize,
+}
+
+impl Track {
+    fn last_frequency(&self) -> f32 {
+        self.frequencies.last().copied().unwrap_or(0.0)
+    }
+
+    /// Drop the frames it went missing for at its end
+    fn finish(mut self) -> Self {
+        let end = self.amplitudes.len() - self.missed;
+        self.frequencies.truncate(end);
+        self.amplitudes.truncate(end);
+        self.missed = 0;
+        self
+    }
+}
+
+impl SinusoidalAnalysis {
+    /// Analyse a recording into its strongest partials
+    ///
+    /// # Arguments
+    /// * `sample` - The recording to analyse
+    /// * `max_partials` - Most partials to keep, those with the most energy first
+    ///
+    /// # Errors
+    /// Returns an error if the recording is shorter than one analysis frame
+    /// (4096 samples) or contains no sustained partials.
+    pub fn from_sample(sample: &Sample, max_partials: usize) -> Result<Self> {
+        let mono = sample.to_mono();
+        let sample_rate = sample.sample_rate as f32;
+        if mono.len() < FRAME_SIZE {
+            return Err(TunesError::InvalidAudioFormat(format!(
+                "{} samples are too few to analyse, {} are needed",
+                mono.len(),
+                FRAME_SIZE
+            )));
+        }
+
+        let frames = find_peaks(&mono, sample_rate);
+        let loudest = frames
+            .iter()
+            .flatten()
+            .map(|&(_, amplitude)| amplitude)
+            .fold(0.0f32, f32::max);
+        let floor = loudest * 10.0f32.powf(-PEAK_FLOOR_DB / 20.0);
+        let mut tracks = follow_peaks(&frames, floor);
+
+        let min_frames = (MIN_PARTIAL_TIME * sample_rate / HOP_SIZE as f32).ceil() as usize;
+        tracks.retain(|track| track.amplitudes.len() >= min_frames);
+        let energy = |track: &Track| -> f32 {
+            track
+                .amplitudes
+                .iter()
+                .map(|amplitude| amplitude * amplitude)
+                .sum()
+        };
+        tracks.sort_by(|a, b| energy(b).total_cmp(&energy(a)));
+        tracks.truncate(max_partials);
+        if tracks.is_empty() {
+            return Err(TunesError::InvalidAudioFormat(
+                "no sustained partials found in sample".to_string(),
+            ));
+        }
+
+        let strongest = energy(&tracks[0]);
+        let mean_frequency = |track: &Track| -> f32 {
+            let weight: f32 = track.amplitudes.iter().sum();
+            let sum: f32 = track
+                .frequencies
+                .iter()
+                .zip(&track.amplitudes)
+                .map(|(frequency, amplitude)| frequency * amplitude)
+                .sum();
+            sum / weight.max(f32::MIN_POSITIVE)
+        };
+        let fundamental = tracks
+            .iter()
+            .filter(|track| energy(track) >= strongest * FUNDAMENTAL_ENERGY)
+            .map(mean_frequency)
+            .fold(f32::MAX, f32::min);
+
+        let frame_count = frames.len();
+        let frame_time = HOP_SIZE as f32 / sample_rate;
+        let mut partials: Vec<TrackedPartial> = tracks
+            .iter()
+            .map(|track| {
+                let mean = mean_frequency(track);
+                let first = track.frequencies[0] / mean;
+                let last = track.last_frequency() / mean;
+                let mut amplitude = vec![0.0; frame_count];
+                let mut frequency = vec![first; frame_count];
+                for (i, (f, a)) in track.frequencies.iter().zip(&track.amplitudes).enumerate() {
+                    amplitude[track.start + i] = *a;
+                    frequency[track.start + i] = f / mean;
+                }
+                for f in &mut frequency[track.start + track.frequencies.len()..] {
+                    *f = last;
+                }
+                TrackedPartial {
+                    partial: Partial::new(mean / fundamental, 1.0, 0.0),
+                    envelope: PartialEnvelope::new(frame_time, amplitude, frequency),
+                }
+            })
+            .collect();
+        partials.sort_by(|a, b| {
+            a.partial
+                .frequency_ratio
+                .total_cmp(&b.partial.frequency_ratio)
+        });
+
+        Ok(Self {
+            fundamental,
+            duration: mono.len() as f32 / sample_rate,
+            partials,
+        })
+    }
+}
+
+/// Spectral peaks of every frame as (frequency, amplitude)
+///
+/// The signal is padded by half a frame at each end, so frame `k` is centred
+/// `k * HOP_SIZE` samples in.
+fn find_peaks(signal: &[f32], sample_rate: f32) -> Vec<Vec<(f32, f32)>> {
+    let half = FRAME_SIZE / 2;
+    let mut padded = vec![0.0; half];
+    padded.extend_from_slice(signal);
+    padded.resize(padded.len() + half, 0.0);
+
+    // Blackman-Harris: sidelobes 92 dB down are never mistaken for partials
+    let window: Vec<f32> = (0..FRAME_SIZE)
+        .map(|i| {
+            let x = 2.0 * PI * i as f32 / FRAME_SIZE as f32;
+            0.35875 - 0.48829 * x.cos() + 0.14128 * (2.0 * x).cos() - 0.01168 * (3.0 * x).cos()
+        })
+        .collect();
+    let window_sum: f32 = window.iter().sum();
+    let size = FRAME_SIZE * ZERO_PADDING;
+    let fft = FftPlanner::new().plan_fft_forward(size);
+    let bin_width = sample_rate / size as f32;
+    let frame_floor = 10.0f32.powf(-FRAME_FLOOR_DB / 20.0);
+
+    let frame_count = (padded.len() - FRAME_SIZE) / HOP_SIZE + 1;
+    let mut spectrum = vec![Complex::new(0.0, 0.0); size];
+    (0..frame_count)
+        .map(|frame| {
+            let start = frame * HOP_SIZE;
+            for (i, bin) in spectrum.iter_mut().enumerate() {
+                let value = if i < FRAME_SIZE {
+                    padded[start + i] * window[i]
+                } else {
+                    0.0
+                };
+                *bin = Complex::new(value, 0.0);
+            }
+            fft.process(&mut spectrum);
+
+            let magnitudes: Vec<f32> = spectrum[..size / 2].iter().map(|c| c.norm()).collect();
+            let loudest = magnitudes.iter().copied().fold(0.0f32, f32::max);
+            if loudest <= 0.0 {
+                return Vec::new();
+            }
+            (1..magnitudes.len() - 1)
+                .filter(|&i| {
+                    let m = magnitudes[i];
+                    m > loudest * frame_floor && m > magnitudes[i - 1] && m >= magnitudes[i + 1]
+                })
+                .map(|i| {
+                    // A parabola through the log magnitudes places the peak between bins
+                    let (a, b, c) = (
+                        magnitudes[i - 1].max(f32::MIN_POSITIVE).ln(),
+                        magnitudes[i].ln(),
+                        magnitudes[i + 1].max(f32::MIN_POSITIVE).ln(),
+                    );
+                    let denominator = a - 2.0 * b + c;
+                    let offset = if denominator.abs() > f32::EPSILON {
+                        (0.5 * (a - c) / denominator).clamp(-0.5, 0.5)
+                    } else {
+                        0.0
+                    };
+                    let peak = (b - 0.25 * (a - c) * offset).exp();
+                    ((i as f32 + offset) * bin_width, 2.0 * peak / window_sum)
+                })
+                .filter(|&(frequency, _)| frequency >= 20.0 && frequency < sample_rate * 0.45)
+                .collect()
+        })
+        .collect()
+}
+
+/// Join peaks from frame to frame into partials
+///
+/// Each frame, live partials take the nearest unclaimed peak within
+/// `MAX_DEVIATION` of their last frequency, closest pairs first. Unclaimed peaks
+/// start new partials, and partials unmatched for more than `MAX_GAP` frames end.
+fn follow_peaks(frames: &[Vec<(f32, f32)>], floor: f32) -> Vec<Track> {
+    let mut live: Vec<Track> = Vec::new();
+    let mut finished: Vec<Track> = Vec::new();
+
+    for (frame, peaks) in frames.iter().enumerate() {
+        let peaks: Vec<(f32, f32)> = peaks
+            .iter()
+            .copied()
+            .filter(|&(_, amplitude)| amplitude > floor)
+            .collect();
+
+        let mut pairs: Vec<(f32, usize, usize)> = Vec::new();
+        for (t, track) in live.iter().enumerate() {
+            let last = track.last_frequency();
+            for (p, &(frequency, _)) in peaks.iter().enumerate() {
+                let distance = (frequency - last).abs();
+                if distance <= last * MAX_DEVIATION {
+                    pairs.push((distance, t, p));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut track_matched = vec![false; live.len()];
+        let mut peak_claimed = vec![false; peaks.len()];
+        for (_, t, p) in pairs {
+            if track_matched[t] || peak_claimed[p] {
+                continue;
+            }
+            track_matched[t] = true;
+            peak_claimed[p] = true;
+            let (frequency, amplitude) = peaks[p];
+            let track = &mut live[t];
+            track.frequencies.push(frequency);
+            track.amplitudes.push(amplitude);
+            track.missed = 0;
+        }
+
+        let mut still_live = Vec::with_capacity(live.len());
+        for (track, matched) in live.drain(..).zip(track_matched) {
+            let mut track = track;
+            if !matched {
+                let last = track.last_frequency();
+                track.frequencies.push(last);
+                track.amplitudes.push(0.0);
+                track.missed += 1;
+            }
+            if track.missed > MAX_GAP {
+                finished.push(track.finish());
+            } else {
+                still_live.push(track);
+            }
+        }
+        live = still_live;
+
+        for (p, &(frequency, amplitude)) in peaks.iter().enumerate() {
+            if !peak_claimed[p] {
+                live.push(Track {
+                    start: frame,
+                    frequencies: vec![frequency],
+                    amplitudes: vec![amplitude],
+                    missed: 0,
+                });
+            }
+        }
+    }
+
+    finished.extend(live.into_iter().map(Track::finish));
+    finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthesis::additive::AdditiveSynth;
+    use std::f32::consts::TAU;
+
+    const SR: f32 = 44100.0;
+
+    /// Decaying harmonic tone at 220 Hz with partials at 1/2 the level of the one below
+    fn harmonic_tone(seconds: f32) -> Vec<f32> {
+        (0..(seconds * SR) as usize)
+            .map(|i| {
+                let t = i as f32 / SR;
+                let decay = (-2.0 * t).exp();
+                (1..=4)
+                    .map(|n| (TAU * 220.0 * n as f32 * t).sin() * 0.4 / (1 << (n - 1)) as f32)
+                    .sum::<f32>()
+                    * decay
+            })
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len().max(1) as f32).sqrt()
+    }
+
+    fn cycles(samples: &[f32]) -> usize {
+        samples
+            .windows(2)
+            .filter(|w| w[0] <= 0.0 && w[1] > 0.0)
+            .count()
+    }
+
+    #[test]
+    fn test_analysis_finds_harmonics() {
+        let analysis =
+            SinusoidalAnalysis::from_sample(&Sample::from_mono(harmonic_tone(1.0), 44100), 8)
+                .unwrap();
+        assert!(
+            (analysis.fundamental - 220.0).abs() < 1.0,
+            "{}",
+            analysis.fundamental
+        );
+        assert!((analysis.duration - 1.0).abs() < 1e-3);
+        assert_eq!(analysis.partials.len(), 4);
+
+        for (n, tracked) in analysis.partials.iter().enumerate() {
+            let harmonic = (n + 1) as f32;
+            assert!((tracked.partial.frequency_ratio - harmonic).abs() < 0.01);
+            // Level at 0.5 s is the partial's level times the decay
+            let expected = 0.4 / 2.0f32.powi(n as i32) * (-1.0f32).exp();
+            let found = tracked.envelope.amplitude_at(0.5);
+            assert!(
+                (found / expected - 1.0).abs() < 0.1,
+                "{} vs {}",
+                found,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_analysis_follows_a_glide() {
+        // 300 Hz rising to 400 Hz over a second
+        let glide: Vec<f32> = (0..SR as usize)
+            .map(|i| {
+                let t = i as f32 / SR;
+                (TAU * (300.0 * t + 50.0 * t * t)).sin() * 0.5
+            })
+            .collect();
+        let analysis =
+            SinusoidalAnalysis::from_sample(&Sample::from_mono(glide, 44100), 4).unwrap();
+        assert_eq!(analysis.partials.len(), 1);
+
+        let tracked = &analysis.partials[0];
+        let frequency_at = |t: f32| {
+            analysis.fundamental
+                * tracked.partial.frequency_ratio
+                * tracked.envelope.frequency_at(t)
+        };
+        for t in [0.25, 0.5, 0.75] {
+            let expected = 300.0 + 100.0 * t;
+            assert!(
+                (frequency_at(t) - expected).abs() < 2.0,
+                "{} at {}",
+                frequency_at(t),
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn test_resynthesis_matches_the_recording() {
+        let tone = harmonic_tone(1.0);
+        let analysis =
+            SinusoidalAnalysis::from_sample(&Sample::from_mono(tone.clone(), 44100), 8).unwrap();
+        let mut synth = AdditiveSynth::from_analysis(&analysis, SR);
+        let length = (synth.duration().unwrap() * SR) as usize;
+        let resynthesized = synth.generate(length);
+
+        let window = (0.05 * SR) as usize;
+        for start in [0.1, 0.4, 0.8].map(|t: f32| (t * SR) as usize) {
+            let original = rms(&tone[start..start + window]);
+            let rebuilt = rms(&resynthesized[start..start + window]);
+            let difference = 20.0 * (rebuilt / original).log10();
+            assert!(difference.abs() < 1.5, "{} dB off at {}", difference, start);
+        }
+        // Phases aren't kept, so a cycle may straddle the edge of the window
+        let original = cycles(&tone[4410..8820]);
+        let rebuilt = cycles(&resynthesized[4410..8820]);
+        assert!(
+            original.abs_diff(rebuilt) <= 1,
+            "{} vs {} cycles",
+            original,
+            rebuilt
+        );
+    }
+
+    #[test]
+    fn test_pitch_and_time_change_independently() {
+        let analysis =
+            SinusoidalAnalysis::from_sample(&Sample::from_mono(harmonic_tone(0.5), 44100), 1)
+                .unwrap();
+        let plain = AdditiveSynth::from_analysis(&analysis, SR);
+        let duration = plain.duration().unwrap();
+
+        // Twice as long, same pitch
+        let mut stretched = plain.clone().with_time_stretch(2.0);
+        assert!((stretched.duration().unwrap() - 2.0 * duration).abs() < 1e-4);
+        let samples = stretched.generate((0.6 * SR) as usize);
+        let pitch = cycles(&samples[(0.4 * SR) as usize..(0.5 * SR) as usize]);
+        assert!((21..=23).contains(&pitch), "{} cycles", pitch);
+
+        // An octave up, same length
+        let mut raised = plain.clone();
+        raised.set_frequency(analysis.fundamental * 2.0);
+        assert_eq!(raised.duration(), Some(duration));
+        let samples = raised.generate((0.3 * SR) as usize);
+        let pitch = cycles(&samples[(0.1 * SR) as usize..(0.2 * SR) as usize]);
+        assert!((43..=45).contains(&pitch), "{} cycles", pitch);
+
+        // Nothing sounds once the envelopes run out
+        let mut plain = plain;
+        let samples = plain.generate(((duration + 0.1) * SR) as usize);
+        assert!(samples[((duration + 0.01) * SR) as usize..]
+            .iter()
+            .all(|s| *s == 0.0));
+    }
+
+    #[test]
+    fn test_analysis_rejects_short_or_silent_samples() {
+        assert!(
+            SinusoidalAnalysis::from_sample(&Sample::from_mono(vec![0.5; 100], 44100), 8).is_err()
+        );
+        assert!(
+            SinusoidalAnalysis::from_sample(&Sample::from_mono(vec![0.0; 8192], 44100), 8).is_err()
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Unison: stacked, detuned copies of each oscillator
+//!
+//! Every frequency in a note is played by up to eight voices fanned out in pitch
+//! and across the stereo field. The per-voice parameters (pitch ratios, start
+//! phases, gains and pans) live in the lanes of one SIMD vector and the voices are
+//! mixed down with SIMD, but each voice's oscillator is still sampled one at a time.
+
+use wide::f32x8;
+
+/// Most voices a unison stack can hold (one per SIMD lane)
+pub const MAX_UNISON_VOICES: u8 = 8;
+
+/// ln(2) / 1200: multiply cents by this and exponentiate to get a pitch ratio
+const CENTS_TO_LN: f32 = std::f32::consts::LN_2 / 1200.0;
+
+/// Start phase of each voice at full scatter
+///
+/// Successive multiples of the golden ratio, so no two voices start close together.
+/// The table is fixed: scatter is not drawn from a random generator.
+const SCATTER: [f32; 8] = [0.37, 0.988, 0.606, 0.224, 0.842, 0.46, 0.078, 0.696];
+
+/// Unison settings for a note
+///
+/// A track with any spread notes renders in stereo, and its filter, volume and
+/// effects process both channels.
+///
+/// # Example
+/// ```
+/// use tunes::synthesis::unison::Unison;
+///
+/// // Seven voices spread ±25 cents, wide stereo, fully scattered start phases
+/// let unison = Unison::new(7, 25.0).with_spread(0.8).with_phase_random(1.0);
+/// assert!(unison.is_stereo());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unison {
+    /// Number of voices per frequency (1 turns unison off, at most 8)
+    pub voices: u8,
+    /// Detune of the outermost voices in cents, either side of the note
+    pub detune_cents: f32,
+    /// Stereo width (0.0 = mono, 1.0 = outermost voices hard left and right)
+    pub spread: f32,
+    /// How far the voices' start phases are scattered (0.0 = all in phase, 1.0 = spread
+    /// over the whole cycle)
+    ///
+    /// The phases are fixed fractions of a cycle, not random draws: every note starts
+    /// its voices the same way.
+    pub phase_random: f32,
+}
+
+impl Unison {
+    /// Create unison with `voices` voices detuned up to `detune_cents` either side
+    pub fn new(voices: u8, detune_cents: f32) -> Self {
+        Self {
+            voices: voices.clamp(1, MAX_UNISON_VOICES),
+            detune_cents: detune_cents.max(0.0),
+            spread: 0.0,
+            phase_random: 0.0,
+        }
+    }
+
+    /// Single voice: unison off
+    pub fn off() -> Self {
+        Self::new(1, 0.0)
+    }
+
+    /// Set the stereo width (0.0 to 1.0)
+    pub fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set how far the voices' start phases are scattered (0.0 to 1.0)
+    ///
+    /// Each voice's phase is a fixed fraction of a cycle scaled by `amount`, so every
+    /// note starts the same way and renders are repeatable.
+    pub fn with_phase_random(mut self, amount: f32) -> Self {
+        self.phase_random = amount.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Check whether more than one voice plays
+    pub fn is_active(&self) -> bool {
+        self.voices > 1
+    }
+
+    /// Check whether the voices are spread across the stereo field
+    pub fn is_stereo(&self) -> bool {
+        self.is_active() && self.spread > 0.0
+    }
+
+    /// Pitch ratio, start phase, gain and pan of every voice, one per lane
+    ///
+    /// Unused lanes have zero gain. Gains are scaled by 1/√voices so a stack is
+    /// about as loud as a single oscillator.
+    #[inline]
+    pub(crate) fn lanes(&self) -> UnisonLanes {
+        let voices = self.voices.clamp(1, MAX_UNISON_VOICES) as usize;
+        let position = if voices == 1 {
+            f32x8::ZERO
+        } else {
+            // -1.0 (lowest voice) to 1.0 (highest), lanes past the last voice ignored
+            f32x8::from([0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0])
+                * f32x8::splat(2.0 / (voices - 1) as f32)
+                - f32x8::ONE
+        };
+        let mut gain = [0.0; 8];
+        gain[..voices].fill(1.0 / (voices as f32).sqrt());
+        let gain = f32x8::from(gain);
+
+        UnisonLanes {
+            ratio: (position * f32x8::splat(self.detune_cents * CENTS_TO_LN)).exp(),
+            phase: f32x8::from(SCATTER) * f32x8::splat(self.phase_random),
+            gain,
+            side: gain * position * f32x8::splat(self.spread),
+            voices,
+        }
+    }
+}
+
+impl Default for Unison {
+    fn default() -> Self {
+        Self::off()
+    }
+}
+
+/// Per-voice parameters of a unison stack, laid out for SIMD
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct UnisonLanes {
+    ratio: f32x8,
+    phase: f32x8,
+    gain: f32x8,
+    side: f32x8,
+    voices: usize,
+}
+
+impl UnisonLanes {
+    /// Render one sample of the stack as `(mid, side)`
+    ///
+    /// `oscillator(time, frequency)` produces one voice; each voice is asked for
+    /// its own frequency at a time shifted by its start phase. Left is
+    /// `mid - side` and right is `mid + side`.
+    #[inline]
+    pub fn sample(
+        &self,
+        time: f32,
+        frequency: f32,
+        mut oscillator: impl FnMut(f32, f32) -> f32,
+    ) -> (f32, f32) {
+        let frequencies = self.ratio * f32x8::splat(frequency);
+        let times = f32x8::splat(time) + self.phase / frequencies.max(f32x8::splat(1e-6));
+
+        let frequencies = frequencies.to_array();
+        let times = times.to_array();
+        let mut outputs = [0.0; 8];
+        for voice in 0..self.voices {
+            outputs[voice] = oscillator(times[voice], frequencies[voice]);
+        }
+
+        let outputs = f32x8::from(outputs);
+        (
+            (outputs * self.gain).reduce_add(),
+            (outputs * self.side).reduce_add(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthesis::waveform::Waveform;
+
+    fn render(unison: Unison, frequency: f32) -> Vec<(f32, f32)> {
+        let lanes = unison.lanes();
+        (0..44100)
+            .map(|i| {
+                lanes.sample(i as f32 / 44100.0, frequency, |t, f| {
+                    Waveform::Sawtooth.oscillate(t, f, 44100.0)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_single_voice_matches_the_oscillator() {
+        let lanes = Unison::off().lanes();
+        for i in 0..100 {
+            let time = i as f32 / 44100.0;
+            let (mid, side) =
+                lanes.sample(time, 220.0, |t, f| Waveform::Sine.oscillate(t, f, 44100.0));
+            assert_eq!(mid, Waveform::Sine.oscillate(time, 220.0, 44100.0));
+            assert_eq!(side, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_voices_are_detuned_symmetrically() {
+        let lanes = Unison::new(3, 1200.0).lanes();
+        let mut seen = Vec::new();
+        lanes.sample(0.0, 100.0, |_, f| {
+            seen.push(f);
+            0.0
+        });
+        assert_eq!(seen.len(), 3);
+        assert!((seen[0] - 50.0).abs() < 1e-3);
+        assert!((seen[1] - 100.0).abs() < 1e-3);
+        assert!((seen[2] - 200.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_spread_only_adds_side() {
+        let narrow = render(Unison::new(5, 15.0), 110.0);
+        let wide = render(Unison::new(5, 15.0).with_spread(1.0), 110.0);
+        for ((mid_a, side_a), (mid_b, _)) in narrow.iter().zip(&wide) {
+            assert_eq!(mid_a, mid_b);
+            assert_eq!(*side_a, 0.0);
+        }
+        assert!(wide.iter().any(|(_, side)| side.abs() > 0.1));
+    }
+
+    #[test]
+    fn test_stack_loudness_stays_close_to_one_voice() {
+        let rms = |frames: &[(f32, f32)]| {
+            (frames.iter().map(|(mid, _)| mid * mid).sum::<f32>() / frames.len() as f32).sqrt()
+        };
+        let single = rms(&render(Unison::off(), 110.0));
+        let stack = rms(&render(Unison::new(8, 20.0).with_phase_random(1.0), 110.0));
+        assert!(
+            (stack / single - 1.0).abs() < 0.3,
+            "{} vs {}",
+            stack,
+            single
+        );
+    }
+}
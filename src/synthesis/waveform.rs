@@ -4,20 +4,150 @@ use crate::synthesis::wavetable::{SAWTOOTH_WAVETABLE, SQUARE_WAVETABLE, TRIANGLE
 ///
 /// All waveforms use band-limited wavetables to prevent aliasing at high frequencies.
 /// This ensures clean audio quality across the entire frequency spectrum.
+///
+/// The virtual-analog shapes (`Pulse`, `Sync`, `Ring`, `CrossMod`, `Noise`,
+/// `Supersaw`) need to know the note's frequency and time, so they are rendered
+/// through [`Waveform::oscillate`]; their edges are smoothed with PolyBLEP
+/// corrections, which greatly reduces aliasing.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Waveform {
     Sine,
     Square,
     Sawtooth,
     Triangle,
+    /// Pulse wave high for `width` of each cycle (0.5 is a square wave),
+    /// swept by `pwm_depth` at `pwm_rate` Hz
+    Pulse {
+        width: f32,
+        pwm_rate: f32,
+        pwm_depth: f32,
+    },
+    /// Sawtooth running `ratio` times faster than the note, hard-synced so it
+    /// restarts with every cycle of the note
+    Sync { ratio: f32 },
+    /// Sawtooth ring-modulated by a second oscillator at `ratio` times the note
+    /// frequency
+    Ring { ratio: f32, modulator: Modulator },
+    /// Sawtooth whose phase is modulated by a second oscillator at `ratio` times
+    /// the note frequency; `amount` is the modulation depth in cycles
+    CrossMod {
+        ratio: f32,
+        amount: f32,
+        modulator: Modulator,
+    },
+    /// White noise (pitch is ignored); `seed` picks which noise is played
+    ///
+    /// Each note added to a track draws a fresh seed from the track's generator,
+    /// so noise notes differ from one another but follow the composition seed.
+    Noise { seed: u32 },
+    /// Seven detuned sawtooths; `detune` (0.0-1.0) spreads them apart and
+    /// `mix` (0.0-1.0) brings in the six side voices
+    Supersaw { detune: f32, mix: f32 },
 }
 
+/// Shape of the second oscillator in [`Waveform::Ring`] and [`Waveform::CrossMod`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Modulator {
+    #[default]
+    Sine,
+    Triangle,
+    Square,
+    Sawtooth,
+}
+
+impl Modulator {
+    /// Sample the modulator at a given phase (0.0 to 1.0), from the band-limited wavetables
+    #[inline(always)]
+    pub fn sample(self, phase: f32) -> f32 {
+        match self {
+            Modulator::Sine => Waveform::sine(phase),
+            Modulator::Triangle => Waveform::triangle(phase),
+            Modulator::Square => Waveform::square(phase),
+            Modulator::Sawtooth => Waveform::sawtooth(phase),
+        }
+    }
+}
+
+/// Detune of each supersaw voice relative to the centre, at full `detune`
+const SUPERSAW_DETUNE: [f32; 7] = [
+    -0.110_023_13,
+    -0.062_884_39,
+    -0.019_523_56,
+    0.0,
+    0.019_912_21,
+    0.062_165_38,
+    0.107_452_42,
+];
+
+/// Start phase of each supersaw voice, so the stack doesn't start in phase
+const SUPERSAW_PHASE: [f32; 7] = [0.0, 0.63, 0.19, 0.87, 0.41, 0.72, 0.28];
+
 impl Waveform {
+    /// Pulse wave with a fixed width (0.5 is a square wave)
+    pub fn pulse(width: f32) -> Self {
+        Waveform::Pulse {
+            width,
+            pwm_rate: 0.0,
+            pwm_depth: 0.0,
+        }
+    }
+
+    /// Pulse wave whose width is swept by a sine LFO
+    ///
+    /// # Arguments
+    /// * `width` - Centre pulse width (0.0 to 1.0)
+    /// * `rate` - Sweep rate in Hz
+    /// * `depth` - How far the width swings either side of `width`
+    pub fn pwm(width: f32, rate: f32, depth: f32) -> Self {
+        Waveform::Pulse {
+            width,
+            pwm_rate: rate,
+            pwm_depth: depth,
+        }
+    }
+
+    /// Hard-synced sawtooth at `ratio` times the note frequency (clamped to at least 1.0)
+    pub fn sync(ratio: f32) -> Self {
+        Waveform::Sync { ratio }
+    }
+
+    /// Sawtooth ring-modulated by `modulator` at `ratio` times the note frequency
+    pub fn ring(ratio: f32, modulator: Modulator) -> Self {
+        Waveform::Ring { ratio, modulator }
+    }
+
+    /// Sawtooth phase-modulated by `modulator` at `ratio` times the note frequency,
+    /// `amount` cycles deep
+    pub fn cross_mod(ratio: f32, amount: f32, modulator: Modulator) -> Self {
+        Waveform::CrossMod {
+            ratio,
+            amount,
+            modulator,
+        }
+    }
+
+    /// White noise with seed 0 (tracks give each note a seed of its own)
+    pub fn noise() -> Self {
+        Waveform::Noise { seed: 0 }
+    }
+
+    /// Classic supersaw with moderate detune and side voices mixed in
+    pub fn supersaw() -> Self {
+        Waveform::Supersaw {
+            detune: 0.5,
+            mix: 0.6,
+        }
+    }
+
     /// Generate a sample for this waveform at a given phase (0.0 to 1.0)
     ///
     /// All waveforms use pre-computed band-limited wavetables for high-quality,
     /// alias-free synthesis. This is much faster than computing waveforms
     /// mathematically and produces better audio quality.
+    ///
+    /// The virtual-analog shapes are evaluated as if one cycle lasted one
+    /// second and without edge smoothing; use [`oscillate`](Self::oscillate)
+    /// when rendering notes.
     #[inline(always)]
     pub fn sample(&self, phase: f32) -> f32 {
         match self {
@@ -25,6 +155,58 @@ impl Waveform {
             Waveform::Square => Self::square(phase),
             Waveform::Sawtooth => Self::sawtooth(phase),
             Waveform::Triangle => Self::triangle(phase),
+            _ => self.analog(phase, 1.0, 0.0, phase.to_bits()),
+        }
+    }
+
+    /// Generate a sample for a note `time` seconds after it started
+    ///
+    /// This is what the renderers call: the classic shapes read their
+    /// wavetables at `time * frequency`, and the virtual-analog shapes use the
+    /// time and sample rate for modulation, detuning and edge smoothing.
+    #[inline(always)]
+    pub fn oscillate(&self, time: f32, frequency: f32, sample_rate: f32) -> f32 {
+        match self {
+            Waveform::Sine | Waveform::Square | Waveform::Sawtooth | Waveform::Triangle => {
+                self.sample((time * frequency) % 1.0)
+            }
+            _ => {
+                let index = (time * sample_rate).round() as u32;
+                self.analog(time, frequency, frequency / sample_rate, index)
+            }
+        }
+    }
+
+    /// Virtual-analog shapes; `dt` is the phase increment per sample (0.0 disables PolyBLEP)
+    /// and `index` the sample's position in the note
+    fn analog(&self, time: f32, frequency: f32, dt: f32, index: u32) -> f32 {
+        let phase = (time * frequency) % 1.0;
+        match *self {
+            Waveform::Pulse {
+                width,
+                pwm_rate,
+                pwm_depth,
+            } => {
+                let lfo = (time * pwm_rate * 2.0 * std::f32::consts::PI).sin();
+                Self::pulse_blep(phase, (width + pwm_depth * lfo).clamp(0.02, 0.98), dt)
+            }
+            Waveform::Sync { ratio } => Self::sync_blep(phase, ratio.max(1.0), dt),
+            Waveform::Ring { ratio, modulator } => {
+                Self::saw_blep(phase, dt) * modulator.sample((time * frequency * ratio) % 1.0)
+            }
+            Waveform::CrossMod {
+                ratio,
+                amount,
+                modulator,
+            } => {
+                let modulation = modulator.sample((time * frequency * ratio) % 1.0);
+                Self::sawtooth((phase + amount * modulation).rem_euclid(1.0))
+            }
+            Waveform::Noise { seed } => Self::white_noise(index.wrapping_add(seed)),
+            Waveform::Supersaw { detune, mix } => {
+                Self::supersaw_blep(time, frequency, detune, mix, dt)
+            }
+            _ => self.sample(phase),
         }
     }
 
@@ -63,11 +245,83 @@ impl Waveform {
         TRIANGLE_WAVETABLE.sample(phase)
     }
 
+    /// PolyBLEP residual for a unit step at phase 0, scaled for a jump of 2
+    #[inline(always)]
+    fn poly_blep(phase: f32, dt: f32) -> f32 {
+        if dt <= 0.0 {
+            0.0
+        } else if phase < dt {
+            let t = phase / dt;
+            t + t - t * t - 1.0
+        } else if phase > 1.0 - dt {
+            let t = (phase - 1.0) / dt;
+            t * t + t + t + 1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Rising sawtooth with a smoothed wrap
+    fn saw_blep(phase: f32, dt: f32) -> f32 {
+        2.0 * phase - 1.0 - Self::poly_blep(phase, dt)
+    }
+
+    /// Pulse high for the first `width` of the cycle, with both edges smoothed
+    fn pulse_blep(phase: f32, width: f32, dt: f32) -> f32 {
+        let naive = if phase < width { 1.0 } else { -1.0 };
+        naive + Self::poly_blep(phase, dt) - Self::poly_blep((phase - width + 1.0) % 1.0, dt)
+    }
+
+    /// Sawtooth at `ratio` times the master, restarted when the master wraps
+    fn sync_blep(phase: f32, ratio: f32, dt: f32) -> f32 {
+        let cycles = phase * ratio;
+        let slave = cycles.fract();
+        let slave_dt = dt * ratio;
+        let mut value = 2.0 * slave - 1.0;
+
+        // The slave's own wraps, except the one the master reset replaces
+        let wrapped = slave < slave_dt && cycles >= 1.0;
+        let wrapping = slave > 1.0 - slave_dt && cycles.floor() + 1.0 < ratio;
+        if wrapped || wrapping {
+            value -= Self::poly_blep(slave, slave_dt);
+        }
+
+        // The reset drops the slave from wherever it got to back to -1
+        let reached = 1.0 - (ratio.ceil() - ratio);
+        value - reached * Self::poly_blep(phase, dt)
+    }
+
+    /// Seven detuned sawtooths, normalized to stay within -1.0 to 1.0
+    fn supersaw_blep(time: f32, frequency: f32, detune: f32, mix: f32, dt: f32) -> f32 {
+        let mix = mix.clamp(0.0, 1.0);
+        let centre_gain = 1.0 - 0.55 * mix;
+        let side_gain = -0.74 * mix * mix + 1.28 * mix + 0.04;
+
+        let mut sum = 0.0;
+        for (voice, (offset, start)) in SUPERSAW_DETUNE.iter().zip(SUPERSAW_PHASE).enumerate() {
+            let ratio = 1.0 + detune.clamp(0.0, 1.0) * offset;
+            let phase = (time * frequency * ratio + start) % 1.0;
+            let gain = if voice == 3 { centre_gain } else { side_gain };
+            sum += gain * Self::saw_blep(phase, dt * ratio);
+        }
+        sum / (centre_gain + 6.0 * side_gain)
+    }
+
+    /// White noise from an integer hash, so the same note always renders the same
+    fn white_noise(seed: u32) -> f32 {
+        let mut x = seed.wrapping_mul(0x9E37_79B9) ^ 0x85EB_CA6B;
+        x ^= x >> 16;
+        x = x.wrapping_mul(0x7FEB_352D);
+        x ^= x >> 15;
+        x = x.wrapping_mul(0x846C_A68B);
+        x ^= x >> 16;
+        x as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+
     /// Generate a sample for a frequency at a given sample clock and sample rate
     #[inline(always)]
     pub fn sample_at(&self, frequency: f32, sample_clock: f32, sample_rate: f32) -> f32 {
-        let phase = sample_clock * frequency / sample_rate;
-        self.sample(phase)
+        self.oscillate(sample_clock / sample_rate, frequency, sample_rate)
     }
 }
 
@@ -98,4 +352,109 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_analog_waveforms_stay_in_range() {
+        let waveforms = [
+            Waveform::pwm(0.3, 2.0, 0.2),
+            Waveform::sync(2.7),
+            Waveform::ring(1.5, Modulator::Sine),
+            Waveform::ring(0.5, Modulator::Square),
+            Waveform::cross_mod(2.0, 0.3, Modulator::Sine),
+            Waveform::cross_mod(1.5, 0.2, Modulator::Triangle),
+            Waveform::noise(),
+            Waveform::supersaw(),
+        ];
+
+        for waveform in &waveforms {
+            for frequency in [55.0, 440.0, 3520.0] {
+                for i in 0..4410 {
+                    let sample = waveform.oscillate(i as f32 / 44100.0, frequency, 44100.0);
+                    assert!(
+                        (-1.0..=1.0).contains(&sample),
+                        "{:?} at {} Hz produced out of range sample: {}",
+                        waveform,
+                        frequency,
+                        sample
+                    );
+                }
+                assert!((-1.0..=1.0).contains(&waveform.sample(0.37)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pulse_width_sets_duty_cycle() {
+        let pulse = Waveform::pulse(0.25);
+        let high = (0..44100)
+            .filter(|i| pulse.oscillate(*i as f32 / 44100.0, 100.0, 44100.0) > 0.0)
+            .count();
+        assert!((high as f32 / 44100.0 - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_sync_restarts_with_the_note() {
+        let sync = Waveform::sync(3.3);
+        let offset = 10.0 / 48000.0;
+        let start = sync.oscillate(offset, 100.0, 48000.0);
+        for cycle in 1..10 {
+            let time = cycle as f32 / 100.0 + offset;
+            assert!((sync.oscillate(time, 100.0, 48000.0) - start).abs() < 1e-3);
+        }
+        // Between edges the slave is a plain rising sawtooth at `ratio` times the pitch
+        let slave = Waveform::sync(2.0).oscillate(0.00123, 100.0, 48000.0);
+        assert!((slave - (2.0 * 0.246 - 1.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_polyblep_softens_edges() {
+        // Naive edges jump by 2.0 in a single sample; smoothed ones spread the step
+        let pulse = Waveform::pulse(0.5);
+        let max_step = (1..4410)
+            .map(|i| {
+                let a = pulse.oscillate((i - 1) as f32 / 44100.0, 2000.0, 44100.0);
+                let b = pulse.oscillate(i as f32 / 44100.0, 2000.0, 44100.0);
+                (b - a).abs()
+            })
+            .fold(0.0f32, f32::max);
+        assert!(max_step < 1.9, "max step {}", max_step);
+    }
+
+    #[test]
+    fn test_noise_is_white_and_repeatable() {
+        let samples: Vec<f32> = (0..44100)
+            .map(|i| Waveform::noise().oscillate(i as f32 / 44100.0, 440.0, 44100.0))
+            .collect();
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        assert!(mean.abs() < 0.02);
+        assert_eq!(
+            samples[100],
+            Waveform::noise().oscillate(100.0 / 44100.0, 220.0, 44100.0)
+        );
+        assert_ne!(samples[100], samples[101]);
+    }
+
+    #[test]
+    fn test_noise_seed_picks_the_sequence() {
+        let play = |seed| -> Vec<f32> {
+            (0..64)
+                .map(|i| Waveform::Noise { seed }.oscillate(i as f32 / 44100.0, 440.0, 44100.0))
+                .collect()
+        };
+        assert_eq!(play(7), play(7));
+        assert_ne!(play(7), play(8_000_000));
+    }
+
+    #[test]
+    fn test_ring_modulator_shape() {
+        // A square modulator only flips the carrier's sign
+        let time = 0.0013;
+        let carrier = Waveform::Sawtooth.oscillate(time, 100.0, 44100.0);
+        let ring = Waveform::ring(0.5, Modulator::Square).oscillate(time, 100.0, 44100.0);
+        assert!((ring.abs() - carrier.abs()).abs() < 0.05);
+        assert_ne!(
+            ring,
+            Waveform::ring(0.5, Modulator::Sine).oscillate(time, 100.0, 44100.0)
+        );
+    }
 }
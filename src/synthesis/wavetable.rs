@@ -6,15 +6,38 @@
 /// - Samples: `Wavetable::from_samples(vec![...])`
 /// - Harmonics: `Wavetable::from_harmonics(&[(1, 1.0), (3, 0.3)])`
 /// - Presets: `Wavetable::sine()`, `Wavetable::saw()`, etc.
+/// - Several frames to morph between: `Wavetable::from_frames(...)`, `Wavetable::from_file(path, 2048)`
+use super::sample::Sample;
+use crate::error::{Result, TunesError};
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
 use std::f32::consts::PI;
+use std::path::Path;
 use std::sync::Arc;
 
 /// Default size for wavetables (power of 2 for efficiency)
 /// 2048 samples provides good quality while being cache-friendly
 pub const DEFAULT_TABLE_SIZE: usize = 2048;
 
+/// Smallest table a band-limited mip level is stored at
+///
+/// Low levels hold only a few harmonics; keeping at least this many samples per
+/// cycle keeps linear interpolation from adding harmonics of its own.
+const MIN_MIP_SIZE: usize = 256;
+
+/// Harmonics quieter than this, relative to the loudest, count as absent when
+/// deciding how many mip levels a table needs
+const HARMONIC_FLOOR: f32 = 1e-5;
+
 /// User-definable wavetable for fast oscillator lookups
 ///
+/// A wavetable holds one or more single-cycle frames. With several frames, the
+/// [`position`](Wavetable::with_position) morphs between neighbouring frames, and
+/// the mod matrix can move it per note. Every frame also gets band-limited copies
+/// (mipmaps) one octave apart, which [`sample_band_limited`](Wavetable::sample_band_limited)
+/// and [`fill_buffer_simd`](Wavetable::fill_buffer_simd) pick from so high notes
+/// don't alias.
+///
 /// # Examples
 /// ```
 /// use tunes::synthesis::wavetable::Wavetable;
@@ -28,13 +51,75 @@ pub const DEFAULT_TABLE_SIZE: usize = 2048;
 /// // Use presets
 /// let sine = Wavetable::sine();
 /// let saw = Wavetable::saw_bandlimited();
+///
+/// // Morph a third of the way from sine towards square
+/// let shapes = Wavetable::basic_shapes().with_position(0.33);
+/// assert_eq!(shapes.frame_count(), 4);
 /// ```
 #[derive(Clone, Debug)]
 pub struct Wavetable {
-    table: Arc<Vec<f32>>,
+    // Level 0 holds the frames as given; later levels hold fewer harmonics each
+    levels: Arc<Vec<MipLevel>>,
+    frames: usize,
+    position: f32,
+}
+
+/// Every frame of a wavetable, band-limited to `max_harmonic`
+#[derive(Debug)]
+struct MipLevel {
+    max_harmonic: usize,
+    /// Samples per frame
+    size: usize,
+    /// The frames one after another
+    data: Vec<f32>,
+}
+
+impl MipLevel {
+    /// Linearly interpolated lookup into one frame
+    #[inline(always)]
+    fn lookup(&self, frame: usize, phase: f32) -> f32 {
+        let size = self.size;
+        let table_pos = phase * size as f32;
+        let index = (table_pos as usize).min(size - 1);
+        let frac = table_pos - index as f32;
+        let next = if index + 1 == size { 0 } else { index + 1 };
+
+        // Use unchecked indexing for performance: `frame` is below the frame count
+        // and both indices are below `size`
+        let base = frame * size;
+        let sample1 = unsafe { *self.data.get_unchecked(base + index) };
+        let sample2 = unsafe { *self.data.get_unchecked(base + next) };
+
+        // FMA (fused multiply-add) is a single CPU instruction on modern hardware
+        sample1 + (sample2 - sample1) * frac
+    }
+}
+
+/// The two frames either side of a morph position, and the blend between them
+#[derive(Debug, Clone, Copy)]
+struct Morph {
+    from: usize,
+    to: usize,
+    amount: f32,
+}
+
+impl Morph {
+    #[inline(always)]
+    fn sample(&self, level: &MipLevel, phase: f32) -> f32 {
+        let from = level.lookup(self.from, phase);
+        if self.amount == 0.0 {
+            return from;
+        }
+        from + (level.lookup(self.to, phase) - from) * self.amount
+    }
 }
 
 impl Wavetable {
+    /// Build a single-frame wavetable
+    fn single(table: Vec<f32>) -> Self {
+        Self::from_frames(vec![table])
+    }
+
     /// Create a wavetable from a function that maps phase (0.0-1.0) to amplitude
     ///
     /// # Example
@@ -54,9 +139,7 @@ impl Wavetable {
             let phase = (i as f32) * size_recip;
             table.push(f(phase));
         }
-        Self {
-            table: Arc::new(table),
-        }
+        Self::single(table)
     }
 
     /// Create a wavetable from a vector of samples
@@ -71,11 +154,84 @@ impl Wavetable {
     /// let wt = Wavetable::from_samples(samples);
     /// ```
     pub fn from_samples(samples: Vec<f32>) -> Self {
+        Self::single(samples)
+    }
+
+    /// Create a morphing wavetable from several single-cycle frames
+    ///
+    /// Position 0.0 plays the first frame and 1.0 the last.
+    ///
+    /// # Panics
+    /// Panics if there are no frames, a frame is empty, or the frames differ in length
+    ///
+    /// # Example
+    /// ```
+    /// use tunes::synthesis::wavetable::Wavetable;
+    /// let bright = (0..64).map(|i| if i < 32 { 1.0 } else { -1.0 }).collect();
+    /// let dark = (0..64).map(|i| (i as f32 / 64.0 * std::f32::consts::TAU).sin()).collect();
+    /// let wt = Wavetable::from_frames(vec![dark, bright]);
+    /// assert_eq!(wt.frame_count(), 2);
+    /// ```
+    pub fn from_frames(frames: Vec<Vec<f32>>) -> Self {
+        assert!(!frames.is_empty(), "a wavetable needs at least one frame");
+        let size = frames[0].len();
+        assert!(size > 0, "wavetable frames can't be empty");
+        assert!(
+            frames.iter().all(|frame| frame.len() == size),
+            "wavetable frames must all be the same length"
+        );
+
+        let count = frames.len();
+        let data: Vec<f32> = frames.into_iter().flatten().collect();
         Self {
-            table: Arc::new(samples),
+            levels: Arc::new(build_mip_levels(data, size, count)),
+            frames: count,
+            position: 0.0,
         }
     }
 
+    /// Create a morphing wavetable from frames laid end to end
+    ///
+    /// This is how Serum and Vital store wavetables in WAV files (2048 samples per
+    /// frame). Samples after the last whole frame are ignored.
+    ///
+    /// # Panics
+    /// Panics if `frame_size` is zero or there are fewer than `frame_size` samples
+    pub fn from_concatenated(samples: &[f32], frame_size: usize) -> Self {
+        assert!(frame_size > 0, "frame size must be positive");
+        Self::from_frames(
+            samples
+                .chunks_exact(frame_size)
+                .map(|frame| frame.to_vec())
+                .collect(),
+        )
+    }
+
+    /// Load a morphing wavetable from an audio file of frames laid end to end
+    ///
+    /// Stereo files are mixed down to mono. Any format [`Sample::from_file`] reads
+    /// is accepted; Serum and Vital tables are WAV files with a `frame_size` of 2048.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::synthesis::wavetable::Wavetable;
+    /// let table = Wavetable::from_file("tables/growl.wav", 2048)?;
+    /// # Ok::<(), tunes::error::TunesError>(())
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P, frame_size: usize) -> Result<Self> {
+        let sample = Sample::from_file(path)?;
+        let mono = sample.to_mono();
+
+        if frame_size == 0 || mono.len() < frame_size {
+            return Err(TunesError::InvalidAudioFormat(format!(
+                "wavetable of {} samples holds no frame of {} samples",
+                mono.len(),
+                frame_size
+            )));
+        }
+        Ok(Self::from_concatenated(&mono, frame_size))
+    }
+
     /// Create a wavetable from harmonics using additive synthesis
     ///
     /// Each harmonic is specified as (harmonic_number, amplitude).
@@ -94,29 +250,7 @@ impl Wavetable {
     /// ]);
     /// ```
     pub fn from_harmonics(size: usize, harmonics: &[(usize, f32)]) -> Self {
-        let mut table = vec![0.0; size];
-        const TWO_PI: f32 = 2.0 * PI;
-
-        let size_recip = 1.0 / (size as f32);
-        for &(harmonic, amplitude) in harmonics {
-            let harmonic_f32 = harmonic as f32;
-            for (i, sample) in table.iter_mut().enumerate() {
-                let phase = (i as f32) * size_recip;
-                *sample += amplitude * (phase * harmonic_f32 * TWO_PI).sin();
-            }
-        }
-
-        // Normalize to [-1.0, 1.0] range
-        let max_amp = table.iter().map(|&x| x.abs()).fold(0.0f32, |a, b| a.max(b));
-        if max_amp > 0.0 {
-            for sample in &mut table {
-                *sample /= max_amp;
-            }
-        }
-
-        Self {
-            table: Arc::new(table),
-        }
+        Self::single(harmonic_table(size, harmonics))
     }
 
     /// Create a sine wave wavetable
@@ -129,23 +263,14 @@ impl Wavetable {
     /// Uses additive synthesis with harmonics to create a smoother sawtooth
     /// that won't alias at high frequencies.
     pub fn saw_bandlimited() -> Self {
-        // Sawtooth = all harmonics with 1/n amplitude
-        let harmonics: Vec<(usize, f32)> = (1..32).map(|n| (n, 1.0 / n as f32)).collect();
-        Self::from_harmonics(DEFAULT_TABLE_SIZE, &harmonics)
+        Self::single(saw_table())
     }
 
     /// Create a band-limited square wave (reduces aliasing)
     ///
     /// Uses additive synthesis with odd harmonics only.
     pub fn square_bandlimited() -> Self {
-        // Square = odd harmonics with 1/n amplitude
-        let harmonics: Vec<(usize, f32)> = (0..16)
-            .map(|i| {
-                let n = 2 * i + 1; // Odd numbers: 1, 3, 5, 7...
-                (n, 1.0 / n as f32)
-            })
-            .collect();
-        Self::from_harmonics(DEFAULT_TABLE_SIZE, &harmonics)
+        Self::single(square_table())
     }
 
     /// Create a band-limited triangle wave (reduces aliasing)
@@ -154,15 +279,15 @@ impl Wavetable {
     /// Triangle waves contain odd harmonics like square waves, but
     /// they decay much faster (1/n² vs 1/n), giving a rounder sound.
     pub fn triangle_bandlimited() -> Self {
-        // Triangle = odd harmonics with 1/n² amplitude
-        let harmonics: Vec<(usize, f32)> = (0..16)
-            .map(|i| {
-                let n = 2 * i + 1; // Odd numbers: 1, 3, 5, 7...
-                let sign = if i % 2 == 0 { 1.0 } else { -1.0 }; // Alternating signs
-                (n, sign / (n * n) as f32)
-            })
+        Self::single(triangle_table())
+    }
+
+    /// Create a four-frame table morphing sine → triangle → saw → square
+    pub fn basic_shapes() -> Self {
+        let sine = (0..DEFAULT_TABLE_SIZE)
+            .map(|i| (i as f32 / DEFAULT_TABLE_SIZE as f32 * 2.0 * PI).sin())
             .collect();
-        Self::from_harmonics(DEFAULT_TABLE_SIZE, &harmonics)
+        Self::from_frames(vec![sine, triangle_table(), saw_table(), square_table()])
     }
 
     /// Create a naive triangle wave (aliasing, for compatibility)
@@ -188,28 +313,52 @@ impl Wavetable {
         })
     }
 
+    /// Set the morph position (0.0 = first frame, 1.0 = last frame)
+    pub fn with_position(mut self, position: f32) -> Self {
+        self.position = position.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The morph position used when none is given
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+
+    /// Number of frames the table morphs through
+    pub fn frame_count(&self) -> usize {
+        self.frames
+    }
+
+    /// Address of the band-limited frames, shared by every clone of this table
+    pub(crate) fn frames_ptr(&self) -> *const () {
+        Arc::as_ptr(&self.levels).cast()
+    }
+
     /// Sample the wavetable at a given phase (0.0 to 1.0) with linear interpolation
     ///
+    /// Reads the frames exactly as given, at the table's morph position.
     /// This is ~10-100x faster than calling sin() or other trig functions directly.
     #[inline(always)]
     pub fn sample(&self, phase: f32) -> f32 {
-        // Wrap phase to 0.0-1.0 range using fast fract() instead of floor subtraction
-        let phase = phase.fract();
-
-        // Convert phase to table index (floating point)
-        let table_size = self.table.len();
-        let table_pos = phase * (table_size as f32);
-        let index = table_pos as usize;
-        let frac = table_pos - index as f32;
+        self.sample_morphed(phase, self.position)
+    }
 
-        // Linear interpolation between two adjacent samples
-        // Use unchecked indexing with manual bounds check for performance
-        // (index is guaranteed < table_size due to phase being in [0,1))
-        let sample1 = unsafe { *self.table.get_unchecked(index) };
-        let sample2 = unsafe { *self.table.get_unchecked((index + 1) & (table_size - 1)) };
+    /// Sample the frames exactly as given at a morph position (0.0 to 1.0)
+    #[inline(always)]
+    pub fn sample_morphed(&self, phase: f32, position: f32) -> f32 {
+        self.morph(position).sample(&self.levels[0], wrap(phase))
+    }
 
-        // FMA (fused multiply-add) is a single CPU instruction on modern hardware
-        sample1 + (sample2 - sample1) * frac
+    /// Sample the mip level that fits below Nyquist at a morph position
+    ///
+    /// # Arguments
+    /// * `phase` - Phase in cycles (wrapped to 0.0-1.0)
+    /// * `phase_increment` - Cycles per sample (frequency / sample_rate), which picks the mip level
+    /// * `position` - Morph position (0.0 to 1.0)
+    #[inline]
+    pub fn sample_band_limited(&self, phase: f32, phase_increment: f32, position: f32) -> f32 {
+        self.morph(position)
+            .sample(self.level_for(phase_increment), wrap(phase))
     }
 
     /// Sample at a specific frequency and time
@@ -223,6 +372,8 @@ impl Wavetable {
     ///
     /// This method processes 4-8 samples at once using SIMD instructions,
     /// providing 2-4x speedup compared to calling `.sample()` in a loop.
+    /// Plays the table's morph position from the mip level that keeps
+    /// `phase_increment` alias-free.
     ///
     /// # Arguments
     /// * `buffer` - Output buffer to fill with samples
@@ -244,22 +395,50 @@ impl Wavetable {
     ///
     /// let final_phase = wavetable.fill_buffer_simd(&mut buffer, 0.0, phase_inc);
     /// ```
-    pub fn fill_buffer_simd(&self, buffer: &mut [f32], start_phase: f32, phase_increment: f32) -> f32 {
+    pub fn fill_buffer_simd(
+        &self,
+        buffer: &mut [f32],
+        start_phase: f32,
+        phase_increment: f32,
+    ) -> f32 {
+        self.fill_buffer_simd_at(buffer, start_phase, phase_increment, self.position)
+    }
+
+    /// Fill a buffer like [`fill_buffer_simd`](Self::fill_buffer_simd) at a given morph position
+    pub fn fill_buffer_simd_at(
+        &self,
+        buffer: &mut [f32],
+        start_phase: f32,
+        phase_increment: f32,
+        position: f32,
+    ) -> f32 {
         use crate::synthesis::simd::{SimdWidth, SIMD};
 
+        let level = self.level_for(phase_increment);
+        let morph = self.morph(position);
         match SIMD.simd_width() {
-            SimdWidth::X8 => self.fill_buffer_impl::<8>(buffer, start_phase, phase_increment),
-            SimdWidth::X4 => self.fill_buffer_impl::<4>(buffer, start_phase, phase_increment),
-            SimdWidth::Scalar => self.fill_buffer_scalar(buffer, start_phase, phase_increment),
+            SimdWidth::X8 => {
+                Self::fill_buffer_impl::<8>(level, morph, buffer, start_phase, phase_increment)
+            }
+            SimdWidth::X4 => {
+                Self::fill_buffer_impl::<4>(level, morph, buffer, start_phase, phase_increment)
+            }
+            SimdWidth::Scalar => {
+                Self::fill_buffer_scalar(level, morph, buffer, start_phase, phase_increment)
+            }
         }
     }
 
     /// Generic SIMD implementation - processes N samples at once
     #[inline(always)]
-    fn fill_buffer_impl<const N: usize>(&self, buffer: &mut [f32], start_phase: f32, phase_increment: f32) -> f32 {
+    fn fill_buffer_impl<const N: usize>(
+        level: &MipLevel,
+        morph: Morph,
+        buffer: &mut [f32],
+        start_phase: f32,
+        phase_increment: f32,
+    ) -> f32 {
         let mut phase = start_phase;
-        let table_size = self.table.len();
-        let table_size_f32 = table_size as f32;
 
         // Calculate how many complete chunks we can process
         let num_chunks = buffer.len() / N;
@@ -273,53 +452,206 @@ impl Wavetable {
             // Generate N phases
             let mut phases = [0.0f32; 8]; // Max size for N=8
             for i in 0..N {
-                phases[i] = (phase + (i as f32) * phase_increment).fract();
+                phases[i] = wrap(phase + (i as f32) * phase_increment);
             }
 
             // Sample N values from the wavetable
             for i in 0..N {
-                let table_pos = phases[i] * table_size_f32;
-                let index = table_pos as usize;
-                let frac = table_pos - index as f32;
-
-                let sample1 = unsafe { *self.table.get_unchecked(index) };
-                let sample2 = unsafe { *self.table.get_unchecked((index + 1) & (table_size - 1)) };
-
-                chunk[i] = sample1 + (sample2 - sample1) * frac;
+                chunk[i] = morph.sample(level, phases[i]);
             }
 
             phase += (N as f32) * phase_increment;
         }
 
         // Handle remainder samples with scalar code
-        for i in remainder_start..buffer.len() {
-            buffer[i] = self.sample(phase);
+        for sample in &mut buffer[remainder_start..] {
+            *sample = morph.sample(level, wrap(phase));
             phase += phase_increment;
         }
 
-        phase.fract()
+        wrap(phase)
     }
 
     /// Scalar fallback implementation
     #[inline(always)]
-    fn fill_buffer_scalar(&self, buffer: &mut [f32], start_phase: f32, phase_increment: f32) -> f32 {
+    fn fill_buffer_scalar(
+        level: &MipLevel,
+        morph: Morph,
+        buffer: &mut [f32],
+        start_phase: f32,
+        phase_increment: f32,
+    ) -> f32 {
         let mut phase = start_phase;
         for sample in buffer {
-            *sample = self.sample(phase);
+            *sample = morph.sample(level, wrap(phase));
             phase += phase_increment;
         }
-        phase.fract()
+        wrap(phase)
+    }
+
+    /// Frames either side of `position`
+    #[inline(always)]
+    fn morph(&self, position: f32) -> Morph {
+        if self.frames == 1 {
+            return Morph {
+                from: 0,
+                to: 0,
+                amount: 0.0,
+            };
+        }
+        let frame = position.clamp(0.0, 1.0) * (self.frames - 1) as f32;
+        let from = (frame as usize).min(self.frames - 1);
+        Morph {
+            from,
+            to: (from + 1).min(self.frames - 1),
+            amount: frame - from as f32,
+        }
     }
 
-    /// Get the number of samples in this wavetable
+    /// The fullest mip level with no harmonic above Nyquist at `phase_increment`
+    #[inline]
+    fn level_for(&self, phase_increment: f32) -> &MipLevel {
+        let highest = 0.5 / phase_increment.abs().max(1e-9);
+        self.levels
+            .iter()
+            .find(|level| level.max_harmonic as f32 <= highest)
+            .unwrap_or_else(|| &self.levels[self.levels.len() - 1])
+    }
+
+    /// Get the number of samples in each frame
     pub fn len(&self) -> usize {
-        self.table.len()
+        self.levels[0].size
     }
 
     /// Check if the wavetable is empty
     pub fn is_empty(&self) -> bool {
-        self.table.is_empty()
+        self.levels[0].data.is_empty()
+    }
+}
+
+/// Wrap a phase in cycles to 0.0-1.0 (negative phases included)
+#[inline(always)]
+fn wrap(phase: f32) -> f32 {
+    phase - phase.floor()
+}
+
+/// One cycle summed from `(harmonic, amplitude)` pairs, normalized to ±1.0
+fn harmonic_table(size: usize, harmonics: &[(usize, f32)]) -> Vec<f32> {
+    let mut table = vec![0.0; size];
+    const TWO_PI: f32 = 2.0 * PI;
+
+    let size_recip = 1.0 / (size as f32);
+    for &(harmonic, amplitude) in harmonics {
+        let harmonic_f32 = harmonic as f32;
+        for (i, sample) in table.iter_mut().enumerate() {
+            let phase = (i as f32) * size_recip;
+            *sample += amplitude * (phase * harmonic_f32 * TWO_PI).sin();
+        }
+    }
+
+    // Normalize to [-1.0, 1.0] range
+    let max_amp = table.iter().map(|&x| x.abs()).fold(0.0f32, |a, b| a.max(b));
+    if max_amp > 0.0 {
+        for sample in &mut table {
+            *sample /= max_amp;
+        }
     }
+    table
+}
+
+fn saw_table() -> Vec<f32> {
+    // Sawtooth = all harmonics with 1/n amplitude
+    let harmonics: Vec<(usize, f32)> = (1..32).map(|n| (n, 1.0 / n as f32)).collect();
+    harmonic_table(DEFAULT_TABLE_SIZE, &harmonics)
+}
+
+fn square_table() -> Vec<f32> {
+    // Square = odd harmonics with 1/n amplitude
+    let harmonics: Vec<(usize, f32)> = (0..16)
+        .map(|i| {
+            let n = 2 * i + 1; // Odd numbers: 1, 3, 5, 7...
+            (n, 1.0 / n as f32)
+        })
+        .collect();
+    harmonic_table(DEFAULT_TABLE_SIZE, &harmonics)
+}
+
+fn triangle_table() -> Vec<f32> {
+    // Triangle = odd harmonics with 1/n² amplitude
+    let harmonics: Vec<(usize, f32)> = (0..16)
+        .map(|i| {
+            let n = 2 * i + 1; // Odd numbers: 1, 3, 5, 7...
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 }; // Alternating signs
+            (n, sign / (n * n) as f32)
+        })
+        .collect();
+    harmonic_table(DEFAULT_TABLE_SIZE, &harmonics)
+}
+
+/// Level 0 (the frames as given) plus one level per octave down to a single harmonic
+///
+/// Each level keeps half the harmonics of the one before. Levels are only made
+/// once band-limiting removes something, so a sine table has just level 0.
+fn build_mip_levels(data: Vec<f32>, size: usize, frames: usize) -> Vec<MipLevel> {
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(size);
+    let spectra: Vec<Vec<Complex<f32>>> = data
+        .chunks_exact(size)
+        .map(|frame| {
+            let mut spectrum: Vec<Complex<f32>> =
+                frame.iter().map(|&x| Complex::new(x, 0.0)).collect();
+            fft.process(&mut spectrum);
+            spectrum
+        })
+        .collect();
+
+    // Highest harmonic any frame actually uses
+    let nyquist = size / 2;
+    let loudest = spectra
+        .iter()
+        .flat_map(|spectrum| spectrum[1..=nyquist].iter().map(|bin| bin.norm()))
+        .fold(0.0f32, f32::max);
+    let top = (1..=nyquist)
+        .rev()
+        .find(|&k| {
+            spectra
+                .iter()
+                .any(|spectrum| spectrum[k].norm() > loudest * HARMONIC_FLOOR)
+        })
+        .unwrap_or(1);
+
+    let mut levels = vec![MipLevel {
+        max_harmonic: top,
+        size,
+        data,
+    }];
+
+    let mut max_harmonic = top / 2;
+    while max_harmonic >= 1 {
+        let level_size = (4 * max_harmonic).next_power_of_two().max(MIN_MIP_SIZE);
+        let ifft = FftPlanner::<f32>::new().plan_fft_inverse(level_size);
+        // Rescale bins so the inverse transform of the new size lands on the same levels
+        let scale = 1.0 / size as f32;
+
+        let mut level_data = Vec::with_capacity(level_size * frames);
+        for spectrum in &spectra {
+            let mut bins = vec![Complex::new(0.0, 0.0); level_size];
+            bins[0] = spectrum[0] * scale;
+            for k in 1..=max_harmonic {
+                bins[k] = spectrum[k] * scale;
+                bins[level_size - k] = spectrum[k].conj() * scale;
+            }
+            ifft.process(&mut bins);
+            level_data.extend(bins.iter().map(|bin| bin.re));
+        }
+
+        levels.push(MipLevel {
+            max_harmonic,
+            size: level_size,
+            data: level_data,
+        });
+        max_harmonic /= 2;
+    }
+    levels
 }
 
 impl Default for Wavetable {
@@ -478,4 +810,66 @@ mod tests {
         assert!(wt.sample(0.5) < -0.5);
         assert!(wt.sample(0.9) < -0.5);
     }
+
+    #[test]
+    fn test_morph_between_frames() {
+        let wt = Wavetable::from_frames(vec![vec![0.0; 64], vec![1.0; 64]]);
+
+        assert_eq!(wt.sample_morphed(0.3, 0.0), 0.0);
+        assert_eq!(wt.sample_morphed(0.3, 1.0), 1.0);
+        assert!((wt.sample_morphed(0.3, 0.25) - 0.25).abs() < 1e-6);
+        assert!((wt.clone().with_position(0.5).sample(0.7) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_from_concatenated_splits_frames() {
+        let samples: Vec<f32> = (0..10).map(|i| (i / 4) as f32).collect();
+        let wt = Wavetable::from_concatenated(&samples, 4);
+
+        // The two leftover samples don't make a frame
+        assert_eq!(wt.frame_count(), 2);
+        assert_eq!(wt.len(), 4);
+        assert_eq!(wt.sample_morphed(0.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_mipmaps_remove_harmonics_above_nyquist() {
+        let saw = Wavetable::saw_bandlimited();
+        let sample_rate = 44100.0;
+        let frequency = 4000.0; // Only harmonics 1-5 fit below Nyquist
+        let increment = frequency / sample_rate;
+
+        let mut buffer = vec![0.0; 4410];
+        saw.fill_buffer_simd(&mut buffer, 0.0, increment);
+
+        // Correlate against harmonic 8, which would alias without the mipmap
+        let alias: f32 = buffer
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x * (i as f32 * increment * 8.0 * 2.0 * PI).sin())
+            .sum::<f32>()
+            .abs()
+            / buffer.len() as f32;
+        let fundamental: f32 = buffer
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x * (i as f32 * increment * 2.0 * PI).sin())
+            .sum::<f32>()
+            .abs()
+            / buffer.len() as f32;
+        assert!(fundamental > 0.2);
+        assert!(alias < 0.005, "harmonic 8 still present: {alias}");
+
+        // Low notes play the full table
+        assert_eq!(saw.sample_band_limited(0.1, 0.0001, 0.0), saw.sample(0.1));
+    }
+
+    #[test]
+    fn test_sine_needs_no_mipmaps() {
+        let wt = Wavetable::sine();
+        for i in 0..16 {
+            let phase = i as f32 / 16.0;
+            assert_eq!(wt.sample_band_limited(phase, 0.2, 0.0), wt.sample(phase));
+        }
+    }
 }
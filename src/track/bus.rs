@@ -41,11 +41,12 @@
 //! ```
 
 use crate::synthesis::effects::{
-    AutoPan, BitCrusher, Chorus, Compressor, Delay, Distortion, EQ, EffectChain, Flanger, Gate,
-    Limiter, ParametricEQ, Phaser, Reverb, RingModulator, Saturation, Tremolo,
+    AutoPan, BitCrusher, Chorus, Compressor, Delay, Distortion, EQ, Effect, EffectChain, Flanger,
+    Gate, Limiter, ParametricEQ, Phaser, Reverb, RingModulator, Saturation, Tremolo,
 };
 use crate::track::Track;
 use crate::track::ids::BusId;
+use crate::track::latency::CompensationDelay;
 
 /// A bus groups multiple tracks together for processing
 ///
@@ -77,6 +78,9 @@ pub struct Bus {
 
     /// Whether this bus is soloed
     pub soloed: bool,
+
+    // Delay that aligns this bus with slower buses
+    pub(super) delay_compensation: CompensationDelay,
 }
 
 impl Bus {
@@ -95,6 +99,7 @@ impl Bus {
             pan: 0.0,
             muted: false,
             soloed: false,
+            delay_compensation: CompensationDelay::default(),
         }
     }
 
@@ -110,8 +115,10 @@ impl Bus {
         self
     }
 
-    /// Add a track to this bus
-    pub fn add_track(&mut self, track: Track) {
+    /// Add a track to this bus, allocating its notes to voices
+    /// (see [`Track::allocate_voices`])
+    pub fn add_track(&mut self, mut track: Track) {
+        track.allocate_voices();
         self.tracks.push(track);
     }
 
@@ -128,11 +135,45 @@ impl Bus {
         self.tracks.is_empty()
     }
 
+    /// Processing latency of this bus in frames
+    ///
+    /// The latency of its slowest track plus the latency of the bus effects.
+    pub fn latency(&self) -> usize {
+        let track_latency = self
+            .tracks
+            .iter()
+            .map(|t| t.effects.latency())
+            .max()
+            .unwrap_or(0);
+        track_latency + self.effects.latency()
+    }
+
     /// Get the number of tracks in this bus
     pub fn track_count(&self) -> usize {
         self.tracks.len()
     }
 
+    /// Prepare the bus effects and every track in this bus for rendering
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Sample rate in Hz
+    /// * `max_block_size` - Largest number of frames rendered per block
+    pub fn prepare(&mut self, sample_rate: f32, max_block_size: usize) {
+        self.effects.prepare(sample_rate, max_block_size);
+        for track in &mut self.tracks {
+            track.prepare(sample_rate, max_block_size);
+        }
+    }
+
+    /// Clear the state of the bus effects and every track in this bus
+    pub fn reset(&mut self) {
+        self.effects.reset();
+        self.delay_compensation.reset();
+        for track in &mut self.tracks {
+            track.reset();
+        }
+    }
+
     /// Mix all tracks in this bus at a given time and sample rate, returning stereo output
     ///
     /// Note: This method is not currently used - track processing happens in Mixer::process_track().
@@ -400,4 +441,10 @@ impl<'a> BusBuilder<'a> {
         self.bus.effects.compute_effect_order();
         self
     }
+
+    /// Append any effect (built-in or user-defined) to this bus's insert list
+    pub fn effect<E: Effect + 'static>(self, effect: E) -> Self {
+        self.bus.effects.add_effect(effect);
+        self
+    }
 }
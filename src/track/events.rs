@@ -5,19 +5,29 @@
 //! - NoteEvent, DrumEvent, SampleEvent
 //! - TempoChangeEvent, TimeSignatureEvent, KeySignatureEvent
 
+use crate::composition::timing::{TempoMap, Ticks};
 use crate::instruments::drums::DrumType;
 use crate::synthesis::envelope::Envelope;
 use crate::synthesis::filter_envelope::FilterEnvelope;
+use crate::synthesis::fm_operators::FMPatch;
 use crate::synthesis::fm_synthesis::FMParams;
+use crate::synthesis::mod_matrix::{NoteContext, NoteModulation};
+use crate::synthesis::modal::ModalResonator;
+use crate::synthesis::physical_model::PhysicalModel;
+use crate::synthesis::render_cache;
 use crate::synthesis::sample::Sample;
 use crate::synthesis::spatial::SpatialPosition;
+use crate::synthesis::unison::Unison;
 use crate::synthesis::waveform::Waveform;
 use crate::theory::key_signature::KeySignature;
+use std::sync::Arc;
 
 /// Represents different types of audio events
 ///
 /// Audio events are the fundamental building blocks of a track. Each event has
 /// a start time and contains specific parameters for its type (pitch, duration, etc.).
+/// Start times and note lengths are [`Ticks`] of musical time; the track's tempo
+/// map decides where they fall in seconds.
 #[derive(Debug, Clone)]
 pub enum AudioEvent {
     Note(NoteEvent),
@@ -31,7 +41,7 @@ pub enum AudioEvent {
 impl AudioEvent {
     /// Get the start time of this event
     #[inline]
-    pub fn start_time(&self) -> f32 {
+    pub fn start_time(&self) -> Ticks {
         match self {
             AudioEvent::Note(note) => note.start_time,
             AudioEvent::Drum(drum) => drum.start_time,
@@ -42,55 +52,226 @@ impl AudioEvent {
         }
     }
 
-    /// Get the end time of this event (including release for notes)
-    #[inline]
-    pub fn end_time(&self) -> f32 {
+    /// Where this event falls in seconds under `tempo_map`
+    pub fn seconds(&self, tempo_map: &TempoMap) -> EventTime {
+        match self {
+            AudioEvent::Note(note) => note.seconds(tempo_map),
+            event => EventTime {
+                start: tempo_map.seconds_at(event.start_time()) as f32,
+                length: 0.0,
+            },
+        }
+    }
+
+    /// Keep an event that was placed in seconds at those seconds under a new tempo map
+    ///
+    /// The event's ticks are derived again from its seconds under `new`. Events placed
+    /// in ticks are left alone, as are events whose ticks no longer match the seconds
+    /// under `old` because they were edited in ticks since; those forget their seconds.
+    pub(crate) fn keep_placed_seconds(&mut self, old: &TempoMap, new: &TempoMap) {
         match self {
             AudioEvent::Note(note) => {
-                let total_duration = note.envelope.total_duration(note.duration);
-                note.start_time + total_duration
+                if let Some(placed) = note.placed_seconds {
+                    if ticks_span(old, placed.start, placed.length) == (note.start_time, note.duration) {
+                        (note.start_time, note.duration) = ticks_span(new, placed.start, placed.length);
+                    } else {
+                        note.placed_seconds = None;
+                    }
+                }
             }
-            AudioEvent::Drum(drum) => drum.start_time + drum.drum_type.duration(),
+            AudioEvent::Drum(drum) => {
+                if let Some(placed) = drum.placed_seconds {
+                    if old.ticks_at(placed as f64) == drum.start_time {
+                        drum.start_time = new.ticks_at(placed as f64);
+                    } else {
+                        drum.placed_seconds = None;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Get the end time of this event in seconds, including any release tail
+    ///
+    /// `time` is where the event falls in seconds under the track's tempo map.
+    #[inline]
+    pub fn end_time(&self, time: EventTime) -> f32 {
+        match self {
+            AudioEvent::Note(note) => time.start + note.total_duration(time.length),
+            AudioEvent::Drum(drum) => time.start + drum.drum_type.duration(),
             AudioEvent::Sample(sample) => {
-                sample.start_time + (sample.sample.duration / sample.playback_rate)
+                time.start + (sample.sample.duration / sample.playback_rate)
             }
-            AudioEvent::TempoChange(tempo) => tempo.start_time,
-            AudioEvent::TimeSignature(time_sig) => time_sig.start_time,
-            AudioEvent::KeySignature(key_sig) => key_sig.start_time,
+            AudioEvent::TempoChange(_) => time.start,
+            AudioEvent::TimeSignature(_) => time.start,
+            AudioEvent::KeySignature(_) => time.start,
         }
     }
 }
 
+/// Start and duration in ticks of a span given in seconds under `tempo_map`
+pub(crate) fn ticks_span(tempo_map: &TempoMap, start: f32, duration: f32) -> (Ticks, Ticks) {
+    let start_ticks = tempo_map.ticks_at(start as f64);
+    let end_ticks = tempo_map.ticks_at((start + duration) as f64);
+    (start_ticks, end_ticks - start_ticks)
+}
+
+/// Where an event falls in seconds once its track's tempo map is applied
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EventTime {
+    /// Seconds from the start of the song
+    pub start: f32,
+    /// Seconds a note is held before its release (zero for other events)
+    pub length: f32,
+}
+
 /// Represents a note event with timing information
 #[derive(Debug, Clone)]
 pub struct NoteEvent {
     pub frequencies: [f32; 8], // Support up to 8 simultaneous frequencies
     pub num_freqs: usize,
-    pub start_time: f32,    // When to start playing (in seconds from track start)
-    pub duration: f32,      // How long to play (in seconds)
+    pub start_time: Ticks,  // When to start playing (from track start)
+    pub duration: Ticks,    // How long to hold the note before its release
     pub waveform: Waveform, // Waveform type to use (ignored if FM is enabled or custom wavetable is set)
     pub envelope: Envelope, // ADSR envelope for amplitude
     pub filter_envelope: FilterEnvelope, // ADSR envelope for filter cutoff
     pub fm_params: FMParams, // FM synthesis parameters (mod_index=0 disables FM)
+    pub fm_patch: Option<Box<FMPatch>>, // Multi-operator FM patch (overrides waveform and fm_params if present)
+    pub physical_model: Option<Arc<PhysicalModel>>, // Waveguide model (overrides every other oscillator if present)
+    pub resonator: Option<Arc<ModalResonator>>, // Modal resonator bank (overrides every oscillator but a physical model)
     pub pitch_bend_semitones: f32, // Pitch bend amount in semitones (0.0 = no bend)
-    pub custom_wavetable: Option<crate::synthesis::wavetable::Wavetable>, // Custom wavetable (overrides waveform if present)
+    pub custom_wavetable: Option<Box<crate::synthesis::wavetable::Wavetable>>, // Custom wavetable (overrides waveform if present)
     pub velocity: f32, // Note velocity (0.0 to 1.0), affects MIDI export and can be used for expression
     pub spatial_position: Option<SpatialPosition>, // 3D spatial position for spatial audio (None = no spatial processing)
+    pub unison: Unison, // Detuned voice stack per frequency (1 voice = off)
+    pub voice: VoiceState, // Glide, legato and voice stealing written by the voice allocator
+    pub(crate) renderings: NoteRenderings, // Physical model or resonator output per frequency, set when the track is prepared
+    pub(crate) placed_seconds: Option<EventTime>, // Start and length in seconds, if added through a seconds-based method
+}
+
+/// A note's frequencies as rendered by its physical model or resonator, shown by
+/// count in debug output
+#[derive(Clone, Default)]
+pub(crate) struct NoteRenderings(pub(crate) Box<[Arc<[f32]>]>);
+
+impl std::fmt::Debug for NoteRenderings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} renderings", self.0.len())
+    }
+}
+
+/// Where a note sits in its instrument's voice allocation
+///
+/// Written by [`Track::allocate_voices`](crate::track::Track::allocate_voices) when a
+/// track has a [`Voicing`](crate::track::Voicing); the default leaves a note playing
+/// exactly as written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoiceState {
+    /// Pitch the note glides from, as a ratio of its own pitch (1.0 = no glide)
+    pub glide_from: f32,
+    /// Seconds the glide takes
+    pub glide_time: f32,
+    /// Seconds already spent in the envelope when the note starts (legato notes skip the attack)
+    pub envelope_offset: f32,
+    /// Oscillator phase in cycles carried over from the note this one took over from
+    pub phase: f32,
+    /// Seconds into the note at which its voice is taken away
+    pub cut_at: Option<f32>,
+    /// Seconds the note fades over once cut (0.0 = silent at once)
+    pub cut_fade: f32,
+}
+
+impl VoiceState {
+    /// Check whether the note plays exactly as written
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Pitch ratio of the glide at `time` seconds into the note
+    #[inline]
+    pub(crate) fn glide_ratio(&self, time: f32) -> f32 {
+        if self.glide_from == 1.0 || time >= self.glide_time {
+            1.0
+        } else {
+            self.glide_from.powf(1.0 - time / self.glide_time)
+        }
+    }
+
+    /// Cycles played by a 1 Hz target pitch after `time` seconds, following the glide
+    #[inline]
+    fn glide_cycles(&self, time: f32) -> f32 {
+        if self.glide_from == 1.0 || self.glide_time <= 0.0 {
+            return time;
+        }
+        // Integral of glide_from^(1 - t/T) over the glide, then straight on at pitch
+        let scale = self.glide_time / self.glide_from.ln();
+        if time < self.glide_time {
+            scale * (self.glide_from - self.glide_ratio(time))
+        } else {
+            scale * (self.glide_from - 1.0) + (time - self.glide_time)
+        }
+    }
+
+    /// Gain applied after the voice is cut
+    #[inline]
+    fn cut_gain(&self, time: f32) -> f32 {
+        match self.cut_at {
+            Some(cut_at) if time >= cut_at => {
+                if self.cut_fade > 0.0 {
+                    (1.0 - (time - cut_at) / self.cut_fade).max(0.0)
+                } else {
+                    0.0
+                }
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+impl Default for VoiceState {
+    fn default() -> Self {
+        Self {
+            glide_from: 1.0,
+            glide_time: 0.0,
+            envelope_offset: 0.0,
+            phase: 0.0,
+            cut_at: None,
+            cut_fade: 0.0,
+        }
+    }
 }
 
 /// Represents a drum hit event
 #[derive(Debug, Clone, Copy)]
 pub struct DrumEvent {
     pub drum_type: DrumType,
-    pub start_time: f32,
+    pub start_time: Ticks,
     pub spatial_position: Option<SpatialPosition>, // 3D spatial position for spatial audio
+    pub(crate) placed_seconds: Option<f32>, // Start in seconds, if added through a seconds-based method
+}
+
+impl DrumEvent {
+    /// Create a drum hit event
+    ///
+    /// # Arguments
+    /// * `drum_type` - Type of drum
+    /// * `start_time` - When to trigger the drum (from track start)
+    pub fn new(drum_type: DrumType, start_time: Ticks) -> Self {
+        Self {
+            drum_type,
+            start_time,
+            spatial_position: None,
+            placed_seconds: None,
+        }
+    }
 }
 
 /// Represents a sample playback event
 #[derive(Debug, Clone)]
 pub struct SampleEvent {
     pub sample: Sample,
-    pub start_time: f32,
+    pub start_time: Ticks,
     pub playback_rate: f32, // 1.0 = normal speed, 2.0 = double speed, 0.5 = half speed
     pub volume: f32,        // 0.0 to 1.0
     pub spatial_position: Option<SpatialPosition>, // 3D spatial position for spatial audio
@@ -99,14 +280,14 @@ pub struct SampleEvent {
 /// Represents a tempo change event
 #[derive(Debug, Clone, Copy)]
 pub struct TempoChangeEvent {
-    pub start_time: f32,
+    pub start_time: Ticks,
     pub bpm: f32,
 }
 
 /// Represents a time signature change event
 #[derive(Debug, Clone, Copy)]
 pub struct TimeSignatureEvent {
-    pub start_time: f32,
+    pub start_time: Ticks,
     pub numerator: u8,   // Top number (e.g., 3 in 3/4)
     pub denominator: u8, // Bottom number (e.g., 4 in 3/4)
 }
@@ -114,7 +295,7 @@ pub struct TimeSignatureEvent {
 /// Represents a key signature change event
 #[derive(Debug, Clone, Copy)]
 pub struct KeySignatureEvent {
-    pub start_time: f32,
+    pub start_time: Ticks,
     pub key_signature: KeySignature,
 }
 
@@ -123,8 +304,8 @@ impl SampleEvent {
     ///
     /// # Arguments
     /// * `sample` - The audio sample to play
-    /// * `start_time` - When to start playback (in seconds from track start)
-    pub fn new(sample: Sample, start_time: f32) -> Self {
+    /// * `start_time` - When to start playback (from track start)
+    pub fn new(sample: Sample, start_time: Ticks) -> Self {
         Self {
             sample,
             start_time,
@@ -161,9 +342,9 @@ impl NoteEvent {
     ///
     /// # Arguments
     /// * `frequencies` - Note frequencies in Hz (up to 8 for chords)
-    /// * `start_time` - When to start playing (in seconds from track start)
-    /// * `duration` - How long to sustain the note (in seconds)
-    pub fn new(frequencies: &[f32], start_time: f32, duration: f32) -> Self {
+    /// * `start_time` - When to start playing (from track start)
+    /// * `duration` - How long to sustain the note
+    pub fn new(frequencies: &[f32], start_time: Ticks, duration: Ticks) -> Self {
         Self::with_waveform(frequencies, start_time, duration, Waveform::Sine)
     }
 
@@ -171,13 +352,13 @@ impl NoteEvent {
     ///
     /// # Arguments
     /// * `frequencies` - Note frequencies in Hz (up to 8 for chords)
-    /// * `start_time` - When to start playing (in seconds from track start)
-    /// * `duration` - How long to sustain the note (in seconds)
+    /// * `start_time` - When to start playing (from track start)
+    /// * `duration` - How long to sustain the note
     /// * `waveform` - Waveform type (Sine, Square, Saw, Triangle)
     pub fn with_waveform(
         frequencies: &[f32],
-        start_time: f32,
-        duration: f32,
+        start_time: Ticks,
+        duration: Ticks,
         waveform: Waveform,
     ) -> Self {
         Self::with_waveform_and_envelope(
@@ -193,14 +374,14 @@ impl NoteEvent {
     ///
     /// # Arguments
     /// * `frequencies` - Note frequencies in Hz (up to 8 for chords)
-    /// * `start_time` - When to start playing (in seconds from track start)
-    /// * `duration` - How long to sustain the note (in seconds)
+    /// * `start_time` - When to start playing (from track start)
+    /// * `duration` - How long to sustain the note
     /// * `waveform` - Waveform type (Sine, Square, Saw, Triangle)
     /// * `envelope` - ADSR amplitude envelope
     pub fn with_waveform_and_envelope(
         frequencies: &[f32],
-        start_time: f32,
-        duration: f32,
+        start_time: Ticks,
+        duration: Ticks,
         waveform: Waveform,
         envelope: Envelope,
     ) -> Self {
@@ -218,15 +399,15 @@ impl NoteEvent {
     ///
     /// # Arguments
     /// * `frequencies` - Note frequencies in Hz (up to 8 for chords)
-    /// * `start_time` - When to start playing (in seconds from track start)
-    /// * `duration` - How long to sustain the note (in seconds)
+    /// * `start_time` - When to start playing (from track start)
+    /// * `duration` - How long to sustain the note
     /// * `waveform` - Waveform type (Sine, Square, Saw, Triangle)
     /// * `envelope` - ADSR amplitude envelope
     /// * `pitch_bend_semitones` - Pitch bend in semitones (e.g., 2.0 for up 2 semitones)
     pub fn with_waveform_envelope_and_bend(
         frequencies: &[f32],
-        start_time: f32,
-        duration: f32,
+        start_time: Ticks,
+        duration: Ticks,
         waveform: Waveform,
         envelope: Envelope,
         pitch_bend_semitones: f32,
@@ -246,16 +427,16 @@ impl NoteEvent {
     ///
     /// # Arguments
     /// * `frequencies` - Note frequencies in Hz (up to 8 for chords)
-    /// * `start_time` - When to start playing (in seconds from track start)
-    /// * `duration` - How long to sustain the note (in seconds)
+    /// * `start_time` - When to start playing (from track start)
+    /// * `duration` - How long to sustain the note
     /// * `waveform` - Waveform type (Sine, Square, Saw, Triangle)
     /// * `envelope` - ADSR amplitude envelope
     /// * `filter_envelope` - ADSR filter envelope for filter cutoff modulation
     /// * `pitch_bend_semitones` - Pitch bend in semitones
     pub fn with_full_params(
         frequencies: &[f32],
-        start_time: f32,
-        duration: f32,
+        start_time: Ticks,
+        duration: Ticks,
         waveform: Waveform,
         envelope: Envelope,
         filter_envelope: FilterEnvelope,
@@ -282,8 +463,8 @@ impl NoteEvent {
     ///
     /// # Arguments
     /// * `frequencies` - Note frequencies in Hz (up to 8 for chords)
-    /// * `start_time` - When to start playing (in seconds from track start)
-    /// * `duration` - How long to sustain the note (in seconds)
+    /// * `start_time` - When to start playing (from track start)
+    /// * `duration` - How long to sustain the note
     /// * `waveform` - Basic waveform (sine, square, saw, triangle)
     /// * `envelope` - ADSR amplitude envelope
     /// * `filter_envelope` - ADSR filter envelope
@@ -294,8 +475,8 @@ impl NoteEvent {
     #[allow(clippy::too_many_arguments)]
     pub fn with_complete_params(
         frequencies: &[f32],
-        start_time: f32,
-        duration: f32,
+        start_time: Ticks,
+        duration: Ticks,
         waveform: Waveform,
         envelope: Envelope,
         filter_envelope: FilterEnvelope,
@@ -322,10 +503,274 @@ impl NoteEvent {
             envelope,
             filter_envelope,
             fm_params,
+            fm_patch: None,
+            physical_model: None,
+            resonator: None,
             pitch_bend_semitones,
-            custom_wavetable,
+            custom_wavetable: custom_wavetable.map(Box::new),
             velocity,
             spatial_position: None,
+            unison: Unison::default(),
+            voice: VoiceState::default(),
+            renderings: NoteRenderings::default(),
+            placed_seconds: None,
+        }
+    }
+
+    /// Where this note falls in seconds under `tempo_map`
+    ///
+    /// The length follows the tempo across the note, so a note held through a
+    /// ramp is as long as the beats it covers.
+    pub fn seconds(&self, tempo_map: &TempoMap) -> EventTime {
+        let start = tempo_map.seconds_at(self.start_time);
+        let end = tempo_map.seconds_at(self.start_time + self.duration);
+        EventTime {
+            start: start as f32,
+            length: (end - start) as f32,
+        }
+    }
+
+    /// Set unison voices for this note
+    ///
+    /// # Arguments
+    /// * `unison` - Voice count, detune, stereo spread and phase scatter
+    pub fn with_unison(mut self, unison: Unison) -> Self {
+        self.unison = unison;
+        self
+    }
+
+    /// Play this note with a multi-operator FM patch
+    ///
+    /// # Arguments
+    /// * `patch` - Operators and algorithm, replacing the waveform and `fm_params`
+    pub fn with_fm_patch(mut self, patch: FMPatch) -> Self {
+        self.fm_patch = Some(Box::new(patch));
+        self
+    }
+
+    /// Play this note with a waveguide physical model
+    ///
+    /// # Arguments
+    /// * `model` - Bowed or plucked string, blown pipe or struck bar, replacing
+    ///   every other oscillator setting
+    pub fn with_physical_model(mut self, model: PhysicalModel) -> Self {
+        self.physical_model = Some(Arc::new(model));
+        self
+    }
+
+    /// Play this note with a modal resonator bank
+    ///
+    /// # Arguments
+    /// * `resonator` - Modes and exciter of a struck object, replacing every
+    ///   oscillator setting but a physical model
+    pub fn with_resonator(mut self, resonator: ModalResonator) -> Self {
+        self.resonator = Some(Arc::new(resonator));
+        self
+    }
+
+    /// Sample one of the note's frequencies, before the amplitude envelope
+    ///
+    /// Applies pitch bend and unison and picks the oscillator (physical model, FM,
+    /// custom wavetable or waveform). `length` is how long the note is held, in
+    /// seconds. Returns `(mid, side)`; side is zero unless the unison is spread.
+    #[inline]
+    pub(crate) fn sample_frequency(
+        &self,
+        freq_idx: usize,
+        time_in_note: f32,
+        length: f32,
+        sample_rate: f32,
+    ) -> (f32, f32) {
+        self.sample_modulated(
+            freq_idx,
+            time_in_note,
+            length,
+            None,
+            &NoteModulation::default(),
+            sample_rate,
+        )
+    }
+
+    /// Sample one of the note's frequencies with mod matrix offsets applied
+    ///
+    /// `clock` replaces the time the oscillators take their phase from when the
+    /// pitch is modulated: it runs faster or slower than `time_in_note` as the
+    /// pitch rises and falls, so the waveform never jumps.
+    #[inline]
+    pub(crate) fn sample_modulated(
+        &self,
+        freq_idx: usize,
+        time_in_note: f32,
+        length: f32,
+        clock: Option<f32>,
+        modulation: &NoteModulation,
+        sample_rate: f32,
+    ) -> (f32, f32) {
+        let base_freq = self.frequencies[freq_idx];
+        let mut freq = if self.pitch_bend_semitones != 0.0 {
+            let bend_progress = (time_in_note / length).min(1.0);
+            base_freq * 2.0f32.powf((self.pitch_bend_semitones * bend_progress) / 12.0)
+        } else {
+            base_freq
+        };
+
+        // Oscillators take phase from time × frequency, so a gliding or phase-carrying
+        // voice is handed the time at which its current pitch reaches the right phase
+        let mut time = clock.unwrap_or(time_in_note);
+        if !self.voice.is_default() {
+            let ratio = self.voice.glide_ratio(time_in_note);
+            time = (self.voice.phase / base_freq.max(1e-6) + self.voice.glide_cycles(time)) / ratio;
+            freq *= ratio;
+        }
+
+        let mut unison = self.unison;
+        if modulation.unison_detune != 0.0 {
+            unison.detune_cents = (unison.detune_cents + modulation.unison_detune).max(0.0);
+        }
+
+        let rendering = self.renderings.0.get(freq_idx).map(|samples| &samples[..]);
+        if unison.is_active() {
+            unison.lanes().sample(time, freq, |time, freq| {
+                self.oscillate(time, freq, base_freq, rendering, length, modulation, sample_rate)
+            })
+        } else {
+            (
+                self.oscillate(time, freq, base_freq, rendering, length, modulation, sample_rate),
+                0.0,
+            )
+        }
+    }
+
+    /// Render the note's physical model or resonator ahead of playback
+    ///
+    /// Each frequency is rendered once for a note held `length` seconds and kept
+    /// on the note, which playback reads directly. The model keeps the renderings
+    /// cached for as long as the note holds them.
+    pub(crate) fn prerender(&mut self, length: f32, sample_rate: f32) {
+        let frequencies = &self.frequencies[..self.num_freqs];
+        self.renderings.0 = if let Some(ref model) = self.physical_model {
+            frequencies
+                .iter()
+                .map(|&frequency| model.prerender(frequency, length, self.velocity, sample_rate))
+                .collect()
+        } else if let Some(ref resonator) = self.resonator {
+            frequencies
+                .iter()
+                .map(|&frequency| resonator.prerender(frequency, self.velocity, sample_rate))
+                .collect()
+        } else {
+            Box::default()
+        };
+    }
+
+    /// What the mod matrix's per-note sources read from this note at `time_in_note`
+    #[inline]
+    pub(crate) fn mod_context(&self, time_in_note: f32, length: f32) -> NoteContext {
+        NoteContext {
+            velocity: self.velocity,
+            frequency: self.frequencies[0],
+            seed: self.start_time.0 as u32 ^ self.frequencies[0].to_bits().rotate_left(16),
+            time_in_note,
+            duration: self.held_duration(length),
+        }
+    }
+
+    /// Amplitude envelope at `time_in_note`, including legato offset and voice cut
+    #[inline]
+    pub(crate) fn amplitude_at(&self, time_in_note: f32, length: f32) -> f32 {
+        let offset = self.voice.envelope_offset;
+        self.envelope.amplitude_at(time_in_note + offset, length + offset)
+            * self.voice.cut_gain(time_in_note)
+    }
+
+    /// Seconds the note sounds for including its release, shortened if its voice is cut
+    ///
+    /// `length` is how long the note is held, in seconds at the track's tempo.
+    pub fn total_duration(&self, length: f32) -> f32 {
+        let total = self.envelope.total_duration(length);
+        match self.voice.cut_at {
+            Some(cut_at) => total.min(cut_at + self.voice.cut_fade),
+            None => total,
+        }
+    }
+
+    /// Seconds the note is held before release, shortened if its voice is cut
+    ///
+    /// `length` is how long the note is written to be held, in seconds at the
+    /// track's tempo.
+    pub fn held_duration(&self, length: f32) -> f32 {
+        match self.voice.cut_at {
+            Some(cut_at) => length.min(cut_at),
+            None => length,
+        }
+    }
+
+    /// Pitch of the first frequency at `time_in_note`, following any glide
+    pub(crate) fn frequency_at(&self, time_in_note: f32) -> f32 {
+        self.frequencies[0] * self.voice.glide_ratio(time_in_note)
+    }
+
+    /// Oscillator phase of the first frequency at `time_in_note`, in cycles
+    pub(crate) fn phase_at(&self, time_in_note: f32) -> f32 {
+        self.voice.phase + self.frequencies[0] * self.voice.glide_cycles(time_in_note)
+    }
+
+    /// Run the note's oscillator for a single voice
+    ///
+    /// A physical model or resonator is rendered once at `base_freq` and read back
+    /// at `freq / base_freq` speed, so bends, glides and unison detune it like a sample.
+    /// `rendering` is that rendering when the track has prepared the note; without
+    /// it the model renders the note, or finds it in its cache, as it plays.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn oscillate(
+        &self,
+        time_in_note: f32,
+        freq: f32,
+        base_freq: f32,
+        rendering: Option<&[f32]>,
+        length: f32,
+        modulation: &NoteModulation,
+        sample_rate: f32,
+    ) -> f32 {
+        if let Some(samples) = rendering {
+            let time = time_in_note * freq / base_freq.max(1e-6);
+            render_cache::read(samples, time, sample_rate)
+        } else if let Some(ref model) = self.physical_model {
+            let time = time_in_note * freq / base_freq.max(1e-6);
+            model.sample(base_freq, time, length, self.velocity, sample_rate)
+        } else if let Some(ref resonator) = self.resonator {
+            let time = time_in_note * freq / base_freq.max(1e-6);
+            resonator.sample(base_freq, time, self.velocity, sample_rate)
+        } else if let Some(ref patch) = self.fm_patch {
+            patch.sample(
+                freq,
+                time_in_note,
+                length,
+                self.velocity,
+                modulation.fm_index,
+            )
+        } else if self.fm_params.mod_index > 0.0 {
+            let mut fm_params = self.fm_params;
+            fm_params.mod_index = (fm_params.mod_index + modulation.fm_index).max(0.0);
+            fm_params.sample(freq, time_in_note, length)
+        } else if let Some(ref wavetable) = self.custom_wavetable {
+            let position = wavetable.position() + modulation.wavetable_position;
+            wavetable.sample_band_limited(time_in_note * freq, freq / sample_rate, position)
+        } else {
+            let waveform = match self.waveform {
+                Waveform::Pulse {
+                    width,
+                    pwm_rate,
+                    pwm_depth,
+                } if modulation.pulse_width != 0.0 => Waveform::Pulse {
+                    width: (width + modulation.pulse_width).clamp(0.01, 0.99),
+                    pwm_rate,
+                    pwm_depth,
+                },
+                waveform => waveform,
+            };
+            waveform.oscillate(time_in_note, freq, sample_rate)
         }
     }
 }
@@ -1,9 +1,106 @@
 //! Export functionality for Mixer
 //!
-//! This module contains methods for exporting audio to WAV and FLAC files,
-//! including stems (individual track exports).
-
+//! This module contains methods for exporting audio to WAV, FLAC and Ogg Vorbis
+//! files, including stems (individual track exports; see `stems` for grouped stems).
+//!
+//! Exports render in blocks and stream each block straight into the encoder, so memory
+//! use stays flat no matter how long the composition is. Nothing is printed; pass a
+//! progress callback to the `*_with_progress` variants to follow (or cancel) a render.
+
+use super::export_format::{
+    downmix, BitDepth, ExportOptions, Normalization, Normalizer, OggOptions, Quantizer,
+};
+use super::metadata::ExportTags;
 use super::mixer::Mixer;
+use super::vorbis::VorbisEncoder;
+use crate::error::TunesError;
+use crate::synthesis::effects::EffectChain;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+
+/// Progress of an offline export, passed to export progress callbacks
+///
+/// # Example
+/// ```no_run
+/// # use tunes::prelude::*;
+/// # fn main() -> anyhow::Result<()> {
+/// # let mut comp = Composition::new(Tempo::new(120.0));
+/// let mut mixer = comp.into_mixer();
+/// let options = ExportOptions::default();
+/// mixer.export_wav_with_progress("output.wav", 44100, &options, |progress| {
+///     eprint!("\r{:.0}%", progress.fraction() * 100.0);
+///     true // Return false to cancel
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportProgress {
+    /// Frames written to the current file so far
+    pub frames_done: usize,
+    /// Total frames the current file will contain
+    pub total_frames: usize,
+    /// Index of the file being written (0 for single-file exports)
+    pub file_index: usize,
+    /// Number of files the export writes (stems write one per track)
+    pub file_count: usize,
+}
+
+impl ExportProgress {
+    pub(super) fn new(total_frames: usize, file_index: usize, file_count: usize) -> Self {
+        Self {
+            frames_done: 0,
+            total_frames,
+            file_index,
+            file_count,
+        }
+    }
+
+    /// Overall progress of the export, from 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        if self.file_count == 0 {
+            return 1.0;
+        }
+        let file_fraction = if self.total_frames == 0 {
+            1.0
+        } else {
+            self.frames_done as f32 / self.total_frames as f32
+        };
+        (self.file_index as f32 + file_fraction.min(1.0)) / self.file_count as f32
+    }
+
+    /// Record `frames` more frames and notify the callback
+    ///
+    /// Returns [`TunesError::ExportCancelled`] if the callback asks to stop.
+    pub(super) fn advance(
+        &mut self,
+        frames: usize,
+        callback: &mut dyn FnMut(&ExportProgress) -> bool,
+    ) -> anyhow::Result<()> {
+        self.frames_done += frames;
+        if callback(self) {
+            Ok(())
+        } else {
+            Err(TunesError::ExportCancelled.into())
+        }
+    }
+}
+
+/// File name for a track or stem, with characters that are unsafe in paths replaced
+pub(super) fn safe_file_name(name: &str) -> String {
+    name.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_")
+}
+
+/// Run `write` against a freshly created file, deleting the file if it fails
+///
+/// Keeps cancelled or failed exports from leaving truncated files behind.
+pub(super) fn remove_on_error(path: &str, write: impl FnOnce() -> anyhow::Result<()>) -> anyhow::Result<()> {
+    let result = write();
+    if result.is_err() {
+        std::fs::remove_file(path).ok();
+    }
+    result
+}
 
 impl Mixer {
     /// Export the mixed audio to a WAV file with explicit sample rate
@@ -36,49 +133,116 @@ impl Mixer {
     /// # }
     /// ```
     pub fn export_wav(&mut self, path: &str, sample_rate: u32) -> anyhow::Result<()> {
-        let spec = hound::WavSpec {
-            channels: 2,
-            sample_rate,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
-        };
+        self.export_wav_with_options(path, sample_rate, &ExportOptions::default())
+    }
 
-        let mut writer = hound::WavWriter::create(path, spec)?;
+    /// Export to a WAV file with explicit format options
+    ///
+    /// Same as [`export_wav`](Self::export_wav), but writes the bit depth, dithering
+    /// and channel layout chosen in `options`. The plain `export_wav` writes 16-bit
+    /// stereo without dither.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::prelude::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// let mut mixer = comp.into_mixer();
+    /// mixer.export_wav_with_options("master.wav", 96000, &ExportOptions::float())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_wav_with_options(
+        &mut self,
+        path: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+    ) -> anyhow::Result<()> {
+        self.export_wav_with_progress(path, sample_rate, options, |_| true)
+    }
 
-        let duration = self.total_duration();
-        let total_samples = (duration * sample_rate as f32).ceil() as usize;
+    /// Export to a WAV file, reporting progress as blocks are written
+    ///
+    /// Same as [`export_wav_with_options`](Self::export_wav_with_options), but calls
+    /// `progress` after every rendered block. Return `false` from the callback to cancel
+    /// the export; the partial file is deleted and [`TunesError::ExportCancelled`] is
+    /// returned.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::prelude::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// # let cancel_requested = || false;
+    /// let mut mixer = comp.into_mixer();
+    /// let options = ExportOptions::mastering();
+    /// mixer.export_wav_with_progress("ambient.wav", 48000, &options, |progress| {
+    ///     eprintln!("rendered {}/{} frames", progress.frames_done, progress.total_frames);
+    ///     !cancel_requested()
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_wav_with_progress(
+        &mut self,
+        path: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        mut progress: impl FnMut(&ExportProgress) -> bool,
+    ) -> anyhow::Result<()> {
+        let state = ExportProgress::new(0, 0, 1);
+        self.write_wav(path, sample_rate, options, state, &mut progress)
+    }
 
-        println!("Rendering to WAV...");
-        println!("  Duration: {:.2}s", duration);
-        println!("  Sample rate: {} Hz", sample_rate);
-        println!("  Total samples: {}", total_samples);
-
-        // 🚀 GPU-accelerated batch rendering!
-        // This uses GPU pre-rendering if enabled (mixer.enable_gpu())
-        let buffer = self.render_to_buffer(sample_rate as f32);
-
-        println!("  Encoding to WAV...");
-
-        // Write samples to WAV file (interleaved stereo: L, R, L, R, ...)
-        for (i, sample) in buffer.iter().enumerate() {
-            // Convert from f32 (-1.0 to 1.0) to i16 (-32768 to 32767)
-            let sample_i16 = (sample.clamp(-1.0, 1.0) * 32767.0) as i16;
-            writer.write_sample(sample_i16)?;
-
-            // Progress indicator every second
-            if i % (sample_rate as usize * 2) == 0 {
-                let progress = (i as f32 / buffer.len() as f32) * 100.0;
-                print!("\r  Progress: {:.0}%", progress);
-                use std::io::Write;
-                std::io::stdout().flush().ok();
-            }
-        }
+    /// Stream the mix into a WAV file
+    fn write_wav(
+        &mut self,
+        path: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        mut state: ExportProgress,
+        progress: &mut dyn FnMut(&ExportProgress) -> bool,
+    ) -> anyhow::Result<()> {
+        state.total_frames = (self.total_duration() * sample_rate as f32).ceil() as usize;
+        let tags = ExportTags::new(self, sample_rate, state.total_frames);
+        let mut encoder = WavEncoder::create(path, sample_rate, options, tags)?;
+
+        remove_on_error(path, || {
+            // 🚀 GPU-accelerated batch rendering!
+            // This uses GPU pre-rendering if enabled (mixer.enable_gpu())
+            self.render_export_blocks(sample_rate, options.normalization, |block| {
+                encoder.write_samples(block)?;
+                state.advance(block.len() / 2, progress)
+            })?;
+            encoder.finalize()
+        })
+    }
 
-        println!("\r  Progress: 100%");
-        writer.finalize()?;
+    /// Render the mix in blocks for export, normalized if requested
+    ///
+    /// Normalizing takes a measuring pass over the whole mix before the blocks are
+    /// rendered again; progress is only reported for the second pass.
+    fn render_export_blocks(
+        &mut self,
+        sample_rate: u32,
+        normalization: Option<Normalization>,
+        mut write: impl FnMut(&[f32]) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let Some(normalization) = normalization else {
+            return self.render_blocks(sample_rate as f32, write);
+        };
 
-        println!("✅ Exported to: {}", path);
-        Ok(())
+        let loudness = self.loudness(sample_rate);
+        let mut normalizer = Normalizer::new(normalization, &loudness, sample_rate as f32);
+        let mut normalized = Vec::with_capacity(Mixer::RENDER_BLOCK_SIZE * 2);
+        self.render_blocks(sample_rate as f32, |block| {
+            normalized.clear();
+            normalizer.process(block, &mut normalized);
+            write(&normalized)
+        })?;
+        normalized.clear();
+        normalizer.finish(&mut normalized);
+        write(&normalized)
     }
 
     /// Export the mixed audio to a FLAC file (lossless compression)
@@ -119,77 +283,115 @@ impl Mixer {
     /// # }
     /// ```
     pub fn export_flac(&mut self, path: &str, sample_rate: u32) -> anyhow::Result<()> {
-        use flacenc::component::BitRepr;
-        use flacenc::error::Verify;
-        use flacenc::source::MemSource;
-
-        let duration = self.total_duration();
-        let total_samples = (duration * sample_rate as f32).ceil() as usize;
-
-        println!("Rendering to FLAC...");
-        println!("  Duration: {:.2}s", duration);
-        println!("  Sample rate: {} Hz", sample_rate);
-        println!("  Total samples: {}", total_samples);
-
-        // 🚀 GPU-accelerated batch rendering!
-        // This uses GPU pre-rendering if enabled (mixer.enable_gpu())
-        let buffer = self.render_to_buffer(sample_rate as f32);
-
-        println!("  Converting to 24-bit...");
-
-        // Convert f32 samples to i32 (24-bit) for FLAC encoding
         // We use 24-bit as it provides better quality than 16-bit while keeping file size reasonable
-        const SCALE: f32 = 8388607.0; // 2^23 - 1
-        let samples_i32: Vec<i32> = buffer
-            .iter()
-            .enumerate()
-            .map(|(i, &sample)| {
-                let sample_i32 = (sample.clamp(-1.0, 1.0) * SCALE) as i32;
-
-                // Progress indicator every second
-                if i % (sample_rate as usize * 2) == 0 {
-                    let progress = (i as f32 / buffer.len() as f32) * 100.0;
-                    print!("\r  Progress: {:.0}%", progress);
-                    use std::io::Write;
-                    std::io::stdout().flush().ok();
-                }
-
-                sample_i32
-            })
-            .collect();
-
-        println!("\r  Progress: 100%");
-        println!("  Encoding FLAC...");
+        let options = ExportOptions::default().with_bit_depth(BitDepth::Int24);
+        self.export_flac_with_options(path, sample_rate, &options)
+    }
 
-        // Create encoder configuration
-        let config = flacenc::config::Encoder::default()
-            .into_verified()
-            .expect("Default encoder config should be valid");
+    /// Export to a FLAC file with explicit format options
+    ///
+    /// FLAC supports [`BitDepth::Int16`] and [`BitDepth::Int24`]; other bit depths
+    /// return [`TunesError::InvalidAudioFormat`]. The plain `export_flac` writes 24-bit
+    /// stereo without dither.
+    pub fn export_flac_with_options(
+        &mut self,
+        path: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+    ) -> anyhow::Result<()> {
+        self.export_flac_with_progress(path, sample_rate, options, |_| true)
+    }
 
-        // Create FLAC source from samples
-        let source = MemSource::from_samples(
-            &samples_i32,
-            2,          // channels (stereo)
-            24,         // bits per sample
-            sample_rate as usize,
+    /// Export to a FLAC file, reporting progress as blocks are written
+    ///
+    /// Same as [`export_flac_with_options`](Self::export_flac_with_options), with the
+    /// progress and cancellation behavior of
+    /// [`export_wav_with_progress`](Self::export_wav_with_progress).
+    pub fn export_flac_with_progress(
+        &mut self,
+        path: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        mut progress: impl FnMut(&ExportProgress) -> bool,
+    ) -> anyhow::Result<()> {
+        let mut state = ExportProgress::new(
+            (self.total_duration() * sample_rate as f32).ceil() as usize,
+            0,
+            1,
         );
+        let tags = ExportTags::new(self, sample_rate, state.total_frames);
+        let mut writer = FlacEncoder::create(path, sample_rate, options, &tags)?;
+
+        remove_on_error(path, || {
+            self.render_export_blocks(sample_rate, options.normalization, |block| {
+                writer.write_samples(block)?;
+                state.advance(block.len() / 2, &mut progress)
+            })?;
+            writer.finalize()
+        })
+    }
 
-        // Encode with fixed block size (use config's default block size)
-        let flac_stream = flacenc::encode_with_fixed_block_size(
-            &config,
-            source,
-            config.block_size,
-        ).map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
-
-        // Write to file using ByteSink
-        let mut sink = flacenc::bitsink::ByteSink::new();
-        flac_stream.write(&mut sink)
-            .map_err(|e| anyhow::anyhow!("Failed to write FLAC stream: {:?}", e))?;
+    /// Export the mixed audio to an Ogg Vorbis file (lossy compression)
+    ///
+    /// Renders the entire composition to a stereo Ogg Vorbis file at quality 0.5,
+    /// typically a tenth of the size of the equivalent WAV. Use this for game and web
+    /// builds; keep WAV or FLAC masters for further processing.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::prelude::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("piano").note(&[440.0], 1.0);
+    ///
+    /// let mut mixer = comp.into_mixer();
+    /// mixer.export_ogg("output.ogg", 44100)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_ogg(&mut self, path: &str, sample_rate: u32) -> anyhow::Result<()> {
+        self.export_ogg_with_options(path, sample_rate, &OggOptions::default())
+    }
 
-        std::fs::write(path, sink.as_slice())?;
+    /// Export to an Ogg Vorbis file with explicit quality, channels and loop tags
+    ///
+    /// See [`OggOptions`] for the available settings.
+    pub fn export_ogg_with_options(
+        &mut self,
+        path: &str,
+        sample_rate: u32,
+        options: &OggOptions,
+    ) -> anyhow::Result<()> {
+        self.export_ogg_with_progress(path, sample_rate, options, |_| true)
+    }
 
-        println!("✅ Exported to: {}", path);
-        Ok(())
+    /// Export to an Ogg Vorbis file, reporting progress as blocks are written
+    ///
+    /// Same as [`export_ogg_with_options`](Self::export_ogg_with_options), with the
+    /// progress and cancellation behavior of
+    /// [`export_wav_with_progress`](Self::export_wav_with_progress).
+    pub fn export_ogg_with_progress(
+        &mut self,
+        path: &str,
+        sample_rate: u32,
+        options: &OggOptions,
+        mut progress: impl FnMut(&ExportProgress) -> bool,
+    ) -> anyhow::Result<()> {
+        let mut state = ExportProgress::new(
+            (self.total_duration() * sample_rate as f32).ceil() as usize,
+            0,
+            1,
+        );
+        let tags = ExportTags::new(self, sample_rate, state.total_frames);
+        let mut encoder = VorbisEncoder::create(path, sample_rate, options, &tags)?;
+
+        remove_on_error(path, || {
+            self.render_export_blocks(sample_rate, options.normalization, |block| {
+                encoder.write_samples(block)?;
+                state.advance(block.len() / 2, &mut progress)
+            })?;
+            encoder.finalize()
+        })
     }
 
     /// Export individual tracks as separate WAV files (stems)
@@ -197,6 +399,8 @@ impl Mixer {
     /// Creates one WAV file per track in the specified output directory.
     /// Each stem contains only the audio for that individual track, making it
     /// perfect for external mixing, remixing, or professional production workflows.
+    /// For stems per bus or per group of tracks, dry stems, or a manifest, use
+    /// [`export_stem_groups`](Self::export_stem_groups).
     ///
     /// # Arguments
     /// * `output_dir` - Directory path where stems will be saved
@@ -226,6 +430,45 @@ impl Mixer {
     /// # }
     /// ```
     pub fn export_stems(&mut self, output_dir: &str, sample_rate: u32) -> anyhow::Result<()> {
+        self.export_stems_with_options(output_dir, sample_rate, &ExportOptions::default())
+    }
+
+    /// Export stems as WAV files with explicit format options
+    pub fn export_stems_with_options(
+        &mut self,
+        output_dir: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+    ) -> anyhow::Result<()> {
+        self.export_stems_with_progress(output_dir, sample_rate, options, |_| true)
+    }
+
+    /// Export stems, reporting progress as blocks are written
+    ///
+    /// Same as [`export_stems_with_options`](Self::export_stems_with_options).
+    /// `file_index` and `file_count` in the reported [`ExportProgress`] tell which stem
+    /// is rendering. Cancelling deletes the partial stem; stems that already finished
+    /// are kept.
+    pub fn export_stems_with_progress(
+        &mut self,
+        output_dir: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        mut progress: impl FnMut(&ExportProgress) -> bool,
+    ) -> anyhow::Result<()> {
+        let total_tracks = self.all_tracks().len();
+        self.write_stems(output_dir, sample_rate, options, total_tracks, &mut progress)
+    }
+
+    /// Render every track to `{output_dir}/{track_name}.wav`
+    fn write_stems(
+        &mut self,
+        output_dir: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        file_count: usize,
+        progress: &mut dyn FnMut(&ExportProgress) -> bool,
+    ) -> anyhow::Result<()> {
         use std::fs;
 
         // Create output directory if it doesn't exist
@@ -233,8 +476,6 @@ impl Mixer {
 
         let total_tracks = self.all_tracks().len();
 
-        println!("Exporting {} stems to: {}", total_tracks, output_dir);
-
         // Export each track individually
         for index in 0..total_tracks {
             let all_tracks = self.all_tracks();
@@ -244,18 +485,15 @@ impl Mixer {
                 .unwrap_or_else(|| format!("untitled_{}", index));
 
             // Sanitize filename (remove special characters)
-            let safe_name = track_name
-                .replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_");
+            let safe_name = safe_file_name(&track_name);
 
             let filename = format!("{}/{}.wav", output_dir, safe_name);
 
-            println!("  [{}/{}] Rendering: {}", index + 1, total_tracks, safe_name);
-
             // Render this track in isolation
-            self.render_track_at_index(index, &filename, sample_rate)?;
+            let state = ExportProgress::new(0, index, file_count);
+            self.render_track_at_index(index, &filename, sample_rate, options, state, progress)?;
         }
 
-        println!("✅ Exported {} stems to: {}", total_tracks, output_dir);
         Ok(())
     }
 
@@ -291,94 +529,310 @@ impl Mixer {
         output_dir: &str,
         sample_rate: u32,
     ) -> anyhow::Result<()> {
+        let options = ExportOptions::default();
+        self.export_stems_with_master_with_progress(output_dir, sample_rate, &options, |_| true)
+    }
+
+    /// Export stems and the master mix, reporting progress as blocks are written
+    ///
+    /// Same as [`export_stems_with_master`](Self::export_stems_with_master), using
+    /// `options` for every file. The master mix is reported as the last file.
+    pub fn export_stems_with_master_with_progress(
+        &mut self,
+        output_dir: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        mut progress: impl FnMut(&ExportProgress) -> bool,
+    ) -> anyhow::Result<()> {
+        let total_tracks = self.all_tracks().len();
+
         // Export individual stems
-        self.export_stems(output_dir, sample_rate)?;
+        self.write_stems(output_dir, sample_rate, options, total_tracks + 1, &mut progress)?;
 
         // Export master mix
         let output_dir_trimmed = output_dir.trim_end_matches('/');
         let master_path = format!("{}/_master.wav", output_dir_trimmed);
-        println!("  Rendering master mix...");
-        self.export_wav(&master_path, sample_rate)?;
-
-        Ok(())
+        let state = ExportProgress::new(0, total_tracks, total_tracks + 1);
+        self.write_wav(&master_path, sample_rate, options, state, &mut progress)
     }
 
     /// Helper: Render a single track (by index) to a WAV file
     ///
     /// Renders only the specified track in isolation, applying all its effects,
-    /// filters, and processing chain.
+    /// filters, and processing chain. Bus and master processing are left out.
     fn render_track_at_index(
         &mut self,
         track_index: usize,
         path: &str,
         sample_rate: u32,
+        options: &ExportOptions,
+        mut state: ExportProgress,
+        progress: &mut dyn FnMut(&ExportProgress) -> bool,
     ) -> anyhow::Result<()> {
+        // Determine duration for this track
+        let duration = self.all_tracks()[track_index].total_duration();
+        let total_samples = (duration * sample_rate as f32).ceil() as usize;
+        let tags = ExportTags::new(self, sample_rate, total_samples);
+        let mut encoder = WavEncoder::create(path, sample_rate, options, tags)?;
+        state.total_frames = total_samples;
+
+        // Render through the same block path as the master mix
+        let mut isolated = self.isolated_track_mixer(track_index);
+        remove_on_error(path, || {
+            isolated.render_frames(sample_rate as f32, total_samples, |block| {
+                encoder.write_samples(block)?;
+                state.advance(block.len() / 2, progress)
+            })?;
+            encoder.finalize()
+        })
+    }
+
+    /// Copy of the mixer that plays only `track_index`, through unity buses and no master
+    fn isolated_track_mixer(&self, track_index: usize) -> Mixer {
+        let mut isolated = self.clone();
+        let mut index = 0;
+        for bus in isolated.buses.iter_mut().flatten() {
+            for track in &mut bus.tracks {
+                if index != track_index {
+                    track.events.clear();
+                    track.invalidate_time_cache();
+                }
+                index += 1;
+            }
+            bus.effects = EffectChain::new();
+            bus.volume = 1.0;
+            bus.pan = 0.0;
+            bus.muted = false;
+            bus.soloed = false;
+        }
+        isolated.master = EffectChain::new();
+        isolated
+    }
+}
+
+/// Streaming WAV encoder that converts float blocks to the export format
+///
+/// Metadata chunks are appended after the audio data once it is complete.
+pub(super) struct WavEncoder {
+    writer: hound::WavWriter<BufWriter<File>>,
+    quantizer: Quantizer,
+    path: String,
+    tags: ExportTags,
+}
+
+impl WavEncoder {
+    pub(super) fn create(
+        path: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        tags: ExportTags,
+    ) -> anyhow::Result<Self> {
+        let sample_format = match options.bit_depth {
+            BitDepth::Float32 => hound::SampleFormat::Float,
+            _ => hound::SampleFormat::Int,
+        };
         let spec = hound::WavSpec {
-            channels: 2,
+            channels: options.channels.count(),
             sample_rate,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
+            bits_per_sample: options.bit_depth.bits(),
+            sample_format,
         };
 
-        let mut writer = hound::WavWriter::create(path, spec)?;
+        Ok(Self {
+            writer: hound::WavWriter::create(path, spec)?,
+            quantizer: Quantizer::new(*options),
+            path: path.to_string(),
+            tags,
+        })
+    }
 
-        // Determine duration for this track
-        let all_tracks = self.all_tracks();
-        let duration = all_tracks[track_index].total_duration();
-        let total_samples = (duration * sample_rate as f32).ceil() as usize;
-        let sample_rate_f32 = sample_rate as f32;
-        let mut sample_clock = 0.0;
+    /// Write a block of interleaved stereo samples
+    pub(super) fn write_samples(&mut self, samples: &[f32]) -> anyhow::Result<()> {
+        let options = *self.quantizer.options();
+        downmix(samples, options.channels, |sample, channel| match options.bit_depth {
+            BitDepth::Float32 => self.writer.write_sample(sample),
+            BitDepth::Int16 => {
+                let quantized = self.quantizer.quantize(sample, channel);
+                self.writer.write_sample(quantized as i16)
+            }
+            BitDepth::Int24 | BitDepth::Int32 => {
+                let quantized = self.quantizer.quantize(sample, channel);
+                self.writer.write_sample(quantized)
+            }
+        })?;
+        Ok(())
+    }
 
-        // Render the track sample by sample
-        for i in 0..total_samples {
-            let time = i as f32 / sample_rate_f32;
+    pub(super) fn finalize(self) -> anyhow::Result<()> {
+        self.writer.finalize()?;
+
+        // Append the metadata chunks and grow the RIFF size to cover them
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&self.path)?;
+        let mut riff_size = [0; 4];
+        file.seek(SeekFrom::Start(4))?;
+        file.read_exact(&mut riff_size)?;
+        let mut chunks = self.tags.wav_chunks();
+        // Chunks start on even offsets, so an odd-sized data chunk needs a pad byte
+        if file.seek(SeekFrom::End(0))? % 2 == 1 {
+            chunks.insert(0, 0);
+        }
+        let riff_size = u32::from_le_bytes(riff_size) as usize + chunks.len();
+        file.write_all(&chunks)?;
+        file.seek(SeekFrom::Start(4))?;
+        file.write_all(&(riff_size as u32).to_le_bytes())?;
+        Ok(())
+    }
+}
 
-            // Sample this track in isolation (similar logic to Mixer::sample_at but for one track)
-            let (left, right) = self.sample_track_at_index(track_index, time, sample_rate_f32, sample_clock);
+/// Streaming FLAC encoder
+///
+/// Each frame is encoded and written to disk as soon as it fills up. The STREAMINFO
+/// header is written as a placeholder first and rewritten with the final sample count,
+/// frame sizes and MD5 once the stream is complete.
+struct FlacEncoder {
+    file: BufWriter<File>,
+    // Holds the stream info only; frames go straight to `file`
+    stream: flacenc::component::Stream,
+    config: flacenc::error::Verified<flacenc::config::Encoder>,
+    framebuf: flacenc::source::FrameBuf,
+    context: flacenc::source::Context,
+    quantizer: Quantizer,
+    pending: Vec<i32>,
+    sink: flacenc::bitsink::ByteSink,
+}
 
-            // Convert from f32 (-1.0 to 1.0) to i16 (-32768 to 32767)
-            let left_i16 = (left.clamp(-1.0, 1.0) * 32767.0) as i16;
-            let right_i16 = (right.clamp(-1.0, 1.0) * 32767.0) as i16;
+impl FlacEncoder {
+    fn create(
+        path: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        tags: &ExportTags,
+    ) -> anyhow::Result<Self> {
+        use flacenc::component::MetadataBlockData;
+        use flacenc::error::Verify;
 
-            writer.write_sample(left_i16)?;
-            writer.write_sample(right_i16)?;
+        let bits_per_sample = match options.bit_depth {
+            BitDepth::Int16 | BitDepth::Int24 => options.bit_depth.bits() as usize,
+            depth => {
+                return Err(TunesError::InvalidAudioFormat(format!(
+                    "FLAC export supports 16- and 24-bit samples, not {:?}",
+                    depth
+                ))
+                .into())
+            }
+        };
+        let channels = options.channels.count() as usize;
 
-            sample_clock = (sample_clock + 1.0) % sample_rate_f32;
+        // Create encoder configuration
+        let config = flacenc::config::Encoder::default()
+            .into_verified()
+            .expect("Default encoder config should be valid");
+        let block_size = config.block_size;
+
+        let mut stream =
+            flacenc::component::Stream::new(sample_rate as usize, channels, bits_per_sample)
+                .map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
+        stream
+            .stream_info_mut()
+            .set_block_sizes(block_size, block_size)
+            .map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
+        let framebuf = flacenc::source::FrameBuf::with_size(channels, block_size)
+            .map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
+
+        // VORBIS_COMMENT (4) and CUESHEET (5) blocks follow the stream info
+        let comments = tags.vorbis_comment_block(&[]);
+        let cuesheet = tags.flac_cuesheet().map(|sheet| (5, sheet));
+        for (typetag, data) in std::iter::once((4, comments)).chain(cuesheet) {
+            let block = MetadataBlockData::new_unknown(typetag, &data)
+                .map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
+            stream.add_metadata_block(block);
         }
 
-        writer.finalize()?;
-        Ok(())
+        let mut encoder = Self {
+            file: BufWriter::new(File::create(path)?),
+            stream,
+            config,
+            framebuf,
+            context: flacenc::source::Context::new(bits_per_sample, channels),
+            quantizer: Quantizer::new(*options),
+            pending: Vec::with_capacity(block_size * channels),
+            sink: flacenc::bitsink::ByteSink::new(),
+        };
+        encoder.write_header()?;
+        Ok(encoder)
     }
 
-    /// Helper: Sample a single track (by index) at a specific time
-    ///
-    /// This is similar to `sample_at()` but only processes one track,
-    /// used internally for stem export.
-    fn sample_track_at_index(
-        &mut self,
-        track_index: usize,
-        time: f32,
-        sample_rate: f32,
-        _sample_clock: f32,
-    ) -> (f32, f32) {
-        // Find the track by iterating through buses
-        let sample_count = self.sample_count;
-        let mut current_index = 0;
-        for bus_opt in self.buses.iter_mut() {
-            let bus = match bus_opt {
-                Some(b) => b,
-                None => continue,
-            };
-
-            for track in &mut bus.tracks {
-                if current_index == track_index {
-                    // Found the track! Use the static process_track helper from Mixer
-                    return Mixer::process_track_static(track, time, sample_rate, sample_count);
-                }
-                current_index += 1;
+    /// Queue interleaved stereo samples, encoding every frame that fills up
+    fn write_samples(&mut self, samples: &[f32]) -> anyhow::Result<()> {
+        let channels = self.quantizer.options().channels;
+        let frame_len = self.framebuf.size() * channels.count() as usize;
+        downmix(samples, channels, |sample, channel| {
+            self.pending.push(self.quantizer.quantize(sample, channel));
+            if self.pending.len() == frame_len {
+                self.encode_pending()?;
             }
+            Ok::<(), anyhow::Error>(())
+        })
+    }
+
+    /// Encode the remaining samples and patch the header with the final stream info
+    fn finalize(mut self) -> anyhow::Result<()> {
+        if !self.pending.is_empty() {
+            self.encode_pending()?;
         }
-        (0.0, 0.0) // Track not found
+        let md5 = self.context.md5_digest();
+        let total_samples = self.context.total_samples();
+        let info = self.stream.stream_info_mut();
+        info.set_md5_digest(&md5);
+        info.set_total_samples(total_samples);
+
+        self.file.seek(SeekFrom::Start(0))?;
+        self.write_header()?;
+        self.file.flush()?;
+        Ok(())
+    }
+
+    fn encode_pending(&mut self) -> anyhow::Result<()> {
+        use flacenc::component::BitRepr;
+        use flacenc::source::Fill;
+
+        (&mut self.framebuf, &mut self.context)
+            .fill_interleaved(&self.pending)
+            .map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
+        self.pending.clear();
+
+        let frame_number = self.context.current_frame_number().unwrap_or(0);
+        let frame = flacenc::encode_fixed_size_frame(
+            &self.config,
+            &self.framebuf,
+            frame_number,
+            self.stream.stream_info(),
+        )
+        .map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
+        self.stream.stream_info_mut().update_frame_info(&frame);
+
+        self.sink.clear();
+        frame
+            .write(&mut self.sink)
+            .map_err(|e| anyhow::anyhow!("Failed to write FLAC stream: {:?}", e))?;
+        self.file.write_all(self.sink.as_slice())?;
+        Ok(())
+    }
+
+    /// Write the `fLaC` marker and metadata blocks (always the same length)
+    fn write_header(&mut self) -> anyhow::Result<()> {
+        use flacenc::component::BitRepr;
+
+        // A stream without frames serializes to just the header
+        self.sink.clear();
+        self.stream
+            .write(&mut self.sink)
+            .map_err(|e| anyhow::anyhow!("Failed to write FLAC stream: {:?}", e))?;
+        self.file.write_all(self.sink.as_slice())?;
+        Ok(())
     }
 }
 
@@ -387,6 +841,7 @@ mod tests {
     use super::*;
     use crate::composition::timing::Tempo;
     use crate::instruments::drums::DrumType;
+    use crate::synthesis::waveform::Waveform;
 
     #[test]
     fn test_export_wav_creates_file() {
@@ -550,4 +1005,419 @@ mod tests {
 
         std::fs::remove_file(test_file).ok();
     }
+
+    #[test]
+    fn test_repeated_renders_are_identical() {
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        let mut track = crate::track::Track::new()
+            .with_delay(crate::synthesis::effects::Delay::new(0.1, 0.5, 0.5))
+            .with_reverb(crate::synthesis::effects::Reverb::new(0.8, 0.5, 0.4));
+        track.add_note(&[440.0], 0.0, 0.2);
+        mixer.add_track(track);
+
+        // Tails from the first render must not leak into the second
+        let first = mixer.render_to_buffer(48000.0);
+        let second = mixer.render_to_buffer(48000.0);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_noise_tracks_render_the_same_take_every_time() {
+        let take = || {
+            let mut mixer = Mixer::new(Tempo::new(120.0));
+            let mut track = crate::track::Track::new();
+            for hit in 0..4 {
+                let start = hit as f32 * 0.1;
+                track.add_note_with_waveform(&[440.0], start, 0.05, Waveform::noise());
+            }
+            mixer.add_track(track);
+            mixer.render_to_buffer(44100.0)
+        };
+
+        // Tracks built outside a composition start from a fixed seed
+        let first = take();
+        assert!(first.iter().any(|&sample| sample != 0.0));
+        assert_eq!(first, take());
+    }
+
+    fn progress_mixer() -> Mixer {
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        let mut track = crate::track::Track::new();
+        track.add_note(&[440.0], 0.0, 0.3);
+        track.add_drum(DrumType::Kick, 0.1, None);
+        mixer.add_track(track);
+        mixer
+    }
+
+    #[test]
+    fn test_streaming_flac_matches_one_shot_encoder() {
+        use flacenc::component::BitRepr;
+        use flacenc::error::Verify;
+
+        let mut mixer = progress_mixer();
+        let test_file = "test_streaming.flac";
+        mixer.export_flac(test_file, 44100).unwrap();
+        let streamed = std::fs::read(test_file).unwrap();
+        std::fs::remove_file(test_file).ok();
+
+        // Encode the same render in one go with flacenc's own driver
+        let samples: Vec<i32> = mixer
+            .render_to_buffer(44100.0)
+            .iter()
+            .map(|&s| (s as f64 * 8388607.0).round() as i32)
+            .collect();
+        let config = flacenc::config::Encoder::default().into_verified().unwrap();
+        let source = flacenc::source::MemSource::from_samples(&samples, 2, 24, 44100);
+        let mut stream =
+            flacenc::encode_with_fixed_block_size(&config, source, config.block_size).unwrap();
+        let tags = ExportTags::new(&mixer, 44100, samples.len() / 2);
+        stream.add_metadata_block(
+            flacenc::component::MetadataBlockData::new_unknown(4, &tags.vorbis_comment_block(&[]))
+                .unwrap(),
+        );
+        let mut sink = flacenc::bitsink::ByteSink::new();
+        stream.write(&mut sink).unwrap();
+
+        assert_eq!(streamed, sink.as_slice());
+    }
+
+    #[test]
+    fn test_export_progress_reaches_completion() {
+        let mut mixer = progress_mixer();
+        let test_file = "test_progress.wav";
+        let mut reports = Vec::new();
+        mixer
+            .export_wav_with_progress(test_file, 44100, &ExportOptions::default(), |progress| {
+                reports.push(*progress);
+                true
+            })
+            .unwrap();
+        std::fs::remove_file(test_file).ok();
+
+        assert!(reports.len() > 1);
+        assert!(reports.windows(2).all(|w| w[0].frames_done < w[1].frames_done));
+        let last = reports.last().unwrap();
+        assert_eq!(last.frames_done, last.total_frames);
+        assert_eq!(last.fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_cancelled_export_removes_file() {
+        let mut mixer = progress_mixer();
+        let test_file = "test_cancelled.flac";
+        let err = mixer
+            .export_flac_with_progress(test_file, 44100, &ExportOptions::default(), |_| false)
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<TunesError>(),
+            Some(TunesError::ExportCancelled)
+        ));
+        assert!(!std::path::Path::new(test_file).exists());
+    }
+
+    #[test]
+    fn test_stem_progress_covers_every_file() {
+        let mut mixer = progress_mixer();
+        let mut track = crate::track::Track::new();
+        track.add_note(&[220.0], 0.0, 0.2);
+        mixer.add_track(track);
+
+        let output_dir = "test_stem_progress";
+        let mut finished = Vec::new();
+        mixer
+            .export_stems_with_master_with_progress(output_dir, 22050, &ExportOptions::default(), |progress| {
+                if progress.frames_done == progress.total_frames {
+                    finished.push(progress.file_index);
+                }
+                assert_eq!(progress.file_count, 3);
+                true
+            })
+            .unwrap();
+        std::fs::remove_dir_all(output_dir).ok();
+
+        assert_eq!(finished, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_float_stems_match_the_block_render_unclamped() {
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        let mut track = crate::track::Track::new();
+        track.name = Some("hot".to_string());
+        track.volume = 4.0;
+        track.add_note(&[220.0], 0.0, 0.5);
+        mixer.add_track(track);
+        let reference = mixer.render_to_buffer(44100.0);
+
+        let output_dir = "test_float_stems";
+        mixer
+            .export_stems_with_options(output_dir, 44100, &ExportOptions::float())
+            .unwrap();
+        let stem: Vec<f32> = hound::WavReader::open(format!("{}/hot.wav", output_dir))
+            .unwrap()
+            .samples::<f32>()
+            .map(Result::unwrap)
+            .collect();
+        std::fs::remove_dir_all(output_dir).ok();
+
+        let peak = stem.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        assert!(peak > 1.5, "stem peaks at {}", peak);
+        assert_eq!(stem[..], reference[..stem.len()]);
+    }
+
+    fn read_wav_spec(path: &str) -> hound::WavSpec {
+        let spec = hound::WavReader::open(path).unwrap().spec();
+        std::fs::remove_file(path).ok();
+        spec
+    }
+
+    #[test]
+    fn test_export_wav_with_options_writes_requested_format() {
+        use crate::track::{BitDepth, Channels, Dither};
+
+        let mut mixer = progress_mixer();
+        let cases = [
+            (ExportOptions::mastering(), 2, 24, hound::SampleFormat::Int),
+            (ExportOptions::float(), 2, 32, hound::SampleFormat::Float),
+            (
+                ExportOptions::default()
+                    .with_bit_depth(BitDepth::Int32)
+                    .with_dither(Dither::NoiseShaped)
+                    .with_channels(Channels::Mono),
+                1,
+                32,
+                hound::SampleFormat::Int,
+            ),
+        ];
+        for (index, (options, channels, bits, format)) in cases.into_iter().enumerate() {
+            let test_file = format!("test_options_{}.wav", index);
+            mixer
+                .export_wav_with_options(&test_file, 44100, &options)
+                .unwrap();
+            let spec = read_wav_spec(&test_file);
+            assert_eq!(spec.channels, channels);
+            assert_eq!(spec.bits_per_sample, bits);
+            assert_eq!(spec.sample_format, format);
+        }
+    }
+
+    #[test]
+    fn test_float_wav_is_bit_exact_with_render() {
+        let mut mixer = progress_mixer();
+        let test_file = "test_float_exact.wav";
+        mixer
+            .export_wav_with_options(test_file, 44100, &ExportOptions::float())
+            .unwrap();
+        let written: Vec<f32> = hound::WavReader::open(test_file)
+            .unwrap()
+            .samples::<f32>()
+            .map(Result::unwrap)
+            .collect();
+        std::fs::remove_file(test_file).ok();
+
+        assert_eq!(written, mixer.render_to_buffer(44100.0));
+    }
+
+    #[test]
+    fn test_flac_rejects_unsupported_bit_depth() {
+        let mut mixer = progress_mixer();
+        let test_file = "test_float.flac";
+        let err = mixer
+            .export_flac_with_options(test_file, 44100, &ExportOptions::float())
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<TunesError>(),
+            Some(TunesError::InvalidAudioFormat(_))
+        ));
+        assert!(!std::path::Path::new(test_file).exists());
+    }
+
+    /// Two seconds of chords over a kick, long enough to gate
+    fn loudness_mixer() -> Mixer {
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        let mut track = crate::track::Track::new();
+        for beat in 0..4 {
+            track.add_note(&[220.0, 277.18, 329.63], beat as f32 * 0.5, 0.5);
+            track.add_drum(DrumType::Kick, beat as f32 * 0.5, None);
+        }
+        mixer.add_track(track);
+        mixer
+    }
+
+    #[test]
+    fn test_normalized_export_hits_target_loudness() {
+        let mut mixer = loudness_mixer();
+        let test_file = "test_normalized.wav";
+        let options = ExportOptions::float().with_normalization(Normalization::new(-20.0, -1.0));
+        mixer
+            .export_wav_with_options(test_file, 44100, &options)
+            .unwrap();
+        let exported = crate::synthesis::sample::Sample::from_file(test_file).unwrap();
+        std::fs::remove_file(test_file).ok();
+
+        let loudness = exported.loudness();
+        assert!((loudness.integrated + 20.0).abs() < 0.1, "{:?}", loudness);
+        assert!(loudness.true_peak <= -1.0 + 0.1);
+    }
+
+    #[test]
+    fn test_normalization_limits_true_peak() {
+        // Pushed to -4 LUFS, the kicks overshoot a -1 dBTP ceiling by over 2 dB
+        let mut mixer = loudness_mixer();
+        let test_file = "test_limited.wav";
+        let options = ExportOptions::float().with_normalization(Normalization::new(-4.0, -1.0));
+        mixer
+            .export_wav_with_options(test_file, 44100, &options)
+            .unwrap();
+        let exported = crate::synthesis::sample::Sample::from_file(test_file).unwrap();
+        std::fs::remove_file(test_file).ok();
+
+        let loudness = exported.loudness();
+        assert!(loudness.true_peak <= -1.0 + 0.01, "{:?}", loudness);
+        assert!(loudness.integrated > -12.0, "{:?}", loudness);
+    }
+
+    #[test]
+    fn test_normalization_turns_down_a_hot_mix_without_clipping_it() {
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        let mut track = crate::track::Track::new();
+        track.volume = 4.0;
+        track.add_note(&[220.0], 0.0, 2.0);
+        mixer.add_track(track);
+        let reference = mixer.render_to_buffer(44100.0);
+        let peak = reference.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        assert!(peak > 1.5, "mix peaks at {}", peak);
+
+        let test_file = "test_hot_normalized.wav";
+        let options = ExportOptions::float().with_normalization(Normalization::new(-20.0, -1.0));
+        mixer
+            .export_wav_with_options(test_file, 44100, &options)
+            .unwrap();
+        let exported = crate::synthesis::sample::Sample::from_file(test_file).unwrap();
+        std::fs::remove_file(test_file).ok();
+
+        // The export is the whole waveform turned down, crests included
+        let loudest = (0..reference.len())
+            .max_by(|&a, &b| reference[a].abs().total_cmp(&reference[b].abs()))
+            .unwrap();
+        let gain = exported.data[loudest] / reference[loudest];
+        assert!(gain < 0.5, "gain {}", gain);
+        for (exported, reference) in exported.data.iter().zip(&reference) {
+            assert!((exported - reference * gain).abs() < 1e-5);
+        }
+    }
+
+    /// Signal-to-noise ratio of `decoded` against `reference`, in dB
+    fn snr_db(reference: &[f32], decoded: &[f32]) -> f64 {
+        let signal: f64 = reference.iter().map(|&s| (s as f64).powi(2)).sum();
+        let noise: f64 = reference
+            .iter()
+            .zip(decoded)
+            .map(|(&a, &b)| (a as f64 - b as f64).powi(2))
+            .sum();
+        10.0 * (signal / noise).log10()
+    }
+
+    #[test]
+    fn test_ogg_round_trip_through_decoder() {
+        let mut mixer = progress_mixer();
+        let reference = mixer.render_to_buffer(44100.0);
+        let test_file = "test_round_trip.ogg";
+        mixer.export_ogg(test_file, 44100).unwrap();
+        let decoded = crate::synthesis::sample::Sample::from_file(test_file).unwrap();
+        let ogg_size = std::fs::metadata(test_file).unwrap().len();
+        std::fs::remove_file(test_file).ok();
+
+        assert_eq!(decoded.channels, 2);
+        assert_eq!(decoded.sample_rate, 44100);
+        // Without gapless playback the decoder keeps the padding of the last block
+        assert!(decoded.data.len() >= reference.len());
+        assert!(decoded.data.len() < reference.len() + 2 * 1024);
+        let snr = snr_db(&reference, &decoded.data);
+        assert!(snr > 20.0, "SNR {:.1} dB", snr);
+        // A tenth of the size of the 16-bit WAV equivalent, as documented
+        assert!((ogg_size as usize) < reference.len() * 2 / 10);
+    }
+
+    #[test]
+    fn test_ogg_mono_with_loop_tags() {
+        let mut mixer = progress_mixer();
+        let test_file = "test_loop.ogg";
+        let options = OggOptions::default()
+            .with_channels(crate::track::Channels::Mono)
+            .with_loop(0.1, 0.25);
+        mixer
+            .export_ogg_with_options(test_file, 44100, &options)
+            .unwrap();
+        let bytes = std::fs::read(test_file).unwrap();
+        let decoded = crate::synthesis::sample::Sample::from_file(test_file).unwrap();
+        std::fs::remove_file(test_file).ok();
+
+        assert_eq!(decoded.channels, 1);
+        let contains = |tag: &str| bytes.windows(tag.len()).any(|w| w == tag.as_bytes());
+        assert!(contains("LOOPSTART=4410"));
+        assert!(contains("LOOPLENGTH=6615"));
+    }
+
+    fn tagged_mixer() -> Mixer {
+        let mut mixer = progress_mixer();
+        mixer.metadata = crate::track::Metadata::default()
+            .with_title("Tagged")
+            .with_artist("Tester")
+            .with_marker(crate::track::Marker::new("hit", 0.1))
+            .with_marker(crate::track::Marker::region("tail", 0.2, 0.1));
+        mixer
+    }
+
+    #[test]
+    fn test_wav_export_embeds_markers_and_stays_decodable() {
+        let mut mixer = tagged_mixer();
+        let test_file = "test_tagged.wav";
+        mixer.export_wav(test_file, 44100).unwrap();
+        let bytes = std::fs::read(test_file).unwrap();
+        let decoded = crate::synthesis::sample::Sample::from_file(test_file).unwrap();
+        let reader = hound::WavReader::open(test_file).unwrap();
+        let frames = reader.duration() as usize;
+        std::fs::remove_file(test_file).ok();
+
+        let riff_size = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+        assert_eq!(riff_size, bytes.len() - 8);
+        let contains = |tag: &[u8]| bytes.windows(tag.len()).any(|w| w == tag);
+        for chunk in [b"cue ", b"labl", b"ltxt", b"bext", b"INAM"] {
+            assert!(contains(chunk), "missing {:?} chunk", chunk);
+        }
+        assert!(contains(b"Tagged"));
+        assert!(contains(b"tail"));
+        assert_eq!(decoded.data.len(), frames * 2);
+    }
+
+    #[test]
+    fn test_flac_export_embeds_comments_and_cuesheet() {
+        let mut mixer = tagged_mixer();
+        let test_file = "test_tagged.flac";
+        mixer.export_flac(test_file, 44100).unwrap();
+        let bytes = std::fs::read(test_file).unwrap();
+        std::fs::remove_file(test_file).ok();
+
+        // Walk the metadata blocks up to the first frame
+        let mut types = Vec::new();
+        let mut pos = 4;
+        loop {
+            let header = bytes[pos];
+            let len = u32::from_be_bytes([0, bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]]);
+            types.push(header & 0x7f);
+            pos += 4 + len as usize;
+            if header & 0x80 != 0 {
+                break;
+            }
+        }
+        assert_eq!(types, vec![0, 4, 5]);
+        assert_eq!(&bytes[pos..pos + 2], &[0xff, 0xf8]);
+
+        let contains = |tag: &str| bytes.windows(tag.len()).any(|w| w == tag.as_bytes());
+        assert!(contains("TITLE=Tagged"));
+        assert!(contains("ARTIST=Tester"));
+        assert!(contains("BPM=120"));
+        assert!(contains("CHAPTER002NAME=tail"));
+    }
 }
@@ -0,0 +1,502 @@
+//! Output formats for offline export
+//!
+//! [`ExportOptions`] chooses the bit depth, dithering and channel layout of exported
+//! files. The mixer renders in 32-bit float; [`Quantizer`] turns those samples into the
+//! requested integer format, rounding to the nearest step and optionally adding TPDF
+//! dither with first-order noise shaping so quiet passages and fades are not truncated.
+//! [`Normalization`] brings the whole mix to a target loudness before quantizing.
+
+use crate::synthesis::effects::Limiter;
+use crate::synthesis::loudness::Loudness;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+/// How far the true-peak limiter looks ahead, in seconds
+const LIMITER_LOOKAHEAD: f32 = 0.005;
+/// Release time of the true-peak limiter, in seconds
+const LIMITER_RELEASE: f32 = 0.05;
+
+/// Sample format of an exported file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitDepth {
+    /// 16-bit integer (CD quality)
+    #[default]
+    Int16,
+    /// 24-bit integer (studio masters)
+    Int24,
+    /// 32-bit integer
+    Int32,
+    /// 32-bit IEEE float (no quantization; WAV only)
+    Float32,
+}
+
+impl BitDepth {
+    /// Number of bits per sample
+    pub fn bits(self) -> u16 {
+        match self {
+            BitDepth::Int16 => 16,
+            BitDepth::Int24 => 24,
+            BitDepth::Int32 | BitDepth::Float32 => 32,
+        }
+    }
+
+    /// Largest positive integer sample value (`None` for float)
+    fn full_scale(self) -> Option<f64> {
+        match self {
+            BitDepth::Float32 => None,
+            depth => Some(((1u64 << (depth.bits() - 1)) - 1) as f64),
+        }
+    }
+}
+
+/// Dithering applied when reducing to an integer bit depth
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dither {
+    /// Round to the nearest step
+    #[default]
+    None,
+    /// Triangular (TPDF) dither of ±1 LSB, decorrelating the quantization error
+    Tpdf,
+    /// TPDF dither with first-order noise shaping, moving the noise towards high frequencies
+    NoiseShaped,
+}
+
+/// Channel layout of an exported file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Channels {
+    /// Single channel, downmixed as the average of left and right
+    Mono,
+    /// Left and right channels
+    #[default]
+    Stereo,
+}
+
+impl Channels {
+    /// Number of channels
+    pub fn count(self) -> u16 {
+        match self {
+            Channels::Mono => 1,
+            Channels::Stereo => 2,
+        }
+    }
+}
+
+/// Loudness target for exports
+///
+/// The mix is measured first (see [`Mixer::loudness`](super::Mixer::loudness)), then
+/// rendered again with the gain that reaches `target_lufs`. If that gain would push
+/// the true peak above `true_peak_ceiling`, a [`Limiter`] with lookahead holds the
+/// peaks down, measuring them with the same oversampling as the loudness meter, so the
+/// result can read slightly quieter than the target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normalization {
+    /// Integrated loudness to reach (LUFS)
+    pub target_lufs: f32,
+    /// Highest true peak allowed (dBTP)
+    pub true_peak_ceiling: f32,
+}
+
+impl Normalization {
+    /// Normalize to `target_lufs` with peaks held below `true_peak_ceiling` dBTP
+    pub fn new(target_lufs: f32, true_peak_ceiling: f32) -> Self {
+        Self {
+            target_lufs,
+            true_peak_ceiling: true_peak_ceiling.min(0.0),
+        }
+    }
+
+    /// -14 LUFS with a -1 dBTP ceiling, as used by most streaming services
+    pub fn streaming() -> Self {
+        Self::new(-14.0, -1.0)
+    }
+
+    /// -23 LUFS with a -1 dBTP ceiling (EBU R128 broadcast)
+    pub fn broadcast() -> Self {
+        Self::new(-23.0, -1.0)
+    }
+}
+
+/// Format options for WAV, FLAC and stem exports
+///
+/// The default is 16-bit stereo without dither or normalization. Use
+/// [`ExportOptions::mastering`] for files handed on for further processing.
+///
+/// # Example
+/// ```no_run
+/// # use tunes::prelude::*;
+/// # use tunes::track::{BitDepth, Channels, Dither, ExportOptions, Normalization};
+/// # fn main() -> anyhow::Result<()> {
+/// # let mut comp = Composition::new(Tempo::new(120.0));
+/// let mut mixer = comp.into_mixer();
+///
+/// // 24-bit master for the mastering engineer
+/// mixer.export_wav_with_options("master.wav", 48000, &ExportOptions::mastering())?;
+///
+/// // 16-bit mono with noise-shaped dither
+/// let options = ExportOptions::default()
+///     .with_dither(Dither::NoiseShaped)
+///     .with_channels(Channels::Mono);
+/// mixer.export_flac_with_options("voice.flac", 44100, &options)?;
+///
+/// // Ready for streaming services
+/// let options = ExportOptions::cd().with_normalization(Normalization::streaming());
+/// mixer.export_wav_with_options("release.wav", 44100, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ExportOptions {
+    /// Sample format
+    pub bit_depth: BitDepth,
+    /// Dithering used for integer formats
+    pub dither: Dither,
+    /// Output channel layout
+    pub channels: Channels,
+    /// Loudness normalization of the full mix (stems are never normalized)
+    pub normalization: Option<Normalization>,
+}
+
+impl ExportOptions {
+    /// 16-bit stereo with TPDF dither, for CD and distribution
+    pub fn cd() -> Self {
+        Self {
+            bit_depth: BitDepth::Int16,
+            dither: Dither::Tpdf,
+            channels: Channels::Stereo,
+            normalization: None,
+        }
+    }
+
+    /// 24-bit stereo with TPDF dither, for masters and further processing
+    pub fn mastering() -> Self {
+        Self {
+            bit_depth: BitDepth::Int24,
+            dither: Dither::Tpdf,
+            channels: Channels::Stereo,
+            normalization: None,
+        }
+    }
+
+    /// 32-bit float stereo, bit-exact with the mixer's output
+    pub fn float() -> Self {
+        Self {
+            bit_depth: BitDepth::Float32,
+            dither: Dither::None,
+            channels: Channels::Stereo,
+            normalization: None,
+        }
+    }
+
+    /// Set the sample format
+    pub fn with_bit_depth(mut self, bit_depth: BitDepth) -> Self {
+        self.bit_depth = bit_depth;
+        self
+    }
+
+    /// Set the dithering mode
+    pub fn with_dither(mut self, dither: Dither) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// Set the output channel layout
+    pub fn with_channels(mut self, channels: Channels) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Normalize the mix to a loudness target
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+}
+
+/// Options for Ogg Vorbis export
+///
+/// The default is stereo at quality 0.5 without loop tags.
+///
+/// # Example
+/// ```no_run
+/// # use tunes::prelude::*;
+/// # use tunes::track::OggOptions;
+/// # fn main() -> anyhow::Result<()> {
+/// # let mut comp = Composition::new(Tempo::new(120.0));
+/// let mut mixer = comp.into_mixer();
+///
+/// // Game music that loops back to the start of bar 2 after 32 seconds
+/// let options = OggOptions::default().with_quality(0.7).with_loop(2.0, 32.0);
+/// mixer.export_ogg_with_options("level1.ogg", 44100, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OggOptions {
+    /// Encoding quality from 0.0 (smallest files) to 1.0 (least audible noise)
+    pub quality: f32,
+    /// Output channel layout
+    pub channels: Channels,
+    /// Loop start and end in seconds, written as `LOOPSTART`/`LOOPLENGTH` tags
+    pub loop_region: Option<(f32, f32)>,
+    /// Loudness normalization of the mix
+    pub normalization: Option<Normalization>,
+}
+
+impl Default for OggOptions {
+    fn default() -> Self {
+        Self {
+            quality: 0.5,
+            channels: Channels::Stereo,
+            loop_region: None,
+            normalization: None,
+        }
+    }
+}
+
+impl OggOptions {
+    /// Set the encoding quality (clamped to 0.0 - 1.0)
+    pub fn with_quality(mut self, quality: f32) -> Self {
+        self.quality = quality.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the output channel layout
+    pub fn with_channels(mut self, channels: Channels) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Tag the file with a loop region, in seconds
+    ///
+    /// Game engines and players that understand the RPG Maker convention loop
+    /// playback from `end` back to `start`. The tags hold sample positions at the
+    /// export sample rate.
+    pub fn with_loop(mut self, start: f32, end: f32) -> Self {
+        self.loop_region = Some((start, end));
+        self
+    }
+
+    /// Normalize the mix to a loudness target
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Ratio of each band's loudest line to its quantization noise, in dB
+    pub(super) fn signal_to_noise_db(&self) -> f32 {
+        10.0 + 30.0 * self.quality.clamp(0.0, 1.0)
+    }
+}
+
+/// Feed each output sample of an interleaved stereo block to `write`
+///
+/// Handles the mono downmix; `write` receives the sample and its output channel.
+pub(super) fn downmix<E>(
+    block: &[f32],
+    channels: Channels,
+    mut write: impl FnMut(f32, usize) -> Result<(), E>,
+) -> Result<(), E> {
+    match channels {
+        Channels::Stereo => {
+            for (index, &sample) in block.iter().enumerate() {
+                write(sample, index % 2)?;
+            }
+        }
+        Channels::Mono => {
+            for frame in block.chunks_exact(2) {
+                write((frame[0] + frame[1]) * 0.5, 0)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies a [`Normalization`] to rendered blocks, given the mix's measured loudness
+///
+/// When limiting, output lags input by the limiter's lookahead: feed every block to
+/// [`process`](Self::process), then call [`finish`](Self::finish) for the last frames.
+#[derive(Debug, Clone)]
+pub(super) struct Normalizer {
+    gain: f32,
+    limiter: Option<Limiter>,
+    sample_rate: f32,
+}
+
+impl Normalizer {
+    pub fn new(normalization: Normalization, loudness: &Loudness, sample_rate: f32) -> Self {
+        let gain_db = loudness.gain_to(normalization.target_lufs);
+        // Only limit when the gained-up true peak would cross the ceiling
+        let limiter = (loudness.true_peak + gain_db > normalization.true_peak_ceiling).then(|| {
+            Limiter::with_sample_rate(normalization.true_peak_ceiling, LIMITER_RELEASE, sample_rate)
+                .with_true_peak_lookahead(LIMITER_LOOKAHEAD, sample_rate)
+        });
+        Self {
+            gain: 10f32.powf(gain_db / 20.0),
+            limiter,
+            sample_rate,
+        }
+    }
+
+    /// Apply the gain and limiting to a block of interleaved stereo samples
+    ///
+    /// Appends the normalized samples that are ready to `out`.
+    pub fn process(&mut self, block: &[f32], out: &mut Vec<f32>) {
+        match &mut self.limiter {
+            Some(limiter) => {
+                for frame in block.chunks_exact(2) {
+                    let gained = [frame[0] * self.gain, frame[1] * self.gain];
+                    limiter.process_lookahead(gained, self.sample_rate, out);
+                }
+            }
+            None => out.extend(block.iter().map(|sample| sample * self.gain)),
+        }
+    }
+
+    /// Append the samples still held back by the limiter's lookahead to `out`
+    pub fn finish(&mut self, out: &mut Vec<f32>) {
+        if let Some(limiter) = &mut self.limiter {
+            limiter.flush_lookahead(self.sample_rate, out);
+        }
+    }
+
+    /// Normalize a complete interleaved stereo buffer
+    pub fn apply(mut self, samples: &mut Vec<f32>) {
+        let mut normalized = Vec::with_capacity(samples.len());
+        self.process(samples, &mut normalized);
+        self.finish(&mut normalized);
+        *samples = normalized;
+    }
+}
+
+/// Converts the mixer's float output into the samples of an export format
+///
+/// Dither noise comes from a fixed seed, so exporting the same mix twice produces
+/// identical files.
+#[derive(Debug, Clone)]
+pub(super) struct Quantizer {
+    options: ExportOptions,
+    rng: SmallRng,
+    // Noise-shaping error carried to the next sample, per output channel
+    error: [f64; 2],
+}
+
+impl Quantizer {
+    pub fn new(options: ExportOptions) -> Self {
+        Self {
+            options,
+            rng: SmallRng::seed_from_u64(0x5EED_D17E),
+            error: [0.0; 2],
+        }
+    }
+
+    pub fn options(&self) -> &ExportOptions {
+        &self.options
+    }
+
+    /// Quantize a sample (-1.0 to 1.0) to the integer format
+    ///
+    /// For [`BitDepth::Float32`] the sample is returned scaled to `i32` range; callers
+    /// writing float files should use the sample directly instead.
+    pub fn quantize(&mut self, sample: f32, channel: usize) -> i32 {
+        let scale = self.options.bit_depth.full_scale().unwrap_or(i32::MAX as f64);
+        let target = sample.clamp(-1.0, 1.0) as f64 * scale;
+
+        let quantized = match self.options.dither {
+            Dither::None => target.round(),
+            Dither::Tpdf => (target + self.tpdf()).round(),
+            Dither::NoiseShaped => {
+                // Subtract the previous error so it is pushed towards high frequencies
+                let shaped = target - self.error[channel];
+                let quantized = (shaped + self.tpdf()).round();
+                self.error[channel] = quantized - shaped;
+                quantized
+            }
+        };
+        quantized.clamp(-scale - 1.0, scale) as i32
+    }
+
+    /// Triangular noise in -1.0..1.0 LSB
+    fn tpdf(&mut self) -> f64 {
+        self.rng.random::<f64>() - self.rng.random::<f64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantize_rounds_instead_of_truncating() {
+        let mut quantizer = Quantizer::new(ExportOptions::default());
+        // 0.9 LSB used to truncate to zero
+        assert_eq!(quantizer.quantize(0.9 / 32767.0, 0), 1);
+        assert_eq!(quantizer.quantize(-1.0, 0), -32767);
+        assert_eq!(quantizer.quantize(2.0, 0), 32767);
+
+        let mut quantizer = Quantizer::new(ExportOptions::mastering().with_dither(Dither::None));
+        assert_eq!(quantizer.quantize(0.5, 0), 4194304);
+    }
+
+    #[test]
+    fn test_tpdf_dither_preserves_low_level_signal() {
+        // A constant signal of 0.25 LSB vanishes without dither...
+        let level = 0.25 / 32767.0;
+        let mut plain = Quantizer::new(ExportOptions::default());
+        assert!((0..1000).all(|_| plain.quantize(level, 0) == 0));
+
+        // ...but survives on average with it, within ±1 LSB per sample
+        let mut dithered = Quantizer::new(ExportOptions::cd());
+        let samples: Vec<i32> = (0..20000).map(|_| dithered.quantize(level, 0)).collect();
+        assert!(samples.iter().all(|s| s.abs() <= 1));
+        let mean = samples.iter().sum::<i32>() as f64 / samples.len() as f64;
+        assert!((mean - 0.25).abs() < 0.05, "mean {}", mean);
+    }
+
+    #[test]
+    fn test_noise_shaping_moves_error_to_high_frequencies() {
+        let options = ExportOptions::default().with_dither(Dither::NoiseShaped);
+        let mut quantizer = Quantizer::new(options);
+        let input: Vec<f64> = (0..4096).map(|i| (i as f64 * 0.01).sin() * 0.1).collect();
+        let error: Vec<f64> = input
+            .iter()
+            .map(|&x| quantizer.quantize(x as f32, 0) as f64 - x * 32767.0)
+            .collect();
+
+        // First-order shaping makes successive errors anti-correlated
+        let lag0: f64 = error.iter().map(|e| e * e).sum();
+        let lag1: f64 = error.windows(2).map(|w| w[0] * w[1]).sum();
+        assert!(lag1 / lag0 < -0.3, "autocorrelation {}", lag1 / lag0);
+    }
+
+    #[test]
+    fn test_mono_downmix() {
+        let mut out = Vec::new();
+        downmix(&[1.0, 0.0, 0.5, 0.5], Channels::Mono, |sample, channel| {
+            out.push((sample, channel));
+            Ok::<(), ()>(())
+        })
+        .unwrap();
+        assert_eq!(out, vec![(0.5, 0), (0.5, 0)]);
+    }
+
+    #[test]
+    fn test_normalizer_limits_peaks_between_samples() {
+        use crate::synthesis::loudness;
+        use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+        // A quarter-rate sine sampled 45 degrees off its crests peaks 3 dB above its samples
+        let mut samples: Vec<f32> = (0..44100)
+            .map(|i| (i as f32 * FRAC_PI_2 + FRAC_PI_4).sin() * 0.5)
+            .flat_map(|sample| [sample, sample])
+            .collect();
+        let length = samples.len();
+        let measured = loudness::measure(&samples, 44100, 2);
+        let normalization = Normalization::new(measured.integrated + 6.0, -1.0);
+        Normalizer::new(normalization, &measured, 44100.0).apply(&mut samples);
+
+        assert_eq!(samples.len(), length);
+        let limited = loudness::measure(&samples, 44100, 2);
+        assert!(limited.true_peak <= -1.0 + 0.01, "{:?}", limited);
+        assert!(limited.sample_peak < -3.5, "{:?}", limited);
+    }
+}
@@ -0,0 +1,103 @@
+//! Plugin delay compensation
+//!
+//! Lookahead effects (limiters, linear-phase EQ, block convolution) report how far they
+//! delay their output through [`Effect::latency`](crate::synthesis::effects::Effect::latency).
+//! Before tracks and buses are summed, the mixer holds back every faster signal path
+//! with a [`CompensationDelay`] so that all of them line up with the slowest one.
+
+/// Fixed delay line that holds back an interleaved signal by a whole number of frames
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CompensationDelay {
+    buffer: Vec<f32>,
+    position: usize,
+    channels: usize,
+}
+
+impl CompensationDelay {
+    /// Current delay in frames
+    pub fn frames(&self) -> usize {
+        self.buffer.len().checked_div(self.channels).unwrap_or(0)
+    }
+
+    /// Set the delay for a stream with `channels` interleaved channels
+    ///
+    /// The delay line is cleared when the delay changes; setting the current delay
+    /// again keeps its contents.
+    pub fn set(&mut self, frames: usize, channels: usize) {
+        if frames == self.frames() && channels == self.channels {
+            return;
+        }
+        self.buffer = vec![0.0; frames * channels];
+        self.position = 0;
+        self.channels = channels;
+    }
+
+    /// Delay a block of interleaved samples in-place
+    pub fn process(&mut self, buffer: &mut [f32]) {
+        let len = self.buffer.len();
+        if len == 0 {
+            return;
+        }
+        for sample in buffer.iter_mut() {
+            std::mem::swap(sample, &mut self.buffer[self.position]);
+            self.position += 1;
+            if self.position == len {
+                self.position = 0;
+            }
+        }
+    }
+
+    /// Clear the delay line
+    pub fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        self.position = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_delay_is_passthrough() {
+        let mut delay = CompensationDelay::default();
+        delay.set(0, 2);
+        let mut buffer = vec![1.0, 2.0, 3.0, 4.0];
+        delay.process(&mut buffer);
+        assert_eq!(buffer, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_stereo_delay_across_blocks() {
+        let mut delay = CompensationDelay::default();
+        delay.set(3, 2);
+        assert_eq!(delay.frames(), 3);
+
+        let mut first: Vec<f32> = (1..=4).map(|i| i as f32).collect();
+        let mut second: Vec<f32> = (5..=8).map(|i| i as f32).collect();
+        delay.process(&mut first);
+        delay.process(&mut second);
+
+        // Three frames (six samples) of silence, then the input
+        assert_eq!(first, vec![0.0; 4]);
+        assert_eq!(second, vec![0.0, 0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_set_same_delay_keeps_contents() {
+        let mut delay = CompensationDelay::default();
+        delay.set(1, 1);
+        let mut buffer = vec![5.0];
+        delay.process(&mut buffer);
+
+        delay.set(1, 1);
+        let mut buffer = vec![0.0];
+        delay.process(&mut buffer);
+        assert_eq!(buffer, vec![5.0]);
+
+        delay.reset();
+        let mut buffer = vec![0.0];
+        delay.process(&mut buffer);
+        assert_eq!(buffer, vec![0.0]);
+    }
+}
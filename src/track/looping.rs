@@ -0,0 +1,337 @@
+//! Seamless loop rendering
+//!
+//! A plain render of a looping section starts dry and cuts its reverb and delay tails
+//! off at the end, so the loop clicks and gaps every time it wraps. A loop render keeps
+//! rendering past the loop end until the tails have died away (or `max_tail` is
+//! reached), then mixes everything past the end back onto the start. For linear
+//! effects such as reverb and delay this is exactly what the start of the second pass
+//! through a looping section sounds like, so the file repeats without a seam.
+//!
+//! Exported loops carry a WAV `smpl` chunk with sample-exact loop points, and the
+//! join is checked for clicks.
+
+use super::export::{remove_on_error, WavEncoder};
+use super::export_format::{ExportOptions, Normalizer};
+use super::metadata::ExportTags;
+use super::mixer::Mixer;
+use crate::synthesis::loudness;
+
+/// Level below which the rendered tail counts as silent
+const SILENCE: f32 = 1e-5;
+
+/// Seam discontinuity always accepted as click-free (-60 dBFS)
+const CLICK_TOLERANCE: f32 = 1e-3;
+
+/// How far either side of the join [`LoopSeam`] compares against, in seconds
+const SEAM_WINDOW: f32 = 0.005;
+
+/// Settings for a loop render
+///
+/// # Example
+/// ```
+/// # use tunes::track::LoopOptions;
+/// // Loop exactly two bars at 120 BPM, allowing up to 4 seconds of reverb tail
+/// let options = LoopOptions::default().with_length(4.0).with_max_tail(4.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoopOptions {
+    /// Loop length in seconds (defaults to the mixer's total duration)
+    pub length: Option<f32>,
+    /// Longest tail, in seconds, rendered past the loop end and folded back
+    pub max_tail: f32,
+}
+
+impl Default for LoopOptions {
+    fn default() -> Self {
+        Self {
+            length: None,
+            max_tail: 10.0,
+        }
+    }
+}
+
+impl LoopOptions {
+    /// Loop a fixed number of seconds instead of the mixer's total duration
+    ///
+    /// Use the musical length (whole bars) so notes ringing past the end are
+    /// folded back instead of stretching the loop.
+    pub fn with_length(mut self, seconds: f32) -> Self {
+        self.length = Some(seconds.max(0.0));
+        self
+    }
+
+    /// Set the longest tail rendered past the loop end
+    pub fn with_max_tail(mut self, seconds: f32) -> Self {
+        self.max_tail = seconds.max(0.0);
+        self
+    }
+}
+
+/// How smoothly a loop joins its end back to its start
+///
+/// Both values are the largest second difference (the change in slope between
+/// neighbouring samples) across all channels. A click shows up as a kink at the join
+/// that is much sharper than anything in the audio around it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoopSeam {
+    /// Sharpest kink at the join itself
+    pub discontinuity: f32,
+    /// Sharpest kink within 5 ms either side of the join
+    pub surrounding: f32,
+}
+
+impl LoopSeam {
+    /// Measure the join of an interleaved loop with `channels` channels
+    fn measure(samples: &[f32], channels: usize, sample_rate: f32) -> Self {
+        let frames = samples.len() / channels.max(1);
+        if frames < 3 {
+            return Self {
+                discontinuity: 0.0,
+                surrounding: 0.0,
+            };
+        }
+        let window = ((SEAM_WINDOW * sample_rate) as usize).clamp(1, frames / 4 + 1);
+        let kink = |frame: usize, channel: usize| {
+            let at = |offset: usize| samples[(frame + offset) % frames * channels + channel];
+            // Frames `frame - 1`, `frame` and `frame + 1`, wrapping around the loop
+            (at(frames + 1) - 2.0 * at(frames) + at(frames - 1)).abs()
+        };
+
+        let mut seam = Self {
+            discontinuity: 0.0,
+            surrounding: 0.0,
+        };
+        for channel in 0..channels {
+            for frame in [frames - 1, 0] {
+                seam.discontinuity = seam.discontinuity.max(kink(frame, channel));
+            }
+            let before = frames - 1 - window..frames - 1;
+            for frame in before.chain(1..=window) {
+                seam.surrounding = seam.surrounding.max(kink(frame, channel));
+            }
+        }
+        seam
+    }
+
+    /// Whether the join is no sharper than the audio around it
+    pub fn is_click_free(&self) -> bool {
+        self.discontinuity <= 2.0 * self.surrounding + CLICK_TOLERANCE
+    }
+}
+
+/// A rendered loop, ready to repeat without a seam
+#[derive(Debug, Clone)]
+pub struct LoopRender {
+    /// Interleaved stereo samples, exactly one loop long
+    pub samples: Vec<f32>,
+    /// Loop length in frames
+    pub frames: usize,
+    /// Frames of tail rendered past the loop end and folded onto the start
+    pub tail_frames: usize,
+    /// Smoothness of the join
+    pub seam: LoopSeam,
+}
+
+impl Mixer {
+    /// Render the mix as a seamless loop
+    ///
+    /// Renders past the loop end until the effect tails and ringing notes fall
+    /// silent (up to `options.max_tail`), then mixes everything past the end back
+    /// onto the start, so the loop begins with the reverb and delay of its own ending.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # use tunes::track::LoopOptions;
+    /// # use tunes::synthesis::effects::Delay;
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("lead")
+    ///     .delay(Delay::new(0.25, 0.5, 0.4))
+    ///     .notes(&[C4, E4, G4, C5], 0.5);
+    /// let mut mixer = comp.into_mixer();
+    ///
+    /// let looped = mixer.render_loop(44100.0, &LoopOptions::default().with_length(2.0));
+    /// assert_eq!(looped.frames, 88200);
+    /// assert!(looped.seam.is_click_free());
+    /// ```
+    pub fn render_loop(&mut self, sample_rate: f32, options: &LoopOptions) -> LoopRender {
+        let content_frames = (self.total_duration() * sample_rate).ceil() as usize;
+        let frames = match options.length {
+            Some(length) => (length * sample_rate).round() as usize,
+            None => content_frames,
+        };
+        let render_frames =
+            content_frames.max(frames) + (options.max_tail * sample_rate).ceil() as usize;
+
+        let mut rendered = Vec::with_capacity(render_frames * 2);
+        let result: Result<(), std::convert::Infallible> =
+            self.render_frames(sample_rate, render_frames, |block| {
+                rendered.extend_from_slice(block);
+                Ok(())
+            });
+        if let Err(never) = result {
+            match never {}
+        }
+        if frames == 0 {
+            return LoopRender {
+                samples: Vec::new(),
+                frames: 0,
+                tail_frames: 0,
+                seam: LoopSeam::measure(&[], 2, sample_rate),
+            };
+        }
+
+        // Drop the silent end of the tail, then wrap what is left onto the start
+        let audible_frames = rendered
+            .iter()
+            .rposition(|sample| sample.abs() > SILENCE)
+            .map_or(0, |index| index / 2 + 1);
+        let mut samples = rendered;
+        let tail: Vec<f32> = samples.split_off(frames.min(samples.len() / 2) * 2);
+        samples.resize(frames * 2, 0.0);
+        let tail_frames = audible_frames.saturating_sub(frames);
+        for (index, sample) in tail[..tail_frames * 2].iter().enumerate() {
+            samples[index % (frames * 2)] += sample;
+        }
+
+        LoopRender {
+            seam: LoopSeam::measure(&samples, 2, sample_rate),
+            samples,
+            frames,
+            tail_frames,
+        }
+    }
+
+    /// Export the mix as a seamless loop to a WAV file
+    ///
+    /// Renders with [`render_loop`](Self::render_loop) and writes a `smpl` chunk whose
+    /// loop covers every frame, so samplers and game audio engines pick up the loop
+    /// points automatically. Returns the seam measurement of the written loop.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use tunes::prelude::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// let mut mixer = comp.into_mixer();
+    /// let seam = mixer.export_loop_wav("level1.wav", 48000)?;
+    /// assert!(seam.is_click_free());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_loop_wav(&mut self, path: &str, sample_rate: u32) -> anyhow::Result<LoopSeam> {
+        self.export_loop_wav_with_options(
+            path,
+            sample_rate,
+            &ExportOptions::default(),
+            &LoopOptions::default(),
+        )
+    }
+
+    /// Export a seamless loop with explicit format and loop settings
+    ///
+    /// Same as [`export_loop_wav`](Self::export_loop_wav), but writes the format chosen
+    /// in `options` and loops as set in `loop_options`. Normalization measures one
+    /// pass through the loop.
+    pub fn export_loop_wav_with_options(
+        &mut self,
+        path: &str,
+        sample_rate: u32,
+        options: &ExportOptions,
+        loop_options: &LoopOptions,
+    ) -> anyhow::Result<LoopSeam> {
+        let mut looped = self.render_loop(sample_rate as f32, loop_options);
+        if let Some(normalization) = options.normalization {
+            let loudness = loudness::measure(&looped.samples, sample_rate, 2);
+            Normalizer::new(normalization, &loudness, sample_rate as f32)
+                .apply(&mut looped.samples);
+            looped.seam = LoopSeam::measure(&looped.samples, 2, sample_rate as f32);
+        }
+
+        let tags = ExportTags::new(self, sample_rate, looped.frames).with_sample_loop();
+        let mut encoder = WavEncoder::create(path, sample_rate, options, tags)?;
+        remove_on_error(path, || {
+            for block in looped.samples.chunks(Mixer::RENDER_BLOCK_SIZE * 2) {
+                encoder.write_samples(block)?;
+            }
+            encoder.finalize()
+        })?;
+        Ok(looped.seam)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composition::timing::Tempo;
+    use crate::composition::Composition;
+    use crate::synthesis::effects::{Delay, Reverb};
+
+    fn echo_mixer() -> Mixer {
+        let mut comp = Composition::new(Tempo::new(120.0));
+        comp.track("lead")
+            .delay(Delay::new(0.3, 0.5, 0.5))
+            .reverb(Reverb::new(0.6, 0.5, 0.3))
+            .at(0.2)
+            .notes(&[440.0, 660.0], 0.3);
+        comp.into_mixer()
+    }
+
+    #[test]
+    fn test_tail_is_folded_onto_the_start() {
+        let mut mixer = echo_mixer();
+        let dry = mixer.render_to_buffer(44100.0);
+        let looped = mixer.render_loop(44100.0, &LoopOptions::default().with_length(1.0));
+
+        assert_eq!(looped.frames, 44100);
+        assert_eq!(looped.samples.len(), 88200);
+        assert!(looped.tail_frames > 0);
+        // The plain render is silent until the first note; the loop hears the echoes
+        let head = 0.1 * 44100.0 * 2.0;
+        let energy = |samples: &[f32]| samples[..head as usize].iter().map(|s| s * s).sum::<f32>();
+        assert!(energy(&dry) < 1e-9);
+        assert!(energy(&looped.samples) > 1e-4);
+    }
+
+    #[test]
+    fn test_folded_loop_joins_without_a_click() {
+        let mut mixer = echo_mixer();
+        let options = LoopOptions::default().with_length(1.0);
+        let looped = mixer.render_loop(44100.0, &options);
+        assert!(looped.seam.is_click_free(), "{:?}", looped.seam);
+
+        // Cutting a plain render off mid-note clicks
+        let cut = mixer.render_to_buffer(44100.0);
+        let seam = LoopSeam::measure(&cut[..(0.4 * 44100.0) as usize * 2], 2, 44100.0);
+        assert!(!seam.is_click_free(), "{:?}", seam);
+    }
+
+    #[test]
+    fn test_loop_export_writes_smpl_loop_points() {
+        let mut mixer = echo_mixer();
+        let test_file = "test_loop_export.wav";
+        let loop_options = LoopOptions::default().with_length(0.5).with_max_tail(2.0);
+        let seam = mixer
+            .export_loop_wav_with_options(
+                test_file,
+                44100,
+                &ExportOptions::default(),
+                &loop_options,
+            )
+            .unwrap();
+        let bytes = std::fs::read(test_file).unwrap();
+        let frames = hound::WavReader::open(test_file).unwrap().duration();
+        std::fs::remove_file(test_file).ok();
+
+        assert!(seam.is_click_free());
+        assert_eq!(frames, 22050);
+        let smpl = bytes.windows(4).position(|w| w == b"smpl").unwrap();
+        let field = |index: usize| {
+            let at = smpl + 8 + index * 4;
+            u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+        };
+        assert_eq!(field(7), 1); // One loop
+        assert_eq!(field(11), 0); // Loop start
+        assert_eq!(field(12), 22049); // Loop end, inclusive
+    }
+}
@@ -0,0 +1,482 @@
+//! Descriptive tags and markers embedded in exported files
+//!
+//! A mixer's [`Metadata`] travels with every export, together with the tempo and the
+//! first key signature of the composition:
+//!
+//! - **WAV** - `LIST/INFO` tags, a BWF `bext` chunk, and `cue ` points labelled in a
+//!   `LIST/adtl` chunk (markers with a duration become regions); loop renders add a
+//!   `smpl` chunk
+//! - **FLAC** - Vorbis comments and a CUESHEET with one track per marker
+//! - **Ogg Vorbis** - Vorbis comments
+//! - **MIDI** - marker and key signature meta events
+//!
+//! In Vorbis comments, markers follow the `CHAPTER001=00:01:30.000` /
+//! `CHAPTER001NAME=Chorus` convention understood by most players.
+
+use super::events::AudioEvent;
+use super::mixer::Mixer;
+use crate::theory::key_signature::KeySignature;
+
+/// Most markers a FLAC cuesheet can hold (track 255 is the lead-out)
+const MAX_CUESHEET_TRACKS: usize = 254;
+
+/// A named position in the timeline
+///
+/// Markers with a duration describe a region, such as a section placed by
+/// [`Composition::arrange`](crate::composition::Composition::arrange).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Marker {
+    /// Label shown in DAWs and players
+    pub name: String,
+    /// Position in seconds
+    pub time: f32,
+    /// Length in seconds, for regions
+    pub duration: Option<f32>,
+}
+
+impl Marker {
+    /// Create a marker at `time` seconds
+    pub fn new(name: &str, time: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            time,
+            duration: None,
+        }
+    }
+
+    /// Create a region starting at `time` and lasting `duration` seconds
+    pub fn region(name: &str, time: f32, duration: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            time,
+            duration: Some(duration),
+        }
+    }
+}
+
+/// Title, artist and markers written into exported files
+///
+/// # Example
+/// ```no_run
+/// # use tunes::prelude::*;
+/// # use tunes::track::Metadata;
+/// # fn main() -> anyhow::Result<()> {
+/// let mut comp = Composition::new(Tempo::new(124.0));
+/// comp.set_metadata(Metadata::default().with_title("Night Drive").with_artist("Synthwave Kid"));
+/// comp.mark_at("drop", 16.0);
+/// # comp.track("lead").notes(&[C4, E4, G4], 0.5);
+///
+/// // Tags, BPM and the "drop" marker end up in the file
+/// comp.into_mixer().export_flac("night_drive.flac", 44100)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metadata {
+    /// Song title
+    pub title: Option<String>,
+    /// Performer or composer
+    pub artist: Option<String>,
+    /// Album or collection
+    pub album: Option<String>,
+    /// Free-form description
+    pub comment: Option<String>,
+    /// Markers and regions, in any order
+    pub markers: Vec<Marker>,
+}
+
+impl Metadata {
+    /// Set the title
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Set the artist
+    pub fn with_artist(mut self, artist: &str) -> Self {
+        self.artist = Some(artist.to_string());
+        self
+    }
+
+    /// Set the album
+    pub fn with_album(mut self, album: &str) -> Self {
+        self.album = Some(album.to_string());
+        self
+    }
+
+    /// Set a free-form comment
+    pub fn with_comment(mut self, comment: &str) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    /// Add a marker
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.markers.push(marker);
+        self
+    }
+}
+
+/// Everything written into one exported file, resolved from the mixer
+#[derive(Debug, Clone)]
+pub(super) struct ExportTags {
+    metadata: Metadata,
+    bpm: f32,
+    key: Option<KeySignature>,
+    sample_rate: u32,
+    total_frames: u64,
+    sample_loop: bool,
+}
+
+impl ExportTags {
+    /// Tags for a file of `total_frames` frames rendered from `mixer`
+    pub fn new(mixer: &Mixer, sample_rate: u32, total_frames: usize) -> Self {
+        let mut metadata = mixer.metadata.clone();
+        metadata
+            .markers
+            .sort_by(|a, b| a.time.total_cmp(&b.time).then_with(|| a.name.cmp(&b.name)));
+        Self {
+            metadata,
+            bpm: mixer.tempo.bpm,
+            key: initial_key(mixer),
+            sample_rate,
+            total_frames: total_frames as u64,
+            sample_loop: false,
+        }
+    }
+
+    /// Mark the whole file as a sustain loop for samplers (WAV `smpl` chunk)
+    pub fn with_sample_loop(mut self) -> Self {
+        self.sample_loop = true;
+        self
+    }
+
+    /// Markers that fall inside the exported audio, with their frame positions
+    fn markers(&self) -> impl Iterator<Item = (&Marker, u64)> {
+        self.metadata.markers.iter().filter_map(|marker| {
+            let frame = (marker.time.max(0.0) * self.sample_rate as f32).round() as u64;
+            (frame < self.total_frames).then_some((marker, frame))
+        })
+    }
+
+    /// "Title, 120 BPM, A Minor", for fields that only hold free text
+    fn summary(&self) -> String {
+        let mut parts: Vec<String> = self.metadata.title.iter().cloned().collect();
+        parts.push(format!("{} BPM", self.bpm));
+        parts.extend(self.key.map(|key| key.name()));
+        parts.join(", ")
+    }
+
+    /// Vorbis comment entries (`FIELD=value`) shared by FLAC and Ogg
+    pub fn vorbis_comments(&self) -> Vec<String> {
+        let metadata = &self.metadata;
+        let mut comments = Vec::new();
+        let fields = [
+            ("TITLE", &metadata.title),
+            ("ARTIST", &metadata.artist),
+            ("ALBUM", &metadata.album),
+            ("COMMENT", &metadata.comment),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                comments.push(format!("{}={}", field, value));
+            }
+        }
+        comments.push(format!("BPM={}", self.bpm));
+        if let Some(key) = self.key {
+            comments.push(format!("KEY={}", key.name()));
+        }
+        for (index, (marker, frame)) in self.markers().enumerate() {
+            let millis = frame * 1000 / self.sample_rate as u64;
+            comments.push(format!(
+                "CHAPTER{:03}={:02}:{:02}:{:02}.{:03}",
+                index + 1,
+                millis / 3_600_000,
+                millis / 60_000 % 60,
+                millis / 1000 % 60,
+                millis % 1000
+            ));
+            comments.push(format!("CHAPTER{:03}NAME={}", index + 1, marker.name));
+        }
+        comments
+    }
+
+    /// Chunks appended to a WAV file after its audio data
+    pub fn wav_chunks(&self) -> Vec<u8> {
+        let metadata = &self.metadata;
+        let mut out = Vec::new();
+
+        let mut info = b"INFO".to_vec();
+        let software = vendor();
+        let fields = [
+            (b"INAM", metadata.title.as_deref()),
+            (b"IART", metadata.artist.as_deref()),
+            (b"IPRD", metadata.album.as_deref()),
+            (b"ICMT", metadata.comment.as_deref()),
+            (b"ISFT", Some(software.as_str())),
+        ];
+        for (id, value) in fields {
+            if let Some(value) = value {
+                write_chunk(&mut info, id, &zero_terminated(value));
+            }
+        }
+        write_chunk(&mut out, b"LIST", &info);
+        write_chunk(&mut out, b"bext", &self.bext());
+        if self.sample_loop {
+            write_chunk(&mut out, b"smpl", &self.smpl());
+        }
+
+        let markers: Vec<(&Marker, u64)> = self.markers().collect();
+        if !markers.is_empty() {
+            let mut cue = (markers.len() as u32).to_le_bytes().to_vec();
+            let mut labels = b"adtl".to_vec();
+            for (id, (marker, frame)) in (1u32..).zip(&markers) {
+                let frame = *frame as u32;
+                cue.extend(id.to_le_bytes());
+                cue.extend(frame.to_le_bytes());
+                cue.extend(b"data");
+                cue.extend(0u32.to_le_bytes()); // Chunk start
+                cue.extend(0u32.to_le_bytes()); // Block start
+                cue.extend(frame.to_le_bytes());
+
+                let mut label = id.to_le_bytes().to_vec();
+                label.extend(zero_terminated(&marker.name));
+                write_chunk(&mut labels, b"labl", &label);
+
+                if let Some(duration) = marker.duration {
+                    let frames = (duration.max(0.0) * self.sample_rate as f32).round() as u32;
+                    let mut region = id.to_le_bytes().to_vec();
+                    region.extend(frames.to_le_bytes());
+                    region.extend(b"rgn ");
+                    region.extend([0; 8]); // Country, language, dialect, code page
+                    write_chunk(&mut labels, b"ltxt", &region);
+                }
+            }
+            write_chunk(&mut out, b"cue ", &cue);
+            write_chunk(&mut out, b"LIST", &labels);
+        }
+        out
+    }
+
+    /// Broadcast Wave `bext` chunk (EBU Tech 3285, version 1)
+    fn bext(&self) -> Vec<u8> {
+        let mut bext = Vec::with_capacity(602);
+        let description = self
+            .metadata
+            .comment
+            .clone()
+            .unwrap_or_else(|| self.summary());
+        bext.extend(fixed_ascii(&description, 256));
+        bext.extend(fixed_ascii(
+            self.metadata.artist.as_deref().unwrap_or("tunes"),
+            32,
+        ));
+        bext.extend(fixed_ascii("", 32)); // Originator reference
+                                          // Origination date and time are left blank so repeated exports are identical
+        bext.extend(fixed_ascii("", 10));
+        bext.extend(fixed_ascii("", 8));
+        bext.extend(0u64.to_le_bytes()); // Time reference
+        bext.extend(1u16.to_le_bytes()); // Version
+        bext.extend([0; 64]); // UMID
+        bext.extend([0; 190]); // Reserved
+        bext
+    }
+
+    /// Sampler `smpl` chunk with one forward loop over every frame
+    fn smpl(&self) -> Vec<u8> {
+        let sample_period = 1_000_000_000 / self.sample_rate.max(1);
+        let last_frame = self.total_frames.saturating_sub(1) as u32;
+        let fields = [
+            0,             // Manufacturer
+            0,             // Product
+            sample_period, // Nanoseconds per sample
+            60,            // MIDI unity note
+            0,             // MIDI pitch fraction
+            0,             // SMPTE format
+            0,             // SMPTE offset
+            1,             // Sample loops
+            0,             // Sampler data
+            0,             // Loop cue point ID
+            0,             // Loop type: forward
+            0,             // Loop start
+            last_frame,    // Loop end (inclusive)
+            0,             // Fraction
+            0,             // Play count: infinite
+        ];
+        fields
+            .iter()
+            .flat_map(|field| field.to_le_bytes())
+            .collect()
+    }
+
+    /// FLAC VORBIS_COMMENT block or Ogg comment packet body, without framing
+    pub fn vorbis_comment_block(&self, extra: &[String]) -> Vec<u8> {
+        let vendor = vendor();
+        let comments = self.vorbis_comments();
+        let mut block = (vendor.len() as u32).to_le_bytes().to_vec();
+        block.extend(vendor.as_bytes());
+        block.extend(((comments.len() + extra.len()) as u32).to_le_bytes());
+        for entry in comments.iter().chain(extra) {
+            block.extend((entry.len() as u32).to_le_bytes());
+            block.extend(entry.as_bytes());
+        }
+        block
+    }
+
+    /// FLAC CUESHEET block with one track per marker, if there are any
+    pub fn flac_cuesheet(&self) -> Option<Vec<u8>> {
+        let markers: Vec<u64> = self
+            .markers()
+            .map(|(_, frame)| frame)
+            .take(MAX_CUESHEET_TRACKS)
+            .collect();
+        if markers.is_empty() {
+            return None;
+        }
+
+        let mut sheet = vec![0; 128]; // Media catalog number
+        sheet.extend(0u64.to_be_bytes()); // Lead-in
+        sheet.extend([0; 259]); // Not a CD, reserved
+        sheet.push(markers.len() as u8 + 1);
+        for (number, frame) in (1u8..).zip(&markers) {
+            sheet.extend(frame.to_be_bytes());
+            sheet.push(number);
+            sheet.extend([0; 12]); // ISRC
+            sheet.extend([0; 14]); // Audio track, no pre-emphasis, reserved
+            sheet.push(1);
+            // Index 1 at the start of the track
+            sheet.extend(0u64.to_be_bytes());
+            sheet.push(1);
+            sheet.extend([0; 3]);
+        }
+        // Lead-out
+        sheet.extend(self.total_frames.to_be_bytes());
+        sheet.push(255);
+        sheet.extend([0; 26]);
+        sheet.push(0);
+        Some(sheet)
+    }
+}
+
+/// Earliest key signature set on any track
+pub(super) fn initial_key(mixer: &Mixer) -> Option<KeySignature> {
+    mixer
+        .all_tracks()
+        .into_iter()
+        .flat_map(|track| &track.events)
+        .filter_map(|event| match event {
+            AudioEvent::KeySignature(key) => Some(key),
+            _ => None,
+        })
+        .min_by_key(|key| key.start_time)
+        .map(|key| key.key_signature)
+}
+
+/// Encoder name written into every file
+fn vendor() -> String {
+    format!("tunes {}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Append a RIFF chunk, padded to an even length
+fn write_chunk(out: &mut Vec<u8>, id: &[u8; 4], data: &[u8]) {
+    out.extend(id);
+    out.extend((data.len() as u32).to_le_bytes());
+    out.extend(data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+fn zero_terminated(text: &str) -> Vec<u8> {
+    let mut bytes = text.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+/// ASCII text truncated or zero-padded to `len` bytes
+fn fixed_ascii(text: &str, len: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = text
+        .chars()
+        .filter(char::is_ascii)
+        .map(|c| c as u8)
+        .collect();
+    bytes.resize(len, 0);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composition::timing::Tempo;
+    use crate::composition::timing::ticks::at;
+    use crate::track::Track;
+
+    fn tagged_mixer() -> Mixer {
+        let mut mixer = Mixer::new(Tempo::new(128.0));
+        let mut track = Track::new();
+        track.add_note(&[440.0], 0.0, 4.0);
+        track
+            .events
+            .push(AudioEvent::KeySignature(super::super::KeySignatureEvent {
+                start_time: at(0.0),
+                key_signature: KeySignature::A_MINOR,
+            }));
+        mixer.add_track(track);
+        mixer.metadata = Metadata::default()
+            .with_title("Tides")
+            .with_marker(Marker::region("chorus", 2.0, 1.5))
+            .with_marker(Marker::new("intro", 0.0))
+            .with_marker(Marker::new("past the end", 10.0));
+        mixer
+    }
+
+    #[test]
+    fn test_vorbis_comments_include_tempo_key_and_chapters() {
+        let tags = ExportTags::new(&tagged_mixer(), 44100, 4 * 44100);
+        assert_eq!(
+            tags.vorbis_comments(),
+            vec![
+                "TITLE=Tides",
+                "BPM=128",
+                "KEY=A Minor",
+                "CHAPTER001=00:00:00.000",
+                "CHAPTER001NAME=intro",
+                "CHAPTER002=00:00:02.000",
+                "CHAPTER002NAME=chorus",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wav_chunks_are_word_aligned_riff() {
+        let chunks = ExportTags::new(&tagged_mixer(), 44100, 4 * 44100).wav_chunks();
+        let mut ids = Vec::new();
+        let mut offset = 0;
+        while offset < chunks.len() {
+            ids.push(&chunks[offset..offset + 4]);
+            let size = u32::from_le_bytes(chunks[offset + 4..offset + 8].try_into().unwrap());
+            offset += 8 + size as usize + size as usize % 2;
+        }
+        assert_eq!(offset, chunks.len());
+        assert_eq!(ids, [b"LIST", b"bext", b"cue ", b"LIST"]);
+        // Two cue points: the marker past the end is dropped
+        let cue = chunks.windows(4).position(|w| w == b"cue ").unwrap();
+        assert_eq!(chunks[cue + 8], 2);
+    }
+
+    #[test]
+    fn test_cuesheet_ends_with_lead_out() {
+        let sheet = ExportTags::new(&tagged_mixer(), 44100, 4 * 44100)
+            .flac_cuesheet()
+            .unwrap();
+        // Header, two tracks with one index each, and the lead-out
+        assert_eq!(sheet.len(), 396 + 2 * (36 + 12) + 36);
+        assert_eq!(sheet[395], 3);
+        let lead_out = &sheet[sheet.len() - 36..];
+        assert_eq!(
+            u64::from_be_bytes(lead_out[..8].try_into().unwrap()),
+            4 * 44100
+        );
+        assert_eq!(lead_out[8], 255);
+    }
+}
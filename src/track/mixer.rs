@@ -5,12 +5,17 @@
 
 use super::bus::{Bus, BusBuilder};
 use super::events::*;
+use super::metadata::Metadata;
+use super::modulation;
+use super::scene::{BusScene, MixerScene, SceneChange, TrackScene};
 use super::track::Track;
 use crate::cache::{CacheKey, CachedSample, SampleCache};
-use crate::composition::timing::Tempo;
+use crate::composition::timing::{Tempo, TempoMap, Ticks};
 #[cfg(feature = "gpu")]
 use crate::gpu::GpuSynthesizer;
 use crate::synthesis::effects::{EffectChain, ResolvedSidechainSource};
+use crate::synthesis::loudness::{Loudness, LoudnessMeter};
+use crate::synthesis::mod_matrix::{NoteModulation, TrackModulation};
 use crate::track::ids::{BusId, TrackId};
 use rayon::prelude::*;
 use std::collections::HashMap;
@@ -147,11 +152,21 @@ pub struct Mixer {
     prerendered: bool,
 
     pub tempo: Tempo,
+    pub tempo_map: TempoMap,      // Places every track's ticks in seconds (see set_tempo_map)
     pub(super) sample_count: u64, // For quantized automation lookups
     pub master: EffectChain,      // Master effects chain (stereo processing)
+    pub metadata: Metadata,       // Tags and markers written into exported files
+
+    // Scene timeline (sorted by time) and the state captured before the first change
+    scene_changes: Vec<SceneChange>,
+    scene_base: Option<MixerScene>,
+    scenes_settled: usize, // Number of changes whose morphs have completed
 }
 
 impl Mixer {
+    /// Number of frames processed per block by offline renders
+    pub const RENDER_BLOCK_SIZE: usize = 512;
+
     /// Create a new mixer with the specified tempo
     ///
     /// # Arguments
@@ -173,16 +188,47 @@ impl Mixer {
             gpu_synthesizer: None, // GPU disabled by default (requires explicit enable_gpu call)
             prerendered: false,
             tempo,
+            tempo_map: TempoMap::new(tempo.bpm),
             sample_count: 0,
             master: EffectChain::new(),
+            metadata: Metadata::default(),
+            scene_changes: Vec::new(),
+            scene_base: None,
+            scenes_settled: 0,
+        }
+    }
+
+    /// Replace the tempo map and give it to every track
+    ///
+    /// Events keep their positions in ticks and move to wherever those now fall.
+    /// Assigning `tempo_map` directly takes effect on the tracks at the next
+    /// [`prepare`](Self::prepare).
+    pub fn set_tempo_map(&mut self, tempo_map: TempoMap) {
+        self.tempo_map = tempo_map;
+        self.sync_tempo_map();
+    }
+
+    /// Give every track the mixer's tempo map
+    fn sync_tempo_map(&mut self) {
+        for bus in self.buses.iter_mut().flatten() {
+            for track in &mut bus.tracks {
+                if track.tempo_map != self.tempo_map {
+                    track.set_tempo_map(self.tempo_map.clone());
+                }
+            }
         }
     }
 
     /// Add a bus to the mixer
     ///
+    /// Its tracks are given the mixer's tempo map.
+    ///
     /// # Arguments
     /// * `bus` - The bus to add
-    pub fn add_bus(&mut self, bus: Bus) {
+    pub fn add_bus(&mut self, mut bus: Bus) {
+        for track in &mut bus.tracks {
+            track.set_tempo_map(self.tempo_map.clone());
+        }
         let bus_id = bus.id;
         let bus_name = bus.name.clone();
 
@@ -209,11 +255,12 @@ impl Mixer {
     /// Add a track to the default bus for backward compatibility
     ///
     /// This maintains compatibility with existing code that adds tracks directly.
-    /// Tracks are added to a bus named "default".
+    /// Tracks are added to a bus named "default" and given the mixer's tempo map.
     ///
     /// # Arguments
     /// * `track` - The track to add
-    pub fn add_track(&mut self, track: Track) {
+    pub fn add_track(&mut self, mut track: Track) {
+        track.set_tempo_map(self.tempo_map.clone());
         self.get_or_create_bus("default").add_track(track);
     }
 
@@ -579,21 +626,28 @@ impl Mixer {
             None => return,
         };
 
-        println!("🔄 Pre-rendering unique notes...");
-
         // Collect all unique notes from all tracks
-        let mut unique_notes: HashMap<CacheKey, NoteEvent> = HashMap::new();
+        let mut unique_notes: HashMap<CacheKey, (NoteEvent, f32)> = HashMap::new();
 
         for bus_opt in &self.buses {
             if let Some(bus) = bus_opt {
                 for track in &bus.tracks {
+                    // Notes under the mod matrix are synthesized as they play
+                    if track.modulation_reads_notes() {
+                        continue;
+                    }
                     for event in &track.events {
                         if let AudioEvent::Note(note_event) = event {
-                            let cache_key = CacheKey::from_note_event(note_event, sample_rate);
+                            // Spread unison needs the side channel, which the cache doesn't hold
+                            if note_event.unison.is_stereo() {
+                                continue;
+                            }
+                            let length = note_event.seconds(&track.tempo_map).length;
+                            let cache_key = CacheKey::from_note_event(note_event, length, sample_rate);
 
                             // Only add if not already seen
                             if !unique_notes.contains_key(&cache_key) {
-                                unique_notes.insert(cache_key, note_event.clone());
+                                unique_notes.insert(cache_key, (note_event.clone(), length));
                             }
                         }
                     }
@@ -601,14 +655,8 @@ impl Mixer {
             }
         }
 
-        let total_notes = unique_notes.len();
-        println!("   Found {} unique notes to render", total_notes);
-
         // Batch render all unique notes
-        let start = std::time::Instant::now();
-        let mut rendered_count = 0;
-
-        for (cache_key, note_event) in unique_notes {
+        for (cache_key, (note_event, length)) in unique_notes {
             // Check if already cached
             if cache
                 .lock()
@@ -620,11 +668,12 @@ impl Mixer {
             }
 
             // Render the note (GPU if available, CPU fallback)
-            let total_duration = note_event.envelope.total_duration(note_event.duration);
+            let total_duration = note_event.total_duration(length);
 
             if total_duration > 0.0 && total_duration < 10.0 {
                 let rendered_samples = Self::render_note_to_buffer(
                     &note_event,
+                    length,
                     sample_rate,
                     #[cfg(feature = "gpu")]
                     self.gpu_synthesizer.as_ref(),
@@ -641,41 +690,255 @@ impl Mixer {
                     .lock()
                     .unwrap_or_else(|e| e.into_inner())
                     .insert(cache_key, cached_sample);
-                rendered_count += 1;
             }
         }
 
-        let elapsed = start.elapsed();
+        // Mark as pre-rendered so streaming skips cache lookups
+        self.prerendered = true;
+    }
 
-        #[cfg(feature = "gpu")]
-        {
-            if self.gpu_enabled() {
-                let notes_per_second = rendered_count as f32 / elapsed.as_secs_f32();
-                println!(
-                    "   ✅ Pre-rendered {} notes in {:.3}s ({:.0} notes/sec)",
-                    rendered_count,
-                    elapsed.as_secs_f32(),
-                    notes_per_second
-                );
-            } else {
-                println!(
-                    "   ✅ Pre-rendered {} notes in {:.3}s",
-                    rendered_count,
-                    elapsed.as_secs_f32()
-                );
+    /// Prepare every track, bus and master effect for rendering at `sample_rate`
+    ///
+    /// Effects are constructed for 44.1 kHz; this resizes their delay lines and
+    /// reverb combs and retunes track LFOs so times in seconds stay correct at any
+    /// sample rate. `render_to_buffer`, the export functions and the audio engine call
+    /// this automatically.
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Sample rate in Hz
+    /// * `max_block_size` - Largest number of frames passed to `process_block`
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("lead").delay(Delay::new(0.25, 0.4, 0.3)).note(&[440.0], 1.0);
+    ///
+    /// let mut mixer = comp.into_mixer();
+    /// mixer.prepare(96000.0, 512);
+    /// mixer.reset();
+    /// ```
+    pub fn prepare(&mut self, sample_rate: f32, max_block_size: usize) {
+        self.sync_tempo_map();
+        for bus in self.buses.iter_mut().flatten() {
+            bus.prepare(sample_rate, max_block_size);
+        }
+        self.master.prepare(sample_rate, max_block_size);
+    }
+
+    /// Clear effect tails, filter state and the automation clock
+    ///
+    /// Call between renders so delay repeats and reverb decays from one render
+    /// do not leak into the next. `render_to_buffer` and the export functions
+    /// reset the mixer before they start.
+    pub fn reset(&mut self) {
+        for bus in self.buses.iter_mut().flatten() {
+            bus.reset();
+        }
+        self.master.reset();
+        self.sample_count = 0;
+
+        // Undo scheduled scene changes so the next render starts from the same mix
+        if let Some(base) = self.scene_base.take() {
+            self.recall_scene(&base);
+        }
+        self.scenes_settled = 0;
+    }
+
+    /// Processing latency of the whole mix in frames
+    ///
+    /// The latency of the slowest bus (including its tracks) plus the latency of the
+    /// master effects. Offline renders trim this many frames from the start of the
+    /// output so the result lines up with the composition timeline.
+    pub fn latency(&self) -> usize {
+        let bus_latency = self
+            .buses
+            .iter()
+            .flatten()
+            .map(|bus| bus.latency())
+            .max()
+            .unwrap_or(0);
+        bus_latency + self.master.latency()
+    }
+
+    /// Align every track and bus with the slowest path through the mixer
+    ///
+    /// Tracks are delayed to match the slowest track on their bus, and buses are
+    /// delayed to match the slowest bus. Delay lines are only reallocated when a
+    /// latency changes, so this is cheap to call every block.
+    fn update_delay_compensation(&mut self) {
+        let max_bus_latency = self
+            .buses
+            .iter()
+            .flatten()
+            .map(|bus| bus.latency())
+            .max()
+            .unwrap_or(0);
+
+        for bus in self.buses.iter_mut().flatten() {
+            let max_track_latency = bus
+                .tracks
+                .iter()
+                .map(|t| t.effects.latency())
+                .max()
+                .unwrap_or(0);
+            for track in &mut bus.tracks {
+                let track_latency = track.effects.latency();
+                // Tracks with spread unison render in stereo
+                let channels = if track.has_stereo_unison() { 2 } else { 1 };
+                track
+                    .delay_compensation
+                    .set(max_track_latency - track_latency, channels);
             }
+
+            let bus_latency = max_track_latency + bus.effects.latency();
+            bus.delay_compensation.set(max_bus_latency - bus_latency, 2);
         }
-        #[cfg(not(feature = "gpu"))]
-        {
-            println!(
-                "   ✅ Pre-rendered {} notes in {:.3}s",
-                rendered_count,
-                elapsed.as_secs_f32()
-            );
+    }
+
+    /// Snapshot the current mix as a named scene
+    ///
+    /// Captures the volume and pan of every track and bus, bus mute/solo, and the
+    /// parameters of every effect on tracks, buses and the master chain.
+    pub fn capture_scene(&self, name: &str) -> MixerScene {
+        let mut tracks = Vec::new();
+        let mut buses = Vec::new();
+        for bus in self.buses.iter().flatten() {
+            for track in &bus.tracks {
+                tracks.push(TrackScene {
+                    id: track.id,
+                    volume: track.volume,
+                    pan: track.pan,
+                    effects: track.capture_effect_parameters(),
+                });
+            }
+            buses.push(BusScene {
+                id: bus.id,
+                volume: bus.volume,
+                pan: bus.pan,
+                muted: bus.muted,
+                soloed: bus.soloed,
+                effects: bus.effects.capture_parameters(),
+            });
+        }
+        MixerScene {
+            name: name.to_string(),
+            tracks,
+            buses,
+            master: self.master.capture_parameters(),
         }
+    }
 
-        // Mark as pre-rendered so streaming skips cache lookups
-        self.prerendered = true;
+    /// Apply a scene to the mixer immediately
+    ///
+    /// Tracks and buses are matched by ID; anything the scene does not know about
+    /// is left unchanged.
+    pub fn recall_scene(&mut self, scene: &MixerScene) {
+        for bus in self.buses.iter_mut().flatten() {
+            if let Some(settings) = scene.buses.iter().find(|b| b.id == bus.id) {
+                bus.volume = settings.volume;
+                bus.pan = settings.pan;
+                bus.muted = settings.muted;
+                bus.soloed = settings.soloed;
+                bus.effects.apply_parameters(&settings.effects);
+            }
+            for track in &mut bus.tracks {
+                if let Some(settings) = scene.tracks.iter().find(|t| t.id == track.id) {
+                    track.volume = settings.volume;
+                    track.pan = settings.pan;
+                    track.release_modulated_effects();
+                    track.effects.apply_parameters(&settings.effects);
+                }
+            }
+        }
+        self.master.apply_parameters(&scene.master);
+    }
+
+    /// Schedule a scene change on the mixer timeline
+    ///
+    /// At `time` seconds the mix starts morphing from its current state into `scene`,
+    /// reaching it after `morph_duration` seconds (0.0 switches instantly). The morph
+    /// is applied once per processing block. A later change interrupts an unfinished
+    /// morph and continues from wherever it got to.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// # comp.track("lead").note(&[440.0], 8.0);
+    /// let mut mixer = comp.into_mixer();
+    /// let verse = mixer.capture_scene("verse");
+    ///
+    /// mixer.get_bus_mut("default").unwrap().volume = 0.2;
+    /// let breakdown = mixer.capture_scene("breakdown");
+    /// mixer.recall_scene(&verse);
+    ///
+    /// mixer.schedule_scene(4.0, &breakdown, 1.0); // Fade down over a second at 4s
+    /// mixer.schedule_scene(6.0, &verse, 0.0);     // Jump back at 6s
+    /// ```
+    pub fn schedule_scene(&mut self, time: f32, scene: &MixerScene, morph_duration: f32) {
+        let index = self.scene_changes.partition_point(|change| change.time <= time);
+        self.scene_changes.insert(
+            index,
+            SceneChange {
+                time,
+                scene: scene.clone(),
+                morph_duration: morph_duration.max(0.0),
+                mix: scene.clone(),
+            },
+        );
+        self.scenes_settled = 0;
+    }
+
+    /// Remove every scheduled scene change
+    pub fn clear_scene_schedule(&mut self) {
+        self.scene_changes.clear();
+        self.scene_base = None;
+        self.scenes_settled = 0;
+    }
+
+    /// Apply the scene timeline at `time`
+    ///
+    /// The mix is recomputed from the state captured before the first change, so
+    /// seeking and re-rendering give the same result. Once every change up to `time`
+    /// has finished morphing the mixer is left alone, leaving it free for live edits.
+    fn apply_scene_timeline(&mut self, time: f32) {
+        let active = self
+            .scene_changes
+            .partition_point(|change| change.time <= time);
+        if active == 0 {
+            return;
+        }
+
+        let last = &self.scene_changes[active - 1];
+        let settled = last.progress(time) >= 1.0;
+        if settled && self.scenes_settled == active {
+            return;
+        }
+
+        // Each change blends into its own storage; only capturing the base allocates
+        let base = match self.scene_base.take() {
+            Some(base) => base,
+            None => self.capture_scene("base"),
+        };
+        let mut changes = std::mem::take(&mut self.scene_changes);
+        for index in 0..active {
+            // An unfinished morph is frozen where the next change starts
+            let until = changes[..active]
+                .get(index + 1)
+                .map_or(time, |next| next.time);
+            let (previous, rest) = changes.split_at_mut(index);
+            let change = &mut rest[0];
+            let from = previous.last().map_or(&base, |previous| &previous.mix);
+            from.interpolate_into(&change.scene, change.progress(until), &mut change.mix);
+        }
+        self.recall_scene(&changes[active - 1].mix);
+        self.scene_changes = changes;
+        self.scene_base = Some(base);
+
+        if settled {
+            self.scenes_settled = active;
+        }
     }
 
     /// Get the total duration across all buses in seconds
@@ -780,7 +1043,8 @@ impl Mixer {
             return self;
         }
 
-        let total_duration = self.total_duration();
+        // Copies follow one another in musical time, a whole song's length of ticks apart
+        let length = self.tempo_map.ticks_at(self.total_duration() as f64);
 
         // For each bus, repeat all its track events
         for bus_opt in self.buses.iter_mut() {
@@ -793,12 +1057,12 @@ impl Mixer {
                 let original_events: Vec<_> = track.events.clone();
 
                 for i in 0..times {
-                    let offset = total_duration * (i + 1) as f32;
+                    let offset = Ticks(length.0 * (i + 1) as i64);
 
                     for event in &original_events {
                         match event {
                             AudioEvent::Note(note) => {
-                                track.add_note_with_waveform_envelope_and_bend(
+                                track.add_note_with_waveform_envelope_and_bend_ticks(
                                     &note.frequencies[..note.num_freqs],
                                     note.start_time + offset,
                                     note.duration,
@@ -808,7 +1072,7 @@ impl Mixer {
                                 );
                             }
                             AudioEvent::Drum(drum) => {
-                                track.add_drum(
+                                track.add_drum_ticks(
                                     drum.drum_type,
                                     drum.start_time + offset,
                                     drum.spatial_position,
@@ -1034,6 +1298,9 @@ impl Mixer {
     /// This is the new block-based processing API that processes multiple samples at once,
     /// significantly reducing function call overhead and enabling future optimizations.
     ///
+    /// Tracks and buses are delay-compensated for the latency their effects report, so
+    /// the output as a whole lags the timeline by [`latency`](Self::latency) frames.
+    ///
     /// # Arguments
     /// * `buffer` - Interleaved stereo buffer [L0, R0, L1, R1, ...] to fill
     /// * `sample_rate` - Sample rate in Hz
@@ -1059,6 +1326,13 @@ impl Mixer {
         // Clear envelope cache for this block
         self.envelope_cache.clear();
 
+        if !self.scene_changes.is_empty() {
+            self.apply_scene_timeline(start_time);
+        }
+
+        // Keep lookahead effects from pulling tracks and buses out of phase
+        self.update_delay_compensation();
+
         // Temporary mono buffer for track processing (will be reused)
         let track_buffer = vec![0.0f32; num_frames];
 
@@ -1102,6 +1376,8 @@ impl Mixer {
                     .map(|track| {
                         let track_id = track.id;
                         let mut track_buffer = vec![0.0f32; num_frames];
+                        let mut stereo_buffer =
+                            track.has_stereo_unison().then(|| vec![0.0f32; num_frames * 2]);
 
                         // Generate mono track audio using block processing
                         // Cache is thread-safe via Arc<Mutex>, GPU synthesizer via Arc
@@ -1115,22 +1391,26 @@ impl Mixer {
                             #[cfg(feature = "gpu")]
                             gpu_clone.as_ref(),
                             prerendered,
+                            stereo_buffer.as_deref_mut(),
                         );
+                        let output = stereo_buffer.as_deref_mut().unwrap_or(&mut track_buffer);
+                        track.delay_compensation.process(output);
 
                         // Calculate RMS envelope for this track
                         let mut sum_squares = 0.0;
-                        for &sample in track_buffer.iter() {
+                        for &sample in output.iter() {
                             sum_squares += sample * sample;
                         }
-                        let track_envelope = (sum_squares / num_frames as f32).sqrt();
+                        let track_envelope = (sum_squares / output.len() as f32).sqrt();
 
-                        (track_id, track_buffer, track_envelope, track.pan)
+                        let pan = track.modulated_pan();
+                        (track_id, track_buffer, stereo_buffer, track_envelope, pan)
                     })
                     .collect();
 
                 // Mix track results into bus buffer
                 let mut track_envelopes = Vec::new();
-                for (track_id, track_buffer, track_envelope, pan) in track_results {
+                for (track_id, track_buffer, stereo_buffer, track_envelope, pan) in track_results {
                     track_envelopes.push((track_id, track_envelope));
 
                     // Apply stereo panning and mix
@@ -1138,10 +1418,19 @@ impl Mixer {
                     let left_gain = pan_angle.cos();
                     let right_gain = pan_angle.sin();
 
-                    for (frame_idx, &mono_sample) in track_buffer.iter().enumerate() {
-                        let stereo_idx = frame_idx * 2;
-                        bus_buffer[stereo_idx] += mono_sample * left_gain;
-                        bus_buffer[stereo_idx + 1] += mono_sample * right_gain;
+                    if let Some(stereo_buffer) = stereo_buffer {
+                        // Spread unison: pan balances the track's own stereo image
+                        let frames = stereo_buffer.chunks_exact(2);
+                        for (frame, out) in frames.zip(bus_buffer.chunks_exact_mut(2)) {
+                            out[0] += frame[0] * left_gain;
+                            out[1] += frame[1] * right_gain;
+                        }
+                    } else {
+                        for (frame_idx, &mono_sample) in track_buffer.iter().enumerate() {
+                            let stereo_idx = frame_idx * 2;
+                            bus_buffer[stereo_idx] += mono_sample * left_gain;
+                            bus_buffer[stereo_idx + 1] += mono_sample * right_gain;
+                        }
                     }
                 }
 
@@ -1210,6 +1499,7 @@ impl Mixer {
                 start_sample_count,
                 sidechain_env,
             );
+            bus.delay_compensation.process(&mut bus_buffer);
 
             // Mix into output buffer
             let bus_pan_angle = (bus.pan + 1.0) * 0.25 * std::f32::consts::PI;
@@ -1259,20 +1549,28 @@ impl Mixer {
     /// If GPU synthesizer is provided, uses GPU, otherwise falls back to CPU.
     fn render_note_to_buffer(
         note: &NoteEvent,
+        length: f32,
         sample_rate: f32,
         #[cfg(feature = "gpu")] gpu_synthesizer: Option<&Arc<GpuSynthesizer>>,
     ) -> Vec<f32> {
-        // Try GPU first if available
+        // Try GPU first if available (the GPU renders plain single voices only,
+        // without FM patches, physical models or resonators)
+        #[cfg(feature = "gpu")]
+        let plain = !note.unison.is_active()
+            && note.voice.is_default()
+            && note.fm_patch.is_none()
+            && note.physical_model.is_none()
+            && note.resonator.is_none();
         #[cfg(feature = "gpu")]
-        if let Some(gpu) = gpu_synthesizer {
-            if let Ok(samples) = gpu.synthesize_note(note, sample_rate) {
+        if let Some(gpu) = gpu_synthesizer.filter(|_| plain) {
+            if let Ok(samples) = gpu.synthesize_note(note, length, sample_rate) {
                 return samples;
             }
             // GPU failed, fall through to CPU
         }
 
         // CPU synthesis fallback
-        let total_duration = note.envelope.total_duration(note.duration);
+        let total_duration = note.total_duration(length);
         let num_samples = (total_duration * sample_rate) as usize;
         let mut buffer = vec![0.0f32; num_samples];
 
@@ -1280,34 +1578,15 @@ impl Mixer {
 
         for (i, sample_out) in buffer.iter_mut().enumerate() {
             let time_in_note = i as f32 * time_delta;
-            let envelope_amp = note.envelope.amplitude_at(time_in_note, note.duration);
+            let envelope_amp = note.amplitude_at(time_in_note, length);
 
             let mut note_value = 0.0;
 
             // Synthesize for the first frequency only (monophonic cache)
             // Polyphonic notes will be handled separately
+            // Notes with spread unison are never cached, so the side channel can be dropped
             if note.num_freqs > 0 {
-                let base_freq = note.frequencies[0];
-
-                let freq = if note.pitch_bend_semitones != 0.0 {
-                    let bend_progress = (time_in_note / note.duration).min(1.0);
-                    let bend_multiplier =
-                        2.0f32.powf((note.pitch_bend_semitones * bend_progress) / 12.0);
-                    base_freq * bend_multiplier
-                } else {
-                    base_freq
-                };
-
-                let sample = if note.fm_params.mod_index > 0.0 {
-                    note.fm_params.sample(freq, time_in_note, note.duration)
-                } else if let Some(ref wavetable) = note.custom_wavetable {
-                    let phase = (time_in_note * freq) % 1.0;
-                    wavetable.sample(phase)
-                } else {
-                    let phase = (time_in_note * freq) % 1.0;
-                    note.waveform.sample(phase)
-                };
-
+                let (sample, _) = note.sample_frequency(0, time_in_note, length, sample_rate);
                 note_value += sample * envelope_amp;
             }
 
@@ -1331,6 +1610,10 @@ impl Mixer {
     /// * `cache` - Optional sample cache for pre-rendered synthesis
     /// * `gpu_synthesizer` - Optional GPU synthesizer for 500-1000x faster rendering
     /// * `prerendered` - If true, skip cache-miss detection (already pre-rendered)
+    /// * `stereo` - For tracks with spread unison: an interleaved stereo buffer of twice
+    ///   the length of `buffer`, which then receives the track's output from the stereo
+    ///   path of its effect chain (`buffer` is left holding the unprocessed mid signal)
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn process_track_block(
         track: &mut Track,
         buffer: &mut [f32],
@@ -1340,13 +1623,21 @@ impl Mixer {
         cache: Option<&Arc<Mutex<SampleCache>>>,
         #[cfg(feature = "gpu")] gpu_synthesizer: Option<&Arc<GpuSynthesizer>>,
         prerendered: bool,
+        mut stereo: Option<&mut [f32]>,
     ) {
         // Clear output buffer
         buffer.fill(0.0);
+        if let Some(stereo) = stereo.as_deref_mut() {
+            stereo.fill(0.0);
+        }
 
         // Ensure events are sorted by start_time for binary search
         track.ensure_sorted();
 
+        // Notes the mod matrix reads or changes are synthesized as they play, never cached
+        track.ensure_modulation(sample_rate);
+        let uncached_notes = track.modulation_reads_notes();
+
         let track_start = track.start_time();
         let track_end = track.end_time();
         let time_delta = 1.0 / sample_rate;
@@ -1354,10 +1645,7 @@ impl Mixer {
         let block_end_time = start_time + block_duration;
 
         // Skip track entirely if we're completely outside its active range
-        if (block_end_time < track_start || start_time > track_end)
-            && track.effects.delay.is_none()
-            && track.effects.reverb.is_none()
-        {
+        if (block_end_time < track_start || start_time > track_end) && !track.effects.has_tail() {
             return;
         }
 
@@ -1371,22 +1659,28 @@ impl Mixer {
             let cache_arc = cache.unwrap();
             // Handle poisoned mutex gracefully (don't panic in audio thread)
             let mut cache_lock = cache_arc.lock().unwrap_or_else(|e| e.into_inner());
-            for event in &track.events[start_idx..end_idx] {
+            let events = track.events[start_idx..end_idx].iter();
+            for (event, time) in events.zip(&track.timeline[start_idx..end_idx]) {
                 if let AudioEvent::Note(note_event) = event {
+                    if uncached_notes || note_event.unison.is_stereo() {
+                        continue;
+                    }
+
                     // Compute cache key for this note
-                    let cache_key = CacheKey::from_note_event(note_event, sample_rate);
+                    let cache_key = CacheKey::from_note_event(note_event, time.length, sample_rate);
 
                     // Check if we have this note cached
                     if cache_lock.get(&cache_key).is_none() {
                         // Cache miss - render the full note and store it
                         let total_duration =
-                            note_event.envelope.total_duration(note_event.duration);
+                            note_event.total_duration(time.length);
 
                         // Only cache notes with reasonable duration
                         if total_duration > 0.0 && total_duration < 10.0 {
                             // Render the complete note (GPU if available, otherwise CPU)
                             let rendered_samples = Self::render_note_to_buffer(
                                 note_event,
+                                time.length,
                                 sample_rate,
                                 #[cfg(feature = "gpu")]
                                 gpu_synthesizer,
@@ -1412,7 +1706,7 @@ impl Mixer {
         if prerendered && cache.is_some() {
             // All notes are pre-rendered, mark ALL NoteEvents as cached
             for (idx, event) in track.events[start_idx..end_idx].iter().enumerate() {
-                if let AudioEvent::Note(_) = event {
+                if matches!(event, AudioEvent::Note(n) if !uncached_notes && !n.unison.is_stereo()) {
                     cached_note_indices.insert(start_idx + idx);
                 }
             }
@@ -1422,7 +1716,11 @@ impl Mixer {
             let mut cache_lock = cache_arc.lock().unwrap_or_else(|e| e.into_inner());
             for (idx, event) in track.events[start_idx..end_idx].iter().enumerate() {
                 if let AudioEvent::Note(note_event) = event {
-                    let cache_key = CacheKey::from_note_event(note_event, sample_rate);
+                    if uncached_notes || note_event.unison.is_stereo() {
+                        continue;
+                    }
+                    let length = track.timeline[start_idx + idx].length;
+                    let cache_key = CacheKey::from_note_event(note_event, length, sample_rate);
                     if cache_lock.get(&cache_key).is_some() {
                         cached_note_indices.insert(start_idx + idx);
                     }
@@ -1435,13 +1733,17 @@ impl Mixer {
         if let Some(cache_arc) = cache {
             // Handle poisoned mutex gracefully (don't panic in audio thread)
             let mut cache_lock = cache_arc.lock().unwrap_or_else(|e| e.into_inner());
-            for (_idx, event) in track.events[start_idx..end_idx].iter().enumerate() {
+            let events = track.events[start_idx..end_idx].iter();
+            for (event, time) in events.zip(&track.timeline[start_idx..end_idx]) {
                 if let AudioEvent::Note(note_event) = event {
-                    let cache_key = CacheKey::from_note_event(note_event, sample_rate);
+                    if uncached_notes || note_event.unison.is_stereo() {
+                        continue;
+                    }
+                    let cache_key = CacheKey::from_note_event(note_event, time.length, sample_rate);
 
                     if let Some(cached_sample) = cache_lock.get(&cache_key) {
                         // Cache hit! Use the cached sample
-                        let note_start = note_event.start_time;
+                        let note_start = time.start;
                         let note_end = note_start + cached_sample.duration;
 
                         // Skip if note doesn't overlap with current block
@@ -1488,11 +1790,12 @@ impl Mixer {
         // Pre-render sample events with SIMD for better performance
         // This processes whole blocks instead of per-sample, enabling vectorization
         let mut sample_buffer = vec![0.0f32; buffer.len()];
-        for event in &track.events[start_idx..end_idx] {
+        let events = track.events[start_idx..end_idx].iter();
+        for (event, event_time) in events.zip(&track.timeline[start_idx..end_idx]) {
             if let AudioEvent::Sample(sample_event) = event {
                 sample_event.sample.fill_buffer_simd_mono(
                     &mut sample_buffer,
-                    sample_event.start_time,
+                    event_time.start,
                     start_time,
                     time_delta,
                     sample_event.playback_rate,
@@ -1501,14 +1804,25 @@ impl Mixer {
             }
         }
 
+        // The side channel shares the track filter's settings but keeps its own state
+        if stereo.is_some() {
+            track.sync_side_filter();
+        }
+
         // For each sample in the block
         for (i, sample_out) in buffer.iter_mut().enumerate() {
             let time = start_time + (i as f32 * time_delta);
             let mut track_value = 0.0;
+            let mut side_value = 0.0;
+
+            if let Some(modulation) = track.modulation_state.as_mut() {
+                modulation.tick(time, sample_rate);
+            }
 
             // Process events (reuse binary search result for entire block)
             for (relative_idx, event) in track.events[start_idx..end_idx].iter().enumerate() {
                 let absolute_idx = start_idx + relative_idx;
+                let event_time = track.timeline[absolute_idx];
 
                 match event {
                     AudioEvent::Note(note_event) => {
@@ -1519,54 +1833,41 @@ impl Mixer {
                             continue;
                         }
 
+                        let length = event_time.length;
                         let total_duration =
-                            note_event.envelope.total_duration(note_event.duration);
-                        let note_end_with_release = note_event.start_time + total_duration;
-
-                        if time >= note_event.start_time && time < note_end_with_release {
-                            let time_in_note = time - note_event.start_time;
-                            let envelope_amp = note_event
-                                .envelope
-                                .amplitude_at(time_in_note, note_event.duration);
+                            note_event.total_duration(length);
+                        let note_end_with_release = event_time.start + total_duration;
+
+                        if time >= event_time.start && time < note_end_with_release {
+                            let time_in_note = time - event_time.start;
+                            let envelope_amp = note_event.amplitude_at(time_in_note, length);
+                            let (clock, note_modulation) = match track.modulation_state.as_mut() {
+                                Some(modulation) => {
+                                    modulation.note(absolute_idx, note_event, time_in_note, length)
+                                }
+                                None => (None, NoteModulation::default()),
+                            };
 
                             for freq_idx in 0..note_event.num_freqs {
-                                let base_freq = note_event.frequencies[freq_idx];
-
-                                let freq = if note_event.pitch_bend_semitones != 0.0 {
-                                    let bend_progress =
-                                        (time_in_note / note_event.duration).min(1.0);
-                                    let bend_multiplier = 2.0f32.powf(
-                                        (note_event.pitch_bend_semitones * bend_progress) / 12.0,
-                                    );
-                                    base_freq * bend_multiplier
-                                } else {
-                                    base_freq
-                                };
-
-                                let sample = if note_event.fm_params.mod_index > 0.0 {
-                                    note_event.fm_params.sample(
-                                        freq,
-                                        time_in_note,
-                                        note_event.duration,
-                                    )
-                                } else if let Some(ref wavetable) = note_event.custom_wavetable {
-                                    let phase = (time_in_note * freq) % 1.0;
-                                    wavetable.sample(phase)
-                                } else {
-                                    let phase = (time_in_note * freq) % 1.0;
-                                    note_event.waveform.sample(phase)
-                                };
-
-                                track_value += sample * envelope_amp;
+                                let (mid, side) = note_event.sample_modulated(
+                                    freq_idx,
+                                    time_in_note,
+                                    length,
+                                    clock,
+                                    &note_modulation,
+                                    sample_rate,
+                                );
+                                track_value += mid * envelope_amp;
+                                side_value += side * envelope_amp;
                             }
                         }
                     }
                     AudioEvent::Drum(drum_event) => {
                         let drum_duration = drum_event.drum_type.duration();
-                        if time >= drum_event.start_time
-                            && time < drum_event.start_time + drum_duration
+                        if time >= event_time.start
+                            && time < event_time.start + drum_duration
                         {
-                            let time_in_drum = time - drum_event.start_time;
+                            let time_in_drum = time - event_time.start;
                             let sample_index = (time_in_drum * sample_rate) as usize;
                             track_value += drum_event.drum_type.sample(sample_index, sample_rate);
                         }
@@ -1585,19 +1886,51 @@ impl Mixer {
             // Add cached notes (if cache is enabled)
             track_value += cached_notes_buffer[i];
 
+            let track_modulation = match track.modulation_state.as_mut() {
+                Some(modulation) => modulation.finish(track_value),
+                None => TrackModulation::default(),
+            };
+            let volume = track.volume * (1.0 + track_modulation.volume).max(0.0);
+
             // Apply track volume
-            track_value *= track.volume;
+            track_value *= volume;
 
             // Apply filter (per-sample, maintains state)
-            track_value = track.filter.process(track_value, sample_rate);
+            track_value =
+                modulation::process_filter(&mut track.filter, track_value, sample_rate, &track_modulation);
 
             *sample_out = track_value;
+
+            // Spread unison: left gets mid - side, right mid + side
+            if let Some(stereo) = stereo.as_deref_mut() {
+                let side_value = modulation::process_filter(
+                    &mut track.side_filter,
+                    side_value * volume,
+                    sample_rate,
+                    &track_modulation,
+                );
+                stereo[i * 2] = track_value - side_value;
+                stereo[i * 2 + 1] = track_value + side_value;
+            }
         }
 
         // Apply effects to entire buffer (block processing!)
-        track
-            .effects
-            .process_mono_block(buffer, sample_rate, start_time, start_sample_count);
+        let process = |effects: &mut EffectChain| match stereo {
+            Some(stereo) => effects.process_stereo_block(
+                stereo,
+                sample_rate,
+                start_time,
+                start_sample_count,
+                None,
+            ),
+            None => effects.process_mono_block(buffer, sample_rate, start_time, start_sample_count),
+        };
+
+        // Modulated effect parameters hold their end-of-block values for the whole block
+        match track.modulation_state.as_mut() {
+            Some(modulation) => modulation.process_effects(&mut track.effects, process),
+            None => process(&mut track.effects),
+        }
     }
 
     /// Process a single track and return its stereo output (static version)
@@ -1612,79 +1945,74 @@ impl Mixer {
     ) -> (f32, f32) {
         // Ensure events are sorted by start_time for binary search
         track.ensure_sorted();
+        track.ensure_modulation(sample_rate);
 
         // Quick time-bounds check: skip entire track if current time is outside its active range
         let track_start = track.start_time();
         let track_end = track.end_time();
 
         // Skip track entirely if we're before it starts or after it ends
-        if (time < track_start || time > track_end)
-            && track.effects.delay.is_none()
-            && track.effects.reverb.is_none()
-        {
+        if (time < track_start || time > track_end) && !track.effects.has_tail() {
             return (0.0, 0.0);
         }
 
         let mut track_value = 0.0;
+        let mut side_value = 0.0;
         let mut has_active_event = false;
 
         // Binary search to find potentially active events
         let (start_idx, end_idx) = track.find_active_range(time);
 
+        if let Some(modulation) = track.modulation_state.as_mut() {
+            modulation.tick(time, sample_rate);
+        }
+
         // Process events
-        for event in &track.events[start_idx..end_idx] {
+        for (relative_idx, event) in track.events[start_idx..end_idx].iter().enumerate() {
+            let absolute_idx = start_idx + relative_idx;
+            let event_time = track.timeline[absolute_idx];
             match event {
                 AudioEvent::Note(note_event) => {
-                    let total_duration = note_event.envelope.total_duration(note_event.duration);
-                    let note_end_with_release = note_event.start_time + total_duration;
+                    let length = event_time.length;
+                    let total_duration = note_event.total_duration(length);
+                    let note_end_with_release = event_time.start + total_duration;
 
-                    if time >= note_event.start_time && time < note_end_with_release {
+                    if time >= event_time.start && time < note_end_with_release {
                         has_active_event = true;
-                        let time_in_note = time - note_event.start_time;
-                        let envelope_amp = note_event
-                            .envelope
-                            .amplitude_at(time_in_note, note_event.duration);
+                        let time_in_note = time - event_time.start;
+                        let envelope_amp = note_event.amplitude_at(time_in_note, length);
+                        let (clock, note_modulation) = match track.modulation_state.as_mut() {
+                            Some(modulation) => {
+                                modulation.note(absolute_idx, note_event, time_in_note, length)
+                            }
+                            None => (None, NoteModulation::default()),
+                        };
 
                         for i in 0..note_event.num_freqs {
-                            let base_freq = note_event.frequencies[i];
-
-                            let freq = if note_event.pitch_bend_semitones != 0.0 {
-                                let bend_progress = (time_in_note / note_event.duration).min(1.0);
-                                let bend_multiplier = 2.0f32
-                                    .powf((note_event.pitch_bend_semitones * bend_progress) / 12.0);
-                                base_freq * bend_multiplier
-                            } else {
-                                base_freq
-                            };
-
-                            let sample = if note_event.fm_params.mod_index > 0.0 {
-                                note_event
-                                    .fm_params
-                                    .sample(freq, time_in_note, note_event.duration)
-                            } else if let Some(ref wavetable) = note_event.custom_wavetable {
-                                let phase = (time_in_note * freq) % 1.0;
-                                wavetable.sample(phase)
-                            } else {
-                                let phase = (time_in_note * freq) % 1.0;
-                                note_event.waveform.sample(phase)
-                            };
-
-                            track_value += sample * envelope_amp;
+                            let (mid, side) = note_event.sample_modulated(
+                                i,
+                                time_in_note,
+                                length,
+                                clock,
+                                &note_modulation,
+                                sample_rate,
+                            );
+                            track_value += mid * envelope_amp;
+                            side_value += side * envelope_amp;
                         }
                     }
                 }
                 AudioEvent::Drum(drum_event) => {
                     let drum_duration = drum_event.drum_type.duration();
-                    if time >= drum_event.start_time && time < drum_event.start_time + drum_duration
-                    {
+                    if time >= event_time.start && time < event_time.start + drum_duration {
                         has_active_event = true;
-                        let time_in_drum = time - drum_event.start_time;
+                        let time_in_drum = time - event_time.start;
                         let sample_index = (time_in_drum * sample_rate) as usize;
                         track_value += drum_event.drum_type.sample(sample_index, sample_rate);
                     }
                 }
                 AudioEvent::Sample(sample_event) => {
-                    let time_in_sample = time - sample_event.start_time;
+                    let time_in_sample = time - event_time.start;
                     let sample_duration = sample_event.sample.duration / sample_event.playback_rate;
 
                     if time_in_sample >= 0.0 && time_in_sample < sample_duration {
@@ -1700,27 +2028,63 @@ impl Mixer {
         }
 
         // Skip effect processing if track has no active events and no tail effects
-        if !has_active_event && track.effects.delay.is_none() && track.effects.reverb.is_none() {
+        if !has_active_event && !track.effects.has_tail() {
             return (0.0, 0.0);
         }
 
+        let track_modulation = match track.modulation_state.as_mut() {
+            Some(modulation) => modulation.finish(track_value),
+            None => TrackModulation::default(),
+        };
+        let volume = track.volume * (1.0 + track_modulation.volume).max(0.0);
+
         // Apply track volume
-        track_value *= track.volume;
+        track_value *= volume;
 
         // Apply filter
-        track_value = track.filter.process(track_value, sample_rate);
+        track_value =
+            modulation::process_filter(&mut track.filter, track_value, sample_rate, &track_modulation);
+
+        // Spread unison renders in stereo: left gets mid - side, right mid + side.
+        // The side channel shares the filter settings and goes through the effects too.
+        let stereo = track.has_stereo_unison();
+        if stereo {
+            track.sync_side_filter();
+            side_value = modulation::process_filter(
+                &mut track.side_filter,
+                side_value * volume,
+                sample_rate,
+                &track_modulation,
+            );
+        }
 
         // Apply effects through the unified effect chain
-        track_value = track
-            .effects
-            .process_mono(track_value, sample_rate, time, sample_count);
+        let process = |effects: &mut EffectChain| {
+            if stereo {
+                effects.process_stereo(
+                    track_value - side_value,
+                    track_value + side_value,
+                    sample_rate,
+                    time,
+                    sample_count,
+                    None,
+                )
+            } else {
+                let mono = effects.process_mono(track_value, sample_rate, time, sample_count);
+                (mono, mono)
+            }
+        };
+        let (left, right) = match track.modulation_state.as_mut() {
+            Some(modulation) => modulation.process_effects(&mut track.effects, process),
+            None => process(&mut track.effects),
+        };
 
         // Apply stereo panning using constant power panning
-        let pan_angle = (track.pan + 1.0) * 0.25 * std::f32::consts::PI;
+        let pan_angle = (track.modulated_pan() + 1.0) * 0.25 * std::f32::consts::PI;
         let left_gain = pan_angle.cos();
         let right_gain = pan_angle.sin();
 
-        (track_value * left_gain, track_value * right_gain)
+        (left * left_gain, right * right_gain)
     }
 
     /// Render the mixer to an in-memory stereo buffer
@@ -1728,15 +2092,101 @@ impl Mixer {
     /// Pre-renders the entire composition to a Vec of interleaved stereo samples (left, right, left, right...).
     /// This is used for efficient playback without real-time synthesis overhead.
     ///
+    /// For long renders, prefer [`render_blocks`](Self::render_blocks), which never holds
+    /// more than one block in memory.
+    ///
     /// # Arguments
     /// * `sample_rate` - Sample rate in Hz
     ///
     /// # Returns
     /// A Vec of f32 samples in interleaved stereo format (left, right, left, right...)
     pub fn render_to_buffer(&mut self, sample_rate: f32) -> Vec<f32> {
-        let duration = self.total_duration();
-        let total_samples = (duration * sample_rate).ceil() as usize;
+        let total_samples = (self.total_duration() * sample_rate).ceil() as usize;
+        let mut buffer = Vec::with_capacity(total_samples * 2);
+        let result: Result<(), std::convert::Infallible> =
+            self.render_blocks(sample_rate, |block| {
+                buffer.extend_from_slice(block);
+                Ok(())
+            });
+        match result {
+            Ok(()) => buffer,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Measure the loudness of the mix
+    ///
+    /// Renders the composition block by block through a [`LoudnessMeter`], so nothing
+    /// is held in memory. Use the result to check a mix against delivery specs, or
+    /// export with [`ExportOptions::with_normalization`](super::ExportOptions::with_normalization)
+    /// to hit a target automatically.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// comp.track("lead").notes(&[C4, E4, G4, C5], 0.5);
+    /// let mut mixer = comp.into_mixer();
+    ///
+    /// let loudness = mixer.loudness(44100);
+    /// println!("{:.1} LUFS integrated, {:.1} dBTP", loudness.integrated, loudness.true_peak);
+    /// ```
+    pub fn loudness(&mut self, sample_rate: u32) -> Loudness {
+        let mut meter = LoudnessMeter::new(sample_rate, 2);
+        let result: Result<(), std::convert::Infallible> =
+            self.render_blocks(sample_rate as f32, |block| {
+                meter.process(block);
+                Ok(())
+            });
+        match result {
+            Ok(()) => meter.loudness(),
+            Err(never) => match never {},
+        }
+    }
+
+    /// Render the mixer block by block, handing each block to a callback
+    ///
+    /// Produces exactly the same samples as [`render_to_buffer`](Self::render_to_buffer),
+    /// but only one block is held in memory at a time, so hour-long renders can be
+    /// streamed straight into an encoder. Each block is interleaved stereo and already
+    /// trimmed for the mixer's [`latency`](Self::latency). Samples are not clamped, so a
+    /// hot mix can go past -1.0..=1.0; integer exports clip them when quantizing.
+    /// Blocks hold at most [`RENDER_BLOCK_SIZE`](Self::RENDER_BLOCK_SIZE) frames.
+    ///
+    /// Returning an error from `write` stops the render and passes the error through.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::prelude::*;
+    /// # let mut comp = Composition::new(Tempo::new(120.0));
+    /// # comp.track("lead").note(&[440.0], 1.0);
+    /// let mut mixer = comp.into_mixer();
+    ///
+    /// let mut peak = 0.0f32;
+    /// mixer.render_blocks(44100.0, |block| {
+    ///     peak = block.iter().fold(peak, |p, s| p.max(s.abs()));
+    ///     Ok::<(), std::io::Error>(())
+    /// })?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn render_blocks<E>(
+        &mut self,
+        sample_rate: f32,
+        write: impl FnMut(&[f32]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let total_samples = (self.total_duration() * sample_rate).ceil() as usize;
+        self.render_frames(sample_rate, total_samples, write)
+    }
 
+    /// Render `total_samples` frames in blocks, like [`render_blocks`](Self::render_blocks)
+    ///
+    /// The count may run past the end of the composition to capture effect tails.
+    pub(super) fn render_frames<E>(
+        &mut self,
+        sample_rate: f32,
+        total_samples: usize,
+        mut write: impl FnMut(&[f32]) -> Result<(), E>,
+    ) -> Result<(), E> {
         // 🚀 KEY OPTIMIZATION: Pre-render all unique notes before streaming
         // This eliminates per-block cache lookups and unleashes GPU performance!
         let should_prerender = self.cache.is_some() || {
@@ -1754,40 +2204,47 @@ impl Mixer {
             self.prerender_notes(sample_rate);
         }
 
-        // Pre-allocate buffer for interleaved stereo (2 channels)
-        let mut buffer = vec![0.0; total_samples * 2];
+        // Size effects for this sample rate and start from silence
+        self.prepare(sample_rate, Self::RENDER_BLOCK_SIZE);
+        self.reset();
 
-        // Process in blocks of 512 samples for better performance
-        const BLOCK_SIZE: usize = 512;
+        // Render extra frames to cover the processing latency, then skip them at the start
+        let latency = self.latency();
+        let render_samples = total_samples + latency;
+
+        // One reusable interleaved stereo block
+        let mut buffer = vec![0.0; Self::RENDER_BLOCK_SIZE * 2];
         let mut processed_samples = 0;
 
-        while processed_samples < total_samples {
-            let remaining = total_samples - processed_samples;
-            let block_samples = remaining.min(BLOCK_SIZE);
-            let block_frames = block_samples * 2; // stereo
+        while processed_samples < render_samples {
+            let remaining = render_samples - processed_samples;
+            let block_samples = remaining.min(Self::RENDER_BLOCK_SIZE);
+            let block = &mut buffer[..block_samples * 2];
 
             let start_time = processed_samples as f32 / sample_rate;
-            let start_idx = processed_samples * 2;
-            let end_idx = start_idx + block_frames;
 
             // Process this block
-            self.process_block(
-                &mut buffer[start_idx..end_idx],
-                sample_rate,
-                start_time,
-                None,
-                None,
-            );
+            self.process_block(block, sample_rate, start_time, None, None);
 
+            let skip = latency.saturating_sub(processed_samples).min(block_samples);
             processed_samples += block_samples;
+            if skip < block_samples {
+                if let Err(error) = write(&block[skip * 2..]) {
+                    self.release_modulated_effects();
+                    return Err(error);
+                }
+            }
         }
 
-        // Clamp to valid range
-        for sample in &mut buffer {
-            *sample = sample.clamp(-1.0, 1.0);
-        }
+        self.release_modulated_effects();
+        Ok(())
+    }
 
-        buffer
+    /// Put back the effect parameters each track's mod matrix has been modulating
+    fn release_modulated_effects(&mut self) {
+        for track in self.buses.iter_mut().flatten().flat_map(|bus| &mut bus.tracks) {
+            track.release_modulated_effects();
+        }
     }
 
     /// Add a compressor to the master output
@@ -2126,6 +2583,26 @@ impl Mixer {
         self.master.autopan = Some(autopan);
         self.master.compute_effect_order();
     }
+
+    /// Append any effect to the master insert list
+    ///
+    /// Master inserts run after the built-in master effects, in the order they are
+    /// added. Use this for user-defined [`Effect`](crate::synthesis::effects::Effect)s or
+    /// for additional instances of built-in effects.
+    ///
+    /// # Example
+    /// ```
+    /// # use tunes::composition::Composition;
+    /// # use tunes::composition::timing::Tempo;
+    /// # use tunes::synthesis::effects::{Limiter, Saturation};
+    /// let mut comp = Composition::new(Tempo::new(120.0));
+    /// let mut mixer = comp.into_mixer();
+    /// mixer.master_effect(Saturation::new(1.5, 0.3, 0.5));
+    /// mixer.master_effect(Limiter::new(0.95, 0.05));
+    /// ```
+    pub fn master_effect<E: crate::synthesis::effects::Effect + 'static>(&mut self, effect: E) {
+        self.master.add_effect(effect);
+    }
 }
 
 impl Default for Mixer {
@@ -24,7 +24,7 @@
 //! - Volume and pan (stereo positioning)
 //! - Filter (low-pass, high-pass, band-pass, etc.)
 //! - Effects (reverb, delay, distortion, chorus, etc.)
-//! - Modulation routes (LFO modulation of parameters)
+//! - Modulation routes (LFO modulation of parameters) and a mod matrix
 //!
 //! # Effect Processing Order
 //!
@@ -85,6 +85,15 @@ mod track;
 mod bus;
 mod mixer;
 mod export;
+mod export_format;
+mod latency;
+mod looping;
+mod metadata;
+mod modulation;
+mod scene;
+mod stems;
+mod vorbis;
+mod voices;
 pub mod ids;
 
 // Re-export public types
@@ -92,22 +101,35 @@ pub use events::*;
 pub use track::Track;
 pub use bus::{Bus, BusBuilder};
 pub use mixer::Mixer;
+pub use scene::MixerScene;
+pub use metadata::{Marker, Metadata};
+pub use looping::{LoopOptions, LoopRender, LoopSeam};
+pub use stems::{StemGroup, StemInfo, StemManifest, StemOptions, StemSplit};
+pub use export::ExportProgress;
+pub use export_format::{BitDepth, Channels, Dither, ExportOptions, Normalization, OggOptions};
 pub use ids::{BusId, TrackId, BusIdGenerator, TrackIdGenerator};
+pub use voices::{NotePriority, Retrigger, VoiceMode, VoiceStealing, Voicing, VOICE_STEAL_FADE};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::consts::notes::*;
     use crate::instruments::drums::DrumType;
-    use crate::composition::timing::Tempo;
+    use crate::composition::timing::{Tempo, TempoMap, Ticks};
+    use crate::composition::timing::ticks::at;
+    use crate::synthesis::effects::{Delay, EffectChain};
+    use crate::synthesis::{
+        Automation, Envelope, FMParams, FilterEnvelope, LFO, MatrixRoute, ModDestination,
+        ModMatrix, ModRoute, ModSource, ModTarget, Unison, Waveform,
+    };
 
     #[test]
     fn test_note_event_construction() {
         let freqs = [440.0, 554.37]; // A4 and C#5
-        let note = NoteEvent::new(&freqs, 0.0, 1.0);
+        let note = NoteEvent::new(&freqs, at(0.0), at(1.0));
 
-        assert_eq!(note.start_time, 0.0);
-        assert_eq!(note.duration, 1.0);
+        assert_eq!(note.start_time, at(0.0));
+        assert_eq!(note.duration, at(1.0));
         assert_eq!(note.num_freqs, 2);
         assert_eq!(note.frequencies[0], 440.0);
         assert_eq!(note.frequencies[1], 554.37);
@@ -121,7 +143,7 @@ mod tests {
         let freqs = [
             100.0, 200.0, 300.0, 400.0, 500.0, 600.0, 700.0, 800.0, 900.0, 1000.0,
         ];
-        let note = NoteEvent::new(&freqs, 0.0, 1.0);
+        let note = NoteEvent::new(&freqs, at(0.0), at(1.0));
 
         assert_eq!(note.num_freqs, 8, "Should truncate to max 8 frequencies");
         assert_eq!(
@@ -133,7 +155,7 @@ mod tests {
     #[test]
     fn test_note_event_empty_frequencies() {
         // Test handling of empty frequency array
-        let note = NoteEvent::new(&[], 0.0, 1.0);
+        let note = NoteEvent::new(&[], at(0.0), at(1.0));
 
         assert_eq!(note.num_freqs, 0);
         assert_eq!(note.frequencies[0], 0.0);
@@ -161,8 +183,8 @@ mod tests {
         match &track.events[0] {
             AudioEvent::Note(note) => {
                 assert_eq!(note.frequencies[0], 440.0);
-                assert_eq!(note.start_time, 0.0);
-                assert_eq!(note.duration, 1.0);
+                assert_eq!(note.start_time, at(0.0));
+                assert_eq!(note.duration, at(1.0));
             }
             _ => panic!("Expected NoteEvent"),
         }
@@ -179,7 +201,7 @@ mod tests {
         match &track.events[0] {
             AudioEvent::Drum(drum) => {
                 assert!(matches!(drum.drum_type, DrumType::Kick));
-                assert_eq!(drum.start_time, 0.0);
+                assert_eq!(drum.start_time, at(0.0));
             }
             _ => panic!("Expected DrumEvent"),
         }
@@ -236,13 +258,13 @@ mod tests {
 
         // Verify timing
         if let AudioEvent::Note(note) = &track.events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &track.events[1] {
-            assert_eq!(note.start_time, 0.5);
+            assert_eq!(note.start_time, at(0.5));
         }
         if let AudioEvent::Note(note) = &track.events[2] {
-            assert_eq!(note.start_time, 1.0);
+            assert_eq!(note.start_time, at(1.0));
         }
     }
 
@@ -310,13 +332,13 @@ mod tests {
 
         // Verify timing offsets
         if let AudioEvent::Note(note) = &repeated_mixer.tracks()[0].events[0] {
-            assert_eq!(note.start_time, 0.0);
+            assert_eq!(note.start_time, at(0.0));
         }
         if let AudioEvent::Note(note) = &repeated_mixer.tracks()[0].events[1] {
-            assert_eq!(note.start_time, 1.0); // Original duration offset
+            assert_eq!(note.start_time, at(1.0)); // Original duration offset
         }
         if let AudioEvent::Note(note) = &repeated_mixer.tracks()[0].events[2] {
-            assert_eq!(note.start_time, 2.0); // 2x original duration
+            assert_eq!(note.start_time, at(2.0)); // 2x original duration
         }
     }
 
@@ -336,19 +358,15 @@ mod tests {
 
     #[test]
     fn test_drum_event_construction() {
-        let drum = DrumEvent {
-            drum_type: DrumType::Snare,
-            start_time: 0.5,
-            spatial_position: None,
-        };
+        let drum = DrumEvent::new(DrumType::Snare, at(0.5));
 
         assert!(matches!(drum.drum_type, DrumType::Snare));
-        assert_eq!(drum.start_time, 0.5);
+        assert_eq!(drum.start_time, at(0.5));
     }
 
     #[test]
     fn test_audio_event_enum() {
-        let note = NoteEvent::new(&[440.0], 0.0, 1.0);
+        let note = NoteEvent::new(&[440.0], at(0.0), at(1.0));
         let note_event = AudioEvent::Note(note);
 
         match note_event {
@@ -356,15 +374,522 @@ mod tests {
             _ => panic!("Expected Note variant"),
         }
 
-        let drum_event = AudioEvent::Drum(DrumEvent {
-            drum_type: DrumType::Kick,
-            start_time: 0.0,
-            spatial_position: None,
-        });
+        let drum_event = AudioEvent::Drum(DrumEvent::new(DrumType::Kick, at(0.0)));
 
         match drum_event {
             AudioEvent::Drum(d) => assert!(matches!(d.drum_type, DrumType::Kick)),
             _ => panic!("Expected Drum variant"),
         }
     }
+
+    /// Test effect that delays its input and reports the delay as latency
+    #[derive(Debug, Clone)]
+    struct Lookahead {
+        // One delay line per channel
+        lines: [std::collections::VecDeque<f32>; 2],
+    }
+
+    impl Lookahead {
+        fn new(samples: usize) -> Self {
+            let line: std::collections::VecDeque<f32> = std::iter::repeat_n(0.0, samples).collect();
+            Self {
+                lines: [line.clone(), line],
+            }
+        }
+
+        fn delay(line: &mut std::collections::VecDeque<f32>, buffer: &mut [f32]) {
+            for sample in buffer.iter_mut() {
+                line.push_back(*sample);
+                *sample = line.pop_front().unwrap();
+            }
+        }
+    }
+
+    impl crate::synthesis::effects::Effect for Lookahead {
+        fn process_block(&mut self, buffer: &mut [f32], _sample_rate: f32, _time: f32, _sample_count: u64) {
+            Self::delay(&mut self.lines[0], buffer);
+        }
+
+        fn process_stereo_block(
+            &mut self,
+            left: &mut [f32],
+            right: &mut [f32],
+            _sample_rate: f32,
+            _time: f32,
+            _sample_count: u64,
+        ) {
+            Self::delay(&mut self.lines[0], left);
+            Self::delay(&mut self.lines[1], right);
+        }
+
+        fn reset(&mut self) {
+            for line in &mut self.lines {
+                line.iter_mut().for_each(|s| *s = 0.0);
+            }
+        }
+
+        fn latency(&self) -> usize {
+            self.lines[0].len()
+        }
+    }
+
+    /// Mixer with a snare on the "lead" bus and a kick on the "drums" bus
+    fn two_bus_mixer() -> Mixer {
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        let mut lead = Track::new();
+        lead.add_drum(DrumType::Snare, 0.0, None);
+        let mut drums = Track::new();
+        drums.add_drum(DrumType::Kick, 0.0, None);
+        mixer.get_or_create_bus("lead").add_track(lead);
+        mixer.get_or_create_bus("drums").add_track(drums);
+        mixer
+    }
+
+    #[test]
+    fn test_mixer_latency_is_slowest_path() {
+        let mut mixer = two_bus_mixer();
+        assert_eq!(mixer.latency(), 0);
+
+        mixer.get_or_create_bus("lead").tracks[0]
+            .effects
+            .add_effect(Lookahead::new(100));
+        mixer.get_or_create_bus("drums").effects.add_effect(Lookahead::new(30));
+        mixer.master.add_effect(Lookahead::new(10));
+
+        assert_eq!(mixer.get_bus("lead").unwrap().latency(), 100);
+        assert_eq!(mixer.get_bus("drums").unwrap().latency(), 30);
+        assert_eq!(mixer.latency(), 110);
+    }
+
+    #[test]
+    fn test_delay_compensation_aligns_buses_and_trims_render() {
+        let reference = two_bus_mixer().render_to_buffer(44100.0);
+
+        // A lookahead effect on one track only; the other bus must be delayed to match
+        // and the offline render trimmed back onto the timeline
+        let mut mixer = two_bus_mixer();
+        mixer.get_or_create_bus("lead").tracks[0]
+            .effects
+            .add_effect(Lookahead::new(64));
+        mixer.master.add_effect(Lookahead::new(16));
+        let compensated = mixer.render_to_buffer(44100.0);
+
+        assert_eq!(reference.len(), compensated.len());
+        for (a, b) in reference.iter().zip(&compensated) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_delay_compensation_covers_convolution_reverb() {
+        use crate::synthesis::effects::ConvolutionReverb;
+
+        let reference = two_bus_mixer().render_to_buffer(44100.0);
+
+        // A unit impulse response at half mix passes the signal through unchanged,
+        // but one block late on the track and on the (stereo) bus
+        let mut mixer = two_bus_mixer();
+        let track_reverb = ConvolutionReverb::from_samples(&[1.0], 0.5, Some(256)).unwrap();
+        let bus_reverb = ConvolutionReverb::from_samples(&[1.0], 0.5, Some(512)).unwrap();
+        mixer.get_or_create_bus("lead").tracks[0].effects.convolution_reverb = Some(track_reverb);
+        mixer.get_or_create_bus("drums").effects.convolution_reverb = Some(bus_reverb);
+        mixer.get_or_create_bus("lead").tracks[0].effects.compute_effect_order();
+        mixer.get_or_create_bus("drums").effects.compute_effect_order();
+        assert_eq!(mixer.latency(), 512);
+
+        let compensated = mixer.render_to_buffer(44100.0);
+        assert_eq!(reference.len(), compensated.len());
+        for (a, b) in reference.iter().zip(&compensated) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+
+    fn unison_mixer(unison: Unison) -> Mixer {
+        let mut track = Track::new();
+        track.unison = unison;
+        track.add_note_with_waveform(&[220.0], 0.0, 0.25, Waveform::Sawtooth);
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        mixer.add_track(track);
+        mixer
+    }
+
+    #[test]
+    fn test_track_unison_is_given_to_added_notes() {
+        let mut track = Track::new();
+        track.unison = Unison::new(4, 10.0);
+        track.add_note(&[440.0], 0.0, 1.0);
+        track.add_note_with_complete_params(
+            &[440.0],
+            1.0,
+            1.0,
+            Waveform::Sine,
+            Envelope::default(),
+            FilterEnvelope::default(),
+            FMParams::default(),
+            0.0,
+            None,
+            0.8,
+            None,
+        );
+
+        for event in &track.events {
+            if let AudioEvent::Note(note) = event {
+                assert_eq!(note.unison, Unison::new(4, 10.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_instruments_play_their_engine() {
+        use crate::instruments::Instrument;
+
+        // Each preset with its own engine, and how long a 0.25s note keeps sounding
+        let table = [
+            (Instrument::electric_piano(), 0.25),
+            (Instrument::violin(), 0.25),
+            (Instrument::tubular_bells(), 0.75),
+        ];
+        for (instrument, rings_for) in table {
+            let name = &instrument.name;
+            let mut track = instrument.apply_to_track(Track::new());
+            track.add_note_with_waveform_and_envelope(
+                &[220.0],
+                0.0,
+                0.25,
+                Waveform::Sine,
+                instrument.envelope,
+            );
+            let AudioEvent::Note(note) = &track.events[0] else {
+                panic!("expected a note");
+            };
+            assert_eq!(note.fm_patch.as_deref(), instrument.fm_patch.as_ref(), "{}", name);
+            assert_eq!(note.physical_model.as_deref(), instrument.physical_model.as_ref(), "{}", name);
+            assert_eq!(note.resonator.as_deref(), instrument.resonator.as_ref(), "{}", name);
+
+            // The engine replaces the sine the note was written with
+            let sine = NoteEvent::with_waveform(&[220.0], at(0.0), at(0.25), Waveform::Sine);
+            let differs = (1..200).any(|i| {
+                let t = i as f32 / 44100.0;
+                let (engine, _) = note.sample_frequency(0, t, 0.25, 44100.0);
+                let (plain, _) = sine.sample_frequency(0, t, 0.25, 44100.0);
+                (engine - plain).abs() > 0.05
+            });
+            assert!(differs, "{}", name);
+
+            // A later note keeps the buffer going past the first one
+            track.add_note(&[330.0], 1.0, 0.1);
+            let buffer = render_left(track);
+            let end = (rings_for * 44100.0) as usize;
+            assert!(buffer.iter().all(|x| x.is_finite()), "{}", name);
+            assert!(buffer[end - 2205..end].iter().any(|x| x.abs() > 0.01), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_many_overlapping_model_notes_are_rendered_ahead() {
+        // More notes sound at once than the model's cache keeps of notes nobody holds
+        let mut track = crate::instruments::Instrument::violin().apply_to_track(Track::new());
+        for i in 0..34 {
+            track.add_note(&[220.0 * 2.0f32.powf(i as f32 / 12.0)], 0.0, 0.1);
+        }
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        mixer.add_track(track);
+        let buffer = mixer.render_to_buffer(22050.0);
+        assert!(buffer.iter().all(|x| x.is_finite()));
+        assert!(buffer.iter().any(|x| x.abs() > 0.01));
+
+        // Each note holds its rendering, which playback reads without the cache
+        let track = &mixer.get_or_create_bus("default").tracks[0];
+        assert!(track.events.iter().all(|event| {
+            matches!(event, AudioEvent::Note(note) if note.renderings.0.len() == 1)
+        }));
+    }
+
+    #[test]
+    fn test_unison_spread_renders_in_stereo() {
+        let mono = unison_mixer(Unison::new(5, 20.0)).render_to_buffer(44100.0);
+        assert!(mono.chunks_exact(2).all(|f| (f[0] - f[1]).abs() < 1e-6));
+
+        let wide = unison_mixer(Unison::new(5, 20.0).with_spread(1.0)).render_to_buffer(44100.0);
+        assert!(wide.chunks_exact(2).any(|f| (f[0] - f[1]).abs() > 0.01));
+
+        // The per-sample path spreads the voices too
+        let mut mixer = unison_mixer(Unison::new(5, 20.0).with_spread(1.0));
+        assert!((0..2000).any(|i| {
+            let (left, right) = mixer.sample_at(i as f32 / 44100.0, 44100.0, 0.0, None, None);
+            (left - right).abs() > 0.01
+        }));
+    }
+
+    #[test]
+    fn test_unison_spread_goes_through_the_effects() {
+        // A fully wet delay holds back both channels, and the echo stays wide
+        let spread_echo = || {
+            let mut mixer = unison_mixer(Unison::new(5, 20.0).with_spread(1.0));
+            let track = &mut mixer.get_or_create_bus("default").tracks[0];
+            track.effects = EffectChain::new().with_delay(Delay::new(0.2, 0.0, 1.0));
+            mixer
+        };
+        let check = |frames: &[(f32, f32)]| {
+            let silent = |&(left, right): &(f32, f32)| left.abs() < 1e-6 && right.abs() < 1e-6;
+            assert!(frames[..2205].iter().all(silent));
+            assert!(frames[8820..].iter().any(|(left, right)| (left - right).abs() > 0.01));
+        };
+
+        let buffer = spread_echo().render_to_buffer(44100.0);
+        let frames: Vec<_> = buffer.chunks_exact(2).map(|f| (f[0], f[1])).collect();
+        check(&frames);
+
+        let mut mixer = spread_echo();
+        let frames: Vec<_> = (0..frames.len())
+            .map(|i| mixer.sample_at(i as f32 / 44100.0, 44100.0, 0.0, None, None))
+            .collect();
+        check(&frames);
+    }
+
+    #[test]
+    fn test_mixer_allocates_voices_of_added_tracks() {
+        let played_notes = |mixer: &mut Mixer| -> Vec<(Ticks, Option<f32>)> {
+            mixer.get_or_create_bus("default").tracks[0]
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    AudioEvent::Note(note) => Some((note.start_time, note.voice.cut_at)),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // One voice: the second note steals it from the first
+        let mut track = Track::new();
+        track.voicing = Voicing::poly(1);
+        track.add_note(&[C4], 0.0, 1.0);
+        track.add_note(&[E4], 0.5, 1.0);
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        mixer.add_track(track);
+        assert_eq!(played_notes(&mut mixer), vec![(at(0.0), Some(0.5)), (at(0.5), None)]);
+
+        // A note added afterwards is allocated along with the rest when rendering
+        let track = &mut mixer.get_or_create_bus("default").tracks[0];
+        track.add_note(&[G4], 0.75, 1.0);
+        assert_eq!(played_notes(&mut mixer).len(), 3);
+        mixer.render_to_buffer(8000.0);
+        let mut notes = played_notes(&mut mixer);
+        notes.sort_by_key(|note| note.0);
+        assert_eq!(notes, vec![(at(0.0), Some(0.5)), (at(0.5), Some(0.25)), (at(0.75), None)]);
+    }
+
+    #[test]
+    fn test_legato_handoff_is_continuous() {
+        // A sine line played legato: no jump where one note hands over to the next
+        let mut track = Track::new();
+        track.voicing = Voicing::mono().legato().with_glide(0.05);
+        for (i, &freq) in [C4, E4, G4, C5].iter().enumerate() {
+            track.add_note(&[freq], i as f32 * 0.25, 0.25);
+        }
+        track.allocate_voices();
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        mixer.add_track(track);
+        let rendered = mixer.render_to_buffer(44100.0);
+
+        let largest_step = rendered
+            .chunks_exact(2)
+            .map(|frame| frame[0])
+            .collect::<Vec<_>>()
+            .windows(2)
+            .skip(4410) // past the attack
+            .take(3 * 11025)
+            .map(|pair| (pair[1] - pair[0]).abs())
+            .fold(0.0, f32::max);
+        // A C5 sine at this level moves at most ~0.05 per sample
+        assert!(largest_step < 0.06, "step of {}", largest_step);
+    }
+
+    fn render_left(track: Track) -> Vec<f32> {
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        mixer.add_track(track);
+        mixer
+            .render_to_buffer(44100.0)
+            .chunks_exact(2)
+            .map(|frame| frame[0])
+            .collect()
+    }
+
+    #[test]
+    fn test_mixer_tempo_map_moves_rendered_notes() {
+        // A note on beat 2: one second in at 120 BPM, two seconds in at 60
+        let first_sound = |bpm: f32| -> usize {
+            let mut track = Track::new();
+            track.add_note_ticks(&[440.0], Ticks::from_beats(2.0), Ticks::from_beats(1.0));
+            let mut mixer = Mixer::new(Tempo::new(120.0));
+            mixer.add_track(track);
+            mixer.set_tempo_map(TempoMap::new(bpm));
+            let left: Vec<f32> = mixer
+                .render_to_buffer(1000.0)
+                .chunks_exact(2)
+                .map(|frame| frame[0])
+                .collect();
+            left.iter().position(|sample| sample.abs() > 1e-6).unwrap()
+        };
+        assert!((1000..1010).contains(&first_sound(120.0)));
+        assert!((2000..2010).contains(&first_sound(60.0)));
+    }
+
+    #[test]
+    fn test_events_changed_in_place_render_at_their_new_time() {
+        let mut track = Track::new();
+        track.add_note(&[440.0], 0.0, 0.25);
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        mixer.add_track(track);
+        mixer.render_to_buffer(1000.0);
+
+        // Same number of events, moved to one second in
+        if let AudioEvent::Note(note) = &mut mixer.get_or_create_bus("default").tracks[0].events[0] {
+            note.start_time = Ticks::from_beats(2.0);
+        }
+        let left: Vec<f32> = mixer
+            .render_to_buffer(1000.0)
+            .chunks_exact(2)
+            .map(|frame| frame[0])
+            .collect();
+        let first_sound = left.iter().position(|sample| sample.abs() > 1e-6).unwrap();
+        assert!((1000..1010).contains(&first_sound));
+    }
+
+    #[test]
+    fn test_seconds_are_placed_under_the_track_tempo_map() {
+        let mut track = Track::new();
+        track.set_tempo_map(TempoMap::new(60.0));
+        track.add_note(&[440.0], 1.0, 0.5);
+        track.add_drum(DrumType::Kick, 2.0, None);
+
+        match &track.events[0] {
+            AudioEvent::Note(note) => {
+                assert_eq!(note.start_time, Ticks::from_beats(1.0));
+                assert_eq!(note.duration, Ticks::from_beats(0.5));
+            }
+            other => panic!("expected a note, got {:?}", other),
+        }
+        assert_eq!(track.events[1].start_time(), Ticks::from_beats(2.0));
+    }
+
+    #[test]
+    fn test_seconds_placed_events_keep_their_seconds_under_the_mixer_tempo() {
+        let mut track = Track::new();
+        track.add_note(&[440.0], 1.0, 0.5);
+        track.add_drum(DrumType::Kick, 2.0, None);
+        let mut mixer = Mixer::new(Tempo::new(60.0));
+        mixer.add_track(track);
+
+        let buffer = mixer.render_to_buffer(44100.0);
+        let onset = buffer.iter().position(|s| s.abs() > 1e-6).unwrap() / 2;
+        assert!((onset as f32 / 44100.0 - 1.0).abs() < 0.002, "onset at {}", onset);
+        let track = &mixer.tracks()[0];
+        assert_eq!(track.events[1].start_time(), Ticks::from_beats(2.0));
+
+        // A note moved in ticks after it was placed follows the tempo map instead
+        let mut track = Track::new();
+        track.add_note(&[440.0], 1.0, 0.5);
+        if let AudioEvent::Note(note) = &mut track.events[0] {
+            note.start_time = Ticks::from_beats(1.0);
+        }
+        track.set_tempo_map(TempoMap::new(60.0));
+        assert_eq!(track.events[0].start_time(), Ticks::from_beats(1.0));
+    }
+
+    #[test]
+    fn test_lfo_routes_are_rendered() {
+        let mut plain = Track::new();
+        plain.add_note(&[440.0], 0.0, 0.5);
+        let vibrato = plain.clone().with_modulation(ModRoute::new(
+            LFO::new(Waveform::Sine, 6.0, 1.0),
+            ModTarget::Pitch,
+            1.0,
+        ));
+
+        let plain = render_left(plain);
+        let vibrato = render_left(vibrato);
+        assert!(plain.iter().zip(&vibrato).any(|(a, b)| (a - b).abs() > 0.1));
+    }
+
+    #[test]
+    fn test_pitch_modulation_keeps_the_waveform_continuous() {
+        // An octave jump halfway through a sine note
+        let mut track = Track::new().with_mod_matrix(ModMatrix::new().with_route(MatrixRoute::new(
+            ModSource::Automation(Automation::steps(&[(0.0, 0.0), (0.25, 1.0)])),
+            ModDestination::Pitch,
+            12.0,
+        )));
+        track.add_note(&[C4], 0.0, 0.5);
+        let rendered = render_left(track);
+
+        let largest_step = rendered
+            .windows(2)
+            .skip(4410) // past the attack
+            .take(13230)
+            .map(|pair| (pair[1] - pair[0]).abs())
+            .fold(0.0, f32::max);
+        // A C5 sine at this level moves at most ~0.05 per sample
+        assert!(largest_step < 0.06, "step of {}", largest_step);
+
+        let crossings = |range: std::ops::Range<usize>| {
+            rendered[range]
+                .windows(2)
+                .filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0))
+                .count() as f32
+        };
+        let ratio = crossings(13230..19845) / crossings(4410..11025);
+        assert!((ratio - 2.0).abs() < 0.1, "pitch ratio {}", ratio);
+    }
+
+    #[test]
+    fn test_effect_parameters_are_modulated_and_restored() {
+        let mut modulated = Track::new().with_mod_matrix(ModMatrix::new().with_route(
+            MatrixRoute::new(
+                ModSource::Automation(Automation::constant(1.0)),
+                ModDestination::effect("Delay", "mix"),
+                -0.5,
+            ),
+        ));
+        modulated.effects.delay = Some(Delay::new(0.1, 0.3, 0.5));
+        modulated.add_note(&[440.0], 0.0, 0.2);
+        let mut dry = modulated.clone();
+        dry.mod_matrix = ModMatrix::new();
+        dry.effects.delay = Some(Delay::new(0.1, 0.3, 0.0));
+
+        let mut mixer = Mixer::new(Tempo::new(120.0));
+        mixer.add_track(modulated);
+        let rendered = mixer.render_to_buffer(44100.0);
+        for (a, b) in rendered.chunks_exact(2).map(|frame| frame[0]).zip(render_left(dry)) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        let track = &mixer.get_or_create_bus("default").tracks[0];
+        assert_eq!(track.effects.delay.as_ref().unwrap().mix, 0.5);
+    }
+
+    #[test]
+    fn test_delay_time_is_modulated_through_the_matrix() {
+        let mut modulated = Track::new().with_mod_matrix(ModMatrix::new().with_route(
+            MatrixRoute::new(
+                ModSource::Automation(Automation::constant(1.0)),
+                ModDestination::effect("Delay", "delay_time"),
+                0.1,
+            ),
+        ));
+        modulated.effects.delay = Some(Delay::new(0.1, 0.0, 0.5));
+        modulated.add_note(&[440.0], 0.0, 0.05);
+        modulated.add_note(&[440.0], 0.5, 0.05);
+        let mut reference = modulated.clone();
+        reference.mod_matrix = ModMatrix::new();
+        reference.effects.delay = Some(Delay::new(0.2, 0.0, 0.5));
+
+        // The echo lands 0.2 s after the note, not 0.1 s
+        let rendered = render_left(modulated);
+        let echo: f32 = rendered[8820..11025].iter().map(|s| s.abs()).sum();
+        assert!(echo > 1.0);
+        for (a, b) in rendered.iter().zip(render_left(reference)) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
 }
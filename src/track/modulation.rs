@@ -0,0 +1,266 @@
+//! Render-time state of a track's modulation
+//!
+//! The mixer works through a copy of the track's mod matrix with its LFO routes
+//! folded in, so LFO phases and envelope followers advance without touching the
+//! track's settings. The copy is made on the first rendered block and dropped
+//! by `Track::prepare` and `Track::reset`.
+//!
+//! Modulated effect parameters are looked up once, and only written when their
+//! value changes. Their unmodulated values are put back when an offline render
+//! finishes, before the copy is dropped and before a mixer scene is recalled.
+
+use super::events::NoteEvent;
+use crate::synthesis::effects::{ChainParameters, EffectChain, EffectLocation};
+use crate::synthesis::filter::Filter;
+use crate::synthesis::lfo::ModRoute;
+use crate::synthesis::mod_matrix::{
+    MatrixRoute, ModDestination, ModMatrix, NoteContext, NoteModulation, TrackModulation,
+};
+
+/// A track's modulation while it renders
+#[derive(Debug, Clone)]
+pub(super) struct ModulationState {
+    matrix: ModMatrix,
+    modulates_notes: bool,
+    modulates_pitch: bool,
+
+    // Per event: the last time into the note that was rendered, and the
+    // pitch-modulated clock its oscillators had reached by then
+    clocks: Vec<(f32, f32)>,
+
+    // Track signal of the previous sample, for the envelope followers
+    input: f32,
+
+    // Per-note sources of the latest sounding note, for the track destinations
+    latest: Option<NoteContext>,
+    track: TrackModulation,
+
+    // Summed offset of each modulated effect parameter, and where to apply it,
+    // in the order the matrix reports them
+    effect_offsets: Vec<(&'static str, &'static str, f32)>,
+    effect_targets: Vec<EffectTarget>,
+}
+
+/// A modulated effect parameter, as found in the track's effect chain
+#[derive(Debug, Clone, Copy)]
+enum EffectTarget {
+    /// Not looked up yet
+    Unbound,
+    /// The chain has no such effect or parameter
+    Missing,
+    /// Where the effect sits, the parameter's unmodulated value, and the value last written
+    Bound {
+        location: EffectLocation,
+        base: f32,
+        applied: f32,
+    },
+}
+
+impl ModulationState {
+    /// Combine the matrix and LFO routes, or `None` if the track has neither
+    pub(super) fn new(matrix: &ModMatrix, routes: &[ModRoute], sample_rate: f32) -> Option<Self> {
+        if matrix.is_empty() && routes.is_empty() {
+            return None;
+        }
+
+        let mut matrix = matrix.clone();
+        matrix
+            .routes
+            .extend(routes.iter().map(|route| MatrixRoute::from(*route)));
+        matrix.prepare(sample_rate);
+        matrix.reset();
+
+        Some(Self {
+            modulates_notes: matrix.modulates_notes(),
+            modulates_pitch: matrix
+                .routes
+                .iter()
+                .any(|route| route.destination == ModDestination::Pitch),
+            matrix,
+            clocks: Vec::new(),
+            input: 0.0,
+            latest: None,
+            track: TrackModulation::default(),
+            effect_offsets: Vec::new(),
+            effect_targets: Vec::new(),
+        })
+    }
+
+    /// Start a sample: advance the free-running sources to `time`
+    #[inline]
+    pub(super) fn tick(&mut self, time: f32, sample_rate: f32) {
+        self.matrix.tick(time, self.input, sample_rate);
+        self.latest = None;
+    }
+
+    /// Pitch clock and per-note offsets for a sounding note
+    ///
+    /// Call for every sounding note in start-time order, so the last one seen is
+    /// the one track destinations read from. `length` is how long the note is
+    /// held, in seconds.
+    #[inline]
+    pub(super) fn note(
+        &mut self,
+        index: usize,
+        note: &NoteEvent,
+        time_in_note: f32,
+        length: f32,
+    ) -> (Option<f32>, NoteModulation) {
+        let context = note.mod_context(time_in_note, length);
+        self.latest = Some(context);
+        if !self.modulates_notes {
+            return (None, NoteModulation::default());
+        }
+
+        let modulation = self.matrix.note_modulation(&context);
+        let clock = self.modulates_pitch.then(|| {
+            let ratio = 2.0f32.powf(modulation.pitch / 12.0);
+            self.advance_clock(index, time_in_note, ratio)
+        });
+        (clock, modulation)
+    }
+
+    /// Move a note's clock on to `time_in_note` at `ratio` times real time
+    ///
+    /// A note seen for the first time, or rendered again from an earlier point,
+    /// starts its clock over.
+    fn advance_clock(&mut self, index: usize, time_in_note: f32, ratio: f32) -> f32 {
+        if index >= self.clocks.len() {
+            self.clocks.resize(index + 1, (f32::NAN, 0.0));
+        }
+        let (last, clock) = &mut self.clocks[index];
+        if time_in_note >= *last {
+            *clock += (time_in_note - *last) * ratio;
+        } else {
+            *clock = time_in_note * ratio;
+        }
+        *last = time_in_note;
+        *clock
+    }
+
+    /// Finish a sample: follow the track's signal and work out the track destinations
+    #[inline]
+    pub(super) fn finish(&mut self, input: f32) -> TrackModulation {
+        self.input = input;
+        self.track = self.matrix.track_modulation(self.latest.as_ref());
+        self.track
+    }
+
+    /// Pan offset as of the last finished sample
+    pub(super) fn pan(&self) -> f32 {
+        self.track.pan
+    }
+
+    /// Run `process` with the modulated effect parameters in place
+    ///
+    /// Each parameter is found in the chain the first time it is modulated; after
+    /// that it is only written when its modulated value changes.
+    pub(super) fn process_effects<R>(
+        &mut self,
+        effects: &mut EffectChain,
+        process: impl FnOnce(&mut EffectChain) -> R,
+    ) -> R {
+        self.matrix
+            .effect_modulation(self.latest.as_ref(), &mut self.effect_offsets);
+        if self.effect_targets.len() < self.effect_offsets.len() {
+            self.effect_targets
+                .resize(self.effect_offsets.len(), EffectTarget::Unbound);
+        }
+
+        for (target, &(name, parameter, offset)) in
+            self.effect_targets.iter_mut().zip(&self.effect_offsets)
+        {
+            if let EffectTarget::Unbound = target {
+                *target = EffectTarget::bind(effects, name, parameter);
+            }
+            if let EffectTarget::Bound {
+                location,
+                base,
+                applied,
+            } = target
+            {
+                let value = *base + offset;
+                if value != *applied {
+                    if let Some(effect) = effects.effect_at_mut(*location) {
+                        effect.set_parameter(parameter, value);
+                    }
+                    *applied = value;
+                }
+            }
+        }
+
+        process(effects)
+    }
+
+    /// Put the unmodulated effect parameters back, and look them up afresh next time
+    pub(super) fn release_effects(&mut self, effects: &mut EffectChain) {
+        for (target, &(_, parameter, _)) in self.effect_targets.iter().zip(&self.effect_offsets) {
+            if let EffectTarget::Bound {
+                location,
+                base,
+                applied,
+            } = *target
+            {
+                if applied != base {
+                    if let Some(effect) = effects.effect_at_mut(location) {
+                        effect.set_parameter(parameter, base);
+                    }
+                }
+            }
+        }
+        self.effect_targets.clear();
+    }
+
+    /// Swap the unmodulated values into a snapshot of the track's effect parameters
+    pub(super) fn unmodulated(&self, parameters: &mut ChainParameters) {
+        for (target, &(_, parameter, _)) in self.effect_targets.iter().zip(&self.effect_offsets) {
+            if let EffectTarget::Bound { location, base, .. } = *target {
+                parameters.set(location, parameter, base);
+            }
+        }
+    }
+}
+
+impl EffectTarget {
+    /// Find a parameter in the chain and read its unmodulated value
+    fn bind(effects: &mut EffectChain, name: &str, parameter: &str) -> Self {
+        let Some(location) = effects.locate(name) else {
+            return EffectTarget::Missing;
+        };
+        let base = effects.effect_at_mut(location).and_then(|effect| {
+            effect
+                .parameters()
+                .into_iter()
+                .find(|&(candidate, _)| candidate == parameter)
+        });
+        match base {
+            Some((_, base)) => EffectTarget::Bound {
+                location,
+                base,
+                applied: base,
+            },
+            None => EffectTarget::Missing,
+        }
+    }
+}
+
+/// Filter one sample with the cutoff and resonance offsets applied
+#[inline]
+pub(super) fn process_filter(
+    filter: &mut Filter,
+    input: f32,
+    sample_rate: f32,
+    modulation: &TrackModulation,
+) -> f32 {
+    if modulation.cutoff == 0.0 && modulation.resonance == 0.0 {
+        return filter.process(input, sample_rate);
+    }
+
+    let (cutoff, resonance) = (filter.cutoff, filter.resonance);
+    filter.cutoff = (cutoff * 2.0f32.powf(modulation.cutoff)).clamp(20.0, sample_rate * 0.45);
+    filter.resonance = (resonance + modulation.resonance).clamp(0.0, 0.99);
+    let output = filter.process(input, sample_rate);
+    filter.cutoff = cutoff;
+    filter.resonance = resonance;
+    output
+}
@@ -0,0 +1,293 @@
+//! Mixer scenes
+//!
+//! A [`MixerScene`] is a named snapshot of every mix setting in a [`Mixer`](super::Mixer):
+//! track and bus volume and pan, bus mute/solo, and the parameters of every effect on
+//! tracks, buses and the master chain. Scenes can be recalled instantly, scheduled on
+//! the mixer timeline, or morphed into on a playing sound through the audio engine.
+//!
+//! Scenes only store settings, not structure: recalling a scene never adds or removes
+//! tracks, buses or effects. Entries are matched by track and bus ID, so a scene can
+//! be captured from one mixer and recalled on a clone of it.
+
+use crate::synthesis::effects::ChainParameters;
+use crate::track::ids::{BusId, TrackId};
+
+/// Mix settings of a single track
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TrackScene {
+    pub id: TrackId,
+    pub volume: f32,
+    pub pan: f32,
+    pub effects: ChainParameters,
+}
+
+/// Mix settings of a single bus
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct BusScene {
+    pub id: BusId,
+    pub volume: f32,
+    pub pan: f32,
+    pub muted: bool,
+    pub soloed: bool,
+    pub effects: ChainParameters,
+}
+
+/// Named snapshot of a mixer's state
+///
+/// Create one with [`Mixer::capture_scene`](super::Mixer::capture_scene), then apply it
+/// with [`Mixer::recall_scene`](super::Mixer::recall_scene),
+/// [`Mixer::schedule_scene`](super::Mixer::schedule_scene), or
+/// [`AudioEngine::morph_scene`](crate::engine::AudioEngine::morph_scene) for a playing sound.
+///
+/// # Example
+/// ```
+/// # use tunes::prelude::*;
+/// let mut comp = Composition::new(Tempo::new(120.0));
+/// comp.track("pad").reverb(Reverb::new(0.5, 0.5, 0.3)).note(&[220.0], 4.0);
+/// let mut mixer = comp.into_mixer();
+///
+/// let verse = mixer.capture_scene("verse");
+/// mixer.get_bus_mut("default").unwrap().volume = 0.5;
+/// let chorus = mixer.capture_scene("chorus");
+///
+/// // Start in the verse mix and morph into the chorus over two seconds at 8s
+/// mixer.recall_scene(&verse);
+/// mixer.schedule_scene(8.0, &chorus, 2.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixerScene {
+    /// Name of the scene
+    pub name: String,
+    pub(super) tracks: Vec<TrackScene>,
+    pub(super) buses: Vec<BusScene>,
+    pub(super) master: ChainParameters,
+}
+
+impl MixerScene {
+    /// Blend this scene towards `target` by `amount` (0.0 = this scene, 1.0 = target)
+    ///
+    /// Volume, pan and effect parameters are interpolated linearly. Mute and solo
+    /// switch to the target's state once `amount` reaches 1.0. Tracks and buses that
+    /// only exist in `target` take the target's settings. The result is named after
+    /// the target.
+    pub fn interpolate(&self, target: &MixerScene, amount: f32) -> MixerScene {
+        let mut blended = target.clone();
+        self.interpolate_into(target, amount, &mut blended);
+        blended
+    }
+
+    /// Blend like [`interpolate`](Self::interpolate), writing into existing storage
+    ///
+    /// `out` must be a clone of `target` or an earlier result against it; only its
+    /// values are overwritten, so this never allocates and is safe on the audio thread.
+    pub(crate) fn interpolate_into(&self, target: &MixerScene, amount: f32, out: &mut MixerScene) {
+        let amount = amount.clamp(0.0, 1.0);
+        let lerp = |from: f32, to: f32| from + (to - from) * amount;
+
+        for (to, out) in target.tracks.iter().zip(&mut out.tracks) {
+            match self.tracks.iter().find(|from| from.id == to.id) {
+                Some(from) => {
+                    out.volume = lerp(from.volume, to.volume);
+                    out.pan = lerp(from.pan, to.pan);
+                    from.effects.interpolate_into(&to.effects, amount, &mut out.effects);
+                }
+                None => {
+                    out.volume = to.volume;
+                    out.pan = to.pan;
+                    to.effects.interpolate_into(&to.effects, 1.0, &mut out.effects);
+                }
+            }
+        }
+
+        let settled = amount >= 1.0;
+        for (to, out) in target.buses.iter().zip(&mut out.buses) {
+            match self.buses.iter().find(|from| from.id == to.id) {
+                Some(from) => {
+                    out.volume = lerp(from.volume, to.volume);
+                    out.pan = lerp(from.pan, to.pan);
+                    out.muted = if settled { to.muted } else { from.muted };
+                    out.soloed = if settled { to.soloed } else { from.soloed };
+                    from.effects.interpolate_into(&to.effects, amount, &mut out.effects);
+                }
+                None => {
+                    out.volume = to.volume;
+                    out.pan = to.pan;
+                    out.muted = to.muted;
+                    out.soloed = to.soloed;
+                    to.effects.interpolate_into(&to.effects, 1.0, &mut out.effects);
+                }
+            }
+        }
+
+        self.master.interpolate_into(&target.master, amount, &mut out.master);
+    }
+}
+
+/// A scene change on the mixer timeline
+#[derive(Debug, Clone)]
+pub(super) struct SceneChange {
+    /// Time the morph starts, in seconds
+    pub time: f32,
+    /// Scene to morph into
+    pub scene: MixerScene,
+    /// Morph length in seconds (0.0 = instant)
+    pub morph_duration: f32,
+    /// Mix reached by this change, rewritten in place as the timeline plays
+    pub mix: MixerScene,
+}
+
+impl SceneChange {
+    /// Morph progress (0.0 to 1.0) at `time`
+    pub fn progress(&self, time: f32) -> f32 {
+        if self.morph_duration <= 0.0 {
+            return 1.0;
+        }
+        ((time - self.time) / self.morph_duration).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::composition::{Composition, Tempo};
+    use crate::synthesis::effects::{Delay, Effect};
+    use crate::track::Mixer;
+
+    fn mixer_with_delay() -> Mixer {
+        let mut comp = Composition::new(Tempo::new(120.0));
+        comp.track("lead")
+            .delay(Delay::new(0.25, 0.3, 0.2))
+            .note(&[440.0], 4.0);
+        comp.into_mixer()
+    }
+
+    fn bus_volume(mixer: &Mixer) -> f32 {
+        mixer.get_bus("default").unwrap().volume
+    }
+
+    #[test]
+    fn test_capture_and_recall_round_trip() {
+        let mut mixer = mixer_with_delay();
+        let dry = mixer.capture_scene("dry");
+
+        {
+            let bus = mixer.get_bus_mut("default").unwrap();
+            bus.volume = 0.5;
+            bus.muted = true;
+            let track = &mut bus.tracks[0];
+            track.pan = -0.7;
+            track.effects.delay.as_mut().unwrap().mix = 0.8;
+        }
+        let wet = mixer.capture_scene("wet");
+        assert_ne!(dry, wet);
+
+        mixer.recall_scene(&dry);
+        assert_eq!(mixer.capture_scene("dry"), dry);
+        let bus = mixer.get_bus("default").unwrap();
+        assert!(!bus.muted);
+        assert_eq!(bus.tracks[0].effects.delay.as_ref().unwrap().mix, 0.2);
+    }
+
+    #[test]
+    fn test_interpolate_blends_values_and_switches_mute_at_end() {
+        let mut mixer = mixer_with_delay();
+        let from = mixer.capture_scene("from");
+        {
+            let bus = mixer.get_bus_mut("default").unwrap();
+            bus.volume = 0.0;
+            bus.muted = true;
+            bus.tracks[0].effects.delay.as_mut().unwrap().feedback = 0.7;
+        }
+        let to = mixer.capture_scene("to");
+
+        mixer.recall_scene(&from.interpolate(&to, 0.5));
+        let bus = mixer.get_bus("default").unwrap();
+        assert!((bus.volume - 0.5).abs() < 1e-6);
+        assert!(!bus.muted);
+        let feedback = bus.tracks[0].effects.delay.as_ref().unwrap().feedback;
+        assert!((feedback - 0.5).abs() < 1e-6);
+
+        let end = from.interpolate(&to, 1.0);
+        assert_eq!(end.name, "to");
+        mixer.recall_scene(&end);
+        assert!(mixer.get_bus("default").unwrap().muted);
+    }
+
+    #[test]
+    fn test_interpolate_into_overwrites_storage_in_place() {
+        let mut mixer = mixer_with_delay();
+        let from = mixer.capture_scene("from");
+        {
+            let bus = mixer.get_bus_mut("default").unwrap();
+            bus.volume = 0.0;
+            bus.tracks[0].effects.delay.as_mut().unwrap().mix = 1.0;
+        }
+        let to = mixer.capture_scene("to");
+
+        let mut mix = to.clone();
+        let tracks = mix.tracks.as_ptr();
+        for amount in [0.25, 0.5, 1.0] {
+            from.interpolate_into(&to, amount, &mut mix);
+            assert_eq!(mix, from.interpolate(&to, amount));
+        }
+        assert_eq!(mix.tracks.as_ptr(), tracks);
+    }
+
+    #[test]
+    fn test_set_parameter_clamps_and_ignores_unknown_names() {
+        let mut delay = Delay::new(0.25, 0.3, 0.2);
+        delay.set_parameter("feedback", 2.0);
+        delay.set_parameter("no_such_parameter", 1.0);
+        assert_eq!(delay.feedback, 0.99);
+        assert_eq!(
+            delay.parameters(),
+            vec![("delay_time", 0.25), ("feedback", 0.99), ("mix", 0.2)]
+        );
+    }
+
+    #[test]
+    fn test_scheduled_morph_follows_timeline_and_resets() {
+        let mut mixer = mixer_with_delay();
+        mixer.get_bus_mut("default").unwrap().volume = 0.0;
+        let quiet = mixer.capture_scene("quiet");
+        mixer.get_bus_mut("default").unwrap().volume = 1.0;
+
+        mixer.schedule_scene(1.0, &quiet, 1.0);
+        let mut buffer = vec![0.0; 128];
+
+        mixer.process_block(&mut buffer, 44100.0, 0.5, None, None);
+        assert_eq!(bus_volume(&mixer), 1.0);
+
+        mixer.process_block(&mut buffer, 44100.0, 1.5, None, None);
+        assert!((bus_volume(&mixer) - 0.5).abs() < 1e-6);
+
+        mixer.process_block(&mut buffer, 44100.0, 2.5, None, None);
+        assert_eq!(bus_volume(&mixer), 0.0);
+
+        // Once the morph has settled, live edits are left alone
+        mixer.get_bus_mut("default").unwrap().volume = 0.8;
+        mixer.process_block(&mut buffer, 44100.0, 3.0, None, None);
+        assert_eq!(bus_volume(&mixer), 0.8);
+
+        // Resetting restores the mix from before the first scene change
+        mixer.reset();
+        assert_eq!(bus_volume(&mixer), 1.0);
+    }
+
+    #[test]
+    fn test_later_change_interrupts_unfinished_morph() {
+        let mut mixer = mixer_with_delay();
+        let loud = mixer.capture_scene("loud");
+        mixer.get_bus_mut("default").unwrap().volume = 0.0;
+        let quiet = mixer.capture_scene("quiet");
+        mixer.recall_scene(&loud);
+
+        // Morph down over 2s, but jump back up after 1s
+        mixer.schedule_scene(0.0, &quiet, 2.0);
+        mixer.schedule_scene(1.0, &loud, 1.0);
+        let mut buffer = vec![0.0; 128];
+
+        mixer.process_block(&mut buffer, 44100.0, 1.5, None, None);
+        // Halfway from 0.5 (where the first morph stopped) back to 1.0
+        assert!((bus_volume(&mixer) - 0.75).abs() < 1e-6);
+    }
+}
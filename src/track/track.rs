@@ -5,19 +5,40 @@
 //! volume, pan, filter, and effects.
 
 use super::events::*;
+use crate::composition::timing::{TempoMap, Ticks};
 use crate::instruments::drums::DrumType;
-use crate::synthesis::effects::{Delay, Distortion, EffectChain, Reverb};
+use crate::synthesis::effects::{ChainParameters, Delay, Distortion, EffectChain, Reverb};
 use crate::synthesis::envelope::Envelope;
 use crate::synthesis::filter::Filter;
 use crate::synthesis::filter_envelope::FilterEnvelope;
+use crate::synthesis::fm_operators::FMPatch;
 use crate::synthesis::fm_synthesis::FMParams;
-use crate::synthesis::lfo::ModRoute;
+use crate::synthesis::lfo::{ModRoute, ModTarget};
+use crate::synthesis::mod_matrix::ModMatrix;
+use crate::synthesis::modal::ModalResonator;
+use crate::synthesis::physical_model::PhysicalModel;
+use crate::synthesis::unison::Unison;
 use crate::synthesis::waveform::Waveform;
 use crate::track::ids::{BusId, TrackId};
+use crate::track::latency::CompensationDelay;
+use crate::track::modulation::ModulationState;
+use crate::track::voices::Voicing;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
 
 /// A track contains a sequence of audio events (notes and drums)
+///
+/// Events are placed in [`Ticks`] of musical time; the track's tempo map says
+/// where those fall in seconds when it renders. The `add_*` methods take seconds and
+/// keep the event at those seconds when the track is given another tempo map, such
+/// as the mixer's; their `*_ticks` variants take ticks, which follow the tempo map.
 #[derive(Debug, Clone)]
 pub struct Track {
+    /// The track's events
+    ///
+    /// Events changed in place here are picked up at the next
+    /// [`prepare`](Self::prepare), which the mixer runs before every render.
     pub events: Vec<AudioEvent>,
 
     /// Unique integer ID for fast lookups (used in real-time audio path)
@@ -41,12 +62,57 @@ pub struct Track {
 
     pub modulation: Vec<ModRoute>, // LFO modulation routes
 
+    /// Modulation routes from any source to any destination
+    pub mod_matrix: ModMatrix,
+
+    /// Unison given to notes added through the `add_note*` methods
+    pub unison: Unison,
+
+    /// Multi-operator FM patch given to notes added through the `add_note*` methods
+    pub fm_patch: Option<FMPatch>,
+
+    /// Waveguide physical model given to notes added through the `add_note*` methods
+    pub physical_model: Option<PhysicalModel>,
+
+    /// Modal resonator bank given to notes added through the `add_note*` methods
+    pub resonator: Option<ModalResonator>,
+
+    /// Polyphony, voice stealing and mono/legato handling, applied by `allocate_voices`
+    pub voicing: Voicing,
+
+    // Tempo map that places the events in seconds (set by the composition or mixer)
+    pub(super) tempo_map: TempoMap,
+
     // Cached time bounds for performance (computed on-demand)
     pub(super) cached_start_time: Option<f32>,
     pub(super) cached_end_time: Option<f32>,
 
     // Flag to track if events are sorted by start_time
     pub(super) events_sorted: bool,
+
+    // Where each sorted event falls in seconds under `tempo_map`, in the order of
+    // `events` (resolved by `ensure_sorted` whenever `timeline_dirty` is set)
+    pub(super) timeline: Vec<EventTime>,
+    pub(super) timeline_dirty: bool,
+
+    // Delay that aligns this track with slower tracks on the same bus
+    pub(super) delay_compensation: CompensationDelay,
+
+    // Whether any note spreads unison across the stereo field, and the filter state
+    // of the side channel such tracks render alongside the mid
+    pub(super) cached_stereo_unison: Option<bool>,
+    pub(super) side_filter: Filter,
+
+    // Set once the notes have been rewritten for `voicing`, with the notes as
+    // written kept so allocation can start over when more notes are added
+    pub(super) voices_allocated: bool,
+    pub(super) written_notes: Vec<NoteEvent>,
+
+    // Generator the noise seed of each added note is drawn from
+    pub(super) rng: StdRng,
+
+    // Working copy of the modulation routes while rendering (built on first use)
+    pub(super) modulation_state: Option<ModulationState>,
 }
 
 impl Track {
@@ -70,13 +136,64 @@ impl Track {
             effects: EffectChain::new(),
 
             modulation: Vec::new(),
+            mod_matrix: ModMatrix::new(),
+            unison: Unison::default(),
+            fm_patch: None,
+            physical_model: None,
+            resonator: None,
+            voicing: Voicing::default(),
+            tempo_map: TempoMap::default(),
             cached_start_time: None,
             cached_end_time: None,
             events_sorted: true, // Empty list is sorted
+            timeline: Vec::new(),
+            timeline_dirty: false,
+            delay_compensation: CompensationDelay::default(),
+            cached_stereo_unison: None,
+            side_filter: Filter::none(),
+            voices_allocated: false,
+            written_notes: Vec::new(),
+            // A fixed seed, so a track renders the same noise every time
+            rng: StdRng::seed_from_u64(0),
+            modulation_state: None,
+        }
+    }
+
+    /// Seed the generator that noise notes added from now on draw their seeds from
+    ///
+    /// Tracks built through a [`Composition`](crate::composition::Composition) are
+    /// seeded from its seed; without this, a track starts from seed 0.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Get the tempo map that places the track's events in seconds
+    pub fn tempo_map(&self) -> &TempoMap {
+        &self.tempo_map
+    }
+
+    /// Replace the tempo map that places the track's events in seconds
+    ///
+    /// Events added in ticks keep their positions in ticks and move to wherever those
+    /// now fall. Events added in seconds (`add_note`, `add_drum`, ...) stay at those
+    /// seconds, unless their ticks were edited since.
+    /// A [`Composition`](crate::composition::Composition) or
+    /// [`Mixer`](crate::track::Mixer) gives its tracks its own map, so there is
+    /// rarely a need to call this yourself.
+    pub fn set_tempo_map(&mut self, tempo_map: TempoMap) {
+        if self.tempo_map == tempo_map {
+            return;
+        }
+        // Voice allocation cuts notes at times in seconds, so it starts over
+        self.unallocate_voices();
+        for event in &mut self.events {
+            event.keep_placed_seconds(&self.tempo_map, &tempo_map);
         }
+        self.tempo_map = tempo_map;
+        self.invalidate_time_cache();
     }
 
-    /// Get the start time of the first event (cached for performance)
+    /// Get the start time of the first event in seconds (cached for performance)
     ///
     /// Returns the earliest start time among all events in the track.
     /// This value is cached and only recomputed when events are added.
@@ -88,22 +205,15 @@ impl Track {
         let start = self
             .events
             .iter()
-            .map(|e| match e {
-                AudioEvent::Note(n) => n.start_time,
-                AudioEvent::Drum(d) => d.start_time,
-                AudioEvent::Sample(s) => s.start_time,
-                AudioEvent::TempoChange(t) => t.start_time,
-                AudioEvent::TimeSignature(ts) => ts.start_time,
-                AudioEvent::KeySignature(ks) => ks.start_time,
-            })
-            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
+            .map(AudioEvent::start_time)
+            .min()
+            .map_or(0.0, |start| self.tempo_map.seconds_at(start) as f32);
 
         self.cached_start_time = Some(start);
         start
     }
 
-    /// Get the end time of the last event (cached for performance)
+    /// Get the end time of the last event in seconds (cached for performance)
     ///
     /// Returns the latest end time among all events in the track (including release times).
     /// This value is cached and only recomputed when events are added.
@@ -124,22 +234,113 @@ impl Track {
     pub(crate) fn invalidate_time_cache(&mut self) {
         self.cached_start_time = None;
         self.cached_end_time = None;
+        self.cached_stereo_unison = None;
         self.events_sorted = false; // Events need to be re-sorted
+        self.timeline_dirty = true;
+    }
+
+    /// Check whether any note spreads its unison voices across the stereo field
+    /// (cached, like the time bounds)
+    pub(super) fn has_stereo_unison(&mut self) -> bool {
+        if let Some(cached) = self.cached_stereo_unison {
+            return cached;
+        }
+
+        let stereo = self
+            .events
+            .iter()
+            .any(|e| matches!(e, AudioEvent::Note(n) if n.unison.is_stereo()));
+        self.cached_stereo_unison = Some(stereo);
+        stereo
+    }
+
+    /// Rewrite the notes as played by the track's [`Voicing`]
+    ///
+    /// Stolen notes are cut short, and mono voices become chains of notes that glide
+    /// and hand their envelope and phase to one another. The mixer does this when a
+    /// track is added and again before each render, so there is rarely a need to
+    /// call it yourself. Adding a note puts the notes back as written, and the next
+    /// allocation starts over with the new note included.
+    pub fn allocate_voices(&mut self) {
+        if self.voices_allocated {
+            return;
+        }
+        if !self.voicing.is_unlimited() {
+            self.written_notes = self
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    AudioEvent::Note(note) => Some(note.clone()),
+                    _ => None,
+                })
+                .collect();
+        }
+        super::voices::allocate(&mut self.events, &self.voicing, &self.tempo_map);
+        self.voices_allocated = true;
+        self.invalidate_time_cache();
+    }
+
+    /// Undo `allocate_voices`, putting the notes back as written
+    fn unallocate_voices(&mut self) {
+        if !self.voices_allocated {
+            return;
+        }
+        if !self.written_notes.is_empty() {
+            self.events.retain(|event| !matches!(event, AudioEvent::Note(_)));
+            self.events
+                .extend(self.written_notes.drain(..).map(AudioEvent::Note));
+        }
+        self.voices_allocated = false;
+        self.invalidate_time_cache();
+    }
+
+    /// Copy the filter settings onto the unison side channel's filter
+    pub(super) fn sync_side_filter(&mut self) {
+        self.side_filter.filter_type = self.filter.filter_type;
+        self.side_filter.cutoff = self.filter.cutoff;
+        self.side_filter.resonance = self.filter.resonance;
+        self.side_filter.slope = self.filter.slope;
+    }
+
+    /// Add a note, giving it the track's unison, FM patch, physical model and
+    /// resonator unless it has its own, and a noise seed if it plays noise
+    fn push_note(&mut self, mut note: NoteEvent) {
+        self.unallocate_voices();
+        if let Waveform::Noise { ref mut seed } = note.waveform {
+            *seed = self.rng.random();
+        }
+        if !note.unison.is_active() {
+            note.unison = self.unison;
+        }
+        if note.fm_patch.is_none() {
+            note.fm_patch = self.fm_patch.clone().map(Box::new);
+        }
+        if note.physical_model.is_none() {
+            note.physical_model = self.physical_model.clone().map(Arc::new);
+        }
+        if note.resonator.is_none() {
+            note.resonator = self.resonator.clone().map(Arc::new);
+        }
+        self.events.push(AudioEvent::Note(note));
+        self.invalidate_time_cache();
     }
 
-    /// Ensure events are sorted by start_time (lazy sorting for performance)
+    /// Ensure events are sorted by start_time and placed in seconds (lazy, for performance)
     ///
     /// Events are sorted on-demand rather than on every insert.
     /// This is more efficient when adding multiple events in a batch.
+    /// The timeline of seconds is resolved from the tempo map here, once per change,
+    /// so rendering never converts ticks itself.
     pub(super) fn ensure_sorted(&mut self) {
         if !self.events_sorted {
-            self.events.sort_by(|a, b| {
-                a.start_time()
-                    .partial_cmp(&b.start_time())
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
+            self.events.sort_by_key(AudioEvent::start_time);
             self.events_sorted = true;
         }
+        if self.timeline_dirty {
+            let tempo_map = &self.tempo_map;
+            self.timeline = self.events.iter().map(|e| e.seconds(tempo_map)).collect();
+            self.timeline_dirty = false;
+        }
     }
 
     /// Find the range of events that could be active at the given time using binary search
@@ -156,9 +357,15 @@ impl Track {
         // For simplicity, we search for events that start before or at current time + lookahead
 
         // Find first event that ends after current time
-        let start_idx = self
-            .events
-            .partition_point(|event| event.end_time() <= time);
+        let (mut start_idx, mut end_idx) = (0, self.events.len());
+        while start_idx < end_idx {
+            let mid = (start_idx + end_idx) / 2;
+            if self.events[mid].end_time(self.timeline[mid]) <= time {
+                start_idx = mid + 1;
+            } else {
+                end_idx = mid;
+            }
+        }
 
         // All remaining events could potentially be active (they end after current time)
         // We could further optimize by finding events that start after current time,
@@ -221,6 +428,14 @@ impl Track {
         self
     }
 
+    /// Set the track's mod matrix (builder pattern)
+    ///
+    /// The matrix is rendered together with any LFO routes added by `with_modulation`.
+    pub fn with_mod_matrix(mut self, mod_matrix: ModMatrix) -> Self {
+        self.mod_matrix = mod_matrix;
+        self
+    }
+
     /// Add a simple note event to the track
     ///
     /// Creates a note with default sine waveform and envelope.
@@ -230,12 +445,15 @@ impl Track {
     /// * `start_time` - When to start playing (in seconds from track start)
     /// * `duration` - How long to sustain the note (in seconds)
     pub fn add_note(&mut self, frequencies: &[f32], start_time: f32, duration: f32) {
-        self.events.push(AudioEvent::Note(NoteEvent::new(
-            frequencies,
-            start_time,
-            duration,
-        )));
-        self.invalidate_time_cache();
+        let seconds = EventTime { start: start_time, length: duration };
+        let (start_time, duration) = self.ticks_span(start_time, duration);
+        self.add_note_ticks(frequencies, start_time, duration);
+        self.place_last_in_seconds(seconds);
+    }
+
+    /// Add a simple note event, with its start and duration in ticks
+    pub fn add_note_ticks(&mut self, frequencies: &[f32], start_time: Ticks, duration: Ticks) {
+        self.push_note(NoteEvent::new(frequencies, start_time, duration));
     }
 
     /// Add a note event with a specific waveform
@@ -252,13 +470,26 @@ impl Track {
         duration: f32,
         waveform: Waveform,
     ) {
-        self.events.push(AudioEvent::Note(NoteEvent::with_waveform(
+        let seconds = EventTime { start: start_time, length: duration };
+        let (start_time, duration) = self.ticks_span(start_time, duration);
+        self.add_note_with_waveform_ticks(frequencies, start_time, duration, waveform);
+        self.place_last_in_seconds(seconds);
+    }
+
+    /// Add a note event with a specific waveform, with its start and duration in ticks
+    pub fn add_note_with_waveform_ticks(
+        &mut self,
+        frequencies: &[f32],
+        start_time: Ticks,
+        duration: Ticks,
+        waveform: Waveform,
+    ) {
+        self.push_note(NoteEvent::with_waveform(
             frequencies,
             start_time,
             duration,
             waveform,
-        )));
-        self.invalidate_time_cache();
+        ));
     }
 
     /// Add a note event with waveform and ADSR envelope
@@ -277,15 +508,34 @@ impl Track {
         waveform: Waveform,
         envelope: Envelope,
     ) {
-        self.events
-            .push(AudioEvent::Note(NoteEvent::with_waveform_and_envelope(
-                frequencies,
-                start_time,
-                duration,
-                waveform,
-                envelope,
-            )));
-        self.invalidate_time_cache();
+        let seconds = EventTime { start: start_time, length: duration };
+        let (start_time, duration) = self.ticks_span(start_time, duration);
+        self.add_note_with_waveform_and_envelope_ticks(
+            frequencies,
+            start_time,
+            duration,
+            waveform,
+            envelope,
+        );
+        self.place_last_in_seconds(seconds);
+    }
+
+    /// Add a note event with waveform and ADSR envelope, with its start and duration in ticks
+    pub fn add_note_with_waveform_and_envelope_ticks(
+        &mut self,
+        frequencies: &[f32],
+        start_time: Ticks,
+        duration: Ticks,
+        waveform: Waveform,
+        envelope: Envelope,
+    ) {
+        self.push_note(NoteEvent::with_waveform_and_envelope(
+            frequencies,
+            start_time,
+            duration,
+            waveform,
+            envelope,
+        ));
     }
 
     /// Add a note event with waveform, envelope, and pitch bend
@@ -306,17 +556,38 @@ impl Track {
         envelope: Envelope,
         pitch_bend_semitones: f32,
     ) {
-        self.events.push(AudioEvent::Note(
-            NoteEvent::with_waveform_envelope_and_bend(
-                frequencies,
-                start_time,
-                duration,
-                waveform,
-                envelope,
-                pitch_bend_semitones,
-            ),
+        let seconds = EventTime { start: start_time, length: duration };
+        let (start_time, duration) = self.ticks_span(start_time, duration);
+        self.add_note_with_waveform_envelope_and_bend_ticks(
+            frequencies,
+            start_time,
+            duration,
+            waveform,
+            envelope,
+            pitch_bend_semitones,
+        );
+        self.place_last_in_seconds(seconds);
+    }
+
+    /// Add a note event with waveform, envelope, and pitch bend, with its start and
+    /// duration in ticks
+    pub fn add_note_with_waveform_envelope_and_bend_ticks(
+        &mut self,
+        frequencies: &[f32],
+        start_time: Ticks,
+        duration: Ticks,
+        waveform: Waveform,
+        envelope: Envelope,
+        pitch_bend_semitones: f32,
+    ) {
+        self.push_note(NoteEvent::with_waveform_envelope_and_bend(
+            frequencies,
+            start_time,
+            duration,
+            waveform,
+            envelope,
+            pitch_bend_semitones,
         ));
-        self.invalidate_time_cache();
     }
 
     /// Add a note event with all possible synthesis parameters
@@ -349,6 +620,41 @@ impl Track {
         custom_wavetable: Option<crate::synthesis::wavetable::Wavetable>,
         velocity: f32,
         spatial_position: Option<crate::synthesis::spatial::SpatialPosition>,
+    ) {
+        let seconds = EventTime { start: start_time, length: duration };
+        let (start_time, duration) = self.ticks_span(start_time, duration);
+        self.add_note_with_complete_params_ticks(
+            frequencies,
+            start_time,
+            duration,
+            waveform,
+            envelope,
+            filter_envelope,
+            fm_params,
+            pitch_bend_semitones,
+            custom_wavetable,
+            velocity,
+            spatial_position,
+        );
+        self.place_last_in_seconds(seconds);
+    }
+
+    /// Add a note event with all possible synthesis parameters, with its start and
+    /// duration in ticks
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_note_with_complete_params_ticks(
+        &mut self,
+        frequencies: &[f32],
+        start_time: Ticks,
+        duration: Ticks,
+        waveform: Waveform,
+        envelope: Envelope,
+        filter_envelope: FilterEnvelope,
+        fm_params: FMParams,
+        pitch_bend_semitones: f32,
+        custom_wavetable: Option<crate::synthesis::wavetable::Wavetable>,
+        velocity: f32,
+        spatial_position: Option<crate::synthesis::spatial::SpatialPosition>,
     ) {
         let mut note = NoteEvent::with_complete_params(
             frequencies,
@@ -363,8 +669,7 @@ impl Track {
             velocity,
         );
         note.spatial_position = spatial_position;
-        self.events.push(AudioEvent::Note(note));
-        self.invalidate_time_cache();
+        self.push_note(note);
     }
 
     /// Add a drum hit event to the track
@@ -374,10 +679,19 @@ impl Track {
     /// * `start_time` - When to trigger the drum (in seconds from track start)
     /// * `spatial_position` - Optional 3D spatial position
     pub fn add_drum(&mut self, drum_type: DrumType, start_time: f32, spatial_position: Option<crate::synthesis::spatial::SpatialPosition>) {
+        let seconds = start_time;
+        let start_time = self.tempo_map.ticks_at(start_time as f64);
+        self.add_drum_ticks(drum_type, start_time, spatial_position);
+        if let Some(AudioEvent::Drum(drum)) = self.events.last_mut() {
+            drum.placed_seconds = Some(seconds);
+        }
+    }
+
+    /// Add a drum hit event, with its start in ticks
+    pub fn add_drum_ticks(&mut self, drum_type: DrumType, start_time: Ticks, spatial_position: Option<crate::synthesis::spatial::SpatialPosition>) {
         self.events.push(AudioEvent::Drum(DrumEvent {
-            drum_type,
-            start_time,
             spatial_position,
+            ..DrumEvent::new(drum_type, start_time)
         }));
         self.invalidate_time_cache();
     }
@@ -404,6 +718,33 @@ impl Track {
         }
     }
 
+    /// Add a sequence of notes with equal duration, with the start and note duration in ticks
+    pub fn add_sequence_ticks(
+        &mut self,
+        frequencies_list: Vec<&[f32]>,
+        start_time: Ticks,
+        note_duration: Ticks,
+    ) {
+        let mut current_time = start_time;
+        for freqs in frequencies_list {
+            self.add_note_ticks(freqs, current_time, note_duration);
+            current_time += note_duration;
+        }
+    }
+
+    /// Convert a start and duration in seconds to ticks under the track's tempo map
+    fn ticks_span(&self, start_time: f32, duration: f32) -> (Ticks, Ticks) {
+        super::events::ticks_span(&self.tempo_map, start_time, duration)
+    }
+
+    /// Remember the seconds the note just added was placed at, so it keeps them
+    /// when the tempo map changes
+    fn place_last_in_seconds(&mut self, seconds: EventTime) {
+        if let Some(AudioEvent::Note(note)) = self.events.last_mut() {
+            note.placed_seconds = Some(seconds);
+        }
+    }
+
     /// Get the total duration of the track in seconds
     ///
     /// Returns the end time of the last event (including release times for notes).
@@ -411,16 +752,105 @@ impl Track {
     pub fn total_duration(&self) -> f32 {
         self.events
             .iter()
-            .map(|e| match e {
-                AudioEvent::Note(n) => n.start_time + n.duration,
-                AudioEvent::Drum(d) => d.start_time + d.drum_type.duration(),
-                AudioEvent::Sample(s) => s.start_time + (s.sample.duration / s.playback_rate),
-                AudioEvent::TempoChange(t) => t.start_time,
-                AudioEvent::TimeSignature(ts) => ts.start_time,
-                AudioEvent::KeySignature(ks) => ks.start_time,
+            .map(|e| {
+                let time = e.seconds(&self.tempo_map);
+                match e {
+                    AudioEvent::Note(_) => time.start + time.length,
+                    AudioEvent::Drum(d) => time.start + d.drum_type.duration(),
+                    AudioEvent::Sample(s) => time.start + (s.sample.duration / s.playback_rate),
+                    AudioEvent::TempoChange(_) => time.start,
+                    AudioEvent::TimeSignature(_) => time.start,
+                    AudioEvent::KeySignature(_) => time.start,
+                }
             })
             .fold(0.0, f32::max)
     }
+
+    /// Prepare the track's effects and modulation LFOs for rendering
+    ///
+    /// Notes played by a physical model or resonator are rendered here, ahead of
+    /// playback, rather than when they first sound.
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Sample rate in Hz
+    /// * `max_block_size` - Largest number of frames rendered per block
+    pub fn prepare(&mut self, sample_rate: f32, max_block_size: usize) {
+        // Events may have been changed in place since the last render
+        self.invalidate_time_cache();
+        self.allocate_voices();
+        self.prerender_notes(sample_rate);
+        self.release_modulated_effects();
+        self.effects.prepare(sample_rate, max_block_size);
+        for route in &mut self.modulation {
+            route.lfo.set_sample_rate(sample_rate);
+        }
+        self.modulation_state = None;
+    }
+
+    /// Render every physical model and resonator note, giving each note its renderings
+    fn prerender_notes(&mut self, sample_rate: f32) {
+        for event in &mut self.events {
+            if let AudioEvent::Note(note) = event {
+                let length = note.seconds(&self.tempo_map).length;
+                note.prerender(length, sample_rate);
+            }
+        }
+    }
+
+    /// Clear filter, effect and LFO state left over from a previous render
+    pub fn reset(&mut self) {
+        self.release_modulated_effects();
+        self.filter.reset();
+        self.effects.reset();
+        self.delay_compensation.reset();
+        self.side_filter.reset();
+        for route in &mut self.modulation {
+            route.lfo.reset();
+        }
+        self.modulation_state = None;
+    }
+
+    /// Build the working copy of the modulation routes, if the track has any
+    pub(super) fn ensure_modulation(&mut self, sample_rate: f32) {
+        if self.modulation_state.is_none() {
+            self.modulation_state =
+                ModulationState::new(&self.mod_matrix, &self.modulation, sample_rate);
+        }
+    }
+
+    /// Put back the effect parameters the mod matrix has been modulating
+    pub(super) fn release_modulated_effects(&mut self) {
+        if let Some(state) = self.modulation_state.as_mut() {
+            state.release_effects(&mut self.effects);
+        }
+    }
+
+    /// Snapshot the effect parameters as set, without the mod matrix's offsets
+    pub(super) fn capture_effect_parameters(&self) -> ChainParameters {
+        let mut parameters = self.effects.capture_parameters();
+        if let Some(state) = &self.modulation_state {
+            state.unmodulated(&mut parameters);
+        }
+        parameters
+    }
+
+    /// Check whether the mod matrix changes or reads individual notes, which keeps
+    /// them out of the sample cache
+    pub(super) fn modulation_reads_notes(&self) -> bool {
+        self.mod_matrix.reads_notes()
+            || self
+                .modulation
+                .iter()
+                .any(|route| route.target == ModTarget::Pitch)
+    }
+
+    /// Pan with the mod matrix's offset as of the last rendered sample
+    pub(super) fn modulated_pan(&self) -> f32 {
+        match &self.modulation_state {
+            Some(state) => (self.pan + state.pan()).clamp(-1.0, 1.0),
+            None => self.pan,
+        }
+    }
 }
 
 impl Default for Track {
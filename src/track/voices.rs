@@ -0,0 +1,523 @@
+//! Voice allocation: polyphony limits, voice stealing, mono and legato modes
+//!
+//! Notes are written as independent events, each free to ring for as long as its
+//! envelope lasts. A [`Voicing`] makes a track behave like a synth with a fixed
+//! number of voices: the allocator walks the track's notes as key presses and
+//! releases and rewrites them into what that synth would actually play. Stolen
+//! notes are cut short, and a mono voice becomes a chain of notes that hand over
+//! pitch, envelope and oscillator phase to one another, gliding between pitches.
+
+use super::events::{AudioEvent, NoteEvent};
+use crate::composition::timing::{TempoMap, Ticks};
+
+/// Seconds a stolen or retriggered voice takes to fade out
+pub const VOICE_STEAL_FADE: f32 = 0.005;
+
+/// Which voice gives way when a poly track runs out of voices
+///
+/// Voices already in their release are always taken first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VoiceStealing {
+    /// The voice that started first
+    #[default]
+    Oldest,
+    /// The voice whose envelope is lowest right now
+    Quietest,
+    /// The lowest-pitched voice
+    Lowest,
+    /// The highest-pitched voice
+    Highest,
+}
+
+/// Which held key a mono voice plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotePriority {
+    /// The most recently pressed key (releasing it returns to the one before)
+    #[default]
+    Last,
+    /// The lowest held key
+    Low,
+    /// The highest held key
+    High,
+}
+
+/// What a mono voice does to its envelope when it moves to a new key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Retrigger {
+    /// Every new key restarts the envelope from its attack
+    #[default]
+    Always,
+    /// Keys pressed while another is held continue the envelope (legato)
+    Legato,
+}
+
+/// How many voices a track has and how notes are given to them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceMode {
+    /// Independent voices, up to `polyphony` at once (0 = unlimited)
+    Poly {
+        polyphony: usize,
+        stealing: VoiceStealing,
+    },
+    /// A single voice following the held keys
+    Mono {
+        priority: NotePriority,
+        retrigger: Retrigger,
+    },
+}
+
+/// Voice allocation settings for a track or instrument
+///
+/// Notes that touch or overlap count as held together, so a mono voice plays
+/// `.notes()` runs legato. Glide applies whenever a mono voice moves from one held
+/// key to another.
+///
+/// # Example
+/// ```
+/// # use tunes::prelude::*;
+/// let mut comp = Composition::new(Tempo::new(120.0));
+/// comp.track("bass")
+///     .voicing(Voicing::mono().legato().with_glide(0.08))
+///     .notes(&[C2, C3, G2, C3], 0.25);
+///
+/// comp.track("pad")
+///     .voicing(Voicing::poly(4).with_stealing(VoiceStealing::Quietest))
+///     .note(&[C4, E4, G4, B4, D5], 2.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Voicing {
+    /// Poly or mono voice handling
+    pub mode: VoiceMode,
+    /// Seconds a mono voice takes to glide between held keys
+    pub glide: f32,
+}
+
+impl Voicing {
+    /// Poly voicing with at most `polyphony` voices (0 = unlimited)
+    pub fn poly(polyphony: usize) -> Self {
+        Self {
+            mode: VoiceMode::Poly {
+                polyphony,
+                stealing: VoiceStealing::Oldest,
+            },
+            glide: 0.0,
+        }
+    }
+
+    /// A single voice playing the last pressed key, retriggered on every key
+    pub fn mono() -> Self {
+        Self {
+            mode: VoiceMode::Mono {
+                priority: NotePriority::Last,
+                retrigger: Retrigger::Always,
+            },
+            glide: 0.0,
+        }
+    }
+
+    /// Set which voice is stolen (poly only)
+    pub fn with_stealing(mut self, stealing: VoiceStealing) -> Self {
+        if let VoiceMode::Poly { stealing: s, .. } = &mut self.mode {
+            *s = stealing;
+        }
+        self
+    }
+
+    /// Set which held key plays (mono only)
+    pub fn with_priority(mut self, priority: NotePriority) -> Self {
+        if let VoiceMode::Mono { priority: p, .. } = &mut self.mode {
+            *p = priority;
+        }
+        self
+    }
+
+    /// Keep the envelope running between overlapping keys (mono only)
+    pub fn legato(mut self) -> Self {
+        if let VoiceMode::Mono { retrigger, .. } = &mut self.mode {
+            *retrigger = Retrigger::Legato;
+        }
+        self
+    }
+
+    /// Set the glide time in seconds (mono only)
+    pub fn with_glide(mut self, seconds: f32) -> Self {
+        self.glide = seconds.max(0.0);
+        self
+    }
+
+    /// Check whether notes play exactly as written (unlimited poly)
+    pub fn is_unlimited(&self) -> bool {
+        matches!(self.mode, VoiceMode::Poly { polyphony: 0, .. })
+    }
+}
+
+impl Default for Voicing {
+    fn default() -> Self {
+        Self::poly(0)
+    }
+}
+
+/// One key of a chord: a single frequency held from `on` to `off`
+#[derive(Debug, Clone, Copy)]
+struct Key {
+    freq: f32,
+    on: Ticks,
+    off: Ticks,
+    note: usize,
+}
+
+/// Rewrite a track's notes as played by `voicing`
+///
+/// Keys go down and up at their positions in ticks; `tempo_map` turns those into
+/// the seconds that envelopes, glides and cuts are measured in. Non-note events are
+/// kept as they are; notes come back sorted by start time.
+pub(super) fn allocate(events: &mut Vec<AudioEvent>, voicing: &Voicing, tempo_map: &TempoMap) {
+    if voicing.is_unlimited() {
+        return;
+    }
+
+    let mut notes = Vec::new();
+    events.retain(|event| match event {
+        AudioEvent::Note(note) => {
+            notes.push(note.clone());
+            false
+        }
+        _ => true,
+    });
+
+    // Every frequency of a chord is a key of its own
+    let mut keys: Vec<Key> = notes
+        .iter()
+        .enumerate()
+        .flat_map(|(index, note)| {
+            note.frequencies[..note.num_freqs]
+                .iter()
+                .map(move |&freq| Key {
+                    freq,
+                    on: note.start_time,
+                    off: note.start_time + note.duration,
+                    note: index,
+                })
+        })
+        .collect();
+    keys.sort_by_key(|key| key.on);
+
+    let played = match voicing.mode {
+        VoiceMode::Poly {
+            polyphony,
+            stealing,
+        } => allocate_poly(&notes, &keys, polyphony, stealing, tempo_map),
+        VoiceMode::Mono {
+            priority,
+            retrigger,
+        } => allocate_mono(&notes, &keys, priority, retrigger, voicing.glide, tempo_map),
+    };
+
+    events.extend(played.into_iter().map(AudioEvent::Note));
+}
+
+/// A note for one key, carrying everything but the pitch from the note it came from
+fn key_note(notes: &[NoteEvent], key: &Key, start: Ticks) -> NoteEvent {
+    let mut note = notes[key.note].clone();
+    note.frequencies = [0.0; 8];
+    note.frequencies[0] = key.freq;
+    note.num_freqs = 1;
+    note.start_time = start;
+    note.duration = (key.off - start).max(Ticks::ZERO);
+    note
+}
+
+/// Seconds from the timeline start at which a note has finished sounding
+fn end_of(note: &NoteEvent, tempo_map: &TempoMap) -> f32 {
+    let time = note.seconds(tempo_map);
+    time.start + note.total_duration(time.length)
+}
+
+/// Cut a note `time` seconds after the timeline start, fading over `fade`
+fn cut(note: &mut NoteEvent, tempo_map: &TempoMap, time: f32, fade: f32) {
+    let cut_at = (time - note.seconds(tempo_map).start).max(0.0);
+    if note.voice.cut_at.is_none_or(|existing| cut_at < existing) {
+        note.voice.cut_at = Some(cut_at);
+        note.voice.cut_fade = fade;
+    }
+}
+
+fn allocate_poly(
+    notes: &[NoteEvent],
+    keys: &[Key],
+    polyphony: usize,
+    stealing: VoiceStealing,
+    tempo_map: &TempoMap,
+) -> Vec<NoteEvent> {
+    let mut played: Vec<NoteEvent> = Vec::with_capacity(keys.len());
+    let mut sounding: Vec<usize> = Vec::with_capacity(polyphony);
+
+    for key in keys {
+        let now = tempo_map.seconds_at(key.on) as f32;
+        sounding.retain(|&i| end_of(&played[i], tempo_map) > now);
+
+        if sounding.len() >= polyphony {
+            // Released voices go first, then the stealing rule picks among the rest
+            let released = |i: usize| {
+                let time = played[i].seconds(tempo_map);
+                time.start + played[i].held_duration(time.length) <= now
+            };
+            let releasing = sounding.iter().any(|&i| released(i));
+            let candidates = sounding
+                .iter()
+                .enumerate()
+                .filter(|&(_, &i)| !releasing || released(i));
+            let score = |i: usize| -> f32 {
+                let note = &played[i];
+                let time = note.seconds(tempo_map);
+                match stealing {
+                    VoiceStealing::Oldest => time.start,
+                    VoiceStealing::Quietest => note.amplitude_at(now - time.start, time.length),
+                    VoiceStealing::Lowest => note.frequencies[0],
+                    VoiceStealing::Highest => -note.frequencies[0],
+                }
+            };
+            if let Some((slot, &victim)) =
+                candidates.min_by(|a, b| score(*a.1).total_cmp(&score(*b.1)))
+            {
+                cut(&mut played[victim], tempo_map, now, VOICE_STEAL_FADE);
+                sounding.remove(slot);
+            }
+        }
+
+        sounding.push(played.len());
+        played.push(key_note(notes, key, key.on));
+    }
+
+    played
+}
+
+fn allocate_mono(
+    notes: &[NoteEvent],
+    keys: &[Key],
+    priority: NotePriority,
+    retrigger: Retrigger,
+    glide: f32,
+    tempo_map: &TempoMap,
+) -> Vec<NoteEvent> {
+    // Presses sort before releases at the same moment, so touching notes overlap
+    let mut changes: Vec<(Ticks, bool, usize)> = Vec::with_capacity(keys.len() * 2);
+    for (index, key) in keys.iter().enumerate() {
+        if key.off > key.on {
+            changes.push((key.on, true, index));
+            changes.push((key.off, false, index));
+        }
+    }
+    changes.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+    let mut played: Vec<NoteEvent> = Vec::new();
+    let mut held: Vec<usize> = Vec::new();
+    // The key the voice is playing, and whether it is still held down
+    let mut playing: Option<usize> = None;
+    let mut envelope_start = 0.0;
+
+    for (i, &(time, pressed, index)) in changes.iter().enumerate() {
+        if pressed {
+            held.push(index);
+        } else {
+            held.retain(|&k| k != index);
+        }
+
+        // Wait until every press and release at this moment has been applied
+        if changes.get(i + 1).is_some_and(|next| next.0 == time) {
+            continue;
+        }
+
+        let wanted = match priority {
+            NotePriority::Last => held.last().copied(),
+            NotePriority::Low => held
+                .iter()
+                .copied()
+                .min_by(|&a, &b| keys[a].freq.total_cmp(&keys[b].freq)),
+            NotePriority::High => held
+                .iter()
+                .copied()
+                .max_by(|&a, &b| keys[a].freq.total_cmp(&keys[b].freq)),
+        };
+        if wanted == playing {
+            continue;
+        }
+
+        let Some(next) = wanted else {
+            // Every key is up: the last note releases on its own
+            playing = None;
+            continue;
+        };
+
+        let mut note = key_note(notes, &keys[next], time);
+        let now = tempo_map.seconds_at(time) as f32;
+        match (playing, played.last_mut()) {
+            (Some(_), Some(previous)) => {
+                // Moving between held keys: glide, and carry the phase across
+                let elapsed = now - previous.seconds(tempo_map).start;
+                note.voice.glide_from = previous.frequency_at(elapsed) / keys[next].freq;
+                note.voice.glide_time = glide;
+                note.voice.phase = previous.phase_at(elapsed).fract();
+                if retrigger == Retrigger::Legato {
+                    note.voice.envelope_offset = now - envelope_start;
+                    cut(previous, tempo_map, now, 0.0);
+                } else {
+                    envelope_start = now;
+                    cut(previous, tempo_map, now, VOICE_STEAL_FADE);
+                }
+            }
+            (_, previous) => {
+                // A fresh press silences whatever is left of the last release
+                if let Some(previous) = previous {
+                    if end_of(previous, tempo_map) > now {
+                        cut(previous, tempo_map, now, VOICE_STEAL_FADE);
+                    }
+                }
+                envelope_start = now;
+            }
+        }
+        if note.voice.glide_from == 1.0 || note.voice.glide_time <= 0.0 {
+            note.voice.glide_from = 1.0;
+            note.voice.glide_time = 0.0;
+        }
+
+        played.push(note);
+        playing = Some(next);
+    }
+
+    played
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composition::timing::ticks::at;
+
+    fn allocate(events: &mut Vec<AudioEvent>, voicing: &Voicing) {
+        super::allocate(events, voicing, &TempoMap::default());
+    }
+
+    fn notes(track: &[(f32, f64, f64)]) -> Vec<AudioEvent> {
+        track
+            .iter()
+            .map(|&(freq, start, duration)| {
+                AudioEvent::Note(NoteEvent::new(&[freq], at(start), at(duration)))
+            })
+            .collect()
+    }
+
+    fn played(events: &[AudioEvent]) -> Vec<&NoteEvent> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                AudioEvent::Note(note) => Some(note),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unlimited_poly_leaves_notes_alone() {
+        let mut events = notes(&[(220.0, 0.0, 1.0), (330.0, 0.5, 1.0)]);
+        let before = format!("{:?}", events);
+        allocate(&mut events, &Voicing::default());
+        assert_eq!(format!("{:?}", events), before);
+    }
+
+    #[test]
+    fn test_poly_steals_the_oldest_voice() {
+        let chord = NoteEvent::new(&[220.0, 275.0], at(0.0), at(2.0));
+        let mut events = vec![AudioEvent::Note(chord)];
+        events.extend(notes(&[(330.0, 0.5, 1.0)]));
+        allocate(&mut events, &Voicing::poly(2));
+
+        let notes = played(&events);
+        assert_eq!(notes.len(), 3);
+        assert_eq!(notes[0].voice.cut_at, Some(0.5));
+        assert_eq!(notes[0].voice.cut_fade, VOICE_STEAL_FADE);
+        assert_eq!(notes[1].voice.cut_at, None);
+        assert_eq!(notes[2].voice.cut_at, None);
+    }
+
+    #[test]
+    fn test_poly_steals_released_voices_first() {
+        let mut events = notes(&[(220.0, 0.0, 2.0), (330.0, 0.1, 0.25), (440.0, 0.5, 1.0)]);
+        allocate(
+            &mut events,
+            &Voicing::poly(2).with_stealing(VoiceStealing::Lowest),
+        );
+
+        let notes = played(&events);
+        assert_eq!(notes[0].voice.cut_at, None);
+        assert!(notes[1].voice.cut_at.is_some());
+    }
+
+    #[test]
+    fn test_mono_last_priority_returns_to_held_key() {
+        // A long C with a short E on top: the voice goes C, E, back to C
+        let mut events = notes(&[(261.63, 0.0, 2.0), (329.63, 0.5, 0.5)]);
+        allocate(&mut events, &Voicing::mono());
+
+        let notes = played(&events);
+        let pitches: Vec<f32> = notes.iter().map(|n| n.frequencies[0]).collect();
+        assert_eq!(pitches, vec![261.63, 329.63, 261.63]);
+        assert_eq!(notes[0].voice.cut_at, Some(0.5));
+        assert_eq!(notes[1].voice.cut_at, Some(0.5));
+        assert_eq!(notes[2].start_time, at(1.0));
+        assert_eq!(notes[2].duration, at(1.0));
+    }
+
+    #[test]
+    fn test_mono_low_priority_ignores_higher_keys() {
+        let mut events = notes(&[(261.63, 0.0, 2.0), (329.63, 0.5, 0.5)]);
+        allocate(
+            &mut events,
+            &Voicing::mono().with_priority(NotePriority::Low),
+        );
+
+        let notes = played(&events);
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].duration, at(2.0));
+    }
+
+    #[test]
+    fn test_legato_continues_envelope_and_glides() {
+        let mut events = notes(&[(220.0, 0.0, 0.5), (440.0, 0.5, 0.5)]);
+        allocate(&mut events, &Voicing::mono().legato().with_glide(0.1));
+
+        let notes = played(&events);
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].voice.cut_at, Some(0.5));
+        assert_eq!(notes[0].voice.cut_fade, 0.0);
+        assert_eq!(notes[1].voice.envelope_offset, 0.5);
+        assert!((notes[1].voice.glide_from - 0.5).abs() < 1e-6);
+        assert_eq!(notes[1].voice.glide_time, 0.1);
+
+        // Pitch and phase pick up where the first note left off
+        assert!((notes[1].frequency_at(0.0) - 220.0).abs() < 1e-3);
+        assert!((notes[1].frequency_at(0.1) - 440.0).abs() < 1e-3);
+        assert_eq!(notes[1].voice.phase, notes[0].phase_at(0.5).fract());
+    }
+
+    #[test]
+    fn test_retrigger_restarts_envelope() {
+        let mut events = notes(&[(220.0, 0.0, 0.5), (440.0, 0.5, 0.5)]);
+        allocate(&mut events, &Voicing::mono());
+
+        let notes = played(&events);
+        assert_eq!(notes[1].voice.envelope_offset, 0.0);
+        assert_eq!(notes[0].voice.cut_fade, VOICE_STEAL_FADE);
+        assert_eq!(notes[1].voice.glide_from, 1.0);
+    }
+
+    #[test]
+    fn test_separate_notes_cut_the_previous_release() {
+        let mut events = notes(&[(220.0, 0.0, 0.5), (440.0, 0.55, 0.5)]);
+        allocate(&mut events, &Voicing::mono().with_glide(0.1));
+
+        let notes = played(&events);
+        assert!((notes[0].voice.cut_at.unwrap() - 0.55).abs() < 1e-6);
+        // Glide only happens between held keys
+        assert_eq!(notes[1].voice.glide_from, 1.0);
+    }
+}
@@ -0,0 +1,1224 @@
+//! Ogg Vorbis encoder for offline export
+//!
+//! A compact Vorbis I encoder: fixed 2048-sample blocks, a floor 1 spectral envelope
+//! and residue format 1 vector quantization with codebooks built from model
+//! distributions. There is no psychoacoustic model; [`OggOptions::quality`] sets how
+//! far below the loudest partial of each frequency band the quantization noise sits.
+//!
+//! Every block is encoded and handed to the Ogg page writer as soon as it fills up,
+//! so memory use does not grow with the length of the export.
+
+use super::export_format::{downmix, OggOptions};
+use super::metadata::ExportTags;
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+/// Block size exponent (both Vorbis block sizes use 2048 samples)
+const BLOCK_EXP: u8 = 11;
+const BLOCK_SIZE: usize = 1 << BLOCK_EXP;
+/// Spectral lines per block, and samples output per packet
+const HALF: usize = BLOCK_SIZE / 2;
+
+/// Floor 1 amplitude resolution: posts hold 0..128, scaled by 2 into the dB table
+const FLOOR_MULTIPLIER: i32 = 2;
+const FLOOR_RANGE: i32 = 128;
+const FLOOR_RANGE_BITS: u32 = 10;
+const FLOOR_Y_BITS: u32 = 7;
+
+/// Floor posts after the implicit ones at 0 and 1024, coarse to fine so each post is
+/// predicted from neighbors that are already known
+const FLOOR_POSTS: [u32; 30] = [
+    128, 32, 512, 8, 64, 256, 768, 2, 4, 16, 24, 48, 96, 192, 384, 640, 896, 12, 40, 80, 160, 320,
+    448, 576, 704, 832, 960, 6, 20, 56,
+];
+
+/// Residue partition length and partitions coded per class codeword
+const PARTITION_SIZE: usize = 32;
+const PARTITIONS_PER_CLASSWORD: usize = 2;
+/// Residue classes: silent, |v| <= 1, |v| <= 4, |v| <= 254
+const CLASSES: usize = 4;
+/// Largest quantized line; the coupled angle of two such lines can reach twice this
+const LARGEST_RESIDUE: i32 = 127;
+const LARGEST_COUPLED: i32 = 2 * LARGEST_RESIDUE;
+
+/// Lowest quantization noise level, below the noise floor of 16-bit audio
+const QUIETEST_NOISE: f32 = 1e-5;
+
+/// Codebook numbers in the setup header
+const FLOOR_BOOK: usize = 0;
+const CLASS_BOOK: usize = 1;
+const CLASS_BOOKS: [usize; CLASSES - 1] = [2, 3, 4];
+
+/// Serial number of the single logical stream in the file
+const STREAM_SERIAL: u32 = 0x7475_6e65;
+
+/// `floor1_inverse_dB_table` from section 10.1 of the Vorbis I specification
+#[allow(clippy::excessive_precision)]
+#[rustfmt::skip]
+const INVERSE_DB_TABLE: [f32; 256] = [
+    1.0649863e-07, 1.1341951e-07, 1.2079015e-07, 1.2863978e-07,
+    1.3699951e-07, 1.4590251e-07, 1.5538408e-07, 1.6548181e-07,
+    1.7623575e-07, 1.8768855e-07, 1.9988561e-07, 2.1287530e-07,
+    2.2670913e-07, 2.4144197e-07, 2.5713223e-07, 2.7384213e-07,
+    2.9163793e-07, 3.1059021e-07, 3.3077411e-07, 3.5226968e-07,
+    3.7516214e-07, 3.9954229e-07, 4.2550680e-07, 4.5315863e-07,
+    4.8260743e-07, 5.1396998e-07, 5.4737065e-07, 5.8294187e-07,
+    6.2082472e-07, 6.6116941e-07, 7.0413592e-07, 7.4989464e-07,
+    7.9862701e-07, 8.5052630e-07, 9.0579828e-07, 9.6466216e-07,
+    1.0273513e-06, 1.0941144e-06, 1.1652161e-06, 1.2409384e-06,
+    1.3215816e-06, 1.4074654e-06, 1.4989305e-06, 1.5963394e-06,
+    1.7000785e-06, 1.8105592e-06, 1.9282195e-06, 2.0535261e-06,
+    2.1869758e-06, 2.3290978e-06, 2.4804557e-06, 2.6416497e-06,
+    2.8133190e-06, 2.9961443e-06, 3.1908506e-06, 3.3982101e-06,
+    3.6190449e-06, 3.8542308e-06, 4.1047004e-06, 4.3714470e-06,
+    4.6555282e-06, 4.9580707e-06, 5.2802740e-06, 5.6234160e-06,
+    5.9888572e-06, 6.3780469e-06, 6.7925283e-06, 7.2339451e-06,
+    7.7040476e-06, 8.2047000e-06, 8.7378876e-06, 9.3057248e-06,
+    9.9104632e-06, 1.0554501e-05, 1.1240392e-05, 1.1970856e-05,
+    1.2748789e-05, 1.3577278e-05, 1.4459606e-05, 1.5399272e-05,
+    1.6400004e-05, 1.7465768e-05, 1.8600792e-05, 1.9809576e-05,
+    2.1096914e-05, 2.2467911e-05, 2.3928002e-05, 2.5482978e-05,
+    2.7139006e-05, 2.8902651e-05, 3.0780908e-05, 3.2781225e-05,
+    3.4911534e-05, 3.7180282e-05, 3.9596466e-05, 4.2169667e-05,
+    4.4910090e-05, 4.7828601e-05, 5.0936773e-05, 5.4246931e-05,
+    5.7772202e-05, 6.1526565e-05, 6.5524908e-05, 6.9783085e-05,
+    7.4317983e-05, 7.9147585e-05, 8.4291040e-05, 8.9768747e-05,
+    9.5602426e-05, 0.00010181521, 0.00010843174, 0.00011547824,
+    0.00012298267, 0.00013097477, 0.00013948625, 0.00014855085,
+    0.00015820453, 0.00016848555, 0.00017943469, 0.00019109536,
+    0.00020351382, 0.00021673929, 0.00023082423, 0.00024582449,
+    0.00026179955, 0.00027881276, 0.00029693158, 0.00031622787,
+    0.00033677814, 0.00035866388, 0.00038197188, 0.00040679456,
+    0.00043323036, 0.00046138411, 0.00049136745, 0.00052329927,
+    0.00055730621, 0.00059352311, 0.00063209358, 0.00067317058,
+    0.00071691700, 0.00076350630, 0.00081312324, 0.00086596457,
+    0.00092223983, 0.00098217216, 0.0010459992,  0.0011139742,
+    0.0011863665,  0.0012634633,  0.0013455702,  0.0014330129,
+    0.0015261382,  0.0016253153,  0.0017309374,  0.0018434235,
+    0.0019632195,  0.0020908006,  0.0022266726,  0.0023713743,
+    0.0025254795,  0.0026895994,  0.0028643847,  0.0030505286,
+    0.0032487691,  0.0034598925,  0.0036847358,  0.0039241906,
+    0.0041792066,  0.0044507950,  0.0047400328,  0.0050480668,
+    0.0053761186,  0.0057254891,  0.0060975636,  0.0064938176,
+    0.0069158225,  0.0073652516,  0.0078438871,  0.0083536271,
+    0.0088964928,  0.009474637,   0.010090352,   0.010746080,
+    0.011444421,   0.012188144,   0.012980198,   0.013823725,
+    0.014722068,   0.015678791,   0.016697687,   0.017782797,
+    0.018938423,   0.020169149,   0.021479854,   0.022875735,
+    0.024362330,   0.025945531,   0.027631618,   0.029427276,
+    0.031339626,   0.033376252,   0.035545228,   0.037855157,
+    0.040315199,   0.042935108,   0.045725273,   0.048696758,
+    0.051861348,   0.055231591,   0.058820850,   0.062643361,
+    0.066714279,   0.071049749,   0.075666962,   0.080584227,
+    0.085821044,   0.091398179,   0.097337747,   0.10366330,
+    0.11039993,    0.11757434,    0.12521498,    0.13335215,
+    0.14201813,    0.15124727,    0.16107617,    0.17154380,
+    0.18269168,    0.19456402,    0.20720788,    0.22067342,
+    0.23501402,    0.25028656,    0.26655159,    0.28387361,
+    0.30232132,    0.32196786,    0.34289114,    0.36517414,
+    0.38890521,    0.41417847,    0.44109412,    0.46975890,
+    0.50028648,    0.53279791,    0.56742212,    0.60429640,
+    0.64356699,    0.68538959,    0.72993007,    0.77736504,
+    0.82788260,    0.88168307,    0.9389798,     1.0,
+];
+
+/// Streaming Ogg Vorbis encoder
+///
+/// Encoded packets are held back by one so the final packet can close the stream
+/// with the exact sample count, which trims the padding of the last block.
+pub(super) struct VorbisEncoder {
+    writer: ogg::PacketWriter<BufWriter<File>>,
+    setup: Setup,
+    options: OggOptions,
+    /// Noise level relative to the loudest nearby line, for each floor post
+    noise_gain: Vec<f32>,
+    mdct: Mdct,
+    /// Per-channel input, starting half a block before the first sample
+    input: Vec<Vec<f32>>,
+    spectrum: Vec<Vec<f32>>,
+    curve: Vec<f32>,
+    residue: Vec<Vec<i32>>,
+    packets: u64,
+    frames: u64,
+    pending: Option<Box<[u8]>>,
+}
+
+impl VorbisEncoder {
+    pub fn create(
+        path: &str,
+        sample_rate: u32,
+        options: &OggOptions,
+        tags: &ExportTags,
+    ) -> anyhow::Result<Self> {
+        let channels = options.channels.count() as usize;
+        let setup = Setup::new(channels);
+
+        // Hearing is less sensitive to noise at high frequencies, so allow it to rise
+        // by 6 dB per octave above 4 kHz
+        let noise_gain = setup
+            .floor
+            .x
+            .iter()
+            .map(|&x| {
+                let frequency = x as f32 * sample_rate as f32 / BLOCK_SIZE as f32;
+                let allowance = (6.0 * (frequency / 4000.0).log2()).clamp(0.0, 18.0);
+                10f32.powf((allowance - options.signal_to_noise_db()) / 20.0)
+            })
+            .collect();
+
+        let mut encoder = Self {
+            writer: ogg::PacketWriter::new(BufWriter::new(File::create(path)?)),
+            setup,
+            options: *options,
+            noise_gain,
+            mdct: Mdct::new(),
+            input: vec![vec![0.0; HALF]; channels],
+            spectrum: vec![vec![0.0; HALF]; channels],
+            curve: vec![0.0; HALF],
+            residue: vec![vec![0; HALF]; channels],
+            packets: 0,
+            frames: 0,
+            pending: None,
+        };
+        encoder.write_headers(sample_rate, tags)?;
+        Ok(encoder)
+    }
+
+    /// Queue interleaved stereo samples, encoding every block that fills up
+    pub fn write_samples(&mut self, samples: &[f32]) -> anyhow::Result<()> {
+        let channels = self.options.channels;
+        let mut frame_samples = 0;
+        downmix(samples, channels, |sample, channel| {
+            self.input[channel].push(sample);
+            frame_samples += 1;
+            Ok::<(), anyhow::Error>(())
+        })?;
+        self.frames += (frame_samples / channels.count() as usize) as u64;
+
+        while self.input[0].len() >= BLOCK_SIZE {
+            let packet = self.encode_block();
+            self.push_packet(packet, ogg::PacketWriteEndInfo::NormalPacket)?;
+        }
+        Ok(())
+    }
+
+    /// Encode the remaining samples and close the stream
+    pub fn finalize(mut self) -> anyhow::Result<()> {
+        // The decoder outputs nothing for the first packet and half a block for each
+        // packet after it, so one extra packet is needed to flush the last samples
+        let needed_packets = self.frames.div_ceil(HALF as u64) + 1;
+        while self.packets < needed_packets {
+            for channel in &mut self.input {
+                channel.resize(BLOCK_SIZE, 0.0);
+            }
+            let packet = self.encode_block();
+            // Decoders trim the last block by the granule difference between the last
+            // two pages, so the final packet needs a page of its own
+            let end = if self.packets + 1 == needed_packets {
+                ogg::PacketWriteEndInfo::EndPage
+            } else {
+                ogg::PacketWriteEndInfo::NormalPacket
+            };
+            self.push_packet(packet, end)?;
+        }
+
+        if let Some(packet) = self.pending.take() {
+            self.writer.write_packet(
+                packet,
+                STREAM_SERIAL,
+                ogg::PacketWriteEndInfo::EndStream,
+                self.frames,
+            )?;
+        }
+        self.writer.inner_mut().flush()?;
+        Ok(())
+    }
+
+    fn write_headers(&mut self, sample_rate: u32, tags: &ExportTags) -> anyhow::Result<()> {
+        let channels = self.options.channels.count() as u32;
+
+        // Identification header (section 4.2.2)
+        let mut ident = BitWriter::default();
+        write_packet_signature(&mut ident, 1);
+        ident.write(0, 32);
+        ident.write(channels, 8);
+        ident.write(sample_rate, 32);
+        // Maximum, nominal and minimum bitrate are left unset
+        ident.write(0, 32);
+        ident.write(0, 32);
+        ident.write(0, 32);
+        ident.write(BLOCK_EXP as u32, 4);
+        ident.write(BLOCK_EXP as u32, 4);
+        ident.write(1, 1);
+
+        // Comment header (section 5)
+        let mut loop_tags = Vec::new();
+        if let Some((start, end)) = self.options.loop_region {
+            let start = (start.max(0.0) * sample_rate as f32).round() as u64;
+            let end = (end.max(0.0) * sample_rate as f32).round() as u64;
+            loop_tags.push(format!("LOOPSTART={}", start));
+            loop_tags.push(format!("LOOPLENGTH={}", end.saturating_sub(start)));
+        }
+        let mut comment = BitWriter::default();
+        write_packet_signature(&mut comment, 3);
+        comment.write_bytes(&tags.vorbis_comment_block(&loop_tags));
+        comment.write(1, 1);
+
+        let mut setup = BitWriter::default();
+        write_packet_signature(&mut setup, 5);
+        self.setup.write(&mut setup);
+
+        use ogg::PacketWriteEndInfo::{EndPage, NormalPacket};
+        // The identification header sits alone on the first page, and audio starts on
+        // a fresh page after the setup header
+        self.writer
+            .write_packet(ident.finish(), STREAM_SERIAL, EndPage, 0)?;
+        self.writer
+            .write_packet(comment.finish(), STREAM_SERIAL, NormalPacket, 0)?;
+        self.writer
+            .write_packet(setup.finish(), STREAM_SERIAL, EndPage, 0)?;
+        Ok(())
+    }
+
+    /// Encode one block from the start of the input buffers and drop its first half
+    fn encode_block(&mut self) -> Box<[u8]> {
+        for (input, spectrum) in self.input.iter().zip(&mut self.spectrum) {
+            self.mdct.forward(&input[..BLOCK_SIZE], spectrum);
+        }
+
+        let mut packet = BitWriter::default();
+        packet.write(0, 1); // audio packet; the single mode needs no bits
+
+        // All channels share one floor, so identical channels quantize to identical
+        // residues and the coupled angle channel is all zeros
+        match self.setup.floor.fit(&self.spectrum, &self.noise_gain) {
+            Some(floor) => {
+                for _ in &self.spectrum {
+                    packet.write(1, 1);
+                    self.setup.write_floor(&mut packet, &floor);
+                }
+
+                self.setup.floor.render(&floor, &mut self.curve);
+                for (spectrum, residue) in self.spectrum.iter().zip(&mut self.residue) {
+                    for ((value, &line), &step) in residue.iter_mut().zip(spectrum).zip(&self.curve)
+                    {
+                        *value =
+                            ((line / step).round() as i32).clamp(-LARGEST_RESIDUE, LARGEST_RESIDUE);
+                    }
+                }
+                if let [left, right] = self.residue.as_mut_slice() {
+                    couple(left, right);
+                }
+                self.setup.write_residue(&mut packet, &self.residue);
+            }
+            None => {
+                for _ in &self.spectrum {
+                    packet.write(0, 1);
+                }
+            }
+        }
+
+        for channel in &mut self.input {
+            channel.drain(..HALF);
+        }
+        packet.finish()
+    }
+
+    /// Write the previously encoded packet with `end` and hold on to this one
+    fn push_packet(
+        &mut self,
+        packet: Box<[u8]>,
+        end: ogg::PacketWriteEndInfo,
+    ) -> anyhow::Result<()> {
+        if let Some(previous) = self.pending.replace(packet) {
+            // Packet n completes the output up to sample n * HALF
+            let granule = (self.packets - 1) * HALF as u64;
+            self.writer
+                .write_packet(previous, STREAM_SERIAL, end, granule)?;
+        }
+        self.packets += 1;
+        Ok(())
+    }
+}
+
+/// Square polar stereo coupling (the inverse of section 1.3.3)
+///
+/// Replaces the left and right residues with a magnitude and an angle. The transform
+/// is exact on integers; the angle is zero wherever both channels are equal.
+fn couple(left: &mut [i32], right: &mut [i32]) {
+    for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+        let (magnitude, angle) = if (*l).max(*r) > 0 {
+            ((*l).max(*r), *l - *r)
+        } else {
+            ((*l).min(*r), *r - *l)
+        };
+        *l = magnitude;
+        *r = angle;
+    }
+}
+
+/// Write the packet type and `vorbis` signature that start every header packet
+fn write_packet_signature(writer: &mut BitWriter, packet_type: u32) {
+    writer.write(packet_type, 8);
+    writer.write_bytes(b"vorbis");
+}
+
+/// Codebooks, floor and residue configuration shared by the setup header and the
+/// audio packets
+struct Setup {
+    channels: usize,
+    books: Vec<Codebook>,
+    floor: Floor,
+}
+
+impl Setup {
+    fn new(channels: usize) -> Self {
+        // Floor values are mostly small steps away from their prediction
+        let floor_book = Codebook::scalar(
+            &(0..FLOOR_RANGE)
+                .map(|value| (-(value as f64) / 6.0).exp() + 1e-3)
+                .collect::<Vec<_>>(),
+        );
+
+        let class_weights = [0.3, 0.3, 0.25, 0.15];
+        // The decoder reads the class book's dimensions as partitions per codeword
+        let class_weights: Vec<f64> = (0..CLASSES * CLASSES)
+            .map(|code| class_weights[code / CLASSES] * class_weights[code % CLASSES])
+            .collect();
+        let class_book = Codebook::new(PARTITIONS_PER_CLASSWORD as u16, &class_weights, None);
+
+        let laplace = |scale: f64| {
+            move |vector: &[i32]| -> f64 {
+                vector
+                    .iter()
+                    .map(|&v| (-(v.abs() as f64) / scale).exp())
+                    .product()
+            }
+        };
+        let books = vec![
+            floor_book,
+            class_book,
+            Codebook::lattice(4, 1, laplace(0.6)),
+            Codebook::lattice(2, 4, laplace(1.5)),
+            Codebook::lattice(1, LARGEST_COUPLED, |v: &[i32]| {
+                (-(v[0].abs() as f64) / 12.0).exp() + 1e-4
+            }),
+        ];
+
+        Self {
+            channels,
+            books,
+            floor: Floor::new(),
+        }
+    }
+
+    /// Write the codebook, floor, residue, mapping and mode configuration (section 4.2.4)
+    fn write(&self, writer: &mut BitWriter) {
+        writer.write(self.books.len() as u32 - 1, 8);
+        for book in &self.books {
+            book.write_header(writer);
+        }
+
+        // Time domain transforms (unused placeholder)
+        writer.write(0, 6);
+        writer.write(0, 16);
+
+        // One floor of type 1: every partition uses class 0, one value coded with the
+        // floor book
+        writer.write(0, 6);
+        writer.write(1, 16);
+        writer.write(FLOOR_POSTS.len() as u32, 5);
+        for _ in FLOOR_POSTS {
+            writer.write(0, 4);
+        }
+        writer.write(0, 3); // class dimensions - 1
+        writer.write(0, 2); // no subclasses
+        writer.write(FLOOR_BOOK as u32 + 1, 8);
+        writer.write(FLOOR_MULTIPLIER as u32 - 1, 2);
+        writer.write(FLOOR_RANGE_BITS, 4);
+        for x in FLOOR_POSTS {
+            writer.write(x, FLOOR_RANGE_BITS);
+        }
+
+        // One residue of type 1 covering the whole spectrum
+        writer.write(0, 6);
+        writer.write(1, 16);
+        writer.write(0, 24);
+        writer.write(HALF as u32, 24);
+        writer.write(PARTITION_SIZE as u32 - 1, 24);
+        writer.write(CLASSES as u32 - 1, 6);
+        writer.write(CLASS_BOOK as u32, 8);
+        // Class 0 is silent; the others code a single pass
+        writer.write(0, 3);
+        writer.write(0, 1);
+        for _ in CLASS_BOOKS {
+            writer.write(1, 3);
+            writer.write(0, 1);
+        }
+        for book in CLASS_BOOKS {
+            writer.write(book as u32, 8);
+        }
+
+        // One mapping with a single submap; stereo couples left (magnitude) with right
+        // (angle)
+        writer.write(0, 6);
+        writer.write(0, 16);
+        writer.write(0, 1);
+        if self.channels == 2 {
+            writer.write(1, 1);
+            writer.write(0, 8);
+            writer.write(0, 1);
+            writer.write(1, 1);
+        } else {
+            writer.write(0, 1);
+        }
+        writer.write(0, 2);
+        writer.write(0, 8);
+        writer.write(0, 8);
+        writer.write(0, 8);
+
+        // One mode using the (single-size) block
+        writer.write(0, 6);
+        writer.write(0, 1);
+        writer.write(0, 16);
+        writer.write(0, 16);
+        writer.write(0, 8);
+
+        writer.write(1, 1);
+    }
+
+    fn write_floor(&self, writer: &mut BitWriter, floor: &FloorFit) {
+        writer.write(floor.values[0], FLOOR_Y_BITS);
+        writer.write(floor.values[1], FLOOR_Y_BITS);
+        for &value in &floor.values[2..] {
+            self.books[FLOOR_BOOK].write(writer, value as usize);
+        }
+    }
+
+    /// Write the residue of every channel (section 8.6.2)
+    fn write_residue(&self, writer: &mut BitWriter, channels: &[Vec<i32>]) {
+        let classes: Vec<Vec<usize>> = channels
+            .iter()
+            .map(|residue| residue.chunks(PARTITION_SIZE).map(classify).collect())
+            .collect();
+        let partitions = HALF / PARTITION_SIZE;
+
+        for first in (0..partitions).step_by(PARTITIONS_PER_CLASSWORD) {
+            for channel_classes in &classes {
+                let code = channel_classes[first..first + PARTITIONS_PER_CLASSWORD]
+                    .iter()
+                    .fold(0, |code, &class| code * CLASSES + class);
+                self.books[CLASS_BOOK].write(writer, code);
+            }
+            for partition in first..first + PARTITIONS_PER_CLASSWORD {
+                for (residue, channel_classes) in channels.iter().zip(&classes) {
+                    let class = channel_classes[partition];
+                    if class == 0 {
+                        continue;
+                    }
+                    let book = &self.books[CLASS_BOOKS[class - 1]];
+                    let start = partition * PARTITION_SIZE;
+                    for vector in
+                        residue[start..start + PARTITION_SIZE].chunks(book.dimensions as usize)
+                    {
+                        book.write_vector(writer, vector);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Residue class of a partition, from the largest value it has to code
+fn classify(partition: &[i32]) -> usize {
+    match partition.iter().map(|v| v.abs()).max().unwrap_or(0) {
+        0 => 0,
+        1 => 1,
+        2..=4 => 2,
+        _ => 3,
+    }
+}
+
+/// Floor 1 posts as coded for one channel of one block
+struct FloorFit {
+    /// Coded values: the first two posts directly, the rest relative to their prediction
+    values: Vec<u32>,
+    /// Decoded amplitude of every post
+    y: Vec<i32>,
+}
+
+/// Floor 1 curve fitting and synthesis (section 7)
+struct Floor {
+    /// Post positions in coding order, starting with the implicit 0 and 1024
+    x: Vec<u32>,
+    /// Low and high neighbor of each post among the posts coded before it
+    neighbors: Vec<(usize, usize)>,
+    /// Post indices sorted by position
+    order: Vec<usize>,
+}
+
+impl Floor {
+    fn new() -> Self {
+        let mut x = vec![0, 1 << FLOOR_RANGE_BITS];
+        x.extend_from_slice(&FLOOR_POSTS);
+
+        let neighbors = (0..x.len())
+            .map(|i| {
+                let low = (0..i).filter(|&j| x[j] < x[i]).max_by_key(|&j| x[j]);
+                let high = (0..i).filter(|&j| x[j] > x[i]).min_by_key(|&j| x[j]);
+                (low.unwrap_or(0), high.unwrap_or(0))
+            })
+            .collect();
+        let mut order: Vec<usize> = (0..x.len()).collect();
+        order.sort_by_key(|&i| x[i]);
+
+        Self {
+            x,
+            neighbors,
+            order,
+        }
+    }
+
+    /// Fit a floor to the loudest line of any channel around each post
+    ///
+    /// Each post sits `noise_gain[post]` below the loudest line between its sorted
+    /// neighbors, so the interpolated curve never dips below that level between two
+    /// posts. Returns `None` for silence, which is coded as an unused floor.
+    fn fit(&self, spectra: &[Vec<f32>], noise_gain: &[f32]) -> Option<FloorFit> {
+        let loudest = |from: usize, to: usize| {
+            spectra.iter().fold(0.0f32, |peak, spectrum| {
+                spectrum[from..to]
+                    .iter()
+                    .fold(peak, |peak, line| peak.max(line.abs()))
+            })
+        };
+        // Lines this quiet would all quantize to zero
+        if loudest(0, HALF) < QUIETEST_NOISE * 0.5 {
+            return None;
+        }
+
+        let mut target = vec![0; self.x.len()];
+        for (rank, &post) in self.order.iter().enumerate() {
+            let from = match rank {
+                0 => 0,
+                _ => (self.x[self.order[rank - 1]] as usize).min(HALF - 1),
+            };
+            let to = match self.order.get(rank + 1) {
+                Some(&next) => (self.x[next] as usize).min(HALF),
+                None => HALF,
+            };
+            let noise = (loudest(from, to) * noise_gain[post]).max(QUIETEST_NOISE);
+            target[post] = amplitude_to_y(noise);
+        }
+
+        let mut values = vec![target[0] as u32, target[1] as u32];
+        let mut y = vec![target[0], target[1]];
+        for (i, &target) in target.iter().enumerate().skip(2) {
+            let (low, high) = self.neighbors[i];
+            let predicted = render_point(self.x[low], y[low], self.x[high], y[high], self.x[i]);
+
+            // A zero value would mark the post unused and drop it from the curve, so
+            // nudge exact predictions by one step
+            let mut wanted = target;
+            if wanted == predicted {
+                wanted = if predicted + 1 < FLOOR_RANGE {
+                    predicted + 1
+                } else {
+                    predicted - 1
+                };
+            }
+            let value = encode_floor_value(wanted, predicted);
+            debug_assert_eq!(decode_floor_value(value, predicted), wanted);
+            values.push(value as u32);
+            y.push(wanted);
+        }
+
+        Some(FloorFit { values, y })
+    }
+
+    /// Render the floor curve exactly as the decoder will
+    fn render(&self, floor: &FloorFit, curve: &mut [f32]) {
+        let mut previous = self.order[0];
+        for &post in &self.order[1..] {
+            render_line(
+                self.x[previous],
+                floor.y[previous] * FLOOR_MULTIPLIER,
+                self.x[post],
+                floor.y[post] * FLOOR_MULTIPLIER,
+                curve,
+            );
+            previous = post;
+        }
+    }
+}
+
+/// Smallest floor amplitude step at or above `amplitude`
+fn amplitude_to_y(amplitude: f32) -> i32 {
+    let index = INVERSE_DB_TABLE.partition_point(|&step| step < amplitude);
+    ((index as i32 + FLOOR_MULTIPLIER - 1) / FLOOR_MULTIPLIER).min(FLOOR_RANGE - 1)
+}
+
+/// Inverse of [`decode_floor_value`] for a `wanted` amplitude different from `predicted`
+fn encode_floor_value(wanted: i32, predicted: i32) -> i32 {
+    let high_room = FLOOR_RANGE - predicted;
+    let low_room = predicted;
+    let room = 2 * high_room.min(low_room);
+    let difference = wanted - predicted;
+
+    if difference > 0 && 2 * difference < room {
+        2 * difference
+    } else if difference < 0 && -2 * difference - 1 < room {
+        -2 * difference - 1
+    } else if high_room > low_room {
+        difference + low_room
+    } else {
+        predicted - wanted + high_room - 1
+    }
+}
+
+/// Amplitude of a floor post from its coded value (section 7.2.4, step 1)
+fn decode_floor_value(value: i32, predicted: i32) -> i32 {
+    let high_room = FLOOR_RANGE - predicted;
+    let low_room = predicted;
+    let room = 2 * high_room.min(low_room);
+
+    if value >= room {
+        if high_room > low_room {
+            value - low_room + predicted
+        } else {
+            predicted - value + high_room - 1
+        }
+    } else if value % 2 == 1 {
+        predicted - (value + 1) / 2
+    } else {
+        predicted + value / 2
+    }
+}
+
+/// `render_point` from section 9.2.6
+fn render_point(x0: u32, y0: i32, x1: u32, y1: i32, x: u32) -> i32 {
+    let dy = y1 - y0;
+    let offset = (dy.unsigned_abs() * (x - x0) / (x1 - x0)) as i32;
+    if dy < 0 {
+        y0 - offset
+    } else {
+        y0 + offset
+    }
+}
+
+/// `render_line` from section 9.2.7, writing floor amplitudes into `curve`
+fn render_line(x0: u32, y0: i32, x1: u32, y1: i32, curve: &mut [f32]) {
+    let dy = y1 - y0;
+    let adx = (x1 - x0) as i32;
+    let base = dy / adx;
+    let step = if dy < 0 { base - 1 } else { base + 1 };
+    let ady = dy.abs() - base.abs() * adx;
+
+    let mut y = y0;
+    let mut error = 0;
+    curve[x0 as usize] = INVERSE_DB_TABLE[y as usize];
+    for value in &mut curve[x0 as usize + 1..(x1 as usize).min(HALF)] {
+        error += ady;
+        if error >= adx {
+            error -= adx;
+            y += step;
+        } else {
+            y += base;
+        }
+        *value = INVERSE_DB_TABLE[y as usize];
+    }
+}
+
+/// Huffman codebook, optionally with a lattice of integer vectors (lookup type 1)
+struct Codebook {
+    dimensions: u16,
+    lengths: Vec<u8>,
+    /// Codewords with their bits reversed, ready for LSB-first packing
+    codes: Vec<u32>,
+    /// Largest absolute component of a lattice book (`None` for scalar books)
+    radius: Option<i32>,
+}
+
+impl Codebook {
+    /// Scalar book with one entry per weight
+    fn scalar(weights: &[f64]) -> Self {
+        Self::new(1, weights, None)
+    }
+
+    /// Book of every vector with components in `-radius..=radius`
+    fn lattice(dimensions: u16, radius: i32, weight: impl Fn(&[i32]) -> f64) -> Self {
+        let values = (2 * radius + 1) as usize;
+        let entries = values.pow(dimensions as u32);
+        let mut vector = vec![0; dimensions as usize];
+        let weights: Vec<f64> = (0..entries)
+            .map(|entry| {
+                let mut rest = entry;
+                for component in vector.iter_mut() {
+                    *component = (rest % values) as i32 - radius;
+                    rest /= values;
+                }
+                weight(&vector)
+            })
+            .collect();
+        Self::new(dimensions, &weights, Some(radius))
+    }
+
+    fn new(dimensions: u16, weights: &[f64], radius: Option<i32>) -> Self {
+        let lengths = huffman_lengths(weights);
+        let codes = codewords(&lengths)
+            .into_iter()
+            .zip(&lengths)
+            .map(|(code, &length)| code.reverse_bits() >> (32 - length as u32))
+            .collect();
+        Self {
+            dimensions,
+            lengths,
+            codes,
+            radius,
+        }
+    }
+
+    /// Write the codebook configuration (section 3.2.1)
+    fn write_header(&self, writer: &mut BitWriter) {
+        writer.write(0x564342, 24);
+        writer.write(self.dimensions as u32, 16);
+        writer.write(self.lengths.len() as u32, 24);
+        writer.write(0, 1); // not ordered
+        writer.write(0, 1); // not sparse
+        for &length in &self.lengths {
+            writer.write(length as u32 - 1, 5);
+        }
+
+        match self.radius {
+            None => writer.write(0, 4),
+            Some(radius) => {
+                let values = (2 * radius + 1) as u32;
+                let value_bits = ilog(values - 1);
+                writer.write(1, 4);
+                writer.write(float32_pack(-radius as f32), 32);
+                writer.write(float32_pack(1.0), 32);
+                writer.write(value_bits - 1, 4);
+                writer.write(0, 1); // no sequence
+                for multiplicand in 0..values {
+                    writer.write(multiplicand, value_bits);
+                }
+            }
+        }
+    }
+
+    fn write(&self, writer: &mut BitWriter, entry: usize) {
+        writer.write(self.codes[entry], self.lengths[entry] as u32);
+    }
+
+    /// Write the lattice entry holding `vector` (components must be within the radius)
+    fn write_vector(&self, writer: &mut BitWriter, vector: &[i32]) {
+        let radius = self.radius.expect("scalar codebook used for a vector");
+        let values = (2 * radius + 1) as usize;
+        let entry = vector
+            .iter()
+            .rev()
+            .fold(0, |entry, &v| entry * values + (v + radius) as usize);
+        self.write(writer, entry);
+    }
+}
+
+/// Huffman code lengths for the given symbol weights, limited to 32 bits
+fn huffman_lengths(weights: &[f64]) -> Vec<u8> {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    let total: f64 = weights.iter().sum();
+    // Flooring rare symbols bounds the tree depth; retry coarser if it is still too deep
+    let mut resolution = 20;
+    loop {
+        let scale = (1u64 << resolution) as f64 / total;
+        let mut heap: BinaryHeap<Reverse<(u64, usize)>> = weights
+            .iter()
+            .enumerate()
+            .map(|(symbol, &weight)| Reverse((((weight * scale) as u64).max(1), symbol)))
+            .collect();
+        let mut parent = vec![usize::MAX; weights.len()];
+        while heap.len() > 1 {
+            let Reverse((weight_a, a)) = heap.pop().unwrap();
+            let Reverse((weight_b, b)) = heap.pop().unwrap();
+            let node = parent.len();
+            parent.push(usize::MAX);
+            parent[a] = node;
+            parent[b] = node;
+            heap.push(Reverse((weight_a + weight_b, node)));
+        }
+
+        let lengths: Vec<u8> = (0..weights.len())
+            .map(|symbol| {
+                let mut depth = 0;
+                let mut node = symbol;
+                while parent[node] != usize::MAX {
+                    node = parent[node];
+                    depth += 1;
+                }
+                depth.max(1)
+            })
+            .collect();
+        if lengths.iter().all(|&length| length <= 32) {
+            return lengths;
+        }
+        resolution -= 2;
+    }
+}
+
+/// Codewords for the given lengths, assigned as in section 3.2.1 of the specification
+///
+/// Each entry takes the lowest free codeword of its length in entry order. The result
+/// holds each codeword's bits most significant first.
+fn codewords(lengths: &[u8]) -> Vec<u32> {
+    let mut available = [0u32; 33];
+    let mut codes = Vec::with_capacity(lengths.len());
+    for (entry, &length) in lengths.iter().enumerate() {
+        let length = length as usize;
+        if entry == 0 {
+            codes.push(0);
+            for (depth, slot) in available.iter_mut().enumerate().take(length + 1).skip(1) {
+                *slot = 1 << (32 - depth);
+            }
+            continue;
+        }
+
+        let mut depth = length;
+        while depth > 0 && available[depth] == 0 {
+            depth -= 1;
+        }
+        assert!(depth > 0, "codebook lengths are overspecified");
+        let code = available[depth];
+        available[depth] = 0;
+        for deeper in (depth + 1..=length).rev() {
+            available[deeper] = code + (1 << (32 - deeper));
+        }
+        codes.push(((code as u64) >> (32 - length)) as u32);
+    }
+    codes
+}
+
+/// Number of bits needed to hold `value` (`ilog` from section 9.2.1)
+fn ilog(value: u32) -> u32 {
+    32 - value.leading_zeros()
+}
+
+/// Vorbis packed float: 21-bit mantissa, 10-bit biased exponent and sign (section 9.2.2)
+fn float32_pack(value: f32) -> u32 {
+    if value == 0.0 {
+        return 0;
+    }
+    let sign = if value < 0.0 { 0x8000_0000 } else { 0 };
+    let mut mantissa = value.abs() as f64;
+    let mut exponent = 788i32;
+    while mantissa >= (1 << 21) as f64 {
+        mantissa /= 2.0;
+        exponent += 1;
+    }
+    while mantissa < (1 << 20) as f64 {
+        mantissa *= 2.0;
+        exponent -= 1;
+    }
+    sign | ((exponent as u32) << 21) | mantissa.round() as u32
+}
+
+/// Windowed forward MDCT of one block, computed as a DCT-IV through a complex FFT
+struct Mdct {
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    twiddle: Vec<Complex<f32>>,
+    buffer: Vec<Complex<f32>>,
+}
+
+impl Mdct {
+    fn new() -> Self {
+        let quarter = HALF / 2;
+        let window = (0..BLOCK_SIZE)
+            .map(|n| {
+                // Vorbis power-complementary window (section 4.3.1)
+                let x = ((n as f64 + 0.5) / BLOCK_SIZE as f64 * std::f64::consts::PI).sin();
+                (std::f64::consts::FRAC_PI_2 * x * x).sin() as f32
+            })
+            .collect();
+        let twiddle = (0..quarter)
+            .map(|n| {
+                let angle = -std::f64::consts::PI * (4 * n + 1) as f64 / (4 * HALF) as f64;
+                Complex::new(angle.cos() as f32, angle.sin() as f32)
+            })
+            .collect();
+        Self {
+            fft: FftPlanner::new().plan_fft_forward(quarter),
+            window,
+            twiddle,
+            buffer: vec![Complex::new(0.0, 0.0); quarter],
+        }
+    }
+
+    /// Transform `BLOCK_SIZE` samples into `HALF` spectral lines
+    ///
+    /// Scaled by `2 / HALF` so the decoder's unnormalized inverse transform restores
+    /// the input level.
+    fn forward(&mut self, input: &[f32], output: &mut [f32]) {
+        let quarter = HALF / 2;
+        let windowed = |n: usize| input[n] * self.window[n];
+
+        // Fold the four quarters of the block into the DCT-IV input
+        let folded = |n: usize| {
+            if n < quarter {
+                -windowed(3 * quarter - 1 - n) - windowed(3 * quarter + n)
+            } else {
+                let n = n - quarter;
+                windowed(n) - windowed(HALF - 1 - n)
+            }
+        };
+        for (n, value) in self.buffer.iter_mut().enumerate() {
+            *value = Complex::new(folded(2 * n), folded(HALF - 1 - 2 * n)) * self.twiddle[n];
+        }
+
+        self.fft.process(&mut self.buffer);
+
+        let scale = 2.0 / HALF as f32;
+        for (k, value) in self.buffer.iter().enumerate() {
+            let angle = -std::f32::consts::PI * k as f32 / HALF as f32;
+            let rotated = value * Complex::new(angle.cos(), angle.sin()) * scale;
+            output[2 * k] = rotated.re;
+            output[HALF - 1 - 2 * k] = -rotated.im;
+        }
+    }
+}
+
+/// Packs values least significant bit first, as Vorbis packets are read
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    accumulator: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    /// Append the low `bits` bits of `value`
+    fn write(&mut self, value: u32, bits: u32) {
+        debug_assert!(bits <= 32);
+        let mask = if bits == 32 {
+            u32::MAX
+        } else {
+            (1 << bits) - 1
+        };
+        self.accumulator |= ((value & mask) as u64) << self.bits;
+        self.bits += bits;
+        while self.bits >= 8 {
+            self.bytes.push(self.accumulator as u8);
+            self.accumulator >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write(byte as u32, 8);
+        }
+    }
+
+    fn finish(mut self) -> Box<[u8]> {
+        if self.bits > 0 {
+            self.bytes.push(self.accumulator as u8);
+        }
+        self.bytes.into_boxed_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composition::timing::Tempo;
+    use crate::track::{Channels, Mixer};
+    use lewton::inside_ogg::OggStreamReader;
+    use lewton::samples::InterleavedSamples;
+
+    /// Two seconds of interleaved stereo: a chord with a decaying noise burst on the
+    /// left and a brighter tone sweeping up on the right
+    fn test_signal(sample_rate: u32) -> Vec<f32> {
+        let rate = sample_rate as f32;
+        let mut seed = 0x2545_f491_u32;
+        (0..2 * sample_rate as usize)
+            .flat_map(|frame| {
+                let t = frame as f32 / rate;
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let noise = (seed >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0;
+                let chord: f32 = [220.0, 277.18, 329.63]
+                    .iter()
+                    .map(|f| (std::f32::consts::TAU * f * t).sin())
+                    .sum();
+                let left = 0.2 * chord + 0.3 * noise * (-8.0 * t).exp();
+                let sweep = 200.0 * t + 150.0 * t * t;
+                let right = 0.4 * (std::f32::consts::TAU * sweep).sin()
+                    + 0.1 * (std::f32::consts::TAU * 3.0 * sweep).sin();
+                [left, right]
+            })
+            .collect()
+    }
+
+    /// Encode interleaved stereo `samples` and decode the file with lewton
+    ///
+    /// Returns the channel count, sample rate and interleaved decoded samples.
+    fn round_trip(name: &str, samples: &[f32], options: &OggOptions) -> (usize, u32, Vec<f32>) {
+        let path = std::env::temp_dir().join(name);
+        let path = path.to_str().unwrap();
+        let mixer = Mixer::new(Tempo::new(120.0));
+        let tags = ExportTags::new(&mixer, 44100, samples.len() / 2);
+        let mut encoder = VorbisEncoder::create(path, 44100, options, &tags).unwrap();
+        // Uneven blocks, as the mixer hands them over
+        for block in samples.chunks(2 * 700) {
+            encoder.write_samples(block).unwrap();
+        }
+        encoder.finalize().unwrap();
+
+        let mut reader = OggStreamReader::new(File::open(path).unwrap()).unwrap();
+        let mut decoded = Vec::new();
+        while let Some(packet) = reader
+            .read_dec_packet_generic::<InterleavedSamples<f32>>()
+            .unwrap()
+        {
+            decoded.extend(packet.samples);
+        }
+        std::fs::remove_file(path).ok();
+        let header = &reader.ident_hdr;
+        (
+            header.audio_channels as usize,
+            header.audio_sample_rate,
+            decoded,
+        )
+    }
+
+    /// Signal-to-noise ratio of `decoded` against `reference`, in dB
+    fn snr_db(reference: impl Iterator<Item = f32>, decoded: impl Iterator<Item = f32>) -> f64 {
+        let (signal, noise) = reference.zip(decoded).fold((0.0, 0.0), |(s, n), (a, b)| {
+            (s + (a as f64).powi(2), n + (a as f64 - b as f64).powi(2))
+        });
+        10.0 * (signal / noise).log10()
+    }
+
+    fn channel(samples: &[f32], channel: usize) -> impl Iterator<Item = f32> + '_ {
+        samples.iter().skip(channel).step_by(2).copied()
+    }
+
+    #[test]
+    fn test_lewton_decodes_stereo_to_the_source() {
+        let source = test_signal(44100);
+        let (channels, sample_rate, decoded) =
+            round_trip("tunes_vorbis_stereo.ogg", &source, &OggOptions::default());
+
+        assert_eq!(channels, 2);
+        assert_eq!(sample_rate, 44100);
+        // The granule position of the last page trims the padding of the last block
+        assert_eq!(decoded.len(), source.len());
+        let target = OggOptions::default().signal_to_noise_db() as f64;
+        for side in 0..2 {
+            let snr = snr_db(channel(&source, side), channel(&decoded, side));
+            assert!(snr > target, "channel {} SNR {:.1} dB", side, snr);
+        }
+        // The last blocks, flushed by finalize, decode as well as the rest
+        let tail = source.len() - 2 * BLOCK_SIZE;
+        let snr = snr_db(
+            source[tail..].iter().copied(),
+            decoded[tail..].iter().copied(),
+        );
+        assert!(snr > target, "tail SNR {:.1} dB", snr);
+    }
+
+    #[test]
+    fn test_lewton_decodes_mono_downmix() {
+        let source = test_signal(44100);
+        let options = OggOptions::default().with_channels(Channels::Mono);
+        let (channels, _, decoded) = round_trip("tunes_vorbis_mono.ogg", &source, &options);
+
+        assert_eq!(channels, 1);
+        assert_eq!(decoded.len(), source.len() / 2);
+        let downmix = source
+            .chunks_exact(2)
+            .map(|frame| (frame[0] + frame[1]) * 0.5);
+        let snr = snr_db(downmix, decoded.into_iter());
+        assert!(
+            snr > options.signal_to_noise_db() as f64,
+            "SNR {:.1} dB",
+            snr
+        );
+    }
+
+    #[test]
+    fn test_lewton_snr_follows_quality() {
+        let source = test_signal(44100);
+        let mut previous = 0.0;
+        for quality in [0.0, 0.5, 1.0] {
+            let options = OggOptions::default().with_quality(quality);
+            let (_, _, decoded) = round_trip("tunes_vorbis_quality.ogg", &source, &options);
+            let snr = snr_db(source.iter().copied(), decoded.into_iter());
+            // Every band keeps its loudest line this far above the noise, so the
+            // whole signal does too
+            assert!(
+                snr > options.signal_to_noise_db() as f64,
+                "quality {}: SNR {:.1} dB",
+                quality,
+                snr
+            );
+            assert!(snr > previous);
+            previous = snr;
+        }
+    }
+
+    #[test]
+    fn test_codewords_match_specification_order() {
+        // Example from section 3.2.1 of the Vorbis I specification
+        let lengths = [2, 4, 4, 4, 4, 2, 3, 3];
+        assert_eq!(codewords(&lengths), vec![0, 4, 5, 6, 7, 2, 6, 7]);
+    }
+
+    #[test]
+    fn test_huffman_lengths_form_complete_code() {
+        let weights: Vec<f64> = (0..255)
+            .map(|v| (-(v as f64) / 12.0).exp() + 1e-4)
+            .collect();
+        let lengths = huffman_lengths(&weights);
+        let kraft: f64 = lengths.iter().map(|&l| 0.5f64.powi(l as i32)).sum();
+        assert!((kraft - 1.0).abs() < 1e-9);
+        assert!(lengths[0] < lengths[254]);
+    }
+
+    #[test]
+    fn test_floor_values_round_trip() {
+        for predicted in 0..FLOOR_RANGE {
+            for wanted in (0..FLOOR_RANGE).filter(|&wanted| wanted != predicted) {
+                let value = encode_floor_value(wanted, predicted);
+                assert!(value > 0 && value < FLOOR_RANGE);
+                assert_eq!(decode_floor_value(value, predicted), wanted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mdct_matches_direct_transform() {
+        let input: Vec<f32> = (0..BLOCK_SIZE)
+            .map(|n| ((n * 7919) % 101) as f32 / 50.0 - 1.0)
+            .collect();
+        let mut fast = vec![0.0; HALF];
+        let mut mdct = Mdct::new();
+        mdct.forward(&input, &mut fast);
+
+        for k in [0, 1, 17, 500, HALF - 1] {
+            let direct: f64 = (0..BLOCK_SIZE)
+                .map(|n| {
+                    let phase = std::f64::consts::PI / HALF as f64
+                        * (n as f64 + 0.5 + HALF as f64 / 2.0)
+                        * (k as f64 + 0.5);
+                    input[n] as f64 * mdct.window[n] as f64 * phase.cos()
+                })
+                .sum::<f64>()
+                * 2.0
+                / HALF as f64;
+            assert!((fast[k] as f64 - direct).abs() < 1e-3, "line {}", k);
+        }
+    }
+}